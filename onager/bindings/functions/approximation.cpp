@@ -2,7 +2,7 @@
  * @file approximation.cpp
  * @brief Approximation algorithm table functions for Onager DuckDB extension.
  *
- * Maximum Clique, Independent Set, Vertex Cover approximations.
+ * Maximum Clique, Independent Set, Vertex Cover approximations, TSP, and P-Median.
  */
 #include "functions.hpp"
 #include <mutex>
@@ -43,7 +43,8 @@ static OperatorFinalizeResultType MaxCliqueFinal(ExecutionContext &ctx, TableFun
     int64_t nc = ::onager::onager_compute_max_clique(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr);
     if (nc < 0) throw InvalidInputException("Max clique failed: " + GetOnagerError());
     gs.result_nodes.resize(nc);
-    ::onager::onager_compute_max_clique(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_max_clique_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Max clique");
+    gs.result_nodes.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -87,7 +88,8 @@ static OperatorFinalizeResultType IndependentSetFinal(ExecutionContext &ctx, Tab
     int64_t nc = ::onager::onager_compute_independent_set(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr);
     if (nc < 0) throw InvalidInputException("Independent set failed: " + GetOnagerError());
     gs.result_nodes.resize(nc);
-    ::onager::onager_compute_independent_set(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_independent_set_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Independent set");
+    gs.result_nodes.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -131,7 +133,8 @@ static OperatorFinalizeResultType VertexCoverFinal(ExecutionContext &ctx, TableF
     int64_t nc = ::onager::onager_compute_vertex_cover(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr);
     if (nc < 0) throw InvalidInputException("Vertex cover failed: " + GetOnagerError());
     gs.result_nodes.resize(nc);
-    ::onager::onager_compute_vertex_cover(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_vertex_cover_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Vertex cover");
+    gs.result_nodes.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -157,10 +160,12 @@ struct TspGlobalState : public GlobalTableFunctionState {
 };
 
 static unique_ptr<FunctionData> TspBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WeightedBindData>();
   CheckInt64Input(input, "onager_apx_tsp", 3);
+  BindWeightSemantics(input, *bd, "onager_apx_tsp");
   rt.push_back(LogicalType::BIGINT); nm.push_back("order");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
-  return make_uniq<TableFunctionData>();
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> TspInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<TspGlobalState>(); }
 static OperatorResultType TspInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
@@ -174,14 +179,16 @@ static OperatorResultType TspInOut(ExecutionContext &ctx, TableFunctionInput &da
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType TspFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<TspGlobalState>();
+  auto &bd = data.bind_data->Cast<WeightedBindData>(); auto &gs = data.global_state->Cast<TspGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "TSP");
     int64_t nc = ::onager::onager_compute_tsp(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("TSP failed: " + GetOnagerError());
     gs.result_tour.resize(nc);
-    ::onager::onager_compute_tsp(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_tour.data(), &gs.result_cost);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_tsp_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_tour.data(), &gs.result_cost, gs.result_tour.size()), gs.result_tour.size(), "TSP");
+    gs.result_tour.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_tour.size() - gs.output_idx;
@@ -193,6 +200,69 @@ static OperatorFinalizeResultType TspFinal(ExecutionContext &ctx, TableFunctionI
   return gs.output_idx >= gs.result_tour.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// P-Median Facility Location
+// =============================================================================
+
+struct PMedianBindData : public WeightedBindData { int64_t p = 1; bool weighted = false; };
+struct PMedianGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_facilities;
+  std::vector<double> weights, result_distances;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> PMedianBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<PMedianBindData>();
+  CheckInt64Input(input, "onager_apx_p_median");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "p") bd->p = kv.second.GetValue<int64_t>();
+  }
+  if (bd->p <= 0) throw InvalidInputException("onager_apx_p_median requires p to be positive");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_apx_p_median");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("facility");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> PMedianInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<PMedianGlobalState>(); }
+static OperatorResultType PMedianInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<PMedianBindData>(); auto &gs = data.global_state->Cast<PMedianGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType PMedianFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<PMedianBindData>(); auto &gs = data.global_state->Cast<PMedianGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "P-median");
+    size_t p = static_cast<size_t>(bd.p);
+    int64_t nc = ::onager::onager_compute_p_median(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), p, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("P-median failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_facilities.resize(nc); gs.result_distances.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_p_median(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), p, gs.result_nodes.data(), gs.result_facilities.data(), gs.result_distances.data(), gs.result_nodes.size()), gs.result_nodes.size(), "P-median");
+    gs.result_nodes.resize(written); gs.result_facilities.resize(written); gs.result_distances.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto f = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; f[i] = gs.result_facilities[gs.output_idx+i]; w[i] = gs.result_distances[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -221,8 +291,17 @@ void RegisterApproximationFunctions(ExtensionLoader &loader) {
   TableFunction tsp("onager_apx_tsp", {LogicalType::TABLE}, nullptr, TspBind, TspInitGlobal);
   tsp.in_out_function = TspInOut;
   tsp.in_out_function_final = TspFinal;
+  tsp.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
   ONAGER_SET_NO_ORDER(tsp);
   loader.RegisterFunction(tsp);
+
+  TableFunction p_median("onager_apx_p_median", {LogicalType::TABLE}, nullptr, PMedianBind, PMedianInitGlobal);
+  p_median.in_out_function = PMedianInOut;
+  p_median.in_out_function_final = PMedianFinal;
+  p_median.named_parameters["p"] = LogicalType::BIGINT;
+  p_median.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(p_median);
+  loader.RegisterFunction(p_median);
 }
 
 } // namespace onager
@@ -2,7 +2,7 @@
  * @file centrality.cpp
  * @brief Centrality table functions for Onager DuckDB extension.
  *
- * PageRank, Degree, Betweenness, Closeness, Eigenvector, Katz, Harmonic.
+ * PageRank, Degree, Betweenness, Closeness, Eigenvector, Katz, Harmonic, node roles.
  */
 #include "functions.hpp"
 #include <mutex>
@@ -19,6 +19,9 @@ struct PageRankBindData : public TableFunctionData {
   double damping = 0.85;
   int64_t iterations = 100;
   bool directed = true;
+  bool weighted = false;
+  EdgeTypeWeights types;
+  ScoreSelection selection;
 };
 
 struct PageRankGlobalState : public GlobalTableFunctionState {
@@ -26,7 +29,9 @@ struct PageRankGlobalState : public GlobalTableFunctionState {
   std::vector<int64_t> src_nodes;
   std::vector<int64_t> dst_nodes;
   std::vector<int64_t> result_nodes;
+  std::vector<double> weights;
   std::vector<double> result_ranks;
+  EdgeTypeCodes type_codes;
   idx_t output_idx = 0;
   bool computed = false;
   idx_t MaxThreads() const override { return 1; }
@@ -44,6 +49,11 @@ static unique_ptr<FunctionData> PageRankBind(ClientContext &context,
     else if (kv.first == "iterations") bind_data->iterations = kv.second.GetValue<int64_t>();
     else if (kv.first == "directed") bind_data->directed = kv.second.GetValue<bool>();
   }
+  BindEdgeTypeWeights(input, bind_data->types, 2, "onager_ctr_pagerank");
+  // With type_weights the extra column is the edge type, otherwise it holds edge weights
+  bind_data->weighted = !bind_data->types.enabled && input.input_table_types.size() >= 3;
+  if (bind_data->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindScoreSelection(input, bind_data->selection, {"rank"}, "onager_ctr_pagerank");
   return_types.push_back(LogicalType::BIGINT); names.push_back("node_id");
   return_types.push_back(LogicalType::DOUBLE); names.push_back("rank");
   return std::move(bind_data);
@@ -55,6 +65,7 @@ static unique_ptr<GlobalTableFunctionState> PageRankInitGlobal(ClientContext &co
 
 static OperatorResultType PageRankInOut(ExecutionContext &context, TableFunctionInput &data,
                                          DataChunk &input, DataChunk &output) {
+  auto &bind = data.bind_data->Cast<PageRankBindData>();
   auto &gs = data.global_state->Cast<PageRankGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto src = FlatVector::GetData<int64_t>(input.data[0]);
@@ -62,6 +73,11 @@ static OperatorResultType PageRankInOut(ExecutionContext &context, TableFunction
   for (idx_t i = 0; i < input.size(); i++) {
     gs.src_nodes.push_back(src[i]);
     gs.dst_nodes.push_back(dst[i]);
+    if (bind.types.enabled) gs.type_codes.Add(input.data[input.ColumnCount() - 1], i);
+  }
+  if (bind.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
   }
   output.SetCardinality(0);
   return OperatorResultType::NEED_MORE_INPUT;
@@ -74,12 +90,26 @@ static OperatorFinalizeResultType PageRankFinal(ExecutionContext &context, Table
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
     size_t ec = gs.src_nodes.size();
-    int64_t nc = ::onager::onager_compute_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), ec,
-        bind.damping, static_cast<size_t>(bind.iterations), bind.directed, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("PageRank failed: " + GetOnagerError());
-    gs.result_nodes.resize(static_cast<size_t>(nc)); gs.result_ranks.resize(static_cast<size_t>(nc));
-    ::onager::onager_compute_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), ec,
-        bind.damping, static_cast<size_t>(bind.iterations), bind.directed, gs.result_nodes.data(), gs.result_ranks.data());
+    if (bind.types.enabled) {
+      auto type_ids = gs.type_codes.TypeIds(bind.types);
+      int64_t nc = ::onager::onager_compute_typed_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), ec,
+          type_ids.data(), bind.types.multipliers.data(), type_ids.size(), bind.damping, static_cast<size_t>(bind.iterations), bind.directed, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("PageRank failed: " + GetOnagerError());
+      gs.result_nodes.resize(static_cast<size_t>(nc)); gs.result_ranks.resize(static_cast<size_t>(nc));
+      size_t written = CheckOnagerWrite(::onager::onager_compute_typed_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), ec,
+          type_ids.data(), bind.types.multipliers.data(), type_ids.size(), bind.damping, static_cast<size_t>(bind.iterations), bind.directed,
+          gs.result_nodes.data(), gs.result_ranks.data(), gs.result_nodes.size()), gs.result_nodes.size(), "PageRank");
+      gs.result_nodes.resize(written); gs.result_ranks.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_pagerank_v2(gs.src_nodes.data(), gs.dst_nodes.data(), ec, gs.weights.data(), gs.weights.size(),
+          bind.damping, static_cast<size_t>(bind.iterations), bind.directed, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("PageRank failed: " + GetOnagerError());
+      gs.result_nodes.resize(static_cast<size_t>(nc)); gs.result_ranks.resize(static_cast<size_t>(nc));
+      size_t written = CheckOnagerWrite(::onager::onager_compute_pagerank_v2(gs.src_nodes.data(), gs.dst_nodes.data(), ec, gs.weights.data(), gs.weights.size(),
+          bind.damping, static_cast<size_t>(bind.iterations), bind.directed, gs.result_nodes.data(), gs.result_ranks.data(), gs.result_nodes.size()), gs.result_nodes.size(), "PageRank");
+      gs.result_nodes.resize(written); gs.result_ranks.resize(written);
+    }
+    ApplyScoreSelection(bind.selection, gs.result_nodes, {&gs.result_ranks}, "PageRank");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -96,7 +126,7 @@ static OperatorFinalizeResultType PageRankFinal(ExecutionContext &context, Table
 // Degree Centrality Table Function
 // =============================================================================
 
-struct DegreeBindData : public TableFunctionData { bool directed = true; };
+struct DegreeBindData : public TableFunctionData { bool directed = true; ScoreSelection selection; };
 struct DegreeGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
@@ -109,6 +139,7 @@ static unique_ptr<FunctionData> DegreeBind(ClientContext &ctx, TableFunctionBind
   auto bd = make_uniq<DegreeBindData>();
   CheckInt64Input(input, "onager_degree");
   for (auto &kv : input.named_parameters) if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+  BindScoreSelection(input, bd->selection, {"in_degree", "out_degree"}, "onager_ctr_degree");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("in_degree");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("out_degree");
@@ -130,7 +161,9 @@ static OperatorFinalizeResultType DegreeFinal(ExecutionContext &ctx, TableFuncti
     int64_t nc = ::onager::onager_compute_degree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.directed, nullptr, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Degree failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_in.resize(nc); gs.result_out.resize(nc);
-    ::onager::onager_compute_degree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.directed, gs.result_nodes.data(), gs.result_in.data(), gs.result_out.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_degree_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.directed, gs.result_nodes.data(), gs.result_in.data(), gs.result_out.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Degree");
+    gs.result_nodes.resize(written); gs.result_in.resize(written); gs.result_out.resize(written);
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_in, &gs.result_out}, "Degree");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -146,11 +179,11 @@ static OperatorFinalizeResultType DegreeFinal(ExecutionContext &ctx, TableFuncti
 // Betweenness Centrality Table Function
 // =============================================================================
 
-struct BetweennessBindData : public TableFunctionData { bool normalized = true; };
+struct BetweennessBindData : public WeightedBindData { bool normalized = true; double cutoff = -1.0; bool weighted = false; bool directed = false; std::string parallel_edges; ScoreSelection selection; };
 struct BetweennessGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
-  std::vector<double> result_centralities;
+  std::vector<double> weights, result_centralities;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -158,17 +191,33 @@ struct BetweennessGlobalState : public GlobalTableFunctionState {
 static unique_ptr<FunctionData> BetweennessBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<BetweennessBindData>();
   CheckInt64Input(input, "onager_betweenness");
-  for (auto &kv : input.named_parameters) if (kv.first == "normalized") bd->normalized = kv.second.GetValue<bool>();
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "normalized") bd->normalized = kv.second.GetValue<bool>();
+    else if (kv.first == "cutoff") bd->cutoff = kv.second.GetValue<double>();
+    else if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+    else if (kv.first == "parallel_edges") bd->parallel_edges = StringUtil::Lower(kv.second.GetValue<string>());
+  }
+  if (!bd->parallel_edges.empty() && bd->parallel_edges != "min" && bd->parallel_edges != "count") {
+    throw InvalidInputException("onager_ctr_betweenness parallel_edges must be 'min' or 'count'");
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_ctr_betweenness");
+  BindScoreSelection(input, bd->selection, {"betweenness"}, "onager_ctr_betweenness");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("betweenness");
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> BetweennessInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<BetweennessGlobalState>(); }
 static OperatorResultType BetweennessInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
-  auto &gs = data.global_state->Cast<BetweennessGlobalState>();
+  auto &bd = data.bind_data->Cast<BetweennessBindData>(); auto &gs = data.global_state->Cast<BetweennessGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
   for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType BetweennessFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
@@ -176,10 +225,29 @@ static OperatorFinalizeResultType BetweennessFinal(ExecutionContext &ctx, TableF
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_betweenness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.normalized, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Betweenness failed: " + GetOnagerError());
-    gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
-    ::onager::onager_compute_betweenness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.normalized, gs.result_nodes.data(), gs.result_centralities.data());
+    if (bd.directed || !bd.parallel_edges.empty()) {
+      ApplyWeightSemantics(bd, gs.weights, "Betweenness");
+      const char *parallel = bd.parallel_edges.empty() ? "min" : bd.parallel_edges.c_str();
+      int64_t nc = ::onager::onager_compute_betweenness_general(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.normalized, parallel, bd.cutoff, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Betweenness failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_betweenness_general(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.normalized, parallel, bd.cutoff, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Betweenness");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    } else if (bd.cutoff >= 0 || bd.weighted) {
+      ApplyWeightSemantics(bd, gs.weights, "Betweenness");
+      int64_t nc = ::onager::onager_compute_betweenness_cutoff(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.normalized, bd.cutoff, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Betweenness failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_betweenness_cutoff(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.normalized, bd.cutoff, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Betweenness");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_betweenness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.normalized, nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Betweenness failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_betweenness_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.normalized, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Betweenness");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    }
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_centralities}, "Betweenness");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -195,37 +263,64 @@ static OperatorFinalizeResultType BetweennessFinal(ExecutionContext &ctx, TableF
 // Closeness Centrality Table Function
 // =============================================================================
 
+struct ClosenessBindData : public WeightedBindData { double cutoff = -1.0; bool weighted = false; bool directed = false; std::vector<int64_t> targets; bool has_targets = false; ScoreSelection selection; };
 struct ClosenessGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
-  std::vector<double> result_centralities;
+  std::vector<double> weights, result_centralities;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
 
 static unique_ptr<FunctionData> ClosenessBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<ClosenessBindData>();
   CheckInt64Input(input, "onager_closeness");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "cutoff") bd->cutoff = kv.second.GetValue<double>();
+    else if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_ctr_closeness");
+  bd->has_targets = BindTargets(input, bd->targets, "onager_ctr_closeness");
+  if (bd->has_targets && bd->targets.empty()) throw InvalidInputException("onager_ctr_closeness targets must not be empty");
+  BindScoreSelection(input, bd->selection, {"closeness"}, "onager_ctr_closeness");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("closeness");
-  return make_uniq<TableFunctionData>();
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> ClosenessInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ClosenessGlobalState>(); }
 static OperatorResultType ClosenessInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
-  auto &gs = data.global_state->Cast<ClosenessGlobalState>();
+  auto &bd = data.bind_data->Cast<ClosenessBindData>(); auto &gs = data.global_state->Cast<ClosenessGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
   for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType ClosenessFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<ClosenessGlobalState>();
+  auto &bd = data.bind_data->Cast<ClosenessBindData>(); auto &gs = data.global_state->Cast<ClosenessGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_closeness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Closeness failed: " + GetOnagerError());
-    gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
-    ::onager::onager_compute_closeness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_centralities.data());
+    if (bd.has_targets || bd.directed || bd.cutoff >= 0 || bd.weighted) {
+      ApplyWeightSemantics(bd, gs.weights, "Closeness");
+      int64_t nc = ::onager::onager_compute_closeness_general(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.targets.data(), bd.targets.size(), bd.cutoff, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Closeness failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_closeness_general(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.targets.data(), bd.targets.size(), bd.cutoff, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Closeness");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_closeness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Closeness failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_closeness_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Closeness");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    }
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_centralities}, "Closeness");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -241,37 +336,64 @@ static OperatorFinalizeResultType ClosenessFinal(ExecutionContext &ctx, TableFun
 // Harmonic Centrality Table Function
 // =============================================================================
 
+struct HarmonicBindData : public WeightedBindData { double cutoff = -1.0; bool weighted = false; bool directed = false; std::vector<int64_t> targets; bool has_targets = false; ScoreSelection selection; };
 struct HarmonicGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
-  std::vector<double> result_centralities;
+  std::vector<double> weights, result_centralities;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
 
 static unique_ptr<FunctionData> HarmonicBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<HarmonicBindData>();
   CheckInt64Input(input, "onager_harmonic");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "cutoff") bd->cutoff = kv.second.GetValue<double>();
+    else if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_ctr_harmonic");
+  bd->has_targets = BindTargets(input, bd->targets, "onager_ctr_harmonic");
+  if (bd->has_targets && bd->targets.empty()) throw InvalidInputException("onager_ctr_harmonic targets must not be empty");
+  BindScoreSelection(input, bd->selection, {"harmonic"}, "onager_ctr_harmonic");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("harmonic");
-  return make_uniq<TableFunctionData>();
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> HarmonicInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<HarmonicGlobalState>(); }
 static OperatorResultType HarmonicInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
-  auto &gs = data.global_state->Cast<HarmonicGlobalState>();
+  auto &bd = data.bind_data->Cast<HarmonicBindData>(); auto &gs = data.global_state->Cast<HarmonicGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
   for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType HarmonicFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<HarmonicGlobalState>();
+  auto &bd = data.bind_data->Cast<HarmonicBindData>(); auto &gs = data.global_state->Cast<HarmonicGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_harmonic(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Harmonic failed: " + GetOnagerError());
-    gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
-    ::onager::onager_compute_harmonic(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_centralities.data());
+    if (bd.has_targets || bd.directed || bd.cutoff >= 0 || bd.weighted) {
+      ApplyWeightSemantics(bd, gs.weights, "Harmonic");
+      int64_t nc = ::onager::onager_compute_harmonic_general(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.targets.data(), bd.targets.size(), bd.cutoff, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Harmonic failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_harmonic_general(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.targets.data(), bd.targets.size(), bd.cutoff, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Harmonic");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_harmonic(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Harmonic failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_harmonic_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Harmonic");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    }
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_centralities}, "Harmonic");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -287,11 +409,12 @@ static OperatorFinalizeResultType HarmonicFinal(ExecutionContext &ctx, TableFunc
 // Katz Centrality Table Function
 // =============================================================================
 
-struct KatzBindData : public TableFunctionData { double alpha = 0.1; int64_t max_iter = 100; double tolerance = 1e-6; };
+struct KatzBindData : public TableFunctionData { double alpha = 0.1; int64_t max_iter = 100; double tolerance = 1e-6; EdgeTypeWeights types; ScoreSelection selection; };
 struct KatzGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
   std::vector<double> result_centralities;
+  EdgeTypeCodes type_codes;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -304,16 +427,21 @@ static unique_ptr<FunctionData> KatzBind(ClientContext &ctx, TableFunctionBindIn
     if (kv.first == "max_iter") bd->max_iter = kv.second.GetValue<int64_t>();
     if (kv.first == "tolerance") bd->tolerance = kv.second.GetValue<double>();
   }
+  BindEdgeTypeWeights(input, bd->types, 2, "onager_ctr_katz");
+  BindScoreSelection(input, bd->selection, {"katz"}, "onager_ctr_katz");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("katz");
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> KatzInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<KatzGlobalState>(); }
 static OperatorResultType KatzInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
-  auto &gs = data.global_state->Cast<KatzGlobalState>();
+  auto &bd = data.bind_data->Cast<KatzBindData>(); auto &gs = data.global_state->Cast<KatzGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
-  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  for (idx_t i = 0; i < input.size(); i++) {
+    gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]);
+    if (bd.types.enabled) gs.type_codes.Add(input.data[input.ColumnCount() - 1], i);
+  }
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType KatzFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
@@ -321,10 +449,21 @@ static OperatorFinalizeResultType KatzFinal(ExecutionContext &ctx, TableFunction
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_katz(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.alpha, bd.max_iter, bd.tolerance, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Katz failed: " + GetOnagerError());
-    gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
-    ::onager::onager_compute_katz(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.alpha, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_centralities.data());
+    if (bd.types.enabled) {
+      auto type_ids = gs.type_codes.TypeIds(bd.types);
+      int64_t nc = ::onager::onager_compute_typed_katz(gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), gs.src_nodes.size(), type_ids.data(), bd.types.multipliers.data(), type_ids.size(), bd.alpha, bd.max_iter, bd.tolerance, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Katz failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_typed_katz(gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), gs.src_nodes.size(), type_ids.data(), bd.types.multipliers.data(), type_ids.size(), bd.alpha, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Katz");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_katz(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.alpha, bd.max_iter, bd.tolerance, nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Katz failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_katz_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.alpha, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Katz");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    }
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_centralities}, "Katz");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -340,11 +479,11 @@ static OperatorFinalizeResultType KatzFinal(ExecutionContext &ctx, TableFunction
 // Eigenvector Centrality Table Function
 // =============================================================================
 
-struct EigenvectorBindData : public TableFunctionData { int64_t max_iter = 100; double tolerance = 1e-6; };
+struct EigenvectorBindData : public TableFunctionData { int64_t max_iter = 100; double tolerance = 1e-6; bool directed = false; bool weighted = false; std::string normalization; ScoreSelection selection; };
 struct EigenvectorGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
-  std::vector<double> result_centralities;
+  std::vector<double> weights, result_centralities;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -355,17 +494,29 @@ static unique_ptr<FunctionData> EigenvectorBind(ClientContext &ctx, TableFunctio
   for (auto &kv : input.named_parameters) {
     if (kv.first == "max_iter") bd->max_iter = kv.second.GetValue<int64_t>();
     if (kv.first == "tolerance") bd->tolerance = kv.second.GetValue<double>();
+    if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+    if (kv.first == "normalization") bd->normalization = StringUtil::Lower(kv.second.GetValue<string>());
+  }
+  if (!bd->normalization.empty() && bd->normalization != "l1" && bd->normalization != "l2" && bd->normalization != "max") {
+    throw InvalidInputException("onager_ctr_eigenvector normalization must be 'l1', 'l2', or 'max'");
   }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindScoreSelection(input, bd->selection, {"eigenvector"}, "onager_ctr_eigenvector");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("eigenvector");
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> EigenvectorInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<EigenvectorGlobalState>(); }
 static OperatorResultType EigenvectorInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
-  auto &gs = data.global_state->Cast<EigenvectorGlobalState>();
+  auto &bd = data.bind_data->Cast<EigenvectorBindData>(); auto &gs = data.global_state->Cast<EigenvectorGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
   for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType EigenvectorFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
@@ -373,10 +524,21 @@ static OperatorFinalizeResultType EigenvectorFinal(ExecutionContext &ctx, TableF
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_eigenvector(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.tolerance, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Eigenvector failed: " + GetOnagerError());
-    gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
-    ::onager::onager_compute_eigenvector(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_centralities.data());
+    if (bd.weighted || !bd.normalization.empty()) {
+      const char *normalization = bd.normalization.empty() ? "l2" : bd.normalization.c_str();
+      int64_t nc = ::onager::onager_compute_eigenvector_weighted(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.max_iter, bd.tolerance, bd.directed, normalization, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Eigenvector failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_eigenvector_weighted(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.max_iter, bd.tolerance, bd.directed, normalization, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Eigenvector");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_eigenvector_directed(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.tolerance, bd.directed, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Eigenvector failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_eigenvector_directed(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.tolerance, bd.directed, gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Eigenvector");
+      gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    }
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_centralities}, "Eigenvector");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -388,6 +550,72 @@ static OperatorFinalizeResultType EigenvectorFinal(ExecutionContext &ctx, TableF
   return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Node Roles
+// =============================================================================
+
+struct NodeRolesBindData : public TableFunctionData { int64_t samples = 256; int64_t seed = -1; };
+struct NodeRolesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_roles, result_degrees;
+  std::vector<double> result_betweenness, result_clustering;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+// Indexed by the role codes returned from onager_classify_node_roles
+static const char *const NODE_ROLE_NAMES[] = {"isolate", "peripheral", "connector", "hub"};
+
+static unique_ptr<FunctionData> NodeRolesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<NodeRolesBindData>();
+  CheckInt64Input(input, "onager_ctr_node_roles");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "samples") bd->samples = kv.second.GetValue<int64_t>();
+    if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  }
+  if (bd->samples <= 0) throw InvalidInputException("onager_ctr_node_roles requires samples to be positive");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("role");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("degree");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("betweenness");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("clustering");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> NodeRolesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<NodeRolesGlobalState>(); }
+static OperatorResultType NodeRolesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<NodeRolesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType NodeRolesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<NodeRolesBindData>(); auto &gs = data.global_state->Cast<NodeRolesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_classify_node_roles(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.samples, bd.seed, nullptr, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Node roles failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_roles.resize(nc); gs.result_degrees.resize(nc); gs.result_betweenness.resize(nc); gs.result_clustering.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_classify_node_roles(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.samples, bd.seed, gs.result_nodes.data(), gs.result_roles.data(), gs.result_degrees.data(), gs.result_betweenness.data(), gs.result_clustering.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Node roles");
+    gs.result_nodes.resize(written); gs.result_roles.resize(written); gs.result_degrees.resize(written); gs.result_betweenness.resize(written); gs.result_clustering.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto r = GetFlatVectorDataWritable<string_t>(output.data[1]);
+  auto dg = GetFlatVectorDataWritable<int64_t>(output.data[2]);
+  auto b = GetFlatVectorDataWritable<double>(output.data[3]); auto c = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    n[i] = gs.result_nodes[k]; dg[i] = gs.result_degrees[k]; b[i] = gs.result_betweenness[k]; c[i] = gs.result_clustering[k];
+    r[i] = StringVector::AddString(output.data[1], NODE_ROLE_NAMES[gs.result_roles[k]]);
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -401,6 +629,8 @@ void RegisterCentralityFunctions(ExtensionLoader &loader) {
   pagerank.named_parameters["damping"] = LogicalType::DOUBLE;
   pagerank.named_parameters["iterations"] = LogicalType::BIGINT;
   pagerank.named_parameters["directed"] = LogicalType::BOOLEAN;
+  pagerank.named_parameters["type_weights"] = LogicalType::MAP(LogicalType::VARCHAR, LogicalType::DOUBLE);
+  AddScoreSelectionParameters(pagerank);
   ONAGER_SET_NO_ORDER(pagerank);
   loader.RegisterFunction(pagerank);
 
@@ -408,25 +638,41 @@ void RegisterCentralityFunctions(ExtensionLoader &loader) {
   degree.in_out_function = DegreeInOut;
   degree.in_out_function_final = DegreeFinal;
   degree.named_parameters["directed"] = LogicalType::BOOLEAN;
+  AddScoreSelectionParameters(degree);
   ONAGER_SET_NO_ORDER(degree);
   loader.RegisterFunction(degree);
 
   TableFunction betweenness("onager_ctr_betweenness", {LogicalType::TABLE}, nullptr, BetweennessBind, BetweennessInitGlobal);
   betweenness.in_out_function = BetweennessInOut;
   betweenness.in_out_function_final = BetweennessFinal;
+  betweenness.named_parameters["cutoff"] = LogicalType::DOUBLE;
+  betweenness.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
   betweenness.named_parameters["normalized"] = LogicalType::BOOLEAN;
+  betweenness.named_parameters["directed"] = LogicalType::BOOLEAN;
+  betweenness.named_parameters["parallel_edges"] = LogicalType::VARCHAR;
+  AddScoreSelectionParameters(betweenness);
   ONAGER_SET_NO_ORDER(betweenness);
   loader.RegisterFunction(betweenness);
 
   TableFunction closeness("onager_ctr_closeness", {LogicalType::TABLE}, nullptr, ClosenessBind, ClosenessInitGlobal);
   closeness.in_out_function = ClosenessInOut;
   closeness.in_out_function_final = ClosenessFinal;
+  closeness.named_parameters["cutoff"] = LogicalType::DOUBLE;
+  closeness.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  closeness.named_parameters["targets"] = LogicalType::LIST(LogicalType::BIGINT);
+  closeness.named_parameters["directed"] = LogicalType::BOOLEAN;
+  AddScoreSelectionParameters(closeness);
   ONAGER_SET_NO_ORDER(closeness);
   loader.RegisterFunction(closeness);
 
   TableFunction harmonic("onager_ctr_harmonic", {LogicalType::TABLE}, nullptr, HarmonicBind, HarmonicInitGlobal);
   harmonic.in_out_function = HarmonicInOut;
   harmonic.in_out_function_final = HarmonicFinal;
+  harmonic.named_parameters["cutoff"] = LogicalType::DOUBLE;
+  harmonic.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  harmonic.named_parameters["targets"] = LogicalType::LIST(LogicalType::BIGINT);
+  harmonic.named_parameters["directed"] = LogicalType::BOOLEAN;
+  AddScoreSelectionParameters(harmonic);
   ONAGER_SET_NO_ORDER(harmonic);
   loader.RegisterFunction(harmonic);
 
@@ -436,6 +682,8 @@ void RegisterCentralityFunctions(ExtensionLoader &loader) {
   katz.named_parameters["alpha"] = LogicalType::DOUBLE;
   katz.named_parameters["max_iter"] = LogicalType::BIGINT;
   katz.named_parameters["tolerance"] = LogicalType::DOUBLE;
+  katz.named_parameters["type_weights"] = LogicalType::MAP(LogicalType::VARCHAR, LogicalType::DOUBLE);
+  AddScoreSelectionParameters(katz);
   ONAGER_SET_NO_ORDER(katz);
   loader.RegisterFunction(katz);
 
@@ -444,8 +692,19 @@ void RegisterCentralityFunctions(ExtensionLoader &loader) {
   eigenvector.in_out_function_final = EigenvectorFinal;
   eigenvector.named_parameters["max_iter"] = LogicalType::BIGINT;
   eigenvector.named_parameters["tolerance"] = LogicalType::DOUBLE;
+  eigenvector.named_parameters["directed"] = LogicalType::BOOLEAN;
+  eigenvector.named_parameters["normalization"] = LogicalType::VARCHAR;
+  AddScoreSelectionParameters(eigenvector);
   ONAGER_SET_NO_ORDER(eigenvector);
   loader.RegisterFunction(eigenvector);
+
+  TableFunction node_roles("onager_ctr_node_roles", {LogicalType::TABLE}, nullptr, NodeRolesBind, NodeRolesInitGlobal);
+  node_roles.in_out_function = NodeRolesInOut;
+  node_roles.in_out_function_final = NodeRolesFinal;
+  node_roles.named_parameters["samples"] = LogicalType::BIGINT;
+  node_roles.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(node_roles);
+  loader.RegisterFunction(node_roles);
 }
 
 // Forward declare VoteRank registration (defined at end of file)
@@ -500,7 +759,8 @@ static OperatorFinalizeResultType VoteRankFinal(ExecutionContext &ctx, TableFunc
     int64_t nc = ::onager::onager_compute_voterank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.num_seeds, nullptr);
     if (nc < 0) throw InvalidInputException("VoteRank failed: " + GetOnagerError());
     gs.result_nodes.resize(nc);
-    ::onager::onager_compute_voterank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.num_seeds, gs.result_nodes.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_voterank_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.num_seeds, gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "VoteRank");
+    gs.result_nodes.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -532,7 +792,7 @@ void RegisterVoteRankFunction(ExtensionLoader &loader) {
 namespace duckdb {
 using namespace onager;
 
-struct LocalReachingBindData : public TableFunctionData { int64_t distance = 2; };
+struct LocalReachingBindData : public TableFunctionData { int64_t distance = 2; ScoreSelection selection; };
 struct LocalReachingGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
@@ -547,6 +807,7 @@ static unique_ptr<FunctionData> LocalReachingBind(ClientContext &ctx, TableFunct
   for (auto &kv : input.named_parameters) {
     if (kv.first == "distance") bd->distance = kv.second.GetValue<int64_t>();
   }
+  BindScoreSelection(input, bd->selection, {"centrality"}, "onager_ctr_local_reaching");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("centrality");
   return std::move(bd);
@@ -567,7 +828,9 @@ static OperatorFinalizeResultType LocalReachingFinal(ExecutionContext &ctx, Tabl
     int64_t nc = ::onager::onager_compute_local_reaching(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.distance, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("LocalReaching failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_centrality.resize(nc);
-    ::onager::onager_compute_local_reaching(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.distance, gs.result_nodes.data(), gs.result_centrality.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_local_reaching_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.distance, gs.result_nodes.data(), gs.result_centrality.data(), gs.result_nodes.size()), gs.result_nodes.size(), "LocalReaching");
+    gs.result_nodes.resize(written); gs.result_centrality.resize(written);
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_centrality}, "LocalReaching");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -583,6 +846,7 @@ static OperatorFinalizeResultType LocalReachingFinal(ExecutionContext &ctx, Tabl
 // Laplacian Centrality Table Function
 // =============================================================================
 
+struct LaplacianBindData : public TableFunctionData { ScoreSelection selection; };
 struct LaplacianGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
@@ -592,10 +856,12 @@ struct LaplacianGlobalState : public GlobalTableFunctionState {
 };
 
 static unique_ptr<FunctionData> LaplacianBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<LaplacianBindData>();
   CheckInt64Input(input, "onager_laplacian");
+  BindScoreSelection(input, bd->selection, {"centrality"}, "onager_ctr_laplacian");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("centrality");
-  return make_uniq<TableFunctionData>();
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> LaplacianInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<LaplacianGlobalState>(); }
 static OperatorResultType LaplacianInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
@@ -606,14 +872,16 @@ static OperatorResultType LaplacianInOut(ExecutionContext &ctx, TableFunctionInp
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType LaplacianFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<LaplacianGlobalState>();
+  auto &bd = data.bind_data->Cast<LaplacianBindData>(); auto &gs = data.global_state->Cast<LaplacianGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
     int64_t nc = ::onager::onager_compute_laplacian(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Laplacian failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_centrality.resize(nc);
-    ::onager::onager_compute_laplacian(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_centrality.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_laplacian_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_centrality.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Laplacian");
+    gs.result_nodes.resize(written); gs.result_centrality.resize(written);
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_centrality}, "Laplacian");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -631,6 +899,7 @@ void RegisterLocalReachingFunction(ExtensionLoader &loader) {
   lr.in_out_function = LocalReachingInOut;
   lr.in_out_function_final = LocalReachingFinal;
   lr.named_parameters["distance"] = LogicalType::BIGINT;
+  AddScoreSelectionParameters(lr);
   ONAGER_SET_NO_ORDER(lr);
   loader.RegisterFunction(lr);
 }
@@ -638,6 +907,7 @@ void RegisterLaplacianFunction(ExtensionLoader &loader) {
   TableFunction lap("onager_ctr_laplacian", {LogicalType::TABLE}, nullptr, LaplacianBind, LaplacianInitGlobal);
   lap.in_out_function = LaplacianInOut;
   lap.in_out_function_final = LaplacianFinal;
+  AddScoreSelectionParameters(lap);
   ONAGER_SET_NO_ORDER(lap);
   loader.RegisterFunction(lap);
 }
@@ -5,6 +5,7 @@
  * Louvain, Connected Components, Label Propagation, Girvan-Newman.
  */
 #include "functions.hpp"
+#include <algorithm>
 #include <mutex>
 
 namespace duckdb {
@@ -47,7 +48,8 @@ static OperatorFinalizeResultType LouvainFinal(ExecutionContext &ctx, TableFunct
     int64_t nc = ::onager::onager_compute_louvain(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.seed, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Louvain failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
-    ::onager::onager_compute_louvain(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.seed, gs.result_nodes.data(), gs.result_communities.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_louvain_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.seed, gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Louvain");
+    gs.result_nodes.resize(written); gs.result_communities.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -70,11 +72,20 @@ struct ComponentsGlobalState : public GlobalTableFunctionState {
   idx_t MaxThreads() const override { return 1; }
 };
 
+struct ComponentsBindData : public TableFunctionData { std::string mode = "undirected"; };
+
 static unique_ptr<FunctionData> ComponentsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<ComponentsBindData>();
   CheckInt64Input(input, "onager_cmm_components");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "mode") bd->mode = StringUtil::Lower(kv.second.GetValue<string>());
+  }
+  if (bd->mode != "undirected" && bd->mode != "weak" && bd->mode != "strong") {
+    throw InvalidInputException("onager_cmm_components mode must be 'undirected', 'weak', or 'strong'");
+  }
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::BIGINT); nm.push_back("component");
-  return make_uniq<TableFunctionData>();
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> ComponentsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ComponentsGlobalState>(); }
 static OperatorResultType ComponentsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
@@ -85,14 +96,15 @@ static OperatorResultType ComponentsInOut(ExecutionContext &ctx, TableFunctionIn
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType ComponentsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<ComponentsGlobalState>();
+  auto &bd = data.bind_data->Cast<ComponentsBindData>(); auto &gs = data.global_state->Cast<ComponentsGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_connected_components(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
+    int64_t nc = ::onager::onager_compute_components_with_mode(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.mode.c_str(), nullptr, nullptr, 0);
     if (nc < 0) throw InvalidInputException("Components failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_components.resize(nc);
-    ::onager::onager_compute_connected_components(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_components.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_components_with_mode(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.mode.c_str(), gs.result_nodes.data(), gs.result_components.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Components");
+    gs.result_nodes.resize(written); gs.result_components.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -104,6 +116,121 @@ static OperatorFinalizeResultType ComponentsFinal(ExecutionContext &ctx, TableFu
   return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Component Of A Single Node
+// =============================================================================
+
+struct ComponentOfBindData : public TableFunctionData { int64_t node = 0; };
+struct ComponentOfGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> ComponentOfBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<ComponentOfBindData>();
+  CheckInt64Input(input, "onager_cmm_component_of");
+  auto node = input.named_parameters.find("node");
+  if (node == input.named_parameters.end() || node->second.IsNull()) throw InvalidInputException("onager_cmm_component_of requires node := ...");
+  bd->node = node->second.GetValue<int64_t>();
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> ComponentOfInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ComponentOfGlobalState>(); }
+static OperatorResultType ComponentOfInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<ComponentOfGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ComponentOfFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ComponentOfBindData>(); auto &gs = data.global_state->Cast<ComponentOfGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_component_of(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.node, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Component lookup failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_component_of(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.node, gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Component lookup");
+    gs.result_nodes.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Similarity-Threshold Components
+// =============================================================================
+
+struct SimilarityComponentsBindData : public TableFunctionData { double threshold = 0.0; };
+struct SimilarityComponentsGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_clusters, result_sizes;
+  std::vector<double> weights, result_min, result_max;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> SimilarityComponentsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<SimilarityComponentsBindData>();
+  CheckInt64Input(input, "onager_cmm_similarity_components", 3);
+  if (input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  auto threshold = input.named_parameters.find("threshold");
+  if (threshold == input.named_parameters.end() || threshold->second.IsNull()) throw InvalidInputException("onager_cmm_similarity_components requires threshold := ...");
+  bd->threshold = threshold->second.GetValue<double>();
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("cluster");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("cluster_size");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("min_similarity");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("max_similarity");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> SimilarityComponentsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<SimilarityComponentsGlobalState>(); }
+static OperatorResultType SimilarityComponentsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<SimilarityComponentsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType SimilarityComponentsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SimilarityComponentsBindData>(); auto &gs = data.global_state->Cast<SimilarityComponentsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_similarity_components(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), bd.threshold, nullptr, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Similarity components failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_clusters.resize(nc); gs.result_sizes.resize(nc); gs.result_min.resize(nc); gs.result_max.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_similarity_components(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), bd.threshold, gs.result_nodes.data(), gs.result_clusters.data(), gs.result_sizes.data(), gs.result_min.data(), gs.result_max.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Similarity components");
+    gs.result_nodes.resize(written); gs.result_clusters.resize(written); gs.result_sizes.resize(written); gs.result_min.resize(written); gs.result_max.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto sz = GetFlatVectorDataWritable<int64_t>(output.data[2]);
+  auto lo = GetFlatVectorDataWritable<double>(output.data[3]); auto hi = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    n[i] = gs.result_nodes[k]; c[i] = gs.result_clusters[k]; sz[i] = gs.result_sizes[k];
+    // A cluster without internal edges has no similarity range.
+    if (std::isnan(gs.result_min[k])) { FlatVector::SetNull(output.data[3], i, true); FlatVector::SetNull(output.data[4], i, true); }
+    else { lo[i] = gs.result_min[k]; hi[i] = gs.result_max[k]; }
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
 // =============================================================================
 // Label Propagation
 // =============================================================================
@@ -137,7 +264,8 @@ static OperatorFinalizeResultType LabelPropFinal(ExecutionContext &ctx, TableFun
     int64_t nc = ::onager::onager_compute_label_propagation(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Label propagation failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_labels.resize(nc);
-    ::onager::onager_compute_label_propagation(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_labels.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_label_propagation_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_labels.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Label propagation");
+    gs.result_nodes.resize(written); gs.result_labels.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -153,10 +281,11 @@ static OperatorFinalizeResultType LabelPropFinal(ExecutionContext &ctx, TableFun
 // Girvan-Newman
 // =============================================================================
 
-struct GirvanNewmanBindData : public TableFunctionData { int64_t target_communities = 2; };
+struct GirvanNewmanBindData : public TableFunctionData { int64_t target_communities = 2; bool max_modularity = false; int64_t max_iterations = 0; int64_t time_budget_ms = 0; };
 struct GirvanNewmanGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_ids, result_communities;
+  double modularity = 0.0;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -164,9 +293,18 @@ struct GirvanNewmanGlobalState : public GlobalTableFunctionState {
 static unique_ptr<FunctionData> GirvanNewmanBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<GirvanNewmanBindData>();
   CheckInt64Input(input, "onager_cmm_girvan_newman");
-  for (auto &kv : input.named_parameters) if (kv.first == "communities") bd->target_communities = kv.second.GetValue<int64_t>();
+  bool communities_set = false;
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "communities") { bd->target_communities = kv.second.GetValue<int64_t>(); communities_set = true; }
+    if (kv.first == "max_modularity") bd->max_modularity = kv.second.GetValue<bool>();
+    if (kv.first == "max_iterations") { bd->max_iterations = kv.second.GetValue<int64_t>(); if (bd->max_iterations <= 0) throw InvalidInputException("onager_cmm_girvan_newman requires max_iterations to be positive"); }
+    if (kv.first == "time_budget_ms") { bd->time_budget_ms = kv.second.GetValue<int64_t>(); if (bd->time_budget_ms <= 0) throw InvalidInputException("onager_cmm_girvan_newman requires time_budget_ms to be positive"); }
+  }
+  if (bd->max_modularity && communities_set) throw InvalidInputException("onager_cmm_girvan_newman takes either communities or max_modularity, not both");
+  if (bd->target_communities <= 0) throw InvalidInputException("onager_cmm_girvan_newman requires communities to be positive");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  if (bd->max_modularity) { rt.push_back(LogicalType::DOUBLE); nm.push_back("modularity"); }
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> GirvanNewmanInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<GirvanNewmanGlobalState>(); }
@@ -182,10 +320,22 @@ static OperatorFinalizeResultType GirvanNewmanFinal(ExecutionContext &ctx, Table
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_girvan_newman(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.target_communities, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Girvan-Newman failed: " + GetOnagerError());
-    gs.result_ids.resize(nc); gs.result_communities.resize(nc);
-    ::onager::onager_compute_girvan_newman(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.target_communities, gs.result_ids.data(), gs.result_communities.data());
+    if (bd.max_modularity || bd.max_iterations > 0 || bd.time_budget_ms > 0) {
+      int64_t target = bd.max_modularity ? 0 : bd.target_communities;
+      size_t max_iterations = static_cast<size_t>(bd.max_iterations);
+      uint64_t time_budget_ms = static_cast<uint64_t>(bd.time_budget_ms);
+      int64_t nc = ::onager::onager_compute_girvan_newman_with_limits(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), target, max_iterations, time_budget_ms, nullptr, nullptr, 0, nullptr);
+      if (nc < 0) throw InvalidInputException("Girvan-Newman failed: " + GetOnagerError());
+      gs.result_ids.resize(nc); gs.result_communities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_girvan_newman_with_limits(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), target, max_iterations, time_budget_ms, gs.result_ids.data(), gs.result_communities.data(), gs.result_ids.size(), &gs.modularity), gs.result_ids.size(), "Girvan-Newman");
+      gs.result_ids.resize(written); gs.result_communities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_girvan_newman(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.target_communities, nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Girvan-Newman failed: " + GetOnagerError());
+      gs.result_ids.resize(nc); gs.result_communities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_girvan_newman_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.target_communities, gs.result_ids.data(), gs.result_communities.data(), gs.result_ids.size()), gs.result_ids.size(), "Girvan-Newman");
+      gs.result_ids.resize(written); gs.result_communities.resize(written);
+    }
     gs.computed = true;
   }
   idx_t rem = gs.result_ids.size() - gs.output_idx;
@@ -193,6 +343,10 @@ static OperatorFinalizeResultType GirvanNewmanFinal(ExecutionContext &ctx, Table
   idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
   auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
   for (idx_t i = 0; i < to; i++) { n[i] = gs.result_ids[gs.output_idx+i]; c[i] = gs.result_communities[gs.output_idx+i]; }
+  if (bd.max_modularity) {
+    auto q = GetFlatVectorDataWritable<double>(output.data[2]);
+    for (idx_t i = 0; i < to; i++) q[i] = gs.modularity;
+  }
   gs.output_idx += to; output.SetCardinality(to);
   return gs.output_idx >= gs.result_ids.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
@@ -201,10 +355,12 @@ static OperatorFinalizeResultType GirvanNewmanFinal(ExecutionContext &ctx, Table
 // Spectral Clustering
 // =============================================================================
 
-struct SpectralBindData : public TableFunctionData { int64_t k = 2; int64_t seed = -1; };
+struct SpectralBindData : public TableFunctionData { int64_t k = 2; int64_t seed = -1; bool weighted = false; bool auto_k = false; int64_t max_k = 10; };
 struct SpectralGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_communities;
+  std::vector<double> weights;
+  int64_t chosen_k = 0;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -212,20 +368,33 @@ struct SpectralGlobalState : public GlobalTableFunctionState {
 static unique_ptr<FunctionData> SpectralBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<SpectralBindData>();
   CheckInt64Input(input, "onager_cmm_spectral");
+  bool k_set = false;
   for (auto &kv : input.named_parameters) {
-    if (kv.first == "k") bd->k = kv.second.GetValue<int64_t>();
+    if (kv.first == "k") { bd->k = kv.second.GetValue<int64_t>(); k_set = true; }
     if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+    if (kv.first == "auto_k") bd->auto_k = kv.second.GetValue<bool>();
+    if (kv.first == "max_k") bd->max_k = kv.second.GetValue<int64_t>();
   }
+  if (bd->auto_k && k_set) throw InvalidInputException("onager_cmm_spectral takes either k or auto_k, not both");
+  if (bd->k <= 0) throw InvalidInputException("onager_cmm_spectral requires k to be positive");
+  if (bd->max_k < 2) throw InvalidInputException("onager_cmm_spectral requires max_k to be at least 2");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  if (bd->auto_k) { rt.push_back(LogicalType::BIGINT); nm.push_back("k"); }
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> SpectralInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<SpectralGlobalState>(); }
 static OperatorResultType SpectralInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
-  auto &gs = data.global_state->Cast<SpectralGlobalState>();
+  auto &bd = data.bind_data->Cast<SpectralBindData>(); auto &gs = data.global_state->Cast<SpectralGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
   for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType SpectralFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
@@ -233,10 +402,21 @@ static OperatorFinalizeResultType SpectralFinal(ExecutionContext &ctx, TableFunc
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_spectral_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.k, bd.seed, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Spectral clustering failed: " + GetOnagerError());
-    gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
-    ::onager::onager_compute_spectral_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.k, bd.seed, gs.result_nodes.data(), gs.result_communities.data());
+    if (bd.weighted || bd.auto_k) {
+      size_t k = bd.auto_k ? 0 : static_cast<size_t>(bd.k);
+      size_t max_k = static_cast<size_t>(bd.max_k);
+      int64_t nc = ::onager::onager_compute_weighted_spectral_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), k, max_k, bd.seed, nullptr, nullptr, 0, nullptr);
+      if (nc < 0) throw InvalidInputException("Spectral clustering failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_weighted_spectral_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), k, max_k, bd.seed, gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size(), &gs.chosen_k), gs.result_nodes.size(), "Spectral clustering");
+      gs.result_nodes.resize(written); gs.result_communities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_spectral_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.k, bd.seed, nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Spectral clustering failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_spectral_clustering_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.k, bd.seed, gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Spectral clustering");
+      gs.result_nodes.resize(written); gs.result_communities.resize(written);
+    }
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -244,6 +424,10 @@ static OperatorFinalizeResultType SpectralFinal(ExecutionContext &ctx, TableFunc
   idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
   auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
   for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; c[i] = gs.result_communities[gs.output_idx+i]; }
+  if (bd.auto_k) {
+    auto kc = GetFlatVectorDataWritable<int64_t>(output.data[2]);
+    for (idx_t i = 0; i < to; i++) kc[i] = gs.chosen_k;
+  }
   gs.output_idx += to; output.SetCardinality(to);
   return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
@@ -252,10 +436,11 @@ static OperatorFinalizeResultType SpectralFinal(ExecutionContext &ctx, TableFunc
 // Infomap
 // =============================================================================
 
-struct InfomapBindData : public TableFunctionData { int64_t max_iter = 100; int64_t seed = -1; };
+struct InfomapBindData : public TableFunctionData { int64_t max_iter = 100; int64_t seed = -1; bool weighted = false; bool directed = false; double teleportation = 0.15; bool flow = false; };
 struct InfomapGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_communities;
+  std::vector<double> weights;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -266,17 +451,27 @@ static unique_ptr<FunctionData> InfomapBind(ClientContext &ctx, TableFunctionBin
   for (auto &kv : input.named_parameters) {
     if (kv.first == "max_iter") bd->max_iter = kv.second.GetValue<int64_t>();
     if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+    if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+    if (kv.first == "teleportation") { bd->teleportation = kv.second.GetValue<double>(); bd->flow = true; }
   }
+  if (bd->max_iter <= 0) throw InvalidInputException("onager_cmm_infomap requires max_iter to be positive");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  bd->flow = bd->flow || bd->weighted || bd->directed;
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::BIGINT); nm.push_back("community");
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> InfomapInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<InfomapGlobalState>(); }
 static OperatorResultType InfomapInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
-  auto &gs = data.global_state->Cast<InfomapGlobalState>();
+  auto &bd = data.bind_data->Cast<InfomapBindData>(); auto &gs = data.global_state->Cast<InfomapGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
   for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType InfomapFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
@@ -284,10 +479,223 @@ static OperatorFinalizeResultType InfomapFinal(ExecutionContext &ctx, TableFunct
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_infomap(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.seed, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Infomap failed: " + GetOnagerError());
+    if (bd.flow) {
+      size_t max_iter = static_cast<size_t>(bd.max_iter);
+      int64_t nc = ::onager::onager_compute_weighted_infomap(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.teleportation, max_iter, bd.seed, nullptr, nullptr, 0, nullptr);
+      if (nc < 0) throw InvalidInputException("Infomap failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_weighted_infomap(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.directed, bd.teleportation, max_iter, bd.seed, gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size(), nullptr), gs.result_nodes.size(), "Infomap");
+      gs.result_nodes.resize(written); gs.result_communities.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_infomap(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.seed, nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Infomap failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_infomap_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.seed, gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Infomap");
+      gs.result_nodes.resize(written); gs.result_communities.resize(written);
+    }
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; c[i] = gs.result_communities[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Degree-Corrected Stochastic Block Model
+// =============================================================================
+
+struct SbmBindData : public TableFunctionData { int64_t k = 2; int64_t seed = -1; };
+struct SbmGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_blocks;
+  std::vector<double> result_probabilities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<SbmBindData> SbmBindParams(TableFunctionBindInput &input, const std::string &name) {
+  auto bd = make_uniq<SbmBindData>();
+  CheckInt64Input(input, name);
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "k") bd->k = kv.second.GetValue<int64_t>();
+    if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  }
+  if (bd->k <= 0) throw InvalidInputException(name + " requires k to be positive");
+  return bd;
+}
+static unique_ptr<FunctionData> SbmBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = SbmBindParams(input, "onager_cmm_sbm");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("block");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> SbmProbabilitiesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = SbmBindParams(input, "onager_cmm_sbm_probabilities");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("block_a");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("block_b");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("probability");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> SbmInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<SbmGlobalState>(); }
+static OperatorResultType SbmInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<SbmGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static void SbmCompute(const SbmBindData &bd, SbmGlobalState &gs) {
+  int64_t nc = ::onager::onager_fit_sbm(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.k, bd.seed, nullptr, nullptr, 0, nullptr, 0);
+  if (nc < 0) throw InvalidInputException("SBM fitting failed: " + GetOnagerError());
+  size_t k = static_cast<size_t>(bd.k);
+  gs.result_nodes.resize(nc); gs.result_blocks.resize(nc); gs.result_probabilities.resize(k * k);
+  size_t written = CheckOnagerWrite(::onager::onager_fit_sbm(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.k, bd.seed, gs.result_nodes.data(), gs.result_blocks.data(), gs.result_nodes.size(), gs.result_probabilities.data(), gs.result_probabilities.size()), gs.result_nodes.size(), "SBM fitting");
+  gs.result_nodes.resize(written); gs.result_blocks.resize(written);
+}
+static OperatorFinalizeResultType SbmFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SbmBindData>(); auto &gs = data.global_state->Cast<SbmGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    SbmCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto b = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; b[i] = gs.result_blocks[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+static OperatorFinalizeResultType SbmProbabilitiesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SbmBindData>(); auto &gs = data.global_state->Cast<SbmGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    SbmCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t k = static_cast<idx_t>(bd.k);
+  idx_t rem = gs.result_probabilities.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto a = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto b = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto p = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t cell = gs.output_idx + i;
+    a[i] = static_cast<int64_t>(cell / k); b[i] = static_cast<int64_t>(cell % k); p[i] = gs.result_probabilities[cell];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_probabilities.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Hierarchical Clustering
+// =============================================================================
+
+struct HierarchicalBindData : public TableFunctionData { std::string linkage = "average"; };
+struct HierarchicalGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_child_a, result_child_b, result_merged;
+  std::vector<double> result_heights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> HierarchicalBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<HierarchicalBindData>();
+  CheckInt64Input(input, "onager_cmm_hierarchical");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "linkage") bd->linkage = StringUtil::Lower(kv.second.GetValue<string>());
+  }
+  if (bd->linkage != "single" && bd->linkage != "complete" && bd->linkage != "average") {
+    throw InvalidInputException("onager_cmm_hierarchical linkage must be 'single', 'complete', or 'average'");
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("child_a");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("child_b");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("merged_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("height");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> HierarchicalInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<HierarchicalGlobalState>(); }
+static OperatorResultType HierarchicalInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<HierarchicalGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType HierarchicalFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<HierarchicalBindData>(); auto &gs = data.global_state->Cast<HierarchicalGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_hierarchical_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.linkage.c_str(), nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Hierarchical clustering failed: " + GetOnagerError());
+    gs.result_child_a.resize(nc); gs.result_child_b.resize(nc); gs.result_merged.resize(nc); gs.result_heights.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_hierarchical_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.linkage.c_str(), gs.result_child_a.data(), gs.result_child_b.data(), gs.result_merged.data(), gs.result_heights.data(), gs.result_merged.size()), gs.result_merged.size(), "Hierarchical clustering");
+    gs.result_child_a.resize(written); gs.result_child_b.resize(written); gs.result_merged.resize(written); gs.result_heights.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_merged.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto a = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto b = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto m = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto h = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    a[i] = gs.result_child_a[gs.output_idx+i]; b[i] = gs.result_child_b[gs.output_idx+i];
+    m[i] = gs.result_merged[gs.output_idx+i]; h[i] = gs.result_heights[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_merged.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Correlation Clustering
+// =============================================================================
+
+struct CorrelationBindData : public TableFunctionData { int64_t seed = -1; };
+struct CorrelationGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_communities;
+  std::vector<double> weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> CorrelationBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<CorrelationBindData>();
+  CheckInt64Input(input, "onager_cmm_correlation", 3);
+  if (input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  for (auto &kv : input.named_parameters) if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> CorrelationInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<CorrelationGlobalState>(); }
+static OperatorResultType CorrelationInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<CorrelationGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType CorrelationFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CorrelationBindData>(); auto &gs = data.global_state->Cast<CorrelationGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_correlation_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), bd.seed, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Correlation clustering failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
-    ::onager::onager_compute_infomap(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.seed, gs.result_nodes.data(), gs.result_communities.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_correlation_clustering(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), bd.seed, gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Correlation clustering");
+    gs.result_nodes.resize(written); gs.result_communities.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -299,6 +707,346 @@ static OperatorFinalizeResultType InfomapFinal(ExecutionContext &ctx, TableFunct
   return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Fluid Communities
+// =============================================================================
+
+struct FluidBindData : public TableFunctionData { int64_t k = 0; int64_t seed = -1; };
+struct FluidGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_communities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> FluidBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<FluidBindData>();
+  CheckInt64Input(input, "onager_cmm_fluid");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "k") bd->k = kv.second.GetValue<int64_t>();
+    if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  }
+  if (bd->k <= 0) throw InvalidInputException("onager_cmm_fluid requires k to be positive");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> FluidInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<FluidGlobalState>(); }
+static OperatorResultType FluidInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<FluidGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType FluidFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<FluidBindData>(); auto &gs = data.global_state->Cast<FluidGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    size_t k = static_cast<size_t>(bd.k);
+    int64_t nc = ::onager::onager_compute_fluid_communities(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), k, bd.seed, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Fluid Communities failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_fluid_communities(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), k, bd.seed, gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Fluid Communities");
+    gs.result_nodes.resize(written); gs.result_communities.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; c[i] = gs.result_communities[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Greedy Modularity (Clauset-Newman-Moore)
+// =============================================================================
+
+struct GreedyModularityGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_communities;
+  double modularity = 0.0;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> GreedyModularityBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  CheckInt64Input(input, "onager_cmm_greedy_modularity");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("modularity");
+  return make_uniq<TableFunctionData>();
+}
+static unique_ptr<GlobalTableFunctionState> GreedyModularityInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<GreedyModularityGlobalState>(); }
+static OperatorResultType GreedyModularityInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<GreedyModularityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType GreedyModularityFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<GreedyModularityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_greedy_modularity(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr, 0, nullptr);
+    if (nc < 0) throw InvalidInputException("Greedy modularity failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_communities.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_greedy_modularity(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size(), &gs.modularity), gs.result_nodes.size(), "Greedy modularity");
+    gs.result_nodes.resize(written); gs.result_communities.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto q = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; c[i] = gs.result_communities[gs.output_idx+i]; q[i] = gs.modularity; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Community Bridges
+// =============================================================================
+
+struct CommunityBridgesBindData : public TableFunctionData { ScoreSelection selection; };
+struct CommunityBridgesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, src_communities, dst_communities;
+  std::vector<int64_t> result_src, result_dst, result_src_communities, result_dst_communities, result_nodes, result_communities;
+  std::vector<double> result_edge_paths, result_node_paths;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<CommunityBridgesBindData> CommunityBridgesBindParams(TableFunctionBindInput &input, const std::string &name) {
+  auto bd = make_uniq<CommunityBridgesBindData>();
+  CheckInt64Input(input, name, 4);
+  if (input.input_table_types[2] != LogicalType::BIGINT || input.input_table_types[3] != LogicalType::BIGINT) {
+    throw InvalidInputException(name + " requires (src_community, dst_community) columns to be BIGINT. Please cast them (e.g. community::bigint)");
+  }
+  BindScoreSelection(input, bd->selection, {"paths"}, name);
+  return bd;
+}
+static unique_ptr<FunctionData> CommunityBridgesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = CommunityBridgesBindParams(input, "onager_cmm_community_bridges");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src_community");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst_community");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("paths");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> BridgeNodesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = CommunityBridgesBindParams(input, "onager_cmm_bridge_nodes");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("paths");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> CommunityBridgesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<CommunityBridgesGlobalState>(); }
+static OperatorResultType CommunityBridgesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<CommunityBridgesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto sc = FlatVector::GetData<int64_t>(input.data[2]); auto dc = FlatVector::GetData<int64_t>(input.data[3]);
+  for (idx_t i = 0; i < input.size(); i++) {
+    if (FlatVector::IsNull(input.data[2], i) || FlatVector::IsNull(input.data[3], i)) throw InvalidInputException("Community columns must not contain NULL");
+    gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]);
+    gs.src_communities.push_back(sc[i]); gs.dst_communities.push_back(dc[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static void CommunityBridgesCompute(const CommunityBridgesBindData &bd, CommunityBridgesGlobalState &gs) {
+  // Each edge lists the community of both endpoints
+  std::vector<int64_t> node_ids(gs.src_nodes), node_communities(gs.src_communities);
+  node_ids.insert(node_ids.end(), gs.dst_nodes.begin(), gs.dst_nodes.end());
+  node_communities.insert(node_communities.end(), gs.dst_communities.begin(), gs.dst_communities.end());
+  int64_t node_count = 0;
+  int64_t ec = ::onager::onager_compute_community_bridges(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), node_ids.data(), node_communities.data(), node_ids.size(),
+      nullptr, nullptr, nullptr, nullptr, nullptr, 0, nullptr, nullptr, nullptr, 0, &node_count);
+  if (ec < 0) throw InvalidInputException("Community bridges failed: " + GetOnagerError());
+  gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_src_communities.resize(ec); gs.result_dst_communities.resize(ec); gs.result_edge_paths.resize(ec);
+  gs.result_nodes.resize(node_count); gs.result_communities.resize(node_count); gs.result_node_paths.resize(node_count);
+  size_t written = CheckOnagerWrite(::onager::onager_compute_community_bridges(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), node_ids.data(), node_communities.data(), node_ids.size(),
+      gs.result_src.data(), gs.result_dst.data(), gs.result_src_communities.data(), gs.result_dst_communities.data(), gs.result_edge_paths.data(), gs.result_src.size(),
+      gs.result_nodes.data(), gs.result_communities.data(), gs.result_node_paths.data(), gs.result_nodes.size(), &node_count), gs.result_src.size(), "Community bridges");
+  gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_src_communities.resize(written); gs.result_dst_communities.resize(written); gs.result_edge_paths.resize(written);
+  if (bd.selection.Active()) {
+    auto edges = SelectRows(bd.selection, gs.result_edge_paths, "Community bridges");
+    KeepRows(edges, gs.result_src); KeepRows(edges, gs.result_dst); KeepRows(edges, gs.result_src_communities);
+    KeepRows(edges, gs.result_dst_communities); KeepRows(edges, gs.result_edge_paths);
+    auto nodes = SelectRows(bd.selection, gs.result_node_paths, "Community bridges");
+    KeepRows(nodes, gs.result_nodes); KeepRows(nodes, gs.result_communities); KeepRows(nodes, gs.result_node_paths);
+  }
+}
+static OperatorFinalizeResultType CommunityBridgesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CommunityBridgesBindData>(); auto &gs = data.global_state->Cast<CommunityBridgesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    CommunityBridgesCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto sc = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto dc = GetFlatVectorDataWritable<int64_t>(output.data[3]);
+  auto p = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    s[i] = gs.result_src[k]; d[i] = gs.result_dst[k]; sc[i] = gs.result_src_communities[k]; dc[i] = gs.result_dst_communities[k]; p[i] = gs.result_edge_paths[k];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+static OperatorFinalizeResultType BridgeNodesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CommunityBridgesBindData>(); auto &gs = data.global_state->Cast<CommunityBridgesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    CommunityBridgesCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto p = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    n[i] = gs.result_nodes[k]; c[i] = gs.result_communities[k]; p[i] = gs.result_node_paths[k];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Partition Modularity
+// =============================================================================
+
+struct ModularityGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, src_communities, dst_communities;
+  double result = 0.0;
+  bool computed = false, output_done = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> ModularityBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  CheckInt64Input(input, "onager_cmm_modularity", 4);
+  if (input.input_table_types[2] != LogicalType::BIGINT || input.input_table_types[3] != LogicalType::BIGINT) {
+    throw InvalidInputException("onager_cmm_modularity requires (src_community, dst_community) columns to be BIGINT. Please cast them (e.g. community::bigint)");
+  }
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("modularity");
+  return make_uniq<TableFunctionData>();
+}
+static unique_ptr<GlobalTableFunctionState> ModularityInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ModularityGlobalState>(); }
+static OperatorResultType ModularityInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<ModularityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto sc = FlatVector::GetData<int64_t>(input.data[2]); auto dc = FlatVector::GetData<int64_t>(input.data[3]);
+  for (idx_t i = 0; i < input.size(); i++) {
+    if (FlatVector::IsNull(input.data[2], i) || FlatVector::IsNull(input.data[3], i)) throw InvalidInputException("Community columns must not contain NULL");
+    gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]);
+    gs.src_communities.push_back(sc[i]); gs.dst_communities.push_back(dc[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ModularityFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<ModularityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    // Each edge lists the community of both endpoints
+    std::vector<int64_t> node_ids(gs.src_nodes), node_communities(gs.src_communities);
+    node_ids.insert(node_ids.end(), gs.dst_nodes.begin(), gs.dst_nodes.end());
+    node_communities.insert(node_communities.end(), gs.dst_communities.begin(), gs.dst_communities.end());
+    gs.result = ::onager::onager_compute_modularity(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), node_ids.data(), node_communities.data(), node_ids.size());
+    if (std::isnan(gs.result)) throw InvalidInputException("Modularity failed: " + GetOnagerError());
+    gs.computed = true;
+  }
+  if (gs.output_done) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<double>(output.data[0])[0] = gs.result;
+  output.SetCardinality(1); gs.output_done = true;
+  return OperatorFinalizeResultType::FINISHED;
+}
+
+// =============================================================================
+// Partition Similarity
+// =============================================================================
+
+struct PartitionSimilarityBindData : public TableFunctionData { std::string metric = "nmi"; };
+struct PartitionSimilarityGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> nodes, communities, partitions;
+  double similarity = 0.0; int64_t common_count = 0;
+  bool computed = false, emitted = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> PartitionSimilarityBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<PartitionSimilarityBindData>();
+  auto &types = input.input_table_types;
+  if (types.size() < 3 || types[0] != LogicalType::BIGINT || types[1] != LogicalType::BIGINT || types[2] != LogicalType::BIGINT) {
+    throw InvalidInputException("onager_cmm_partition_similarity requires (node_id BIGINT, community BIGINT, partition BIGINT) columns. Please cast inputs (e.g. community::bigint)");
+  }
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "metric") bd->metric = StringUtil::Lower(kv.second.GetValue<string>());
+  }
+  if (bd->metric != "nmi" && bd->metric != "ari" && bd->metric != "v_measure") {
+    throw InvalidInputException("onager_cmm_partition_similarity metric must be 'nmi', 'ari', or 'v_measure'");
+  }
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("similarity");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("common_nodes");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> PartitionSimilarityInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<PartitionSimilarityGlobalState>(); }
+static OperatorResultType PartitionSimilarityInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<PartitionSimilarityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto n = FlatVector::GetData<int64_t>(input.data[0]); auto c = FlatVector::GetData<int64_t>(input.data[1]);
+  auto p = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) {
+    if (FlatVector::IsNull(input.data[1], i)) throw InvalidInputException("Community column must not contain NULL");
+    gs.nodes.push_back(n[i]); gs.communities.push_back(c[i]); gs.partitions.push_back(p[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType PartitionSimilarityFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<PartitionSimilarityBindData>(); auto &gs = data.global_state->Cast<PartitionSimilarityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.nodes.empty()) { gs.computed = true; gs.emitted = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    // The smaller partition value is the first partition and the larger is the second one
+    int64_t first_partition = *std::min_element(gs.partitions.begin(), gs.partitions.end());
+    int64_t second_partition = *std::max_element(gs.partitions.begin(), gs.partitions.end());
+    std::vector<int64_t> a_nodes, a_communities, b_nodes, b_communities;
+    for (idx_t i = 0; i < gs.nodes.size(); i++) {
+      if (gs.partitions[i] == first_partition) { a_nodes.push_back(gs.nodes[i]); a_communities.push_back(gs.communities[i]); }
+      else if (gs.partitions[i] == second_partition) { b_nodes.push_back(gs.nodes[i]); b_communities.push_back(gs.communities[i]); }
+      else throw InvalidInputException("onager_cmm_partition_similarity requires exactly two partition values");
+    }
+    if (b_nodes.empty()) throw InvalidInputException("onager_cmm_partition_similarity requires exactly two partition values");
+    gs.common_count = ::onager::onager_compute_partition_similarity(a_nodes.data(), a_communities.data(), a_nodes.size(), b_nodes.data(), b_communities.data(), b_nodes.size(),
+        bd.metric.c_str(), &gs.similarity);
+    if (gs.common_count < 0) throw InvalidInputException("Partition similarity failed: " + GetOnagerError());
+    gs.computed = true;
+  }
+  if (gs.emitted) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<double>(output.data[0])[0] = gs.similarity;
+  GetFlatVectorDataWritable<int64_t>(output.data[1])[0] = gs.common_count;
+  gs.emitted = true; output.SetCardinality(1);
+  return OperatorFinalizeResultType::FINISHED;
+}
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -316,9 +1064,24 @@ void RegisterCommunityFunctions(ExtensionLoader &loader) {
   TableFunction components("onager_cmm_components", {LogicalType::TABLE}, nullptr, ComponentsBind, ComponentsInitGlobal);
   components.in_out_function = ComponentsInOut;
   components.in_out_function_final = ComponentsFinal;
+  components.named_parameters["mode"] = LogicalType::VARCHAR;
   ONAGER_SET_NO_ORDER(components);
   loader.RegisterFunction(components);
 
+  TableFunction component_of("onager_cmm_component_of", {LogicalType::TABLE}, nullptr, ComponentOfBind, ComponentOfInitGlobal);
+  component_of.in_out_function = ComponentOfInOut;
+  component_of.in_out_function_final = ComponentOfFinal;
+  component_of.named_parameters["node"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(component_of);
+  loader.RegisterFunction(component_of);
+
+  TableFunction similarity_components("onager_cmm_similarity_components", {LogicalType::TABLE}, nullptr, SimilarityComponentsBind, SimilarityComponentsInitGlobal);
+  similarity_components.in_out_function = SimilarityComponentsInOut;
+  similarity_components.in_out_function_final = SimilarityComponentsFinal;
+  similarity_components.named_parameters["threshold"] = LogicalType::DOUBLE;
+  ONAGER_SET_NO_ORDER(similarity_components);
+  loader.RegisterFunction(similarity_components);
+
   TableFunction label_prop("onager_cmm_label_prop", {LogicalType::TABLE}, nullptr, LabelPropBind, LabelPropInitGlobal);
   label_prop.in_out_function = LabelPropInOut;
   label_prop.in_out_function_final = LabelPropFinal;
@@ -329,6 +1092,9 @@ void RegisterCommunityFunctions(ExtensionLoader &loader) {
   girvan_newman.in_out_function = GirvanNewmanInOut;
   girvan_newman.in_out_function_final = GirvanNewmanFinal;
   girvan_newman.named_parameters["communities"] = LogicalType::BIGINT;
+  girvan_newman.named_parameters["max_modularity"] = LogicalType::BOOLEAN;
+  girvan_newman.named_parameters["max_iterations"] = LogicalType::BIGINT;
+  girvan_newman.named_parameters["time_budget_ms"] = LogicalType::BIGINT;
   ONAGER_SET_NO_ORDER(girvan_newman);
   loader.RegisterFunction(girvan_newman);
 
@@ -337,6 +1103,8 @@ void RegisterCommunityFunctions(ExtensionLoader &loader) {
   spectral.in_out_function_final = SpectralFinal;
   spectral.named_parameters["k"] = LogicalType::BIGINT;
   spectral.named_parameters["seed"] = LogicalType::BIGINT;
+  spectral.named_parameters["auto_k"] = LogicalType::BOOLEAN;
+  spectral.named_parameters["max_k"] = LogicalType::BIGINT;
   ONAGER_SET_NO_ORDER(spectral);
   loader.RegisterFunction(spectral);
 
@@ -345,8 +1113,81 @@ void RegisterCommunityFunctions(ExtensionLoader &loader) {
   infomap.in_out_function_final = InfomapFinal;
   infomap.named_parameters["max_iter"] = LogicalType::BIGINT;
   infomap.named_parameters["seed"] = LogicalType::BIGINT;
+  infomap.named_parameters["directed"] = LogicalType::BOOLEAN;
+  infomap.named_parameters["teleportation"] = LogicalType::DOUBLE;
   ONAGER_SET_NO_ORDER(infomap);
   loader.RegisterFunction(infomap);
+
+  TableFunction sbm("onager_cmm_sbm", {LogicalType::TABLE}, nullptr, SbmBind, SbmInitGlobal);
+  sbm.in_out_function = SbmInOut;
+  sbm.in_out_function_final = SbmFinal;
+  sbm.named_parameters["k"] = LogicalType::BIGINT;
+  sbm.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(sbm);
+  loader.RegisterFunction(sbm);
+
+  TableFunction sbm_prob("onager_cmm_sbm_probabilities", {LogicalType::TABLE}, nullptr, SbmProbabilitiesBind, SbmInitGlobal);
+  sbm_prob.in_out_function = SbmInOut;
+  sbm_prob.in_out_function_final = SbmProbabilitiesFinal;
+  sbm_prob.named_parameters["k"] = LogicalType::BIGINT;
+  sbm_prob.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(sbm_prob);
+  loader.RegisterFunction(sbm_prob);
+
+  TableFunction hierarchical("onager_cmm_hierarchical", {LogicalType::TABLE}, nullptr, HierarchicalBind, HierarchicalInitGlobal);
+  hierarchical.in_out_function = HierarchicalInOut;
+  hierarchical.in_out_function_final = HierarchicalFinal;
+  hierarchical.named_parameters["linkage"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(hierarchical);
+  loader.RegisterFunction(hierarchical);
+
+  TableFunction correlation("onager_cmm_correlation", {LogicalType::TABLE}, nullptr, CorrelationBind, CorrelationInitGlobal);
+  correlation.in_out_function = CorrelationInOut;
+  correlation.in_out_function_final = CorrelationFinal;
+  correlation.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(correlation);
+  loader.RegisterFunction(correlation);
+
+  TableFunction fluid("onager_cmm_fluid", {LogicalType::TABLE}, nullptr, FluidBind, FluidInitGlobal);
+  fluid.in_out_function = FluidInOut;
+  fluid.in_out_function_final = FluidFinal;
+  fluid.named_parameters["k"] = LogicalType::BIGINT;
+  fluid.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(fluid);
+  loader.RegisterFunction(fluid);
+
+  TableFunction greedy_modularity("onager_cmm_greedy_modularity", {LogicalType::TABLE}, nullptr, GreedyModularityBind, GreedyModularityInitGlobal);
+  greedy_modularity.in_out_function = GreedyModularityInOut;
+  greedy_modularity.in_out_function_final = GreedyModularityFinal;
+  ONAGER_SET_NO_ORDER(greedy_modularity);
+  loader.RegisterFunction(greedy_modularity);
+
+  TableFunction community_bridges("onager_cmm_community_bridges", {LogicalType::TABLE}, nullptr, CommunityBridgesBind, CommunityBridgesInitGlobal);
+  community_bridges.in_out_function = CommunityBridgesInOut;
+  community_bridges.in_out_function_final = CommunityBridgesFinal;
+  AddScoreSelectionParameters(community_bridges);
+  ONAGER_SET_NO_ORDER(community_bridges);
+  loader.RegisterFunction(community_bridges);
+
+  TableFunction bridge_nodes("onager_cmm_bridge_nodes", {LogicalType::TABLE}, nullptr, BridgeNodesBind, CommunityBridgesInitGlobal);
+  bridge_nodes.in_out_function = CommunityBridgesInOut;
+  bridge_nodes.in_out_function_final = BridgeNodesFinal;
+  AddScoreSelectionParameters(bridge_nodes);
+  ONAGER_SET_NO_ORDER(bridge_nodes);
+  loader.RegisterFunction(bridge_nodes);
+
+  TableFunction modularity("onager_cmm_modularity", {LogicalType::TABLE}, nullptr, ModularityBind, ModularityInitGlobal);
+  modularity.in_out_function = ModularityInOut;
+  modularity.in_out_function_final = ModularityFinal;
+  ONAGER_SET_NO_ORDER(modularity);
+  loader.RegisterFunction(modularity);
+
+  TableFunction partition_similarity("onager_cmm_partition_similarity", {LogicalType::TABLE}, nullptr, PartitionSimilarityBind, PartitionSimilarityInitGlobal);
+  partition_similarity.in_out_function = PartitionSimilarityInOut;
+  partition_similarity.in_out_function_final = PartitionSimilarityFinal;
+  partition_similarity.named_parameters["metric"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(partition_similarity);
+  loader.RegisterFunction(partition_similarity);
 }
 
 } // namespace onager
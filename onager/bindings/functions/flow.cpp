@@ -0,0 +1,375 @@
+/**
+ * @file flow.cpp
+ * @brief Flow and cut table functions for Onager DuckDB extension.
+ *
+ * Maximum flow and minimum cut of a directed network with capacities, minimum
+ * edge and vertex cuts between two nodes, the global minimum cut, and edge and
+ * node connectivity.
+ */
+#include "functions.hpp"
+#include <mutex>
+
+namespace duckdb {
+
+using namespace onager;
+
+struct CutBindData : public TableFunctionData { int64_t source = 0; int64_t target = 0; };
+
+// Reads the required source and target nodes of a cut
+static void BindCutEndpoints(TableFunctionBindInput &input, const char *name, CutBindData &bd) {
+  for (auto param : {"source", "target"}) {
+    auto it = input.named_parameters.find(param);
+    if (it == input.named_parameters.end() || it->second.IsNull()) throw InvalidInputException(string(name) + " requires " + param + " := ...");
+  }
+  bd.source = input.named_parameters["source"].GetValue<int64_t>();
+  bd.target = input.named_parameters["target"].GetValue<int64_t>();
+}
+
+// =============================================================================
+// Maximum Flow and Minimum Cut
+// =============================================================================
+
+struct FlowBindData : public CutBindData { bool cut = false; bool weighted = false; };
+struct FlowGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst; std::vector<double> capacities, result_values;
+  double total = 0.0;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+// Shared by both functions, which differ only in the edges they return
+static unique_ptr<FunctionData> BindFlow(TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm, const char *name, bool cut) {
+  auto bd = make_uniq<FlowBindData>();
+  bd->cut = cut;
+  CheckInt64Input(input, name);
+  BindCutEndpoints(input, name, *bd);
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Capacity column must be DOUBLE. Please cast it (e.g. capacity::double)");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back(cut ? "capacity" : "flow");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back(cut ? "cut_value" : "flow_value");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> MaxFlowBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  return BindFlow(input, rt, nm, "onager_flw_max_flow", false);
+}
+static unique_ptr<FunctionData> MinCutBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  return BindFlow(input, rt, nm, "onager_flw_min_cut", true);
+}
+static unique_ptr<GlobalTableFunctionState> FlowInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<FlowGlobalState>(); }
+static OperatorResultType FlowInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<FlowBindData>(); auto &gs = data.global_state->Cast<FlowGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto c = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.capacities.push_back(c[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static int64_t FlowCompute(FlowBindData &bd, FlowGlobalState &gs, int64_t *out_src, int64_t *out_dst, double *out_values, size_t capacity) {
+  return bd.cut
+    ? ::onager::onager_compute_min_cut(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.capacities.data(), gs.capacities.size(), bd.source, bd.target, out_src, out_dst, out_values, capacity, &gs.total)
+    : ::onager::onager_compute_max_flow(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.capacities.data(), gs.capacities.size(), bd.source, bd.target, out_src, out_dst, out_values, capacity, &gs.total);
+}
+static OperatorFinalizeResultType FlowFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<FlowBindData>(); auto &gs = data.global_state->Cast<FlowGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    const char *label = bd.cut ? "Minimum cut" : "Maximum flow";
+    int64_t nc = FlowCompute(bd, gs, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException(string(label) + " failed: " + GetOnagerError());
+    gs.result_src.resize(nc); gs.result_dst.resize(nc); gs.result_values.resize(nc);
+    size_t written = CheckOnagerWrite(FlowCompute(bd, gs, gs.result_src.data(), gs.result_dst.data(), gs.result_values.data(), gs.result_src.size()), gs.result_src.size(), label);
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_values.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto v = GetFlatVectorDataWritable<double>(output.data[2]); auto t = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; v[i] = gs.result_values[gs.output_idx+i]; t[i] = gs.total; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Minimum Edge Cut
+// =============================================================================
+
+struct MinEdgeCutGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> MinEdgeCutBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<CutBindData>();
+  CheckInt64Input(input, "onager_flw_min_edge_cut");
+  BindCutEndpoints(input, "onager_flw_min_edge_cut", *bd);
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> MinEdgeCutInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<MinEdgeCutGlobalState>(); }
+static OperatorResultType MinEdgeCutInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<MinEdgeCutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType MinEdgeCutFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CutBindData>(); auto &gs = data.global_state->Cast<MinEdgeCutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_min_edge_cut(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, bd.target, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Minimum edge cut failed: " + GetOnagerError());
+    gs.result_src.resize(nc); gs.result_dst.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_min_edge_cut(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, bd.target, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size()), gs.result_src.size(), "Minimum edge cut");
+    gs.result_src.resize(written); gs.result_dst.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Minimum Vertex Cut
+// =============================================================================
+
+struct MinVertexCutGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> MinVertexCutBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<CutBindData>();
+  CheckInt64Input(input, "onager_flw_min_vertex_cut");
+  BindCutEndpoints(input, "onager_flw_min_vertex_cut", *bd);
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> MinVertexCutInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<MinVertexCutGlobalState>(); }
+static OperatorResultType MinVertexCutInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<MinVertexCutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType MinVertexCutFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CutBindData>(); auto &gs = data.global_state->Cast<MinVertexCutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_min_vertex_cut(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, bd.target, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Minimum vertex cut failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_min_vertex_cut(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, bd.target, gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Minimum vertex cut");
+    gs.result_nodes.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Global Minimum Cut
+// =============================================================================
+
+struct GlobalMinCutBindData : public TableFunctionData { bool weighted = false; };
+struct GlobalMinCutGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_sides; std::vector<double> weights;
+  double cut_weight = 0.0;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> GlobalMinCutBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<GlobalMinCutBindData>();
+  CheckInt64Input(input, "onager_flw_global_min_cut");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("side");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("cut_weight");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> GlobalMinCutInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<GlobalMinCutGlobalState>(); }
+static OperatorResultType GlobalMinCutInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<GlobalMinCutBindData>(); auto &gs = data.global_state->Cast<GlobalMinCutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType GlobalMinCutFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<GlobalMinCutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_global_min_cut(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), nullptr, nullptr, 0, nullptr);
+    if (nc < 0) throw InvalidInputException("Global minimum cut failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_sides.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_global_min_cut(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), gs.result_nodes.data(), gs.result_sides.data(), gs.result_nodes.size(), &gs.cut_weight), gs.result_nodes.size(), "Global minimum cut");
+    gs.result_nodes.resize(written); gs.result_sides.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto sd = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto c = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; sd[i] = gs.result_sides[gs.output_idx+i]; c[i] = gs.cut_weight; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Edge and Node Connectivity
+// =============================================================================
+
+struct ConnectivityBindData : public TableFunctionData { bool nodes = false; bool pairwise = false; int64_t source = 0; int64_t target = 0; };
+struct ConnectivityGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  int64_t result = -1;
+  bool computed = false, output_done = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+// Shared by both connectivity functions, which differ only in what they count
+static unique_ptr<FunctionData> BindConnectivity(TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm, const char *name, bool nodes) {
+  auto bd = make_uniq<ConnectivityBindData>();
+  bd->nodes = nodes;
+  CheckInt64Input(input, name);
+  bool has_source = false, has_target = false;
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "source") { bd->source = kv.second.GetValue<int64_t>(); has_source = true; }
+    else if (kv.first == "target") { bd->target = kv.second.GetValue<int64_t>(); has_target = true; }
+  }
+  if (has_source != has_target) throw InvalidInputException(string(name) + " takes both source and target, or neither");
+  bd->pairwise = has_source;
+  rt.push_back(LogicalType::BIGINT); nm.push_back("connectivity");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> EdgeConnectivityBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  return BindConnectivity(input, rt, nm, "onager_flw_edge_connectivity", false);
+}
+static unique_ptr<FunctionData> NodeConnectivityBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  return BindConnectivity(input, rt, nm, "onager_flw_node_connectivity", true);
+}
+static unique_ptr<GlobalTableFunctionState> ConnectivityInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ConnectivityGlobalState>(); }
+static OperatorResultType ConnectivityInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<ConnectivityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ConnectivityFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ConnectivityBindData>(); auto &gs = data.global_state->Cast<ConnectivityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    gs.result = bd.nodes
+      ? ::onager::onager_compute_node_connectivity(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.pairwise, bd.source, bd.target)
+      : ::onager::onager_compute_edge_connectivity(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.pairwise, bd.source, bd.target);
+    if (gs.result < 0) throw InvalidInputException(string(bd.nodes ? "Node" : "Edge") + " connectivity failed: " + GetOnagerError());
+    gs.computed = true;
+  }
+  if (gs.output_done) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<int64_t>(output.data[0])[0] = gs.result;
+  output.SetCardinality(1); gs.output_done = true;
+  return OperatorFinalizeResultType::FINISHED;
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterFlowFunctions(ExtensionLoader &loader) {
+  TableFunction max_flow("onager_flw_max_flow", {LogicalType::TABLE}, nullptr, MaxFlowBind, FlowInitGlobal);
+  max_flow.in_out_function = FlowInOut;
+  max_flow.in_out_function_final = FlowFinal;
+  max_flow.named_parameters["source"] = LogicalType::BIGINT;
+  max_flow.named_parameters["target"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(max_flow);
+  loader.RegisterFunction(max_flow);
+
+  TableFunction min_cut("onager_flw_min_cut", {LogicalType::TABLE}, nullptr, MinCutBind, FlowInitGlobal);
+  min_cut.in_out_function = FlowInOut;
+  min_cut.in_out_function_final = FlowFinal;
+  min_cut.named_parameters["source"] = LogicalType::BIGINT;
+  min_cut.named_parameters["target"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(min_cut);
+  loader.RegisterFunction(min_cut);
+
+  TableFunction min_edge_cut("onager_flw_min_edge_cut", {LogicalType::TABLE}, nullptr, MinEdgeCutBind, MinEdgeCutInitGlobal);
+  min_edge_cut.in_out_function = MinEdgeCutInOut;
+  min_edge_cut.in_out_function_final = MinEdgeCutFinal;
+  min_edge_cut.named_parameters["source"] = LogicalType::BIGINT;
+  min_edge_cut.named_parameters["target"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(min_edge_cut);
+  loader.RegisterFunction(min_edge_cut);
+
+  TableFunction min_vertex_cut("onager_flw_min_vertex_cut", {LogicalType::TABLE}, nullptr, MinVertexCutBind, MinVertexCutInitGlobal);
+  min_vertex_cut.in_out_function = MinVertexCutInOut;
+  min_vertex_cut.in_out_function_final = MinVertexCutFinal;
+  min_vertex_cut.named_parameters["source"] = LogicalType::BIGINT;
+  min_vertex_cut.named_parameters["target"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(min_vertex_cut);
+  loader.RegisterFunction(min_vertex_cut);
+
+  TableFunction global_min_cut("onager_flw_global_min_cut", {LogicalType::TABLE}, nullptr, GlobalMinCutBind, GlobalMinCutInitGlobal);
+  global_min_cut.in_out_function = GlobalMinCutInOut;
+  global_min_cut.in_out_function_final = GlobalMinCutFinal;
+  ONAGER_SET_NO_ORDER(global_min_cut);
+  loader.RegisterFunction(global_min_cut);
+
+  TableFunction edge_connectivity("onager_flw_edge_connectivity", {LogicalType::TABLE}, nullptr, EdgeConnectivityBind, ConnectivityInitGlobal);
+  edge_connectivity.in_out_function = ConnectivityInOut;
+  edge_connectivity.in_out_function_final = ConnectivityFinal;
+  edge_connectivity.named_parameters["source"] = LogicalType::BIGINT;
+  edge_connectivity.named_parameters["target"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(edge_connectivity);
+  loader.RegisterFunction(edge_connectivity);
+
+  TableFunction node_connectivity("onager_flw_node_connectivity", {LogicalType::TABLE}, nullptr, NodeConnectivityBind, ConnectivityInitGlobal);
+  node_connectivity.in_out_function = ConnectivityInOut;
+  node_connectivity.in_out_function_final = ConnectivityFinal;
+  node_connectivity.named_parameters["source"] = LogicalType::BIGINT;
+  node_connectivity.named_parameters["target"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(node_connectivity);
+  loader.RegisterFunction(node_connectivity);
+}
+
+} // namespace onager
+} // namespace duckdb
@@ -2,23 +2,76 @@
  * @file generators.cpp
  * @brief Graph generator table functions for Onager DuckDB extension.
  *
- * Erdős-Rényi, Barabási-Albert, Watts-Strogatz.
+ * Erdős-Rényi (G(n, p) and G(n, m)), Barabási-Albert, Watts-Strogatz, LFR,
+ * random geometric graphs, and k-nearest-neighbor and similarity graphs.
  */
 #include "functions.hpp"
+#include <limits>
+#include <mutex>
 
 namespace duckdb {
 
 using namespace onager;
 
+// =============================================================================
+// Shared Options
+// =============================================================================
+
+// Named parameters shared by the edge-list generators. `start_id` offsets every
+// node ID and `node_list` returns the generated nodes instead of the edges.
+// `directed`, `self_loops`, and `parallel_edges` choose which edges may appear.
+struct GeneratorOptions {
+  int64_t start_id = 0; bool node_list = false;
+  bool directed = false; bool self_loops = false; bool parallel_edges = false;
+};
+
+static void GeneratorBindOptions(TableFunctionBindInput &input, int64_t &seed, GeneratorOptions &opts, vector<LogicalType> &rt, vector<string> &nm) {
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "seed") seed = kv.second.GetValue<int64_t>();
+    else if (kv.first == "start_id") opts.start_id = kv.second.GetValue<int64_t>();
+    else if (kv.first == "node_list") opts.node_list = kv.second.GetValue<bool>();
+    else if (kv.first == "directed") opts.directed = kv.second.GetValue<bool>();
+    else if (kv.first == "self_loops") opts.self_loops = kv.second.GetValue<bool>();
+    else if (kv.first == "parallel_edges") opts.parallel_edges = kv.second.GetValue<bool>();
+  }
+  if (opts.node_list) { rt.push_back(LogicalType::BIGINT); nm.push_back("node_id"); return; }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+}
+
+// Drops the self-loops and repeated edges that the options do not allow.
+static void GeneratorSimplify(const GeneratorOptions &opts, std::vector<int64_t> &src, std::vector<int64_t> &dst, const std::string &name) {
+  if (opts.self_loops && opts.parallel_edges) return;
+  std::vector<int64_t> s(src.size()), d(dst.size());
+  size_t written = CheckOnagerWrite(::onager::onager_simplify_edges(src.data(), dst.data(), src.size(), opts.directed, opts.self_loops, opts.parallel_edges, s.data(), d.data(), s.size()), s.size(), name);
+  s.resize(written); d.resize(written);
+  src.swap(s); dst.swap(d);
+}
+
+static void GeneratorEmit(const GeneratorOptions &opts, const std::vector<int64_t> &src, const std::vector<int64_t> &dst, const std::vector<int64_t> &nodes, idx_t &output_idx, DataChunk &output) {
+  idx_t rem = (opts.node_list ? nodes.size() : src.size()) - output_idx;
+  if (rem == 0) { output.SetCardinality(0); return; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  if (opts.node_list) {
+    auto nd = GetFlatVectorDataWritable<int64_t>(output.data[0]);
+    for (idx_t i = 0; i < to; i++) nd[i] = nodes[output_idx+i];
+  } else {
+    auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+    for (idx_t i = 0; i < to; i++) { s[i] = src[output_idx+i]; d[i] = dst[output_idx+i]; }
+  }
+  output_idx += to; output.SetCardinality(to);
+}
+
 // =============================================================================
 // Erdős-Rényi Random Graph
 // =============================================================================
 
 struct ErdosRenyiBindData : public TableFunctionData {
   int64_t n = 10; double p = 0.5; int64_t seed = 42;
+  GeneratorOptions opts;
 };
 struct ErdosRenyiGlobalState : public GlobalTableFunctionState {
-  std::vector<int64_t> result_src, result_dst;
+  std::vector<int64_t> result_src, result_dst, result_nodes;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -27,27 +80,69 @@ static unique_ptr<FunctionData> ErdosRenyiBind(ClientContext &ctx, TableFunction
   auto bd = make_uniq<ErdosRenyiBindData>();
   if (input.inputs.size() >= 1) bd->n = input.inputs[0].GetValue<int64_t>();
   if (input.inputs.size() >= 2) bd->p = input.inputs[1].GetValue<double>();
-  for (auto &kv : input.named_parameters) if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
-  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
-  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  GeneratorBindOptions(input, bd->seed, bd->opts, rt, nm);
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> ErdosRenyiInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ErdosRenyiGlobalState>(); }
 static void ErdosRenyiFunction(ClientContext &ctx, TableFunctionInput &data, DataChunk &output) {
   auto &bd = data.bind_data->Cast<ErdosRenyiBindData>(); auto &gs = data.global_state->Cast<ErdosRenyiGlobalState>();
   if (!gs.computed) {
-    int64_t ec = ::onager::onager_generate_erdos_renyi(static_cast<size_t>(bd.n), bd.p, static_cast<uint64_t>(bd.seed), nullptr, nullptr);
-    if (ec < 0) throw InvalidInputException("Erdos-Renyi failed: " + GetOnagerError());
-    gs.result_src.resize(ec); gs.result_dst.resize(ec);
-    ::onager::onager_generate_erdos_renyi(static_cast<size_t>(bd.n), bd.p, static_cast<uint64_t>(bd.seed), gs.result_src.data(), gs.result_dst.data());
+    size_t n = static_cast<size_t>(bd.n); uint64_t seed = static_cast<uint64_t>(bd.seed);
+    gs.result_nodes.resize(n);
+    if (bd.opts.directed || bd.opts.self_loops) {
+      // Directed edges and self-loops need the G(n, p) sampler instead of graphina's
+      int64_t ec = ::onager::onager_generate_gnp(n, bd.p, bd.opts.directed, bd.opts.self_loops, seed, bd.opts.start_id, nullptr, nullptr, 0, nullptr, 0);
+      if (ec < 0) throw InvalidInputException("Erdos-Renyi failed: " + GetOnagerError());
+      gs.result_src.resize(ec); gs.result_dst.resize(ec);
+      size_t written = CheckOnagerWrite(::onager::onager_generate_gnp(n, bd.p, bd.opts.directed, bd.opts.self_loops, seed, bd.opts.start_id, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_src.size(), "Erdos-Renyi");
+      gs.result_src.resize(written); gs.result_dst.resize(written);
+    } else {
+      int64_t ec = ::onager::onager_generate_erdos_renyi(n, bd.p, seed, nullptr, nullptr);
+      if (ec < 0) throw InvalidInputException("Erdos-Renyi failed: " + GetOnagerError());
+      gs.result_src.resize(ec); gs.result_dst.resize(ec);
+      size_t written = CheckOnagerWrite(::onager::onager_generate_erdos_renyi_with_ids(n, bd.p, seed, bd.opts.start_id, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_src.size(), "Erdos-Renyi");
+      gs.result_src.resize(written); gs.result_dst.resize(written);
+    }
     gs.computed = true;
   }
-  idx_t rem = gs.result_src.size() - gs.output_idx;
-  if (rem == 0) { output.SetCardinality(0); return; }
-  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
-  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
-  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; }
-  gs.output_idx += to; output.SetCardinality(to);
+  GeneratorEmit(bd.opts, gs.result_src, gs.result_dst, gs.result_nodes, gs.output_idx, output);
+}
+
+// =============================================================================
+// Erdős-Rényi G(n, m) with an Exact Edge Count
+// =============================================================================
+
+struct GnmBindData : public TableFunctionData {
+  int64_t n = 10; int64_t m = 10; int64_t seed = 42;
+  GeneratorOptions opts;
+};
+struct GnmGlobalState : public GlobalTableFunctionState {
+  std::vector<int64_t> result_src, result_dst, result_nodes;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> GnmBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<GnmBindData>();
+  if (input.inputs.size() >= 1) bd->n = input.inputs[0].GetValue<int64_t>();
+  if (input.inputs.size() >= 2) bd->m = input.inputs[1].GetValue<int64_t>();
+  if (bd->n <= 0 || bd->m < 0) throw InvalidInputException("onager_gen_gnm requires n > 0 and m >= 0");
+  GeneratorBindOptions(input, bd->seed, bd->opts, rt, nm);
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> GnmInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<GnmGlobalState>(); }
+static void GnmFunction(ClientContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<GnmBindData>(); auto &gs = data.global_state->Cast<GnmGlobalState>();
+  if (!gs.computed) {
+    size_t n = static_cast<size_t>(bd.n), m = static_cast<size_t>(bd.m); uint64_t seed = static_cast<uint64_t>(bd.seed);
+    int64_t ec = ::onager::onager_generate_gnm(n, m, bd.opts.directed, bd.opts.self_loops, bd.opts.parallel_edges, seed, bd.opts.start_id, nullptr, nullptr, 0, nullptr, 0);
+    if (ec < 0) throw InvalidInputException("G(n, m) generation failed: " + GetOnagerError());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_nodes.resize(n);
+    size_t written = CheckOnagerWrite(::onager::onager_generate_gnm(n, m, bd.opts.directed, bd.opts.self_loops, bd.opts.parallel_edges, seed, bd.opts.start_id, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_src.size(), "G(n, m) generation");
+    gs.result_src.resize(written); gs.result_dst.resize(written);
+    gs.computed = true;
+  }
+  GeneratorEmit(bd.opts, gs.result_src, gs.result_dst, gs.result_nodes, gs.output_idx, output);
 }
 
 // =============================================================================
@@ -56,9 +151,10 @@ static void ErdosRenyiFunction(ClientContext &ctx, TableFunctionInput &data, Dat
 
 struct BarabasiAlbertBindData : public TableFunctionData {
   int64_t n = 10; int64_t m = 2; int64_t seed = 42;
+  GeneratorOptions opts;
 };
 struct BarabasiAlbertGlobalState : public GlobalTableFunctionState {
-  std::vector<int64_t> result_src, result_dst;
+  std::vector<int64_t> result_src, result_dst, result_nodes;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -67,9 +163,7 @@ static unique_ptr<FunctionData> BarabasiAlbertBind(ClientContext &ctx, TableFunc
   auto bd = make_uniq<BarabasiAlbertBindData>();
   if (input.inputs.size() >= 1) bd->n = input.inputs[0].GetValue<int64_t>();
   if (input.inputs.size() >= 2) bd->m = input.inputs[1].GetValue<int64_t>();
-  for (auto &kv : input.named_parameters) if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
-  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
-  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  GeneratorBindOptions(input, bd->seed, bd->opts, rt, nm);
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> BarabasiAlbertInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<BarabasiAlbertGlobalState>(); }
@@ -78,16 +172,13 @@ static void BarabasiAlbertFunction(ClientContext &ctx, TableFunctionInput &data,
   if (!gs.computed) {
     int64_t ec = ::onager::onager_generate_barabasi_albert(static_cast<size_t>(bd.n), static_cast<size_t>(bd.m), static_cast<uint64_t>(bd.seed), nullptr, nullptr);
     if (ec < 0) throw InvalidInputException("Barabasi-Albert failed: " + GetOnagerError());
-    gs.result_src.resize(ec); gs.result_dst.resize(ec);
-    ::onager::onager_generate_barabasi_albert(static_cast<size_t>(bd.n), static_cast<size_t>(bd.m), static_cast<uint64_t>(bd.seed), gs.result_src.data(), gs.result_dst.data());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_nodes.resize(static_cast<size_t>(bd.n));
+    size_t written = CheckOnagerWrite(::onager::onager_generate_barabasi_albert_with_ids(static_cast<size_t>(bd.n), static_cast<size_t>(bd.m), static_cast<uint64_t>(bd.seed), bd.opts.start_id, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_src.size(), "Barabasi-Albert");
+    gs.result_src.resize(written); gs.result_dst.resize(written);
+    GeneratorSimplify(bd.opts, gs.result_src, gs.result_dst, "Barabasi-Albert");
     gs.computed = true;
   }
-  idx_t rem = gs.result_src.size() - gs.output_idx;
-  if (rem == 0) { output.SetCardinality(0); return; }
-  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
-  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
-  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; }
-  gs.output_idx += to; output.SetCardinality(to);
+  GeneratorEmit(bd.opts, gs.result_src, gs.result_dst, gs.result_nodes, gs.output_idx, output);
 }
 
 // =============================================================================
@@ -96,9 +187,10 @@ static void BarabasiAlbertFunction(ClientContext &ctx, TableFunctionInput &data,
 
 struct WattsStrogatzBindData : public TableFunctionData {
   int64_t n = 10; int64_t k = 4; double beta = 0.5; int64_t seed = 42;
+  GeneratorOptions opts;
 };
 struct WattsStrogatzGlobalState : public GlobalTableFunctionState {
-  std::vector<int64_t> result_src, result_dst;
+  std::vector<int64_t> result_src, result_dst, result_nodes;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
@@ -108,9 +200,7 @@ static unique_ptr<FunctionData> WattsStrogatzBind(ClientContext &ctx, TableFunct
   if (input.inputs.size() >= 1) bd->n = input.inputs[0].GetValue<int64_t>();
   if (input.inputs.size() >= 2) bd->k = input.inputs[1].GetValue<int64_t>();
   if (input.inputs.size() >= 3) bd->beta = input.inputs[2].GetValue<double>();
-  for (auto &kv : input.named_parameters) if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
-  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
-  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  GeneratorBindOptions(input, bd->seed, bd->opts, rt, nm);
   return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> WattsStrogatzInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<WattsStrogatzGlobalState>(); }
@@ -119,10 +209,130 @@ static void WattsStrogatzFunction(ClientContext &ctx, TableFunctionInput &data,
   if (!gs.computed) {
     int64_t ec = ::onager::onager_generate_watts_strogatz(static_cast<size_t>(bd.n), static_cast<size_t>(bd.k), bd.beta, static_cast<uint64_t>(bd.seed), nullptr, nullptr);
     if (ec < 0) throw InvalidInputException("Watts-Strogatz failed: " + GetOnagerError());
-    gs.result_src.resize(ec); gs.result_dst.resize(ec);
-    ::onager::onager_generate_watts_strogatz(static_cast<size_t>(bd.n), static_cast<size_t>(bd.k), bd.beta, static_cast<uint64_t>(bd.seed), gs.result_src.data(), gs.result_dst.data());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_nodes.resize(static_cast<size_t>(bd.n));
+    size_t written = CheckOnagerWrite(::onager::onager_generate_watts_strogatz_with_ids(static_cast<size_t>(bd.n), static_cast<size_t>(bd.k), bd.beta, static_cast<uint64_t>(bd.seed), bd.opts.start_id, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_src.size(), "Watts-Strogatz");
+    gs.result_src.resize(written); gs.result_dst.resize(written);
+    GeneratorSimplify(bd.opts, gs.result_src, gs.result_dst, "Watts-Strogatz");
     gs.computed = true;
   }
+  GeneratorEmit(bd.opts, gs.result_src, gs.result_dst, gs.result_nodes, gs.output_idx, output);
+}
+
+// =============================================================================
+// LFR Benchmark with Planted Communities
+// =============================================================================
+
+struct LfrBindData : public TableFunctionData {
+  int64_t n = 100; double mu = 0.2; double avg_degree = 10.0; int64_t seed = 42;
+  GeneratorOptions opts;
+};
+struct LfrGlobalState : public GlobalTableFunctionState {
+  std::vector<int64_t> result_src, result_dst, result_nodes, result_communities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<LfrBindData> LfrBindParams(TableFunctionBindInput &input, const std::string &name) {
+  auto bd = make_uniq<LfrBindData>();
+  if (input.inputs.size() >= 1) bd->n = input.inputs[0].GetValue<int64_t>();
+  if (input.inputs.size() >= 2) bd->mu = input.inputs[1].GetValue<double>();
+  if (input.inputs.size() >= 3) bd->avg_degree = input.inputs[2].GetValue<double>();
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+    else if (kv.first == "start_id") bd->opts.start_id = kv.second.GetValue<int64_t>();
+  }
+  if (bd->n <= 0) throw InvalidInputException(name + " requires n to be positive");
+  return bd;
+}
+static unique_ptr<FunctionData> LfrBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = LfrBindParams(input, "onager_gen_lfr");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> LfrCommunitiesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = LfrBindParams(input, "onager_gen_lfr_communities");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> LfrInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<LfrGlobalState>(); }
+static void LfrCompute(const LfrBindData &bd, LfrGlobalState &gs) {
+  size_t n = static_cast<size_t>(bd.n);
+  int64_t ec = ::onager::onager_generate_lfr(n, bd.mu, bd.avg_degree, static_cast<uint64_t>(bd.seed), nullptr, nullptr, 0, nullptr, nullptr, 0);
+  if (ec < 0) throw InvalidInputException("LFR generation failed: " + GetOnagerError());
+  gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_nodes.resize(n); gs.result_communities.resize(n);
+  size_t written = CheckOnagerWrite(::onager::onager_generate_lfr_with_ids(n, bd.mu, bd.avg_degree, static_cast<uint64_t>(bd.seed), bd.opts.start_id, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size(), gs.result_nodes.data(), gs.result_communities.data(), gs.result_nodes.size()), gs.result_src.size(), "LFR generation");
+  gs.result_src.resize(written); gs.result_dst.resize(written);
+  gs.computed = true;
+}
+static void LfrFunction(ClientContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<LfrBindData>(); auto &gs = data.global_state->Cast<LfrGlobalState>();
+  if (!gs.computed) LfrCompute(bd, gs);
+  GeneratorEmit(bd.opts, gs.result_src, gs.result_dst, gs.result_nodes, gs.output_idx, output);
+}
+static void LfrCommunitiesFunction(ClientContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<LfrBindData>(); auto &gs = data.global_state->Cast<LfrGlobalState>();
+  if (!gs.computed) LfrCompute(bd, gs);
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto nd = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { nd[i] = gs.result_nodes[gs.output_idx+i]; c[i] = gs.result_communities[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+}
+
+// =============================================================================
+// Random Geometric Graphs
+// =============================================================================
+
+struct GeometricBindData : public TableFunctionData {
+  int64_t n = 100; double radius = 0.1; int64_t seed = 42; int64_t start_id = 0;
+};
+struct GeometricGlobalState : public GlobalTableFunctionState {
+  std::vector<int64_t> result_src, result_dst, result_nodes;
+  std::vector<double> result_x, result_y;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<GeometricBindData> GeometricBindParams(TableFunctionBindInput &input, const std::string &name) {
+  auto bd = make_uniq<GeometricBindData>();
+  if (input.inputs.size() >= 1) bd->n = input.inputs[0].GetValue<int64_t>();
+  if (input.inputs.size() >= 2) bd->radius = input.inputs[1].GetValue<double>();
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+    else if (kv.first == "start_id") bd->start_id = kv.second.GetValue<int64_t>();
+  }
+  if (bd->n <= 0) throw InvalidInputException(name + " requires n to be positive");
+  return bd;
+}
+static unique_ptr<FunctionData> GeometricBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = GeometricBindParams(input, "onager_gen_random_geometric");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> GeometricNodesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = GeometricBindParams(input, "onager_gen_random_geometric_nodes");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("x");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("y");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> GeometricInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<GeometricGlobalState>(); }
+static void GeometricCompute(const GeometricBindData &bd, GeometricGlobalState &gs) {
+  size_t n = static_cast<size_t>(bd.n); uint64_t seed = static_cast<uint64_t>(bd.seed);
+  int64_t ec = ::onager::onager_generate_random_geometric(n, bd.radius, seed, bd.start_id, nullptr, nullptr, 0, nullptr, nullptr, nullptr, 0);
+  if (ec < 0) throw InvalidInputException("Random geometric graph failed: " + GetOnagerError());
+  gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_nodes.resize(n); gs.result_x.resize(n); gs.result_y.resize(n);
+  size_t written = CheckOnagerWrite(::onager::onager_generate_random_geometric(n, bd.radius, seed, bd.start_id, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size(), gs.result_nodes.data(), gs.result_x.data(), gs.result_y.data(), gs.result_nodes.size()), gs.result_src.size(), "Random geometric graph");
+  gs.result_src.resize(written); gs.result_dst.resize(written);
+  gs.computed = true;
+}
+static void GeometricFunction(ClientContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<GeometricBindData>(); auto &gs = data.global_state->Cast<GeometricGlobalState>();
+  if (!gs.computed) GeometricCompute(bd, gs);
   idx_t rem = gs.result_src.size() - gs.output_idx;
   if (rem == 0) { output.SetCardinality(0); return; }
   idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
@@ -130,6 +340,137 @@ static void WattsStrogatzFunction(ClientContext &ctx, TableFunctionInput &data,
   for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; }
   gs.output_idx += to; output.SetCardinality(to);
 }
+static void GeometricNodesFunction(ClientContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<GeometricBindData>(); auto &gs = data.global_state->Cast<GeometricGlobalState>();
+  if (!gs.computed) GeometricCompute(bd, gs);
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto nd = GetFlatVectorDataWritable<int64_t>(output.data[0]);
+  auto x = GetFlatVectorDataWritable<double>(output.data[1]); auto y = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { nd[i] = gs.result_nodes[gs.output_idx+i]; x[i] = gs.result_x[gs.output_idx+i]; y[i] = gs.result_y[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+}
+
+// =============================================================================
+// K-Nearest-Neighbor Graphs
+// =============================================================================
+
+struct KnnBindData : public TableFunctionData { int64_t k = 5; };
+struct KnnGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> node_ids; std::vector<double> xs, ys;
+  std::vector<int64_t> result_src, result_dst; std::vector<double> result_distances;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> KnnBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<KnnBindData>();
+  auto &types = input.input_table_types;
+  if (types.size() < 3 || types[0] != LogicalType::BIGINT || types[1] != LogicalType::DOUBLE || types[2] != LogicalType::DOUBLE) {
+    throw InvalidInputException("onager_gen_knn requires (node_id BIGINT, x DOUBLE, y DOUBLE) columns. Please cast inputs (e.g. x::double)");
+  }
+  for (auto &kv : input.named_parameters) if (kv.first == "k") bd->k = kv.second.GetValue<int64_t>();
+  if (bd->k <= 0) throw InvalidInputException("onager_gen_knn requires k to be positive");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> KnnInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<KnnGlobalState>(); }
+static OperatorResultType KnnInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<KnnGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto ids = FlatVector::GetData<int64_t>(input.data[0]);
+  auto x = FlatVector::GetData<double>(input.data[1]); auto y = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.node_ids.push_back(ids[i]); gs.xs.push_back(x[i]); gs.ys.push_back(y[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType KnnFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<KnnBindData>(); auto &gs = data.global_state->Cast<KnnGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.node_ids.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    size_t k = static_cast<size_t>(bd.k);
+    int64_t ec = ::onager::onager_build_knn_graph(gs.node_ids.data(), gs.xs.data(), gs.ys.data(), gs.node_ids.size(), k, nullptr, nullptr, nullptr, 0);
+    if (ec < 0) throw InvalidInputException("KNN graph failed: " + GetOnagerError());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_distances.resize(ec);
+    size_t written = CheckOnagerWrite(::onager::onager_build_knn_graph(gs.node_ids.data(), gs.xs.data(), gs.ys.data(), gs.node_ids.size(), k, gs.result_src.data(), gs.result_dst.data(), gs.result_distances.data(), gs.result_src.size()), gs.result_src.size(), "KNN graph");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_distances.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_distances[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Similarity Graphs
+// =============================================================================
+
+struct SimilarityBindData : public TableFunctionData {
+  double threshold = -std::numeric_limits<double>::infinity(); int64_t top_k = 0;
+};
+struct SimilarityGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes; std::vector<double> similarities;
+  std::vector<int64_t> result_src, result_dst; std::vector<double> result_similarities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> SimilarityBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<SimilarityBindData>();
+  CheckInt64Input(input, "onager_gen_similarity", 3);
+  if (input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Similarity column must be DOUBLE. Please cast it (e.g. similarity::double)");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "threshold") bd->threshold = kv.second.GetValue<double>();
+    else if (kv.first == "top_k") bd->top_k = kv.second.GetValue<int64_t>();
+  }
+  if (bd->top_k < 0) throw InvalidInputException("onager_gen_similarity requires top_k to be 0 or more");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("similarity");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> SimilarityInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<SimilarityGlobalState>(); }
+static OperatorResultType SimilarityInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<SimilarityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.similarities.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType SimilarityFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SimilarityBindData>(); auto &gs = data.global_state->Cast<SimilarityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    size_t top_k = static_cast<size_t>(bd.top_k);
+    int64_t ec = ::onager::onager_build_similarity_graph_pairs(gs.src_nodes.data(), gs.dst_nodes.data(), gs.similarities.data(), gs.src_nodes.size(), bd.threshold, top_k, nullptr, nullptr, nullptr, 0);
+    if (ec < 0) throw InvalidInputException("Similarity graph failed: " + GetOnagerError());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_similarities.resize(ec);
+    size_t written = CheckOnagerWrite(::onager::onager_build_similarity_graph_pairs(gs.src_nodes.data(), gs.dst_nodes.data(), gs.similarities.data(), gs.src_nodes.size(), bd.threshold, top_k,
+        gs.result_src.data(), gs.result_dst.data(), gs.result_similarities.data(), gs.result_src.size()), gs.result_src.size(), "Similarity graph");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_similarities.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_similarities[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
 
 // =============================================================================
 // Registration
@@ -140,18 +481,79 @@ namespace onager {
 void RegisterGeneratorFunctions(ExtensionLoader &loader) {
   TableFunction erdos_renyi("onager_gen_erdos_renyi", {LogicalType::BIGINT, LogicalType::DOUBLE}, ErdosRenyiFunction, ErdosRenyiBind, ErdosRenyiInitGlobal);
   erdos_renyi.named_parameters["seed"] = LogicalType::BIGINT;
+  erdos_renyi.named_parameters["start_id"] = LogicalType::BIGINT;
+  erdos_renyi.named_parameters["node_list"] = LogicalType::BOOLEAN;
+  erdos_renyi.named_parameters["directed"] = LogicalType::BOOLEAN;
+  erdos_renyi.named_parameters["self_loops"] = LogicalType::BOOLEAN;
   ONAGER_SET_NO_ORDER(erdos_renyi);
   loader.RegisterFunction(erdos_renyi);
 
+  TableFunction gnm("onager_gen_gnm", {LogicalType::BIGINT, LogicalType::BIGINT}, GnmFunction, GnmBind, GnmInitGlobal);
+  gnm.named_parameters["seed"] = LogicalType::BIGINT;
+  gnm.named_parameters["start_id"] = LogicalType::BIGINT;
+  gnm.named_parameters["node_list"] = LogicalType::BOOLEAN;
+  gnm.named_parameters["directed"] = LogicalType::BOOLEAN;
+  gnm.named_parameters["self_loops"] = LogicalType::BOOLEAN;
+  gnm.named_parameters["parallel_edges"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(gnm);
+  loader.RegisterFunction(gnm);
+
   TableFunction barabasi_albert("onager_gen_barabasi_albert", {LogicalType::BIGINT, LogicalType::BIGINT}, BarabasiAlbertFunction, BarabasiAlbertBind, BarabasiAlbertInitGlobal);
   barabasi_albert.named_parameters["seed"] = LogicalType::BIGINT;
+  barabasi_albert.named_parameters["start_id"] = LogicalType::BIGINT;
+  barabasi_albert.named_parameters["node_list"] = LogicalType::BOOLEAN;
+  barabasi_albert.named_parameters["self_loops"] = LogicalType::BOOLEAN;
+  barabasi_albert.named_parameters["parallel_edges"] = LogicalType::BOOLEAN;
   ONAGER_SET_NO_ORDER(barabasi_albert);
   loader.RegisterFunction(barabasi_albert);
 
   TableFunction watts_strogatz("onager_gen_watts_strogatz", {LogicalType::BIGINT, LogicalType::BIGINT, LogicalType::DOUBLE}, WattsStrogatzFunction, WattsStrogatzBind, WattsStrogatzInitGlobal);
   watts_strogatz.named_parameters["seed"] = LogicalType::BIGINT;
+  watts_strogatz.named_parameters["start_id"] = LogicalType::BIGINT;
+  watts_strogatz.named_parameters["node_list"] = LogicalType::BOOLEAN;
+  watts_strogatz.named_parameters["self_loops"] = LogicalType::BOOLEAN;
+  watts_strogatz.named_parameters["parallel_edges"] = LogicalType::BOOLEAN;
   ONAGER_SET_NO_ORDER(watts_strogatz);
   loader.RegisterFunction(watts_strogatz);
+
+  TableFunction lfr("onager_gen_lfr", {LogicalType::BIGINT, LogicalType::DOUBLE, LogicalType::DOUBLE}, LfrFunction, LfrBind, LfrInitGlobal);
+  lfr.named_parameters["seed"] = LogicalType::BIGINT;
+  lfr.named_parameters["start_id"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(lfr);
+  loader.RegisterFunction(lfr);
+
+  TableFunction lfr_communities("onager_gen_lfr_communities", {LogicalType::BIGINT, LogicalType::DOUBLE, LogicalType::DOUBLE}, LfrCommunitiesFunction, LfrCommunitiesBind, LfrInitGlobal);
+  lfr_communities.named_parameters["seed"] = LogicalType::BIGINT;
+  lfr_communities.named_parameters["start_id"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(lfr_communities);
+  loader.RegisterFunction(lfr_communities);
+
+  TableFunction geometric("onager_gen_random_geometric", {LogicalType::BIGINT, LogicalType::DOUBLE}, GeometricFunction, GeometricBind, GeometricInitGlobal);
+  geometric.named_parameters["seed"] = LogicalType::BIGINT;
+  geometric.named_parameters["start_id"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(geometric);
+  loader.RegisterFunction(geometric);
+
+  TableFunction geometric_nodes("onager_gen_random_geometric_nodes", {LogicalType::BIGINT, LogicalType::DOUBLE}, GeometricNodesFunction, GeometricNodesBind, GeometricInitGlobal);
+  geometric_nodes.named_parameters["seed"] = LogicalType::BIGINT;
+  geometric_nodes.named_parameters["start_id"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(geometric_nodes);
+  loader.RegisterFunction(geometric_nodes);
+
+  TableFunction knn("onager_gen_knn", {LogicalType::TABLE}, nullptr, KnnBind, KnnInitGlobal);
+  knn.in_out_function = KnnInOut;
+  knn.in_out_function_final = KnnFinal;
+  knn.named_parameters["k"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(knn);
+  loader.RegisterFunction(knn);
+
+  TableFunction similarity("onager_gen_similarity", {LogicalType::TABLE}, nullptr, SimilarityBind, SimilarityInitGlobal);
+  similarity.in_out_function = SimilarityInOut;
+  similarity.in_out_function_final = SimilarityFinal;
+  similarity.named_parameters["threshold"] = LogicalType::DOUBLE;
+  similarity.named_parameters["top_k"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(similarity);
+  loader.RegisterFunction(similarity);
 }
 
 } // namespace onager
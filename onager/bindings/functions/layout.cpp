@@ -0,0 +1,228 @@
+/**
+ * @file layout.cpp
+ * @brief Graph layout table functions for Onager DuckDB extension.
+ *
+ * Two-dimensional node coordinates from force-directed, spectral, circular,
+ * and shell layouts of (src, dst [, weight]) edge tables, and community
+ * layouts with node and community coordinates.
+ */
+#include "functions.hpp"
+#include <mutex>
+#include <random>
+
+namespace duckdb {
+
+using namespace onager;
+
+// =============================================================================
+// Node Layout
+// =============================================================================
+
+struct LayoutBindData : public TableFunctionData { std::string method = "force"; int64_t iterations = 50; int64_t seed = -1; bool weighted = false; };
+struct LayoutGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  std::vector<double> weights, result_x, result_y;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> LayoutBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<LayoutBindData>();
+  CheckInt64Input(input, "onager_lay_layout");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "method") bd->method = StringUtil::Lower(kv.second.GetValue<string>());
+    else if (kv.first == "iterations") bd->iterations = kv.second.GetValue<int64_t>();
+    else if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  }
+  if (bd->method != "force" && bd->method != "spectral" && bd->method != "circular" && bd->method != "shell")
+    throw InvalidInputException("onager_lay_layout method must be 'force', 'spectral', 'circular', or 'shell'");
+  if (bd->iterations <= 0) throw InvalidInputException("onager_lay_layout requires iterations to be positive");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("x");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("y");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> LayoutInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<LayoutGlobalState>(); }
+static OperatorResultType LayoutInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<LayoutBindData>(); auto &gs = data.global_state->Cast<LayoutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType LayoutFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<LayoutBindData>(); auto &gs = data.global_state->Cast<LayoutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_layout(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.method.c_str(), bd.iterations, bd.seed,
+        nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Graph layout failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_x.resize(nc); gs.result_y.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_layout(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.method.c_str(), bd.iterations, bd.seed,
+        gs.result_nodes.data(), gs.result_x.data(), gs.result_y.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Graph layout");
+    gs.result_nodes.resize(written); gs.result_x.resize(written); gs.result_y.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto x = GetFlatVectorDataWritable<double>(output.data[1]);
+  auto y = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { idx_t k = gs.output_idx + i; n[i] = gs.result_nodes[k]; x[i] = gs.result_x[k]; y[i] = gs.result_y[k]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Community Layout
+// =============================================================================
+
+struct CommunityLayoutBindData : public TableFunctionData { int64_t iterations = 50; int64_t seed = -1; bool weighted = false; };
+struct CommunityLayoutGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_communities, result_community_ids, result_sizes;
+  std::vector<double> weights, result_x, result_y, result_community_x, result_community_y;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<CommunityLayoutBindData> CommunityLayoutBindParams(TableFunctionBindInput &input, const std::string &name) {
+  auto bd = make_uniq<CommunityLayoutBindData>();
+  CheckInt64Input(input, name);
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "iterations") bd->iterations = kv.second.GetValue<int64_t>();
+    else if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  }
+  if (bd->iterations <= 0) throw InvalidInputException(name + " requires iterations to be positive");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  return bd;
+}
+static unique_ptr<FunctionData> CommunityLayoutBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = CommunityLayoutBindParams(input, "onager_lay_community_layout");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("x");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("y");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> CommunityCentersBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = CommunityLayoutBindParams(input, "onager_lay_community_centers");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("community");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("size");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("x");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("y");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> CommunityLayoutInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<CommunityLayoutGlobalState>(); }
+static OperatorResultType CommunityLayoutInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CommunityLayoutBindData>(); auto &gs = data.global_state->Cast<CommunityLayoutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static void CommunityLayoutCompute(const CommunityLayoutBindData &bd, CommunityLayoutGlobalState &gs) {
+  // Both calls must lay out the same way, so a random seed is drawn once here
+  int64_t seed = bd.seed >= 0 ? bd.seed : static_cast<int64_t>(std::random_device{}() >> 1);
+  int64_t community_count = 0;
+  int64_t nc = ::onager::onager_compute_community_layout(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.iterations, seed,
+      nullptr, nullptr, nullptr, nullptr, 0, nullptr, nullptr, nullptr, nullptr, 0, &community_count);
+  if (nc < 0) throw InvalidInputException("Community layout failed: " + GetOnagerError());
+  gs.result_nodes.resize(nc); gs.result_communities.resize(nc); gs.result_x.resize(nc); gs.result_y.resize(nc);
+  gs.result_community_ids.resize(community_count); gs.result_sizes.resize(community_count);
+  gs.result_community_x.resize(community_count); gs.result_community_y.resize(community_count);
+  size_t written = CheckOnagerWrite(::onager::onager_compute_community_layout(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.iterations, seed,
+      gs.result_nodes.data(), gs.result_communities.data(), gs.result_x.data(), gs.result_y.data(), gs.result_nodes.size(),
+      gs.result_community_ids.data(), gs.result_sizes.data(), gs.result_community_x.data(), gs.result_community_y.data(), gs.result_community_ids.size(), &community_count),
+      gs.result_nodes.size(), "Community layout");
+  gs.result_nodes.resize(written); gs.result_communities.resize(written); gs.result_x.resize(written); gs.result_y.resize(written);
+}
+static OperatorFinalizeResultType CommunityLayoutFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CommunityLayoutBindData>(); auto &gs = data.global_state->Cast<CommunityLayoutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    CommunityLayoutCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto x = GetFlatVectorDataWritable<double>(output.data[2]); auto y = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    n[i] = gs.result_nodes[k]; c[i] = gs.result_communities[k]; x[i] = gs.result_x[k]; y[i] = gs.result_y[k];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+static OperatorFinalizeResultType CommunityCentersFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CommunityLayoutBindData>(); auto &gs = data.global_state->Cast<CommunityLayoutGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    CommunityLayoutCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_community_ids.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto c = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto s = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto x = GetFlatVectorDataWritable<double>(output.data[2]); auto y = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    c[i] = gs.result_community_ids[k]; s[i] = gs.result_sizes[k]; x[i] = gs.result_community_x[k]; y[i] = gs.result_community_y[k];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_community_ids.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterLayoutFunctions(ExtensionLoader &loader) {
+  TableFunction layout("onager_lay_layout", {LogicalType::TABLE}, nullptr, LayoutBind, LayoutInitGlobal);
+  layout.in_out_function = LayoutInOut;
+  layout.in_out_function_final = LayoutFinal;
+  layout.named_parameters["method"] = LogicalType::VARCHAR;
+  layout.named_parameters["iterations"] = LogicalType::BIGINT;
+  layout.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(layout);
+  loader.RegisterFunction(layout);
+
+  TableFunction community_layout("onager_lay_community_layout", {LogicalType::TABLE}, nullptr, CommunityLayoutBind, CommunityLayoutInitGlobal);
+  community_layout.in_out_function = CommunityLayoutInOut;
+  community_layout.in_out_function_final = CommunityLayoutFinal;
+  community_layout.named_parameters["iterations"] = LogicalType::BIGINT;
+  community_layout.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(community_layout);
+  loader.RegisterFunction(community_layout);
+
+  TableFunction community_centers("onager_lay_community_centers", {LogicalType::TABLE}, nullptr, CommunityCentersBind, CommunityLayoutInitGlobal);
+  community_centers.in_out_function = CommunityLayoutInOut;
+  community_centers.in_out_function_final = CommunityCentersFinal;
+  community_centers.named_parameters["iterations"] = LogicalType::BIGINT;
+  community_centers.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(community_centers);
+  loader.RegisterFunction(community_centers);
+}
+
+} // namespace onager
+} // namespace duckdb
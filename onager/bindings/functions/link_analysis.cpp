@@ -0,0 +1,170 @@
+/**
+ * @file link_analysis.cpp
+ * @brief Link analysis table functions for Onager DuckDB extension.
+ *
+ * SALSA and co-HITS hub and authority scores.
+ */
+#include "functions.hpp"
+#include <mutex>
+
+namespace duckdb {
+
+using namespace onager;
+
+struct HubAuthorityGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  std::vector<double> weights, result_hubs, result_authorities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<GlobalTableFunctionState> HubAuthorityInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<HubAuthorityGlobalState>(); }
+
+static OperatorFinalizeResultType HubAuthorityEmit(HubAuthorityGlobalState &gs, DataChunk &output) {
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]);
+  auto h = GetFlatVectorDataWritable<double>(output.data[1]);
+  auto a = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) {
+    n[i] = gs.result_nodes[gs.output_idx+i];
+    h[i] = gs.result_hubs[gs.output_idx+i];
+    a[i] = gs.result_authorities[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+static void HubAuthorityReturnTypes(vector<LogicalType> &rt, vector<string> &nm) {
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("hub");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("authority");
+}
+
+// =============================================================================
+// SALSA
+// =============================================================================
+
+struct SalsaBindData : public TableFunctionData { int64_t max_iter = 100; double tolerance = 1e-6; ScoreSelection selection; };
+
+static unique_ptr<FunctionData> SalsaBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<SalsaBindData>();
+  CheckInt64Input(input, "onager_ctr_salsa");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "max_iter") bd->max_iter = kv.second.GetValue<int64_t>();
+    else if (kv.first == "tolerance") bd->tolerance = kv.second.GetValue<double>();
+  }
+  BindScoreSelection(input, bd->selection, {"hub", "authority"}, "onager_ctr_salsa");
+  HubAuthorityReturnTypes(rt, nm);
+  return std::move(bd);
+}
+static OperatorResultType SalsaInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<HubAuthorityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType SalsaFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SalsaBindData>(); auto &gs = data.global_state->Cast<HubAuthorityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_salsa(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.tolerance, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("SALSA failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_hubs.resize(nc); gs.result_authorities.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_salsa(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_hubs.data(), gs.result_authorities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "SALSA");
+    gs.result_nodes.resize(written); gs.result_hubs.resize(written); gs.result_authorities.resize(written);
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_hubs, &gs.result_authorities}, "SALSA");
+    gs.computed = true;
+  }
+  return HubAuthorityEmit(gs, output);
+}
+
+// =============================================================================
+// co-HITS
+// =============================================================================
+
+struct CoHitsBindData : public TableFunctionData {
+  double lambda_u = 0.8;
+  double lambda_v = 0.8;
+  int64_t max_iter = 100;
+  double tolerance = 1e-6;
+  bool weighted = false;
+  ScoreSelection selection;
+};
+
+static unique_ptr<FunctionData> CoHitsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<CoHitsBindData>();
+  CheckInt64Input(input, "onager_ctr_cohits");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "lambda_u") bd->lambda_u = kv.second.GetValue<double>();
+    else if (kv.first == "lambda_v") bd->lambda_v = kv.second.GetValue<double>();
+    else if (kv.first == "max_iter") bd->max_iter = kv.second.GetValue<int64_t>();
+    else if (kv.first == "tolerance") bd->tolerance = kv.second.GetValue<double>();
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindScoreSelection(input, bd->selection, {"hub", "authority"}, "onager_ctr_cohits");
+  HubAuthorityReturnTypes(rt, nm);
+  return std::move(bd);
+}
+static OperatorResultType CoHitsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CoHitsBindData>(); auto &gs = data.global_state->Cast<HubAuthorityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType CoHitsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CoHitsBindData>(); auto &gs = data.global_state->Cast<HubAuthorityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_cohits(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.lambda_u, bd.lambda_v, bd.max_iter, bd.tolerance, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("co-HITS failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_hubs.resize(nc); gs.result_authorities.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_cohits(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.lambda_u, bd.lambda_v, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_hubs.data(), gs.result_authorities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "co-HITS");
+    gs.result_nodes.resize(written); gs.result_hubs.resize(written); gs.result_authorities.resize(written);
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_hubs, &gs.result_authorities}, "co-HITS");
+    gs.computed = true;
+  }
+  return HubAuthorityEmit(gs, output);
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterLinkAnalysisFunctions(ExtensionLoader &loader) {
+  TableFunction salsa("onager_ctr_salsa", {LogicalType::TABLE}, nullptr, SalsaBind, HubAuthorityInitGlobal);
+  salsa.in_out_function = SalsaInOut;
+  salsa.in_out_function_final = SalsaFinal;
+  salsa.named_parameters["max_iter"] = LogicalType::BIGINT;
+  salsa.named_parameters["tolerance"] = LogicalType::DOUBLE;
+  AddScoreSelectionParameters(salsa);
+  ONAGER_SET_NO_ORDER(salsa);
+  loader.RegisterFunction(salsa);
+
+  TableFunction cohits("onager_ctr_cohits", {LogicalType::TABLE}, nullptr, CoHitsBind, HubAuthorityInitGlobal);
+  cohits.in_out_function = CoHitsInOut;
+  cohits.in_out_function_final = CoHitsFinal;
+  cohits.named_parameters["lambda_u"] = LogicalType::DOUBLE;
+  cohits.named_parameters["lambda_v"] = LogicalType::DOUBLE;
+  cohits.named_parameters["max_iter"] = LogicalType::BIGINT;
+  cohits.named_parameters["tolerance"] = LogicalType::DOUBLE;
+  AddScoreSelectionParameters(cohits);
+  ONAGER_SET_NO_ORDER(cohits);
+  loader.RegisterFunction(cohits);
+}
+
+} // namespace onager
+} // namespace duckdb
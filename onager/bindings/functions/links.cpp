@@ -2,10 +2,12 @@
  * @file links.cpp
  * @brief Link prediction table functions for Onager DuckDB extension.
  *
- * Jaccard, Adamic-Adar, Preferential Attachment, Resource Allocation, Common Neighbors.
+ * Jaccard, Adamic-Adar, Preferential Attachment, Resource Allocation, Common Neighbors,
+ * spilling all-pairs scores to disk, and negative edge sampling.
  */
 #include "functions.hpp"
 #include <mutex>
+#include <random>
 
 namespace duckdb {
 
@@ -46,7 +48,8 @@ static OperatorFinalizeResultType JaccardFinal(ExecutionContext &ctx, TableFunct
     int64_t nc = ::onager::onager_compute_jaccard(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Jaccard failed: " + GetOnagerError());
     gs.result_n1.resize(nc); gs.result_n2.resize(nc); gs.result_scores.resize(nc);
-    ::onager::onager_compute_jaccard(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_jaccard_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data(), gs.result_n1.size()), gs.result_n1.size(), "Jaccard");
+    gs.result_n1.resize(written); gs.result_n2.resize(written); gs.result_scores.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_n1.size() - gs.output_idx;
@@ -93,7 +96,8 @@ static OperatorFinalizeResultType AdamicAdarFinal(ExecutionContext &ctx, TableFu
     int64_t nc = ::onager::onager_compute_adamic_adar(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Adamic-Adar failed: " + GetOnagerError());
     gs.result_n1.resize(nc); gs.result_n2.resize(nc); gs.result_scores.resize(nc);
-    ::onager::onager_compute_adamic_adar(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_adamic_adar_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data(), gs.result_n1.size()), gs.result_n1.size(), "Adamic-Adar");
+    gs.result_n1.resize(written); gs.result_n2.resize(written); gs.result_scores.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_n1.size() - gs.output_idx;
@@ -140,7 +144,8 @@ static OperatorFinalizeResultType PrefAttachFinal(ExecutionContext &ctx, TableFu
     int64_t nc = ::onager::onager_compute_preferential_attachment(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Preferential Attachment failed: " + GetOnagerError());
     gs.result_n1.resize(nc); gs.result_n2.resize(nc); gs.result_scores.resize(nc);
-    ::onager::onager_compute_preferential_attachment(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_preferential_attachment_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data(), gs.result_n1.size()), gs.result_n1.size(), "Preferential Attachment");
+    gs.result_n1.resize(written); gs.result_n2.resize(written); gs.result_scores.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_n1.size() - gs.output_idx;
@@ -187,7 +192,8 @@ static OperatorFinalizeResultType ResourceAllocFinal(ExecutionContext &ctx, Tabl
     int64_t nc = ::onager::onager_compute_resource_allocation(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Resource Allocation failed: " + GetOnagerError());
     gs.result_n1.resize(nc); gs.result_n2.resize(nc); gs.result_scores.resize(nc);
-    ::onager::onager_compute_resource_allocation(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_resource_allocation_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_scores.data(), gs.result_n1.size()), gs.result_n1.size(), "Resource Allocation");
+    gs.result_n1.resize(written); gs.result_n2.resize(written); gs.result_scores.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_n1.size() - gs.output_idx;
@@ -233,7 +239,8 @@ static OperatorFinalizeResultType CommonNeighborsFinal(ExecutionContext &ctx, Ta
     int64_t nc = ::onager::onager_compute_common_neighbors(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("CommonNeighbors failed: " + GetOnagerError());
     gs.result_n1.resize(nc); gs.result_n2.resize(nc); gs.result_counts.resize(nc);
-    ::onager::onager_compute_common_neighbors(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_counts.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_common_neighbors_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_n1.data(), gs.result_n2.data(), gs.result_counts.data(), gs.result_n1.size()), gs.result_n1.size(), "CommonNeighbors");
+    gs.result_n1.resize(written); gs.result_n2.resize(written); gs.result_counts.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_n1.size() - gs.output_idx;
@@ -245,6 +252,119 @@ static OperatorFinalizeResultType CommonNeighborsFinal(ExecutionContext &ctx, Ta
   return gs.output_idx >= gs.result_n1.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Spill Link Scores
+// =============================================================================
+
+struct SpillScoresBindData : public TableFunctionData { std::string score = "jaccard"; std::string path; };
+struct SpillScoresGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  std::string path; int64_t row_count = 0; bool computed = false, emitted = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> SpillScoresBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<SpillScoresBindData>();
+  CheckInt64Input(input, "onager_lnk_spill_scores");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "score") bd->score = StringUtil::Lower(kv.second.GetValue<string>());
+    else if (kv.first == "path") bd->path = kv.second.GetValue<string>();
+  }
+  CheckSpillPath(ctx, bd->path, "onager_lnk_spill_scores");
+  if (bd->score != "jaccard" && bd->score != "adamic_adar" && bd->score != "resource_alloc" && bd->score != "common_neighbors" && bd->score != "pref_attach") {
+    throw InvalidInputException("onager_lnk_spill_scores score must be 'jaccard', 'adamic_adar', 'resource_alloc', 'common_neighbors', or 'pref_attach'");
+  }
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("path");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("row_count");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> SpillScoresInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<SpillScoresGlobalState>(); }
+static OperatorResultType SpillScoresInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<SpillScoresGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType SpillScoresFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SpillScoresBindData>(); auto &gs = data.global_state->Cast<SpillScoresGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; gs.emitted = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    gs.path = RunSpill(ctx.client, bd.path, "Spill link scores", gs.row_count, [&](::onager::OnagerWriteFn write, void *out) {
+      return ::onager::onager_spill_link_scores(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.score.c_str(), write, out);
+    });
+    gs.computed = true;
+  }
+  if (gs.emitted) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<string_t>(output.data[0])[0] = StringVector::AddString(output.data[0], gs.path);
+  GetFlatVectorDataWritable<int64_t>(output.data[1])[0] = gs.row_count;
+  gs.emitted = true; output.SetCardinality(1);
+  return OperatorFinalizeResultType::FINISHED;
+}
+
+// =============================================================================
+// Negative Edge Sampling
+// =============================================================================
+
+struct NegativeEdgesBindData : public TableFunctionData { int64_t n_samples = -1; std::string strategy = "uniform"; int64_t seed = -1; };
+struct NegativeEdgesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_n1, result_n2;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> NegativeEdgesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<NegativeEdgesBindData>();
+  CheckInt64Input(input, "onager_lnk_negative_edges");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "n_samples") bd->n_samples = kv.second.GetValue<int64_t>();
+    else if (kv.first == "strategy") bd->strategy = StringUtil::Lower(kv.second.GetValue<string>());
+    else if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  }
+  if (input.named_parameters.count("n_samples") && bd->n_samples < 0) throw InvalidInputException("onager_lnk_negative_edges n_samples must be non-negative");
+  if (bd->strategy != "uniform" && bd->strategy != "degree" && bd->strategy != "hard") {
+    throw InvalidInputException("onager_lnk_negative_edges strategy must be 'uniform', 'degree', or 'hard'");
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node1");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node2");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> NegativeEdgesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<NegativeEdgesGlobalState>(); }
+static OperatorResultType NegativeEdgesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<NegativeEdgesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType NegativeEdgesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<NegativeEdgesBindData>(); auto &gs = data.global_state->Cast<NegativeEdgesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    // One negative pair per input edge unless told otherwise
+    size_t n_samples = bd.n_samples >= 0 ? static_cast<size_t>(bd.n_samples) : gs.src_nodes.size();
+    // Both calls must draw the same pairs, so a random seed is drawn once here
+    int64_t seed = bd.seed >= 0 ? bd.seed : static_cast<int64_t>(std::random_device{}() >> 1);
+    int64_t nc = ::onager::onager_sample_negative_edges(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), n_samples, bd.strategy.c_str(), seed, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Negative edge sampling failed: " + GetOnagerError());
+    gs.result_n1.resize(nc); gs.result_n2.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_sample_negative_edges(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), n_samples, bd.strategy.c_str(), seed, gs.result_n1.data(), gs.result_n2.data(), gs.result_n1.size()), gs.result_n1.size(), "Negative edge sampling");
+    gs.result_n1.resize(written); gs.result_n2.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_n1.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n1 = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto n2 = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n1[i] = gs.result_n1[gs.output_idx+i]; n2[i] = gs.result_n2[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_n1.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -281,6 +401,23 @@ void RegisterLinkFunctions(ExtensionLoader &loader) {
   common_neighbors.in_out_function_final = CommonNeighborsFinal;
   ONAGER_SET_NO_ORDER(common_neighbors);
   loader.RegisterFunction(common_neighbors);
+
+  TableFunction spill_scores("onager_lnk_spill_scores", {LogicalType::TABLE}, nullptr, SpillScoresBind, SpillScoresInitGlobal);
+  spill_scores.in_out_function = SpillScoresInOut;
+  spill_scores.in_out_function_final = SpillScoresFinal;
+  spill_scores.named_parameters["score"] = LogicalType::VARCHAR;
+  spill_scores.named_parameters["path"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(spill_scores);
+  loader.RegisterFunction(spill_scores);
+
+  TableFunction negative_edges("onager_lnk_negative_edges", {LogicalType::TABLE}, nullptr, NegativeEdgesBind, NegativeEdgesInitGlobal);
+  negative_edges.in_out_function = NegativeEdgesInOut;
+  negative_edges.in_out_function_final = NegativeEdgesFinal;
+  negative_edges.named_parameters["n_samples"] = LogicalType::BIGINT;
+  negative_edges.named_parameters["strategy"] = LogicalType::VARCHAR;
+  negative_edges.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(negative_edges);
+  loader.RegisterFunction(negative_edges);
 }
 
 } // namespace onager
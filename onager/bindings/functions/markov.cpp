@@ -0,0 +1,192 @@
+/**
+ * @file markov.cpp
+ * @brief Markov chain table functions for Onager DuckDB extension.
+ *
+ * Stationary distribution, hitting times, and removal-effect attribution of
+ * the random walk on (src, dst [, weight]) edge tables.
+ */
+#include "functions.hpp"
+#include <mutex>
+
+namespace duckdb {
+
+using namespace onager;
+
+// Validates a (src, dst [, weight]) input table and returns whether it has weights
+static bool CheckMarkovInput(TableFunctionBindInput &input, const string &name) {
+  CheckInt64Input(input, name);
+  bool weighted = input.input_table_types.size() >= 3;
+  if (weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  return weighted;
+}
+
+struct MarkovBindData : public TableFunctionData { bool weighted = false; int64_t target = 0; };
+struct MarkovGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  std::vector<double> weights, result_values;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<GlobalTableFunctionState> MarkovInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<MarkovGlobalState>(); }
+static OperatorResultType MarkovInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<MarkovBindData>(); auto &gs = data.global_state->Cast<MarkovGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+
+// Writes the next chunk of (node_id, value) rows
+static OperatorFinalizeResultType MarkovOutput(MarkovGlobalState &gs, DataChunk &output) {
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto v = GetFlatVectorDataWritable<double>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx + i]; v[i] = gs.result_values[gs.output_idx + i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Stationary Distribution
+// =============================================================================
+
+static unique_ptr<FunctionData> StationaryBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<MarkovBindData>();
+  bd->weighted = CheckMarkovInput(input, "onager_mkv_stationary");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("probability");
+  return std::move(bd);
+}
+static OperatorFinalizeResultType StationaryFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<MarkovGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_stationary_distribution(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Stationary distribution failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_values.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_stationary_distribution(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(),
+        gs.result_nodes.data(), gs.result_values.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Stationary distribution");
+    gs.result_nodes.resize(written); gs.result_values.resize(written);
+    gs.computed = true;
+  }
+  return MarkovOutput(gs, output);
+}
+
+// =============================================================================
+// Hitting Times
+// =============================================================================
+
+static unique_ptr<FunctionData> HittingTimesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<MarkovBindData>();
+  bd->weighted = CheckMarkovInput(input, "onager_mkv_hitting_times");
+  auto target = input.named_parameters.find("target");
+  if (target == input.named_parameters.end() || target->second.IsNull()) throw InvalidInputException("onager_mkv_hitting_times requires target := ...");
+  bd->target = target->second.GetValue<int64_t>();
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("hitting_time");
+  return std::move(bd);
+}
+static OperatorFinalizeResultType HittingTimesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<MarkovBindData>(); auto &gs = data.global_state->Cast<MarkovGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_hitting_times(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.target, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Hitting times failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_values.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_hitting_times(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.target,
+        gs.result_nodes.data(), gs.result_values.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Hitting times");
+    gs.result_nodes.resize(written); gs.result_values.resize(written);
+    gs.computed = true;
+  }
+  return MarkovOutput(gs, output);
+}
+
+// =============================================================================
+// Markov Attribution
+// =============================================================================
+
+struct AttributionBindData : public MarkovBindData { int64_t start = 0; int64_t conversion = 0; std::vector<int64_t> null_states; };
+struct AttributionGlobalState : public MarkovGlobalState { std::vector<double> result_attributions; };
+
+static unique_ptr<FunctionData> AttributionBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<AttributionBindData>();
+  bd->weighted = CheckMarkovInput(input, "onager_mkv_attribution");
+  auto start = input.named_parameters.find("start");
+  if (start == input.named_parameters.end() || start->second.IsNull()) throw InvalidInputException("onager_mkv_attribution requires start := ...");
+  bd->start = start->second.GetValue<int64_t>();
+  auto conversion = input.named_parameters.find("conversion");
+  if (conversion == input.named_parameters.end() || conversion->second.IsNull()) throw InvalidInputException("onager_mkv_attribution requires conversion := ...");
+  bd->conversion = conversion->second.GetValue<int64_t>();
+  auto null_state = input.named_parameters.find("null");
+  if (null_state != input.named_parameters.end() && !null_state->second.IsNull()) bd->null_states.push_back(null_state->second.GetValue<int64_t>());
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("removal_effect");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("attribution");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> AttributionInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<AttributionGlobalState>(); }
+static OperatorFinalizeResultType AttributionFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<AttributionBindData>(); auto &gs = data.global_state->Cast<AttributionGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_markov_attribution(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.start, bd.conversion,
+        bd.null_states.data(), bd.null_states.size(), nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Markov attribution failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_values.resize(nc); gs.result_attributions.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_markov_attribution(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.start, bd.conversion,
+        bd.null_states.data(), bd.null_states.size(), gs.result_nodes.data(), gs.result_values.data(), gs.result_attributions.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Markov attribution");
+    gs.result_nodes.resize(written); gs.result_values.resize(written); gs.result_attributions.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto r = GetFlatVectorDataWritable<double>(output.data[1]);
+  auto a = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { idx_t k = gs.output_idx + i; n[i] = gs.result_nodes[k]; r[i] = gs.result_values[k]; a[i] = gs.result_attributions[k]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterMarkovFunctions(ExtensionLoader &loader) {
+  TableFunction stationary("onager_mkv_stationary", {LogicalType::TABLE}, nullptr, StationaryBind, MarkovInitGlobal);
+  stationary.in_out_function = MarkovInOut;
+  stationary.in_out_function_final = StationaryFinal;
+  ONAGER_SET_NO_ORDER(stationary);
+  loader.RegisterFunction(stationary);
+
+  TableFunction hitting_times("onager_mkv_hitting_times", {LogicalType::TABLE}, nullptr, HittingTimesBind, MarkovInitGlobal);
+  hitting_times.in_out_function = MarkovInOut;
+  hitting_times.in_out_function_final = HittingTimesFinal;
+  hitting_times.named_parameters["target"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(hitting_times);
+  loader.RegisterFunction(hitting_times);
+
+  TableFunction attribution("onager_mkv_attribution", {LogicalType::TABLE}, nullptr, AttributionBind, AttributionInitGlobal);
+  attribution.in_out_function = MarkovInOut;
+  attribution.in_out_function_final = AttributionFinal;
+  attribution.named_parameters["start"] = LogicalType::BIGINT;
+  attribution.named_parameters["conversion"] = LogicalType::BIGINT;
+  attribution.named_parameters["null"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(attribution);
+  loader.RegisterFunction(attribution);
+}
+
+} // namespace onager
+} // namespace duckdb
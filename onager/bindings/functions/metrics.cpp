@@ -2,9 +2,13 @@
  * @file metrics.cpp
  * @brief Graph metrics table functions for Onager DuckDB extension.
  *
- * Diameter, Radius, Average Clustering, Average Path Length, Transitivity, Triangle Count.
+ * Diameter, Radius, Average Clustering, Average Path Length, Transitivity, Triangle Count,
+ * graph statistics drift between two snapshots, ranking comparison, category mixing matrix,
+ * directed triangle census, weighted triangle intensity, center and periphery nodes, the
+ * bipartite check, Weisfeiler-Lehman labels, and graph kernels between many small graphs.
  */
 #include "functions.hpp"
+#include <algorithm>
 #include <mutex>
 
 namespace duckdb {
@@ -134,6 +138,7 @@ static OperatorFinalizeResultType AvgClusteringFinal(ExecutionContext &ctx, Tabl
 // Triangle Count
 // =============================================================================
 
+struct TriangleCountBindData : public TableFunctionData { ScoreSelection selection; };
 struct TriangleCountGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_counts;
@@ -142,10 +147,12 @@ struct TriangleCountGlobalState : public GlobalTableFunctionState {
 };
 
 static unique_ptr<FunctionData> TriangleCountBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<TriangleCountBindData>();
   CheckInt64Input(input, "onager_mtr_triangles");
+  BindScoreSelection(input, bd->selection, {"triangles"}, "onager_mtr_triangles");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::BIGINT); nm.push_back("triangles");
-  return make_uniq<TableFunctionData>();
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> TriangleCountInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<TriangleCountGlobalState>(); }
 static OperatorResultType TriangleCountInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
@@ -156,14 +163,20 @@ static OperatorResultType TriangleCountInOut(ExecutionContext &ctx, TableFunctio
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType TriangleCountFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<TriangleCountGlobalState>();
+  auto &bd = data.bind_data->Cast<TriangleCountBindData>(); auto &gs = data.global_state->Cast<TriangleCountGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
     int64_t nc = ::onager::onager_compute_triangle_count(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Triangle count failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_counts.resize(nc);
-    ::onager::onager_compute_triangle_count(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_counts.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_triangle_count_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_counts.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Triangle count");
+    gs.result_nodes.resize(written); gs.result_counts.resize(written);
+    if (bd.selection.Active()) {
+      std::vector<double> counts(gs.result_counts.begin(), gs.result_counts.end());
+      auto indices = SelectRows(bd.selection, counts, "Triangle count");
+      KeepRows(indices, gs.result_nodes); KeepRows(indices, gs.result_counts);
+    }
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -338,6 +351,563 @@ static OperatorFinalizeResultType DensityFinal(ExecutionContext &ctx, TableFunct
   return OperatorFinalizeResultType::FINISHED;
 }
 
+// =============================================================================
+// Graph Statistics Drift
+// =============================================================================
+
+struct DriftBindData : public TableFunctionData { int64_t hubs = 10; };
+struct DriftRow { std::string metric; bool has_node; int64_t node; bool has_values; double old_value, new_value, change; };
+struct DriftGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, snapshots;
+  std::vector<DriftRow> rows;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> DriftBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<DriftBindData>();
+  CheckInt64Input(input, "onager_mtr_drift", 3);
+  if (input.input_table_types[2] != LogicalType::BIGINT) throw InvalidInputException("onager_mtr_drift requires the snapshot column to be BIGINT. Please cast it (e.g. snapshot::bigint)");
+  for (auto &kv : input.named_parameters) if (kv.first == "hubs") bd->hubs = kv.second.GetValue<int64_t>();
+  if (bd->hubs <= 0) throw InvalidInputException("onager_mtr_drift requires hubs to be positive");
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("metric");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("old_value");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("new_value");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("change");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> DriftInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<DriftGlobalState>(); }
+static OperatorResultType DriftInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<DriftGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto v = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.snapshots.push_back(v[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType DriftFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<DriftBindData>(); auto &gs = data.global_state->Cast<DriftGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    // The smaller snapshot value is the old graph and the larger is the new one
+    int64_t old_snapshot = *std::min_element(gs.snapshots.begin(), gs.snapshots.end());
+    int64_t new_snapshot = *std::max_element(gs.snapshots.begin(), gs.snapshots.end());
+    std::vector<int64_t> old_src, old_dst, new_src, new_dst;
+    for (idx_t i = 0; i < gs.src_nodes.size(); i++) {
+      if (gs.snapshots[i] == old_snapshot) { old_src.push_back(gs.src_nodes[i]); old_dst.push_back(gs.dst_nodes[i]); }
+      else if (gs.snapshots[i] == new_snapshot) { new_src.push_back(gs.src_nodes[i]); new_dst.push_back(gs.dst_nodes[i]); }
+      else throw InvalidInputException("onager_mtr_drift requires exactly two snapshot values");
+    }
+    if (new_src.empty()) throw InvalidInputException("onager_mtr_drift requires exactly two snapshot values");
+    int64_t node_counts[2], edge_counts[2]; double densities[2], clustering[2], degree_ks = 0.0;
+    int64_t nc = ::onager::onager_compare_graph_stats(old_src.data(), old_dst.data(), old_src.size(), new_src.data(), new_dst.data(), new_src.size(), bd.hubs,
+        node_counts, edge_counts, densities, clustering, &degree_ks, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Graph drift failed: " + GetOnagerError());
+    std::vector<int64_t> hub_nodes(nc), hub_changes(nc), hub_old(nc), hub_new(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compare_graph_stats(old_src.data(), old_dst.data(), old_src.size(), new_src.data(), new_dst.data(), new_src.size(), bd.hubs,
+        nullptr, nullptr, nullptr, nullptr, nullptr, hub_nodes.data(), hub_changes.data(), hub_old.data(), hub_new.data(), hub_nodes.size()), hub_nodes.size(), "Graph drift");
+    auto add_metric = [&](const char *name, double old_value, double new_value) {
+      gs.rows.push_back({name, false, 0, true, old_value, new_value, new_value - old_value});
+    };
+    add_metric("node_count", (double)node_counts[0], (double)node_counts[1]);
+    add_metric("edge_count", (double)edge_counts[0], (double)edge_counts[1]);
+    add_metric("density", densities[0], densities[1]);
+    add_metric("average_clustering", clustering[0], clustering[1]);
+    gs.rows.push_back({"degree_ks_distance", false, 0, false, 0.0, 0.0, degree_ks});
+    for (size_t i = 0; i < written; i++) {
+      gs.rows.push_back({hub_changes[i] > 0 ? "new_hub" : "removed_hub", true, hub_nodes[i], true,
+          (double)hub_old[i], (double)hub_new[i], (double)(hub_new[i] - hub_old[i])});
+    }
+    gs.computed = true;
+  }
+  idx_t rem = gs.rows.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto metric = GetFlatVectorDataWritable<string_t>(output.data[0]); auto node = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto old_value = GetFlatVectorDataWritable<double>(output.data[2]); auto new_value = GetFlatVectorDataWritable<double>(output.data[3]);
+  auto change = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    auto &row = gs.rows[gs.output_idx+i];
+    metric[i] = StringVector::AddString(output.data[0], row.metric);
+    if (row.has_node) node[i] = row.node; else FlatVector::SetNull(output.data[1], i, true);
+    if (row.has_values) { old_value[i] = row.old_value; new_value[i] = row.new_value; }
+    else { FlatVector::SetNull(output.data[2], i, true); FlatVector::SetNull(output.data[3], i, true); }
+    change[i] = row.change;
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.rows.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Ranking Comparison
+// =============================================================================
+
+struct CompareRankingsBindData : public TableFunctionData { std::string method = "spearman"; int64_t top_k = 10; };
+struct CompareRankingsGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> nodes, snapshots;
+  std::vector<double> scores;
+  double correlation = 0.0, top_k_overlap = 0.0; int64_t common_count = 0;
+  bool computed = false, emitted = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> CompareRankingsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<CompareRankingsBindData>();
+  auto &types = input.input_table_types;
+  if (types.size() < 3 || types[0] != LogicalType::BIGINT || types[1] != LogicalType::DOUBLE || types[2] != LogicalType::BIGINT) {
+    throw InvalidInputException("onager_mtr_compare_rankings requires (node_id BIGINT, score DOUBLE, snapshot BIGINT) columns. Please cast inputs (e.g. score::double)");
+  }
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "method") bd->method = StringUtil::Lower(kv.second.GetValue<string>());
+    else if (kv.first == "top_k") bd->top_k = kv.second.GetValue<int64_t>();
+  }
+  if (bd->method != "spearman" && bd->method != "kendall") throw InvalidInputException("onager_mtr_compare_rankings method must be 'spearman' or 'kendall'");
+  if (bd->top_k <= 0) throw InvalidInputException("onager_mtr_compare_rankings requires top_k to be positive");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("correlation");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("common_nodes");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("top_k_overlap");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> CompareRankingsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<CompareRankingsGlobalState>(); }
+static OperatorResultType CompareRankingsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<CompareRankingsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto n = FlatVector::GetData<int64_t>(input.data[0]); auto s = FlatVector::GetData<double>(input.data[1]);
+  auto v = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.nodes.push_back(n[i]); gs.scores.push_back(s[i]); gs.snapshots.push_back(v[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType CompareRankingsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CompareRankingsBindData>(); auto &gs = data.global_state->Cast<CompareRankingsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.nodes.empty()) { gs.computed = true; gs.emitted = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    // The smaller snapshot value is the first ranking and the larger is the second one
+    int64_t first_snapshot = *std::min_element(gs.snapshots.begin(), gs.snapshots.end());
+    int64_t second_snapshot = *std::max_element(gs.snapshots.begin(), gs.snapshots.end());
+    std::vector<int64_t> a_nodes, b_nodes; std::vector<double> a_scores, b_scores;
+    for (idx_t i = 0; i < gs.nodes.size(); i++) {
+      if (gs.snapshots[i] == first_snapshot) { a_nodes.push_back(gs.nodes[i]); a_scores.push_back(gs.scores[i]); }
+      else if (gs.snapshots[i] == second_snapshot) { b_nodes.push_back(gs.nodes[i]); b_scores.push_back(gs.scores[i]); }
+      else throw InvalidInputException("onager_mtr_compare_rankings requires exactly two snapshot values");
+    }
+    if (b_nodes.empty()) throw InvalidInputException("onager_mtr_compare_rankings requires exactly two snapshot values");
+    gs.common_count = ::onager::onager_compare_rankings(a_nodes.data(), a_scores.data(), a_nodes.size(), b_nodes.data(), b_scores.data(), b_nodes.size(),
+        bd.method.c_str(), bd.top_k, &gs.correlation, &gs.top_k_overlap);
+    if (gs.common_count < 0) throw InvalidInputException("Ranking comparison failed: " + GetOnagerError());
+    gs.computed = true;
+  }
+  if (gs.emitted) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  if (std::isnan(gs.correlation)) FlatVector::SetNull(output.data[0], 0, true); else GetFlatVectorDataWritable<double>(output.data[0])[0] = gs.correlation;
+  GetFlatVectorDataWritable<int64_t>(output.data[1])[0] = gs.common_count;
+  GetFlatVectorDataWritable<double>(output.data[2])[0] = gs.top_k_overlap;
+  gs.emitted = true; output.SetCardinality(1);
+  return OperatorFinalizeResultType::FINISHED;
+}
+
+// =============================================================================
+// Mixing Matrix
+// =============================================================================
+
+struct MixingMatrixBindData : public TableFunctionData { bool directed = false; };
+struct MixingMatrixGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, src_categories, dst_categories;
+  // Category names get integer codes in the order they arrive
+  std::unordered_map<std::string, int64_t> codes;
+  std::vector<std::string> names;
+  std::vector<int64_t> result_rows, result_cols, result_counts;
+  double assortativity = 0.0;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+
+  int64_t Code(Vector &column, idx_t row) {
+    if (FlatVector::IsNull(column, row)) throw InvalidInputException("onager_mtr_mixing_matrix category columns must not contain NULL");
+    auto inserted = codes.emplace(FlatVector::GetData<string_t>(column)[row].GetString(), static_cast<int64_t>(names.size()));
+    if (inserted.second) names.push_back(inserted.first->first);
+    return inserted.first->second;
+  }
+};
+
+static unique_ptr<FunctionData> MixingMatrixBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<MixingMatrixBindData>();
+  CheckInt64Input(input, "onager_mtr_mixing_matrix", 4);
+  if (input.input_table_types[2] != LogicalType::VARCHAR || input.input_table_types[3] != LogicalType::VARCHAR) {
+    throw InvalidInputException("Category columns must be VARCHAR. Please cast them (e.g. country::varchar)");
+  }
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+  }
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("src_category");
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("dst_category");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("edge_count");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("assortativity");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> MixingMatrixInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<MixingMatrixGlobalState>(); }
+static OperatorResultType MixingMatrixInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<MixingMatrixGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) {
+    gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]);
+    gs.src_categories.push_back(gs.Code(input.data[2], i)); gs.dst_categories.push_back(gs.Code(input.data[3], i));
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType MixingMatrixFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<MixingMatrixBindData>(); auto &gs = data.global_state->Cast<MixingMatrixGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    // Each edge lists the category of both endpoints
+    std::vector<int64_t> node_ids(gs.src_nodes), node_categories(gs.src_categories);
+    node_ids.insert(node_ids.end(), gs.dst_nodes.begin(), gs.dst_nodes.end());
+    node_categories.insert(node_categories.end(), gs.dst_categories.begin(), gs.dst_categories.end());
+    int64_t nc = ::onager::onager_compute_mixing_matrix(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), node_ids.data(), node_categories.data(), node_ids.size(), bd.directed, nullptr, nullptr, nullptr, 0, nullptr);
+    if (nc < 0) throw InvalidInputException("Mixing matrix failed: " + GetOnagerError());
+    gs.result_rows.resize(nc); gs.result_cols.resize(nc); gs.result_counts.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_mixing_matrix(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), node_ids.data(), node_categories.data(), node_ids.size(), bd.directed,
+        gs.result_rows.data(), gs.result_cols.data(), gs.result_counts.data(), gs.result_rows.size(), &gs.assortativity), gs.result_rows.size(), "Mixing matrix");
+    gs.result_rows.resize(written); gs.result_cols.resize(written); gs.result_counts.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_rows.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto r = GetFlatVectorDataWritable<string_t>(output.data[0]); auto c = GetFlatVectorDataWritable<string_t>(output.data[1]);
+  auto e = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto a = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    r[i] = StringVector::AddString(output.data[0], gs.names[gs.result_rows[k]]);
+    c[i] = StringVector::AddString(output.data[1], gs.names[gs.result_cols[k]]);
+    e[i] = gs.result_counts[k];
+    if (std::isnan(gs.assortativity)) FlatVector::SetNull(output.data[3], i, true); else a[i] = gs.assortativity;
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_rows.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Triangle Census
+// =============================================================================
+
+// Order of the counts written by onager_compute_triangle_census
+static const char *const TRIANGLE_CENSUS_TYPES[] = {"030T", "030C", "120D", "120U", "120C", "210", "300"};
+
+struct TriangleCensusGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_counts;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> TriangleCensusBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  CheckInt64Input(input, "onager_mtr_triangle_census");
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("triangle_type");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("triangle_count");
+  return make_uniq<TableFunctionData>();
+}
+static unique_ptr<GlobalTableFunctionState> TriangleCensusInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<TriangleCensusGlobalState>(); }
+static OperatorResultType TriangleCensusInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<TriangleCensusGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType TriangleCensusFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<TriangleCensusGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_triangle_census(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Triangle census failed: " + GetOnagerError());
+    gs.result_counts.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_triangle_census(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_counts.data(), gs.result_counts.size()), gs.result_counts.size(), "Triangle census");
+    gs.result_counts.resize(MinValue<idx_t>(written, sizeof(TRIANGLE_CENSUS_TYPES) / sizeof(TRIANGLE_CENSUS_TYPES[0])));
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_counts.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto t = GetFlatVectorDataWritable<string_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) {
+    t[i] = StringVector::AddString(output.data[0], TRIANGLE_CENSUS_TYPES[gs.output_idx+i]);
+    c[i] = gs.result_counts[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_counts.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Weighted Triangles
+// =============================================================================
+
+struct WeightedTrianglesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_counts;
+  std::vector<double> weights, result_intensities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> WeightedTrianglesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  CheckInt64Input(input, "onager_mtr_weighted_triangles", 3);
+  if (input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("triangles");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("intensity");
+  return make_uniq<TableFunctionData>();
+}
+static unique_ptr<GlobalTableFunctionState> WeightedTrianglesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<WeightedTrianglesGlobalState>(); }
+static OperatorResultType WeightedTrianglesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<WeightedTrianglesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]); auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType WeightedTrianglesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<WeightedTrianglesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_weighted_triangles(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Weighted triangles failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_counts.resize(nc); gs.result_intensities.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_weighted_triangles(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(),
+        gs.result_nodes.data(), gs.result_counts.data(), gs.result_intensities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Weighted triangles");
+    gs.result_nodes.resize(written); gs.result_counts.resize(written); gs.result_intensities.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; c[i] = gs.result_counts[gs.output_idx+i]; w[i] = gs.result_intensities[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Center and Periphery
+// =============================================================================
+
+struct EccentricityNodesBindData : public TableFunctionData { bool periphery = false; };
+struct EccentricityNodesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  int64_t eccentricity = 0;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> EccentricityNodesBind(TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm, bool periphery) {
+  auto bd = make_uniq<EccentricityNodesBindData>();
+  bd->periphery = periphery;
+  CheckInt64Input(input, periphery ? "onager_mtr_periphery" : "onager_mtr_center");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("eccentricity");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> CenterBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) { return EccentricityNodesBind(input, rt, nm, false); }
+static unique_ptr<FunctionData> PeripheryBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) { return EccentricityNodesBind(input, rt, nm, true); }
+static unique_ptr<GlobalTableFunctionState> EccentricityNodesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<EccentricityNodesGlobalState>(); }
+static OperatorResultType EccentricityNodesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<EccentricityNodesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType EccentricityNodesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<EccentricityNodesBindData>(); auto &gs = data.global_state->Cast<EccentricityNodesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    auto compute = bd.periphery ? ::onager::onager_compute_periphery_nodes : ::onager::onager_compute_center_nodes;
+    const char *name = bd.periphery ? "Periphery" : "Center";
+    int64_t nc = compute(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, 0, nullptr);
+    if (nc < 0) throw InvalidInputException(std::string(name) + " failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc);
+    size_t written = CheckOnagerWrite(compute(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_nodes.size(), &gs.eccentricity), gs.result_nodes.size(), name);
+    gs.result_nodes.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto e = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; e[i] = gs.eccentricity; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Bipartite Check
+// =============================================================================
+
+struct IsBipartiteGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  int64_t result = 0;
+  bool computed = false, output_done = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> IsBipartiteBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  CheckInt64Input(input, "onager_mtr_is_bipartite");
+  rt.push_back(LogicalType::BOOLEAN); nm.push_back("is_bipartite");
+  return make_uniq<TableFunctionData>();
+}
+static unique_ptr<GlobalTableFunctionState> IsBipartiteInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<IsBipartiteGlobalState>(); }
+static OperatorResultType IsBipartiteInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<IsBipartiteGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType IsBipartiteFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<IsBipartiteGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    gs.result = ::onager::onager_compute_is_bipartite(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size());
+    if (gs.result < 0) throw InvalidInputException("Bipartite check failed: " + GetOnagerError());
+    gs.computed = true;
+  }
+  if (gs.output_done) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<bool>(output.data[0])[0] = gs.result == 1;
+  output.SetCardinality(1); gs.output_done = true;
+  return OperatorFinalizeResultType::FINISHED;
+}
+
+// =============================================================================
+// Weisfeiler-Lehman Labels
+// =============================================================================
+
+struct WlLabelsBindData : public TableFunctionData { int64_t iterations = 3; };
+struct WlLabelsGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_iterations, result_labels;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> WlLabelsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WlLabelsBindData>();
+  CheckInt64Input(input, "onager_mtr_wl_labels");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "iterations") bd->iterations = kv.second.GetValue<int64_t>();
+  }
+  if (bd->iterations < 0) throw InvalidInputException("onager_mtr_wl_labels iterations must be non-negative");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("iteration");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("label");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> WlLabelsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<WlLabelsGlobalState>(); }
+static OperatorResultType WlLabelsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<WlLabelsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType WlLabelsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<WlLabelsBindData>(); auto &gs = data.global_state->Cast<WlLabelsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_wl_labels(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.iterations, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("WL labels failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_iterations.resize(nc); gs.result_labels.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_wl_labels(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.iterations, gs.result_nodes.data(), gs.result_iterations.data(), gs.result_labels.data(), gs.result_nodes.size()), gs.result_nodes.size(), "WL labels");
+    gs.result_nodes.resize(written); gs.result_iterations.resize(written); gs.result_labels.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto it = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto l = GetFlatVectorDataWritable<int64_t>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; it[i] = gs.result_iterations[gs.output_idx+i]; l[i] = gs.result_labels[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Graph Kernels
+// =============================================================================
+
+struct GraphKernelBindData : public TableFunctionData { std::string kernel = "wl_subtree"; int64_t iterations = 3; bool normalize = true; };
+struct GraphKernelGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, graph_ids, result_a, result_b;
+  std::vector<double> result_similarities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> GraphKernelBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<GraphKernelBindData>();
+  CheckInt64Input(input, "onager_mtr_graph_kernel", 3);
+  if (input.input_table_types[2] != LogicalType::BIGINT) {
+    throw InvalidInputException("Graph ID column must be BIGINT. Please cast it (e.g. graph_id::bigint)");
+  }
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "kernel") bd->kernel = StringUtil::Lower(kv.second.GetValue<string>());
+    else if (kv.first == "iterations") bd->iterations = kv.second.GetValue<int64_t>();
+    else if (kv.first == "normalize") bd->normalize = kv.second.GetValue<bool>();
+  }
+  if (bd->kernel != "wl_subtree" && bd->kernel != "shortest_path") {
+    throw InvalidInputException("onager_mtr_graph_kernel kernel must be 'wl_subtree' or 'shortest_path'");
+  }
+  if (bd->iterations < 0) throw InvalidInputException("onager_mtr_graph_kernel iterations must be non-negative");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("graph_a");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("graph_b");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("similarity");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> GraphKernelInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<GraphKernelGlobalState>(); }
+static OperatorResultType GraphKernelInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<GraphKernelGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto g = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) {
+    if (FlatVector::IsNull(input.data[2], i)) throw InvalidInputException("Graph ID column must not contain NULL");
+    gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.graph_ids.push_back(g[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType GraphKernelFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<GraphKernelBindData>(); auto &gs = data.global_state->Cast<GraphKernelGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_graph_kernel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.graph_ids.data(), gs.src_nodes.size(), bd.kernel.c_str(), bd.iterations, bd.normalize, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Graph kernel failed: " + GetOnagerError());
+    gs.result_a.resize(nc); gs.result_b.resize(nc); gs.result_similarities.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_graph_kernel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.graph_ids.data(), gs.src_nodes.size(), bd.kernel.c_str(), bd.iterations, bd.normalize,
+        gs.result_a.data(), gs.result_b.data(), gs.result_similarities.data(), gs.result_a.size()), gs.result_a.size(), "Graph kernel");
+    gs.result_a.resize(written); gs.result_b.resize(written); gs.result_similarities.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_a.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto a = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto b = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto sim = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { a[i] = gs.result_a[gs.output_idx+i]; b[i] = gs.result_b[gs.output_idx+i]; sim[i] = gs.result_similarities[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_a.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -366,6 +936,7 @@ void RegisterMetricFunctions(ExtensionLoader &loader) {
   TableFunction triangles("onager_mtr_triangles", {LogicalType::TABLE}, nullptr, TriangleCountBind, TriangleCountInitGlobal);
   triangles.in_out_function = TriangleCountInOut;
   triangles.in_out_function_final = TriangleCountFinal;
+  AddScoreSelectionParameters(triangles);
   ONAGER_SET_NO_ORDER(triangles);
   loader.RegisterFunction(triangles);
 
@@ -393,6 +964,74 @@ void RegisterMetricFunctions(ExtensionLoader &loader) {
   density.named_parameters["directed"] = LogicalType::BOOLEAN;
   ONAGER_SET_NO_ORDER(density);
   loader.RegisterFunction(density);
+
+  TableFunction drift("onager_mtr_drift", {LogicalType::TABLE}, nullptr, DriftBind, DriftInitGlobal);
+  drift.in_out_function = DriftInOut;
+  drift.in_out_function_final = DriftFinal;
+  drift.named_parameters["hubs"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(drift);
+  loader.RegisterFunction(drift);
+
+  TableFunction compare_rankings("onager_mtr_compare_rankings", {LogicalType::TABLE}, nullptr, CompareRankingsBind, CompareRankingsInitGlobal);
+  compare_rankings.in_out_function = CompareRankingsInOut;
+  compare_rankings.in_out_function_final = CompareRankingsFinal;
+  compare_rankings.named_parameters["method"] = LogicalType::VARCHAR;
+  compare_rankings.named_parameters["top_k"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(compare_rankings);
+  loader.RegisterFunction(compare_rankings);
+
+  TableFunction mixing_matrix("onager_mtr_mixing_matrix", {LogicalType::TABLE}, nullptr, MixingMatrixBind, MixingMatrixInitGlobal);
+  mixing_matrix.in_out_function = MixingMatrixInOut;
+  mixing_matrix.in_out_function_final = MixingMatrixFinal;
+  mixing_matrix.named_parameters["directed"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(mixing_matrix);
+  loader.RegisterFunction(mixing_matrix);
+
+  TableFunction triangle_census("onager_mtr_triangle_census", {LogicalType::TABLE}, nullptr, TriangleCensusBind, TriangleCensusInitGlobal);
+  triangle_census.in_out_function = TriangleCensusInOut;
+  triangle_census.in_out_function_final = TriangleCensusFinal;
+  ONAGER_SET_NO_ORDER(triangle_census);
+  loader.RegisterFunction(triangle_census);
+
+  TableFunction weighted_triangles("onager_mtr_weighted_triangles", {LogicalType::TABLE}, nullptr, WeightedTrianglesBind, WeightedTrianglesInitGlobal);
+  weighted_triangles.in_out_function = WeightedTrianglesInOut;
+  weighted_triangles.in_out_function_final = WeightedTrianglesFinal;
+  ONAGER_SET_NO_ORDER(weighted_triangles);
+  loader.RegisterFunction(weighted_triangles);
+
+  TableFunction center("onager_mtr_center", {LogicalType::TABLE}, nullptr, CenterBind, EccentricityNodesInitGlobal);
+  center.in_out_function = EccentricityNodesInOut;
+  center.in_out_function_final = EccentricityNodesFinal;
+  ONAGER_SET_NO_ORDER(center);
+  loader.RegisterFunction(center);
+
+  TableFunction periphery("onager_mtr_periphery", {LogicalType::TABLE}, nullptr, PeripheryBind, EccentricityNodesInitGlobal);
+  periphery.in_out_function = EccentricityNodesInOut;
+  periphery.in_out_function_final = EccentricityNodesFinal;
+  ONAGER_SET_NO_ORDER(periphery);
+  loader.RegisterFunction(periphery);
+
+  TableFunction is_bipartite("onager_mtr_is_bipartite", {LogicalType::TABLE}, nullptr, IsBipartiteBind, IsBipartiteInitGlobal);
+  is_bipartite.in_out_function = IsBipartiteInOut;
+  is_bipartite.in_out_function_final = IsBipartiteFinal;
+  ONAGER_SET_NO_ORDER(is_bipartite);
+  loader.RegisterFunction(is_bipartite);
+
+  TableFunction wl_labels("onager_mtr_wl_labels", {LogicalType::TABLE}, nullptr, WlLabelsBind, WlLabelsInitGlobal);
+  wl_labels.in_out_function = WlLabelsInOut;
+  wl_labels.in_out_function_final = WlLabelsFinal;
+  wl_labels.named_parameters["iterations"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(wl_labels);
+  loader.RegisterFunction(wl_labels);
+
+  TableFunction graph_kernel("onager_mtr_graph_kernel", {LogicalType::TABLE}, nullptr, GraphKernelBind, GraphKernelInitGlobal);
+  graph_kernel.in_out_function = GraphKernelInOut;
+  graph_kernel.in_out_function_final = GraphKernelFinal;
+  graph_kernel.named_parameters["kernel"] = LogicalType::VARCHAR;
+  graph_kernel.named_parameters["iterations"] = LogicalType::BIGINT;
+  graph_kernel.named_parameters["normalize"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(graph_kernel);
+  loader.RegisterFunction(graph_kernel);
 }
 
 } // namespace onager
@@ -2,7 +2,8 @@
  * @file mst.cpp
  * @brief Minimum Spanning Tree table functions for Onager DuckDB extension.
  *
- * Kruskal's MST algorithm.
+ * Kruskal's and Prim's MST algorithms, with a tree label per edge on disconnected graphs, and a
+ * degree-constrained spanning tree heuristic.
  */
 #include "functions.hpp"
 #include <mutex>
@@ -17,19 +18,22 @@ using namespace onager;
 
 struct KruskalMstGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
-  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
-  std::vector<double> weights, result_weights;
-  double total_weight = 0.0;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst, result_components;
+  std::vector<double> weights, result_weights, result_component_weights;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
 
 static unique_ptr<FunctionData> KruskalMstBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WeightedBindData>();
   CheckInt64Input(input, "onager_mst_kruskal", 3);
+  BindWeightSemantics(input, *bd, "onager_mst_kruskal");
   rt.push_back(LogicalType::BIGINT); nm.push_back("src");
   rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
-  return make_uniq<TableFunctionData>();
+  rt.push_back(LogicalType::BIGINT); nm.push_back("component");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("component_weight");
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> KruskalMstInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<KruskalMstGlobalState>(); }
 static OperatorResultType KruskalMstInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
@@ -41,21 +45,27 @@ static OperatorResultType KruskalMstInOut(ExecutionContext &ctx, TableFunctionIn
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType KruskalMstFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<KruskalMstGlobalState>();
+  auto &bd = data.bind_data->Cast<WeightedBindData>(); auto &gs = data.global_state->Cast<KruskalMstGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t ec = ::onager::onager_compute_kruskal_mst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, nullptr);
+    ApplyWeightSemantics(bd, gs.weights, "Kruskal MST");
+    int64_t ec = ::onager::onager_compute_kruskal_mst_forest(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, nullptr, nullptr, 0);
     if (ec < 0) throw InvalidInputException("Kruskal MST failed: " + GetOnagerError());
-    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec);
-    ::onager::onager_compute_kruskal_mst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), &gs.total_weight);
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec); gs.result_components.resize(ec); gs.result_component_weights.resize(ec);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_kruskal_mst_forest(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), gs.result_components.data(), gs.result_component_weights.data(), gs.result_src.size()), gs.result_src.size(), "Kruskal MST");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_weights.resize(written); gs.result_components.resize(written); gs.result_component_weights.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_src.size() - gs.output_idx;
   if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
   idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
   auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
-  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i]; }
+  auto c = GetFlatVectorDataWritable<int64_t>(output.data[3]); auto cw = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i];
+    c[i] = gs.result_components[gs.output_idx+i]; cw[i] = gs.result_component_weights[gs.output_idx+i];
+  }
   gs.output_idx += to; output.SetCardinality(to);
   return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
@@ -66,19 +76,22 @@ static OperatorFinalizeResultType KruskalMstFinal(ExecutionContext &ctx, TableFu
 
 struct PrimMstGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
-  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
-  std::vector<double> weights, result_weights;
-  double total_weight = 0.0;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst, result_components;
+  std::vector<double> weights, result_weights, result_component_weights;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
 
 static unique_ptr<FunctionData> PrimMstBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WeightedBindData>();
   CheckInt64Input(input, "onager_mst_prim", 3);
+  BindWeightSemantics(input, *bd, "onager_mst_prim");
   rt.push_back(LogicalType::BIGINT); nm.push_back("src");
   rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
-  return make_uniq<TableFunctionData>();
+  rt.push_back(LogicalType::BIGINT); nm.push_back("component");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("component_weight");
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> PrimMstInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<PrimMstGlobalState>(); }
 static OperatorResultType PrimMstInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
@@ -90,21 +103,91 @@ static OperatorResultType PrimMstInOut(ExecutionContext &ctx, TableFunctionInput
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType PrimMstFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<PrimMstGlobalState>();
+  auto &bd = data.bind_data->Cast<WeightedBindData>(); auto &gs = data.global_state->Cast<PrimMstGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t ec = ::onager::onager_compute_prim_mst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, nullptr);
+    ApplyWeightSemantics(bd, gs.weights, "Prim MST");
+    int64_t ec = ::onager::onager_compute_prim_mst_forest(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, nullptr, nullptr, 0);
     if (ec < 0) throw InvalidInputException("Prim MST failed: " + GetOnagerError());
-    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec);
-    ::onager::onager_compute_prim_mst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), &gs.total_weight);
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec); gs.result_components.resize(ec); gs.result_component_weights.resize(ec);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_prim_mst_forest(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), gs.result_components.data(), gs.result_component_weights.data(), gs.result_src.size()), gs.result_src.size(), "Prim MST");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_weights.resize(written); gs.result_components.resize(written); gs.result_component_weights.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  auto c = GetFlatVectorDataWritable<int64_t>(output.data[3]); auto cw = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i];
+    c[i] = gs.result_components[gs.output_idx+i]; cw[i] = gs.result_component_weights[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Degree-Constrained Spanning Tree
+// =============================================================================
+
+struct DcmstBindData : public WeightedBindData { int64_t max_degree = 2; };
+struct DcmstGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst, result_components;
+  std::vector<double> weights, result_weights, result_component_weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> DcmstBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<DcmstBindData>();
+  CheckInt64Input(input, "onager_mst_degree_constrained", 3);
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "max_degree") bd->max_degree = kv.second.GetValue<int64_t>();
+  }
+  if (bd->max_degree <= 0) throw InvalidInputException("onager_mst_degree_constrained requires max_degree to be positive");
+  BindWeightSemantics(input, *bd, "onager_mst_degree_constrained");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("component");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("component_weight");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> DcmstInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<DcmstGlobalState>(); }
+static OperatorResultType DcmstInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<DcmstGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType DcmstFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<DcmstBindData>(); auto &gs = data.global_state->Cast<DcmstGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Degree-constrained MST");
+    size_t max_degree = static_cast<size_t>(bd.max_degree);
+    int64_t ec = ::onager::onager_compute_dcmst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), max_degree, nullptr, nullptr, nullptr, nullptr, nullptr, 0);
+    if (ec < 0) throw InvalidInputException("Degree-constrained MST failed: " + GetOnagerError());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec); gs.result_components.resize(ec); gs.result_component_weights.resize(ec);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_dcmst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), max_degree, gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), gs.result_components.data(), gs.result_component_weights.data(), gs.result_src.size()), gs.result_src.size(), "Degree-constrained MST");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_weights.resize(written); gs.result_components.resize(written); gs.result_component_weights.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_src.size() - gs.output_idx;
   if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
   idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
   auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
-  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i]; }
+  auto c = GetFlatVectorDataWritable<int64_t>(output.data[3]); auto cw = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i];
+    c[i] = gs.result_components[gs.output_idx+i]; cw[i] = gs.result_component_weights[gs.output_idx+i];
+  }
   gs.output_idx += to; output.SetCardinality(to);
   return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
@@ -119,14 +202,24 @@ void RegisterMstFunctions(ExtensionLoader &loader) {
   TableFunction kruskal("onager_mst_kruskal", {LogicalType::TABLE}, nullptr, KruskalMstBind, KruskalMstInitGlobal);
   kruskal.in_out_function = KruskalMstInOut;
   kruskal.in_out_function_final = KruskalMstFinal;
+  kruskal.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
   ONAGER_SET_NO_ORDER(kruskal);
   loader.RegisterFunction(kruskal);
 
   TableFunction prim("onager_mst_prim", {LogicalType::TABLE}, nullptr, PrimMstBind, PrimMstInitGlobal);
   prim.in_out_function = PrimMstInOut;
   prim.in_out_function_final = PrimMstFinal;
+  prim.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
   ONAGER_SET_NO_ORDER(prim);
   loader.RegisterFunction(prim);
+
+  TableFunction dcmst("onager_mst_degree_constrained", {LogicalType::TABLE}, nullptr, DcmstBind, DcmstInitGlobal);
+  dcmst.in_out_function = DcmstInOut;
+  dcmst.in_out_function_final = DcmstFinal;
+  dcmst.named_parameters["max_degree"] = LogicalType::BIGINT;
+  dcmst.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(dcmst);
+  loader.RegisterFunction(dcmst);
 }
 
 } // namespace onager
@@ -0,0 +1,255 @@
+/**
+ * @file multilayer.cpp
+ * @brief Multilayer network table functions for Onager DuckDB extension.
+ *
+ * Multiplex degree, layer overlap, and multiplex PageRank over (src, dst, layer)
+ * edge tables.
+ */
+#include "functions.hpp"
+#include <mutex>
+
+namespace duckdb {
+
+using namespace onager;
+
+// Validates a (src, dst, layer) input table
+static void CheckLayeredInput(TableFunctionBindInput &input, const string &name) {
+  CheckInt64Input(input, name, 3);
+  if (input.input_table_types[2] != LogicalType::BIGINT) {
+    throw InvalidInputException("Layer column must be BIGINT. Please cast it (e.g. layer::bigint)");
+  }
+}
+
+struct LayeredEdges {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, layers;
+};
+
+template <class STATE>
+static OperatorResultType LayeredEdgesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<STATE>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto l = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.layers.push_back(l[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+
+// =============================================================================
+// Multiplex Degree
+// =============================================================================
+
+struct MultiplexDegreeBindData : public TableFunctionData { bool per_layer = false; };
+struct MultiplexDegreeGlobalState : public GlobalTableFunctionState, public LayeredEdges {
+  // With per_layer, result_a holds layers and result_b degrees; otherwise degrees and layer counts
+  std::vector<int64_t> result_nodes, result_a, result_b;
+  std::vector<double> result_participation;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> MultiplexDegreeBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<MultiplexDegreeBindData>();
+  CheckLayeredInput(input, "onager_mlx_degree");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "per_layer") bd->per_layer = kv.second.GetValue<bool>();
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  if (bd->per_layer) {
+    rt.push_back(LogicalType::BIGINT); nm.push_back("layer");
+    rt.push_back(LogicalType::BIGINT); nm.push_back("degree");
+  } else {
+    rt.push_back(LogicalType::BIGINT); nm.push_back("degree");
+    rt.push_back(LogicalType::BIGINT); nm.push_back("layer_count");
+    rt.push_back(LogicalType::DOUBLE); nm.push_back("participation");
+  }
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> MultiplexDegreeInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<MultiplexDegreeGlobalState>(); }
+static OperatorFinalizeResultType MultiplexDegreeFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<MultiplexDegreeBindData>(); auto &gs = data.global_state->Cast<MultiplexDegreeGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    if (bd.per_layer) {
+      int64_t nc = ::onager::onager_compute_multiplex_layer_degree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Multiplex degree failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_a.resize(nc); gs.result_b.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_multiplex_layer_degree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(),
+          gs.result_nodes.data(), gs.result_a.data(), gs.result_b.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Multiplex degree");
+      gs.result_nodes.resize(written); gs.result_a.resize(written); gs.result_b.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_multiplex_degree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Multiplex degree failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_a.resize(nc); gs.result_b.resize(nc); gs.result_participation.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_multiplex_degree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(),
+          gs.result_nodes.data(), gs.result_a.data(), gs.result_b.data(), gs.result_participation.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Multiplex degree");
+      gs.result_nodes.resize(written); gs.result_a.resize(written); gs.result_b.resize(written); gs.result_participation.resize(written);
+    }
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto a = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto b = GetFlatVectorDataWritable<int64_t>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; a[i] = gs.result_a[gs.output_idx+i]; b[i] = gs.result_b[gs.output_idx+i]; }
+  if (!bd.per_layer) {
+    auto p = GetFlatVectorDataWritable<double>(output.data[3]);
+    for (idx_t i = 0; i < to; i++) p[i] = gs.result_participation[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Layer Overlap
+// =============================================================================
+
+struct LayerOverlapBindData : public TableFunctionData { bool directed = false; };
+struct LayerOverlapGlobalState : public GlobalTableFunctionState, public LayeredEdges {
+  std::vector<int64_t> result_layer_a, result_layer_b, result_shared;
+  std::vector<double> result_jaccard;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> LayerOverlapBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<LayerOverlapBindData>();
+  CheckLayeredInput(input, "onager_mlx_overlap");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("layer_a");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("layer_b");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("shared_edges");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("jaccard");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> LayerOverlapInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<LayerOverlapGlobalState>(); }
+static OperatorFinalizeResultType LayerOverlapFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<LayerOverlapBindData>(); auto &gs = data.global_state->Cast<LayerOverlapGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_layer_overlap(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), bd.directed, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Layer overlap failed: " + GetOnagerError());
+    gs.result_layer_a.resize(nc); gs.result_layer_b.resize(nc); gs.result_shared.resize(nc); gs.result_jaccard.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_layer_overlap(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), bd.directed,
+        gs.result_layer_a.data(), gs.result_layer_b.data(), gs.result_shared.data(), gs.result_jaccard.data(), gs.result_layer_a.size()), gs.result_layer_a.size(), "Layer overlap");
+    gs.result_layer_a.resize(written); gs.result_layer_b.resize(written); gs.result_shared.resize(written); gs.result_jaccard.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_layer_a.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto a = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto b = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto sh = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto j = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    a[i] = gs.result_layer_a[gs.output_idx+i]; b[i] = gs.result_layer_b[gs.output_idx+i];
+    sh[i] = gs.result_shared[gs.output_idx+i]; j[i] = gs.result_jaccard[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_layer_a.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Multiplex PageRank
+// =============================================================================
+
+struct MultiplexPageRankBindData : public TableFunctionData {
+  double damping = 0.85; int64_t iterations = 100; bool directed = true; bool per_layer = false;
+};
+struct MultiplexPageRankGlobalState : public GlobalTableFunctionState, public LayeredEdges {
+  std::vector<int64_t> result_nodes, result_layers;
+  std::vector<double> result_ranks;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> MultiplexPageRankBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<MultiplexPageRankBindData>();
+  CheckLayeredInput(input, "onager_mlx_pagerank");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "damping") bd->damping = kv.second.GetValue<double>();
+    else if (kv.first == "iterations") bd->iterations = kv.second.GetValue<int64_t>();
+    else if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+    else if (kv.first == "per_layer") bd->per_layer = kv.second.GetValue<bool>();
+  }
+  if (bd->iterations <= 0) throw InvalidInputException("onager_mlx_pagerank requires iterations to be positive");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  if (bd->per_layer) { rt.push_back(LogicalType::BIGINT); nm.push_back("layer"); }
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("rank");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> MultiplexPageRankInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<MultiplexPageRankGlobalState>(); }
+static OperatorFinalizeResultType MultiplexPageRankFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<MultiplexPageRankBindData>(); auto &gs = data.global_state->Cast<MultiplexPageRankGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    auto iterations = static_cast<size_t>(bd.iterations);
+    if (bd.per_layer) {
+      int64_t nc = ::onager::onager_compute_multiplex_layer_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), bd.damping, iterations, bd.directed, nullptr, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Multiplex PageRank failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_layers.resize(nc); gs.result_ranks.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_multiplex_layer_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), bd.damping, iterations, bd.directed,
+          gs.result_nodes.data(), gs.result_layers.data(), gs.result_ranks.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Multiplex PageRank");
+      gs.result_nodes.resize(written); gs.result_layers.resize(written); gs.result_ranks.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_multiplex_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), bd.damping, iterations, bd.directed, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Multiplex PageRank failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_ranks.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_multiplex_pagerank(gs.src_nodes.data(), gs.dst_nodes.data(), gs.layers.data(), gs.src_nodes.size(), bd.damping, iterations, bd.directed,
+          gs.result_nodes.data(), gs.result_ranks.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Multiplex PageRank");
+      gs.result_nodes.resize(written); gs.result_ranks.resize(written);
+    }
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  idx_t rank_col = bd.per_layer ? 2 : 1;
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto r = GetFlatVectorDataWritable<double>(output.data[rank_col]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; r[i] = gs.result_ranks[gs.output_idx+i]; }
+  if (bd.per_layer) {
+    auto l = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+    for (idx_t i = 0; i < to; i++) l[i] = gs.result_layers[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterMultilayerFunctions(ExtensionLoader &loader) {
+  TableFunction degree("onager_mlx_degree", {LogicalType::TABLE}, nullptr, MultiplexDegreeBind, MultiplexDegreeInitGlobal);
+  degree.in_out_function = LayeredEdgesInOut<MultiplexDegreeGlobalState>;
+  degree.in_out_function_final = MultiplexDegreeFinal;
+  degree.named_parameters["per_layer"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(degree);
+  loader.RegisterFunction(degree);
+
+  TableFunction overlap("onager_mlx_overlap", {LogicalType::TABLE}, nullptr, LayerOverlapBind, LayerOverlapInitGlobal);
+  overlap.in_out_function = LayeredEdgesInOut<LayerOverlapGlobalState>;
+  overlap.in_out_function_final = LayerOverlapFinal;
+  overlap.named_parameters["directed"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(overlap);
+  loader.RegisterFunction(overlap);
+
+  TableFunction pagerank("onager_mlx_pagerank", {LogicalType::TABLE}, nullptr, MultiplexPageRankBind, MultiplexPageRankInitGlobal);
+  pagerank.in_out_function = LayeredEdgesInOut<MultiplexPageRankGlobalState>;
+  pagerank.in_out_function_final = MultiplexPageRankFinal;
+  pagerank.named_parameters["damping"] = LogicalType::DOUBLE;
+  pagerank.named_parameters["iterations"] = LogicalType::BIGINT;
+  pagerank.named_parameters["directed"] = LogicalType::BOOLEAN;
+  pagerank.named_parameters["per_layer"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(pagerank);
+  loader.RegisterFunction(pagerank);
+}
+
+} // namespace onager
+} // namespace duckdb
@@ -2,7 +2,8 @@
  * @file parallel.cpp
  * @brief Parallel algorithm table functions for Onager DuckDB extension.
  *
- * Parallel PageRank, BFS, Shortest Paths, Connected Components, Clustering Coefficients, Triangle Count.
+ * Parallel PageRank, BFS, Shortest Paths, Connected Components, Clustering Coefficients, Triangle Count,
+ * Borůvka MST.
  */
 #include "functions.hpp"
 #include <mutex>
@@ -56,7 +57,8 @@ static OperatorFinalizeResultType ParallelPageRankFinal(ExecutionContext &ctx, T
     int64_t nc = ::onager::onager_compute_pagerank_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, 0, bd.damping, bd.iterations, bd.directed, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Parallel PageRank failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_ranks.resize(nc);
-    ::onager::onager_compute_pagerank_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, 0, bd.damping, bd.iterations, bd.directed, gs.result_nodes.data(), gs.result_ranks.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_pagerank_parallel_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, 0, bd.damping, bd.iterations, bd.directed, gs.result_nodes.data(), gs.result_ranks.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Parallel PageRank");
+    gs.result_nodes.resize(written); gs.result_ranks.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -103,7 +105,8 @@ static OperatorFinalizeResultType ParallelBfsFinal(ExecutionContext &ctx, TableF
     int64_t nc = ::onager::onager_compute_bfs_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, nullptr);
     if (nc < 0) throw InvalidInputException("Parallel BFS failed: " + GetOnagerError());
     gs.result_order.resize(nc);
-    ::onager::onager_compute_bfs_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_order.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_bfs_parallel_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_order.data(), gs.result_order.size()), gs.result_order.size(), "Parallel BFS");
+    gs.result_order.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_order.size() - gs.output_idx;
@@ -152,7 +155,8 @@ static OperatorFinalizeResultType ParallelPathsFinal(ExecutionContext &ctx, Tabl
     int64_t nc = ::onager::onager_compute_shortest_paths_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Parallel shortest paths failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_distances.resize(nc);
-    ::onager::onager_compute_shortest_paths_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_nodes.data(), gs.result_distances.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_shortest_paths_parallel_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_nodes.data(), gs.result_distances.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Parallel shortest paths");
+    gs.result_nodes.resize(written); gs.result_distances.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -197,7 +201,8 @@ static OperatorFinalizeResultType ParallelComponentsFinal(ExecutionContext &ctx,
     int64_t nc = ::onager::onager_compute_components_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Parallel components failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_components.resize(nc);
-    ::onager::onager_compute_components_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_components.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_components_parallel_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_components.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Parallel components");
+    gs.result_nodes.resize(written); gs.result_components.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -243,7 +248,8 @@ static OperatorFinalizeResultType ParallelClusteringFinal(ExecutionContext &ctx,
     int64_t nc = ::onager::onager_compute_clustering_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Parallel clustering failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_coefficients.resize(nc);
-    ::onager::onager_compute_clustering_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_coefficients.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_clustering_parallel_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_coefficients.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Parallel clustering");
+    gs.result_nodes.resize(written); gs.result_coefficients.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -288,7 +294,8 @@ static OperatorFinalizeResultType ParallelTrianglesFinal(ExecutionContext &ctx,
     int64_t nc = ::onager::onager_compute_triangles_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Parallel triangles failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_counts.resize(nc);
-    ::onager::onager_compute_triangles_parallel(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_counts.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_triangles_parallel_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_nodes.data(), gs.result_counts.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Parallel triangles");
+    gs.result_nodes.resize(written); gs.result_counts.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -300,6 +307,64 @@ static OperatorFinalizeResultType ParallelTrianglesFinal(ExecutionContext &ctx,
   return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Parallel Borůvka MST
+// =============================================================================
+
+struct ParallelMstGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst, result_components;
+  std::vector<double> weights, result_weights, result_component_weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> ParallelMstBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WeightedBindData>();
+  CheckInt64Input(input, "onager_par_mst", 3);
+  BindWeightSemantics(input, *bd, "onager_par_mst");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("component");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("component_weight");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> ParallelMstInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ParallelMstGlobalState>(); }
+static OperatorResultType ParallelMstInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<ParallelMstGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ParallelMstFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<WeightedBindData>(); auto &gs = data.global_state->Cast<ParallelMstGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Parallel MST");
+    int64_t ec = ::onager::onager_compute_boruvka_mst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr, nullptr, nullptr, 0);
+    if (ec < 0) throw InvalidInputException("Parallel MST failed: " + GetOnagerError());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec); gs.result_components.resize(ec); gs.result_component_weights.resize(ec);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_boruvka_mst(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), gs.result_components.data(), gs.result_component_weights.data(), gs.result_src.size()), gs.result_src.size(), "Parallel MST");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_weights.resize(written); gs.result_components.resize(written); gs.result_component_weights.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  auto c = GetFlatVectorDataWritable<int64_t>(output.data[3]); auto cw = GetFlatVectorDataWritable<double>(output.data[4]);
+  for (idx_t i = 0; i < to; i++) {
+    s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i];
+    c[i] = gs.result_components[gs.output_idx+i]; cw[i] = gs.result_component_weights[gs.output_idx+i];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -347,6 +412,13 @@ void RegisterParallelFunctions(ExtensionLoader &loader) {
   par_triangles.in_out_function_final = ParallelTrianglesFinal;
   ONAGER_SET_NO_ORDER(par_triangles);
   loader.RegisterFunction(par_triangles);
+
+  TableFunction par_mst("onager_par_mst", {LogicalType::TABLE}, nullptr, ParallelMstBind, ParallelMstInitGlobal);
+  par_mst.in_out_function = ParallelMstInOut;
+  par_mst.in_out_function_final = ParallelMstFinal;
+  par_mst.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(par_mst);
+  loader.RegisterFunction(par_mst);
 }
 
 } // namespace onager
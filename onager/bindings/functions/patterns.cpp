@@ -0,0 +1,255 @@
+/**
+ * @file patterns.cpp
+ * @brief Transaction pattern table functions for Onager DuckDB extension.
+ *
+ * Fan-in and fan-out bursts and activity spikes in timestamped edges, and
+ * cycles whose edge weights approximately conserve.
+ */
+#include "functions.hpp"
+#include <mutex>
+
+namespace duckdb {
+
+using namespace onager;
+
+// =============================================================================
+// Fan Patterns
+// =============================================================================
+
+struct FanPatternsBindData : public TableFunctionData { int64_t min_fan = 5; int64_t window = 0; };
+struct FanPatternsGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, timestamps;
+  std::vector<int64_t> result_nodes, result_directions, result_fan_sizes, result_edge_counts, result_starts, result_ends;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+// Indexed by the direction codes returned from onager_compute_fan_patterns
+static const char *const FAN_DIRECTION_NAMES[] = {"in", "out"};
+
+static unique_ptr<FunctionData> FanPatternsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<FanPatternsBindData>();
+  CheckInt64Input(input, "onager_pat_fan", 3);
+  if (input.input_table_types[2] != LogicalType::BIGINT) {
+    throw InvalidInputException("Timestamp column must be BIGINT. Please convert it (e.g. epoch(ts)::bigint)");
+  }
+  auto window = input.named_parameters.find("window");
+  if (window == input.named_parameters.end() || window->second.IsNull()) throw InvalidInputException("onager_pat_fan requires window := ...");
+  bd->window = window->second.GetValue<int64_t>();
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "min_fan") bd->min_fan = kv.second.GetValue<int64_t>();
+  }
+  if (bd->min_fan <= 0) throw InvalidInputException("onager_pat_fan requires min_fan to be positive");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("direction");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("fan_size");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("edge_count");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("window_start");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("window_end");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> FanPatternsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<FanPatternsGlobalState>(); }
+static OperatorResultType FanPatternsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<FanPatternsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto t = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.timestamps.push_back(t[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType FanPatternsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<FanPatternsBindData>(); auto &gs = data.global_state->Cast<FanPatternsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_fan_patterns(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.min_fan, bd.window, nullptr, nullptr, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Fan patterns failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_directions.resize(nc); gs.result_fan_sizes.resize(nc); gs.result_edge_counts.resize(nc); gs.result_starts.resize(nc); gs.result_ends.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_fan_patterns(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.min_fan, bd.window,
+        gs.result_nodes.data(), gs.result_directions.data(), gs.result_fan_sizes.data(), gs.result_edge_counts.data(), gs.result_starts.data(), gs.result_ends.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Fan patterns");
+    gs.result_nodes.resize(written); gs.result_directions.resize(written); gs.result_fan_sizes.resize(written); gs.result_edge_counts.resize(written); gs.result_starts.resize(written); gs.result_ends.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto dir = GetFlatVectorDataWritable<string_t>(output.data[1]);
+  auto f = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto e = GetFlatVectorDataWritable<int64_t>(output.data[3]);
+  auto ws = GetFlatVectorDataWritable<int64_t>(output.data[4]); auto we = GetFlatVectorDataWritable<int64_t>(output.data[5]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    n[i] = gs.result_nodes[k]; f[i] = gs.result_fan_sizes[k]; e[i] = gs.result_edge_counts[k]; ws[i] = gs.result_starts[k]; we[i] = gs.result_ends[k];
+    dir[i] = StringVector::AddString(output.data[1], FAN_DIRECTION_NAMES[gs.result_directions[k]]);
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Activity Bursts
+// =============================================================================
+
+struct BurstsBindData : public TableFunctionData { int64_t window = 0; double zscore_threshold = 3.0; };
+struct BurstsGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, timestamps;
+  std::vector<int64_t> result_nodes, result_starts, result_activities; std::vector<double> result_zscores;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> BurstsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<BurstsBindData>();
+  CheckInt64Input(input, "onager_pat_bursts", 3);
+  if (input.input_table_types[2] != LogicalType::BIGINT) {
+    throw InvalidInputException("Timestamp column must be BIGINT. Please convert it (e.g. epoch(ts)::bigint)");
+  }
+  auto window = input.named_parameters.find("window");
+  if (window == input.named_parameters.end() || window->second.IsNull()) throw InvalidInputException("onager_pat_bursts requires window := ...");
+  bd->window = window->second.GetValue<int64_t>();
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "zscore_threshold") bd->zscore_threshold = kv.second.GetValue<double>();
+  }
+  if (bd->window <= 0) throw InvalidInputException("onager_pat_bursts requires window to be positive");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("window_start");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("activity");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("zscore");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> BurstsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<BurstsGlobalState>(); }
+static OperatorResultType BurstsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<BurstsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto t = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.timestamps.push_back(t[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType BurstsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<BurstsBindData>(); auto &gs = data.global_state->Cast<BurstsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_detect_bursts(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.window, bd.zscore_threshold, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Bursts failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_starts.resize(nc); gs.result_activities.resize(nc); gs.result_zscores.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_detect_bursts(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.window, bd.zscore_threshold,
+        gs.result_nodes.data(), gs.result_starts.data(), gs.result_activities.data(), gs.result_zscores.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Bursts");
+    gs.result_nodes.resize(written); gs.result_starts.resize(written); gs.result_activities.resize(written); gs.result_zscores.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto ws = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto a = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto z = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    n[i] = gs.result_nodes[k]; ws[i] = gs.result_starts[k]; a[i] = gs.result_activities[k]; z[i] = gs.result_zscores[k];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Weighted Cycles
+// =============================================================================
+
+struct WeightedCyclesBindData : public TableFunctionData { int64_t max_len = 4; double tolerance = 0.1; int64_t max_cycles = 1000; };
+struct WeightedCyclesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes; std::vector<double> weights;
+  std::vector<int64_t> result_cycles, result_positions, result_nodes; std::vector<double> result_weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> WeightedCyclesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WeightedCyclesBindData>();
+  CheckInt64Input(input, "onager_pat_weighted_cycles", 3);
+  if (input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "max_len") bd->max_len = kv.second.GetValue<int64_t>();
+    else if (kv.first == "tolerance") bd->tolerance = kv.second.GetValue<double>();
+    else if (kv.first == "max_cycles") bd->max_cycles = kv.second.GetValue<int64_t>();
+  }
+  if (bd->max_len < 2) throw InvalidInputException("onager_pat_weighted_cycles requires max_len to be at least 2");
+  if (bd->max_cycles <= 0) throw InvalidInputException("onager_pat_weighted_cycles requires max_cycles to be positive");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("cycle_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("position");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> WeightedCyclesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<WeightedCyclesGlobalState>(); }
+static OperatorResultType WeightedCyclesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<WeightedCyclesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType WeightedCyclesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<WeightedCyclesBindData>(); auto &gs = data.global_state->Cast<WeightedCyclesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_find_weighted_cycles(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), bd.max_len, bd.tolerance, bd.max_cycles, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Weighted cycles failed: " + GetOnagerError());
+    gs.result_cycles.resize(nc); gs.result_positions.resize(nc); gs.result_nodes.resize(nc); gs.result_weights.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_find_weighted_cycles(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), bd.max_len, bd.tolerance, bd.max_cycles,
+        gs.result_cycles.data(), gs.result_positions.data(), gs.result_nodes.data(), gs.result_weights.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Weighted cycles");
+    gs.result_cycles.resize(written); gs.result_positions.resize(written); gs.result_nodes.resize(written); gs.result_weights.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto c = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto p = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto w = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    c[i] = gs.result_cycles[k]; p[i] = gs.result_positions[k]; n[i] = gs.result_nodes[k]; w[i] = gs.result_weights[k];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterPatternFunctions(ExtensionLoader &loader) {
+  TableFunction fan("onager_pat_fan", {LogicalType::TABLE}, nullptr, FanPatternsBind, FanPatternsInitGlobal);
+  fan.in_out_function = FanPatternsInOut;
+  fan.in_out_function_final = FanPatternsFinal;
+  fan.named_parameters["min_fan"] = LogicalType::BIGINT;
+  fan.named_parameters["window"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(fan);
+  loader.RegisterFunction(fan);
+
+  TableFunction bursts("onager_pat_bursts", {LogicalType::TABLE}, nullptr, BurstsBind, BurstsInitGlobal);
+  bursts.in_out_function = BurstsInOut;
+  bursts.in_out_function_final = BurstsFinal;
+  bursts.named_parameters["window"] = LogicalType::BIGINT;
+  bursts.named_parameters["zscore_threshold"] = LogicalType::DOUBLE;
+  ONAGER_SET_NO_ORDER(bursts);
+  loader.RegisterFunction(bursts);
+
+  TableFunction cycles("onager_pat_weighted_cycles", {LogicalType::TABLE}, nullptr, WeightedCyclesBind, WeightedCyclesInitGlobal);
+  cycles.in_out_function = WeightedCyclesInOut;
+  cycles.in_out_function_final = WeightedCyclesFinal;
+  cycles.named_parameters["max_len"] = LogicalType::BIGINT;
+  cycles.named_parameters["tolerance"] = LogicalType::DOUBLE;
+  cycles.named_parameters["max_cycles"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(cycles);
+  loader.RegisterFunction(cycles);
+}
+
+} // namespace onager
+} // namespace duckdb
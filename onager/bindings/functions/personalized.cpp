@@ -4,6 +4,7 @@
  */
 #include "functions.hpp"
 #include <mutex>
+#include <unordered_set>
 
 namespace duckdb {
 
@@ -17,23 +18,31 @@ struct PersonalizedPageRankBindData : public TableFunctionData {
   double damping = 0.85;
   int64_t max_iter = 100;
   double tolerance = 1e-6;
+  bool uniform = false;
+  EdgeTypeWeights types;
+  ScoreSelection selection;
 };
 struct PersonalizedPageRankGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, pers_nodes, result_nodes;
   std::vector<double> pers_weights, result_scores;
+  EdgeTypeCodes type_codes;
   idx_t output_idx = 0; bool computed = false;
   idx_t MaxThreads() const override { return 1; }
 };
 
 static unique_ptr<FunctionData> PersonalizedPageRankBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<PersonalizedPageRankBindData>();
-  CheckInt64Input(input, "onager_ctr_personalized_pagerank", 4);
   for (auto &kv : input.named_parameters) {
     if (kv.first == "damping") bd->damping = kv.second.GetValue<double>();
     if (kv.first == "max_iter") bd->max_iter = kv.second.GetValue<int64_t>();
     if (kv.first == "tolerance") bd->tolerance = kv.second.GetValue<double>();
+    if (kv.first == "uniform") bd->uniform = kv.second.GetValue<bool>();
   }
+  // With uniform teleportation the weight column is optional
+  CheckInt64Input(input, "onager_ctr_personalized_pagerank", bd->uniform ? 3 : 4);
+  BindEdgeTypeWeights(input, bd->types, bd->uniform ? 3 : 4, "onager_ctr_personalized_pagerank");
+  BindScoreSelection(input, bd->selection, {"score"}, "onager_ctr_personalized_pagerank");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("score");
   return std::move(bd);
@@ -43,18 +52,31 @@ static unique_ptr<GlobalTableFunctionState> PersonalizedPageRankInitGlobal(Clien
   return make_uniq<PersonalizedPageRankGlobalState>();
 }
 
+// Rows with NULL src or dst only contribute personalization, and rows with a
+// NULL personalization node only contribute an edge. This lets callers union
+// an edge table with an aggregated (node, weight) table.
 static OperatorResultType PersonalizedPageRankInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<PersonalizedPageRankBindData>();
   auto &gs = data.global_state->Cast<PersonalizedPageRankGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   auto s = FlatVector::GetData<int64_t>(input.data[0]);
   auto d = FlatVector::GetData<int64_t>(input.data[1]);
   auto pn = FlatVector::GetData<int64_t>(input.data[2]);
-  auto pw = FlatVector::GetData<double>(input.data[3]);
+  bool has_weights = !bd.uniform;
+  auto pw = has_weights ? FlatVector::GetData<double>(input.data[3]) : nullptr;
   for (idx_t i = 0; i < input.size(); i++) {
-    gs.src_nodes.push_back(s[i]);
-    gs.dst_nodes.push_back(d[i]);
-    gs.pers_nodes.push_back(pn[i]);
-    gs.pers_weights.push_back(pw[i]);
+    if (!FlatVector::IsNull(input.data[0], i) && !FlatVector::IsNull(input.data[1], i)) {
+      gs.src_nodes.push_back(s[i]);
+      gs.dst_nodes.push_back(d[i]);
+      if (bd.types.enabled) gs.type_codes.Add(input.data[input.ColumnCount() - 1], i);
+    }
+    if (FlatVector::IsNull(input.data[2], i)) continue;
+    if (bd.uniform) {
+      gs.pers_nodes.push_back(pn[i]);
+    } else if (!FlatVector::IsNull(input.data[3], i)) {
+      gs.pers_nodes.push_back(pn[i]);
+      gs.pers_weights.push_back(pw[i]);
+    }
   }
   output.SetCardinality(0);
   return OperatorResultType::NEED_MORE_INPUT;
@@ -66,16 +88,56 @@ static OperatorFinalizeResultType PersonalizedPageRankFinal(ExecutionContext &ct
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
-    int64_t nc = ::onager::onager_compute_personalized_pagerank(
-      gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(),
-      gs.pers_nodes.data(), gs.pers_weights.data(), gs.pers_nodes.size(),
-      bd.damping, bd.max_iter, bd.tolerance, nullptr, nullptr);
-    if (nc < 0) throw InvalidInputException("Personalized PageRank failed: " + GetOnagerError());
-    gs.result_nodes.resize(nc); gs.result_scores.resize(nc);
-    ::onager::onager_compute_personalized_pagerank(
-      gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(),
-      gs.pers_nodes.data(), gs.pers_weights.data(), gs.pers_nodes.size(),
-      bd.damping, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_scores.data());
+    if (bd.types.enabled) {
+      // Uniform teleportation counts each subset node once
+      if (bd.uniform) {
+        std::unordered_set<int64_t> seen;
+        std::vector<int64_t> subset;
+        for (auto node : gs.pers_nodes) if (seen.insert(node).second) subset.push_back(node);
+        gs.pers_nodes.swap(subset);
+        if (gs.pers_nodes.empty()) throw InvalidInputException("Personalized PageRank failed: Teleport subset must not be empty");
+        gs.pers_weights.assign(gs.pers_nodes.size(), 1.0);
+      }
+      auto type_ids = gs.type_codes.TypeIds(bd.types);
+      int64_t nc = ::onager::onager_compute_typed_personalized_pagerank(
+        gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), gs.src_nodes.size(),
+        type_ids.data(), bd.types.multipliers.data(), type_ids.size(),
+        gs.pers_nodes.data(), gs.pers_weights.data(), gs.pers_nodes.size(),
+        bd.damping, bd.max_iter, bd.tolerance, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Personalized PageRank failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_scores.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_typed_personalized_pagerank(
+        gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), gs.src_nodes.size(),
+        type_ids.data(), bd.types.multipliers.data(), type_ids.size(),
+        gs.pers_nodes.data(), gs.pers_weights.data(), gs.pers_nodes.size(),
+        bd.damping, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_scores.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Personalized PageRank");
+      gs.result_nodes.resize(written); gs.result_scores.resize(written);
+    } else if (bd.uniform) {
+      int64_t nc = ::onager::onager_compute_personalized_pagerank_uniform(
+        gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(),
+        gs.pers_nodes.data(), gs.pers_nodes.size(),
+        bd.damping, bd.max_iter, bd.tolerance, nullptr, nullptr, 0);
+      if (nc < 0) throw InvalidInputException("Personalized PageRank failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_scores.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_personalized_pagerank_uniform(
+        gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(),
+        gs.pers_nodes.data(), gs.pers_nodes.size(),
+        bd.damping, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_scores.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Personalized PageRank");
+      gs.result_nodes.resize(written); gs.result_scores.resize(written);
+    } else {
+      int64_t nc = ::onager::onager_compute_personalized_pagerank(
+        gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(),
+        gs.pers_nodes.data(), gs.pers_weights.data(), gs.pers_nodes.size(),
+        bd.damping, bd.max_iter, bd.tolerance, nullptr, nullptr);
+      if (nc < 0) throw InvalidInputException("Personalized PageRank failed: " + GetOnagerError());
+      gs.result_nodes.resize(nc); gs.result_scores.resize(nc);
+      size_t written = CheckOnagerWrite(::onager::onager_compute_personalized_pagerank_v2(
+        gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(),
+        gs.pers_nodes.data(), gs.pers_weights.data(), gs.pers_nodes.size(),
+        bd.damping, bd.max_iter, bd.tolerance, gs.result_nodes.data(), gs.result_scores.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Personalized PageRank");
+      gs.result_nodes.resize(written); gs.result_scores.resize(written);
+    }
+    ApplyScoreSelection(bd.selection, gs.result_nodes, {&gs.result_scores}, "Personalized PageRank");
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -104,6 +166,9 @@ void RegisterPersonalizedFunctions(ExtensionLoader &loader) {
   pers_pr.named_parameters["damping"] = LogicalType::DOUBLE;
   pers_pr.named_parameters["max_iter"] = LogicalType::BIGINT;
   pers_pr.named_parameters["tolerance"] = LogicalType::DOUBLE;
+  pers_pr.named_parameters["uniform"] = LogicalType::BOOLEAN;
+  pers_pr.named_parameters["type_weights"] = LogicalType::MAP(LogicalType::VARCHAR, LogicalType::DOUBLE);
+  AddScoreSelectionParameters(pers_pr);
   ONAGER_SET_NO_ORDER(pers_pr);
   loader.RegisterFunction(pers_pr);
 }
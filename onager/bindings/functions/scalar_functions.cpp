@@ -2,7 +2,8 @@
  * @file scalar_functions.cpp
  * @brief Scalar functions for the Onager DuckDB extension.
  *
- * This file contains utility and graph management scalar functions.
+ * This file contains utility, graph management, and contraction hierarchy
+ * scalar functions.
  */
 #include "functions.hpp"
 
@@ -22,6 +23,12 @@ static void GetVersion(DataChunk &args, ExpressionState &state, Vector &result)
   ::onager::onager_free(version_c);
 }
 
+static void GetAbiVersion(DataChunk &args, ExpressionState &state, Vector &result) {
+  result.SetVectorType(VectorType::CONSTANT_VECTOR);
+  GetConstantVectorDataWritable<int32_t>(result)[0] = static_cast<int32_t>(::onager::onager_abi_version());
+  ConstantVector::SetNull(result, false);
+}
+
 static void GetLastError(DataChunk &args, ExpressionState &state, Vector &result) {
   result.SetVectorType(VectorType::CONSTANT_VECTOR);
   const char *err = ::onager::onager_last_error();
@@ -185,11 +192,201 @@ static void GetEdgeCount(DataChunk &args, ExpressionState &state, Vector &result
   }
 }
 
+static void GetGraphInfo(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat name_data;
+  args.data[0].ToUnifiedFormat(count, name_data);
+
+  auto result_data = GetFlatVectorDataWritable<string_t>(result);
+  auto &result_validity = GetFlatVectorValidityWritable(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto name = ((string_t*)name_data.data)[name_data.sel->get_index(i)];
+    char *json = ::onager::onager_graph_info(name.GetString().c_str());
+    if (!json) {
+      result_validity.SetInvalid(i);
+    } else {
+      result_data[i] = StringVector::AddString(result, json);
+      ::onager::onager_free(json);
+    }
+  }
+}
+
+static void SameComponent(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat name_data, a_data, b_data;
+  args.data[0].ToUnifiedFormat(count, name_data);
+  args.data[1].ToUnifiedFormat(count, a_data);
+  args.data[2].ToUnifiedFormat(count, b_data);
+
+  auto result_data = GetFlatVectorDataWritable<bool>(result);
+  auto &result_validity = GetFlatVectorValidityWritable(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto name = ((string_t*)name_data.data)[name_data.sel->get_index(i)];
+    auto a = ((int64_t*)a_data.data)[a_data.sel->get_index(i)];
+    auto b = ((int64_t*)b_data.data)[b_data.sel->get_index(i)];
+    int32_t same = ::onager::onager_same_component(name.GetString().c_str(), a, b);
+    if (same < 0) {
+      result_validity.SetInvalid(i);
+    } else {
+      result_data[i] = same == 1;
+    }
+  }
+}
+
+static void GetComponentCount(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat name_data;
+  args.data[0].ToUnifiedFormat(count, name_data);
+
+  auto result_data = GetFlatVectorDataWritable<int64_t>(result);
+  auto &result_validity = GetFlatVectorValidityWritable(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto name = ((string_t*)name_data.data)[name_data.sel->get_index(i)];
+    int64_t components = ::onager::onager_component_count(name.GetString().c_str());
+    if (components < 0) {
+      result_validity.SetInvalid(i);
+    } else {
+      result_data[i] = components;
+    }
+  }
+}
+
+static void GetGraphVersion(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat name_data;
+  args.data[0].ToUnifiedFormat(count, name_data);
+
+  auto result_data = GetFlatVectorDataWritable<int64_t>(result);
+  auto &result_validity = GetFlatVectorValidityWritable(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto name = ((string_t*)name_data.data)[name_data.sel->get_index(i)];
+    int64_t version = ::onager::onager_graph_version(name.GetString().c_str());
+    if (version < 0) {
+      result_validity.SetInvalid(i);
+    } else {
+      result_data[i] = version;
+    }
+  }
+}
+
+static void GraphCentrality(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat name_data, algo_data, params_data;
+  args.data[0].ToUnifiedFormat(count, name_data);
+  args.data[1].ToUnifiedFormat(count, algo_data);
+  args.data[2].ToUnifiedFormat(count, params_data);
+
+  auto result_data = GetFlatVectorDataWritable<string_t>(result);
+  auto &result_validity = GetFlatVectorValidityWritable(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto name = ((string_t*)name_data.data)[name_data.sel->get_index(i)];
+    auto algo = ((string_t*)algo_data.data)[algo_data.sel->get_index(i)];
+    auto params = ((string_t*)params_data.data)[params_data.sel->get_index(i)];
+    char *json = ::onager::onager_graph_centrality(name.GetString().c_str(), algo.GetString().c_str(), params.GetString().c_str());
+    if (!json) {
+      result_validity.SetInvalid(i);
+    } else {
+      result_data[i] = StringVector::AddString(result, json);
+      ::onager::onager_free(json);
+    }
+  }
+}
+
+// =============================================================================
+// Contraction Hierarchy Scalar Functions
+// =============================================================================
+
+static void BuildChIndex(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat name_data;
+  args.data[0].ToUnifiedFormat(count, name_data);
+
+  auto result_data = GetFlatVectorDataWritable<int64_t>(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto name = ((string_t*)name_data.data)[name_data.sel->get_index(i)];
+    result_data[i] = ::onager::onager_build_ch_index(name.GetString().c_str());
+  }
+}
+
+static void ChDistance(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat name_data, src_data, dst_data;
+  args.data[0].ToUnifiedFormat(count, name_data);
+  args.data[1].ToUnifiedFormat(count, src_data);
+  args.data[2].ToUnifiedFormat(count, dst_data);
+
+  auto result_data = GetFlatVectorDataWritable<double>(result);
+  auto &result_validity = GetFlatVectorValidityWritable(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto name = ((string_t*)name_data.data)[name_data.sel->get_index(i)];
+    auto src = ((int64_t*)src_data.data)[src_data.sel->get_index(i)];
+    auto dst = ((int64_t*)dst_data.data)[dst_data.sel->get_index(i)];
+    double distance = ::onager::onager_ch_distance(name.GetString().c_str(), src, dst);
+    if (std::isnan(distance)) {
+      result_validity.SetInvalid(i);
+    } else {
+      result_data[i] = distance;
+    }
+  }
+}
+
+// =============================================================================
+// Background Job Scalar Functions
+// =============================================================================
+
+static void SubmitJob(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat algo_data, params_data;
+  args.data[0].ToUnifiedFormat(count, algo_data);
+  args.data[1].ToUnifiedFormat(count, params_data);
+
+  auto result_data = GetFlatVectorDataWritable<int64_t>(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto algo = ((string_t*)algo_data.data)[algo_data.sel->get_index(i)];
+    auto params = ((string_t*)params_data.data)[params_data.sel->get_index(i)];
+    result_data[i] = ::onager::onager_submit_job(algo.GetString().c_str(), params.GetString().c_str());
+  }
+}
+
+// Shared body for job functions that return an Onager-allocated string or null
+template <char *(*JOB_FN)(int64_t)>
+static void JobStringFunction(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat id_data;
+  args.data[0].ToUnifiedFormat(count, id_data);
+
+  auto result_data = GetFlatVectorDataWritable<string_t>(result);
+  auto &result_validity = GetFlatVectorValidityWritable(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto id = ((int64_t*)id_data.data)[id_data.sel->get_index(i)];
+    char *value = JOB_FN(id);
+    if (!value) {
+      result_validity.SetInvalid(i);
+    } else {
+      result_data[i] = StringVector::AddString(result, value);
+      ::onager::onager_free(value);
+    }
+  }
+}
+
+static void DropJob(DataChunk &args, ExpressionState &state, Vector &result) {
+  auto count = args.size();
+  UnifiedVectorFormat id_data;
+  args.data[0].ToUnifiedFormat(count, id_data);
+
+  auto result_data = GetFlatVectorDataWritable<int32_t>(result);
+  for (idx_t i = 0; i < count; i++) {
+    auto id = ((int64_t*)id_data.data)[id_data.sel->get_index(i)];
+    result_data[i] = ::onager::onager_drop_job(id);
+  }
+}
+
 namespace onager {
 
 void RegisterScalarFunctions(ExtensionLoader &loader) {
   // Version and error functions
   loader.RegisterFunction(ScalarFunction("onager_version", {}, LogicalType::VARCHAR, GetVersion));
+  loader.RegisterFunction(ScalarFunction("onager_abi_version", {}, LogicalType::INTEGER, GetAbiVersion));
   loader.RegisterFunction(ScalarFunction("onager_last_error", {}, LogicalType::VARCHAR, GetLastError));
 
   // Graph management functions
@@ -207,12 +404,42 @@ void RegisterScalarFunctions(ExtensionLoader &loader) {
       {LogicalType::VARCHAR}, LogicalType::BIGINT, GetNodeCount));
   loader.RegisterFunction(ScalarFunction("onager_edge_count",
       {LogicalType::VARCHAR}, LogicalType::BIGINT, GetEdgeCount));
+  loader.RegisterFunction(ScalarFunction("onager_graph_info",
+      {LogicalType::VARCHAR}, LogicalType::VARCHAR, GetGraphInfo));
 
   // Node degree functions
   loader.RegisterFunction(ScalarFunction("onager_node_in_degree",
       {LogicalType::VARCHAR, LogicalType::BIGINT}, LogicalType::BIGINT, GetNodeInDegree));
   loader.RegisterFunction(ScalarFunction("onager_node_out_degree",
       {LogicalType::VARCHAR, LogicalType::BIGINT}, LogicalType::BIGINT, GetNodeOutDegree));
+
+  // Incremental component functions
+  loader.RegisterFunction(ScalarFunction("onager_same_component",
+      {LogicalType::VARCHAR, LogicalType::BIGINT, LogicalType::BIGINT}, LogicalType::BOOLEAN, SameComponent));
+  loader.RegisterFunction(ScalarFunction("onager_component_count",
+      {LogicalType::VARCHAR}, LogicalType::BIGINT, GetComponentCount));
+
+  // Graph version and cached centrality functions
+  loader.RegisterFunction(ScalarFunction("onager_graph_version",
+      {LogicalType::VARCHAR}, LogicalType::BIGINT, GetGraphVersion));
+  loader.RegisterFunction(ScalarFunction("onager_graph_centrality",
+      {LogicalType::VARCHAR, LogicalType::VARCHAR, LogicalType::VARCHAR}, LogicalType::VARCHAR, GraphCentrality));
+
+  // Contraction hierarchy functions
+  loader.RegisterFunction(ScalarFunction("onager_build_ch_index",
+      {LogicalType::VARCHAR}, LogicalType::BIGINT, BuildChIndex));
+  loader.RegisterFunction(ScalarFunction("onager_ch_distance",
+      {LogicalType::VARCHAR, LogicalType::BIGINT, LogicalType::BIGINT}, LogicalType::DOUBLE, ChDistance));
+
+  // Background job functions
+  loader.RegisterFunction(ScalarFunction("onager_submit_job",
+      {LogicalType::VARCHAR, LogicalType::VARCHAR}, LogicalType::BIGINT, SubmitJob));
+  loader.RegisterFunction(ScalarFunction("onager_job_status",
+      {LogicalType::BIGINT}, LogicalType::VARCHAR, JobStringFunction<::onager::onager_job_status>));
+  loader.RegisterFunction(ScalarFunction("onager_job_result",
+      {LogicalType::BIGINT}, LogicalType::VARCHAR, JobStringFunction<::onager::onager_job_result>));
+  loader.RegisterFunction(ScalarFunction("onager_drop_job",
+      {LogicalType::BIGINT}, LogicalType::INTEGER, DropJob));
 }
 
 } // namespace onager
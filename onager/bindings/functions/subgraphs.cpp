@@ -2,10 +2,13 @@
  * @file subgraphs.cpp
  * @brief Subgraph extraction table functions for Onager DuckDB extension.
  *
- * Ego Graph, K-Hop Neighbors, Induced Subgraph.
+ * Ego Graph, Ego Network Metrics, K-Hop Neighbors, Induced Subgraph, Densest Subgraph,
+ * Multilevel Coarsening, Bipartite Projection, Bipartite Matching.
  */
 #include "functions.hpp"
+#include <algorithm>
 #include <mutex>
+#include <random>
 
 namespace duckdb {
 
@@ -50,7 +53,8 @@ static OperatorFinalizeResultType EgoGraphFinal(ExecutionContext &ctx, TableFunc
     int64_t nc = ::onager::onager_compute_ego_graph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.center, bd.radius, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Ego graph failed: " + GetOnagerError());
     gs.result_src.resize(nc); gs.result_dst.resize(nc);
-    ::onager::onager_compute_ego_graph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.center, bd.radius, gs.result_src.data(), gs.result_dst.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_ego_graph_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.center, bd.radius, gs.result_src.data(), gs.result_dst.data(), gs.result_src.size()), gs.result_src.size(), "Ego graph");
+    gs.result_src.resize(written); gs.result_dst.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_src.size() - gs.output_idx;
@@ -62,6 +66,80 @@ static OperatorFinalizeResultType EgoGraphFinal(ExecutionContext &ctx, TableFunc
   return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Ego Network Metrics
+// =============================================================================
+
+struct EgoMetricsBindData : public TableFunctionData { int64_t center = 0; int64_t radius = 1; bool directed = false, weighted = false; };
+struct EgoMetricsGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  std::vector<double> weights;
+  int64_t counts[2] = {0, 0}; double metrics[5] = {0.0, 0.0, 0.0, 0.0, 0.0};
+  bool computed = false, emitted = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> EgoMetricsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<EgoMetricsBindData>();
+  CheckInt64Input(input, "onager_sub_ego_metrics");
+  auto center = input.named_parameters.find("center");
+  if (center == input.named_parameters.end() || center->second.IsNull()) throw InvalidInputException("onager_sub_ego_metrics requires center := ...");
+  bd->center = center->second.GetValue<int64_t>();
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "radius") bd->radius = kv.second.GetValue<int64_t>();
+    else if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+  }
+  if (bd->radius < 0) throw InvalidInputException("onager_sub_ego_metrics requires radius to be non-negative");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("center");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("size");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("density");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("broker_score");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("tie_count");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("tie_strength_mean");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("tie_strength_min");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("tie_strength_max");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> EgoMetricsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<EgoMetricsGlobalState>(); }
+static OperatorResultType EgoMetricsInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<EgoMetricsBindData>(); auto &gs = data.global_state->Cast<EgoMetricsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType EgoMetricsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<EgoMetricsBindData>(); auto &gs = data.global_state->Cast<EgoMetricsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; gs.emitted = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_ego_metrics(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(),
+        bd.center, bd.radius, bd.directed, gs.counts, gs.metrics);
+    if (nc < 0) throw InvalidInputException("Ego metrics failed: " + GetOnagerError());
+    gs.computed = true;
+  }
+  if (gs.emitted) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<int64_t>(output.data[0])[0] = bd.center;
+  GetFlatVectorDataWritable<int64_t>(output.data[1])[0] = gs.counts[0];
+  GetFlatVectorDataWritable<double>(output.data[2])[0] = gs.metrics[0];
+  GetFlatVectorDataWritable<double>(output.data[3])[0] = gs.metrics[1];
+  GetFlatVectorDataWritable<int64_t>(output.data[4])[0] = gs.counts[1];
+  // Tie strength statistics are NaN when the center has no ties
+  for (idx_t k = 2; k < 5; k++) {
+    if (std::isnan(gs.metrics[k])) FlatVector::SetNull(output.data[k + 3], 0, true);
+    else GetFlatVectorDataWritable<double>(output.data[k + 3])[0] = gs.metrics[k];
+  }
+  gs.emitted = true; output.SetCardinality(1);
+  return OperatorFinalizeResultType::FINISHED;
+}
+
 // =============================================================================
 // K-Hop Neighbors
 // =============================================================================
@@ -100,7 +178,8 @@ static OperatorFinalizeResultType KHopFinal(ExecutionContext &ctx, TableFunction
     int64_t nc = ::onager::onager_compute_k_hop_neighbors(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.start, bd.k, nullptr);
     if (nc < 0) throw InvalidInputException("K-hop neighbors failed: " + GetOnagerError());
     gs.result_nodes.resize(nc);
-    ::onager::onager_compute_k_hop_neighbors(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.start, bd.k, gs.result_nodes.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_k_hop_neighbors_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.start, bd.k, gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "K-hop neighbors");
+    gs.result_nodes.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -151,7 +230,283 @@ static OperatorFinalizeResultType InducedSubgraphFinal(ExecutionContext &ctx, Ta
     int64_t nc = ::onager::onager_compute_induced_subgraph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.filter_nodes.data(), gs.filter_nodes.size(), nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Induced subgraph failed: " + GetOnagerError());
     gs.result_src.resize(nc); gs.result_dst.resize(nc);
-    ::onager::onager_compute_induced_subgraph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.filter_nodes.data(), gs.filter_nodes.size(), gs.result_src.data(), gs.result_dst.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_induced_subgraph_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.filter_nodes.data(), gs.filter_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_src.size()), gs.result_src.size(), "Induced subgraph");
+    gs.result_src.resize(written); gs.result_dst.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Densest Subgraph
+// =============================================================================
+
+struct DensestSubgraphBindData : public TableFunctionData { bool exact = false; };
+struct DensestSubgraphGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  double density = 0.0;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> DensestSubgraphBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<DensestSubgraphBindData>();
+  CheckInt64Input(input, "onager_sub_densest");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "exact") bd->exact = kv.second.GetValue<bool>();
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("density");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> DensestSubgraphInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<DensestSubgraphGlobalState>(); }
+static OperatorResultType DensestSubgraphInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<DensestSubgraphGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType DensestSubgraphFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<DensestSubgraphBindData>(); auto &gs = data.global_state->Cast<DensestSubgraphGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_densest_subgraph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.exact, nullptr, 0, nullptr);
+    if (nc < 0) throw InvalidInputException("Densest subgraph failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_densest_subgraph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.exact, gs.result_nodes.data(), gs.result_nodes.size(), &gs.density), gs.result_nodes.size(), "Densest subgraph");
+    gs.result_nodes.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto q = GetFlatVectorDataWritable<double>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; q[i] = gs.density; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Multilevel Coarsening
+// =============================================================================
+
+struct CoarsenBindData : public TableFunctionData { int64_t levels = 1; std::string method = "heavy_edge"; int64_t seed = -1; bool weighted = false; };
+struct CoarsenGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_edge_levels, result_src, result_dst, result_map_levels, result_fine, result_coarse;
+  std::vector<double> weights, result_weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<CoarsenBindData> CoarsenBindParams(TableFunctionBindInput &input, const std::string &name) {
+  auto bd = make_uniq<CoarsenBindData>();
+  CheckInt64Input(input, name);
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "levels") bd->levels = kv.second.GetValue<int64_t>();
+    else if (kv.first == "method") bd->method = StringUtil::Lower(kv.second.GetValue<string>());
+    else if (kv.first == "seed") bd->seed = kv.second.GetValue<int64_t>();
+  }
+  if (bd->levels <= 0) throw InvalidInputException(name + " requires levels to be positive");
+  if (bd->method != "heavy_edge" && bd->method != "random") throw InvalidInputException(name + " method must be 'heavy_edge' or 'random'");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  return bd;
+}
+static unique_ptr<FunctionData> CoarsenBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = CoarsenBindParams(input, "onager_sub_coarsen");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("level");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
+  return std::move(bd);
+}
+static unique_ptr<FunctionData> CoarsenMapBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = CoarsenBindParams(input, "onager_sub_coarsen_map");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("level");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("coarse_id");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> CoarsenInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<CoarsenGlobalState>(); }
+static OperatorResultType CoarsenInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CoarsenBindData>(); auto &gs = data.global_state->Cast<CoarsenGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static void CoarsenCompute(const CoarsenBindData &bd, CoarsenGlobalState &gs) {
+  // Both calls must coarsen the same way, so a random seed is drawn once here
+  int64_t seed = bd.seed >= 0 ? bd.seed : static_cast<int64_t>(std::random_device{}() >> 1);
+  int64_t map_count = 0;
+  int64_t ec = ::onager::onager_coarsen_graph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.levels, bd.method.c_str(), seed,
+      nullptr, nullptr, nullptr, nullptr, 0, nullptr, nullptr, nullptr, 0, &map_count);
+  if (ec < 0) throw InvalidInputException("Graph coarsening failed: " + GetOnagerError());
+  gs.result_edge_levels.resize(ec); gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec);
+  gs.result_map_levels.resize(map_count); gs.result_fine.resize(map_count); gs.result_coarse.resize(map_count);
+  size_t written = CheckOnagerWrite(::onager::onager_coarsen_graph(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.levels, bd.method.c_str(), seed,
+      gs.result_edge_levels.data(), gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), gs.result_src.size(),
+      gs.result_map_levels.data(), gs.result_fine.data(), gs.result_coarse.data(), gs.result_fine.size(), &map_count), gs.result_src.size(), "Graph coarsening");
+  gs.result_edge_levels.resize(written); gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_weights.resize(written);
+}
+static OperatorFinalizeResultType CoarsenFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CoarsenBindData>(); auto &gs = data.global_state->Cast<CoarsenGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    CoarsenCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto l = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto s = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto d = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto w = GetFlatVectorDataWritable<double>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t r = gs.output_idx + i;
+    l[i] = gs.result_edge_levels[r]; s[i] = gs.result_src[r]; d[i] = gs.result_dst[r]; w[i] = gs.result_weights[r];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+static OperatorFinalizeResultType CoarsenMapFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<CoarsenBindData>(); auto &gs = data.global_state->Cast<CoarsenGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    CoarsenCompute(bd, gs);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_fine.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto l = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto f = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto c = GetFlatVectorDataWritable<int64_t>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t r = gs.output_idx + i;
+    l[i] = gs.result_map_levels[r]; f[i] = gs.result_fine[r]; c[i] = gs.result_coarse[r];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_fine.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Bipartite Projection
+// =============================================================================
+
+struct ProjectionBindData : public TableFunctionData { std::vector<int64_t> nodes; };
+struct ProjectionGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
+  std::vector<double> result_weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> ProjectionBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<ProjectionBindData>();
+  CheckInt64Input(input, "onager_sub_bipartite_projection");
+  auto nodes = input.named_parameters.find("project_onto");
+  if (nodes != input.named_parameters.end() && !nodes->second.IsNull()) {
+    for (auto &node : ListValue::GetChildren(nodes->second)) {
+      if (node.IsNull()) throw InvalidInputException("onager_sub_bipartite_projection project_onto must not contain NULL");
+      bd->nodes.push_back(node.GetValue<int64_t>());
+    }
+    if (bd->nodes.empty()) throw InvalidInputException("onager_sub_bipartite_projection project_onto must not be empty");
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> ProjectionInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ProjectionGlobalState>(); }
+static OperatorResultType ProjectionInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<ProjectionGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ProjectionFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ProjectionBindData>(); auto &gs = data.global_state->Cast<ProjectionGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    // Without project_onto, project onto the nodes of the src column
+    std::vector<int64_t> nodes = bd.nodes;
+    if (nodes.empty()) {
+      nodes = gs.src_nodes;
+      std::sort(nodes.begin(), nodes.end());
+      nodes.erase(std::unique(nodes.begin(), nodes.end()), nodes.end());
+    }
+    int64_t nc = ::onager::onager_compute_bipartite_projection(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nodes.data(), nodes.size(), nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Bipartite projection failed: " + GetOnagerError());
+    gs.result_src.resize(nc); gs.result_dst.resize(nc); gs.result_weights.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_bipartite_projection(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nodes.data(), nodes.size(),
+        gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), gs.result_src.size()), gs.result_src.size(), "Bipartite projection");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_weights.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Bipartite Matching
+// =============================================================================
+
+struct MatchingGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> MatchingBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  CheckInt64Input(input, "onager_sub_bipartite_matching");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  return make_uniq<TableFunctionData>();
+}
+static unique_ptr<GlobalTableFunctionState> MatchingInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<MatchingGlobalState>(); }
+static OperatorResultType MatchingInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<MatchingGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType MatchingFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &gs = data.global_state->Cast<MatchingGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t mc = ::onager::onager_compute_bipartite_matching(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, nullptr, 0);
+    if (mc < 0) throw InvalidInputException("Bipartite matching failed: " + GetOnagerError());
+    gs.result_src.resize(mc); gs.result_dst.resize(mc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_bipartite_matching(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(),
+        gs.result_src.data(), gs.result_dst.data(), gs.result_src.size()), gs.result_src.size(), "Bipartite matching");
+    gs.result_src.resize(written); gs.result_dst.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_src.size() - gs.output_idx;
@@ -178,6 +533,15 @@ void RegisterSubgraphFunctions(ExtensionLoader &loader) {
   ONAGER_SET_NO_ORDER(ego_graph);
   loader.RegisterFunction(ego_graph);
 
+  TableFunction ego_metrics("onager_sub_ego_metrics", {LogicalType::TABLE}, nullptr, EgoMetricsBind, EgoMetricsInitGlobal);
+  ego_metrics.in_out_function = EgoMetricsInOut;
+  ego_metrics.in_out_function_final = EgoMetricsFinal;
+  ego_metrics.named_parameters["center"] = LogicalType::BIGINT;
+  ego_metrics.named_parameters["radius"] = LogicalType::BIGINT;
+  ego_metrics.named_parameters["directed"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(ego_metrics);
+  loader.RegisterFunction(ego_metrics);
+
   TableFunction k_hop("onager_sub_k_hop", {LogicalType::TABLE}, nullptr, KHopBind, KHopInitGlobal);
   k_hop.in_out_function = KHopInOut;
   k_hop.in_out_function_final = KHopFinal;
@@ -191,6 +555,44 @@ void RegisterSubgraphFunctions(ExtensionLoader &loader) {
   induced.in_out_function_final = InducedSubgraphFinal;
   ONAGER_SET_NO_ORDER(induced);
   loader.RegisterFunction(induced);
+
+  TableFunction densest("onager_sub_densest", {LogicalType::TABLE}, nullptr, DensestSubgraphBind, DensestSubgraphInitGlobal);
+  densest.in_out_function = DensestSubgraphInOut;
+  densest.in_out_function_final = DensestSubgraphFinal;
+  densest.named_parameters["exact"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(densest);
+  loader.RegisterFunction(densest);
+
+  TableFunction coarsen("onager_sub_coarsen", {LogicalType::TABLE}, nullptr, CoarsenBind, CoarsenInitGlobal);
+  coarsen.in_out_function = CoarsenInOut;
+  coarsen.in_out_function_final = CoarsenFinal;
+  coarsen.named_parameters["levels"] = LogicalType::BIGINT;
+  coarsen.named_parameters["method"] = LogicalType::VARCHAR;
+  coarsen.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(coarsen);
+  loader.RegisterFunction(coarsen);
+
+  TableFunction coarsen_map("onager_sub_coarsen_map", {LogicalType::TABLE}, nullptr, CoarsenMapBind, CoarsenInitGlobal);
+  coarsen_map.in_out_function = CoarsenInOut;
+  coarsen_map.in_out_function_final = CoarsenMapFinal;
+  coarsen_map.named_parameters["levels"] = LogicalType::BIGINT;
+  coarsen_map.named_parameters["method"] = LogicalType::VARCHAR;
+  coarsen_map.named_parameters["seed"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(coarsen_map);
+  loader.RegisterFunction(coarsen_map);
+
+  TableFunction projection("onager_sub_bipartite_projection", {LogicalType::TABLE}, nullptr, ProjectionBind, ProjectionInitGlobal);
+  projection.in_out_function = ProjectionInOut;
+  projection.in_out_function_final = ProjectionFinal;
+  projection.named_parameters["project_onto"] = LogicalType::LIST(LogicalType::BIGINT);
+  ONAGER_SET_NO_ORDER(projection);
+  loader.RegisterFunction(projection);
+
+  TableFunction matching("onager_sub_bipartite_matching", {LogicalType::TABLE}, nullptr, MatchingBind, MatchingInitGlobal);
+  matching.in_out_function = MatchingInOut;
+  matching.in_out_function_final = MatchingFinal;
+  ONAGER_SET_NO_ORDER(matching);
+  loader.RegisterFunction(matching);
 }
 
 } // namespace onager
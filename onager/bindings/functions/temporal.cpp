@@ -0,0 +1,219 @@
+/**
+ * @file temporal.cpp
+ * @brief Temporal network table functions for Onager DuckDB extension.
+ *
+ * Betweenness and reachability over time-respecting paths, and session paths,
+ * in (src, dst, ts) edge tables.
+ */
+#include "functions.hpp"
+#include <limits>
+#include <mutex>
+
+namespace duckdb {
+
+using namespace onager;
+
+// Validates a (src, dst, ts) input table
+static void CheckTimestampedInput(TableFunctionBindInput &input, const string &name) {
+  CheckInt64Input(input, name, 3);
+  if (input.input_table_types[2] != LogicalType::BIGINT) {
+    throw InvalidInputException("Timestamp column must be BIGINT. Please convert it (e.g. epoch(ts)::bigint)");
+  }
+}
+
+struct TimestampedEdges {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, timestamps;
+};
+
+template <class STATE>
+static OperatorResultType TimestampedEdgesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<STATE>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto t = FlatVector::GetData<int64_t>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.timestamps.push_back(t[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+
+// =============================================================================
+// Temporal Betweenness
+// =============================================================================
+
+struct TemporalBetweennessBindData : public TableFunctionData { bool normalized = true; };
+struct TemporalBetweennessGlobalState : public GlobalTableFunctionState, public TimestampedEdges {
+  std::vector<int64_t> result_nodes; std::vector<double> result_centralities;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> TemporalBetweennessBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<TemporalBetweennessBindData>();
+  CheckTimestampedInput(input, "onager_tmp_betweenness");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "normalized") bd->normalized = kv.second.GetValue<bool>();
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("betweenness");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> TemporalBetweennessInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<TemporalBetweennessGlobalState>(); }
+static OperatorFinalizeResultType TemporalBetweennessFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<TemporalBetweennessBindData>(); auto &gs = data.global_state->Cast<TemporalBetweennessGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_temporal_betweenness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.normalized, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Temporal betweenness failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_centralities.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_temporal_betweenness(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.normalized,
+        gs.result_nodes.data(), gs.result_centralities.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Temporal betweenness");
+    gs.result_nodes.resize(written); gs.result_centralities.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<double>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx + i]; c[i] = gs.result_centralities[gs.output_idx + i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Temporal Reachability
+// =============================================================================
+
+struct TemporalReachabilityBindData : public TableFunctionData {
+  int64_t source = 0;
+  int64_t t_start = std::numeric_limits<int64_t>::min();
+  int64_t t_end = std::numeric_limits<int64_t>::max();
+};
+struct TemporalReachabilityGlobalState : public GlobalTableFunctionState, public TimestampedEdges {
+  std::vector<int64_t> result_nodes, result_arrivals;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> TemporalReachabilityBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<TemporalReachabilityBindData>();
+  CheckTimestampedInput(input, "onager_tmp_reachability");
+  auto source = input.named_parameters.find("source");
+  if (source == input.named_parameters.end() || source->second.IsNull()) throw InvalidInputException("onager_tmp_reachability requires source := ...");
+  bd->source = source->second.GetValue<int64_t>();
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "t_start") bd->t_start = kv.second.GetValue<int64_t>();
+    else if (kv.first == "t_end") bd->t_end = kv.second.GetValue<int64_t>();
+  }
+  if (bd->t_start > bd->t_end) throw InvalidInputException("onager_tmp_reachability requires t_start <= t_end");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("arrival_time");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> TemporalReachabilityInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<TemporalReachabilityGlobalState>(); }
+static OperatorFinalizeResultType TemporalReachabilityFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<TemporalReachabilityBindData>(); auto &gs = data.global_state->Cast<TemporalReachabilityGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_temporal_reachability(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.source, bd.t_start, bd.t_end, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Temporal reachability failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_arrivals.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_temporal_reachability(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.source, bd.t_start, bd.t_end,
+        gs.result_nodes.data(), gs.result_arrivals.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Temporal reachability");
+    gs.result_nodes.resize(written); gs.result_arrivals.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto a = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx + i]; a[i] = gs.result_arrivals[gs.output_idx + i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Session Paths
+// =============================================================================
+
+struct SessionPathsBindData : public TableFunctionData { int64_t session_gap = 0; };
+struct SessionPathsGlobalState : public GlobalTableFunctionState, public TimestampedEdges {
+  std::vector<int64_t> result_sessions, result_entities, result_steps, result_nodes;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> SessionPathsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<SessionPathsBindData>();
+  CheckTimestampedInput(input, "onager_tmp_paths");
+  auto gap = input.named_parameters.find("session_gap");
+  if (gap == input.named_parameters.end() || gap->second.IsNull()) throw InvalidInputException("onager_tmp_paths requires session_gap := ...");
+  bd->session_gap = gap->second.GetValue<int64_t>();
+  if (bd->session_gap < 0) throw InvalidInputException("onager_tmp_paths requires session_gap to be non-negative");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("session_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("entity_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("step");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> SessionPathsInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<SessionPathsGlobalState>(); }
+static OperatorFinalizeResultType SessionPathsFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SessionPathsBindData>(); auto &gs = data.global_state->Cast<SessionPathsGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_extract_paths(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.session_gap, nullptr, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Session paths failed: " + GetOnagerError());
+    gs.result_sessions.resize(nc); gs.result_entities.resize(nc); gs.result_steps.resize(nc); gs.result_nodes.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_extract_paths(gs.src_nodes.data(), gs.dst_nodes.data(), gs.timestamps.data(), gs.src_nodes.size(), bd.session_gap,
+        gs.result_sessions.data(), gs.result_entities.data(), gs.result_steps.data(), gs.result_nodes.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Session paths");
+    gs.result_sessions.resize(written); gs.result_entities.resize(written); gs.result_steps.resize(written); gs.result_nodes.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto ss = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto e = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  auto st = GetFlatVectorDataWritable<int64_t>(output.data[2]); auto n = GetFlatVectorDataWritable<int64_t>(output.data[3]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    ss[i] = gs.result_sessions[k]; e[i] = gs.result_entities[k]; st[i] = gs.result_steps[k]; n[i] = gs.result_nodes[k];
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterTemporalFunctions(ExtensionLoader &loader) {
+  TableFunction betweenness("onager_tmp_betweenness", {LogicalType::TABLE}, nullptr, TemporalBetweennessBind, TemporalBetweennessInitGlobal);
+  betweenness.in_out_function = TimestampedEdgesInOut<TemporalBetweennessGlobalState>;
+  betweenness.in_out_function_final = TemporalBetweennessFinal;
+  betweenness.named_parameters["normalized"] = LogicalType::BOOLEAN;
+  ONAGER_SET_NO_ORDER(betweenness);
+  loader.RegisterFunction(betweenness);
+
+  TableFunction reachability("onager_tmp_reachability", {LogicalType::TABLE}, nullptr, TemporalReachabilityBind, TemporalReachabilityInitGlobal);
+  reachability.in_out_function = TimestampedEdgesInOut<TemporalReachabilityGlobalState>;
+  reachability.in_out_function_final = TemporalReachabilityFinal;
+  reachability.named_parameters["source"] = LogicalType::BIGINT;
+  reachability.named_parameters["t_start"] = LogicalType::BIGINT;
+  reachability.named_parameters["t_end"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(reachability);
+  loader.RegisterFunction(reachability);
+
+  TableFunction paths("onager_tmp_paths", {LogicalType::TABLE}, nullptr, SessionPathsBind, SessionPathsInitGlobal);
+  paths.in_out_function = TimestampedEdgesInOut<SessionPathsGlobalState>;
+  paths.in_out_function_final = SessionPathsFinal;
+  paths.named_parameters["session_gap"] = LogicalType::BIGINT;
+  ONAGER_SET_NO_ORDER(paths);
+  loader.RegisterFunction(paths);
+}
+
+} // namespace onager
+} // namespace duckdb
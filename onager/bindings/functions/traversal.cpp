@@ -2,7 +2,9 @@
  * @file traversal.cpp
  * @brief Traversal and path table functions for Onager DuckDB extension.
  *
- * Dijkstra, BFS, DFS, Bellman-Ford, Floyd-Warshall.
+ * Dijkstra, BFS, DFS, topological sort, DAG check, Bellman-Ford, shortest path between two nodes, Floyd-Warshall, shortest path tree, nodes within a cost budget, shortest distance,
+ * widest path, paths over allowed edge types, pairwise distances among a node subset, Voronoi
+ * partitions from seed nodes, and spilling all-pairs distances to disk.
  */
 #include "functions.hpp"
 #include <mutex>
@@ -15,7 +17,7 @@ using namespace onager;
 // Dijkstra Shortest Paths
 // =============================================================================
 
-struct DijkstraBindData : public TableFunctionData { int64_t source = 0; };
+struct DijkstraBindData : public TableFunctionData { int64_t source = 0; ForbiddenSet forbidden; };
 struct DijkstraGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
@@ -27,6 +29,7 @@ struct DijkstraGlobalState : public GlobalTableFunctionState {
 static unique_ptr<FunctionData> DijkstraBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<DijkstraBindData>();
   CheckInt64Input(input, "onager_pth_dijkstra");
+  BindForbidden(input, bd->forbidden, "onager_pth_dijkstra");
   for (auto &kv : input.named_parameters) if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
@@ -44,11 +47,13 @@ static OperatorFinalizeResultType DijkstraFinal(ExecutionContext &ctx, TableFunc
   auto &bd = data.bind_data->Cast<DijkstraBindData>(); auto &gs = data.global_state->Cast<DijkstraGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, "Dijkstra");
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
     int64_t nc = ::onager::onager_compute_dijkstra(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Dijkstra failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_distances.resize(nc);
-    ::onager::onager_compute_dijkstra(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_nodes.data(), gs.result_distances.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_dijkstra_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_nodes.data(), gs.result_distances.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Dijkstra");
+    gs.result_nodes.resize(written); gs.result_distances.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -64,7 +69,7 @@ static OperatorFinalizeResultType DijkstraFinal(ExecutionContext &ctx, TableFunc
 // BFS Traversal
 // =============================================================================
 
-struct BfsBindData : public TableFunctionData { int64_t source = 0; };
+struct BfsBindData : public TableFunctionData { int64_t source = 0; ForbiddenSet forbidden; };
 struct BfsGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_order;
@@ -75,6 +80,7 @@ struct BfsGlobalState : public GlobalTableFunctionState {
 static unique_ptr<FunctionData> BfsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<BfsBindData>();
   CheckInt64Input(input, "onager_trv_bfs");
+  BindForbidden(input, bd->forbidden, "onager_trv_bfs");
   for (auto &kv : input.named_parameters) if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   return std::move(bd);
@@ -91,11 +97,13 @@ static OperatorFinalizeResultType BfsFinal(ExecutionContext &ctx, TableFunctionI
   auto &bd = data.bind_data->Cast<BfsBindData>(); auto &gs = data.global_state->Cast<BfsGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, "BFS");
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
     int64_t nc = ::onager::onager_compute_bfs(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, nullptr);
     if (nc < 0) throw InvalidInputException("BFS failed: " + GetOnagerError());
     gs.result_order.resize(nc);
-    ::onager::onager_compute_bfs(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_order.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_bfs_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_order.data(), gs.result_order.size()), gs.result_order.size(), "BFS");
+    gs.result_order.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_order.size() - gs.output_idx;
@@ -111,7 +119,7 @@ static OperatorFinalizeResultType BfsFinal(ExecutionContext &ctx, TableFunctionI
 // DFS Traversal
 // =============================================================================
 
-struct DfsBindData : public TableFunctionData { int64_t source = 0; };
+struct DfsBindData : public TableFunctionData { int64_t source = 0; ForbiddenSet forbidden; };
 struct DfsGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_order;
@@ -122,6 +130,7 @@ struct DfsGlobalState : public GlobalTableFunctionState {
 static unique_ptr<FunctionData> DfsBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<DfsBindData>();
   CheckInt64Input(input, "onager_trv_dfs");
+  BindForbidden(input, bd->forbidden, "onager_trv_dfs");
   for (auto &kv : input.named_parameters) if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   return std::move(bd);
@@ -138,11 +147,13 @@ static OperatorFinalizeResultType DfsFinal(ExecutionContext &ctx, TableFunctionI
   auto &bd = data.bind_data->Cast<DfsBindData>(); auto &gs = data.global_state->Cast<DfsGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, "DFS");
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
     int64_t nc = ::onager::onager_compute_dfs(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, nullptr);
     if (nc < 0) throw InvalidInputException("DFS failed: " + GetOnagerError());
     gs.result_order.resize(nc);
-    ::onager::onager_compute_dfs(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_order.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_dfs_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), bd.source, gs.result_order.data(), gs.result_order.size()), gs.result_order.size(), "DFS");
+    gs.result_order.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_order.size() - gs.output_idx;
@@ -154,11 +165,106 @@ static OperatorFinalizeResultType DfsFinal(ExecutionContext &ctx, TableFunctionI
   return gs.output_idx >= gs.result_order.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Topological Sort
+// =============================================================================
+
+struct TopoSortBindData : public TableFunctionData { ForbiddenSet forbidden; };
+struct TopoSortGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_order;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> TopoSortBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<TopoSortBindData>();
+  CheckInt64Input(input, "onager_trv_topological_sort");
+  BindForbidden(input, bd->forbidden, "onager_trv_topological_sort");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("order");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> TopoSortInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<TopoSortGlobalState>(); }
+static OperatorResultType TopoSortInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<TopoSortGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType TopoSortFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<TopoSortBindData>(); auto &gs = data.global_state->Cast<TopoSortGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, "Topological sort");
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = ::onager::onager_compute_topological_sort(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Topological sort failed: " + GetOnagerError());
+    gs.result_order.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_topological_sort(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.result_order.data(), gs.result_order.size()), gs.result_order.size(), "Topological sort");
+    gs.result_order.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_order.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto ord = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto n = GetFlatVectorDataWritable<int64_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { ord[i] = static_cast<int64_t>(gs.output_idx + i); n[i] = gs.result_order[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_order.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// DAG Check
+// =============================================================================
+
+struct IsDagBindData : public TableFunctionData { ForbiddenSet forbidden; };
+struct IsDagGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  bool is_dag = true, computed = false, emitted = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> IsDagBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<IsDagBindData>();
+  CheckInt64Input(input, "onager_trv_is_dag");
+  BindForbidden(input, bd->forbidden, "onager_trv_is_dag");
+  rt.push_back(LogicalType::BOOLEAN); nm.push_back("is_dag");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> IsDagInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<IsDagGlobalState>(); }
+static OperatorResultType IsDagInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<IsDagGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType IsDagFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<IsDagBindData>(); auto &gs = data.global_state->Cast<IsDagGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, "DAG check");
+    if (!gs.src_nodes.empty()) {
+      int32_t result = ::onager::onager_is_dag(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size());
+      if (result < 0) throw InvalidInputException("DAG check failed: " + GetOnagerError());
+      gs.is_dag = result == 1;
+    }
+    gs.computed = true;
+  }
+  if (gs.emitted) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<bool>(output.data[0])[0] = gs.is_dag;
+  gs.emitted = true; output.SetCardinality(1);
+  return OperatorFinalizeResultType::FINISHED;
+}
+
 // =============================================================================
 // Bellman-Ford Shortest Paths (weighted)
 // =============================================================================
 
-struct BellmanFordBindData : public TableFunctionData { int64_t source = 0; };
+struct BellmanFordBindData : public WeightedBindData { int64_t source = 0; ForbiddenSet forbidden; };
 struct BellmanFordGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
@@ -170,7 +276,9 @@ struct BellmanFordGlobalState : public GlobalTableFunctionState {
 static unique_ptr<FunctionData> BellmanFordBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
   auto bd = make_uniq<BellmanFordBindData>();
   CheckInt64Input(input, "onager_pth_bellman_ford", 3);
+  BindForbidden(input, bd->forbidden, "onager_pth_bellman_ford");
   for (auto &kv : input.named_parameters) if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
+  BindWeightSemantics(input, *bd, "onager_pth_bellman_ford");
   rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
   return std::move(bd);
@@ -188,11 +296,78 @@ static OperatorFinalizeResultType BellmanFordFinal(ExecutionContext &ctx, TableF
   auto &bd = data.bind_data->Cast<BellmanFordBindData>(); auto &gs = data.global_state->Cast<BellmanFordGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Bellman-Ford");
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Bellman-Ford");
     int64_t nc = ::onager::onager_compute_bellman_ford(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), bd.source, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Bellman-Ford failed: " + GetOnagerError());
     gs.result_nodes.resize(nc); gs.result_distances.resize(nc);
-    ::onager::onager_compute_bellman_ford(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), bd.source, gs.result_nodes.data(), gs.result_distances.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_bellman_ford_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), bd.source, gs.result_nodes.data(), gs.result_distances.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Bellman-Ford");
+    gs.result_nodes.resize(written); gs.result_distances.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<double>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; d[i] = gs.result_distances[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Shortest Path Between Two Nodes
+// =============================================================================
+
+struct ShortestPathBindData : public WeightedBindData { int64_t source = 0, target = 0; bool directed = false, weighted = false; ForbiddenSet forbidden; };
+struct ShortestPathGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  std::vector<double> weights, result_distances;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> ShortestPathBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<ShortestPathBindData>();
+  CheckInt64Input(input, "onager_pth_shortest_path");
+  BindForbidden(input, bd->forbidden, "onager_pth_shortest_path");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
+    else if (kv.first == "target") bd->target = kv.second.GetValue<int64_t>();
+    else if (kv.first == "directed") bd->directed = kv.second.GetValue<bool>();
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_pth_shortest_path");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> ShortestPathInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ShortestPathGlobalState>(); }
+static OperatorResultType ShortestPathInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ShortestPathBindData>(); auto &gs = data.global_state->Cast<ShortestPathGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ShortestPathFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ShortestPathBindData>(); auto &gs = data.global_state->Cast<ShortestPathGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Shortest path");
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Shortest path");
+    int64_t nc = ::onager::onager_compute_shortest_path(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, bd.target, bd.directed, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Shortest path failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_distances.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_shortest_path(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, bd.target, bd.directed, gs.result_nodes.data(), gs.result_distances.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Shortest path");
+    gs.result_nodes.resize(written); gs.result_distances.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_nodes.size() - gs.output_idx;
@@ -208,6 +383,7 @@ static OperatorFinalizeResultType BellmanFordFinal(ExecutionContext &ctx, TableF
 // Floyd-Warshall All-Pairs Shortest Paths
 // =============================================================================
 
+struct FloydWarshallBindData : public WeightedBindData { ForbiddenSet forbidden; };
 struct FloydWarshallGlobalState : public GlobalTableFunctionState {
   std::mutex input_mutex;
   std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
@@ -217,11 +393,14 @@ struct FloydWarshallGlobalState : public GlobalTableFunctionState {
 };
 
 static unique_ptr<FunctionData> FloydWarshallBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<FloydWarshallBindData>();
   CheckInt64Input(input, "onager_pth_floyd_warshall", 3);
+  BindForbidden(input, bd->forbidden, "onager_pth_floyd_warshall");
+  BindWeightSemantics(input, *bd, "onager_pth_floyd_warshall");
   rt.push_back(LogicalType::BIGINT); nm.push_back("src");
   rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
   rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
-  return make_uniq<TableFunctionData>();
+  return std::move(bd);
 }
 static unique_ptr<GlobalTableFunctionState> FloydWarshallInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<FloydWarshallGlobalState>(); }
 static OperatorResultType FloydWarshallInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
@@ -233,14 +412,402 @@ static OperatorResultType FloydWarshallInOut(ExecutionContext &ctx, TableFunctio
   output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
 }
 static OperatorFinalizeResultType FloydWarshallFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
-  auto &gs = data.global_state->Cast<FloydWarshallGlobalState>();
+  auto &bd = data.bind_data->Cast<FloydWarshallBindData>(); auto &gs = data.global_state->Cast<FloydWarshallGlobalState>();
   std::lock_guard<std::mutex> lock(gs.input_mutex);
   if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Floyd-Warshall");
     if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Floyd-Warshall");
     int64_t nc = ::onager::onager_compute_floyd_warshall(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), nullptr, nullptr, nullptr);
     if (nc < 0) throw InvalidInputException("Floyd-Warshall failed: " + GetOnagerError());
     gs.result_src.resize(nc); gs.result_dst.resize(nc); gs.result_distances.resize(nc);
-    ::onager::onager_compute_floyd_warshall(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_distances.data());
+    size_t written = CheckOnagerWrite(::onager::onager_compute_floyd_warshall_v2(gs.src_nodes.data(), gs.dst_nodes.data(), gs.weights.data(), gs.src_nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_distances.data(), gs.result_src.size()), gs.result_src.size(), "Floyd-Warshall");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_distances.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_distances[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Shortest Path Tree
+// =============================================================================
+
+struct ShortestPathTreeBindData : public WeightedBindData { int64_t source = 0; bool weighted = false; ForbiddenSet forbidden; };
+struct ShortestPathTreeGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_parents, result_children;
+  std::vector<double> weights, result_distances;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> ShortestPathTreeBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<ShortestPathTreeBindData>();
+  CheckInt64Input(input, "onager_pth_shortest_path_tree");
+  BindForbidden(input, bd->forbidden, "onager_pth_shortest_path_tree");
+  for (auto &kv : input.named_parameters) if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_pth_shortest_path_tree");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("parent");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("child");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> ShortestPathTreeInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ShortestPathTreeGlobalState>(); }
+static OperatorResultType ShortestPathTreeInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ShortestPathTreeBindData>(); auto &gs = data.global_state->Cast<ShortestPathTreeGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ShortestPathTreeFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ShortestPathTreeBindData>(); auto &gs = data.global_state->Cast<ShortestPathTreeGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Shortest path tree");
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Shortest path tree");
+    int64_t nc = ::onager::onager_compute_shortest_path_tree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Shortest path tree failed: " + GetOnagerError());
+    gs.result_parents.resize(nc); gs.result_children.resize(nc); gs.result_distances.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_shortest_path_tree(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, gs.result_parents.data(), gs.result_children.data(), gs.result_distances.data(), gs.result_children.size()), gs.result_children.size(), "Shortest path tree");
+    gs.result_parents.resize(written); gs.result_children.resize(written); gs.result_distances.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_children.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto p = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto c = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto d = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { p[i] = gs.result_parents[gs.output_idx+i]; c[i] = gs.result_children[gs.output_idx+i]; d[i] = gs.result_distances[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_children.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Nodes Within Cost
+// =============================================================================
+
+struct WithinCostBindData : public WeightedBindData { int64_t source = 0; double max_cost = 0.0; bool weighted = false; ForbiddenSet forbidden; };
+struct WithinCostGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  std::vector<double> weights, result_distances;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> WithinCostBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WithinCostBindData>();
+  CheckInt64Input(input, "onager_pth_within_cost");
+  BindForbidden(input, bd->forbidden, "onager_pth_within_cost");
+  auto source = input.named_parameters.find("source");
+  if (source == input.named_parameters.end() || source->second.IsNull()) throw InvalidInputException("onager_pth_within_cost requires source := ...");
+  bd->source = source->second.GetValue<int64_t>();
+  auto max_cost = input.named_parameters.find("max_cost");
+  if (max_cost == input.named_parameters.end() || max_cost->second.IsNull()) throw InvalidInputException("onager_pth_within_cost requires max_cost := ...");
+  bd->max_cost = max_cost->second.GetValue<double>();
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_pth_within_cost");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> WithinCostInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<WithinCostGlobalState>(); }
+static OperatorResultType WithinCostInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<WithinCostBindData>(); auto &gs = data.global_state->Cast<WithinCostGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType WithinCostFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<WithinCostBindData>(); auto &gs = data.global_state->Cast<WithinCostGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Nodes within cost");
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Nodes within cost");
+    int64_t nc = ::onager::onager_compute_nodes_within_cost(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, bd.max_cost, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Nodes within cost failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_distances.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_nodes_within_cost(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, bd.max_cost, gs.result_nodes.data(), gs.result_distances.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Nodes within cost");
+    gs.result_nodes.resize(written); gs.result_distances.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<double>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; d[i] = gs.result_distances[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Shortest Distance
+// =============================================================================
+
+struct ShortestDistanceBindData : public WeightedBindData { int64_t source = 0, target = 0; bool weighted = false; ForbiddenSet forbidden; };
+struct ShortestDistanceGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  std::vector<double> weights;
+  double distance = 0.0; bool computed = false, emitted = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> ShortestDistanceBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<ShortestDistanceBindData>();
+  CheckInt64Input(input, "onager_pth_shortest_distance");
+  BindForbidden(input, bd->forbidden, "onager_pth_shortest_distance");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
+    else if (kv.first == "target") bd->target = kv.second.GetValue<int64_t>();
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_pth_shortest_distance");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("source");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("target");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> ShortestDistanceInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<ShortestDistanceGlobalState>(); }
+static OperatorResultType ShortestDistanceInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ShortestDistanceBindData>(); auto &gs = data.global_state->Cast<ShortestDistanceGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType ShortestDistanceFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<ShortestDistanceBindData>(); auto &gs = data.global_state->Cast<ShortestDistanceGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Shortest distance");
+    if (gs.src_nodes.empty()) { gs.computed = true; gs.emitted = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Shortest distance");
+    gs.distance = ::onager::onager_compute_shortest_distance_weighted(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, bd.target);
+    if (std::isnan(gs.distance)) throw InvalidInputException("Shortest distance failed: " + GetOnagerError());
+    gs.computed = true;
+  }
+  if (gs.emitted) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<int64_t>(output.data[0])[0] = bd.source;
+  GetFlatVectorDataWritable<int64_t>(output.data[1])[0] = bd.target;
+  GetFlatVectorDataWritable<double>(output.data[2])[0] = gs.distance;
+  gs.emitted = true; output.SetCardinality(1);
+  return OperatorFinalizeResultType::FINISHED;
+}
+
+// =============================================================================
+// Widest Path
+// =============================================================================
+
+struct WidestPathBindData : public TableFunctionData { int64_t source = 0, target = 0; bool has_target = false; ForbiddenSet forbidden; };
+struct WidestPathGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes;
+  std::vector<double> weights, result_widths;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> WidestPathBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WidestPathBindData>();
+  CheckInt64Input(input, "onager_pth_widest_path", 3);
+  BindForbidden(input, bd->forbidden, "onager_pth_widest_path");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
+    else if (kv.first == "target") { bd->target = kv.second.GetValue<int64_t>(); bd->has_target = true; }
+  }
+  if (input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("width");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> WidestPathInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<WidestPathGlobalState>(); }
+static OperatorResultType WidestPathInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<WidestPathGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]); auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType WidestPathFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<WidestPathBindData>(); auto &gs = data.global_state->Cast<WidestPathGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Widest path");
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t nc = bd.has_target
+      ? ::onager::onager_compute_widest_path(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, bd.target, nullptr, nullptr, 0)
+      : ::onager::onager_compute_widest_paths(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Widest path failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_widths.resize(nc);
+    int64_t wc = bd.has_target
+      ? ::onager::onager_compute_widest_path(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, bd.target, gs.result_nodes.data(), gs.result_widths.data(), gs.result_nodes.size())
+      : ::onager::onager_compute_widest_paths(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.source, gs.result_nodes.data(), gs.result_widths.data(), gs.result_nodes.size());
+    size_t written = CheckOnagerWrite(wc, gs.result_nodes.size(), "Widest path");
+    gs.result_nodes.resize(written); gs.result_widths.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto w = GetFlatVectorDataWritable<double>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; w[i] = gs.result_widths[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Path With Allowed Edge Types
+// =============================================================================
+
+struct TypedPathBindData : public TableFunctionData { int64_t source = 0, target = 0; std::vector<std::string> allowed_types; ForbiddenSet forbidden; };
+struct TypedPathGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_types;
+  EdgeTypeCodes type_codes;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> TypedPathBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<TypedPathBindData>();
+  CheckInt64Input(input, "onager_pth_typed_path", 3);
+  if (input.input_table_types[2] != LogicalType::VARCHAR) throw InvalidInputException("Edge type column must be VARCHAR. Please cast it (e.g. type::varchar)");
+  BindForbidden(input, bd->forbidden, "onager_pth_typed_path");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "source") bd->source = kv.second.GetValue<int64_t>();
+    else if (kv.first == "target") bd->target = kv.second.GetValue<int64_t>();
+  }
+  auto allowed = input.named_parameters.find("allowed_types");
+  if (allowed == input.named_parameters.end() || allowed->second.IsNull()) throw InvalidInputException("onager_pth_typed_path requires allowed_types := [...]");
+  for (auto &type : ListValue::GetChildren(allowed->second)) {
+    if (type.IsNull()) throw InvalidInputException("onager_pth_typed_path allowed_types must not contain NULL");
+    bd->allowed_types.push_back(type.GetValue<string>());
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("edge_type");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> TypedPathInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<TypedPathGlobalState>(); }
+static OperatorResultType TypedPathInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<TypedPathGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.type_codes.Add(input.data[2], i); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType TypedPathFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<TypedPathBindData>(); auto &gs = data.global_state->Cast<TypedPathGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    // Carry the type codes as weights so they stay aligned with the kept edges
+    std::vector<double> codes(gs.type_codes.edge_types.begin(), gs.type_codes.edge_types.end());
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, codes, "Typed path");
+    gs.type_codes.edge_types.assign(codes.begin(), codes.end());
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    auto allowed = gs.type_codes.TypeIds(bd.allowed_types);
+    int64_t nc = ::onager::onager_compute_path_with_allowed_types(gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), gs.src_nodes.size(), allowed.data(), allowed.size(), bd.source, bd.target, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Typed path failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_types.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_path_with_allowed_types(gs.src_nodes.data(), gs.dst_nodes.data(), gs.type_codes.edge_types.data(), gs.src_nodes.size(), allowed.data(), allowed.size(), bd.source, bd.target, gs.result_nodes.data(), gs.result_types.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Typed path");
+    gs.result_nodes.resize(written); gs.result_types.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto t = GetFlatVectorDataWritable<string_t>(output.data[1]);
+  for (idx_t i = 0; i < to; i++) {
+    idx_t k = gs.output_idx + i;
+    n[i] = gs.result_nodes[k];
+    // Row k is reached by edge k - 1, and the source row has no edge
+    if (k == 0) FlatVector::SetNull(output.data[1], i, true);
+    else t[i] = StringVector::AddString(output.data[1], gs.type_codes.names[gs.result_types[k - 1]]);
+  }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Pairwise Distances Among a Node Subset
+// =============================================================================
+
+struct PairwiseDistancesBindData : public WeightedBindData { std::vector<int64_t> nodes; bool weighted = false; ForbiddenSet forbidden; };
+struct PairwiseDistancesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
+  std::vector<double> weights, result_distances;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> PairwiseDistancesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<PairwiseDistancesBindData>();
+  CheckInt64Input(input, "onager_pth_pairwise_distances");
+  BindForbidden(input, bd->forbidden, "onager_pth_pairwise_distances");
+  auto nodes = input.named_parameters.find("nodes");
+  if (nodes == input.named_parameters.end() || nodes->second.IsNull()) throw InvalidInputException("onager_pth_pairwise_distances requires nodes := [...]");
+  for (auto &node : ListValue::GetChildren(nodes->second)) {
+    if (node.IsNull()) throw InvalidInputException("onager_pth_pairwise_distances nodes must not contain NULL");
+    bd->nodes.push_back(node.GetValue<int64_t>());
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_pth_pairwise_distances");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> PairwiseDistancesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<PairwiseDistancesGlobalState>(); }
+static OperatorResultType PairwiseDistancesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<PairwiseDistancesBindData>(); auto &gs = data.global_state->Cast<PairwiseDistancesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType PairwiseDistancesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<PairwiseDistancesBindData>(); auto &gs = data.global_state->Cast<PairwiseDistancesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Pairwise distances");
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Pairwise distances");
+    int64_t nc = ::onager::onager_compute_pairwise_distances(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.nodes.data(), bd.nodes.size(), nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Pairwise distances failed: " + GetOnagerError());
+    gs.result_src.resize(nc); gs.result_dst.resize(nc); gs.result_distances.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_pairwise_distances(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.nodes.data(), bd.nodes.size(), gs.result_src.data(), gs.result_dst.data(), gs.result_distances.data(), gs.result_src.size()), gs.result_src.size(), "Pairwise distances");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_distances.resize(written);
     gs.computed = true;
   }
   idx_t rem = gs.result_src.size() - gs.output_idx;
@@ -252,6 +819,131 @@ static OperatorFinalizeResultType FloydWarshallFinal(ExecutionContext &ctx, Tabl
   return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
 }
 
+// =============================================================================
+// Voronoi Partition From Seed Nodes
+// =============================================================================
+
+struct VoronoiBindData : public WeightedBindData { std::vector<int64_t> seeds; bool weighted = false; ForbiddenSet forbidden; };
+struct VoronoiGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_nodes, result_seeds;
+  std::vector<double> weights, result_distances;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> VoronoiBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<VoronoiBindData>();
+  CheckInt64Input(input, "onager_pth_voronoi");
+  BindForbidden(input, bd->forbidden, "onager_pth_voronoi");
+  auto seeds = input.named_parameters.find("seeds");
+  if (seeds == input.named_parameters.end() || seeds->second.IsNull()) throw InvalidInputException("onager_pth_voronoi requires seeds := [...]");
+  for (auto &seed : ListValue::GetChildren(seeds->second)) {
+    if (seed.IsNull()) throw InvalidInputException("onager_pth_voronoi seeds must not contain NULL");
+    bd->seeds.push_back(seed.GetValue<int64_t>());
+  }
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_pth_voronoi");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("node_id");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("seed");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("distance");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> VoronoiInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<VoronoiGlobalState>(); }
+static OperatorResultType VoronoiInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<VoronoiBindData>(); auto &gs = data.global_state->Cast<VoronoiGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType VoronoiFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<VoronoiBindData>(); auto &gs = data.global_state->Cast<VoronoiGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Voronoi partition");
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Voronoi partition");
+    int64_t nc = ::onager::onager_compute_voronoi(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.seeds.data(), bd.seeds.size(), nullptr, nullptr, nullptr, 0);
+    if (nc < 0) throw InvalidInputException("Voronoi partition failed: " + GetOnagerError());
+    gs.result_nodes.resize(nc); gs.result_seeds.resize(nc); gs.result_distances.resize(nc);
+    size_t written = CheckOnagerWrite(::onager::onager_compute_voronoi(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.seeds.data(), bd.seeds.size(), gs.result_nodes.data(), gs.result_seeds.data(), gs.result_distances.data(), gs.result_nodes.size()), gs.result_nodes.size(), "Voronoi partition");
+    gs.result_nodes.resize(written); gs.result_seeds.resize(written); gs.result_distances.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_nodes.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto n = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto sd = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { n[i] = gs.result_nodes[gs.output_idx+i]; sd[i] = gs.result_seeds[gs.output_idx+i]; w[i] = gs.result_distances[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_nodes.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Spill All-Pairs Distances
+// =============================================================================
+
+struct SpillDistancesBindData : public WeightedBindData { std::string path; bool weighted = false; ForbiddenSet forbidden; };
+struct SpillDistancesGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  std::vector<double> weights;
+  std::string path; int64_t row_count = 0; bool computed = false, emitted = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> SpillDistancesBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<SpillDistancesBindData>();
+  CheckInt64Input(input, "onager_pth_spill_distances");
+  BindForbidden(input, bd->forbidden, "onager_pth_spill_distances");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "path") bd->path = kv.second.GetValue<string>();
+  }
+  CheckSpillPath(ctx, bd->path, "onager_pth_spill_distances");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  BindWeightSemantics(input, *bd, "onager_pth_spill_distances");
+  rt.push_back(LogicalType::VARCHAR); nm.push_back("path");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("row_count");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> SpillDistancesInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<SpillDistancesGlobalState>(); }
+static OperatorResultType SpillDistancesInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SpillDistancesBindData>(); auto &gs = data.global_state->Cast<SpillDistancesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType SpillDistancesFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<SpillDistancesBindData>(); auto &gs = data.global_state->Cast<SpillDistancesGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    ApplyForbidden(bd.forbidden, gs.src_nodes, gs.dst_nodes, gs.weights, "Spill distances");
+    if (gs.src_nodes.empty()) { gs.computed = true; gs.emitted = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    ApplyWeightSemantics(bd, gs.weights, "Spill distances");
+    gs.path = RunSpill(ctx.client, bd.path, "Spill distances", gs.row_count, [&](::onager::OnagerWriteFn write, void *out) {
+      return ::onager::onager_spill_all_pairs_distances(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), write, out);
+    });
+    gs.computed = true;
+  }
+  if (gs.emitted) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  GetFlatVectorDataWritable<string_t>(output.data[0])[0] = StringVector::AddString(output.data[0], gs.path);
+  GetFlatVectorDataWritable<int64_t>(output.data[1])[0] = gs.row_count;
+  gs.emitted = true; output.SetCardinality(1);
+  return OperatorFinalizeResultType::FINISHED;
+}
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -263,6 +955,7 @@ void RegisterTraversalFunctions(ExtensionLoader &loader) {
   dijkstra.in_out_function = DijkstraInOut;
   dijkstra.in_out_function_final = DijkstraFinal;
   dijkstra.named_parameters["source"] = LogicalType::BIGINT;
+  AddForbiddenParameters(dijkstra);
   ONAGER_SET_NO_ORDER(dijkstra);
   loader.RegisterFunction(dijkstra);
 
@@ -270,6 +963,7 @@ void RegisterTraversalFunctions(ExtensionLoader &loader) {
   bfs.in_out_function = BfsInOut;
   bfs.in_out_function_final = BfsFinal;
   bfs.named_parameters["source"] = LogicalType::BIGINT;
+  AddForbiddenParameters(bfs);
   ONAGER_SET_NO_ORDER(bfs);
   loader.RegisterFunction(bfs);
 
@@ -277,21 +971,126 @@ void RegisterTraversalFunctions(ExtensionLoader &loader) {
   dfs.in_out_function = DfsInOut;
   dfs.in_out_function_final = DfsFinal;
   dfs.named_parameters["source"] = LogicalType::BIGINT;
+  AddForbiddenParameters(dfs);
   ONAGER_SET_NO_ORDER(dfs);
   loader.RegisterFunction(dfs);
 
+  TableFunction topo_sort("onager_trv_topological_sort", {LogicalType::TABLE}, nullptr, TopoSortBind, TopoSortInitGlobal);
+  topo_sort.in_out_function = TopoSortInOut;
+  topo_sort.in_out_function_final = TopoSortFinal;
+  AddForbiddenParameters(topo_sort);
+  ONAGER_SET_NO_ORDER(topo_sort);
+  loader.RegisterFunction(topo_sort);
+
+  TableFunction is_dag("onager_trv_is_dag", {LogicalType::TABLE}, nullptr, IsDagBind, IsDagInitGlobal);
+  is_dag.in_out_function = IsDagInOut;
+  is_dag.in_out_function_final = IsDagFinal;
+  AddForbiddenParameters(is_dag);
+  ONAGER_SET_NO_ORDER(is_dag);
+  loader.RegisterFunction(is_dag);
+
   TableFunction bellman_ford("onager_pth_bellman_ford", {LogicalType::TABLE}, nullptr, BellmanFordBind, BellmanFordInitGlobal);
   bellman_ford.in_out_function = BellmanFordInOut;
   bellman_ford.in_out_function_final = BellmanFordFinal;
   bellman_ford.named_parameters["source"] = LogicalType::BIGINT;
+  bellman_ford.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(bellman_ford);
   ONAGER_SET_NO_ORDER(bellman_ford);
   loader.RegisterFunction(bellman_ford);
 
   TableFunction floyd_warshall("onager_pth_floyd_warshall", {LogicalType::TABLE}, nullptr, FloydWarshallBind, FloydWarshallInitGlobal);
   floyd_warshall.in_out_function = FloydWarshallInOut;
   floyd_warshall.in_out_function_final = FloydWarshallFinal;
+  floyd_warshall.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(floyd_warshall);
   ONAGER_SET_NO_ORDER(floyd_warshall);
   loader.RegisterFunction(floyd_warshall);
+
+  TableFunction spt("onager_pth_shortest_path_tree", {LogicalType::TABLE}, nullptr, ShortestPathTreeBind, ShortestPathTreeInitGlobal);
+  spt.in_out_function = ShortestPathTreeInOut;
+  spt.in_out_function_final = ShortestPathTreeFinal;
+  spt.named_parameters["source"] = LogicalType::BIGINT;
+  spt.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(spt);
+  ONAGER_SET_NO_ORDER(spt);
+  loader.RegisterFunction(spt);
+
+  TableFunction within_cost("onager_pth_within_cost", {LogicalType::TABLE}, nullptr, WithinCostBind, WithinCostInitGlobal);
+  within_cost.in_out_function = WithinCostInOut;
+  within_cost.in_out_function_final = WithinCostFinal;
+  within_cost.named_parameters["source"] = LogicalType::BIGINT;
+  within_cost.named_parameters["max_cost"] = LogicalType::DOUBLE;
+  within_cost.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(within_cost);
+  ONAGER_SET_NO_ORDER(within_cost);
+  loader.RegisterFunction(within_cost);
+
+  TableFunction shortest_path("onager_pth_shortest_path", {LogicalType::TABLE}, nullptr, ShortestPathBind, ShortestPathInitGlobal);
+  shortest_path.in_out_function = ShortestPathInOut;
+  shortest_path.in_out_function_final = ShortestPathFinal;
+  shortest_path.named_parameters["source"] = LogicalType::BIGINT;
+  shortest_path.named_parameters["target"] = LogicalType::BIGINT;
+  shortest_path.named_parameters["directed"] = LogicalType::BOOLEAN;
+  shortest_path.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(shortest_path);
+  ONAGER_SET_NO_ORDER(shortest_path);
+  loader.RegisterFunction(shortest_path);
+
+  TableFunction shortest_distance("onager_pth_shortest_distance", {LogicalType::TABLE}, nullptr, ShortestDistanceBind, ShortestDistanceInitGlobal);
+  shortest_distance.in_out_function = ShortestDistanceInOut;
+  shortest_distance.in_out_function_final = ShortestDistanceFinal;
+  shortest_distance.named_parameters["source"] = LogicalType::BIGINT;
+  shortest_distance.named_parameters["target"] = LogicalType::BIGINT;
+  shortest_distance.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(shortest_distance);
+  ONAGER_SET_NO_ORDER(shortest_distance);
+  loader.RegisterFunction(shortest_distance);
+
+  TableFunction widest_path("onager_pth_widest_path", {LogicalType::TABLE}, nullptr, WidestPathBind, WidestPathInitGlobal);
+  widest_path.in_out_function = WidestPathInOut;
+  widest_path.in_out_function_final = WidestPathFinal;
+  widest_path.named_parameters["source"] = LogicalType::BIGINT;
+  widest_path.named_parameters["target"] = LogicalType::BIGINT;
+  AddForbiddenParameters(widest_path);
+  ONAGER_SET_NO_ORDER(widest_path);
+  loader.RegisterFunction(widest_path);
+
+  TableFunction typed_path("onager_pth_typed_path", {LogicalType::TABLE}, nullptr, TypedPathBind, TypedPathInitGlobal);
+  typed_path.in_out_function = TypedPathInOut;
+  typed_path.in_out_function_final = TypedPathFinal;
+  typed_path.named_parameters["source"] = LogicalType::BIGINT;
+  typed_path.named_parameters["target"] = LogicalType::BIGINT;
+  typed_path.named_parameters["allowed_types"] = LogicalType::LIST(LogicalType::VARCHAR);
+  AddForbiddenParameters(typed_path);
+  ONAGER_SET_NO_ORDER(typed_path);
+  loader.RegisterFunction(typed_path);
+
+  TableFunction pairwise("onager_pth_pairwise_distances", {LogicalType::TABLE}, nullptr, PairwiseDistancesBind, PairwiseDistancesInitGlobal);
+  pairwise.in_out_function = PairwiseDistancesInOut;
+  pairwise.in_out_function_final = PairwiseDistancesFinal;
+  pairwise.named_parameters["nodes"] = LogicalType::LIST(LogicalType::BIGINT);
+  pairwise.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(pairwise);
+  ONAGER_SET_NO_ORDER(pairwise);
+  loader.RegisterFunction(pairwise);
+
+  TableFunction voronoi("onager_pth_voronoi", {LogicalType::TABLE}, nullptr, VoronoiBind, VoronoiInitGlobal);
+  voronoi.in_out_function = VoronoiInOut;
+  voronoi.in_out_function_final = VoronoiFinal;
+  voronoi.named_parameters["seeds"] = LogicalType::LIST(LogicalType::BIGINT);
+  voronoi.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(voronoi);
+  ONAGER_SET_NO_ORDER(voronoi);
+  loader.RegisterFunction(voronoi);
+
+  TableFunction spill_distances("onager_pth_spill_distances", {LogicalType::TABLE}, nullptr, SpillDistancesBind, SpillDistancesInitGlobal);
+  spill_distances.in_out_function = SpillDistancesInOut;
+  spill_distances.in_out_function_final = SpillDistancesFinal;
+  spill_distances.named_parameters["path"] = LogicalType::VARCHAR;
+  spill_distances.named_parameters["weight_semantics"] = LogicalType::VARCHAR;
+  AddForbiddenParameters(spill_distances);
+  ONAGER_SET_NO_ORDER(spill_distances);
+  loader.RegisterFunction(spill_distances);
 }
 
 } // namespace onager
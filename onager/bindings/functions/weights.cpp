@@ -0,0 +1,159 @@
+/**
+ * @file weights.cpp
+ * @brief Edge weight table functions for Onager DuckDB extension.
+ *
+ * Log, inverse, min-max, and z-score weight transforms, and aggregation of
+ * repeated edges.
+ */
+#include "functions.hpp"
+#include <mutex>
+
+namespace duckdb {
+
+using namespace onager;
+
+// =============================================================================
+// Weight Transform
+// =============================================================================
+
+struct WeightTransformBindData : public TableFunctionData { std::string method; };
+struct WeightTransformGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes;
+  std::vector<double> weights, result_weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> WeightTransformBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<WeightTransformBindData>();
+  CheckInt64Input(input, "onager_wgt_transform", 3);
+  if (input.input_table_types[2] != LogicalType::DOUBLE) {
+    throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  }
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "method") bd->method = StringUtil::Lower(kv.second.GetValue<string>());
+  }
+  if (bd->method != "log" && bd->method != "inverse" && bd->method != "minmax" && bd->method != "zscore") {
+    throw InvalidInputException("onager_wgt_transform method must be 'log', 'inverse', 'minmax', or 'zscore'");
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> WeightTransformInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<WeightTransformGlobalState>(); }
+static OperatorResultType WeightTransformInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &gs = data.global_state->Cast<WeightTransformGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  auto w = FlatVector::GetData<double>(input.data[2]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); gs.weights.push_back(w[i]); }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType WeightTransformFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<WeightTransformBindData>(); auto &gs = data.global_state->Cast<WeightTransformGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.weights.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    gs.result_weights.resize(gs.weights.size());
+    size_t written = CheckOnagerWrite(::onager::onager_transform_weights(gs.weights.data(), gs.weights.size(), bd.method.c_str(), gs.result_weights.data(), gs.result_weights.size()), gs.result_weights.size(), "Weight transform");
+    gs.result_weights.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_weights.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.src_nodes[gs.output_idx+i]; d[i] = gs.dst_nodes[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_weights.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Parallel Edge Aggregation
+// =============================================================================
+
+struct EdgeAggregateBindData : public TableFunctionData { std::string method = "sum"; bool weighted = false; };
+struct EdgeAggregateGlobalState : public GlobalTableFunctionState {
+  std::mutex input_mutex;
+  std::vector<int64_t> src_nodes, dst_nodes, result_src, result_dst;
+  std::vector<double> weights, result_weights;
+  idx_t output_idx = 0; bool computed = false;
+  idx_t MaxThreads() const override { return 1; }
+};
+
+static unique_ptr<FunctionData> EdgeAggregateBind(ClientContext &ctx, TableFunctionBindInput &input, vector<LogicalType> &rt, vector<string> &nm) {
+  auto bd = make_uniq<EdgeAggregateBindData>();
+  CheckInt64Input(input, "onager_wgt_aggregate");
+  bd->weighted = input.input_table_types.size() >= 3;
+  if (bd->weighted && input.input_table_types[2] != LogicalType::DOUBLE) throw InvalidInputException("Weight column must be DOUBLE. Please cast it (e.g. weight::double)");
+  for (auto &kv : input.named_parameters) {
+    if (kv.first == "method") bd->method = StringUtil::Lower(kv.second.GetValue<string>());
+  }
+  if (bd->method != "sum" && bd->method != "count" && bd->method != "mean" && bd->method != "avg" && bd->method != "max") {
+    throw InvalidInputException("onager_wgt_aggregate method must be 'sum', 'count', 'mean', or 'max'");
+  }
+  rt.push_back(LogicalType::BIGINT); nm.push_back("src");
+  rt.push_back(LogicalType::BIGINT); nm.push_back("dst");
+  rt.push_back(LogicalType::DOUBLE); nm.push_back("weight");
+  return std::move(bd);
+}
+static unique_ptr<GlobalTableFunctionState> EdgeAggregateInitGlobal(ClientContext &ctx, TableFunctionInitInput &input) { return make_uniq<EdgeAggregateGlobalState>(); }
+static OperatorResultType EdgeAggregateInOut(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &input, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<EdgeAggregateBindData>(); auto &gs = data.global_state->Cast<EdgeAggregateGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  auto s = FlatVector::GetData<int64_t>(input.data[0]); auto d = FlatVector::GetData<int64_t>(input.data[1]);
+  for (idx_t i = 0; i < input.size(); i++) { gs.src_nodes.push_back(s[i]); gs.dst_nodes.push_back(d[i]); }
+  if (bd.weighted) {
+    auto w = FlatVector::GetData<double>(input.data[2]);
+    for (idx_t i = 0; i < input.size(); i++) gs.weights.push_back(w[i]);
+  }
+  output.SetCardinality(0); return OperatorResultType::NEED_MORE_INPUT;
+}
+static OperatorFinalizeResultType EdgeAggregateFinal(ExecutionContext &ctx, TableFunctionInput &data, DataChunk &output) {
+  auto &bd = data.bind_data->Cast<EdgeAggregateBindData>(); auto &gs = data.global_state->Cast<EdgeAggregateGlobalState>();
+  std::lock_guard<std::mutex> lock(gs.input_mutex);
+  if (!gs.computed) {
+    if (gs.src_nodes.empty()) { gs.computed = true; output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+    int64_t ec = ::onager::onager_aggregate_parallel_edges(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.method.c_str(), nullptr, nullptr, nullptr, 0);
+    if (ec < 0) throw InvalidInputException("Edge aggregation failed: " + GetOnagerError());
+    gs.result_src.resize(ec); gs.result_dst.resize(ec); gs.result_weights.resize(ec);
+    size_t written = CheckOnagerWrite(::onager::onager_aggregate_parallel_edges(gs.src_nodes.data(), gs.dst_nodes.data(), gs.src_nodes.size(), gs.weights.data(), gs.weights.size(), bd.method.c_str(),
+        gs.result_src.data(), gs.result_dst.data(), gs.result_weights.data(), gs.result_src.size()), gs.result_src.size(), "Edge aggregation");
+    gs.result_src.resize(written); gs.result_dst.resize(written); gs.result_weights.resize(written);
+    gs.computed = true;
+  }
+  idx_t rem = gs.result_src.size() - gs.output_idx;
+  if (rem == 0) { output.SetCardinality(0); return OperatorFinalizeResultType::FINISHED; }
+  idx_t to = MinValue<idx_t>(rem, STANDARD_VECTOR_SIZE);
+  auto s = GetFlatVectorDataWritable<int64_t>(output.data[0]); auto d = GetFlatVectorDataWritable<int64_t>(output.data[1]); auto w = GetFlatVectorDataWritable<double>(output.data[2]);
+  for (idx_t i = 0; i < to; i++) { s[i] = gs.result_src[gs.output_idx+i]; d[i] = gs.result_dst[gs.output_idx+i]; w[i] = gs.result_weights[gs.output_idx+i]; }
+  gs.output_idx += to; output.SetCardinality(to);
+  return gs.output_idx >= gs.result_src.size() ? OperatorFinalizeResultType::FINISHED : OperatorFinalizeResultType::HAVE_MORE_OUTPUT;
+}
+
+// =============================================================================
+// Registration
+// =============================================================================
+
+namespace onager {
+
+void RegisterWeightFunctions(ExtensionLoader &loader) {
+  TableFunction transform("onager_wgt_transform", {LogicalType::TABLE}, nullptr, WeightTransformBind, WeightTransformInitGlobal);
+  transform.in_out_function = WeightTransformInOut;
+  transform.in_out_function_final = WeightTransformFinal;
+  transform.named_parameters["method"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(transform);
+  loader.RegisterFunction(transform);
+
+  TableFunction aggregate("onager_wgt_aggregate", {LogicalType::TABLE}, nullptr, EdgeAggregateBind, EdgeAggregateInitGlobal);
+  aggregate.in_out_function = EdgeAggregateInOut;
+  aggregate.in_out_function_final = EdgeAggregateFinal;
+  aggregate.named_parameters["method"] = LogicalType::VARCHAR;
+  ONAGER_SET_NO_ORDER(aggregate);
+  loader.RegisterFunction(aggregate);
+}
+
+} // namespace onager
+} // namespace duckdb
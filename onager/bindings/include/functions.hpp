@@ -5,16 +5,26 @@
 #pragma once
 
 #include "duckdb.hpp"
+#include "duckdb/common/error_data.hpp"
 #include "duckdb/common/exception.hpp"
+#include "duckdb/common/file_system.hpp"
 #include "duckdb/common/types/data_chunk.hpp"
+#include "duckdb/common/types/uuid.hpp"
 #include "duckdb/common/types/value.hpp"
 #include "duckdb/common/types/vector.hpp"
 #include "duckdb/function/scalar_function.hpp"
 #include "duckdb/function/table_function.hpp"
+#include "duckdb/main/client_context.hpp"
+#include "duckdb/main/client_context_state.hpp"
+#include "duckdb/main/config.hpp"
+#include "duckdb/main/database.hpp"
 #include "duckdb/main/extension/extension_loader.hpp"
+#include <cmath>
 #include <cstdint>
 #include <memory>
+#include <mutex>
 #include <string>
+#include <unordered_map>
 #include <vector>
 
 #include "rust.h"
@@ -36,6 +46,13 @@
   #define ONAGER_SET_NO_ORDER(func) ((void)0)
 #endif
 
+// =============================================================================
+// Rust ABI Version
+// =============================================================================
+// C ABI version these bindings were written against. Must match ABI_VERSION in
+// onager/src/ffi/common.rs, and both are bumped together on breaking changes.
+#define ONAGER_ABI_VERSION 1
+
 namespace duckdb {
 namespace onager {
 
@@ -80,6 +97,22 @@ inline std::string GetOnagerError() {
   return err ? std::string(err) : std::string("unknown error");
 }
 
+/**
+ * @brief Checks the result of a capacity-checked (`_v2`) Onager call.
+ * @param written Value returned by the call (rows written, or -1 on error)
+ * @param capacity Number of rows the output buffers can hold
+ * @param name The algorithm name for error messages
+ * @return Number of rows written to the output buffers
+ * @throws InvalidInputException if the call failed, including on buffers that were too small, or reported more rows than fit
+ */
+inline size_t CheckOnagerWrite(int64_t written, size_t capacity, const std::string &name) {
+  if (written < 0) throw InvalidInputException(name + " failed: " + GetOnagerError());
+  if (static_cast<size_t>(written) > capacity) {
+    throw InvalidInputException(name + " failed: result has " + std::to_string(written) + " rows but only " + std::to_string(capacity) + " were allocated");
+  }
+  return static_cast<size_t>(written);
+}
+
 /**
  * @brief Validates that input table has BIGINT columns for (src, dst).
  * @param input The table function bind input
@@ -96,6 +129,410 @@ inline void CheckInt64Input(TableFunctionBindInput &input, const std::string &na
   }
 }
 
+/**
+ * @brief Bind data for table functions that take edge weights.
+ *
+ * `similarity` is set when the `weight_semantics` parameter is 'similarity',
+ * meaning larger weights mark closer nodes and must be converted to distances.
+ */
+struct WeightedBindData : public TableFunctionData {
+  bool similarity = false;
+};
+
+/**
+ * @brief Reads the `weight_semantics` named parameter ('distance' or 'similarity').
+ * @param input The table function bind input
+ * @param bd The bind data to update
+ * @param name The function name for error messages
+ * @throws InvalidInputException if the value is not recognized
+ */
+inline void BindWeightSemantics(TableFunctionBindInput &input, WeightedBindData &bd, const std::string &name) {
+  auto it = input.named_parameters.find("weight_semantics");
+  if (it == input.named_parameters.end()) return;
+  auto value = StringUtil::Lower(it->second.GetValue<string>());
+  if (value != "distance" && value != "similarity") {
+    throw InvalidInputException(name + " weight_semantics must be 'distance' or 'similarity'");
+  }
+  bd.similarity = value == "similarity";
+}
+
+/**
+ * @brief Converts similarity weights to distances in place when the bind data asks for it.
+ * @param bd The bind data holding the weight semantics
+ * @param weights The edge weights to convert
+ * @param name The algorithm name for error messages
+ * @throws InvalidInputException if a similarity weight is not positive and finite
+ */
+inline void ApplyWeightSemantics(const WeightedBindData &bd, std::vector<double> &weights, const std::string &name) {
+  if (!bd.similarity || weights.empty()) return;
+  std::vector<double> distances(weights.size());
+  CheckOnagerWrite(::onager::onager_weights_to_distances(weights.data(), weights.size(), "similarity", distances.data(), distances.size()), distances.size(), name);
+  weights.swap(distances);
+}
+
+/**
+ * @brief Nodes and edges to leave out of a path query.
+ *
+ * Filled from the `forbidden_nodes` (LIST(BIGINT)) and `forbidden_edges`
+ * (LIST of [src, dst] pairs) named parameters.
+ */
+struct ForbiddenSet {
+  std::vector<int64_t> nodes, edge_src, edge_dst;
+  bool Empty() const { return nodes.empty() && edge_src.empty(); }
+};
+
+/**
+ * @brief Reads the `forbidden_nodes` and `forbidden_edges` named parameters.
+ * @param input The table function bind input
+ * @param forbidden The set to fill
+ * @param name The function name for error messages
+ * @throws InvalidInputException if a value is NULL or an edge is not a pair
+ */
+inline void BindForbidden(TableFunctionBindInput &input, ForbiddenSet &forbidden, const std::string &name) {
+  auto nodes = input.named_parameters.find("forbidden_nodes");
+  if (nodes != input.named_parameters.end() && !nodes->second.IsNull()) {
+    for (auto &node : ListValue::GetChildren(nodes->second)) {
+      if (node.IsNull()) throw InvalidInputException(name + " forbidden_nodes must not contain NULL");
+      forbidden.nodes.push_back(node.GetValue<int64_t>());
+    }
+  }
+  auto edges = input.named_parameters.find("forbidden_edges");
+  if (edges != input.named_parameters.end() && !edges->second.IsNull()) {
+    for (auto &edge : ListValue::GetChildren(edges->second)) {
+      if (edge.IsNull() || ListValue::GetChildren(edge).size() != 2) throw InvalidInputException(name + " forbidden_edges must be a list of [src, dst] pairs");
+      auto &pair = ListValue::GetChildren(edge);
+      if (pair[0].IsNull() || pair[1].IsNull()) throw InvalidInputException(name + " forbidden_edges must not contain NULL");
+      forbidden.edge_src.push_back(pair[0].GetValue<int64_t>());
+      forbidden.edge_dst.push_back(pair[1].GetValue<int64_t>());
+    }
+  }
+}
+
+/**
+ * @brief Registers the `forbidden_nodes` and `forbidden_edges` named parameters.
+ * @param function The table function to update
+ */
+inline void AddForbiddenParameters(TableFunction &function) {
+  function.named_parameters["forbidden_nodes"] = LogicalType::LIST(LogicalType::BIGINT);
+  function.named_parameters["forbidden_edges"] = LogicalType::LIST(LogicalType::LIST(LogicalType::BIGINT));
+}
+
+/**
+ * @brief Removes forbidden nodes and edges from the collected edges in place.
+ * @param forbidden The nodes and edges to remove
+ * @param src The edge sources
+ * @param dst The edge destinations
+ * @param weights The edge weights, or an empty vector for unweighted edges
+ * @param name The algorithm name for error messages
+ */
+inline void ApplyForbidden(const ForbiddenSet &forbidden, std::vector<int64_t> &src, std::vector<int64_t> &dst, std::vector<double> &weights, const std::string &name) {
+  if (forbidden.Empty() || src.empty()) return;
+  std::vector<int64_t> kept_src(src.size()), kept_dst(src.size());
+  std::vector<double> kept_weights(weights.size());
+  size_t written = CheckOnagerWrite(::onager::onager_remove_forbidden(src.data(), dst.data(), src.size(), weights.data(), weights.size(), forbidden.nodes.data(), forbidden.nodes.size(), forbidden.edge_src.data(), forbidden.edge_dst.data(), forbidden.edge_src.size(), kept_src.data(), kept_dst.data(), kept_weights.data(), kept_src.size()), kept_src.size(), name);
+  kept_src.resize(written); kept_dst.resize(written);
+  if (!weights.empty()) kept_weights.resize(written);
+  src.swap(kept_src); dst.swap(kept_dst); weights.swap(kept_weights);
+}
+
+/**
+ * @brief Removes forbidden nodes and edges from unweighted edges in place.
+ */
+inline void ApplyForbidden(const ForbiddenSet &forbidden, std::vector<int64_t> &src, std::vector<int64_t> &dst, const std::string &name) {
+  std::vector<double> no_weights;
+  ApplyForbidden(forbidden, src, dst, no_weights, name);
+}
+
+/**
+ * @brief Per-edge-type weight multipliers.
+ *
+ * Filled from the `type_weights` (MAP(VARCHAR, DOUBLE)) named parameter. When
+ * it is set, the last input column holds the VARCHAR edge type, and types
+ * missing from the map count as 1.
+ */
+struct EdgeTypeWeights {
+  bool enabled = false;
+  std::vector<std::string> names;
+  std::vector<double> multipliers;
+};
+
+/**
+ * @brief Reads the `type_weights` named parameter.
+ * @param input The table function bind input
+ * @param types The multipliers to fill
+ * @param min_cols The number of columns before the edge type column
+ * @param name The function name for error messages
+ * @throws InvalidInputException if the edge type column is missing or the map contains NULL
+ */
+inline void BindEdgeTypeWeights(TableFunctionBindInput &input, EdgeTypeWeights &types, size_t min_cols, const std::string &name) {
+  auto map = input.named_parameters.find("type_weights");
+  if (map == input.named_parameters.end() || map->second.IsNull()) return;
+  if (input.input_table_types.size() <= min_cols || input.input_table_types.back() != LogicalType::VARCHAR) {
+    throw InvalidInputException(name + " with type_weights requires a VARCHAR edge type as the last column");
+  }
+  for (auto &entry : MapValue::GetChildren(map->second)) {
+    auto &pair = StructValue::GetChildren(entry);
+    if (pair[0].IsNull() || pair[1].IsNull()) throw InvalidInputException(name + " type_weights must not contain NULL");
+    types.names.push_back(pair[0].GetValue<string>());
+    types.multipliers.push_back(pair[1].GetValue<double>());
+  }
+  types.enabled = true;
+}
+
+/**
+ * @brief Integer codes for edge type names, assigned as rows arrive.
+ *
+ * `names[code]` is the name of each code. NULL edge types get code -1, which
+ * never has a multiplier and is never allowed.
+ */
+struct EdgeTypeCodes {
+  std::unordered_map<std::string, int64_t> codes;
+  std::vector<std::string> names;
+  std::vector<int64_t> edge_types;
+
+  int64_t Code(const std::string &type) {
+    auto inserted = codes.emplace(type, static_cast<int64_t>(names.size()));
+    if (inserted.second) names.push_back(type);
+    return inserted.first->second;
+  }
+
+  void Add(Vector &column, idx_t row) {
+    if (FlatVector::IsNull(column, row)) { edge_types.push_back(-1); return; }
+    edge_types.push_back(Code(FlatVector::GetData<string_t>(column)[row].GetString()));
+  }
+
+  std::vector<int64_t> TypeIds(const std::vector<std::string> &types) {
+    std::vector<int64_t> ids;
+    for (auto &type : types) ids.push_back(Code(type));
+    return ids;
+  }
+
+  std::vector<int64_t> TypeIds(const EdgeTypeWeights &types) { return TypeIds(types.names); }
+};
+
+/**
+ * @brief Reads the optional `targets` list of node IDs.
+ * @param input The table function bind input
+ * @param targets The target node IDs to fill
+ * @param name The function name for error messages
+ * @return True if `targets` was given
+ * @throws InvalidInputException if the list contains NULL
+ */
+inline bool BindTargets(TableFunctionBindInput &input, std::vector<int64_t> &targets, const std::string &name) {
+  auto list = input.named_parameters.find("targets");
+  if (list == input.named_parameters.end() || list->second.IsNull()) return false;
+  for (auto &node : ListValue::GetChildren(list->second)) {
+    if (node.IsNull()) throw InvalidInputException(name + " targets must not contain NULL");
+    targets.push_back(node.GetValue<int64_t>());
+  }
+  return true;
+}
+
+/**
+ * @brief Row selection read from the `top_k`, `order_by`, and `min_value` named parameters.
+ *
+ * `column` indexes the score columns passed to BindScoreSelection. Rows whose
+ * score is below `min_value` are dropped first, then a non-negative `k` keeps
+ * the `k` highest-ranked rows sorted by descending score.
+ */
+struct ScoreSelection {
+  int64_t k = -1;
+  size_t column = 0;
+  bool has_min_value = false;
+  double min_value = 0.0;
+  bool Active() const { return k >= 0 || has_min_value; }
+};
+
+/**
+ * @brief Reads the `top_k`, `order_by`, and `min_value` named parameters.
+ * @param input The table function bind input
+ * @param sel The selection to fill
+ * @param score_columns Names of the result columns that can be ranked, the first being the default
+ * @param name The function name for error messages
+ * @throws InvalidInputException if `top_k` is negative, `min_value` is NaN, or `order_by` is not a score column
+ */
+inline void BindScoreSelection(TableFunctionBindInput &input, ScoreSelection &sel, const std::vector<std::string> &score_columns, const std::string &name) {
+  auto k = input.named_parameters.find("top_k");
+  if (k != input.named_parameters.end() && !k->second.IsNull()) {
+    sel.k = k->second.GetValue<int64_t>();
+    if (sel.k < 0) throw InvalidInputException(name + " top_k must be non-negative");
+  }
+  auto min_value = input.named_parameters.find("min_value");
+  if (min_value != input.named_parameters.end() && !min_value->second.IsNull()) {
+    sel.min_value = min_value->second.GetValue<double>();
+    if (std::isnan(sel.min_value)) throw InvalidInputException(name + " min_value must not be NaN");
+    sel.has_min_value = true;
+  }
+  auto order_by = input.named_parameters.find("order_by");
+  if (order_by != input.named_parameters.end() && !order_by->second.IsNull()) {
+    auto column = StringUtil::Lower(order_by->second.GetValue<string>());
+    for (size_t i = 0; i < score_columns.size(); i++) {
+      if (score_columns[i] == column) { sel.column = i; return; }
+    }
+    throw InvalidInputException(name + " order_by must be one of: " + StringUtil::Join(score_columns, ", "));
+  }
+}
+
+/**
+ * @brief Registers the `top_k`, `order_by`, and `min_value` named parameters.
+ * @param function The table function to update
+ */
+inline void AddScoreSelectionParameters(TableFunction &function) {
+  function.named_parameters["top_k"] = LogicalType::BIGINT;
+  function.named_parameters["order_by"] = LogicalType::VARCHAR;
+  function.named_parameters["min_value"] = LogicalType::DOUBLE;
+}
+
+/**
+ * @brief Computes the row indices kept by a selection.
+ * @param sel The selection
+ * @param ranking The score column to filter and rank by
+ * @param name The algorithm name for error messages
+ * @return Indices into `ranking`, in output order
+ */
+inline std::vector<int64_t> SelectRows(const ScoreSelection &sel, const std::vector<double> &ranking, const std::string &name) {
+  std::vector<int64_t> indices;
+  if (ranking.empty()) return indices;
+  if (sel.has_min_value) {
+    int64_t count = ::onager::onager_min_value_indices(ranking.data(), ranking.size(), sel.min_value, nullptr, 0);
+    if (count < 0) throw InvalidInputException(name + " failed: " + GetOnagerError());
+    indices.resize(static_cast<size_t>(count));
+    indices.resize(CheckOnagerWrite(::onager::onager_min_value_indices(ranking.data(), ranking.size(), sel.min_value, indices.data(), indices.size()), indices.size(), name));
+  } else {
+    indices.resize(ranking.size());
+    for (size_t i = 0; i < indices.size(); i++) indices[i] = static_cast<int64_t>(i);
+  }
+  if (sel.k < 0 || indices.empty()) return indices;
+  std::vector<double> kept(indices.size());
+  for (size_t i = 0; i < indices.size(); i++) kept[i] = ranking[indices[i]];
+  std::vector<int64_t> top(MinValue<size_t>(static_cast<size_t>(sel.k), kept.size()));
+  top.resize(CheckOnagerWrite(::onager::onager_top_k_indices(kept.data(), kept.size(), static_cast<size_t>(sel.k), top.data(), top.size()), top.size(), name));
+  for (auto &i : top) i = indices[i];
+  return top;
+}
+
+/**
+ * @brief Keeps the given rows of a result column, in the given order.
+ * @param indices Row indices from SelectRows
+ * @param column The result column to update
+ */
+template <class T>
+inline void KeepRows(const std::vector<int64_t> &indices, std::vector<T> &column) {
+  std::vector<T> kept(indices.size());
+  for (size_t i = 0; i < indices.size(); i++) kept[i] = column[indices[i]];
+  column.swap(kept);
+}
+
+/**
+ * @brief Applies a selection to a per-node score result.
+ * @param sel The selection
+ * @param nodes The result node IDs
+ * @param scores The result score columns, in the order given to BindScoreSelection
+ * @param name The algorithm name for error messages
+ */
+inline void ApplyScoreSelection(const ScoreSelection &sel, std::vector<int64_t> &nodes, const std::vector<std::vector<double> *> &scores, const std::string &name) {
+  if (!sel.Active()) return;
+  auto indices = SelectRows(sel, *scores[sel.column], name);
+  KeepRows(indices, nodes);
+  for (auto column : scores) KeepRows(indices, *column);
+}
+
+/**
+ * @brief Checks at bind time that a spill path may be written.
+ * @param ctx The client context
+ * @param path The user-given output path, or empty for a temporary file
+ * @param name The function name for error messages
+ * @throws PermissionException if external access is disabled for the path
+ */
+inline void CheckSpillPath(ClientContext &ctx, const std::string &path, const std::string &name) {
+  if (!path.empty() && !DBConfig::GetConfig(ctx).CanAccessFile(path, FileType::FILE_TYPE_REGULAR)) {
+    throw PermissionException(name + " cannot write to '" + path + "' because external access is disabled");
+  }
+}
+
+/**
+ * @brief Removes the temporary spill files of a connection when it closes.
+ */
+class SpillFileRegistry : public ClientContextState {
+public:
+  explicit SpillFileRegistry(shared_ptr<DatabaseInstance> db) : db(std::move(db)) {}
+  ~SpillFileRegistry() override {
+    auto &fs = FileSystem::GetFileSystem(*db);
+    for (auto &path : paths) {
+      try { fs.TryRemoveFile(path); } catch (...) {}
+    }
+  }
+  void Add(const std::string &path) {
+    std::lock_guard<std::mutex> guard(lock);
+    paths.push_back(path);
+  }
+
+private:
+  shared_ptr<DatabaseInstance> db;
+  std::mutex lock;
+  std::vector<std::string> paths;
+};
+
+/**
+ * @brief Output file handed to Rust as the context of an `OnagerWriteFn`.
+ */
+struct SpillWriter {
+  unique_ptr<FileHandle> handle;
+  std::string error;
+
+  static int32_t Write(void *ctx, const uint8_t *data, uintptr_t len) {
+    auto &writer = *static_cast<SpillWriter *>(ctx);
+    try {
+      writer.handle->Write(const_cast<uint8_t *>(data), len);
+      return 0;
+    } catch (std::exception &ex) {
+      writer.error = ErrorData(ex).RawMessage();
+      return 1;
+    }
+  }
+};
+
+/**
+ * @brief Runs a spilling Onager call and returns the path of the written file.
+ *
+ * A user-given path is opened through the client file system, so
+ * `enable_external_access` and `allowed_directories` apply. An empty path
+ * writes a new file in DuckDB's temporary directory, which is removed when the
+ * connection closes. A partially written file is removed on failure.
+ * @param ctx The client context
+ * @param path The output path, or empty for a temporary file
+ * @param name The algorithm name for error messages
+ * @param row_count Receives the number of rows written
+ * @param spill Calls Onager with the write callback and its context
+ * @throws InvalidInputException if the call or a write failed
+ */
+template <typename F>
+inline std::string RunSpill(ClientContext &ctx, const std::string &path, const std::string &name, int64_t &row_count, F &&spill) {
+  bool temporary = path.empty();
+  auto &fs = temporary ? FileSystem::GetFileSystem(*ctx.db) : FileSystem::GetFileSystem(ctx);
+  std::string target = path;
+  if (temporary) {
+    auto &dir = DBConfig::GetConfig(ctx).options.temporary_directory;
+    if (dir.empty()) throw InvalidInputException(name + " needs a path when temp_directory is not set");
+    if (!fs.DirectoryExists(dir)) fs.CreateDirectory(dir);
+    target = fs.JoinPath(dir, "onager_spill_" + UUID::ToString(UUID::GenerateRandomUUID()) + ".parquet");
+  }
+
+  SpillWriter writer;
+  writer.handle = fs.OpenFile(target, FileFlags::FILE_FLAGS_WRITE | FileFlags::FILE_FLAGS_FILE_CREATE_NEW);
+  row_count = spill(&SpillWriter::Write, &writer);
+  writer.handle->Close();
+  writer.handle.reset();
+  if (row_count < 0) {
+    fs.TryRemoveFile(target);
+    throw InvalidInputException(name + " failed: " + (writer.error.empty() ? GetOnagerError() : writer.error));
+  }
+  if (temporary) {
+    ctx.registered_state->GetOrCreate<SpillFileRegistry>("onager_spill_files", ctx.db)->Add(target);
+  }
+  return target;
+}
+
 // Forward declarations for modular function registration
 void RegisterScalarFunctions(ExtensionLoader &loader);
 void RegisterCentralityFunctions(ExtensionLoader &loader);
@@ -104,12 +541,20 @@ void RegisterCommunityFunctions(ExtensionLoader &loader);
 void RegisterTraversalFunctions(ExtensionLoader &loader);
 void RegisterGeneratorFunctions(ExtensionLoader &loader);
 void RegisterLinkFunctions(ExtensionLoader &loader);
+void RegisterLinkAnalysisFunctions(ExtensionLoader &loader);
 void RegisterMetricFunctions(ExtensionLoader &loader);
 void RegisterMstFunctions(ExtensionLoader &loader);
 void RegisterApproximationFunctions(ExtensionLoader &loader);
 void RegisterPersonalizedFunctions(ExtensionLoader &loader);
 void RegisterSubgraphFunctions(ExtensionLoader &loader);
 void RegisterParallelFunctions(ExtensionLoader &loader);
+void RegisterWeightFunctions(ExtensionLoader &loader);
+void RegisterPatternFunctions(ExtensionLoader &loader);
+void RegisterFlowFunctions(ExtensionLoader &loader);
+void RegisterMultilayerFunctions(ExtensionLoader &loader);
+void RegisterTemporalFunctions(ExtensionLoader &loader);
+void RegisterMarkovFunctions(ExtensionLoader &loader);
+void RegisterLayoutFunctions(ExtensionLoader &loader);
 void RegisterLocalReachingFunction(ExtensionLoader &loader);
 void RegisterLaplacianFunction(ExtensionLoader &loader);
 
@@ -18,6 +18,15 @@
 namespace onager {
 #endif  // __cplusplus
 
+/**
+ * Callback that appends `len` bytes at `data` to the output owned by `ctx`.
+ *
+ * Returns 0 on success and any other value on failure, which aborts the
+ * spill. The caller keeps ownership of the output, so opening, permission
+ * checks, and cleanup of files stay on the caller's side.
+ */
+typedef int32_t (*OnagerWriteFn)(void *ctx, const uint8_t *data, uintptr_t len);
+
 #ifdef __cplusplus
 extern "C" {
 #endif // __cplusplus
@@ -31,6 +40,18 @@ int64_t onager_compute_max_clique(const int64_t *src_ptr,
                                   uintptr_t edge_count,
                                   int64_t *out_nodes);
 
+/**
+ * Capacity-checked variant of `onager_compute_max_clique`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_max_clique_v2(const int64_t *src_ptr,
+                                     const int64_t *dst_ptr,
+                                     uintptr_t edge_count,
+                                     int64_t *out_nodes,
+                                     uintptr_t out_capacity);
+
 /**
  * Compute Maximum Independent Set Approximation.
  */
@@ -40,6 +61,18 @@ int64_t onager_compute_independent_set(const int64_t *src_ptr,
                                        uintptr_t edge_count,
                                        int64_t *out_nodes);
 
+/**
+ * Capacity-checked variant of `onager_compute_independent_set`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_independent_set_v2(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          int64_t *out_nodes,
+                                          uintptr_t out_capacity);
+
 /**
  * Compute Minimum Vertex Cover Approximation.
  */
@@ -49,6 +82,18 @@ int64_t onager_compute_vertex_cover(const int64_t *src_ptr,
                                     uintptr_t edge_count,
                                     int64_t *out_nodes);
 
+/**
+ * Capacity-checked variant of `onager_compute_vertex_cover`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_vertex_cover_v2(const int64_t *src_ptr,
+                                       const int64_t *dst_ptr,
+                                       uintptr_t edge_count,
+                                       int64_t *out_nodes,
+                                       uintptr_t out_capacity);
+
 /**
  * Compute TSP Approximation.
  * Returns tour length and writes tour nodes to out_tour and cost to out_cost.
@@ -61,6 +106,62 @@ int64_t onager_compute_tsp(const int64_t *src_ptr,
                            int64_t *out_tour,
                            double *out_cost);
 
+/**
+ * Capacity-checked variant of `onager_compute_tsp`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_tsp_v2(const int64_t *src_ptr,
+                              const int64_t *dst_ptr,
+                              const double *weight_ptr,
+                              uintptr_t edge_count,
+                              int64_t *out_tour,
+                              double *out_cost,
+                              uintptr_t out_capacity);
+
+/**
+ * Check whether the graph is bipartite.
+ *
+ * Returns 1 if it is, 0 if it is not, and -1 on error.
+ */
+
+int64_t onager_compute_is_bipartite(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count);
+
+/**
+ * Project a bipartite graph onto the nodes in `nodes_ptr`.
+ *
+ * Each projected edge is written to `out_src` and `out_dst` with the number
+ * of shared neighbors in `out_weights`. Returns the number of projected
+ * edges.
+ */
+
+int64_t onager_compute_bipartite_projection(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            uintptr_t edge_count,
+                                            const int64_t *nodes_ptr,
+                                            uintptr_t node_count,
+                                            int64_t *out_src,
+                                            int64_t *out_dst,
+                                            double *out_weights,
+                                            uintptr_t out_capacity);
+
+/**
+ * Compute a maximum matching of a bipartite graph.
+ *
+ * Each matched pair is written to `out_src` and `out_dst` as the first
+ * input edge that joins it. Returns the number of matched pairs.
+ */
+
+int64_t onager_compute_bipartite_matching(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          int64_t *out_src,
+                                          int64_t *out_dst,
+                                          uintptr_t out_capacity);
+
 /**
  * Compute PageRank on edge arrays.
  */
@@ -74,6 +175,25 @@ int64_t onager_compute_pagerank(const int64_t *src_ptr,
                                 int64_t *out_nodes,
                                 double *out_ranks);
 
+/**
+ * Capacity-checked variant of `onager_compute_pagerank` with optional edge weights.
+ *
+ * Empty weights mean every edge has weight 1.0. Returns the required output
+ * size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_pagerank_v2(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   uintptr_t edge_count,
+                                   const double *weights_ptr,
+                                   uintptr_t weights_count,
+                                   double damping,
+                                   uintptr_t iterations,
+                                   bool directed,
+                                   int64_t *out_nodes,
+                                   double *out_ranks,
+                                   uintptr_t out_capacity);
+
 /**
  * Compute PageRank using parallel algorithm.
  */
@@ -89,6 +209,24 @@ int64_t onager_compute_pagerank_parallel(const int64_t *src_ptr,
                                          int64_t *out_node_ids,
                                          double *out_ranks);
 
+/**
+ * Capacity-checked variant of `onager_compute_pagerank_parallel`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_pagerank_parallel_v2(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            uintptr_t edge_count,
+                                            const double *weights_ptr,
+                                            uintptr_t weights_count,
+                                            double damping,
+                                            uintptr_t iterations,
+                                            bool directed,
+                                            int64_t *out_node_ids,
+                                            double *out_ranks,
+                                            uintptr_t out_capacity);
+
 /**
  * Compute degree centrality on edge arrays.
  */
@@ -101,6 +239,21 @@ int64_t onager_compute_degree(const int64_t *src_ptr,
                               double *out_in_degree,
                               double *out_out_degree);
 
+/**
+ * Capacity-checked variant of `onager_compute_degree`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_degree_v2(const int64_t *src_ptr,
+                                 const int64_t *dst_ptr,
+                                 uintptr_t edge_count,
+                                 bool directed,
+                                 int64_t *out_nodes,
+                                 double *out_in_degree,
+                                 double *out_out_degree,
+                                 uintptr_t out_capacity);
+
 /**
  * Compute in-degree of a single node (scalar).
  */
@@ -130,6 +283,20 @@ int64_t onager_compute_betweenness(const int64_t *src_ptr,
                                    int64_t *out_nodes,
                                    double *out_centralities);
 
+/**
+ * Capacity-checked variant of `onager_compute_betweenness`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_betweenness_v2(const int64_t *src_ptr,
+                                      const int64_t *dst_ptr,
+                                      uintptr_t edge_count,
+                                      bool normalized,
+                                      int64_t *out_nodes,
+                                      double *out_centralities,
+                                      uintptr_t out_capacity);
+
 /**
  * Compute closeness centrality.
  */
@@ -140,6 +307,19 @@ int64_t onager_compute_closeness(const int64_t *src_ptr,
                                  int64_t *out_nodes,
                                  double *out_centralities);
 
+/**
+ * Capacity-checked variant of `onager_compute_closeness`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_closeness_v2(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count,
+                                    int64_t *out_nodes,
+                                    double *out_centralities,
+                                    uintptr_t out_capacity);
+
 /**
  * Compute eigenvector centrality.
  */
@@ -152,6 +332,60 @@ int64_t onager_compute_eigenvector(const int64_t *src_ptr,
                                    int64_t *out_nodes,
                                    double *out_centralities);
 
+/**
+ * Capacity-checked variant of `onager_compute_eigenvector`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_eigenvector_v2(const int64_t *src_ptr,
+                                      const int64_t *dst_ptr,
+                                      uintptr_t edge_count,
+                                      uintptr_t max_iter,
+                                      double tolerance,
+                                      int64_t *out_nodes,
+                                      double *out_centralities,
+                                      uintptr_t out_capacity);
+
+/**
+ * Compute eigenvector centrality, following edge direction when `directed` is set.
+ *
+ * Returns the number of nodes.
+ */
+
+int64_t onager_compute_eigenvector_directed(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            uintptr_t edge_count,
+                                            uintptr_t max_iter,
+                                            double tolerance,
+                                            bool directed,
+                                            int64_t *out_nodes,
+                                            double *out_centralities,
+                                            uintptr_t out_capacity);
+
+/**
+ * Compute eigenvector centrality on a weighted graph with an explicit normalization.
+ *
+ * `normalization` is `l1`, `l2`, or `max`. Empty weights mean unit weights.
+ * Scores never sum to a negative value. Returns the number of nodes.
+ *
+ * # Safety
+ * `normalization` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_eigenvector_weighted(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            uintptr_t edge_count,
+                                            const double *weights_ptr,
+                                            uintptr_t weights_count,
+                                            uintptr_t max_iter,
+                                            double tolerance,
+                                            bool directed,
+                                            const char *normalization,
+                                            int64_t *out_nodes,
+                                            double *out_centralities,
+                                            uintptr_t out_capacity);
+
 /**
  * Compute Katz centrality.
  */
@@ -165,6 +399,22 @@ int64_t onager_compute_katz(const int64_t *src_ptr,
                             int64_t *out_nodes,
                             double *out_centralities);
 
+/**
+ * Capacity-checked variant of `onager_compute_katz`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_katz_v2(const int64_t *src_ptr,
+                               const int64_t *dst_ptr,
+                               uintptr_t edge_count,
+                               double alpha,
+                               uintptr_t max_iter,
+                               double tolerance,
+                               int64_t *out_nodes,
+                               double *out_centralities,
+                               uintptr_t out_capacity);
+
 /**
  * Compute harmonic centrality.
  */
@@ -175,6 +425,151 @@ int64_t onager_compute_harmonic(const int64_t *src_ptr,
                                 int64_t *out_nodes,
                                 double *out_centralities);
 
+/**
+ * Capacity-checked variant of `onager_compute_harmonic`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_harmonic_v2(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   uintptr_t edge_count,
+                                   int64_t *out_nodes,
+                                   double *out_centralities,
+                                   uintptr_t out_capacity);
+
+/**
+ * Compute closeness centrality on weighted, optionally directed edges.
+ *
+ * Empty weights mean unit weights and empty targets mean every node. A
+ * negative `cutoff` means no cutoff. Returns the number of nodes.
+ */
+
+int64_t onager_compute_closeness_general(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         const double *weights_ptr,
+                                         uintptr_t weights_count,
+                                         bool directed,
+                                         const int64_t *targets_ptr,
+                                         uintptr_t target_count,
+                                         double cutoff,
+                                         int64_t *out_nodes,
+                                         double *out_centralities,
+                                         uintptr_t out_capacity);
+
+/**
+ * Compute harmonic centrality on weighted, optionally directed edges.
+ *
+ * Empty weights mean unit weights and empty targets mean every node. A
+ * negative `cutoff` means no cutoff. Returns the number of nodes.
+ */
+
+int64_t onager_compute_harmonic_general(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        uintptr_t edge_count,
+                                        const double *weights_ptr,
+                                        uintptr_t weights_count,
+                                        bool directed,
+                                        const int64_t *targets_ptr,
+                                        uintptr_t target_count,
+                                        double cutoff,
+                                        int64_t *out_nodes,
+                                        double *out_centralities,
+                                        uintptr_t out_capacity);
+
+/**
+ * Compute weighted betweenness centrality bounded by a distance cutoff.
+ *
+ * Empty weights mean unit weights. A negative `cutoff` means no cutoff.
+ */
+
+int64_t onager_compute_betweenness_cutoff(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          const double *weights_ptr,
+                                          uintptr_t weights_count,
+                                          bool normalized,
+                                          double cutoff,
+                                          int64_t *out_nodes,
+                                          double *out_centralities,
+                                          uintptr_t out_capacity);
+
+/**
+ * Compute betweenness centrality on directed or undirected weighted multigraphs.
+ *
+ * `parallel` is `min` or `count`. Empty weights mean unit weights. A negative
+ * `cutoff` means no cutoff.
+ *
+ * # Safety
+ * `parallel` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_betweenness_general(const int64_t *src_ptr,
+                                           const int64_t *dst_ptr,
+                                           uintptr_t edge_count,
+                                           const double *weights_ptr,
+                                           uintptr_t weights_count,
+                                           bool directed,
+                                           bool normalized,
+                                           const char *parallel,
+                                           double cutoff,
+                                           int64_t *out_nodes,
+                                           double *out_centralities,
+                                           uintptr_t out_capacity);
+
+/**
+ * Compute betweenness centrality like `onager_compute_betweenness_general`
+ * and report how the scores were normalized.
+ *
+ * `out_node_count` receives the number of nodes and `out_normalizer` the
+ * divisor applied to unnormalized scores (1 when not normalized), so
+ * `score * normalizer` recovers the unnormalized score. Either may be null.
+ * They are written whenever the computation succeeds, including size queries.
+ *
+ * # Safety
+ * `parallel` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_betweenness_scaled(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          const double *weights_ptr,
+                                          uintptr_t weights_count,
+                                          bool directed,
+                                          bool normalized,
+                                          const char *parallel,
+                                          double cutoff,
+                                          int64_t *out_nodes,
+                                          double *out_centralities,
+                                          uintptr_t out_capacity,
+                                          int64_t *out_node_count,
+                                          double *out_normalizer);
+
+/**
+ * Select the indices of the `k` highest scores, sorted by descending score.
+ *
+ * Returns the number of indices, which is `min(k, score_count)`.
+ */
+
+int64_t onager_top_k_indices(const double *scores_ptr,
+                             uintptr_t score_count,
+                             uintptr_t k,
+                             int64_t *out_indices,
+                             uintptr_t out_capacity);
+
+/**
+ * Select the indices of scores at or above `min_value`, in their original order.
+ *
+ * Returns the number of indices. Call with null `out_indices` to get the count.
+ */
+
+int64_t onager_min_value_indices(const double *scores_ptr,
+                                 uintptr_t score_count,
+                                 double min_value,
+                                 int64_t *out_indices,
+                                 uintptr_t out_capacity);
+
 /**
  * Compute VoteRank for influential spreaders.
  */
@@ -185,6 +580,19 @@ int64_t onager_compute_voterank(const int64_t *src_ptr,
                                 uintptr_t num_seeds,
                                 int64_t *out_nodes);
 
+/**
+ * Capacity-checked variant of `onager_compute_voterank`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_voterank_v2(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   uintptr_t edge_count,
+                                   uintptr_t num_seeds,
+                                   int64_t *out_nodes,
+                                   uintptr_t out_capacity);
+
 /**
  * Compute Local Reaching Centrality.
  */
@@ -196,6 +604,20 @@ int64_t onager_compute_local_reaching(const int64_t *src_ptr,
                                       int64_t *out_nodes,
                                       double *out_centralities);
 
+/**
+ * Capacity-checked variant of `onager_compute_local_reaching`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_local_reaching_v2(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         uintptr_t distance,
+                                         int64_t *out_nodes,
+                                         double *out_centralities,
+                                         uintptr_t out_capacity);
+
 /**
  * Compute Laplacian Centrality.
  */
@@ -206,6 +628,19 @@ int64_t onager_compute_laplacian(const int64_t *src_ptr,
                                  int64_t *out_nodes,
                                  double *out_centralities);
 
+/**
+ * Capacity-checked variant of `onager_compute_laplacian`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_laplacian_v2(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count,
+                                    int64_t *out_nodes,
+                                    double *out_centralities,
+                                    uintptr_t out_capacity);
+
 /**
  * Returns the last error message, or null if no error is set.
  * Note: The returned pointer is only valid until the next call to
@@ -225,6 +660,12 @@ int64_t onager_compute_laplacian(const int64_t *src_ptr,
  */
  char *onager_get_version(void);
 
+/**
+ * Returns the C ABI version, which callers compare against the version they
+ * were built for before calling any other function.
+ */
+ uint32_t onager_abi_version(void);
+
 /**
  * Creates a new graph with the given name.
  * # Safety
@@ -286,6 +727,99 @@ int64_t onager_compute_laplacian(const int64_t *src_ptr,
  */
  int64_t onager_graph_node_out_degree(const char *graph_name, int64_t node);
 
+/**
+ * Returns the summary metrics of the named graph as a JSON object.
+ * # Safety
+ * The graph_name pointer must be a valid null-terminated C string.
+ */
+ char *onager_graph_info(const char *graph_name);
+
+/**
+ * Returns 1 if two nodes of the named graph are in the same connected
+ * component, 0 if not, or -1 on error. Edge direction is ignored.
+ * # Safety
+ * The graph_name pointer must be a valid null-terminated C string.
+ */
+ int32_t onager_same_component(const char *graph_name, int64_t a, int64_t b);
+
+/**
+ * Returns the number of connected components in the named graph, ignoring
+ * edge direction.
+ * # Safety
+ * The graph_name pointer must be a valid null-terminated C string.
+ */
+ int64_t onager_component_count(const char *graph_name);
+
+/**
+ * Returns the version of the named graph, or -1 on error.
+ *
+ * The version increases whenever a node or edge is added, so callers can
+ * cache results and compare versions to detect that the graph changed.
+ * # Safety
+ * The graph_name pointer must be a valid null-terminated C string.
+ */
+ int64_t onager_graph_version(const char *graph_name);
+
+/**
+ * Returns centrality scores of the named graph as a JSON object with
+ * `node_ids` and `scores` arrays, or null on error.
+ *
+ * Results are cached per algorithm and parameters until the graph changes.
+ * # Safety
+ * All pointers must be valid null-terminated C strings.
+ */
+ char *onager_graph_centrality(const char *graph_name, const char *algorithm, const char *params);
+
+/**
+ * Builds a contraction hierarchy index for the named graph.
+ * Returns the number of shortcut edges added, or -1 on error.
+ * # Safety
+ * The graph_name pointer must be a valid null-terminated C string.
+ */
+ int64_t onager_build_ch_index(const char *graph_name);
+
+/**
+ * Returns the shortest distance between two nodes using the graph's
+ * contraction hierarchy index, infinity if unreachable, or NaN on error.
+ * # Safety
+ * The graph_name pointer must be a valid null-terminated C string.
+ */
+ double onager_ch_distance(const char *graph_name, int64_t source, int64_t target);
+
+/**
+ * Coarsen a graph by edge matching for up to `levels` levels.
+ *
+ * `method` is `heavy_edge` or `random`, and a negative `seed` means a random
+ * seed. Empty weights mean unit weights. Each coarse edge is written as a row
+ * of `out_edge_levels`, `out_src`, `out_dst`, and `out_weights`. Each fine to
+ * coarse node mapping is written as a row of `out_map_levels`, `out_fine`,
+ * and `out_coarse` when `out_map_capacity` is large enough, and the number of
+ * mapping rows is written to `out_map_count` when non-null. Returns the
+ * number of coarse edges.
+ *
+ * # Safety
+ * `method` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_coarsen_graph(const int64_t *src_ptr,
+                             const int64_t *dst_ptr,
+                             uintptr_t edge_count,
+                             const double *weights_ptr,
+                             uintptr_t weights_count,
+                             uintptr_t levels,
+                             const char *method,
+                             int64_t seed,
+                             int64_t *out_edge_levels,
+                             int64_t *out_src,
+                             int64_t *out_dst,
+                             double *out_weights,
+                             uintptr_t out_capacity,
+                             int64_t *out_map_levels,
+                             int64_t *out_fine,
+                             int64_t *out_coarse,
+                             uintptr_t out_map_capacity,
+                             int64_t *out_map_count);
+
 /**
  * Compute Louvain community detection.
  */
@@ -297,6 +831,20 @@ int64_t onager_compute_louvain(const int64_t *src_ptr,
                                int64_t *out_nodes,
                                int64_t *out_communities);
 
+/**
+ * Capacity-checked variant of `onager_compute_louvain`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_louvain_v2(const int64_t *src_ptr,
+                                  const int64_t *dst_ptr,
+                                  uintptr_t edge_count,
+                                  int64_t seed,
+                                  int64_t *out_nodes,
+                                  int64_t *out_communities,
+                                  uintptr_t out_capacity);
+
 /**
  * Compute connected components.
  */
@@ -307,6 +855,52 @@ int64_t onager_compute_connected_components(const int64_t *src_ptr,
                                             int64_t *out_nodes,
                                             int64_t *out_components);
 
+/**
+ * Capacity-checked variant of `onager_compute_connected_components`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_connected_components_v2(const int64_t *src_ptr,
+                                               const int64_t *dst_ptr,
+                                               uintptr_t edge_count,
+                                               int64_t *out_nodes,
+                                               int64_t *out_components,
+                                               uintptr_t out_capacity);
+
+/**
+ * Compute weakly connected components of directed edges.
+ *
+ * Edge direction is ignored when joining nodes, so reversed edges do not
+ * need to be passed. Each node is written as a row of `out_nodes` and
+ * `out_components`. Returns the number of nodes.
+ */
+
+int64_t onager_compute_weakly_connected_components(const int64_t *src_ptr,
+                                                   const int64_t *dst_ptr,
+                                                   uintptr_t edge_count,
+                                                   int64_t *out_nodes,
+                                                   int64_t *out_components,
+                                                   uintptr_t out_capacity);
+
+/**
+ * Compute connected components with a direction mode.
+ *
+ * `mode` is one of `undirected`, `weak`, or `strong`. Each node is written
+ * as a row of `out_nodes` and `out_components`. Returns the number of nodes.
+ *
+ * # Safety
+ * `mode` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_components_with_mode(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            uintptr_t edge_count,
+                                            const char *mode,
+                                            int64_t *out_nodes,
+                                            int64_t *out_components,
+                                            uintptr_t out_capacity);
+
 /**
  * Compute label propagation.
  */
@@ -317,6 +911,19 @@ int64_t onager_compute_label_propagation(const int64_t *src_ptr,
                                          int64_t *out_node_ids,
                                          int64_t *out_labels);
 
+/**
+ * Capacity-checked variant of `onager_compute_label_propagation`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_label_propagation_v2(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            uintptr_t edge_count,
+                                            int64_t *out_node_ids,
+                                            int64_t *out_labels,
+                                            uintptr_t out_capacity);
+
 /**
  * Compute Girvan-Newman community detection.
  */
@@ -329,17 +936,67 @@ int64_t onager_compute_girvan_newman(const int64_t *src_ptr,
                                      int64_t *out_communities);
 
 /**
- * Compute spectral clustering.
+ * Capacity-checked variant of `onager_compute_girvan_newman`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
  */
 
-int64_t onager_compute_spectral_clustering(const int64_t *src_ptr,
-                                           const int64_t *dst_ptr,
+int64_t onager_compute_girvan_newman_v2(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        uintptr_t edge_count,
+                                        int64_t target_communities,
+                                        int64_t *out_nodes,
+                                        int64_t *out_communities,
+                                        uintptr_t out_capacity);
+
+/**
+ * Compute Girvan-Newman with a stopping rule and limits.
+ *
+ * A `target_communities` of 0 removes edges until none are left and keeps
+ * the partition with the highest modularity. A `max_iterations` or
+ * `time_budget_ms` of 0 means no limit. Writes the modularity of the returned
+ * partition to `out_modularity` when it is not null. Returns the number of
+ * nodes.
+ */
+
+int64_t onager_compute_girvan_newman_with_limits(const int64_t *src_ptr,
+                                                 const int64_t *dst_ptr,
+                                                 uintptr_t edge_count,
+                                                 int64_t target_communities,
+                                                 uintptr_t max_iterations,
+                                                 uint64_t time_budget_ms,
+                                                 int64_t *out_nodes,
+                                                 int64_t *out_communities,
+                                                 uintptr_t out_capacity,
+                                                 double *out_modularity);
+
+/**
+ * Compute spectral clustering.
+ */
+
+int64_t onager_compute_spectral_clustering(const int64_t *src_ptr,
+                                           const int64_t *dst_ptr,
                                            uintptr_t edge_count,
                                            uintptr_t k,
                                            int64_t seed,
                                            int64_t *out_nodes,
                                            int64_t *out_communities);
 
+/**
+ * Capacity-checked variant of `onager_compute_spectral_clustering`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_spectral_clustering_v2(const int64_t *src_ptr,
+                                              const int64_t *dst_ptr,
+                                              uintptr_t edge_count,
+                                              uintptr_t k,
+                                              int64_t seed,
+                                              int64_t *out_nodes,
+                                              int64_t *out_communities,
+                                              uintptr_t out_capacity);
+
 /**
  * Compute infomap community detection.
  */
@@ -352,6 +1009,416 @@ int64_t onager_compute_infomap(const int64_t *src_ptr,
                                int64_t *out_nodes,
                                int64_t *out_communities);
 
+/**
+ * Capacity-checked variant of `onager_compute_infomap`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_infomap_v2(const int64_t *src_ptr,
+                                  const int64_t *dst_ptr,
+                                  uintptr_t edge_count,
+                                  uintptr_t max_iter,
+                                  int64_t seed,
+                                  int64_t *out_nodes,
+                                  int64_t *out_communities,
+                                  uintptr_t out_capacity);
+
+/**
+ * Compute components over edges with a similarity weight above `threshold`.
+ *
+ * Each node is written as a row of `out_nodes`, `out_clusters`, `out_sizes`,
+ * `out_min_similarity`, and `out_max_similarity`, where the cluster is the
+ * smallest node ID in it. The similarities are NaN for a cluster with no
+ * internal edges. Returns the number of nodes.
+ */
+
+int64_t onager_compute_similarity_components(const int64_t *src_ptr,
+                                             const int64_t *dst_ptr,
+                                             const double *weight_ptr,
+                                             uintptr_t edge_count,
+                                             double threshold,
+                                             int64_t *out_nodes,
+                                             int64_t *out_clusters,
+                                             int64_t *out_sizes,
+                                             double *out_min_similarity,
+                                             double *out_max_similarity,
+                                             uintptr_t out_capacity);
+
+/**
+ * Find the members of one node's connected component.
+ *
+ * Writes the members to `out_nodes`, starting with `node`. Returns the number
+ * of members.
+ */
+
+int64_t onager_compute_component_of(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count,
+                                    int64_t node,
+                                    int64_t *out_nodes,
+                                    uintptr_t out_capacity);
+
+/**
+ * Compute Fluid Communities with exactly `k` communities.
+ *
+ * The graph must be connected. A negative `seed` means a random seed.
+ * Returns the number of nodes.
+ */
+
+int64_t onager_compute_fluid_communities(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         uintptr_t k,
+                                         int64_t seed,
+                                         int64_t *out_nodes,
+                                         int64_t *out_communities,
+                                         uintptr_t out_capacity);
+
+/**
+ * Compute communities by Clauset-Newman-Moore greedy modularity maximization.
+ *
+ * Writes the modularity of the partition to `out_modularity` when it is not
+ * null. Returns the number of nodes.
+ */
+
+int64_t onager_compute_greedy_modularity(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         int64_t *out_nodes,
+                                         int64_t *out_communities,
+                                         uintptr_t out_capacity,
+                                         double *out_modularity);
+
+/**
+ * Find the edges and nodes that carry the shortest paths between
+ * communities.
+ *
+ * `node_ids[i]` belongs to community `node_communities[i]` for
+ * `i < node_count`. Each edge is written as a row of `out_src`, `out_dst`,
+ * `out_src_communities`, `out_dst_communities`, and `out_edge_paths`. Each
+ * node is written as a row of `out_nodes`, `out_communities`, and
+ * `out_node_paths` when `out_node_capacity` is large enough, and the number
+ * of nodes is written to `out_node_count` when non-null. Returns the number
+ * of edges.
+ */
+
+int64_t onager_compute_community_bridges(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         const int64_t *node_ids_ptr,
+                                         const int64_t *node_communities_ptr,
+                                         uintptr_t node_count,
+                                         int64_t *out_src,
+                                         int64_t *out_dst,
+                                         int64_t *out_src_communities,
+                                         int64_t *out_dst_communities,
+                                         double *out_edge_paths,
+                                         uintptr_t out_capacity,
+                                         int64_t *out_nodes,
+                                         int64_t *out_communities,
+                                         double *out_node_paths,
+                                         uintptr_t out_node_capacity,
+                                         int64_t *out_node_count);
+
+/**
+ * Compute the modularity of a given partition.
+ *
+ * `node_ids[i]` belongs to community `node_communities[i]` for
+ * `i < node_count`, and every node of the graph must be listed. Returns NaN
+ * on error.
+ */
+
+double onager_compute_modularity(const int64_t *src_ptr,
+                                 const int64_t *dst_ptr,
+                                 uintptr_t edge_count,
+                                 const int64_t *node_ids_ptr,
+                                 const int64_t *node_communities_ptr,
+                                 uintptr_t node_count);
+
+/**
+ * Compare two partitions of the same nodes, such as community assignments
+ * from two runs or two algorithms.
+ *
+ * `metric` is `nmi`, `ari`, or `v_measure`. The similarity over nodes in
+ * both partitions is written to `out_similarity` when non-null. Returns the
+ * number of nodes in both partitions.
+ *
+ * # Safety
+ * `metric` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_partition_similarity(const int64_t *a_nodes_ptr,
+                                            const int64_t *a_communities_ptr,
+                                            uintptr_t a_count,
+                                            const int64_t *b_nodes_ptr,
+                                            const int64_t *b_communities_ptr,
+                                            uintptr_t b_count,
+                                            const char *metric,
+                                            double *out_similarity);
+
+/**
+ * Partition nodes from signed edge weights with correlation clustering.
+ *
+ * Writes node cluster assignments to `out_nodes` and `out_clusters`. A
+ * negative `seed` means a random seed. Returns the number of nodes.
+ */
+
+int64_t onager_compute_correlation_clustering(const int64_t *src_ptr,
+                                              const int64_t *dst_ptr,
+                                              uintptr_t edge_count,
+                                              const double *weight_ptr,
+                                              int64_t seed,
+                                              int64_t *out_nodes,
+                                              int64_t *out_clusters,
+                                              uintptr_t out_capacity);
+
+/**
+ * Compare structural statistics of an old and a new graph snapshot.
+ *
+ * `out_node_counts`, `out_edge_counts`, `out_densities`, and
+ * `out_clustering` each take two values, old then new, and are written when
+ * non-null. Nodes that entered or left the top `hub_count` nodes by degree
+ * are written as rows of `out_hub_nodes`, `out_hub_changes` (1 for added,
+ * -1 for removed), `out_hub_old_degrees`, and `out_hub_new_degrees`.
+ * Returns the number of hub rows.
+ */
+
+int64_t onager_compare_graph_stats(const int64_t *old_src_ptr,
+                                   const int64_t *old_dst_ptr,
+                                   uintptr_t old_edge_count,
+                                   const int64_t *new_src_ptr,
+                                   const int64_t *new_dst_ptr,
+                                   uintptr_t new_edge_count,
+                                   uintptr_t hub_count,
+                                   int64_t *out_node_counts,
+                                   int64_t *out_edge_counts,
+                                   double *out_densities,
+                                   double *out_clustering,
+                                   double *out_degree_ks,
+                                   int64_t *out_hub_nodes,
+                                   int64_t *out_hub_changes,
+                                   int64_t *out_hub_old_degrees,
+                                   int64_t *out_hub_new_degrees,
+                                   uintptr_t out_capacity);
+
+/**
+ * Compare two node rankings, such as centrality scores before and after a
+ * change to a graph.
+ *
+ * `method` is `spearman` or `kendall`. The correlation over nodes scored in
+ * both rankings and the overlap of the top `top_k` nodes of each ranking are
+ * written to `out_correlation` and `out_top_k_overlap` when non-null.
+ * Returns the number of nodes scored in both rankings.
+ *
+ * # Safety
+ * `method` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compare_rankings(const int64_t *a_nodes_ptr,
+                                const double *a_scores_ptr,
+                                uintptr_t a_count,
+                                const int64_t *b_nodes_ptr,
+                                const double *b_scores_ptr,
+                                uintptr_t b_count,
+                                const char *method,
+                                uintptr_t top_k,
+                                double *out_correlation,
+                                double *out_top_k_overlap);
+
+/**
+ * Estimate the output size of per-node algorithms (distinct node count).
+ */
+
+int64_t onager_estimate_output_size_nodes(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count);
+
+/**
+ * Estimate the output size of node-pair algorithms (ordered node pairs).
+ */
+
+int64_t onager_estimate_output_size_node_pairs(const int64_t *src_ptr,
+                                               const int64_t *dst_ptr,
+                                               uintptr_t edge_count);
+
+/**
+ * Estimate the output size of edge-returning algorithms (input edge count).
+ */
+
+int64_t onager_estimate_output_size_edges(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count);
+
+/**
+ * Estimate the output size of path-returning algorithms (node count plus one).
+ */
+
+int64_t onager_estimate_output_size_path(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count);
+
+/**
+ * Estimate the number of edges a generator can produce for n nodes.
+ */
+ int64_t onager_estimate_output_size_generated_edges(uintptr_t n);
+
+/**
+ * Select `p` facility nodes with the p-median heuristic.
+ *
+ * Empty weights mean unit weights. Each node is written as a row of
+ * `out_nodes`, `out_facilities`, and `out_distances`, and facilities are the
+ * rows where the node is its own facility. Returns the number of rows.
+ */
+
+int64_t onager_compute_p_median(const int64_t *src_ptr,
+                                const int64_t *dst_ptr,
+                                uintptr_t edge_count,
+                                const double *weights_ptr,
+                                uintptr_t weights_count,
+                                uintptr_t p,
+                                int64_t *out_nodes,
+                                int64_t *out_facilities,
+                                double *out_distances,
+                                uintptr_t out_capacity);
+
+/**
+ * Assign every node to its nearest seed node.
+ *
+ * Empty weights mean unit weights. Each reachable node is written as a row of
+ * `out_nodes`, `out_seeds`, and `out_distances`. Returns the number of rows.
+ */
+
+int64_t onager_compute_voronoi(const int64_t *src_ptr,
+                               const int64_t *dst_ptr,
+                               uintptr_t edge_count,
+                               const double *weights_ptr,
+                               uintptr_t weights_count,
+                               const int64_t *seeds_ptr,
+                               uintptr_t seed_count,
+                               int64_t *out_nodes,
+                               int64_t *out_seeds,
+                               double *out_distances,
+                               uintptr_t out_capacity);
+
+/**
+ * Compute a maximum flow from `source` to `sink` over directed edges.
+ *
+ * Empty capacities mean every edge has capacity 1.0. Each input edge is
+ * written to `out_src` and `out_dst` with its flow in `out_flows`, in input
+ * order. Writes the total flow to `out_flow_value` when it is not null.
+ * Returns the number of edges.
+ */
+
+int64_t onager_compute_max_flow(const int64_t *src_ptr,
+                                const int64_t *dst_ptr,
+                                uintptr_t edge_count,
+                                const double *capacities_ptr,
+                                uintptr_t capacities_count,
+                                int64_t source,
+                                int64_t sink,
+                                int64_t *out_src,
+                                int64_t *out_dst,
+                                double *out_flows,
+                                uintptr_t out_capacity,
+                                double *out_flow_value);
+
+/**
+ * Find a minimum cut between `source` and `sink` over directed edges.
+ *
+ * Empty capacities mean every edge has capacity 1.0. Each cut edge is
+ * written to `out_src` and `out_dst` with its capacity in `out_capacities`.
+ * Writes the cut value to `out_cut_value` when it is not null. Returns the
+ * number of cut edges.
+ */
+
+int64_t onager_compute_min_cut(const int64_t *src_ptr,
+                               const int64_t *dst_ptr,
+                               uintptr_t edge_count,
+                               const double *capacities_ptr,
+                               uintptr_t capacities_count,
+                               int64_t source,
+                               int64_t sink,
+                               int64_t *out_src,
+                               int64_t *out_dst,
+                               double *out_capacities,
+                               uintptr_t out_capacity,
+                               double *out_cut_value);
+
+/**
+ * Find a minimum edge cut between nodes `a` and `b`.
+ *
+ * Each cut edge is written to `out_src` and `out_dst`, oriented from the side
+ * of `a`. Returns the number of cut edges.
+ */
+
+int64_t onager_compute_min_edge_cut(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count,
+                                    int64_t a,
+                                    int64_t b,
+                                    int64_t *out_src,
+                                    int64_t *out_dst,
+                                    uintptr_t out_capacity);
+
+/**
+ * Find a minimum vertex cut between nodes `a` and `b`.
+ *
+ * Returns the number of cut nodes written to `out_nodes`.
+ */
+
+int64_t onager_compute_min_vertex_cut(const int64_t *src_ptr,
+                                      const int64_t *dst_ptr,
+                                      uintptr_t edge_count,
+                                      int64_t a,
+                                      int64_t b,
+                                      int64_t *out_nodes,
+                                      uintptr_t out_capacity);
+
+/**
+ * Split the graph into two sides joined by the least total edge weight.
+ *
+ * Empty weights mean every edge has weight 1.0. Each node is written to
+ * `out_nodes` with its side, 0 or 1, in `out_sides`. Writes the cut weight to
+ * `out_cut_weight` when it is not null. Returns the number of nodes.
+ */
+
+int64_t onager_compute_global_min_cut(const int64_t *src_ptr,
+                                      const int64_t *dst_ptr,
+                                      uintptr_t edge_count,
+                                      const double *weights_ptr,
+                                      uintptr_t weights_count,
+                                      int64_t *out_nodes,
+                                      int64_t *out_sides,
+                                      uintptr_t out_capacity,
+                                      double *out_cut_weight);
+
+/**
+ * Compute edge connectivity, globally or between nodes `a` and `b`.
+ *
+ * `a` and `b` are ignored unless `pairwise` is true. Returns -1 on error.
+ */
+
+int64_t onager_compute_edge_connectivity(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         bool pairwise,
+                                         int64_t a,
+                                         int64_t b);
+
+/**
+ * Compute node connectivity, globally or between nodes `a` and `b`.
+ *
+ * `a` and `b` are ignored unless `pairwise` is true. Returns -1 on error.
+ */
+
+int64_t onager_compute_node_connectivity(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         bool pairwise,
+                                         int64_t a,
+                                         int64_t b);
+
 /**
  * Generate Erdős-Rényi random graph.
  */
@@ -362,6 +1429,96 @@ int64_t onager_generate_erdos_renyi(uintptr_t n,
                                     int64_t *out_src,
                                     int64_t *out_dst);
 
+/**
+ * Capacity-checked variant of `onager_generate_erdos_renyi`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_generate_erdos_renyi_v2(uintptr_t n,
+                                       double p,
+                                       uint64_t seed,
+                                       int64_t *out_src,
+                                       int64_t *out_dst,
+                                       uintptr_t out_capacity);
+
+/**
+ * Generate an Erdős-Rényi random graph with node IDs starting at `start_id`.
+ *
+ * Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+ * `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes,
+ * including those without edges, to `out_nodes`.
+ */
+
+int64_t onager_generate_erdos_renyi_with_ids(uintptr_t n,
+                                             double p,
+                                             uint64_t seed,
+                                             int64_t start_id,
+                                             int64_t *out_src,
+                                             int64_t *out_dst,
+                                             uintptr_t out_capacity,
+                                             int64_t *out_nodes,
+                                             uintptr_t out_nodes_capacity);
+
+/**
+ * Generate an Erdős-Rényi G(n, p) graph that may be directed or contain
+ * self-loops.
+ *
+ * Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+ * `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes to
+ * `out_nodes`.
+ */
+
+int64_t onager_generate_gnp(uintptr_t n,
+                            double p,
+                            bool directed,
+                            bool self_loops,
+                            uint64_t seed,
+                            int64_t start_id,
+                            int64_t *out_src,
+                            int64_t *out_dst,
+                            uintptr_t out_capacity,
+                            int64_t *out_nodes,
+                            uintptr_t out_nodes_capacity);
+
+/**
+ * Generate an Erdős-Rényi G(n, m) graph with exactly `m` edges.
+ *
+ * Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+ * `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes to
+ * `out_nodes`.
+ */
+
+int64_t onager_generate_gnm(uintptr_t n,
+                            uintptr_t m,
+                            bool directed,
+                            bool self_loops,
+                            bool parallel_edges,
+                            uint64_t seed,
+                            int64_t start_id,
+                            int64_t *out_src,
+                            int64_t *out_dst,
+                            uintptr_t out_capacity,
+                            int64_t *out_nodes,
+                            uintptr_t out_nodes_capacity);
+
+/**
+ * Drop self-loops and repeated edges from a generated edge list.
+ *
+ * Self-loops are kept when `self_loops` is set and repeated edges when
+ * `parallel_edges` is set. Returns the number of edges written.
+ */
+
+int64_t onager_simplify_edges(const int64_t *src_ptr,
+                              const int64_t *dst_ptr,
+                              uintptr_t edge_count,
+                              bool directed,
+                              bool self_loops,
+                              bool parallel_edges,
+                              int64_t *out_src,
+                              int64_t *out_dst,
+                              uintptr_t out_capacity);
+
 /**
  * Generate Barabási-Albert graph.
  */
@@ -372,6 +1529,37 @@ int64_t onager_generate_barabasi_albert(uintptr_t n,
                                         int64_t *out_src,
                                         int64_t *out_dst);
 
+/**
+ * Capacity-checked variant of `onager_generate_barabasi_albert`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_generate_barabasi_albert_v2(uintptr_t n,
+                                           uintptr_t m,
+                                           uint64_t seed,
+                                           int64_t *out_src,
+                                           int64_t *out_dst,
+                                           uintptr_t out_capacity);
+
+/**
+ * Generate a Barabási-Albert graph with node IDs starting at `start_id`.
+ *
+ * Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+ * `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes,
+ * including those without edges, to `out_nodes`.
+ */
+
+int64_t onager_generate_barabasi_albert_with_ids(uintptr_t n,
+                                                 uintptr_t m,
+                                                 uint64_t seed,
+                                                 int64_t start_id,
+                                                 int64_t *out_src,
+                                                 int64_t *out_dst,
+                                                 uintptr_t out_capacity,
+                                                 int64_t *out_nodes,
+                                                 uintptr_t out_nodes_capacity);
+
 /**
  * Generate Watts-Strogatz graph.
  */
@@ -383,6 +1571,402 @@ int64_t onager_generate_watts_strogatz(uintptr_t n,
                                        int64_t *out_src,
                                        int64_t *out_dst);
 
+/**
+ * Capacity-checked variant of `onager_generate_watts_strogatz`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_generate_watts_strogatz_v2(uintptr_t n,
+                                          uintptr_t k,
+                                          double beta,
+                                          uint64_t seed,
+                                          int64_t *out_src,
+                                          int64_t *out_dst,
+                                          uintptr_t out_capacity);
+
+/**
+ * Generate a Watts-Strogatz graph with node IDs starting at `start_id`.
+ *
+ * Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+ * `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes,
+ * including those without edges, to `out_nodes`.
+ */
+
+int64_t onager_generate_watts_strogatz_with_ids(uintptr_t n,
+                                                uintptr_t k,
+                                                double beta,
+                                                uint64_t seed,
+                                                int64_t start_id,
+                                                int64_t *out_src,
+                                                int64_t *out_dst,
+                                                uintptr_t out_capacity,
+                                                int64_t *out_nodes,
+                                                uintptr_t out_nodes_capacity);
+
+/**
+ * Generate an LFR benchmark graph with ground-truth communities.
+ *
+ * Writes the edges to `out_src` and `out_dst` and returns the edge count.
+ * Nodes `0..n` and their planted communities go to `out_nodes` and
+ * `out_communities`, which need room for `n` values.
+ */
+
+int64_t onager_generate_lfr(uintptr_t n,
+                            double mu,
+                            double avg_degree,
+                            uint64_t seed,
+                            int64_t *out_src,
+                            int64_t *out_dst,
+                            uintptr_t out_capacity,
+                            int64_t *out_nodes,
+                            int64_t *out_communities,
+                            uintptr_t out_nodes_capacity);
+
+/**
+ * Generate an LFR benchmark graph with node IDs starting at `start_id`.
+ *
+ * Same as `onager_generate_lfr`, with nodes `start_id..start_id + n` written
+ * to `out_nodes`.
+ */
+
+int64_t onager_generate_lfr_with_ids(uintptr_t n,
+                                     double mu,
+                                     double avg_degree,
+                                     uint64_t seed,
+                                     int64_t start_id,
+                                     int64_t *out_src,
+                                     int64_t *out_dst,
+                                     uintptr_t out_capacity,
+                                     int64_t *out_nodes,
+                                     int64_t *out_communities,
+                                     uintptr_t out_nodes_capacity);
+
+/**
+ * Generate a random geometric graph in the unit square.
+ *
+ * Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+ * `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes
+ * with their positions to `out_nodes`, `out_x`, and `out_y`.
+ */
+
+int64_t onager_generate_random_geometric(uintptr_t n,
+                                         double radius,
+                                         uint64_t seed,
+                                         int64_t start_id,
+                                         int64_t *out_src,
+                                         int64_t *out_dst,
+                                         uintptr_t out_capacity,
+                                         int64_t *out_nodes,
+                                         double *out_x,
+                                         double *out_y,
+                                         uintptr_t out_nodes_capacity);
+
+/**
+ * Build a k-nearest-neighbor graph from 2-d points.
+ *
+ * Writes an edge from each point to each of its `k` nearest points, with the
+ * distance, and returns the edge count.
+ */
+
+int64_t onager_build_knn_graph(const int64_t *node_ids_ptr,
+                               const double *x_ptr,
+                               const double *y_ptr,
+                               uintptr_t node_count,
+                               uintptr_t k,
+                               int64_t *out_src,
+                               int64_t *out_dst,
+                               double *out_distances,
+                               uintptr_t out_capacity);
+
+/**
+ * Build a graph from a dense row-major similarity matrix over `node_ids`.
+ *
+ * Writes an edge for each similarity at or above `threshold`, keeping the
+ * `top_k` most similar per node when `top_k` is positive, and returns the
+ * edge count.
+ */
+
+int64_t onager_build_similarity_graph(const int64_t *node_ids_ptr,
+                                      uintptr_t node_count,
+                                      const double *matrix_ptr,
+                                      uintptr_t matrix_len,
+                                      double threshold,
+                                      uintptr_t top_k,
+                                      int64_t *out_src,
+                                      int64_t *out_dst,
+                                      double *out_similarities,
+                                      uintptr_t out_capacity);
+
+/**
+ * Build a graph from `(src, dst, similarity)` triples.
+ *
+ * Same as `onager_build_similarity_graph` for a matrix given in coordinate
+ * form.
+ */
+
+int64_t onager_build_similarity_graph_pairs(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            const double *similarities_ptr,
+                                            uintptr_t pair_count,
+                                            double threshold,
+                                            uintptr_t top_k,
+                                            int64_t *out_src,
+                                            int64_t *out_dst,
+                                            double *out_similarities,
+                                            uintptr_t out_capacity);
+
+/**
+ * Compute an agglomerative clustering dendrogram.
+ *
+ * `linkage` is one of `single`, `complete`, or `average`. Each merge is
+ * written as a row of `out_child_a`, `out_child_b`, `out_merged`, and
+ * `out_heights`. Returns the number of merges.
+ *
+ * # Safety
+ * `linkage` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_hierarchical_clustering(const int64_t *src_ptr,
+                                               const int64_t *dst_ptr,
+                                               uintptr_t edge_count,
+                                               const char *linkage,
+                                               int64_t *out_child_a,
+                                               int64_t *out_child_b,
+                                               int64_t *out_merged,
+                                               double *out_heights,
+                                               uintptr_t out_capacity);
+
+/**
+ * Compute two-level Infomap over weighted, directed or undirected edges.
+ *
+ * Empty weights mean unit weights. `teleportation` is the PageRank
+ * teleportation probability used for directed flow. A negative `seed` means
+ * a random seed. Writes the codelength in bits to `out_codelength` when it is
+ * not null. Returns the number of nodes.
+ */
+
+int64_t onager_compute_weighted_infomap(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        uintptr_t edge_count,
+                                        const double *weights_ptr,
+                                        uintptr_t weights_count,
+                                        bool directed,
+                                        double teleportation,
+                                        uintptr_t max_iter,
+                                        int64_t seed,
+                                        int64_t *out_nodes,
+                                        int64_t *out_communities,
+                                        uintptr_t out_capacity,
+                                        double *out_codelength);
+
+/**
+ * Compute PageRank on edges with string node IDs.
+ *
+ * Empty weights mean unit weights. Each node is written as a row of
+ * `out_nodes` and `out_ranks`, where `out_nodes` holds pointers into the
+ * input strings. Returns the number of nodes.
+ */
+
+int64_t onager_compute_pagerank_str(const char *const *src_ptr,
+                                    const char *const *dst_ptr,
+                                    uintptr_t edge_count,
+                                    const double *weights_ptr,
+                                    uintptr_t weights_count,
+                                    double damping,
+                                    uintptr_t iterations,
+                                    bool directed,
+                                    const char **out_nodes,
+                                    double *out_ranks,
+                                    uintptr_t out_capacity);
+
+/**
+ * Compute connected components on edges with string node IDs.
+ *
+ * Each node is written as a row of `out_nodes` and `out_components`, where
+ * `out_nodes` holds pointers into the input strings. Returns the number of
+ * nodes.
+ */
+
+int64_t onager_compute_connected_components_str(const char *const *src_ptr,
+                                                const char *const *dst_ptr,
+                                                uintptr_t edge_count,
+                                                const char **out_nodes,
+                                                int64_t *out_components,
+                                                uintptr_t out_capacity);
+
+/**
+ * Compute Louvain communities on edges with string node IDs.
+ *
+ * A negative `seed` means no fixed seed. Each node is written as a row of
+ * `out_nodes` and `out_communities`, where `out_nodes` holds pointers into
+ * the input strings. Returns the number of nodes.
+ */
+
+int64_t onager_compute_louvain_str(const char *const *src_ptr,
+                                   const char *const *dst_ptr,
+                                   uintptr_t edge_count,
+                                   int64_t seed,
+                                   const char **out_nodes,
+                                   int64_t *out_communities,
+                                   uintptr_t out_capacity);
+
+/**
+ * Submits an algorithm to run on a registry graph in the background.
+ * Returns the job handle, or -1 on error.
+ * # Safety
+ * `algorithm` and `params` must point to valid null-terminated C strings.
+ */
+ int64_t onager_submit_job(const char *algorithm, const char *params);
+
+/**
+ * Returns the status of a job (`running`, `completed`, or `failed`), or null
+ * on error. The caller must free the string with onager_free.
+ */
+ char *onager_job_status(int64_t id);
+
+/**
+ * Returns the JSON result of a completed job, or null if the job is unknown,
+ * running, or failed. The caller must free the string with onager_free.
+ */
+ char *onager_job_result(int64_t id);
+
+/**
+ * Forgets a job and its result.
+ */
+ int32_t onager_drop_job(int64_t id);
+
+/**
+ * Compute Weisfeiler-Lehman subtree labels for `iterations` rounds.
+ *
+ * Each node and iteration, from 0 to `iterations`, is written as a row of
+ * `out_nodes`, `out_iterations`, and `out_labels`. Returns the number of rows.
+ */
+
+int64_t onager_compute_wl_labels(const int64_t *src_ptr,
+                                 const int64_t *dst_ptr,
+                                 uintptr_t edge_count,
+                                 uintptr_t iterations,
+                                 int64_t *out_nodes,
+                                 int64_t *out_iterations,
+                                 int64_t *out_labels,
+                                 uintptr_t out_capacity);
+
+/**
+ * Compute kernel similarities between every pair of graphs in a batch.
+ *
+ * Edge `i` belongs to graph `graph_ids[i]`. `kernel` is `wl_subtree` or
+ * `shortest_path`, and `iterations` only applies to `wl_subtree`. Each pair
+ * is written as a row of `out_graph_a`, `out_graph_b`, and
+ * `out_similarities` with `graph_a < graph_b`. Returns the number of pairs.
+ *
+ * # Safety
+ * `kernel` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_graph_kernel(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    const int64_t *graph_ids_ptr,
+                                    uintptr_t edge_count,
+                                    const char *kernel,
+                                    uintptr_t iterations,
+                                    bool normalize,
+                                    int64_t *out_graph_a,
+                                    int64_t *out_graph_b,
+                                    double *out_similarities,
+                                    uintptr_t out_capacity);
+
+/**
+ * Compute two-dimensional node coordinates.
+ *
+ * `method` is `force`, `spectral`, `circular`, or `shell`. `iterations` and
+ * `seed` only affect the force layout, and a negative `seed` means a random
+ * seed. Empty weights mean unit weights. Each node is written to `out_nodes`
+ * with its coordinates in `out_x` and `out_y`. Returns the number of nodes.
+ *
+ * # Safety
+ * `method` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_compute_layout(const int64_t *src_ptr,
+                              const int64_t *dst_ptr,
+                              uintptr_t edge_count,
+                              const double *weights_ptr,
+                              uintptr_t weights_count,
+                              const char *method,
+                              uintptr_t iterations,
+                              int64_t seed,
+                              int64_t *out_nodes,
+                              double *out_x,
+                              double *out_y,
+                              uintptr_t out_capacity);
+
+/**
+ * Compute coordinates that keep every Louvain community together.
+ *
+ * A negative `seed` means a random seed, and empty weights mean unit
+ * weights. Each node is written to `out_nodes` with its community in
+ * `out_communities` and its coordinates in `out_x` and `out_y`. Each
+ * community is written to `out_community_ids` with its number of members in
+ * `out_community_sizes` and its centroid in `out_community_x` and
+ * `out_community_y` when `out_community_capacity` is large enough, and the
+ * number of communities is written to `out_community_count` when non-null.
+ * Returns the number of nodes.
+ */
+
+int64_t onager_compute_community_layout(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        uintptr_t edge_count,
+                                        const double *weights_ptr,
+                                        uintptr_t weights_count,
+                                        uintptr_t iterations,
+                                        int64_t seed,
+                                        int64_t *out_nodes,
+                                        int64_t *out_communities,
+                                        double *out_x,
+                                        double *out_y,
+                                        uintptr_t out_capacity,
+                                        int64_t *out_community_ids,
+                                        int64_t *out_community_sizes,
+                                        double *out_community_x,
+                                        double *out_community_y,
+                                        uintptr_t out_community_capacity,
+                                        int64_t *out_community_count);
+
+/**
+ * Compute SALSA hub and authority scores.
+ */
+
+int64_t onager_compute_salsa(const int64_t *src_ptr,
+                             const int64_t *dst_ptr,
+                             uintptr_t edge_count,
+                             uintptr_t max_iter,
+                             double tolerance,
+                             int64_t *out_nodes,
+                             double *out_hubs,
+                             double *out_authorities,
+                             uintptr_t out_capacity);
+
+/**
+ * Compute co-HITS scores on a bipartite graph.
+ *
+ * Empty weights mean unit weights.
+ */
+
+int64_t onager_compute_cohits(const int64_t *src_ptr,
+                              const int64_t *dst_ptr,
+                              uintptr_t edge_count,
+                              const double *weights_ptr,
+                              uintptr_t weights_count,
+                              double lambda_u,
+                              double lambda_v,
+                              uintptr_t max_iter,
+                              double tolerance,
+                              int64_t *out_nodes,
+                              double *out_hubs,
+                              double *out_authorities,
+                              uintptr_t out_capacity);
+
 /**
  * Compute Jaccard coefficient.
  */
@@ -394,6 +1978,20 @@ int64_t onager_compute_jaccard(const int64_t *src_ptr,
                                int64_t *out_node2,
                                double *out_coefficients);
 
+/**
+ * Capacity-checked variant of `onager_compute_jaccard`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_jaccard_v2(const int64_t *src_ptr,
+                                  const int64_t *dst_ptr,
+                                  uintptr_t edge_count,
+                                  int64_t *out_node1,
+                                  int64_t *out_node2,
+                                  double *out_coefficients,
+                                  uintptr_t out_capacity);
+
 /**
  * Compute Adamic-Adar index.
  */
@@ -405,6 +2003,20 @@ int64_t onager_compute_adamic_adar(const int64_t *src_ptr,
                                    int64_t *out_node2,
                                    double *out_scores);
 
+/**
+ * Capacity-checked variant of `onager_compute_adamic_adar`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_adamic_adar_v2(const int64_t *src_ptr,
+                                      const int64_t *dst_ptr,
+                                      uintptr_t edge_count,
+                                      int64_t *out_node1,
+                                      int64_t *out_node2,
+                                      double *out_scores,
+                                      uintptr_t out_capacity);
+
 /**
  * Compute preferential attachment.
  */
@@ -416,6 +2028,20 @@ int64_t onager_compute_preferential_attachment(const int64_t *src_ptr,
                                                int64_t *out_node2,
                                                double *out_scores);
 
+/**
+ * Capacity-checked variant of `onager_compute_preferential_attachment`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_preferential_attachment_v2(const int64_t *src_ptr,
+                                                  const int64_t *dst_ptr,
+                                                  uintptr_t edge_count,
+                                                  int64_t *out_node1,
+                                                  int64_t *out_node2,
+                                                  double *out_scores,
+                                                  uintptr_t out_capacity);
+
 /**
  * Compute resource allocation index.
  */
@@ -427,6 +2053,20 @@ int64_t onager_compute_resource_allocation(const int64_t *src_ptr,
                                            int64_t *out_node2,
                                            double *out_scores);
 
+/**
+ * Capacity-checked variant of `onager_compute_resource_allocation`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_resource_allocation_v2(const int64_t *src_ptr,
+                                              const int64_t *dst_ptr,
+                                              uintptr_t edge_count,
+                                              int64_t *out_node1,
+                                              int64_t *out_node2,
+                                              double *out_scores,
+                                              uintptr_t out_capacity);
+
 /**
  * Compute common neighbors count.
  */
@@ -438,6 +2078,98 @@ int64_t onager_compute_common_neighbors(const int64_t *src_ptr,
                                         int64_t *out_node2,
                                         int64_t *out_counts);
 
+/**
+ * Capacity-checked variant of `onager_compute_common_neighbors`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_common_neighbors_v2(const int64_t *src_ptr,
+                                           const int64_t *dst_ptr,
+                                           uintptr_t edge_count,
+                                           int64_t *out_node1,
+                                           int64_t *out_node2,
+                                           int64_t *out_counts,
+                                           uintptr_t out_capacity);
+
+/**
+ * Sample up to `n_samples` distinct node pairs that are not joined by an edge.
+ *
+ * `strategy` is `uniform`, `degree`, or `hard`, and a negative `seed` means a
+ * random seed. Each pair is written as a row of `out_node1` and `out_node2`
+ * with `node1 < node2`. Returns the number of pairs.
+ *
+ * # Safety
+ * `strategy` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_sample_negative_edges(const int64_t *src_ptr,
+                                     const int64_t *dst_ptr,
+                                     uintptr_t edge_count,
+                                     uintptr_t n_samples,
+                                     const char *strategy,
+                                     int64_t seed,
+                                     int64_t *out_node1,
+                                     int64_t *out_node2,
+                                     uintptr_t out_capacity);
+
+/**
+ * Compute the stationary distribution of the random walk on weighted edges.
+ *
+ * Empty weights mean unit weights. Each node is written as a row of
+ * `out_nodes` and `out_probabilities`. Returns the number of nodes.
+ */
+
+int64_t onager_compute_stationary_distribution(const int64_t *src_ptr,
+                                               const int64_t *dst_ptr,
+                                               uintptr_t edge_count,
+                                               const double *weights_ptr,
+                                               uintptr_t weights_count,
+                                               int64_t *out_nodes,
+                                               double *out_probabilities,
+                                               uintptr_t out_capacity);
+
+/**
+ * Compute the expected number of random walk steps from each node to `target`.
+ *
+ * Empty weights mean unit weights. Each node is written as a row of
+ * `out_nodes` and `out_hitting_times`, with infinity for nodes that may never
+ * reach the target. Returns the number of nodes.
+ */
+
+int64_t onager_compute_hitting_times(const int64_t *src_ptr,
+                                     const int64_t *dst_ptr,
+                                     uintptr_t edge_count,
+                                     const double *weights_ptr,
+                                     uintptr_t weights_count,
+                                     int64_t target,
+                                     int64_t *out_nodes,
+                                     double *out_hitting_times,
+                                     uintptr_t out_capacity);
+
+/**
+ * Compute removal-effect attribution of the channels in a journey graph.
+ *
+ * Empty weights mean unit weights. `null_states` lists the non-converting
+ * absorbing states and may be empty. Each channel is written as a row of
+ * `out_nodes`, `out_removal_effects`, and `out_attributions`. Returns the
+ * number of channels.
+ */
+
+int64_t onager_compute_markov_attribution(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          const double *weights_ptr,
+                                          uintptr_t weights_count,
+                                          int64_t start,
+                                          int64_t conversion,
+                                          const int64_t *null_states_ptr,
+                                          uintptr_t null_state_count,
+                                          int64_t *out_nodes,
+                                          double *out_removal_effects,
+                                          double *out_attributions,
+                                          uintptr_t out_capacity);
+
 /**
  * Compute graph diameter.
  */
@@ -488,6 +2220,19 @@ int64_t onager_compute_triangle_count(const int64_t *src_ptr,
                                       int64_t *out_nodes,
                                       int64_t *out_counts);
 
+/**
+ * Capacity-checked variant of `onager_compute_triangle_count`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_triangle_count_v2(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         int64_t *out_nodes,
+                                         int64_t *out_counts,
+                                         uintptr_t out_capacity);
+
 /**
  * Compute assortativity coefficient.
  */
@@ -507,6 +2252,85 @@ double onager_compute_graph_density(const int64_t *src_ptr,
                                     uintptr_t edge_count,
                                     bool directed);
 
+/**
+ * Compute the category mixing matrix and categorical assortativity.
+ *
+ * `node_ids[i]` has category `node_categories[i]` for `i < node_count`. Each
+ * of the k x k matrix entries is written as a row of `out_row_categories`,
+ * `out_col_categories`, and `out_counts` in row-major order. Writes the
+ * assortativity, NaN when undefined, to `out_assortativity` when it is not
+ * null. Returns the number of entries.
+ */
+
+int64_t onager_compute_mixing_matrix(const int64_t *src_ptr,
+                                     const int64_t *dst_ptr,
+                                     uintptr_t edge_count,
+                                     const int64_t *node_ids_ptr,
+                                     const int64_t *node_categories_ptr,
+                                     uintptr_t node_count,
+                                     bool directed,
+                                     int64_t *out_row_categories,
+                                     int64_t *out_col_categories,
+                                     int64_t *out_counts,
+                                     uintptr_t out_capacity,
+                                     double *out_assortativity);
+
+/**
+ * Count directed triangles by type.
+ *
+ * Writes one count per type to `out_counts`, ordered 030T, 030C, 120D, 120U,
+ * 120C, 210, and 300. Returns the number of types.
+ */
+
+int64_t onager_compute_triangle_census(const int64_t *src_ptr,
+                                       const int64_t *dst_ptr,
+                                       uintptr_t edge_count,
+                                       int64_t *out_counts,
+                                       uintptr_t out_capacity);
+
+/**
+ * Count triangles and their weighted intensity for each node.
+ *
+ * `weights_ptr` holds one weight per edge. Returns the number of nodes.
+ */
+
+int64_t onager_compute_weighted_triangles(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          const double *weights_ptr,
+                                          uintptr_t edge_count,
+                                          int64_t *out_nodes,
+                                          int64_t *out_counts,
+                                          double *out_intensities,
+                                          uintptr_t out_capacity);
+
+/**
+ * Compute the center nodes, whose eccentricity equals the radius.
+ *
+ * Writes the radius to `out_eccentricity` when it is not null. Returns the
+ * number of center nodes.
+ */
+
+int64_t onager_compute_center_nodes(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count,
+                                    int64_t *out_nodes,
+                                    uintptr_t out_capacity,
+                                    int64_t *out_eccentricity);
+
+/**
+ * Compute the periphery nodes, whose eccentricity equals the diameter.
+ *
+ * Writes the diameter to `out_eccentricity` when it is not null. Returns the
+ * number of periphery nodes.
+ */
+
+int64_t onager_compute_periphery_nodes(const int64_t *src_ptr,
+                                       const int64_t *dst_ptr,
+                                       uintptr_t edge_count,
+                                       int64_t *out_nodes,
+                                       uintptr_t out_capacity,
+                                       int64_t *out_eccentricity);
+
 /**
  * Compute Prim's MST on weighted edge arrays.
  */
@@ -520,6 +2344,22 @@ int64_t onager_compute_prim_mst(const int64_t *src_ptr,
                                 double *out_weight,
                                 double *out_total);
 
+/**
+ * Capacity-checked variant of `onager_compute_prim_mst`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_prim_mst_v2(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   const double *weight_ptr,
+                                   uintptr_t edge_count,
+                                   int64_t *out_src,
+                                   int64_t *out_dst,
+                                   double *out_weight,
+                                   double *out_total,
+                                   uintptr_t out_capacity);
+
 /**
  * Compute Kruskal's MST on weighted edge arrays.
  */
@@ -533,6 +2373,165 @@ int64_t onager_compute_kruskal_mst(const int64_t *src_ptr,
                                    double *out_weight,
                                    double *out_total);
 
+/**
+ * Capacity-checked variant of `onager_compute_kruskal_mst`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_kruskal_mst_v2(const int64_t *src_ptr,
+                                      const int64_t *dst_ptr,
+                                      const double *weight_ptr,
+                                      uintptr_t edge_count,
+                                      int64_t *out_src,
+                                      int64_t *out_dst,
+                                      double *out_weight,
+                                      double *out_total,
+                                      uintptr_t out_capacity);
+
+/**
+ * Compute Prim's MST and label every edge with its tree.
+ *
+ * Each edge is written as a row of `out_src`, `out_dst`, `out_weight`,
+ * `out_component`, and `out_component_weight`, where the component is the
+ * smallest node ID of the tree and the component weight is its total weight.
+ * Returns the number of edges.
+ */
+
+int64_t onager_compute_prim_mst_forest(const int64_t *src_ptr,
+                                       const int64_t *dst_ptr,
+                                       const double *weight_ptr,
+                                       uintptr_t edge_count,
+                                       int64_t *out_src,
+                                       int64_t *out_dst,
+                                       double *out_weight,
+                                       int64_t *out_component,
+                                       double *out_component_weight,
+                                       uintptr_t out_capacity);
+
+/**
+ * Compute Kruskal's MST and label every edge with its tree.
+ *
+ * Each edge is written as a row of `out_src`, `out_dst`, `out_weight`,
+ * `out_component`, and `out_component_weight`, where the component is the
+ * smallest node ID of the tree and the component weight is its total weight.
+ * Returns the number of edges.
+ */
+
+int64_t onager_compute_kruskal_mst_forest(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          const double *weight_ptr,
+                                          uintptr_t edge_count,
+                                          int64_t *out_src,
+                                          int64_t *out_dst,
+                                          double *out_weight,
+                                          int64_t *out_component,
+                                          double *out_component_weight,
+                                          uintptr_t out_capacity);
+
+/**
+ * Compute a spanning tree where no node exceeds `max_degree` tree edges.
+ *
+ * Writes the same rows as `onager_compute_kruskal_mst_forest`. Returns the
+ * number of edges.
+ */
+
+int64_t onager_compute_dcmst(const int64_t *src_ptr,
+                             const int64_t *dst_ptr,
+                             const double *weight_ptr,
+                             uintptr_t edge_count,
+                             uintptr_t max_degree,
+                             int64_t *out_src,
+                             int64_t *out_dst,
+                             double *out_weight,
+                             int64_t *out_component,
+                             double *out_component_weight,
+                             uintptr_t out_capacity);
+
+/**
+ * Compute the degree of every node across all layers.
+ *
+ * Writes the total degree, the number of layers the node appears in, and the
+ * participation coefficient. Returns the number of nodes.
+ */
+
+int64_t onager_compute_multiplex_degree(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        const int64_t *layers_ptr,
+                                        uintptr_t edge_count,
+                                        int64_t *out_nodes,
+                                        int64_t *out_degrees,
+                                        int64_t *out_layer_counts,
+                                        double *out_participation,
+                                        uintptr_t out_capacity);
+
+/**
+ * Compute the degree of every node in each layer.
+ *
+ * Returns the number of `(node, layer)` rows.
+ */
+
+int64_t onager_compute_multiplex_layer_degree(const int64_t *src_ptr,
+                                              const int64_t *dst_ptr,
+                                              const int64_t *layers_ptr,
+                                              uintptr_t edge_count,
+                                              int64_t *out_nodes,
+                                              int64_t *out_layers,
+                                              int64_t *out_degrees,
+                                              uintptr_t out_capacity);
+
+/**
+ * Compare the edge sets of every pair of layers.
+ *
+ * Returns the number of layer pairs.
+ */
+
+int64_t onager_compute_layer_overlap(const int64_t *src_ptr,
+                                     const int64_t *dst_ptr,
+                                     const int64_t *layers_ptr,
+                                     uintptr_t edge_count,
+                                     bool directed,
+                                     int64_t *out_layer_a,
+                                     int64_t *out_layer_b,
+                                     int64_t *out_shared,
+                                     double *out_jaccard,
+                                     uintptr_t out_capacity);
+
+/**
+ * Compute PageRank over the overlay of all layers.
+ *
+ * Returns the number of nodes.
+ */
+
+int64_t onager_compute_multiplex_pagerank(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          const int64_t *layers_ptr,
+                                          uintptr_t edge_count,
+                                          double damping,
+                                          uintptr_t iterations,
+                                          bool directed,
+                                          int64_t *out_nodes,
+                                          double *out_ranks,
+                                          uintptr_t out_capacity);
+
+/**
+ * Compute PageRank within each layer.
+ *
+ * Returns the number of `(node, layer)` rows.
+ */
+
+int64_t onager_compute_multiplex_layer_pagerank(const int64_t *src_ptr,
+                                                const int64_t *dst_ptr,
+                                                const int64_t *layers_ptr,
+                                                uintptr_t edge_count,
+                                                double damping,
+                                                uintptr_t iterations,
+                                                bool directed,
+                                                int64_t *out_nodes,
+                                                int64_t *out_layers,
+                                                double *out_ranks,
+                                                uintptr_t out_capacity);
+
 /**
  * Compute parallel BFS from a single source.
  */
@@ -543,6 +2542,19 @@ int64_t onager_compute_bfs_parallel(const int64_t *src_ptr,
                                     int64_t source,
                                     int64_t *out_order);
 
+/**
+ * Capacity-checked variant of `onager_compute_bfs_parallel`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_bfs_parallel_v2(const int64_t *src_ptr,
+                                       const int64_t *dst_ptr,
+                                       uintptr_t edge_count,
+                                       int64_t source,
+                                       int64_t *out_order,
+                                       uintptr_t out_capacity);
+
 /**
  * Compute parallel shortest paths from a single source.
  */
@@ -554,6 +2566,20 @@ int64_t onager_compute_shortest_paths_parallel(const int64_t *src_ptr,
                                                int64_t *out_nodes,
                                                double *out_distances);
 
+/**
+ * Capacity-checked variant of `onager_compute_shortest_paths_parallel`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_shortest_paths_parallel_v2(const int64_t *src_ptr,
+                                                  const int64_t *dst_ptr,
+                                                  uintptr_t edge_count,
+                                                  int64_t source,
+                                                  int64_t *out_nodes,
+                                                  double *out_distances,
+                                                  uintptr_t out_capacity);
+
 /**
  * Compute parallel connected components.
  */
@@ -564,6 +2590,19 @@ int64_t onager_compute_components_parallel(const int64_t *src_ptr,
                                            int64_t *out_nodes,
                                            int64_t *out_components);
 
+/**
+ * Capacity-checked variant of `onager_compute_components_parallel`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_components_parallel_v2(const int64_t *src_ptr,
+                                              const int64_t *dst_ptr,
+                                              uintptr_t edge_count,
+                                              int64_t *out_nodes,
+                                              int64_t *out_components,
+                                              uintptr_t out_capacity);
+
 /**
  * Compute parallel clustering coefficients.
  */
@@ -574,6 +2613,19 @@ int64_t onager_compute_clustering_parallel(const int64_t *src_ptr,
                                            int64_t *out_nodes,
                                            double *out_coefficients);
 
+/**
+ * Capacity-checked variant of `onager_compute_clustering_parallel`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_clustering_parallel_v2(const int64_t *src_ptr,
+                                              const int64_t *dst_ptr,
+                                              uintptr_t edge_count,
+                                              int64_t *out_nodes,
+                                              double *out_coefficients,
+                                              uintptr_t out_capacity);
+
 /**
  * Compute parallel triangle count.
  */
@@ -584,6 +2636,103 @@ int64_t onager_compute_triangles_parallel(const int64_t *src_ptr,
                                           int64_t *out_nodes,
                                           int64_t *out_counts);
 
+/**
+ * Capacity-checked variant of `onager_compute_triangles_parallel`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_triangles_parallel_v2(const int64_t *src_ptr,
+                                             const int64_t *dst_ptr,
+                                             uintptr_t edge_count,
+                                             int64_t *out_nodes,
+                                             int64_t *out_counts,
+                                             uintptr_t out_capacity);
+
+/**
+ * Compute a minimum spanning tree with parallel Borůvka.
+ *
+ * Each edge is written as a row of `out_src`, `out_dst`, `out_weight`,
+ * `out_component`, and `out_component_weight`, like
+ * `onager_compute_kruskal_mst_forest`. Returns the number of edges.
+ */
+
+int64_t onager_compute_boruvka_mst(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   const double *weight_ptr,
+                                   uintptr_t edge_count,
+                                   int64_t *out_src,
+                                   int64_t *out_dst,
+                                   double *out_weight,
+                                   int64_t *out_component,
+                                   double *out_component_weight,
+                                   uintptr_t out_capacity);
+
+/**
+ * Find nodes with fan-in or fan-out bursts in a timestamped edge list.
+ *
+ * Each reported node and direction is written as a row of `out_nodes`,
+ * `out_directions`, `out_fan_sizes`, `out_edge_counts`, `out_window_starts`,
+ * and `out_window_ends`. Directions are coded as 0 (fan-in) or 1 (fan-out).
+ * Returns the number of rows.
+ */
+
+int64_t onager_compute_fan_patterns(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    const int64_t *ts_ptr,
+                                    uintptr_t edge_count,
+                                    uintptr_t min_fan,
+                                    int64_t window,
+                                    int64_t *out_nodes,
+                                    int64_t *out_directions,
+                                    int64_t *out_fan_sizes,
+                                    int64_t *out_edge_counts,
+                                    int64_t *out_window_starts,
+                                    int64_t *out_window_ends,
+                                    uintptr_t out_capacity);
+
+/**
+ * Find windows in which a node is unusually active.
+ *
+ * Each flagged node and window is written as a row of `out_nodes`,
+ * `out_window_starts`, `out_activities`, and `out_zscores`.
+ * Returns the number of rows.
+ */
+
+int64_t onager_detect_bursts(const int64_t *src_ptr,
+                             const int64_t *dst_ptr,
+                             const int64_t *ts_ptr,
+                             uintptr_t edge_count,
+                             int64_t window,
+                             double zscore_threshold,
+                             int64_t *out_nodes,
+                             int64_t *out_window_starts,
+                             int64_t *out_activities,
+                             double *out_zscores,
+                             uintptr_t out_capacity);
+
+/**
+ * Find directed cycles whose edge weights approximately conserve.
+ *
+ * Each cycle node is written as a row of `out_cycle_ids`, `out_positions`,
+ * `out_nodes`, and `out_weights`, where the weight is that of the edge
+ * leaving the node. At most `max_cycles` cycles are searched for.
+ * Returns the number of rows.
+ */
+
+int64_t onager_find_weighted_cycles(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    const double *weights_ptr,
+                                    uintptr_t edge_count,
+                                    uintptr_t max_len,
+                                    double tolerance,
+                                    uintptr_t max_cycles,
+                                    int64_t *out_cycle_ids,
+                                    int64_t *out_positions,
+                                    int64_t *out_nodes,
+                                    double *out_weights,
+                                    uintptr_t out_capacity);
+
 /**
  * Compute personalized PageRank.
  */
@@ -600,6 +2749,141 @@ int64_t onager_compute_personalized_pagerank(const int64_t *src_ptr,
                                              int64_t *out_nodes,
                                              double *out_scores);
 
+/**
+ * Capacity-checked variant of `onager_compute_personalized_pagerank`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_personalized_pagerank_v2(const int64_t *src_ptr,
+                                                const int64_t *dst_ptr,
+                                                uintptr_t edge_count,
+                                                const int64_t *pers_nodes_ptr,
+                                                const double *pers_weights_ptr,
+                                                uintptr_t pers_count,
+                                                double damping,
+                                                uintptr_t max_iter,
+                                                double tolerance,
+                                                int64_t *out_nodes,
+                                                double *out_scores,
+                                                uintptr_t out_capacity);
+
+/**
+ * Compute personalized PageRank with uniform teleportation over a node subset.
+ */
+
+int64_t onager_compute_personalized_pagerank_uniform(const int64_t *src_ptr,
+                                                     const int64_t *dst_ptr,
+                                                     uintptr_t edge_count,
+                                                     const int64_t *subset_ptr,
+                                                     uintptr_t subset_count,
+                                                     double damping,
+                                                     uintptr_t max_iter,
+                                                     double tolerance,
+                                                     int64_t *out_nodes,
+                                                     double *out_scores,
+                                                     uintptr_t out_capacity);
+
+/**
+ * Classify nodes into hubs, connectors, peripheral nodes, and isolates.
+ *
+ * Each node is written as a row of `out_nodes`, `out_roles`, `out_degrees`,
+ * `out_betweenness`, and `out_clustering`. Roles are coded as 0 (isolate),
+ * 1 (peripheral), 2 (connector), or 3 (hub). A negative `seed` means a
+ * random seed. Returns the number of nodes.
+ */
+
+int64_t onager_classify_node_roles(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   uintptr_t edge_count,
+                                   uintptr_t samples,
+                                   int64_t seed,
+                                   int64_t *out_nodes,
+                                   int64_t *out_roles,
+                                   int64_t *out_degrees,
+                                   double *out_betweenness,
+                                   double *out_clustering,
+                                   uintptr_t out_capacity);
+
+/**
+ * Fit a degree-corrected stochastic block model.
+ *
+ * Writes node block assignments to `out_nodes` and `out_blocks`, and the
+ * row-major `k x k` block probability matrix to `out_probabilities` when
+ * `out_probabilities_capacity` is at least `k * k`. A negative `seed` means
+ * a random seed. Returns the number of nodes.
+ */
+
+int64_t onager_fit_sbm(const int64_t *src_ptr,
+                       const int64_t *dst_ptr,
+                       uintptr_t edge_count,
+                       uintptr_t k,
+                       int64_t seed,
+                       int64_t *out_nodes,
+                       int64_t *out_blocks,
+                       uintptr_t out_capacity,
+                       double *out_probabilities,
+                       uintptr_t out_probabilities_capacity);
+
+/**
+ * Compute normalized spectral clustering over weighted edges.
+ *
+ * Empty weights mean unit weights. A `k` of 0 picks the number of clusters
+ * in `1..=max_k` by the largest eigengap, and the number used is written to
+ * `out_k`. A negative `seed` means a random seed. Returns the number of nodes.
+ */
+
+int64_t onager_compute_weighted_spectral_clustering(const int64_t *src_ptr,
+                                                    const int64_t *dst_ptr,
+                                                    uintptr_t edge_count,
+                                                    const double *weights_ptr,
+                                                    uintptr_t weights_count,
+                                                    uintptr_t k,
+                                                    uintptr_t max_k,
+                                                    int64_t seed,
+                                                    int64_t *out_nodes,
+                                                    int64_t *out_communities,
+                                                    uintptr_t out_capacity,
+                                                    int64_t *out_k);
+
+/**
+ * Write all-pairs shortest distances as a Parquet file through `write`.
+ *
+ * The file has the columns `src`, `dst`, and `distance`. Returns the number
+ * of rows written, or -1 on error.
+ *
+ * # Safety
+ * `write` must be safe to call with `ctx` until this function returns.
+ */
+
+int64_t onager_spill_all_pairs_distances(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         const double *weights_ptr,
+                                         uintptr_t weights_count,
+                                         OnagerWriteFn write,
+                                         void *ctx);
+
+/**
+ * Write link prediction scores for all node pairs as a Parquet file through
+ * `write`.
+ *
+ * `score` is one of `jaccard`, `adamic_adar`, `resource_alloc`,
+ * `common_neighbors`, or `pref_attach`. The file has the columns `node1`,
+ * `node2`, and `score`. Returns the number of rows written, or -1 on error.
+ *
+ * # Safety
+ * `score` must point to a valid null-terminated C string, and `write` must be
+ * safe to call with `ctx` until this function returns.
+ */
+
+int64_t onager_spill_link_scores(const int64_t *src_ptr,
+                                 const int64_t *dst_ptr,
+                                 uintptr_t edge_count,
+                                 const char *score,
+                                 OnagerWriteFn write,
+                                 void *ctx);
+
 /**
  * Compute ego graph.
  */
@@ -612,6 +2896,42 @@ int64_t onager_compute_ego_graph(const int64_t *src_ptr,
                                  int64_t *out_src,
                                  int64_t *out_dst);
 
+/**
+ * Capacity-checked variant of `onager_compute_ego_graph`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_ego_graph_v2(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count,
+                                    int64_t center,
+                                    uintptr_t radius,
+                                    int64_t *out_src,
+                                    int64_t *out_dst,
+                                    uintptr_t out_capacity);
+
+/**
+ * Compute the ego network profile of a node.
+ *
+ * Empty weights mean unit weights. Writes the number of alters and the
+ * number of the center's ties to `out_counts`, and the density, broker score,
+ * and mean, smallest, and largest tie strength to `out_metrics`, when
+ * non-null. `out_counts` takes two values and `out_metrics` takes five.
+ * Returns the number of alters.
+ */
+
+int64_t onager_compute_ego_metrics(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   uintptr_t edge_count,
+                                   const double *weights_ptr,
+                                   uintptr_t weights_count,
+                                   int64_t center,
+                                   uintptr_t radius,
+                                   bool directed,
+                                   int64_t *out_counts,
+                                   double *out_metrics);
+
 /**
  * Compute k-hop neighbors.
  */
@@ -623,6 +2943,20 @@ int64_t onager_compute_k_hop_neighbors(const int64_t *src_ptr,
                                        uintptr_t k,
                                        int64_t *out_nodes);
 
+/**
+ * Capacity-checked variant of `onager_compute_k_hop_neighbors`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_k_hop_neighbors_v2(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          int64_t start,
+                                          uintptr_t k,
+                                          int64_t *out_nodes,
+                                          uintptr_t out_capacity);
+
 /**
  * Compute induced subgraph.
  */
@@ -635,6 +2969,111 @@ int64_t onager_compute_induced_subgraph(const int64_t *src_ptr,
                                         int64_t *out_src,
                                         int64_t *out_dst);
 
+/**
+ * Capacity-checked variant of `onager_compute_induced_subgraph`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_induced_subgraph_v2(const int64_t *src_ptr,
+                                           const int64_t *dst_ptr,
+                                           uintptr_t edge_count,
+                                           const int64_t *node_ids_ptr,
+                                           uintptr_t node_count,
+                                           int64_t *out_src,
+                                           int64_t *out_dst,
+                                           uintptr_t out_capacity);
+
+/**
+ * Remove forbidden nodes and edges from an edge list.
+ *
+ * Empty weights produce no output weights. Returns the number of kept edges.
+ */
+
+int64_t onager_remove_forbidden(const int64_t *src_ptr,
+                                const int64_t *dst_ptr,
+                                uintptr_t edge_count,
+                                const double *weights_ptr,
+                                uintptr_t weights_count,
+                                const int64_t *forbidden_nodes_ptr,
+                                uintptr_t forbidden_nodes_count,
+                                const int64_t *forbidden_src_ptr,
+                                const int64_t *forbidden_dst_ptr,
+                                uintptr_t forbidden_edge_count,
+                                int64_t *out_src,
+                                int64_t *out_dst,
+                                double *out_weights,
+                                uintptr_t out_capacity);
+
+/**
+ * Find the densest subgraph by peeling, or exactly by maximum flow.
+ *
+ * Writes the density of the returned node set to `out_density` when it is not
+ * null. Returns the number of nodes.
+ */
+
+int64_t onager_compute_densest_subgraph(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        uintptr_t edge_count,
+                                        bool exact,
+                                        int64_t *out_nodes,
+                                        uintptr_t out_capacity,
+                                        double *out_density);
+
+/**
+ * Compute betweenness centrality over time-respecting shortest paths.
+ *
+ * Each node is written as a row of `out_nodes` and `out_centralities`.
+ * Returns the number of nodes.
+ */
+
+int64_t onager_compute_temporal_betweenness(const int64_t *src_ptr,
+                                            const int64_t *dst_ptr,
+                                            const int64_t *ts_ptr,
+                                            uintptr_t edge_count,
+                                            bool normalized,
+                                            int64_t *out_nodes,
+                                            double *out_centralities,
+                                            uintptr_t out_capacity);
+
+/**
+ * Find the nodes reachable from `source` by time-respecting paths within
+ * `[t_start, t_end]`.
+ *
+ * Each reached node is written as a row of `out_nodes` and
+ * `out_arrival_times`. Returns the number of reached nodes.
+ */
+
+int64_t onager_compute_temporal_reachability(const int64_t *src_ptr,
+                                             const int64_t *dst_ptr,
+                                             const int64_t *ts_ptr,
+                                             uintptr_t edge_count,
+                                             int64_t source,
+                                             int64_t t_start,
+                                             int64_t t_end,
+                                             int64_t *out_nodes,
+                                             int64_t *out_arrival_times,
+                                             uintptr_t out_capacity);
+
+/**
+ * Split each source entity's timestamped edges into sessions and return the
+ * nodes each session visits.
+ *
+ * Each step is written as a row of `out_sessions`, `out_entities`,
+ * `out_steps`, and `out_nodes`. Returns the number of rows.
+ */
+
+int64_t onager_extract_paths(const int64_t *src_ptr,
+                             const int64_t *dst_ptr,
+                             const int64_t *ts_ptr,
+                             uintptr_t edge_count,
+                             int64_t session_gap,
+                             int64_t *out_sessions,
+                             int64_t *out_entities,
+                             int64_t *out_steps,
+                             int64_t *out_nodes,
+                             uintptr_t out_capacity);
+
 /**
  * Compute Dijkstra shortest paths.
  */
@@ -646,6 +3085,20 @@ int64_t onager_compute_dijkstra(const int64_t *src_ptr,
                                 int64_t *out_nodes,
                                 double *out_distances);
 
+/**
+ * Capacity-checked variant of `onager_compute_dijkstra`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_dijkstra_v2(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   uintptr_t edge_count,
+                                   int64_t source_node,
+                                   int64_t *out_nodes,
+                                   double *out_distances,
+                                   uintptr_t out_capacity);
+
 /**
  * Compute BFS traversal.
  */
@@ -656,6 +3109,19 @@ int64_t onager_compute_bfs(const int64_t *src_ptr,
                            int64_t source_node,
                            int64_t *out_order);
 
+/**
+ * Capacity-checked variant of `onager_compute_bfs`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_bfs_v2(const int64_t *src_ptr,
+                              const int64_t *dst_ptr,
+                              uintptr_t edge_count,
+                              int64_t source_node,
+                              int64_t *out_order,
+                              uintptr_t out_capacity);
+
 /**
  * Compute DFS traversal.
  */
@@ -666,6 +3132,19 @@ int64_t onager_compute_dfs(const int64_t *src_ptr,
                            int64_t source_node,
                            int64_t *out_order);
 
+/**
+ * Capacity-checked variant of `onager_compute_dfs`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_dfs_v2(const int64_t *src_ptr,
+                              const int64_t *dst_ptr,
+                              uintptr_t edge_count,
+                              int64_t source_node,
+                              int64_t *out_order,
+                              uintptr_t out_capacity);
+
 /**
  * Compute Bellman-Ford shortest paths on weighted edge arrays.
  */
@@ -678,6 +3157,42 @@ int64_t onager_compute_bellman_ford(const int64_t *src_ptr,
                                     int64_t *out_nodes,
                                     double *out_distances);
 
+/**
+ * Capacity-checked variant of `onager_compute_bellman_ford`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_bellman_ford_v2(const int64_t *src_ptr,
+                                       const int64_t *dst_ptr,
+                                       const double *weight_ptr,
+                                       uintptr_t edge_count,
+                                       int64_t source,
+                                       int64_t *out_nodes,
+                                       double *out_distances,
+                                       uintptr_t out_capacity);
+
+/**
+ * Compute the shortest path between two nodes.
+ *
+ * Writes the path nodes in order with the distance from the source to each
+ * one. Uses Bellman-Ford when a weight is negative and Dijkstra otherwise.
+ * Empty weights mean unit weights. Returns the number of path nodes, which
+ * is 0 when the target is unreachable.
+ */
+
+int64_t onager_compute_shortest_path(const int64_t *src_ptr,
+                                     const int64_t *dst_ptr,
+                                     uintptr_t edge_count,
+                                     const double *weights_ptr,
+                                     uintptr_t weights_count,
+                                     int64_t source,
+                                     int64_t target,
+                                     bool directed,
+                                     int64_t *out_nodes,
+                                     double *out_distances,
+                                     uintptr_t out_capacity);
+
 /**
  * Compute Floyd-Warshall all-pairs shortest paths.
  */
@@ -690,6 +3205,21 @@ int64_t onager_compute_floyd_warshall(const int64_t *src_ptr,
                                       int64_t *out_dst,
                                       double *out_distances);
 
+/**
+ * Capacity-checked variant of `onager_compute_floyd_warshall`.
+ *
+ * Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+ */
+
+int64_t onager_compute_floyd_warshall_v2(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         const double *weight_ptr,
+                                         uintptr_t edge_count,
+                                         int64_t *out_src,
+                                         int64_t *out_dst,
+                                         double *out_distances,
+                                         uintptr_t out_capacity);
+
 /**
  * Compute shortest distance between two nodes (scalar).
  */
@@ -700,6 +3230,269 @@ double onager_compute_shortest_distance(const int64_t *src_ptr,
                                         int64_t source_node,
                                         int64_t target_node);
 
+/**
+ * Compute the weighted shortest distance between two nodes.
+ *
+ * Empty weights mean unit weights. Returns infinity when the target is
+ * unreachable and NaN on error.
+ */
+
+double onager_compute_shortest_distance_weighted(const int64_t *src_ptr,
+                                                 const int64_t *dst_ptr,
+                                                 uintptr_t edge_count,
+                                                 const double *weights_ptr,
+                                                 uintptr_t weights_count,
+                                                 int64_t source_node,
+                                                 int64_t target_node);
+
+/**
+ * Compute the shortest path tree from a source node.
+ *
+ * Writes one `(parent, child, distance)` row per node reachable from the
+ * source. Empty weights mean unit weights. Returns the number of rows.
+ */
+
+int64_t onager_compute_shortest_path_tree(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          const double *weights_ptr,
+                                          uintptr_t weights_count,
+                                          int64_t source,
+                                          int64_t *out_parents,
+                                          int64_t *out_children,
+                                          double *out_distances,
+                                          uintptr_t out_capacity);
+
+/**
+ * Compute the nodes within a path cost budget of a source node.
+ *
+ * Writes one `(node, distance)` row per node whose shortest distance from
+ * the source is at most `max_cost`. Empty weights mean unit weights.
+ * Returns the number of rows.
+ */
+
+int64_t onager_compute_nodes_within_cost(const int64_t *src_ptr,
+                                         const int64_t *dst_ptr,
+                                         uintptr_t edge_count,
+                                         const double *weights_ptr,
+                                         uintptr_t weights_count,
+                                         int64_t source,
+                                         double max_cost,
+                                         int64_t *out_nodes,
+                                         double *out_distances,
+                                         uintptr_t out_capacity);
+
+/**
+ * Compute a topological ordering of a directed graph.
+ *
+ * Writes every node to `out_nodes` so that each edge points forward in
+ * the ordering. Returns the number of nodes, or -1 with an error naming a
+ * cycle when the graph is not acyclic.
+ */
+
+int64_t onager_compute_topological_sort(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        uintptr_t edge_count,
+                                        int64_t *out_nodes,
+                                        uintptr_t out_capacity);
+
+/**
+ * Returns 1 if the directed graph has no cycles, 0 if it has one, or -1
+ * on error.
+ */
+ int32_t onager_is_dag(const int64_t *src_ptr, const int64_t *dst_ptr, uintptr_t edge_count);
+
+/**
+ * Compute the widest path between two nodes.
+ *
+ * Writes the path nodes in order with the width of each prefix. Empty
+ * weights mean unit weights. Returns the number of path nodes, which is 0
+ * when the target is unreachable.
+ */
+
+int64_t onager_compute_widest_path(const int64_t *src_ptr,
+                                   const int64_t *dst_ptr,
+                                   uintptr_t edge_count,
+                                   const double *weights_ptr,
+                                   uintptr_t weights_count,
+                                   int64_t source,
+                                   int64_t target,
+                                   int64_t *out_nodes,
+                                   double *out_widths,
+                                   uintptr_t out_capacity);
+
+/**
+ * Compute widest path widths from a source to every reachable node.
+ *
+ * Empty weights mean unit weights. Returns the number of nodes.
+ */
+
+int64_t onager_compute_widest_paths(const int64_t *src_ptr,
+                                    const int64_t *dst_ptr,
+                                    uintptr_t edge_count,
+                                    const double *weights_ptr,
+                                    uintptr_t weights_count,
+                                    int64_t source,
+                                    int64_t *out_nodes,
+                                    double *out_widths,
+                                    uintptr_t out_capacity);
+
+/**
+ * Compute a path that only uses edges of the allowed types.
+ *
+ * Writes the path nodes in order to `out_nodes` and the type of each of
+ * the edges between them to `out_types`, which gets one entry fewer.
+ * Returns the number of path nodes, which is 0 when the target is
+ * unreachable.
+ */
+
+int64_t onager_compute_path_with_allowed_types(const int64_t *src_ptr,
+                                               const int64_t *dst_ptr,
+                                               const int64_t *types_ptr,
+                                               uintptr_t edge_count,
+                                               const int64_t *allowed_ptr,
+                                               uintptr_t allowed_count,
+                                               int64_t source,
+                                               int64_t target,
+                                               int64_t *out_nodes,
+                                               int64_t *out_types,
+                                               uintptr_t out_capacity);
+
+/**
+ * Compute shortest distances between every pair of nodes in a subset.
+ *
+ * Empty weights mean unit weights. Returns the number of ordered pairs.
+ */
+
+int64_t onager_compute_pairwise_distances(const int64_t *src_ptr,
+                                          const int64_t *dst_ptr,
+                                          uintptr_t edge_count,
+                                          const double *weights_ptr,
+                                          uintptr_t weights_count,
+                                          const int64_t *nodes_ptr,
+                                          uintptr_t node_count,
+                                          int64_t *out_src,
+                                          int64_t *out_dst,
+                                          double *out_distances,
+                                          uintptr_t out_capacity);
+
+/**
+ * Compute PageRank with per-edge-type weight multipliers.
+ *
+ * Each edge is weighted by the multiplier of its type in `types`, taken from
+ * the `type_ids` and `type_multipliers` pairs. Types without a multiplier
+ * count as 1.
+ */
+
+int64_t onager_compute_typed_pagerank(const int64_t *src_ptr,
+                                      const int64_t *dst_ptr,
+                                      const int64_t *types_ptr,
+                                      uintptr_t edge_count,
+                                      const int64_t *type_ids_ptr,
+                                      const double *type_multipliers_ptr,
+                                      uintptr_t type_count,
+                                      double damping,
+                                      uintptr_t iterations,
+                                      bool directed,
+                                      int64_t *out_nodes,
+                                      double *out_ranks,
+                                      uintptr_t out_capacity);
+
+/**
+ * Compute personalized PageRank with per-edge-type weight multipliers.
+ */
+
+int64_t onager_compute_typed_personalized_pagerank(const int64_t *src_ptr,
+                                                   const int64_t *dst_ptr,
+                                                   const int64_t *types_ptr,
+                                                   uintptr_t edge_count,
+                                                   const int64_t *type_ids_ptr,
+                                                   const double *type_multipliers_ptr,
+                                                   uintptr_t type_count,
+                                                   const int64_t *pers_nodes_ptr,
+                                                   const double *pers_weights_ptr,
+                                                   uintptr_t pers_count,
+                                                   double damping,
+                                                   uintptr_t max_iter,
+                                                   double tolerance,
+                                                   int64_t *out_nodes,
+                                                   double *out_scores,
+                                                   uintptr_t out_capacity);
+
+/**
+ * Compute Katz centrality with per-edge-type weight multipliers.
+ */
+
+int64_t onager_compute_typed_katz(const int64_t *src_ptr,
+                                  const int64_t *dst_ptr,
+                                  const int64_t *types_ptr,
+                                  uintptr_t edge_count,
+                                  const int64_t *type_ids_ptr,
+                                  const double *type_multipliers_ptr,
+                                  uintptr_t type_count,
+                                  double alpha,
+                                  uintptr_t max_iter,
+                                  double tolerance,
+                                  int64_t *out_nodes,
+                                  double *out_centralities,
+                                  uintptr_t out_capacity);
+
+/**
+ * Transform edge weights.
+ *
+ * `method` is one of `log`, `inverse`, `minmax`, or `zscore`. Writes the
+ * transformed weights to `out_weights` in input order. Returns the number of
+ * weights.
+ *
+ * # Safety
+ * `method` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_transform_weights(const double *weights_ptr,
+                                 uintptr_t weights_count,
+                                 const char *method,
+                                 double *out_weights,
+                                 uintptr_t out_capacity);
+
+/**
+ * Convert edge weights to distances.
+ *
+ * `semantics` is `distance`, which copies the weights, or `similarity`, which
+ * inverts them. Writes the distances to `out_weights` in input order. Returns
+ * the number of weights.
+ *
+ * # Safety
+ * `semantics` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_weights_to_distances(const double *weights_ptr,
+                                    uintptr_t weights_count,
+                                    const char *semantics,
+                                    double *out_weights,
+                                    uintptr_t out_capacity);
+
+/**
+ * Collapse repeated `(src, dst)` pairs into one weighted edge each.
+ *
+ * `method` is one of `sum`, `count`, `mean`, or `max`. Empty weights mean
+ * every edge has weight 1. Writes one row per distinct pair in the order it
+ * first appears. Returns the number of rows.
+ *
+ * # Safety
+ * `method` must point to a valid null-terminated C string.
+ */
+
+int64_t onager_aggregate_parallel_edges(const int64_t *src_ptr,
+                                        const int64_t *dst_ptr,
+                                        uintptr_t edge_count,
+                                        const double *weights_ptr,
+                                        uintptr_t weights_count,
+                                        const char *method,
+                                        int64_t *out_src,
+                                        int64_t *out_dst,
+                                        double *out_weights,
+                                        uintptr_t out_capacity);
+
 #ifdef __cplusplus
 }  // extern "C"
 #endif  // __cplusplus
@@ -20,6 +20,13 @@ namespace duckdb {
 void OnagerExtension::Load(ExtensionLoader &loader) {
   loader.SetDescription("Onager: Graph Data Analytics Extension");
 
+  // Refuse to load against a Rust core built for a different ABI
+  uint32_t abi_version = ::onager::onager_abi_version();
+  if (abi_version != ONAGER_ABI_VERSION) {
+    throw IOException("Onager core ABI version " + std::to_string(abi_version) + " does not match extension ABI version " +
+                      std::to_string(ONAGER_ABI_VERSION));
+  }
+
   // Register all functions from modular files
   onager::RegisterScalarFunctions(loader);
   onager::RegisterAllCentralityFunctions(loader);
@@ -27,12 +34,20 @@ void OnagerExtension::Load(ExtensionLoader &loader) {
   onager::RegisterTraversalFunctions(loader);
   onager::RegisterGeneratorFunctions(loader);
   onager::RegisterLinkFunctions(loader);
+  onager::RegisterLinkAnalysisFunctions(loader);
   onager::RegisterMetricFunctions(loader);
   onager::RegisterMstFunctions(loader);
   onager::RegisterApproximationFunctions(loader);
   onager::RegisterPersonalizedFunctions(loader);
   onager::RegisterSubgraphFunctions(loader);
   onager::RegisterParallelFunctions(loader);
+  onager::RegisterWeightFunctions(loader);
+  onager::RegisterPatternFunctions(loader);
+  onager::RegisterFlowFunctions(loader);
+  onager::RegisterMultilayerFunctions(loader);
+  onager::RegisterTemporalFunctions(loader);
+  onager::RegisterMarkovFunctions(loader);
+  onager::RegisterLayoutFunctions(loader);
   onager::RegisterLocalReachingFunction(loader);
   onager::RegisterLaplacianFunction(loader);
 }
@@ -0,0 +1,46 @@
+//! Build script for the onager crate.
+//!
+//! When the `cbindgen` feature is enabled, the C header consumed by the DuckDB
+//! extension (and by any other C or C++ caller) is regenerated from the `ffi`
+//! modules on every build, so the header cannot drift out of sync with the
+//! exported Rust signatures. The output path defaults to
+//! `bindings/include/rust.h` and can be overridden with `ONAGER_HEADER_PATH`.
+
+fn main() {
+    #[cfg(feature = "cbindgen")]
+    generate_header();
+}
+
+#[cfg(feature = "cbindgen")]
+fn generate_header() {
+    use std::env;
+    use std::path::PathBuf;
+
+    let crate_dir = match env::var("CARGO_MANIFEST_DIR") {
+        Ok(dir) => PathBuf::from(dir),
+        Err(e) => panic!("CARGO_MANIFEST_DIR is not set: {e}"),
+    };
+    let output = env::var("ONAGER_HEADER_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| crate_dir.join("bindings").join("include").join("rust.h"));
+
+    println!("cargo:rerun-if-changed=src/ffi");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=ONAGER_HEADER_PATH");
+
+    let config = match cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")) {
+        Ok(config) => config,
+        Err(e) => panic!("Failed to read cbindgen.toml: {e}"),
+    };
+
+    match cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+    {
+        Ok(bindings) => {
+            bindings.write_to_file(&output);
+        }
+        Err(e) => panic!("Failed to generate C bindings: {e}"),
+    }
+}
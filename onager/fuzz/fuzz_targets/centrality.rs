@@ -0,0 +1,460 @@
+//! Fuzzes the centrality, link analysis, personalized, role, typed ranking,
+//! multilayer, and Markov chain FFI functions.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use onager::ffi::*;
+use onager_fuzz::{run, run_legacy, run_scalar, GraphInput, Params};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    selector: u8,
+    graph: GraphInput,
+    params: Params,
+}
+
+fuzz_target!(|input: Input| {
+    let g = input.graph.build();
+    let p = &input.params;
+    let (s, d, n) = (g.src.as_ptr(), g.dst.as_ptr(), g.len());
+    let (w, wn) = (g.weights_ptr(), g.weights.len());
+    let (nodes, nn) = (g.nodes.as_ptr(), g.nodes.len());
+    let types = g.types.as_ptr();
+    // Type multipliers reuse the scores, one per listed node
+    let mult = if g.scores.len() >= nn {
+        g.scores.as_ptr()
+    } else {
+        std::ptr::null()
+    };
+    let text = p.text();
+    let cap = g.capacity;
+
+    match input.selector % 46 {
+        0 => {
+            run_legacy(|o| onager_compute_pagerank(s, d, n, p.x, p.count(), p.flag, o.i(0), o.f(0)))
+        }
+        1 => run(cap, |o| {
+            onager_compute_pagerank_v2(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                p.x,
+                p.count(),
+                p.flag,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        2 => run_legacy(|o| {
+            onager_compute_pagerank_parallel(s, d, n, w, wn, p.x, p.count(), p.flag, o.i(0), o.f(0))
+        }),
+        3 => run(cap, |o| {
+            onager_compute_pagerank_parallel_v2(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                p.x,
+                p.count(),
+                p.flag,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        4 => run_legacy(|o| onager_compute_degree(s, d, n, p.flag, o.i(0), o.f(0), o.f(1))),
+        5 => run(cap, |o| {
+            onager_compute_degree_v2(s, d, n, p.flag, o.i(0), o.f(0), o.f(1), o.cap())
+        }),
+        6 => {
+            run_scalar(|| onager_compute_node_in_degree(s, d, n, g.node(p.pick)));
+            run_scalar(|| onager_compute_node_out_degree(s, d, n, g.node(p.pick)));
+        }
+        7 => run_legacy(|o| onager_compute_betweenness(s, d, n, p.flag, o.i(0), o.f(0))),
+        8 => run(cap, |o| {
+            onager_compute_betweenness_v2(s, d, n, p.flag, o.i(0), o.f(0), o.cap())
+        }),
+        9 => run_legacy(|o| onager_compute_closeness(s, d, n, o.i(0), o.f(0))),
+        10 => run(cap, |o| {
+            onager_compute_closeness_v2(s, d, n, o.i(0), o.f(0), o.cap())
+        }),
+        11 => run_legacy(|o| onager_compute_eigenvector(s, d, n, p.count(), p.x, o.i(0), o.f(0))),
+        12 => {
+            run(cap, |o| {
+                onager_compute_eigenvector_v2(s, d, n, p.count(), p.x, o.i(0), o.f(0), o.cap())
+            });
+            run(cap, |o| {
+                onager_compute_eigenvector_directed(
+                    s,
+                    d,
+                    n,
+                    p.count(),
+                    p.x,
+                    p.flag,
+                    o.i(0),
+                    o.f(0),
+                    o.cap(),
+                )
+            });
+        }
+        13 => run_legacy(|o| onager_compute_katz(s, d, n, p.x, p.count(), p.y, o.i(0), o.f(0))),
+        14 => run(cap, |o| {
+            onager_compute_katz_v2(s, d, n, p.x, p.count(), p.y, o.i(0), o.f(0), o.cap())
+        }),
+        15 => run_legacy(|o| onager_compute_harmonic(s, d, n, o.i(0), o.f(0))),
+        16 => {
+            run(cap, |o| {
+                onager_compute_harmonic_v2(s, d, n, o.i(0), o.f(0), o.cap())
+            });
+        }
+        17 => run(cap, |o| {
+            onager_compute_closeness_general(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                p.flag,
+                nodes,
+                nn,
+                p.x,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        18 => run(cap, |o| {
+            onager_compute_harmonic_general(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                p.flag,
+                nodes,
+                nn,
+                p.x,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        19 => run(cap, |o| {
+            onager_compute_betweenness_cutoff(s, d, n, w, wn, p.flag, p.x, o.i(0), o.f(0), o.cap())
+        }),
+        20 => run(cap, |o| unsafe {
+            onager_compute_betweenness_general(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                p.flag,
+                p.flag2,
+                text.as_ptr(),
+                p.x,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        21 => {
+            let (mut node_count, mut normalizer) = (0i64, 0f64);
+            run(cap, |o| unsafe {
+                onager_compute_betweenness_scaled(
+                    s,
+                    d,
+                    n,
+                    w,
+                    wn,
+                    p.flag,
+                    p.flag2,
+                    text.as_ptr(),
+                    p.x,
+                    o.i(0),
+                    o.f(0),
+                    o.cap(),
+                    &mut node_count,
+                    &mut normalizer,
+                )
+            });
+        }
+        22 => run(cap, |o| {
+            onager_top_k_indices(
+                g.scores.as_ptr(),
+                g.scores.len(),
+                p.count(),
+                o.i(0),
+                o.cap(),
+            )
+        }),
+        23 => run(cap, |o| {
+            onager_min_value_indices(g.scores.as_ptr(), g.scores.len(), p.x, o.i(0), o.cap())
+        }),
+        24 => run_legacy(|o| onager_compute_voterank(s, d, n, p.count(), o.i(0))),
+        25 => run(cap, |o| {
+            onager_compute_voterank_v2(s, d, n, p.count(), o.i(0), o.cap())
+        }),
+        26 => run_legacy(|o| onager_compute_local_reaching(s, d, n, p.count(), o.i(0), o.f(0))),
+        27 => run(cap, |o| {
+            onager_compute_local_reaching_v2(s, d, n, p.count(), o.i(0), o.f(0), o.cap())
+        }),
+        28 => run_legacy(|o| onager_compute_laplacian(s, d, n, o.i(0), o.f(0))),
+        29 => run(cap, |o| {
+            onager_compute_laplacian_v2(s, d, n, o.i(0), o.f(0), o.cap())
+        }),
+        30 => run(cap, |o| {
+            onager_compute_salsa(s, d, n, p.count(), p.x, o.i(0), o.f(0), o.f(1), o.cap())
+        }),
+        31 => run(cap, |o| {
+            onager_compute_cohits(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                p.x,
+                p.y,
+                p.count(),
+                p.z,
+                o.i(0),
+                o.f(0),
+                o.f(1),
+                o.cap(),
+            )
+        }),
+        32 => run_legacy(|o| {
+            onager_compute_personalized_pagerank(
+                s,
+                d,
+                n,
+                nodes,
+                g.scores.as_ptr(),
+                nn.min(g.scores.len()),
+                p.x,
+                p.count(),
+                p.y,
+                o.i(0),
+                o.f(0),
+            )
+        }),
+        33 => run(cap, |o| {
+            onager_compute_personalized_pagerank_v2(
+                s,
+                d,
+                n,
+                nodes,
+                g.scores.as_ptr(),
+                nn.min(g.scores.len()),
+                p.x,
+                p.count(),
+                p.y,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        34 => run(cap, |o| {
+            onager_compute_personalized_pagerank_uniform(
+                s,
+                d,
+                n,
+                nodes,
+                nn,
+                p.x,
+                p.count(),
+                p.y,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        35 => run(cap, |o| {
+            onager_classify_node_roles(
+                s,
+                d,
+                n,
+                p.count(),
+                p.seed,
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.f(0),
+                o.f(1),
+                o.cap(),
+            )
+        }),
+        36 => run(cap, |o| {
+            onager_compute_typed_pagerank(
+                s,
+                d,
+                types,
+                n,
+                nodes,
+                mult,
+                nn,
+                p.x,
+                p.count(),
+                p.flag,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        37 => run(cap, |o| {
+            onager_compute_typed_personalized_pagerank(
+                s,
+                d,
+                types,
+                n,
+                nodes,
+                mult,
+                nn,
+                nodes,
+                g.scores.as_ptr(),
+                nn.min(g.scores.len()),
+                p.x,
+                p.count(),
+                p.y,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        38 => run(cap, |o| {
+            onager_compute_typed_katz(
+                s,
+                d,
+                types,
+                n,
+                nodes,
+                mult,
+                nn,
+                p.x,
+                p.count(),
+                p.y,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        // Edge types double as layers
+        39 => {
+            run(cap, |o| {
+                onager_compute_multiplex_degree(
+                    s,
+                    d,
+                    types,
+                    n,
+                    o.i(0),
+                    o.i(1),
+                    o.i(2),
+                    o.f(0),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_compute_multiplex_layer_degree(
+                    s,
+                    d,
+                    types,
+                    n,
+                    o.i(0),
+                    o.i(1),
+                    o.i(2),
+                    o.cap(),
+                )
+            });
+        }
+        40 => run(cap, |o| {
+            onager_compute_layer_overlap(
+                s,
+                d,
+                types,
+                n,
+                p.flag,
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        41 => {
+            run(cap, |o| {
+                onager_compute_multiplex_pagerank(
+                    s,
+                    d,
+                    types,
+                    n,
+                    p.x,
+                    p.count(),
+                    p.flag,
+                    o.i(0),
+                    o.f(0),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_compute_multiplex_layer_pagerank(
+                    s,
+                    d,
+                    types,
+                    n,
+                    p.x,
+                    p.count(),
+                    p.flag,
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    o.cap(),
+                )
+            });
+        }
+        42 => run(cap, |o| {
+            onager_compute_stationary_distribution(s, d, n, w, wn, o.i(0), o.f(0), o.cap())
+        }),
+        43 => run(cap, |o| {
+            onager_compute_hitting_times(s, d, n, w, wn, g.node(p.pick), o.i(0), o.f(0), o.cap())
+        }),
+        44 => run(cap, |o| {
+            onager_compute_markov_attribution(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                g.node(p.pick),
+                g.node(p.pick2),
+                nodes,
+                nn,
+                o.i(0),
+                o.f(0),
+                o.f(1),
+                o.cap(),
+            )
+        }),
+        45 => run(cap, |o| unsafe {
+            onager_compute_eigenvector_weighted(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                p.count(),
+                p.x,
+                p.flag,
+                text.as_ptr(),
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        _ => unreachable!(),
+    }
+});
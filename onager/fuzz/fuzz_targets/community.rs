@@ -0,0 +1,364 @@
+//! Fuzzes the community detection, clustering, approximation, facility
+//! location, and transaction pattern FFI functions.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use onager::ffi::*;
+use onager_fuzz::{c_string, run, run_legacy, run_scalar, GraphInput, Params};
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    selector: u8,
+    graph: GraphInput,
+    params: Params,
+}
+
+fuzz_target!(|input: Input| {
+    let g = input.graph.build();
+    let p = &input.params;
+    let (s, d, n) = (g.src.as_ptr(), g.dst.as_ptr(), g.len());
+    let (w, wn) = (g.weights_ptr(), g.weights.len());
+    let weights = g.weights.as_ptr();
+    let (nodes, nn) = (g.nodes.as_ptr(), g.nodes.len());
+    let text = p.text();
+    let cap = g.capacity;
+    // Girvan-Newman recomputes betweenness per removed edge, so cap the target
+    // and the number of removed edges
+    let target = i64::from(p.count % 8);
+
+    match input.selector % 43 {
+        0 => run_legacy(|o| onager_compute_louvain(s, d, n, p.seed, o.i(0), o.i(1))),
+        1 => run(cap, |o| {
+            onager_compute_louvain_v2(s, d, n, p.seed, o.i(0), o.i(1), o.cap())
+        }),
+        2 => run_legacy(|o| onager_compute_connected_components(s, d, n, o.i(0), o.i(1))),
+        3 => run(cap, |o| {
+            onager_compute_connected_components_v2(s, d, n, o.i(0), o.i(1), o.cap())
+        }),
+        4 => run(cap, |o| unsafe {
+            onager_compute_components_with_mode(s, d, n, text.as_ptr(), o.i(0), o.i(1), o.cap())
+        }),
+        5 => run_legacy(|o| onager_compute_label_propagation(s, d, n, o.i(0), o.i(1))),
+        6 => run(cap, |o| {
+            onager_compute_label_propagation_v2(s, d, n, o.i(0), o.i(1), o.cap())
+        }),
+        7 => run_legacy(|o| onager_compute_girvan_newman(s, d, n, target, o.i(0), o.i(1))),
+        8 => run(cap, |o| {
+            onager_compute_girvan_newman_v2(s, d, n, target, o.i(0), o.i(1), o.cap())
+        }),
+        9 => run_legacy(|o| {
+            onager_compute_spectral_clustering(s, d, n, p.count(), p.seed, o.i(0), o.i(1))
+        }),
+        10 => run(cap, |o| {
+            onager_compute_spectral_clustering_v2(
+                s,
+                d,
+                n,
+                p.count(),
+                p.seed,
+                o.i(0),
+                o.i(1),
+                o.cap(),
+            )
+        }),
+        11 => run_legacy(|o| onager_compute_infomap(s, d, n, p.count(), p.seed, o.i(0), o.i(1))),
+        12 => run(cap, |o| {
+            onager_compute_infomap_v2(s, d, n, p.count(), p.seed, o.i(0), o.i(1), o.cap())
+        }),
+        13 => run(cap, |o| {
+            onager_compute_similarity_components(
+                s,
+                d,
+                weights,
+                n,
+                p.x,
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.f(0),
+                o.f(1),
+                o.cap(),
+            )
+        }),
+        14 => run(cap, |o| {
+            onager_compute_component_of(s, d, n, g.node(p.pick), o.i(0), o.cap())
+        }),
+        15 => run(cap, |o| {
+            onager_compute_correlation_clustering(s, d, n, weights, p.seed, o.i(0), o.i(1), o.cap())
+        }),
+        16 => run(cap, |o| unsafe {
+            onager_compute_hierarchical_clustering(
+                s,
+                d,
+                n,
+                text.as_ptr(),
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        17 => {
+            // The block matrix has its own capacity, fuzzed separately
+            let mut probabilities = vec![0.0; p.count2()];
+            run(cap, |o| {
+                onager_fit_sbm(
+                    s,
+                    d,
+                    n,
+                    p.count() % 16,
+                    p.seed,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    probabilities.as_mut_ptr(),
+                    probabilities.len(),
+                )
+            });
+        }
+        18 => run_legacy(|o| onager_compute_max_clique(s, d, n, o.i(0))),
+        19 => run(cap, |o| {
+            onager_compute_max_clique_v2(s, d, n, o.i(0), o.cap())
+        }),
+        20 => run_legacy(|o| onager_compute_independent_set(s, d, n, o.i(0))),
+        21 => run(cap, |o| {
+            onager_compute_independent_set_v2(s, d, n, o.i(0), o.cap())
+        }),
+        22 => run_legacy(|o| onager_compute_vertex_cover(s, d, n, o.i(0))),
+        23 => run(cap, |o| {
+            onager_compute_vertex_cover_v2(s, d, n, o.i(0), o.cap())
+        }),
+        24 => {
+            let mut cost = 0.0;
+            run_legacy(|o| onager_compute_tsp(s, d, weights, n, o.i(0), &mut cost));
+        }
+        25 => {
+            let mut cost = 0.0;
+            run(cap, |o| {
+                onager_compute_tsp_v2(s, d, weights, n, o.i(0), &mut cost, o.cap())
+            });
+        }
+        26 => run(cap, |o| {
+            onager_compute_p_median(s, d, n, w, wn, p.count(), o.i(0), o.i(1), o.f(0), o.cap())
+        }),
+        27 => run(cap, |o| {
+            onager_compute_voronoi(s, d, n, w, wn, nodes, nn, o.i(0), o.i(1), o.f(0), o.cap())
+        }),
+        28 => run(cap, |o| {
+            onager_compute_fan_patterns(
+                s,
+                d,
+                g.types.as_ptr(),
+                n,
+                p.count(),
+                p.seed,
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.i(3),
+                o.i(4),
+                o.i(5),
+                o.cap(),
+            )
+        }),
+        29 => run(cap, |o| {
+            onager_find_weighted_cycles(
+                s,
+                d,
+                weights,
+                n,
+                p.count() % 6,
+                p.x,
+                p.count2(),
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        30 => run(cap, |o| unsafe {
+            onager_compute_hierarchical_clustering(
+                s,
+                d,
+                n,
+                std::ptr::null(),
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        31 => {
+            let mut k = 0i64;
+            run(cap, |o| {
+                onager_compute_weighted_spectral_clustering(
+                    s,
+                    d,
+                    n,
+                    w,
+                    wn,
+                    p.count() % 8,
+                    p.count2() % 16,
+                    p.seed,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    &mut k,
+                )
+            });
+        }
+        32 => run(cap, |o| {
+            onager_compute_fluid_communities(s, d, n, p.count(), p.seed, o.i(0), o.i(1), o.cap())
+        }),
+        33 => {
+            let mut q = 0f64;
+            run(cap, |o| {
+                onager_compute_greedy_modularity(s, d, n, o.i(0), o.i(1), o.cap(), &mut q)
+            });
+        }
+        34 => {
+            let mut q = 0f64;
+            run(cap, |o| {
+                onager_compute_girvan_newman_with_limits(
+                    s,
+                    d,
+                    n,
+                    target,
+                    p.count2() % 16 + 1,
+                    u64::from(p.flag),
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    &mut q,
+                )
+            });
+        }
+        35 => {
+            let mut codelength = 0f64;
+            run(cap, |o| {
+                onager_compute_weighted_infomap(
+                    s,
+                    d,
+                    n,
+                    w,
+                    wn,
+                    p.flag,
+                    p.x,
+                    p.count2() % 16,
+                    p.seed,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    &mut codelength,
+                )
+            });
+        }
+        36 => run(cap, |o| {
+            onager_detect_bursts(
+                s,
+                d,
+                g.types.as_ptr(),
+                n,
+                p.seed,
+                p.x,
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        37 | 38 => {
+            // String node IDs, with output pointers stored in the integer
+            // buffers, which have the same width
+            let names: Vec<CString> = g
+                .src
+                .iter()
+                .chain(&g.dst)
+                .map(|v| c_string(v.to_string().as_bytes()))
+                .collect();
+            let ptrs: Vec<*const c_char> = names.iter().map(|c| c.as_ptr()).collect();
+            let (ss, ds) = (ptrs.as_ptr(), ptrs[n..].as_ptr());
+            if input.selector % 39 == 37 {
+                run(cap, |o| {
+                    onager_compute_connected_components_str(
+                        ss,
+                        ds,
+                        n,
+                        o.i(0).cast(),
+                        o.i(1),
+                        o.cap(),
+                    )
+                });
+            } else {
+                run(cap, |o| {
+                    onager_compute_louvain_str(ss, ds, n, p.seed, o.i(0).cast(), o.i(1), o.cap())
+                });
+            }
+        }
+        39 => run(cap, |o| {
+            onager_compute_weakly_connected_components(s, d, n, o.i(0), o.i(1), o.cap())
+        }),
+        40 => {
+            // The node rows have their own capacity, fuzzed separately
+            let communities: Vec<i64> = g.nodes.iter().map(|v| v.rem_euclid(3)).collect();
+            let mut node_ids = vec![0i64; p.count2()];
+            let mut node_communities = vec![0i64; p.count2()];
+            let mut node_paths = vec![0.0; p.count2()];
+            let mut node_count = 0i64;
+            run(cap, |o| {
+                onager_compute_community_bridges(
+                    s,
+                    d,
+                    n,
+                    nodes,
+                    communities.as_ptr(),
+                    nn,
+                    o.i(0),
+                    o.i(1),
+                    o.i(2),
+                    o.i(3),
+                    o.f(0),
+                    o.cap(),
+                    node_ids.as_mut_ptr(),
+                    node_communities.as_mut_ptr(),
+                    node_paths.as_mut_ptr(),
+                    p.count2(),
+                    &mut node_count,
+                )
+            });
+        }
+        41 => {
+            // Partitions that miss nodes are rejected with NaN
+            let communities: Vec<i64> = g.nodes.iter().map(|v| v.rem_euclid(3)).collect();
+            let listed = p.count2().min(nn);
+            run_scalar(|| onager_compute_modularity(s, d, n, nodes, communities.as_ptr(), listed));
+        }
+        42 => {
+            // Two partitions over overlapping prefixes of the node list
+            let a: Vec<i64> = g.nodes.iter().map(|v| v.rem_euclid(3)).collect();
+            let b: Vec<i64> = g.nodes.iter().map(|v| v.rem_euclid(4)).collect();
+            let listed = p.count2().min(nn);
+            let mut similarity = 0.0;
+            run_scalar(|| unsafe {
+                onager_compute_partition_similarity(
+                    nodes,
+                    a.as_ptr(),
+                    nn,
+                    nodes,
+                    b.as_ptr(),
+                    listed,
+                    text.as_ptr(),
+                    &mut similarity,
+                )
+            });
+        }
+        _ => unreachable!(),
+    }
+});
@@ -0,0 +1,434 @@
+//! Fuzzes the traversal, shortest path, parallel, subgraph, coarsening,
+//! spanning tree, cut, and temporal path FFI functions.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use onager::ffi::*;
+use onager_fuzz::{run, run_legacy, run_scalar, GraphInput, Params};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    selector: u8,
+    graph: GraphInput,
+    params: Params,
+}
+
+fuzz_target!(|input: Input| {
+    let g = input.graph.build();
+    let p = &input.params;
+    let (s, d, n) = (g.src.as_ptr(), g.dst.as_ptr(), g.len());
+    let (w, wn) = (g.weights_ptr(), g.weights.len());
+    let weights = g.weights.as_ptr();
+    let (nodes, nn) = (g.nodes.as_ptr(), g.nodes.len());
+    let (source, target) = (g.node(p.pick), g.node(p.pick2));
+    let cap = g.capacity;
+    // Forbidden edges pair up the node picks
+    let half = nn / 2;
+    let (fs, fd) = (g.nodes[..half].as_ptr(), g.nodes[half..].as_ptr());
+
+    match input.selector % 53 {
+        0 => run_legacy(|o| onager_compute_dijkstra(s, d, n, source, o.i(0), o.f(0))),
+        1 => run(cap, |o| {
+            onager_compute_dijkstra_v2(s, d, n, source, o.i(0), o.f(0), o.cap())
+        }),
+        2 => run_legacy(|o| onager_compute_bfs(s, d, n, source, o.i(0))),
+        3 => run(cap, |o| {
+            onager_compute_bfs_v2(s, d, n, source, o.i(0), o.cap())
+        }),
+        4 => run_legacy(|o| onager_compute_dfs(s, d, n, source, o.i(0))),
+        5 => run(cap, |o| {
+            onager_compute_dfs_v2(s, d, n, source, o.i(0), o.cap())
+        }),
+        6 => run_legacy(|o| onager_compute_bellman_ford(s, d, weights, n, source, o.i(0), o.f(0))),
+        7 => run(cap, |o| {
+            onager_compute_bellman_ford_v2(s, d, weights, n, source, o.i(0), o.f(0), o.cap())
+        }),
+        8 => {
+            run_legacy(|o| onager_compute_floyd_warshall(s, d, weights, n, o.i(0), o.i(1), o.f(0)))
+        }
+        9 => run(cap, |o| {
+            onager_compute_floyd_warshall_v2(s, d, weights, n, o.i(0), o.i(1), o.f(0), o.cap())
+        }),
+        10 => {
+            run_scalar(|| onager_compute_shortest_distance(s, d, n, source, target));
+            run_scalar(|| {
+                onager_compute_shortest_distance_weighted(s, d, n, w, wn, source, target)
+            });
+        }
+        11 => run(cap, |o| {
+            onager_compute_shortest_path_tree(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                source,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        12 => run(cap, |o| {
+            onager_compute_widest_path(s, d, n, w, wn, source, target, o.i(0), o.f(0), o.cap())
+        }),
+        13 => run(cap, |o| {
+            onager_compute_widest_paths(s, d, n, w, wn, source, o.i(0), o.f(0), o.cap())
+        }),
+        14 => run(cap, |o| {
+            onager_compute_path_with_allowed_types(
+                s,
+                d,
+                g.types.as_ptr(),
+                n,
+                g.types.as_ptr(),
+                n.min(2),
+                source,
+                target,
+                o.i(0),
+                o.i(1),
+                o.cap(),
+            )
+        }),
+        15 => run(cap, |o| {
+            onager_compute_pairwise_distances(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                nodes,
+                nn,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        16 => run_legacy(|o| onager_compute_bfs_parallel(s, d, n, source, o.i(0))),
+        17 => run(cap, |o| {
+            onager_compute_bfs_parallel_v2(s, d, n, source, o.i(0), o.cap())
+        }),
+        18 => {
+            run_legacy(|o| onager_compute_shortest_paths_parallel(s, d, n, source, o.i(0), o.f(0)))
+        }
+        19 => run(cap, |o| {
+            onager_compute_shortest_paths_parallel_v2(s, d, n, source, o.i(0), o.f(0), o.cap())
+        }),
+        20 => run_legacy(|o| onager_compute_components_parallel(s, d, n, o.i(0), o.i(1))),
+        21 => run(cap, |o| {
+            onager_compute_components_parallel_v2(s, d, n, o.i(0), o.i(1), o.cap())
+        }),
+        22 => run_legacy(|o| onager_compute_clustering_parallel(s, d, n, o.i(0), o.f(0))),
+        23 => run(cap, |o| {
+            onager_compute_clustering_parallel_v2(s, d, n, o.i(0), o.f(0), o.cap())
+        }),
+        24 => run_legacy(|o| onager_compute_triangles_parallel(s, d, n, o.i(0), o.i(1))),
+        25 => run(cap, |o| {
+            onager_compute_triangles_parallel_v2(s, d, n, o.i(0), o.i(1), o.cap())
+        }),
+        26 => run(cap, |o| {
+            onager_compute_boruvka_mst(
+                s,
+                d,
+                weights,
+                n,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.i(2),
+                o.f(1),
+                o.cap(),
+            )
+        }),
+        27 => run_legacy(|o| onager_compute_ego_graph(s, d, n, source, p.count(), o.i(0), o.i(1))),
+        28 => run(cap, |o| {
+            onager_compute_ego_graph_v2(s, d, n, source, p.count(), o.i(0), o.i(1), o.cap())
+        }),
+        29 => run_legacy(|o| onager_compute_k_hop_neighbors(s, d, n, source, p.count(), o.i(0))),
+        30 => run(cap, |o| {
+            onager_compute_k_hop_neighbors_v2(s, d, n, source, p.count(), o.i(0), o.cap())
+        }),
+        31 => run_legacy(|o| onager_compute_induced_subgraph(s, d, n, nodes, nn, o.i(0), o.i(1))),
+        32 => run(cap, |o| {
+            onager_compute_induced_subgraph_v2(s, d, n, nodes, nn, o.i(0), o.i(1), o.cap())
+        }),
+        33 => run(cap, |o| {
+            onager_remove_forbidden(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                nodes,
+                nn,
+                fs,
+                fd,
+                half,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        34 => {
+            let mut total = 0.0;
+            run_legacy(|o| {
+                onager_compute_prim_mst(s, d, weights, n, o.i(0), o.i(1), o.f(0), &mut total)
+            });
+            run(cap, |o| {
+                onager_compute_prim_mst_v2(
+                    s,
+                    d,
+                    weights,
+                    n,
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    &mut total,
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_compute_prim_mst_forest(
+                    s,
+                    d,
+                    weights,
+                    n,
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    o.i(2),
+                    o.f(1),
+                    o.cap(),
+                )
+            });
+        }
+        35 => {
+            let mut total = 0.0;
+            run_legacy(|o| {
+                onager_compute_kruskal_mst(s, d, weights, n, o.i(0), o.i(1), o.f(0), &mut total)
+            });
+            run(cap, |o| {
+                onager_compute_kruskal_mst_v2(
+                    s,
+                    d,
+                    weights,
+                    n,
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    &mut total,
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_compute_kruskal_mst_forest(
+                    s,
+                    d,
+                    weights,
+                    n,
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    o.i(2),
+                    o.f(1),
+                    o.cap(),
+                )
+            });
+        }
+        36 => run(cap, |o| {
+            onager_compute_dcmst(
+                s,
+                d,
+                weights,
+                n,
+                p.count(),
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.i(2),
+                o.f(1),
+                o.cap(),
+            )
+        }),
+        37 => run(cap, |o| {
+            onager_compute_densest_subgraph(s, d, n, p.flag, o.i(0), o.cap(), o.f(0))
+        }),
+        38 => run(cap, |o| {
+            onager_compute_min_edge_cut(s, d, n, source, target, o.i(0), o.i(1), o.cap())
+        }),
+        39 => run(cap, |o| {
+            onager_compute_min_vertex_cut(s, d, n, source, target, o.i(0), o.cap())
+        }),
+        40 => run(cap, |o| {
+            onager_compute_global_min_cut(s, d, n, w, wn, o.i(0), o.i(1), o.cap(), o.f(0))
+        }),
+        41 => {
+            run_scalar(|| onager_compute_edge_connectivity(s, d, n, p.flag, source, target));
+            run_scalar(|| onager_compute_node_connectivity(s, d, n, p.flag, source, target));
+        }
+        42 => run(cap, |o| {
+            onager_compute_temporal_betweenness(
+                s,
+                d,
+                g.types.as_ptr(),
+                n,
+                p.flag,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        43 => {
+            // count2 of 0 gives an inverted window, which must be rejected
+            let t_start = i64::from(p.count % 4);
+            let t_end = t_start + i64::from(p.count2) - 1;
+            run(cap, |o| {
+                onager_compute_temporal_reachability(
+                    s,
+                    d,
+                    g.types.as_ptr(),
+                    n,
+                    source,
+                    t_start,
+                    t_end,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                )
+            })
+        }
+        44 => run(cap, |o| {
+            onager_extract_paths(
+                s,
+                d,
+                g.types.as_ptr(),
+                n,
+                p.seed,
+                o.i(0),
+                o.i(1),
+                o.i(2),
+                o.i(3),
+                o.cap(),
+            )
+        }),
+        45 => run(cap, |o| {
+            onager_compute_nodes_within_cost(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                g.node(p.pick),
+                p.x,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        46 => run(cap, |o| {
+            onager_compute_topological_sort(s, d, n, o.i(0), o.cap())
+        }),
+        47 => {
+            run_scalar(|| onager_is_dag(s, d, n));
+        }
+        48 => {
+            let mut counts = [0i64; 2];
+            let mut metrics = [0.0f64; 5];
+            run_scalar(|| {
+                onager_compute_ego_metrics(
+                    s,
+                    d,
+                    n,
+                    w,
+                    wn,
+                    source,
+                    p.count(),
+                    p.flag,
+                    counts.as_mut_ptr(),
+                    metrics.as_mut_ptr(),
+                )
+            });
+        }
+        49 => {
+            // The node mapping has its own capacity, fuzzed separately
+            let text = p.text();
+            let mut map_levels = vec![0i64; p.count2()];
+            let mut fine = vec![0i64; p.count2()];
+            let mut coarse = vec![0i64; p.count2()];
+            let mut map_count = 0i64;
+            run(cap, |o| unsafe {
+                onager_coarsen_graph(
+                    s,
+                    d,
+                    n,
+                    w,
+                    wn,
+                    p.count() % 8,
+                    text.as_ptr(),
+                    p.seed,
+                    o.i(0),
+                    o.i(1),
+                    o.i(2),
+                    o.f(0),
+                    o.cap(),
+                    map_levels.as_mut_ptr(),
+                    fine.as_mut_ptr(),
+                    coarse.as_mut_ptr(),
+                    p.count2(),
+                    &mut map_count,
+                )
+            });
+        }
+        50 => run(cap, |o| {
+            onager_compute_shortest_path(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                source,
+                target,
+                p.flag,
+                o.i(0),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        51 => run(cap, |o| {
+            onager_compute_max_flow(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                source,
+                target,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.cap(),
+                o.f(1),
+            )
+        }),
+        52 => run(cap, |o| {
+            onager_compute_min_cut(
+                s,
+                d,
+                n,
+                w,
+                wn,
+                source,
+                target,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.cap(),
+                o.f(1),
+            )
+        }),
+        _ => unreachable!(),
+    }
+});
@@ -0,0 +1,182 @@
+//! Fuzzes the graph registry, cached centrality, contraction hierarchy, job,
+//! and utility FFI functions with random sequences of operations.
+#![no_main]
+
+use std::ffi::CString;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use onager::ffi::*;
+use onager_fuzz::{c_string, free_string, run_scalar};
+
+/// Algorithms accepted by `onager_submit_job`.
+const JOB_ALGORITHMS: [&str; 6] = [
+    "pagerank",
+    "betweenness",
+    "closeness",
+    "harmonic",
+    "louvain",
+    "connected_components",
+];
+
+#[derive(Debug, Arbitrary)]
+enum Op {
+    Create {
+        name: u8,
+        directed: bool,
+    },
+    Drop {
+        name: u8,
+    },
+    AddNode {
+        name: u8,
+        node: i64,
+    },
+    AddEdge {
+        name: u8,
+        src: i64,
+        dst: i64,
+        weight: f64,
+    },
+    Query {
+        name: u8,
+        a: i64,
+        b: i64,
+    },
+    BuildIndex {
+        name: u8,
+    },
+    ChDistance {
+        name: u8,
+        source: i64,
+        target: i64,
+    },
+    SubmitJob {
+        name: u8,
+        algorithm: u8,
+    },
+    /// Cached centrality, asked twice so the second call hits the cache
+    Centrality {
+        name: u8,
+        algorithm: u8,
+    },
+    /// Raw job parameters, only run while no graph exists so a job never starts
+    SubmitRawJob {
+        algorithm: Vec<u8>,
+        params: Vec<u8>,
+    },
+    JobLookup {
+        id: i64,
+    },
+    RawName {
+        bytes: Vec<u8>,
+    },
+}
+
+/// Graph names come from a small pool, so operations hit existing graphs.
+fn graph_name(name: u8) -> CString {
+    c_string(format!("fuzz_{}", name % 4).as_bytes())
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let mut created = [false; 4];
+    for op in ops.iter().take(256) {
+        match op {
+            Op::Create { name, directed } => {
+                let c = graph_name(*name);
+                if run_scalar(|| unsafe { onager_create_graph(c.as_ptr(), *directed) }) == 0 {
+                    created[usize::from(name % 4)] = true;
+                }
+            }
+            Op::Drop { name } => {
+                let c = graph_name(*name);
+                run_scalar(|| unsafe { onager_drop_graph(c.as_ptr()) });
+                created[usize::from(name % 4)] = false;
+            }
+            Op::AddNode { name, node } => {
+                let c = graph_name(*name);
+                run_scalar(|| unsafe { onager_add_node(c.as_ptr(), *node) });
+            }
+            Op::AddEdge {
+                name,
+                src,
+                dst,
+                weight,
+            } => {
+                let c = graph_name(*name);
+                run_scalar(|| unsafe { onager_add_edge(c.as_ptr(), *src, *dst, *weight) });
+            }
+            Op::Query { name, a, b } => {
+                let c = graph_name(*name);
+                run_scalar(|| unsafe { onager_node_count(c.as_ptr()) });
+                run_scalar(|| unsafe { onager_edge_count(c.as_ptr()) });
+                run_scalar(|| unsafe { onager_graph_node_in_degree(c.as_ptr(), *a) });
+                run_scalar(|| unsafe { onager_graph_node_out_degree(c.as_ptr(), *b) });
+                run_scalar(|| unsafe { onager_same_component(c.as_ptr(), *a, *b) });
+                run_scalar(|| unsafe { onager_component_count(c.as_ptr()) });
+                run_scalar(|| unsafe { onager_graph_version(c.as_ptr()) });
+                free_string(run_scalar(|| unsafe { onager_graph_info(c.as_ptr()) }));
+                free_string(run_scalar(|| onager_list_graphs()));
+            }
+            Op::BuildIndex { name } => {
+                let c = graph_name(*name);
+                run_scalar(|| unsafe { onager_build_ch_index(c.as_ptr()) });
+            }
+            Op::ChDistance {
+                name,
+                source,
+                target,
+            } => {
+                let c = graph_name(*name);
+                run_scalar(|| unsafe { onager_ch_distance(c.as_ptr(), *source, *target) });
+            }
+            Op::SubmitJob { name, algorithm } => {
+                let algorithm = c_string(JOB_ALGORITHMS[usize::from(algorithm % 6)].as_bytes());
+                let params = c_string(format!("{{\"graph\": \"fuzz_{}\"}}", name % 4).as_bytes());
+                let id = run_scalar(|| unsafe {
+                    onager_submit_job(algorithm.as_ptr(), params.as_ptr())
+                });
+                free_string(run_scalar(|| onager_job_status(id)));
+                run_scalar(|| onager_drop_job(id));
+            }
+            Op::Centrality { name, algorithm } => {
+                let c = graph_name(*name);
+                let algorithm = c_string(JOB_ALGORITHMS[usize::from(algorithm % 6)].as_bytes());
+                let params = c_string(b"{}");
+                for _ in 0..2 {
+                    free_string(run_scalar(|| unsafe {
+                        onager_graph_centrality(c.as_ptr(), algorithm.as_ptr(), params.as_ptr())
+                    }));
+                }
+            }
+            Op::SubmitRawJob { algorithm, params } => {
+                if created.iter().any(|&c| c) {
+                    continue;
+                }
+                let (algorithm, params) = (c_string(algorithm), c_string(params));
+                let id = run_scalar(|| unsafe {
+                    onager_submit_job(algorithm.as_ptr(), params.as_ptr())
+                });
+                run_scalar(|| onager_drop_job(id));
+            }
+            Op::JobLookup { id } => {
+                free_string(run_scalar(|| onager_job_status(*id)));
+                free_string(run_scalar(|| onager_job_result(*id)));
+            }
+            Op::RawName { bytes } => {
+                let c = c_string(bytes);
+                run_scalar(|| unsafe { onager_node_count(c.as_ptr()) });
+                run_scalar(|| unsafe { onager_drop_graph(c.as_ptr()) });
+                run_scalar(|| unsafe { onager_create_graph(std::ptr::null(), false) });
+            }
+        }
+    }
+
+    // Leave the registry empty for the next input
+    for (i, _) in created.iter().enumerate().filter(|(_, &c)| c) {
+        let c = graph_name(i as u8);
+        run_scalar(|| unsafe { onager_drop_graph(c.as_ptr()) });
+    }
+    run_scalar(|| onager_abi_version());
+    free_string(run_scalar(|| onager_get_version()));
+});
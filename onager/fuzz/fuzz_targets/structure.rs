@@ -0,0 +1,546 @@
+//! Fuzzes the link prediction, metric, mixing matrix, triangle census,
+//! eccentricity, estimate, drift, ranking comparison, generator, edge
+//! simplification, KNN and similarity graph, weight transform, edge
+//! aggregation, spill, layout, bipartite, negative sampling, and graph kernel
+//! FFI functions.
+#![no_main]
+
+use std::ffi::c_void;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use onager::ffi::*;
+use onager_fuzz::{run, run_legacy, run_scalar, GraphInput, Params};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    selector: u8,
+    graph: GraphInput,
+    params: Params,
+}
+
+/// Accepts and drops spilled output.
+unsafe extern "C" fn discard_spill(_ctx: *mut c_void, _data: *const u8, _len: usize) -> i32 {
+    0
+}
+
+fuzz_target!(|input: Input| {
+    let g = input.graph.build();
+    let p = &input.params;
+    let (s, d, n) = (g.src.as_ptr(), g.dst.as_ptr(), g.len());
+    let (w, wn) = (g.weights_ptr(), g.weights.len());
+    let text = p.text();
+    let cap = g.capacity;
+
+    match input.selector % 43 {
+        0 => run_legacy(|o| onager_compute_jaccard(s, d, n, o.i(0), o.i(1), o.f(0))),
+        1 => run(cap, |o| {
+            onager_compute_jaccard_v2(s, d, n, o.i(0), o.i(1), o.f(0), o.cap())
+        }),
+        2 => run_legacy(|o| onager_compute_adamic_adar(s, d, n, o.i(0), o.i(1), o.f(0))),
+        3 => run(cap, |o| {
+            onager_compute_adamic_adar_v2(s, d, n, o.i(0), o.i(1), o.f(0), o.cap())
+        }),
+        4 => {
+            run_legacy(|o| onager_compute_preferential_attachment(s, d, n, o.i(0), o.i(1), o.f(0)))
+        }
+        5 => run(cap, |o| {
+            onager_compute_preferential_attachment_v2(s, d, n, o.i(0), o.i(1), o.f(0), o.cap())
+        }),
+        6 => run_legacy(|o| onager_compute_resource_allocation(s, d, n, o.i(0), o.i(1), o.f(0))),
+        7 => run(cap, |o| {
+            onager_compute_resource_allocation_v2(s, d, n, o.i(0), o.i(1), o.f(0), o.cap())
+        }),
+        8 => run_legacy(|o| onager_compute_common_neighbors(s, d, n, o.i(0), o.i(1), o.i(2))),
+        9 => run(cap, |o| {
+            onager_compute_common_neighbors_v2(s, d, n, o.i(0), o.i(1), o.i(2), o.cap())
+        }),
+        10 => {
+            run_scalar(|| onager_compute_diameter(s, d, n));
+            run_scalar(|| onager_compute_radius(s, d, n));
+            run_scalar(|| onager_compute_avg_clustering(s, d, n));
+            run_scalar(|| onager_compute_avg_path_length(s, d, n));
+        }
+        11 => {
+            run_scalar(|| onager_compute_transitivity(s, d, n));
+            run_scalar(|| onager_compute_assortativity(s, d, n));
+            run_scalar(|| onager_compute_graph_density(s, d, n, p.flag));
+        }
+        12 => run_legacy(|o| onager_compute_triangle_count(s, d, n, o.i(0), o.i(1))),
+        13 => run(cap, |o| {
+            onager_compute_triangle_count_v2(s, d, n, o.i(0), o.i(1), o.cap())
+        }),
+        14 => {
+            run_scalar(|| onager_estimate_output_size_nodes(s, d, n));
+            run_scalar(|| onager_estimate_output_size_node_pairs(s, d, n));
+            run_scalar(|| onager_estimate_output_size_edges(s, d, n));
+            run_scalar(|| onager_estimate_output_size_path(s, d, n));
+            run_scalar(|| onager_estimate_output_size_generated_edges(p.count()));
+        }
+        15 => {
+            // Compare the graph against its reversed first half
+            let (mut node_counts, mut edge_counts) = ([0i64; 2], [0i64; 2]);
+            let (mut densities, mut clustering, mut ks) = ([0f64; 2], [0f64; 2], 0f64);
+            run(cap, |o| {
+                onager_compare_graph_stats(
+                    s,
+                    d,
+                    n,
+                    d,
+                    s,
+                    n / 2,
+                    p.count(),
+                    node_counts.as_mut_ptr(),
+                    edge_counts.as_mut_ptr(),
+                    densities.as_mut_ptr(),
+                    clustering.as_mut_ptr(),
+                    &mut ks,
+                    o.i(0),
+                    o.i(1),
+                    o.i(2),
+                    o.i(3),
+                    o.cap(),
+                )
+            });
+        }
+        16 => run_legacy(|o| {
+            onager_generate_erdos_renyi(p.count(), p.x, p.seed as u64, o.i(0), o.i(1))
+        }),
+        17 => run(cap, |o| {
+            onager_generate_erdos_renyi_v2(p.count(), p.x, p.seed as u64, o.i(0), o.i(1), o.cap())
+        }),
+        18 => run_legacy(|o| {
+            onager_generate_barabasi_albert(p.count(), p.count2(), p.seed as u64, o.i(0), o.i(1))
+        }),
+        19 => run(cap, |o| {
+            onager_generate_barabasi_albert_v2(
+                p.count(),
+                p.count2(),
+                p.seed as u64,
+                o.i(0),
+                o.i(1),
+                o.cap(),
+            )
+        }),
+        20 => run_legacy(|o| {
+            onager_generate_watts_strogatz(
+                p.count(),
+                p.count2(),
+                p.x,
+                p.seed as u64,
+                o.i(0),
+                o.i(1),
+            )
+        }),
+        21 => run(cap, |o| {
+            onager_generate_watts_strogatz_v2(
+                p.count(),
+                p.count2(),
+                p.x,
+                p.seed as u64,
+                o.i(0),
+                o.i(1),
+                o.cap(),
+            )
+        }),
+        22 => run(cap, |o| unsafe {
+            onager_transform_weights(
+                g.scores.as_ptr(),
+                g.scores.len(),
+                text.as_ptr(),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        23 => run(cap, |o| unsafe {
+            onager_weights_to_distances(
+                g.scores.as_ptr(),
+                g.scores.len(),
+                text.as_ptr(),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        24 => {
+            run_scalar(|| unsafe {
+                onager_spill_all_pairs_distances(
+                    s,
+                    d,
+                    n,
+                    w,
+                    wn,
+                    Some(discard_spill),
+                    std::ptr::null_mut(),
+                )
+            });
+        }
+        25 => {
+            run_scalar(|| unsafe {
+                onager_spill_link_scores(
+                    s,
+                    d,
+                    n,
+                    text.as_ptr(),
+                    Some(discard_spill),
+                    std::ptr::null_mut(),
+                )
+            });
+        }
+        26 => {
+            // Null score names and callbacks must be rejected rather than called
+            run_scalar(|| unsafe {
+                onager_spill_link_scores(
+                    s,
+                    d,
+                    n,
+                    std::ptr::null(),
+                    Some(discard_spill),
+                    std::ptr::null_mut(),
+                )
+            });
+            run_scalar(|| unsafe {
+                onager_spill_link_scores(s, d, n, text.as_ptr(), None, std::ptr::null_mut())
+            });
+        }
+        27 => run(cap, |o| {
+            onager_generate_lfr(
+                p.count(),
+                p.x,
+                p.y,
+                p.seed as u64,
+                o.i(0),
+                o.i(1),
+                o.cap(),
+                o.i(2),
+                o.i(3),
+                o.cap(),
+            )
+        }),
+        28 => {
+            let categories: Vec<i64> = g.nodes.iter().map(|v| v.rem_euclid(3)).collect();
+            let mut r = 0.0;
+            run(cap, |o| {
+                onager_compute_mixing_matrix(
+                    s,
+                    d,
+                    n,
+                    g.nodes.as_ptr(),
+                    categories.as_ptr(),
+                    g.nodes.len(),
+                    p.flag,
+                    o.i(0),
+                    o.i(1),
+                    o.i(2),
+                    o.cap(),
+                    &mut r,
+                )
+            });
+        }
+        29 => run(cap, |o| {
+            onager_compute_triangle_census(s, d, n, o.i(0), o.cap())
+        }),
+        30 => run(cap, |o| {
+            onager_compute_weighted_triangles(
+                s,
+                d,
+                g.weights.as_ptr(),
+                n,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        31 => {
+            let mut ecc = 0;
+            run(cap, |o| {
+                onager_compute_center_nodes(s, d, n, o.i(0), o.cap(), &mut ecc)
+            });
+            run(cap, |o| {
+                onager_compute_periphery_nodes(s, d, n, o.i(0), o.cap(), &mut ecc)
+            });
+        }
+        32 => {
+            let (k, start) = (p.count(), p.seed);
+            run(cap, |o| {
+                onager_generate_erdos_renyi_with_ids(
+                    k,
+                    p.x,
+                    0,
+                    start,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    o.i(2),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_generate_barabasi_albert_with_ids(
+                    k,
+                    p.count2(),
+                    0,
+                    start,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    o.i(2),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_generate_watts_strogatz_with_ids(
+                    k,
+                    p.count2(),
+                    p.x,
+                    0,
+                    start,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    o.i(2),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_generate_lfr_with_ids(
+                    k,
+                    p.x,
+                    p.y,
+                    0,
+                    start,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    o.i(2),
+                    o.i(3),
+                    o.cap(),
+                )
+            });
+        }
+        33 => {
+            let (k, flag, flag2) = (p.count(), p.flag, p.flag2);
+            run(cap, |o| {
+                onager_generate_gnp(
+                    k,
+                    p.x,
+                    flag,
+                    flag2,
+                    p.seed as u64,
+                    0,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    o.i(2),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_generate_gnm(
+                    k,
+                    p.count2(),
+                    flag,
+                    flag2,
+                    p.flag ^ p.flag2,
+                    p.seed as u64,
+                    0,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    o.i(2),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_simplify_edges(s, d, n, flag, flag2, false, o.i(0), o.i(1), o.cap())
+            });
+        }
+        34 => {
+            run(cap, |o| {
+                onager_generate_random_geometric(
+                    p.count(),
+                    p.x,
+                    p.seed as u64,
+                    0,
+                    o.i(0),
+                    o.i(1),
+                    o.cap(),
+                    o.i(2),
+                    o.f(0),
+                    o.f(1),
+                    o.cap(),
+                )
+            });
+            // Points are the edge sources placed at (weight, weight)
+            let (xy, m) = (g.weights.as_ptr(), n.min(wn));
+            run(cap, |o| {
+                onager_build_knn_graph(s, xy, xy, m, p.count2(), o.i(0), o.i(1), o.f(0), o.cap())
+            });
+        }
+        35 => {
+            // A square matrix over the first nodes, or a mismatched one
+            let k = (wn as f64).sqrt() as usize + usize::from(p.flag);
+            run(cap, |o| {
+                onager_build_similarity_graph(
+                    s,
+                    k.min(n),
+                    g.weights.as_ptr(),
+                    wn,
+                    p.x,
+                    p.count(),
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_build_similarity_graph_pairs(
+                    s,
+                    d,
+                    g.weights.as_ptr(),
+                    n.min(wn),
+                    p.x,
+                    p.count(),
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    o.cap(),
+                )
+            });
+        }
+        36 => {
+            for method in [text.as_ptr(), c"mean".as_ptr()] {
+                run(cap, |o| unsafe {
+                    onager_aggregate_parallel_edges(
+                        s,
+                        d,
+                        n,
+                        w,
+                        wn,
+                        method,
+                        o.i(0),
+                        o.i(1),
+                        o.f(0),
+                        o.cap(),
+                    )
+                });
+            }
+        }
+        37 => {
+            // Score the sources and the destinations with the same weights
+            let count = wn.min(n);
+            let (mut correlation, mut overlap) = (0f64, 0f64);
+            for method in [text.as_ptr(), c"kendall".as_ptr()] {
+                run_scalar(|| unsafe {
+                    onager_compare_rankings(
+                        s,
+                        w,
+                        count,
+                        d,
+                        w,
+                        count,
+                        method,
+                        p.count(),
+                        &mut correlation,
+                        &mut overlap,
+                    )
+                });
+            }
+        }
+        38 => {
+            for method in [text.as_ptr(), c"force".as_ptr()] {
+                run(cap, |o| unsafe {
+                    onager_compute_layout(
+                        s,
+                        d,
+                        n,
+                        w,
+                        wn,
+                        method,
+                        p.count() % 8,
+                        p.seed,
+                        o.i(0),
+                        o.f(0),
+                        o.f(1),
+                        o.cap(),
+                    )
+                });
+            }
+            run(cap, |o| {
+                onager_compute_community_layout(
+                    s,
+                    d,
+                    n,
+                    w,
+                    wn,
+                    p.count() % 8,
+                    p.seed,
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    o.f(1),
+                    o.cap(),
+                    o.i(2),
+                    o.i(3),
+                    o.f(2),
+                    o.f(3),
+                    o.cap(),
+                    std::ptr::null_mut(),
+                )
+            });
+        }
+        39 => {
+            run_scalar(|| onager_compute_is_bipartite(s, d, n));
+            run(cap, |o| {
+                onager_compute_bipartite_projection(
+                    s,
+                    d,
+                    n,
+                    g.nodes.as_ptr(),
+                    g.nodes.len(),
+                    o.i(0),
+                    o.i(1),
+                    o.f(0),
+                    o.cap(),
+                )
+            });
+            run(cap, |o| {
+                onager_compute_bipartite_matching(s, d, n, o.i(0), o.i(1), o.cap())
+            });
+        }
+        40 => run(cap, |o| unsafe {
+            onager_sample_negative_edges(
+                s,
+                d,
+                n,
+                p.count(),
+                text.as_ptr(),
+                p.seed,
+                o.i(0),
+                o.i(1),
+                o.cap(),
+            )
+        }),
+        41 => run(cap, |o| {
+            onager_compute_wl_labels(s, d, n, p.count(), o.i(0), o.i(1), o.i(2), o.cap())
+        }),
+        // Edge types double as graph IDs
+        42 => run(cap, |o| unsafe {
+            onager_compute_graph_kernel(
+                s,
+                d,
+                g.types.as_ptr(),
+                n,
+                text.as_ptr(),
+                p.count(),
+                p.flag,
+                o.i(0),
+                o.i(1),
+                o.f(0),
+                o.cap(),
+            )
+        }),
+        _ => unreachable!(),
+    }
+});
@@ -0,0 +1,269 @@
+//! Shared helpers for the Onager FFI fuzz targets.
+//!
+//! Every target builds a small graph from fuzzer input, calls FFI functions
+//! the way the DuckDB bindings do (a size query with null outputs, then a
+//! call with real buffers), and checks that:
+//!
+//! - no call panicked, since `ffi_call!` turns panics into error returns,
+//! - nothing is written past the capacity passed in `out_capacity`, and
+//! - nothing is written at all when the result does not fit.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+use arbitrary::Arbitrary;
+use onager::ffi::onager_last_error;
+
+/// Node IDs are drawn from `0..NODE_RANGE` before the offset is applied,
+/// which keeps graphs dense enough to have paths and cycles.
+const NODE_RANGE: u8 = 32;
+
+/// Upper bound on edges, which keeps the cubic algorithms fast.
+const MAX_EDGES: usize = 64;
+
+/// Number of sentinel slots after each output buffer.
+const GUARD: usize = 4;
+
+/// Number of integer and float output buffers, enough for the widest function.
+const INT_BUFFERS: usize = 10;
+const FLOAT_BUFFERS: usize = 6;
+
+const INT_SENTINEL: i64 = 0x5a5a_5a5a_5a5a_5a5a;
+const FLOAT_SENTINEL: u64 = 0x7ff8_dead_beef_0001;
+
+/// Graph and arrays decoded from fuzzer input.
+#[derive(Debug, Arbitrary)]
+pub struct GraphInput {
+    edges: Vec<(u8, u8, f64, u8)>,
+    /// Added to every node ID so extreme and negative IDs are exercised too.
+    id_offset: i64,
+    /// Node picks for node-list parameters such as seeds and targets.
+    nodes: Vec<u8>,
+    /// Scores for the functions that take a plain `f64` array.
+    scores: Vec<f64>,
+    /// Capacity passed on the call with real buffers.
+    capacity: u8,
+}
+
+/// Edge arrays ready to pass across the FFI.
+#[derive(Debug)]
+pub struct Graph {
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    pub weights: Vec<f64>,
+    /// Small edge type codes, also usable as timestamps.
+    pub types: Vec<i64>,
+    pub nodes: Vec<i64>,
+    pub scores: Vec<f64>,
+    pub capacity: usize,
+    id_offset: i64,
+}
+
+impl GraphInput {
+    pub fn build(&self) -> Graph {
+        let edges = &self.edges[..self.edges.len().min(MAX_EDGES)];
+        let id = |raw: u8| i64::from(raw % NODE_RANGE).wrapping_add(self.id_offset);
+        Graph {
+            src: edges.iter().map(|e| id(e.0)).collect(),
+            dst: edges.iter().map(|e| id(e.1)).collect(),
+            weights: edges.iter().map(|e| e.2).collect(),
+            types: edges.iter().map(|e| i64::from(e.3 % 4)).collect(),
+            nodes: self.nodes.iter().take(MAX_EDGES).map(|&n| id(n)).collect(),
+            scores: self.scores.iter().take(MAX_EDGES * 4).copied().collect(),
+            capacity: usize::from(self.capacity),
+            id_offset: self.id_offset,
+        }
+    }
+}
+
+impl Graph {
+    /// Number of edges.
+    pub fn len(&self) -> usize {
+        self.src.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.src.is_empty()
+    }
+
+    /// Maps a raw pick to a node ID in the same range as the edges.
+    pub fn node(&self, pick: u8) -> i64 {
+        i64::from(pick % NODE_RANGE).wrapping_add(self.id_offset)
+    }
+
+    /// Pointer to the weights, or null when there are none.
+    pub fn weights_ptr(&self) -> *const f64 {
+        if self.weights.is_empty() {
+            std::ptr::null()
+        } else {
+            self.weights.as_ptr()
+        }
+    }
+}
+
+/// Output buffers for one FFI call.
+///
+/// A null set hands out null pointers and zero capacity, which is how the
+/// bindings query the result size.
+pub struct Outputs {
+    ints: Vec<Vec<i64>>,
+    floats: Vec<Vec<f64>>,
+    capacity: usize,
+    null: bool,
+}
+
+impl Outputs {
+    fn new(capacity: usize, null: bool) -> Self {
+        let len = if null { 0 } else { capacity + GUARD };
+        Outputs {
+            ints: (0..INT_BUFFERS).map(|_| vec![INT_SENTINEL; len]).collect(),
+            floats: (0..FLOAT_BUFFERS)
+                .map(|_| vec![f64::from_bits(FLOAT_SENTINEL); len])
+                .collect(),
+            capacity: if null { 0 } else { capacity },
+            null,
+        }
+    }
+
+    /// Capacity to pass as `out_capacity`.
+    pub fn cap(&self) -> usize {
+        self.capacity
+    }
+
+    /// Integer output buffer `k`.
+    pub fn i(&mut self, k: usize) -> *mut i64 {
+        if self.null {
+            std::ptr::null_mut()
+        } else {
+            self.ints[k].as_mut_ptr()
+        }
+    }
+
+    /// Float output buffer `k`.
+    pub fn f(&mut self, k: usize) -> *mut f64 {
+        if self.null {
+            std::ptr::null_mut()
+        } else {
+            self.floats[k].as_mut_ptr()
+        }
+    }
+
+    /// Panics if a guard slot was written, or if anything was written for a
+    /// result larger than the capacity.
+    fn check(&self, returned: i64) {
+        if self.null {
+            return;
+        }
+        let untouched_from = if returned < 0 || returned as usize > self.capacity {
+            0
+        } else {
+            self.capacity
+        };
+        for (k, buf) in self.ints.iter().enumerate() {
+            if buf[untouched_from..].iter().any(|&v| v != INT_SENTINEL) {
+                panic!("integer output {k} written beyond its allowed range (returned {returned}, capacity {})", self.capacity);
+            }
+        }
+        for (k, buf) in self.floats.iter().enumerate() {
+            if buf[untouched_from..]
+                .iter()
+                .any(|v| v.to_bits() != FLOAT_SENTINEL)
+            {
+                panic!("float output {k} written beyond its allowed range (returned {returned}, capacity {})", self.capacity);
+            }
+        }
+    }
+}
+
+/// Panics if the last FFI call caught a panic.
+pub fn check_no_panic() {
+    let ptr = onager_last_error();
+    if ptr.is_null() {
+        return;
+    }
+    let msg = unsafe { CStr::from_ptr(ptr) }.to_string_lossy();
+    if msg.starts_with("Internal panic") {
+        panic!("FFI call panicked: {msg}");
+    }
+}
+
+/// Runs a capacity-checked FFI call as a size query and then with buffers of
+/// the fuzzed capacity.
+pub fn run(capacity: usize, mut call: impl FnMut(&mut Outputs) -> i64) {
+    let mut query = Outputs::new(0, true);
+    call(&mut query);
+    check_no_panic();
+
+    let mut outputs = Outputs::new(capacity, false);
+    let returned = call(&mut outputs);
+    check_no_panic();
+    outputs.check(returned);
+}
+
+/// Runs a legacy FFI call without `out_capacity`, which trusts the caller to
+/// size the buffers from a size query.
+pub fn run_legacy(mut call: impl FnMut(&mut Outputs) -> i64) {
+    let mut query = Outputs::new(0, true);
+    let needed = call(&mut query);
+    check_no_panic();
+    if needed < 0 || needed as usize > MAX_EDGES * MAX_EDGES {
+        return;
+    }
+
+    let mut outputs = Outputs::new(needed as usize, false);
+    let returned = call(&mut outputs);
+    check_no_panic();
+    outputs.check(returned);
+}
+
+/// Runs an FFI call that returns a scalar.
+pub fn run_scalar<T>(call: impl FnOnce() -> T) -> T {
+    let value = call();
+    check_no_panic();
+    value
+}
+
+/// Converts fuzzer bytes to a C string, dropping interior NUL bytes.
+pub fn c_string(bytes: &[u8]) -> CString {
+    let clean: Vec<u8> = bytes.iter().copied().filter(|&b| b != 0).collect();
+    CString::new(clean).unwrap_or_default()
+}
+
+/// Frees a string returned by Onager. Null pointers are ignored.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+pub fn free_string(ptr: *mut c_char) {
+    unsafe { onager::ffi::onager_free(ptr) };
+}
+
+/// Scalar parameters decoded from fuzzer input.
+///
+/// Iteration counts and sizes are bytes, so no call runs for long.
+#[derive(Debug, Arbitrary)]
+pub struct Params {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub count: u8,
+    pub count2: u8,
+    pub flag: bool,
+    pub flag2: bool,
+    pub seed: i64,
+    pub pick: u8,
+    pub pick2: u8,
+    pub text: Vec<u8>,
+}
+
+impl Params {
+    pub fn count(&self) -> usize {
+        usize::from(self.count)
+    }
+
+    pub fn count2(&self) -> usize {
+        usize::from(self.count2)
+    }
+
+    /// Fuzzed string parameter, such as a mode or method name.
+    pub fn text(&self) -> CString {
+        c_string(&self.text)
+    }
+}
@@ -6,12 +6,14 @@ use graphina::approximation::clique::max_clique;
 use graphina::approximation::independent_set::maximum_independent_set;
 use graphina::approximation::tsp::traveling_salesman_problem;
 use graphina::approximation::vertex_cover::min_weighted_vertex_cover;
-use graphina::core::types::{Graph, NodeId};
+use graphina::core::types::Graph;
+use serde::{Deserialize, Serialize};
 
+use crate::builder::GraphBuilder;
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
 
 /// Result of maximum clique computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CliqueResult {
     pub node_ids: Vec<i64>,
 }
@@ -30,28 +32,11 @@ pub fn compute_max_clique(src: &[i64], dst: &[i64]) -> Result<CliqueResult> {
         });
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
     // Graphina's max_clique returns HashSet<NodeId>
-    let clique_nodes = max_clique(&graph);
+    let clique_nodes = max_clique(&built.graph);
 
     let mut result_nodes = Vec::with_capacity(clique_nodes.len());
     for node_id in clique_nodes {
@@ -69,6 +54,7 @@ pub fn compute_max_clique(src: &[i64], dst: &[i64]) -> Result<CliqueResult> {
 }
 
 /// Result of independent set computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndependentSetResult {
     pub node_ids: Vec<i64>,
 }
@@ -86,27 +72,10 @@ pub fn compute_independent_set(src: &[i64], dst: &[i64]) -> Result<IndependentSe
         });
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let indep_set = maximum_independent_set(&graph);
+    let indep_set = maximum_independent_set(&built.graph);
 
     let mut result_nodes = Vec::with_capacity(indep_set.len());
     for node_id in indep_set {
@@ -122,6 +91,7 @@ pub fn compute_independent_set(src: &[i64], dst: &[i64]) -> Result<IndependentSe
 }
 
 /// Result of vertex cover computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VertexCoverResult {
     pub node_ids: Vec<i64>,
 }
@@ -139,27 +109,10 @@ pub fn compute_vertex_cover(src: &[i64], dst: &[i64]) -> Result<VertexCoverResul
         });
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let cover = min_weighted_vertex_cover(&graph, None);
+    let cover = min_weighted_vertex_cover(&built.graph, None);
 
     let mut result_nodes = Vec::with_capacity(cover.len());
     for node_id in cover {
@@ -175,6 +128,7 @@ pub fn compute_vertex_cover(src: &[i64], dst: &[i64]) -> Result<VertexCoverResul
 }
 
 /// Result of TSP computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TspResult {
     pub tour: Vec<i64>,
     pub cost: f64,
@@ -193,28 +147,11 @@ pub fn compute_tsp(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<TspResul
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, weights[i]);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|i| weights[i]);
+    let reverse_map = built.reverse_map();
 
-    let (tour_internal, cost) =
-        traveling_salesman_problem(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let (tour_internal, cost) = traveling_salesman_problem(&built.graph)
+        .map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
     let mut tour = Vec::with_capacity(tour_internal.len());
     for node_id in tour_internal {
@@ -0,0 +1,340 @@
+//! Bipartite graph module.
+//!
+//! Bipartiteness checks, one-mode projections of two-mode graphs such as
+//! user-item tables, and maximum matchings for assignment problems.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{OnagerError, Result};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Weighted one-mode projection of a bipartite graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BipartiteProjectionResult {
+    /// Projected edges with `src < dst`, in ascending order.
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    /// Number of neighbors the two ends share.
+    pub weights: Vec<f64>,
+}
+
+/// Pairs of a maximum bipartite matching.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BipartiteMatchingResult {
+    /// Matched edges in input order, oriented as in the input.
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+}
+
+fn check_edges(src: &[i64], dst: &[i64]) -> Result<()> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Two-colors the undirected graph breadth-first, or returns `None` when an
+/// edge joins two nodes of the same color. The first node of each component
+/// in `src` gets `false`.
+fn two_color(src: &[i64], dst: &[i64]) -> Option<HashMap<i64, bool>> {
+    let mut neighbors: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (&u, &v) in src.iter().zip(dst) {
+        if u == v {
+            return None;
+        }
+        neighbors.entry(u).or_default().push(v);
+        neighbors.entry(v).or_default().push(u);
+    }
+
+    let mut side: HashMap<i64, bool> = HashMap::new();
+    for &start in src {
+        if side.contains_key(&start) {
+            continue;
+        }
+        side.insert(start, false);
+        let mut queue = VecDeque::from([start]);
+        while let Some(u) = queue.pop_front() {
+            let color = side[&u];
+            for &v in &neighbors[&u] {
+                match side.get(&v) {
+                    Some(&c) if c == color => return None,
+                    Some(_) => {}
+                    None => {
+                        side.insert(v, !color);
+                        queue.push_back(v);
+                    }
+                }
+            }
+        }
+    }
+    Some(side)
+}
+
+/// Check whether the nodes can be split into two sides with every edge
+/// between them.
+///
+/// Edges are undirected. A self-loop makes a graph non-bipartite, and a
+/// graph is bipartite exactly when it has no odd cycle.
+pub fn compute_is_bipartite(src: &[i64], dst: &[i64]) -> Result<bool> {
+    check_edges(src, dst)?;
+    Ok(two_color(src, dst).is_some())
+}
+
+/// Project a bipartite graph onto the nodes in `project_onto`.
+///
+/// Two projected nodes are joined when they share at least one neighbor, and
+/// the weight of the projected edge is the number of neighbors they share,
+/// such as the number of items two users both bought. Edges are undirected
+/// and duplicate edges count once. Every edge must join a node of
+/// `project_onto` with a node outside it. Projected nodes without a shared
+/// neighbor do not appear in the result.
+pub fn compute_bipartite_projection(
+    src: &[i64],
+    dst: &[i64],
+    project_onto: &[i64],
+) -> Result<BipartiteProjectionResult> {
+    check_edges(src, dst)?;
+    if project_onto.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "project_onto must not be empty".to_string(),
+        ));
+    }
+    let projected: HashSet<i64> = project_onto.iter().copied().collect();
+    let present: HashSet<i64> = src.iter().chain(dst).copied().collect();
+    if let Some(&missing) = project_onto.iter().find(|v| !present.contains(v)) {
+        return Err(OnagerError::NodeNotFound(missing));
+    }
+
+    // Projected neighbors of every node on the other side
+    let mut members: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (&u, &v) in src.iter().zip(dst) {
+        let (node, other) = match (projected.contains(&u), projected.contains(&v)) {
+            (true, false) => (u, v),
+            (false, true) => (v, u),
+            _ => {
+                return Err(OnagerError::InvalidArgument(format!(
+                    "Every edge must join a node of project_onto with a node outside it, got edge {}-{}",
+                    u, v
+                )))
+            }
+        };
+        members.entry(other).or_default().push(node);
+    }
+
+    let mut shared: HashMap<(i64, i64), usize> = HashMap::new();
+    for list in members.values_mut() {
+        list.sort_unstable();
+        list.dedup();
+        for (i, &a) in list.iter().enumerate() {
+            for &b in &list[i + 1..] {
+                *shared.entry((a, b)).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut pairs: Vec<((i64, i64), usize)> = shared.into_iter().collect();
+    pairs.sort_unstable();
+    Ok(BipartiteProjectionResult {
+        src: pairs.iter().map(|&((a, _), _)| a).collect(),
+        dst: pairs.iter().map(|&((_, b), _)| b).collect(),
+        weights: pairs.iter().map(|&(_, count)| count as f64).collect(),
+    })
+}
+
+/// Compute a maximum matching of a bipartite graph with Hopcroft-Karp.
+///
+/// A matching is a set of edges where no two share a node, such as an
+/// assignment of workers to jobs where every edge says that a worker can do a
+/// job. Edges are undirected and the two sides are found by two-coloring, so
+/// the graph must be bipartite. Each matched pair is returned as the first
+/// input edge that joins it. Runs in O(E sqrt(V)) time.
+pub fn compute_bipartite_matching(src: &[i64], dst: &[i64]) -> Result<BipartiteMatchingResult> {
+    check_edges(src, dst)?;
+    let side = two_color(src, dst).ok_or_else(|| {
+        OnagerError::InvalidArgument("Graph must be bipartite to compute a matching".to_string())
+    })?;
+
+    // Index both sides in ascending order of node ID so the matching does not
+    // depend on hash order
+    let mut left: Vec<i64> = side.iter().filter(|&(_, &s)| !s).map(|(&v, _)| v).collect();
+    let mut right: Vec<i64> = side.iter().filter(|&(_, &s)| s).map(|(&v, _)| v).collect();
+    left.sort_unstable();
+    right.sort_unstable();
+    let left_index: HashMap<i64, usize> = left.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let right_index: HashMap<i64, usize> = right.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let mut adj: Vec<Vec<usize>> = vec![Vec::new(); left.len()];
+    for (&u, &v) in src.iter().zip(dst) {
+        let (l, r) = if side[&u] { (v, u) } else { (u, v) };
+        adj[left_index[&l]].push(right_index[&r]);
+    }
+    for list in &mut adj {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    let pair_left = hopcroft_karp(&adj, right.len());
+    let mut matched: HashSet<i64> = HashSet::new();
+    let mut result = BipartiteMatchingResult {
+        src: Vec::new(),
+        dst: Vec::new(),
+    };
+    for (&u, &v) in src.iter().zip(dst) {
+        let (l, r) = if side[&u] { (v, u) } else { (u, v) };
+        if pair_left[left_index[&l]] == Some(right_index[&r]) && matched.insert(l) {
+            result.src.push(u);
+            result.dst.push(v);
+        }
+    }
+    Ok(result)
+}
+
+/// Hopcroft-Karp over left nodes `0..adj.len()` and right nodes
+/// `0..right_count`. Returns the partner of every left node.
+fn hopcroft_karp(adj: &[Vec<usize>], right_count: usize) -> Vec<Option<usize>> {
+    let n = adj.len();
+    let mut pair_left: Vec<Option<usize>> = vec![None; n];
+    let mut pair_right: Vec<Option<usize>> = vec![None; right_count];
+    let mut dist = vec![usize::MAX; n];
+    loop {
+        // Layer the left nodes by alternating path length from the free ones
+        let mut queue = VecDeque::new();
+        for u in 0..n {
+            if pair_left[u].is_none() {
+                dist[u] = 0;
+                queue.push_back(u);
+            } else {
+                dist[u] = usize::MAX;
+            }
+        }
+        let mut found = false;
+        while let Some(u) = queue.pop_front() {
+            for &v in &adj[u] {
+                match pair_right[v] {
+                    None => found = true,
+                    Some(w) if dist[w] == usize::MAX => {
+                        dist[w] = dist[u] + 1;
+                        queue.push_back(w);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        if !found {
+            break;
+        }
+
+        // Augment along vertex-disjoint shortest paths with an explicit stack
+        let mut next = vec![0usize; n];
+        for root in 0..n {
+            if pair_left[root].is_some() {
+                continue;
+            }
+            let mut stack = vec![root];
+            while let Some(&u) = stack.last() {
+                let Some(&v) = adj[u].get(next[u]) else {
+                    dist[u] = usize::MAX;
+                    stack.pop();
+                    continue;
+                };
+                next[u] += 1;
+                match pair_right[v] {
+                    None => {
+                        // Each node on the stack takes the edge it last tried
+                        for &x in &stack {
+                            let y = adj[x][next[x] - 1];
+                            pair_left[x] = Some(y);
+                            pair_right[y] = Some(x);
+                        }
+                        break;
+                    }
+                    Some(w) if dist[w] == dist[u] + 1 => stack.push(w),
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+    pair_left
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_bipartite() {
+        // Even cycle and a path
+        assert!(compute_is_bipartite(&[1, 2, 3, 4, 7], &[2, 3, 4, 1, 8]).unwrap());
+        // Triangle in the second component
+        assert!(!compute_is_bipartite(&[1, 5, 6, 7], &[2, 6, 7, 5]).unwrap());
+        // Self-loop
+        assert!(!compute_is_bipartite(&[1, 2], &[2, 2]).unwrap());
+        assert!(compute_is_bipartite(&[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_projection_counts_shared_neighbors() {
+        // Users 1, 2, and 3 bought items 10, 11, and 12, with a repeated purchase
+        let src = vec![1, 1, 2, 2, 3, 1];
+        let dst = vec![10, 11, 10, 11, 12, 10];
+        let users = compute_bipartite_projection(&src, &dst, &[1, 2, 3]).unwrap();
+        assert_eq!(users.src, vec![1]);
+        assert_eq!(users.dst, vec![2]);
+        assert_eq!(users.weights, vec![2.0]);
+
+        // Edge direction does not matter
+        let items = compute_bipartite_projection(&dst, &src, &[10, 11, 12]).unwrap();
+        assert_eq!(items.src, vec![10]);
+        assert_eq!(items.dst, vec![11]);
+        assert_eq!(items.weights, vec![2.0]);
+    }
+
+    #[test]
+    fn test_projection_errors() {
+        let (src, dst) = (vec![1, 2], vec![10, 10]);
+        assert!(compute_bipartite_projection(&src, &dst, &[]).is_err());
+        assert!(matches!(
+            compute_bipartite_projection(&src, &dst, &[1, 99]),
+            Err(OnagerError::NodeNotFound(99))
+        ));
+        // Edge 1-2 stays inside the projected side
+        assert!(compute_bipartite_projection(&[1, 1], &[2, 10], &[1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_matching_is_maximum() {
+        // Workers 1, 2, and 3 and jobs 10, 11, and 12, where a greedy match of
+        // 1-10 and 2-11 would leave worker 3 without a job
+        let src = vec![1, 1, 2, 2, 3];
+        let dst = vec![10, 11, 11, 12, 10];
+        let result = compute_bipartite_matching(&src, &dst).unwrap();
+        assert_eq!(result.src.len(), 3);
+        let mut workers = result.src.clone();
+        let mut jobs = result.dst.clone();
+        workers.sort_unstable();
+        jobs.sort_unstable();
+        assert_eq!(workers, vec![1, 2, 3]);
+        assert_eq!(jobs, vec![10, 11, 12]);
+        for (&u, &v) in result.src.iter().zip(&result.dst) {
+            assert!(src.iter().zip(&dst).any(|(&a, &b)| (a, b) == (u, v)));
+        }
+    }
+
+    #[test]
+    fn test_matching_on_path_and_star() {
+        // Path 1-2-3-4-5-6 needs augmenting paths through matched nodes
+        let result = compute_bipartite_matching(&[2, 2, 4, 4, 6], &[1, 3, 3, 5, 5]).unwrap();
+        assert_eq!(result.src.len(), 3);
+        // A star only matches its center once, and edges keep their direction
+        let star = compute_bipartite_matching(&[1, 1, 1], &[2, 3, 4]).unwrap();
+        assert_eq!(star.src, vec![1]);
+        assert_eq!(star.dst.len(), 1);
+        // Triangles are not bipartite
+        assert!(compute_bipartite_matching(&[1, 2, 3], &[2, 3, 1]).is_err());
+    }
+}
@@ -3,30 +3,36 @@
 //! PageRank, Degree, Betweenness, Closeness, Eigenvector, Katz, Harmonic centrality, VoteRank.
 
 use graphina::centrality::betweenness::betweenness_centrality;
-use graphina::centrality::closeness::closeness_centrality;
 use graphina::centrality::degree::{in_degree_centrality, out_degree_centrality};
 use graphina::centrality::eigenvector::eigenvector_centrality;
-use graphina::centrality::harmonic::harmonic_centrality;
 use graphina::centrality::katz::katz_centrality;
 use graphina::centrality::other::{laplacian_centrality, local_reaching_centrality, voterank};
 use graphina::centrality::pagerank::pagerank;
-use graphina::core::types::{Digraph, Graph, NodeId};
+use graphina::core::types::{Digraph, Graph};
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 
+use crate::builder::{graph_from_edges, index_edges, EdgeGraph, GraphBuilder, IndexedEdges};
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::str::FromStr;
 
 /// Result of PageRank computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PageRankResult {
     pub node_ids: Vec<i64>,
     pub ranks: Vec<f64>,
 }
 
 /// Compute PageRank on a graph defined by edge arrays.
+///
+/// `weights` may be empty, in which case every edge has weight 1.0. Otherwise a
+/// node splits its rank among its out-edges in proportion to their weights.
 pub fn compute_pagerank(
     src: &[i64],
     dst: &[i64],
-    _weights: &[f64],
+    weights: &[f64],
     damping: f64,
     iterations: usize,
     directed: bool,
@@ -36,78 +42,27 @@ pub fn compute_pagerank(
             "src and dst arrays must have same length".to_string(),
         ));
     }
-
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
     let tolerance = 1e-6;
 
-    if directed {
-        let mut graph: Digraph<i64, f64> = Digraph::new();
-        for &node in src.iter().chain(dst.iter()) {
-            if !node_set.contains_key(&node) {
-                let id = graph.add_node(node);
-                node_set.insert(node, id);
-            }
+    let (node_ids, ranks) = match graph_from_edges(src, dst, weights, directed)? {
+        EdgeGraph::Directed(built) => {
+            let ranks = pagerank(&built.graph, damping, iterations, tolerance, None)
+                .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+            let ranks = built.scores(|id| ranks.get(&id).copied());
+            (built.node_ids, ranks)
         }
-        for i in 0..src.len() {
-            let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-            })?;
-            let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!(
-                    "Destination node {} not found in graph",
-                    dst[i]
-                ))
-            })?;
-            graph.add_edge(src_id, dst_id, 1.0);
-        }
-        let ranks = pagerank(&graph, damping, iterations, tolerance, None)
-            .map_err(|e| OnagerError::GraphError(e.to_string()))?;
-        let mut result_nodes = Vec::with_capacity(node_set.len());
-        let mut result_ranks = Vec::with_capacity(node_set.len());
-        for (ext_id, int_id) in &node_set {
-            result_nodes.push(*ext_id);
-            result_ranks.push(*ranks.get(int_id).unwrap_or(&0.0));
-        }
-        Ok(PageRankResult {
-            node_ids: result_nodes,
-            ranks: result_ranks,
-        })
-    } else {
-        let mut graph: Graph<i64, f64> = Graph::new();
-        for &node in src.iter().chain(dst.iter()) {
-            if !node_set.contains_key(&node) {
-                let id = graph.add_node(node);
-                node_set.insert(node, id);
-            }
+        EdgeGraph::Undirected(built) => {
+            let ranks = pagerank(&built.graph, damping, iterations, tolerance, None)
+                .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+            let ranks = built.scores(|id| ranks.get(&id).copied());
+            (built.node_ids, ranks)
         }
-        for i in 0..src.len() {
-            let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-            })?;
-            let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!(
-                    "Destination node {} not found in graph",
-                    dst[i]
-                ))
-            })?;
-            graph.add_edge(src_id, dst_id, 1.0);
-        }
-        let ranks = pagerank(&graph, damping, iterations, tolerance, None)
-            .map_err(|e| OnagerError::GraphError(e.to_string()))?;
-        let mut result_nodes = Vec::with_capacity(node_set.len());
-        let mut result_ranks = Vec::with_capacity(node_set.len());
-        for (ext_id, int_id) in &node_set {
-            result_nodes.push(*ext_id);
-            result_ranks.push(*ranks.get(int_id).unwrap_or(&0.0));
-        }
-        Ok(PageRankResult {
-            node_ids: result_nodes,
-            ranks: result_ranks,
-        })
-    }
+    };
+    Ok(PageRankResult { node_ids, ranks })
 }
 
 /// Result of degree centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DegreeResult {
     pub node_ids: Vec<i64>,
     pub in_degrees: Vec<f64>,
@@ -122,92 +77,80 @@ pub fn compute_degree(src: &[i64], dst: &[i64], directed: bool) -> Result<Degree
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-
-    if directed {
-        let mut graph: Digraph<i64, f64> = Digraph::new();
-        for &node in src.iter().chain(dst.iter()) {
-            if !node_set.contains_key(&node) {
-                let id = graph.add_node(node);
-                node_set.insert(node, id);
-            }
+    match graph_from_edges(src, dst, &[], directed)? {
+        EdgeGraph::Directed(built) => {
+            let in_deg = in_degree_centrality(&built.graph)
+                .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+            let out_deg = out_degree_centrality(&built.graph)
+                .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+            Ok(DegreeResult {
+                in_degrees: built.scores(|id| in_deg.get(&id).copied()),
+                out_degrees: built.scores(|id| out_deg.get(&id).copied()),
+                node_ids: built.node_ids,
+            })
         }
-        for i in 0..src.len() {
-            let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-            })?;
-            let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!(
-                    "Destination node {} not found in graph",
-                    dst[i]
-                ))
-            })?;
-            graph.add_edge(src_id, dst_id, 1.0);
-        }
-        let in_deg =
-            in_degree_centrality(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
-        let out_deg =
-            out_degree_centrality(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
-        let mut result_nodes = Vec::with_capacity(node_set.len());
-        let mut result_in = Vec::with_capacity(node_set.len());
-        let mut result_out = Vec::with_capacity(node_set.len());
-        for (ext_id, int_id) in &node_set {
-            result_nodes.push(*ext_id);
-            result_in.push(*in_deg.get(int_id).unwrap_or(&0.0));
-            result_out.push(*out_deg.get(int_id).unwrap_or(&0.0));
-        }
-        Ok(DegreeResult {
-            node_ids: result_nodes,
-            in_degrees: result_in,
-            out_degrees: result_out,
-        })
-    } else {
-        let mut graph: Graph<i64, f64> = Graph::new();
-        for &node in src.iter().chain(dst.iter()) {
-            if !node_set.contains_key(&node) {
-                let id = graph.add_node(node);
-                node_set.insert(node, id);
-            }
+        EdgeGraph::Undirected(built) => {
+            let deg = in_degree_centrality(&built.graph)
+                .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+            let degrees = built.scores(|id| deg.get(&id).copied());
+            Ok(DegreeResult {
+                node_ids: built.node_ids,
+                in_degrees: degrees.clone(),
+                out_degrees: degrees,
+            })
         }
-        for i in 0..src.len() {
-            let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-            })?;
-            let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!(
-                    "Destination node {} not found in graph",
-                    dst[i]
-                ))
-            })?;
-            graph.add_edge(src_id, dst_id, 1.0);
-        }
-        let deg =
-            in_degree_centrality(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
-        let mut result_nodes = Vec::with_capacity(node_set.len());
-        let mut result_deg = Vec::with_capacity(node_set.len());
-        for (ext_id, int_id) in &node_set {
-            result_nodes.push(*ext_id);
-            result_deg.push(*deg.get(int_id).unwrap_or(&0.0));
-        }
-        Ok(DegreeResult {
-            node_ids: result_nodes,
-            in_degrees: result_deg.clone(),
-            out_degrees: result_deg,
-        })
     }
 }
 
+/// Check that `weights` is empty or holds one finite, non-negative weight per edge.
+fn check_distance_weights(src: &[i64], weights: &[f64]) -> Result<()> {
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights must be empty or same length as edges".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite and non-negative, got {}",
+            w
+        )));
+    }
+    Ok(())
+}
+
 /// Result of betweenness centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BetweennessResult {
     pub node_ids: Vec<i64>,
     pub centralities: Vec<f64>,
 }
 
+/// Build a `$graph` from edge arrays, run `$compute` on it, and return the
+/// external node IDs with their scores.
+///
+/// `Graph` and `Digraph` are separate types, so the directed and undirected
+/// variants of each centrality expand the same code with a different type.
+macro_rules! centrality_on {
+    ($graph:ident<$weight:ty>, $src:expr, $dst:expr, $edge_weight:expr, $compute:expr) => {{
+        let built = GraphBuilder::new($src, $dst)?.build::<$graph<i64, $weight>>($edge_weight);
+        let centralities =
+            $compute(&built.graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+        let result_cent = built.scores(|id| centralities.get(&id).copied());
+        (built.node_ids, result_cent)
+    }};
+}
+
 /// Compute betweenness centrality.
+///
+/// Weights are distances along shortest paths, so they must be finite and
+/// non-negative. Empty `weights` means unit weights. When `directed` is set,
+/// shortest paths follow edge direction.
 pub fn compute_betweenness(
     src: &[i64],
     dst: &[i64],
+    weights: &[f64],
     normalized: bool,
+    directed: bool,
 ) -> Result<BetweennessResult> {
     if src.len() != dst.len() {
         return Err(OnagerError::InvalidArgument(
@@ -219,100 +162,62 @@ pub fn compute_betweenness(
             "Cannot compute betweenness on empty graph".to_string(),
         ));
     }
+    check_distance_weights(src, weights)?;
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
-    }
-    let centralities = betweenness_centrality(&graph, normalized)
-        .map_err(|e| OnagerError::GraphError(e.to_string()))?;
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_cent = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        result_cent.push(*centralities.get(int_id).unwrap_or(&0.0));
-    }
+    let weight = |i: usize| OrderedFloat(if weights.is_empty() { 1.0 } else { weights[i] });
+    let (node_ids, centralities) = if directed {
+        centrality_on!(Digraph<OrderedFloat<f64>>, src, dst, weight, |graph| {
+            betweenness_centrality(graph, normalized)
+        })
+    } else {
+        centrality_on!(Graph<OrderedFloat<f64>>, src, dst, weight, |graph| {
+            betweenness_centrality(graph, normalized)
+        })
+    };
     Ok(BetweennessResult {
-        node_ids: result_nodes,
-        centralities: result_cent,
+        node_ids,
+        centralities,
     })
 }
 
 /// Result of closeness centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClosenessResult {
     pub node_ids: Vec<i64>,
     pub centralities: Vec<f64>,
 }
 
 /// Compute closeness centrality.
-pub fn compute_closeness(src: &[i64], dst: &[i64]) -> Result<ClosenessResult> {
-    if src.len() != dst.len() {
-        return Err(OnagerError::InvalidArgument(
-            "src and dst arrays must have same length".to_string(),
-        ));
-    }
-    if src.is_empty() {
-        return Err(OnagerError::InvalidArgument(
-            "Cannot compute closeness on empty graph".to_string(),
-        ));
-    }
-
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
-    }
-    let centralities =
-        closeness_centrality(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_cent = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        result_cent.push(*centralities.get(int_id).unwrap_or(&0.0));
-    }
-    Ok(ClosenessResult {
-        node_ids: result_nodes,
-        centralities: result_cent,
-    })
+///
+/// Weights are distances, so they must be finite and non-negative. Empty
+/// `weights` means unit weights. When `directed` is set, distances follow
+/// edge direction.
+pub fn compute_closeness(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+) -> Result<ClosenessResult> {
+    compute_closeness_general(src, dst, weights, directed, &[], None)
 }
 
 /// Result of eigenvector centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EigenvectorResult {
     pub node_ids: Vec<i64>,
     pub centralities: Vec<f64>,
 }
 
 /// Compute eigenvector centrality.
+///
+/// When `directed` is set, a node's score comes from the nodes that point to it.
+/// Scores are L2-normalized and never sum to a negative value.
 pub fn compute_eigenvector(
     src: &[i64],
     dst: &[i64],
     max_iter: usize,
     tolerance: f64,
+    directed: bool,
 ) -> Result<EigenvectorResult> {
     if src.len() != dst.len() {
         return Err(OnagerError::InvalidArgument(
@@ -325,38 +230,158 @@ pub fn compute_eigenvector(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
+    let weight = |_| 1.0;
+    let (node_ids, centralities) = if directed {
+        centrality_on!(Digraph<f64>, src, dst, weight, |graph| {
+            eigenvector_centrality(graph, max_iter, tolerance)
+        })
+    } else {
+        centrality_on!(Graph<f64>, src, dst, weight, |graph| {
+            eigenvector_centrality(graph, max_iter, tolerance)
+        })
+    };
+    check_finite_scores(&centralities, "Eigenvector")?;
+    let mut centralities = centralities;
+    normalize_eigenvector(&mut centralities, EigenvectorNormalization::L2);
+    Ok(EigenvectorResult {
+        node_ids,
+        centralities,
+    })
+}
+
+/// How eigenvector centrality scores are scaled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EigenvectorNormalization {
+    /// Scores sum to 1.
+    L1,
+    /// Scores have unit Euclidean length.
+    #[default]
+    L2,
+    /// The largest score is 1.
+    Max,
+}
+
+impl FromStr for EigenvectorNormalization {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "l1" | "sum" => Ok(EigenvectorNormalization::L1),
+            "l2" => Ok(EigenvectorNormalization::L2),
+            "max" => Ok(EigenvectorNormalization::Max),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown eigenvector normalization '{}', expected 'l1', 'l2', or 'max'",
+                s
+            ))),
         }
     }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-    let centralities = eigenvector_centrality(&graph, max_iter, tolerance)
-        .map_err(|e| OnagerError::GraphError(e.to_string()))?;
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_cent = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        result_cent.push(*centralities.get(int_id).unwrap_or(&0.0));
+}
+
+/// Flip an eigenvector so its entries sum to a non-negative value, then scale
+/// it by `normalization`.
+///
+/// An eigenvector is only defined up to a scalar factor, so without this the
+/// sign and scale depend on the solver. A vector that sums to zero takes the
+/// sign of its largest entry by magnitude, and a zero vector is left as is.
+fn normalize_eigenvector(values: &mut [f64], normalization: EigenvectorNormalization) {
+    let sum: f64 = values.iter().sum();
+    let pivot = values.iter().copied().fold(
+        0.0_f64,
+        |best, v| if v.abs() > best.abs() { v } else { best },
+    );
+    let sign = if sum < 0.0 || (sum == 0.0 && pivot < 0.0) {
+        -1.0
+    } else {
+        1.0
+    };
+    let norm = match normalization {
+        EigenvectorNormalization::L1 => values.iter().map(|v| v.abs()).sum(),
+        EigenvectorNormalization::L2 => values.iter().map(|v| v * v).sum::<f64>().sqrt(),
+        EigenvectorNormalization::Max => pivot.abs(),
+    };
+    if norm > 0.0 && norm.is_finite() {
+        for v in values.iter_mut() {
+            // Adding 0.0 turns -0.0 into 0.0
+            *v = sign * *v / norm + 0.0;
+        }
     }
+}
+
+/// Compute eigenvector centrality on a weighted graph by power iteration.
+///
+/// A node's score is proportional to the weighted sum of its neighbors'
+/// scores, and with `directed` set only in-edges count, so a node's score
+/// comes from the nodes that point to it. Parallel edges add up and empty
+/// `weights` means unit weights. Iterating on `A + I` rather than `A` keeps
+/// bipartite graphs from oscillating without changing the eigenvector.
+/// Iteration stops once the total change of the L2-normalized scores drops
+/// below `n * tolerance`, and fails if that takes more than `max_iter` steps.
+/// The result is scaled by `normalization` and never sums to a negative value.
+pub fn compute_eigenvector_weighted(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    max_iter: usize,
+    tolerance: f64,
+    directed: bool,
+    normalization: EigenvectorNormalization,
+) -> Result<EigenvectorResult> {
+    if !tolerance.is_finite() || tolerance <= 0.0 {
+        return Err(OnagerError::InvalidArgument(format!(
+            "tolerance must be positive, got {}",
+            tolerance
+        )));
+    }
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+    let n = adj.node_ids.len();
+    let incoming = if directed {
+        let mut incoming = vec![Vec::new(); n];
+        for &(u, v, w) in &indexed.edges {
+            incoming[v].push((u, w));
+        }
+        incoming
+    } else {
+        adj.neighbors
+    };
+
+    let mut scores = vec![1.0 / n as f64; n];
+    let mut converged = false;
+    for _ in 0..max_iter {
+        let mut next: Vec<f64> = (0..n)
+            .map(|v| scores[v] + incoming[v].iter().map(|&(u, w)| w * scores[u]).sum::<f64>())
+            .collect();
+        let norm = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if !norm.is_finite() {
+            return Err(OnagerError::GraphError(
+                "Eigenvector centrality overflowed, try smaller edge weights".to_string(),
+            ));
+        }
+        for v in &mut next {
+            *v /= norm;
+        }
+        let change: f64 = next.iter().zip(&scores).map(|(a, b)| (a - b).abs()).sum();
+        scores = next;
+        if change < n as f64 * tolerance {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(OnagerError::GraphError(format!(
+            "Eigenvector centrality did not converge in {} iterations",
+            max_iter
+        )));
+    }
+    normalize_eigenvector(&mut scores, normalization);
     Ok(EigenvectorResult {
-        node_ids: result_nodes,
-        centralities: result_cent,
+        node_ids: adj.node_ids,
+        centralities: scores,
     })
 }
 
 /// Result of Katz centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KatzResult {
     pub node_ids: Vec<i64>,
     pub centralities: Vec<f64>,
@@ -381,88 +406,121 @@ pub fn compute_katz(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-    let centralities = katz_centrality(&graph, alpha, None, max_iter, tolerance)
+    check_katz_alpha(src, dst, &[], alpha)?;
+
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let centralities = katz_centrality(&built.graph, alpha, None, max_iter, tolerance)
         .map_err(|e| OnagerError::GraphError(e.to_string()))?;
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_cent = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        result_cent.push(*centralities.get(int_id).unwrap_or(&0.0));
-    }
+    let centralities = built.scores(|id| centralities.get(&id).copied());
+    check_finite_scores(&centralities, "Katz")?;
     Ok(KatzResult {
-        node_ids: result_nodes,
-        centralities: result_cent,
+        centralities,
+        node_ids: built.node_ids,
     })
 }
 
+/// Estimate the largest eigenvalue of the undirected adjacency matrix.
+///
+/// Power iteration on `A + I` keeps every iterate `x` positive, so the
+/// Collatz-Wielandt bound `max_i (A x)_i / x_i` never falls below the largest
+/// eigenvalue while the Rayleigh quotient never exceeds it. Iteration stops
+/// once the two agree to one part in 10^9, or after 1000 steps, and returns
+/// the smallest upper bound seen, so the estimate errs on the high side.
+/// Parallel edges add up and empty `weights` means unit weights.
+pub fn estimate_spectral_radius(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<f64> {
+    let adj = build_weighted_adjacency(src, dst, weights)?;
+    let n = adj.node_ids.len();
+    let mut x = vec![1.0 / (n as f64).sqrt(); n];
+    let mut bound = f64::INFINITY;
+    for _ in 0..1000 {
+        let ax: Vec<f64> = adj
+            .neighbors
+            .iter()
+            .map(|list| list.iter().map(|&(u, w)| w * x[u]).sum())
+            .collect();
+        // Entries that underflowed to zero belong to weaker components and
+        // cannot raise the bound
+        let upper = ax
+            .iter()
+            .zip(&x)
+            .filter(|&(_, &xi)| xi > 0.0)
+            .map(|(a, xi)| a / xi)
+            .fold(0.0, f64::max);
+        if !upper.is_finite() {
+            return Err(OnagerError::GraphError(
+                "Spectral radius estimate overflowed, try smaller edge weights".to_string(),
+            ));
+        }
+        bound = bound.min(upper);
+        let rayleigh: f64 = x.iter().zip(&ax).map(|(xi, a)| xi * a).sum();
+        if bound - rayleigh <= 1e-9 * bound {
+            break;
+        }
+        let mut next: Vec<f64> = x.iter().zip(&ax).map(|(xi, a)| xi + a).collect();
+        let norm = next.iter().map(|v| v * v).sum::<f64>().sqrt();
+        for v in &mut next {
+            *v /= norm;
+        }
+        x = next;
+    }
+    Ok(bound)
+}
+
+/// Reject a Katz `alpha` at or above `1 / lambda_max`, where the series of
+/// walk counts diverges and the iteration would only return overflowed values.
+pub(crate) fn check_katz_alpha(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    alpha: f64,
+) -> Result<()> {
+    if !alpha.is_finite() {
+        return Err(OnagerError::InvalidArgument(format!(
+            "alpha must be finite, got {}",
+            alpha
+        )));
+    }
+    let radius = estimate_spectral_radius(src, dst, weights)?;
+    if alpha.abs() * radius >= 1.0 {
+        return Err(OnagerError::InvalidArgument(format!(
+            "alpha {} is too large for Katz centrality to converge: it must be less than \
+             1 / lambda_max = {:.6}, where lambda_max (about {:.6}) is the largest \
+             adjacency eigenvalue",
+            alpha,
+            1.0 / radius,
+            radius
+        )));
+    }
+    Ok(())
+}
+
+/// Fail when an iterative method produced infinite or NaN scores.
+fn check_finite_scores(scores: &[f64], name: &str) -> Result<()> {
+    if scores.iter().any(|s| !s.is_finite()) {
+        return Err(OnagerError::GraphError(format!(
+            "{} centrality produced non-finite scores, the iteration overflowed",
+            name
+        )));
+    }
+    Ok(())
+}
+
 /// Result of harmonic centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HarmonicResult {
     pub node_ids: Vec<i64>,
     pub centralities: Vec<f64>,
 }
 
 /// Compute harmonic centrality.
-pub fn compute_harmonic(src: &[i64], dst: &[i64]) -> Result<HarmonicResult> {
-    if src.len() != dst.len() {
-        return Err(OnagerError::InvalidArgument(
-            "src and dst arrays must have same length".to_string(),
-        ));
-    }
-    if src.is_empty() {
-        return Err(OnagerError::InvalidArgument(
-            "Cannot compute harmonic on empty graph".to_string(),
-        ));
-    }
-
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
-    }
-    let centralities =
-        harmonic_centrality(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_cent = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        result_cent.push(*centralities.get(int_id).unwrap_or(&0.0));
-    }
-    Ok(HarmonicResult {
-        node_ids: result_nodes,
-        centralities: result_cent,
-    })
+///
+/// When `directed` is set, distances follow edge direction.
+pub fn compute_harmonic(src: &[i64], dst: &[i64], directed: bool) -> Result<HarmonicResult> {
+    compute_harmonic_general(src, dst, &[], directed, &[], None)
 }
 
 /// Result of single-node degree computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeDegreeResult {
     pub in_degree: i64,
     pub out_degree: i64,
@@ -511,6 +569,7 @@ pub fn compute_node_degree(src: &[i64], dst: &[i64], node: i64) -> Result<NodeDe
 }
 
 /// Result of VoteRank computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoteRankResult {
     pub node_ids: Vec<i64>,
 }
@@ -528,27 +587,10 @@ pub fn compute_voterank(src: &[i64], dst: &[i64], num_seeds: usize) -> Result<Vo
         });
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let seeds = voterank(&graph, num_seeds);
+    let seeds = voterank(&built.graph, num_seeds);
     let mut result_nodes = Vec::with_capacity(seeds.len());
     for node_id in seeds {
         if let Some(&ext_id) = reverse_map.get(&node_id) {
@@ -562,6 +604,7 @@ pub fn compute_voterank(src: &[i64], dst: &[i64], num_seeds: usize) -> Result<Vo
 }
 
 /// Result of Local Reaching Centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocalReachingResult {
     pub node_ids: Vec<i64>,
     pub centrality: Vec<f64>,
@@ -586,43 +629,19 @@ pub fn compute_local_reaching(
         });
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    let centrality_map = local_reaching_centrality(&graph, distance)
+    let centrality_map = local_reaching_centrality(&built.graph, distance)
         .map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_centrality = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        result_centrality.push(*centrality_map.get(int_id).unwrap_or(&0.0));
-    }
-
     Ok(LocalReachingResult {
-        node_ids: result_nodes,
-        centrality: result_centrality,
+        centrality: built.scores(|id| centrality_map.get(&id).copied()),
+        node_ids: built.node_ids,
     })
 }
 
 /// Result of Laplacian Centrality computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LaplacianResult {
     pub node_ids: Vec<i64>,
     pub centrality: Vec<f64>,
@@ -643,42 +662,543 @@ pub fn compute_laplacian(src: &[i64], dst: &[i64]) -> Result<LaplacianResult> {
         });
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+
+    let centrality_map =
+        laplacian_centrality(&built.graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+
+    Ok(LaplacianResult {
+        centrality: built.scores(|id| centrality_map.get(&id).copied()),
+        node_ids: built.node_ids,
+    })
+}
+
+/// Undirected weighted adjacency list over compact node indices.
+pub(crate) struct WeightedAdjacency {
+    pub(crate) node_ids: Vec<i64>,
+    pub(crate) neighbors: Vec<Vec<(usize, f64)>>,
+}
+
+impl WeightedAdjacency {
+    /// Build the undirected adjacency list of indexed edges, for callers that
+    /// also look nodes up in `indexed.nodes`.
+    pub(crate) fn from_indexed(indexed: &IndexedEdges) -> Self {
+        let mut neighbors = vec![Vec::new(); indexed.nodes.len()];
+        for &(u, v, w) in &indexed.edges {
+            neighbors[u].push((v, w));
+            if u != v {
+                neighbors[v].push((u, w));
+            }
+        }
+        WeightedAdjacency {
+            node_ids: indexed.nodes.node_ids.clone(),
+            neighbors,
         }
     }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
+}
+
+/// Build an undirected weighted adjacency list.
+/// Empty weights mean every edge has weight 1.0.
+pub(crate) fn build_weighted_adjacency(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+) -> Result<WeightedAdjacency> {
+    Ok(WeightedAdjacency::from_indexed(&index_edges(
+        src, dst, weights,
+    )?))
+}
+
+/// Shortest path DAG from a single source, as used by Brandes' algorithm.
+pub(crate) struct ShortestPathTree {
+    /// Nodes in the order they were settled (non-decreasing distance).
+    pub(crate) order: Vec<usize>,
+    pub(crate) dist: Vec<f64>,
+    pub(crate) sigma: Vec<f64>,
+    /// Predecessors on shortest paths, in the order they were found.
+    pub(crate) preds: Vec<Vec<usize>>,
+}
+
+/// Run Dijkstra from `source`, ignoring nodes farther away than `cutoff`.
+pub(crate) fn bounded_dijkstra(
+    adj: &WeightedAdjacency,
+    source: usize,
+    cutoff: Option<f64>,
+) -> ShortestPathTree {
+    let n = adj.node_ids.len();
+    let mut dist = vec![f64::INFINITY; n];
+    let mut sigma = vec![0.0; n];
+    let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut settled = vec![false; n];
+    let mut order = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    dist[source] = 0.0;
+    sigma[source] = 1.0;
+    heap.push(Reverse((OrderedFloat(0.0), source)));
+    while let Some(Reverse((OrderedFloat(d), u))) = heap.pop() {
+        if settled[u] || d > dist[u] {
+            continue;
+        }
+        settled[u] = true;
+        order.push(u);
+        for &(v, w) in &adj.neighbors[u] {
+            let alt = d + w;
+            if settled[v] || cutoff.is_some_and(|c| alt > c) {
+                continue;
+            }
+            if alt < dist[v] {
+                dist[v] = alt;
+                sigma[v] = sigma[u];
+                preds[v] = vec![u];
+                heap.push(Reverse((OrderedFloat(alt), v)));
+            } else if alt == dist[v] {
+                sigma[v] += sigma[u];
+                preds[v].push(u);
+            }
+        }
+    }
+    ShortestPathTree {
+        order,
+        dist,
+        sigma,
+        preds,
     }
+}
 
-    let centrality_map =
-        laplacian_centrality(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+/// Compute closeness centrality with optional edge weights and a distance cutoff.
+///
+/// Only nodes within `cutoff` of a node contribute to its score, so a small
+/// cutoff trades accuracy for speed on large graphs. Empty `weights` means
+/// unit weights.
+pub fn compute_closeness_cutoff(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    cutoff: Option<f64>,
+) -> Result<ClosenessResult> {
+    compute_closeness_general(src, dst, weights, false, &[], cutoff)
+}
 
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_centrality = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        result_centrality.push(*centrality_map.get(int_id).unwrap_or(&0.0));
+/// Compute harmonic centrality with optional edge weights and a distance cutoff.
+///
+/// Only nodes within `cutoff` of a node contribute to its score. Empty
+/// `weights` means unit weights.
+pub fn compute_harmonic_cutoff(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    cutoff: Option<f64>,
+) -> Result<HarmonicResult> {
+    compute_harmonic_general(src, dst, weights, false, &[], cutoff)
+}
+
+/// Per-node distance totals behind closeness and harmonic centrality.
+struct DistanceSums {
+    node_ids: Vec<i64>,
+    /// Number of other nodes, or of targets other than the node itself, within reach.
+    reached: Vec<usize>,
+    total: Vec<f64>,
+    harmonic: Vec<f64>,
+}
+
+/// Sum the distances from every node to all other nodes, or to `targets`
+/// when it is not empty.
+///
+/// Distances follow edge direction when `directed` is set, and nodes farther
+/// away than `cutoff` are left out. Without targets, one Dijkstra run per node
+/// gives its distances to all others. With targets, one run per target over
+/// the reversed edges gives the distance from every node to that target.
+/// Duplicate targets count once.
+fn distance_sums(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+    targets: &[i64],
+    cutoff: Option<f64>,
+) -> Result<DistanceSums> {
+    let indexed = index_edges(src, dst, weights)?;
+    let mut adj = build_betweenness_adjacency(&indexed, directed, ParallelEdges::Min);
+    let n = adj.node_ids.len();
+    let sources: Vec<usize> = if targets.is_empty() {
+        (0..n).collect()
+    } else {
+        let mut target_indices = Vec::with_capacity(targets.len());
+        for &target in targets {
+            let t = indexed.nodes.get(target).ok_or_else(|| {
+                OnagerError::InvalidArgument(format!("Target node {} not found", target))
+            })?;
+            if !target_indices.contains(&t) {
+                target_indices.push(t);
+            }
+        }
+        if directed {
+            let mut reversed = vec![Vec::new(); n];
+            for (u, neighbors) in adj.neighbors.iter().enumerate() {
+                for &(v, w) in neighbors {
+                    reversed[v].push((u, w));
+                }
+            }
+            adj.neighbors = reversed;
+        }
+        target_indices
+    };
+
+    let mut reached = vec![0; n];
+    let mut total = vec![0.0; n];
+    let mut harmonic = vec![0.0; n];
+    for &source in &sources {
+        let tree = bounded_dijkstra(&adj, source, cutoff);
+        for &v in tree.order.iter().filter(|&&v| v != source) {
+            // Without targets the source is the node being scored
+            let node = if targets.is_empty() { source } else { v };
+            reached[node] += 1;
+            total[node] += tree.dist[v];
+            if tree.dist[v] > 0.0 {
+                harmonic[node] += 1.0 / tree.dist[v];
+            }
+        }
     }
+    Ok(DistanceSums {
+        node_ids: adj.node_ids,
+        reached,
+        total,
+        harmonic,
+    })
+}
 
-    Ok(LaplacianResult {
-        node_ids: result_nodes,
-        centrality: result_centrality,
+/// Compute closeness centrality on directed or undirected, weighted graphs,
+/// optionally with respect to a set of target nodes.
+///
+/// This is the general entry point. The score of a node that reaches `r`
+/// other nodes with total distance `d` is `r / d`, the inverse of its average
+/// distance to the nodes it reaches, or 0 when it reaches none. When `targets`
+/// is not empty only distances to those targets count, which answers questions
+/// such as how close each address is to a set of hospitals; every target must
+/// appear in the edges. Directed graphs measure distances along edge direction
+/// from each node. Only nodes within `cutoff` count, and empty `weights` means
+/// unit weights.
+pub fn compute_closeness_general(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+    targets: &[i64],
+    cutoff: Option<f64>,
+) -> Result<ClosenessResult> {
+    let sums = distance_sums(src, dst, weights, directed, targets, cutoff)?;
+    let centralities = sums
+        .reached
+        .iter()
+        .zip(&sums.total)
+        .map(|(&r, &d)| if d > 0.0 { r as f64 / d } else { 0.0 })
+        .collect();
+    Ok(ClosenessResult {
+        node_ids: sums.node_ids,
+        centralities,
+    })
+}
+
+/// Compute harmonic centrality on directed or undirected, weighted graphs,
+/// optionally with respect to a set of target nodes.
+///
+/// This is the general entry point. The score of a node is the sum of `1 / d`
+/// over the other nodes, or the targets other than itself, within `cutoff`,
+/// so unreachable nodes add nothing. Directed graphs measure distances along
+/// edge direction from each node. Empty `weights` means unit weights, and
+/// every target must appear in the edges.
+pub fn compute_harmonic_general(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+    targets: &[i64],
+    cutoff: Option<f64>,
+) -> Result<HarmonicResult> {
+    let sums = distance_sums(src, dst, weights, directed, targets, cutoff)?;
+    Ok(HarmonicResult {
+        node_ids: sums.node_ids,
+        centralities: sums.harmonic,
+    })
+}
+
+/// Compute closeness centrality with respect to a set of target nodes.
+///
+/// See [`compute_closeness_general`] for the score. Edges are undirected, and
+/// `targets` must not be empty.
+pub fn compute_closeness_to_targets(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    targets: &[i64],
+    cutoff: Option<f64>,
+) -> Result<ClosenessResult> {
+    check_targets(targets)?;
+    compute_closeness_general(src, dst, weights, false, targets, cutoff)
+}
+
+/// Compute harmonic centrality with respect to a set of target nodes.
+///
+/// See [`compute_harmonic_general`] for the score. Edges are undirected, and
+/// `targets` must not be empty.
+pub fn compute_harmonic_to_targets(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    targets: &[i64],
+    cutoff: Option<f64>,
+) -> Result<HarmonicResult> {
+    check_targets(targets)?;
+    compute_harmonic_general(src, dst, weights, false, targets, cutoff)
+}
+
+/// Reject an empty target set, which would otherwise mean every node.
+fn check_targets(targets: &[i64]) -> Result<()> {
+    if targets.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "targets must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Brandes dependency accumulation over every source, before any scaling.
+fn brandes(adj: &WeightedAdjacency, cutoff: Option<f64>) -> Vec<f64> {
+    let n = adj.node_ids.len();
+    let mut centralities = vec![0.0; n];
+    for source in 0..n {
+        let tree = bounded_dijkstra(adj, source, cutoff);
+        let mut delta = vec![0.0; n];
+        for &w in tree.order.iter().rev() {
+            for &v in &tree.preds[w] {
+                delta[v] += tree.sigma[v] / tree.sigma[w] * (1.0 + delta[w]);
+            }
+            if w != source {
+                centralities[w] += delta[w];
+            }
+        }
+    }
+    centralities
+}
+
+/// Normalization applied to betweenness scores.
+///
+/// Unnormalized scores can be recovered as `score * normalizer`, which lets
+/// callers re-scale or combine results computed on different graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BetweennessScaling {
+    /// Number of nodes in the graph.
+    pub node_count: usize,
+    /// Divisor applied to unnormalized scores, or 1 when not normalized.
+    pub normalizer: f64,
+}
+
+impl BetweennessScaling {
+    /// Factor applied to raw Brandes dependency sums, in which undirected
+    /// paths are found once from each endpoint.
+    fn scale(&self, directed: bool) -> f64 {
+        let endpoints = if directed { 1.0 } else { 0.5 };
+        endpoints / self.normalizer
+    }
+}
+
+/// Normalization for betweenness on a graph with `node_count` nodes.
+///
+/// Normalized scores divide by `(n - 1)(n - 2)` for directed graphs and by
+/// `(n - 1)(n - 2) / 2` for undirected graphs. Graphs with fewer than three
+/// nodes have no intermediate nodes and keep a normalizer of 1.
+pub fn betweenness_scaling(
+    node_count: usize,
+    directed: bool,
+    normalized: bool,
+) -> BetweennessScaling {
+    let normalizer = if normalized && node_count > 2 {
+        let pairs = ((node_count - 1) * (node_count - 2)) as f64;
+        if directed {
+            pairs
+        } else {
+            pairs / 2.0
+        }
+    } else {
+        1.0
+    };
+    BetweennessScaling {
+        node_count,
+        normalizer,
+    }
+}
+
+/// Compute betweenness centrality with optional edge weights and a distance cutoff.
+///
+/// Uses Brandes' algorithm and only counts shortest paths no longer than
+/// `cutoff`. Empty `weights` means unit weights.
+pub fn compute_betweenness_cutoff(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    normalized: bool,
+    cutoff: Option<f64>,
+) -> Result<BetweennessResult> {
+    let adj = build_weighted_adjacency(src, dst, weights)?;
+    let n = adj.node_ids.len();
+    let mut centralities = brandes(&adj, cutoff);
+    let scale = betweenness_scaling(n, false, normalized).scale(false);
+    for c in &mut centralities {
+        *c *= scale;
+    }
+    Ok(BetweennessResult {
+        node_ids: adj.node_ids,
+        centralities,
     })
 }
 
+/// How parallel edges between the same pair of nodes are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ParallelEdges {
+    /// Keep only the lightest edge, as if the graph were simple.
+    #[default]
+    Min,
+    /// Keep every edge, so parallel edges of equal weight are separate shortest paths.
+    Count,
+}
+
+impl FromStr for ParallelEdges {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "min" => Ok(ParallelEdges::Min),
+            "count" => Ok(ParallelEdges::Count),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown parallel edge mode '{}', expected 'min' or 'count'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Build an adjacency list of outgoing edges for betweenness.
+///
+/// Undirected edges are stored in both directions, and in undirected graphs
+/// `(u, v)` and `(v, u)` are parallel. Self-loops never lie on a shortest path
+/// and are dropped.
+fn build_betweenness_adjacency(
+    indexed: &IndexedEdges,
+    directed: bool,
+    parallel: ParallelEdges,
+) -> WeightedAdjacency {
+    let key = |u: usize, v: usize| {
+        if directed || u < v {
+            (u, v)
+        } else {
+            (v, u)
+        }
+    };
+
+    let mut lightest: HashMap<(usize, usize), f64> = HashMap::new();
+    if parallel == ParallelEdges::Min {
+        for &(u, v, w) in &indexed.edges {
+            let entry = lightest.entry(key(u, v)).or_insert(f64::INFINITY);
+            *entry = entry.min(w);
+        }
+    }
+    let mut neighbors = vec![Vec::new(); indexed.nodes.len()];
+    for &(u, v, w) in &indexed.edges {
+        if u == v {
+            continue;
+        }
+        let w = match parallel {
+            ParallelEdges::Count => w,
+            // Emit each node pair once, at its first occurrence
+            ParallelEdges::Min => match lightest.remove(&key(u, v)) {
+                Some(w) => w,
+                None => continue,
+            },
+        };
+        neighbors[u].push((v, w));
+        if !directed {
+            neighbors[v].push((u, w));
+        }
+    }
+    WeightedAdjacency {
+        node_ids: indexed.nodes.node_ids.clone(),
+        neighbors,
+    }
+}
+
+/// Compute betweenness centrality on directed or undirected, weighted
+/// multigraphs.
+///
+/// This is the general entry point: directed graphs follow edge direction,
+/// weights must be non-negative distances (empty `weights` means unit
+/// weights), and `parallel` sets how repeated edges between the same nodes
+/// are combined. Only shortest paths no longer than `cutoff` are counted.
+/// Normalized scores divide by `(n - 1)(n - 2)` for directed graphs and by
+/// `(n - 1)(n - 2) / 2` for undirected graphs.
+pub fn compute_betweenness_general(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+    normalized: bool,
+    parallel: ParallelEdges,
+    cutoff: Option<f64>,
+) -> Result<BetweennessResult> {
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = build_betweenness_adjacency(&indexed, directed, parallel);
+    let n = adj.node_ids.len();
+    let mut centralities = brandes(&adj, cutoff);
+    let scale = betweenness_scaling(n, directed, normalized).scale(directed);
+    for c in &mut centralities {
+        *c *= scale;
+    }
+    Ok(BetweennessResult {
+        node_ids: adj.node_ids,
+        centralities,
+    })
+}
+
+/// Select the `k` highest scores, returning their indices by descending score.
+///
+/// Ties keep their original order and NaN scores rank last. Selection takes
+/// O(n + k log k) time, so callers can return the top nodes of a centrality
+/// result without sorting all of it.
+pub fn top_k_indices(scores: &[f64], k: usize) -> Vec<usize> {
+    let rank = |a: &usize, b: &usize| {
+        let (x, y) = (scores[*a], scores[*b]);
+        match (x.is_nan(), y.is_nan()) {
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+            (true, true) => a.cmp(b),
+            (false, false) => y.total_cmp(&x).then(a.cmp(b)),
+        }
+    };
+    let k = k.min(scores.len());
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut indices: Vec<usize> = (0..scores.len()).collect();
+    if k < indices.len() {
+        indices.select_nth_unstable_by(k - 1, rank);
+        indices.truncate(k);
+    }
+    indices.sort_unstable_by(rank);
+    indices
+}
+
+/// Select the indices of scores at or above `min_value`, in their original order.
+///
+/// NaN scores never pass the threshold.
+pub fn min_value_indices(scores: &[f64], min_value: f64) -> Vec<usize> {
+    scores
+        .iter()
+        .enumerate()
+        .filter(|(_, &s)| s >= min_value)
+        .map(|(i, _)| i)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -705,6 +1225,21 @@ mod tests {
         assert!((sum - 1.0).abs() < 0.01, "PageRank should sum to 1");
     }
 
+    #[test]
+    fn test_pagerank_json_roundtrip() {
+        use crate::algorithms::ToJson;
+
+        let (src, dst) = triangle_graph();
+        let result = compute_pagerank(&src, &dst, &[], 0.85, 100, false).unwrap();
+        let json = result.to_json().unwrap();
+        let decoded: PageRankResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded.node_ids, result.node_ids);
+        for (a, b) in decoded.ranks.iter().zip(result.ranks.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
     #[test]
     fn test_pagerank_directed() {
         let (src, dst) = triangle_graph();
@@ -714,6 +1249,21 @@ mod tests {
         assert!(!result.ranks.is_empty());
     }
 
+    #[test]
+    fn test_pagerank_weighted() {
+        // Node 1 sends nine times more rank to 2 than to 3
+        let (src, dst) = (vec![1, 1], vec![2, 3]);
+        let result = compute_pagerank(&src, &dst, &[9.0, 1.0], 0.85, 100, true).unwrap();
+        let rank = |node: i64| {
+            let pos = result.node_ids.iter().position(|&n| n == node).unwrap();
+            result.ranks[pos]
+        };
+        assert!(rank(2) > rank(3));
+
+        let result = compute_pagerank(&src, &dst, &[1.0], 0.85, 100, true);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_degree_undirected() {
         let (src, dst) = star_graph();
@@ -738,7 +1288,7 @@ mod tests {
         // Path graph: 1-2-3-4 (node 2 and 3 have high betweenness)
         let src = vec![1, 2, 3];
         let dst = vec![2, 3, 4];
-        let result = compute_betweenness(&src, &dst, true).unwrap();
+        let result = compute_betweenness(&src, &dst, &[], true, false).unwrap();
 
         assert_eq!(result.node_ids.len(), 4);
         assert!(!result.centralities.is_empty());
@@ -747,21 +1297,154 @@ mod tests {
     #[test]
     fn test_closeness() {
         let (src, dst) = triangle_graph();
-        let result = compute_closeness(&src, &dst).unwrap();
+        let result = compute_closeness(&src, &dst, &[], false).unwrap();
 
         assert_eq!(result.node_ids.len(), 3);
         // All nodes in triangle should have equal closeness
     }
 
+    #[test]
+    fn test_weighted_betweenness_and_closeness() {
+        // Triangle where the direct edge 1-3 is longer than the detour through 2
+        let (src, dst) = triangle_graph();
+        let weights = [1.0, 1.0, 5.0];
+        let score = |ids: &[i64], scores: &[f64], node: i64| {
+            scores[ids.iter().position(|&n| n == node).unwrap()]
+        };
+
+        let plain = compute_betweenness(&src, &dst, &[], false, false).unwrap();
+        let weighted = compute_betweenness(&src, &dst, &weights, false, false).unwrap();
+        assert_eq!(score(&plain.node_ids, &plain.centralities, 2), 0.0);
+        assert!(score(&weighted.node_ids, &weighted.centralities, 2) > 0.0);
+
+        let plain = compute_closeness(&src, &dst, &[], false).unwrap();
+        let weighted = compute_closeness(&src, &dst, &weights, false).unwrap();
+        assert!(
+            score(&weighted.node_ids, &weighted.centralities, 1)
+                < score(&plain.node_ids, &plain.centralities, 1)
+        );
+
+        assert!(compute_betweenness(&src, &dst, &[1.0], false, false).is_err());
+        assert!(compute_closeness(&src, &dst, &[1.0, -1.0, 1.0], false).is_err());
+    }
+
+    #[test]
+    fn test_directed_centralities() {
+        let score = |ids: &[i64], scores: &[f64], node: i64| {
+            scores[ids.iter().position(|&n| n == node).unwrap()]
+        };
+
+        // In the directed cycle 1 -> 2 -> 3 -> 1, going from 1 to 3 passes 2
+        let (src, dst) = triangle_graph();
+        let undirected = compute_betweenness(&src, &dst, &[], false, false).unwrap();
+        let directed = compute_betweenness(&src, &dst, &[], false, true).unwrap();
+        assert_eq!(
+            score(&undirected.node_ids, &undirected.centralities, 2),
+            0.0
+        );
+        assert!(score(&directed.node_ids, &directed.centralities, 2) > 0.0);
+
+        // In the path 1 -> 2 -> 3, node 2 reaches one node in either direction
+        let (src, dst) = (vec![1, 2], vec![2, 3]);
+        let undirected = compute_harmonic(&src, &dst, false).unwrap();
+        let directed = compute_harmonic(&src, &dst, true).unwrap();
+        assert!(
+            score(&directed.node_ids, &directed.centralities, 2)
+                < score(&undirected.node_ids, &undirected.centralities, 2)
+        );
+
+        let (src, dst) = triangle_graph();
+        assert_eq!(
+            compute_closeness(&src, &dst, &[], true)
+                .unwrap()
+                .node_ids
+                .len(),
+            3
+        );
+        assert_eq!(
+            compute_eigenvector(&src, &dst, 100, 1e-6, true)
+                .unwrap()
+                .node_ids
+                .len(),
+            3
+        );
+    }
+
     #[test]
     fn test_eigenvector() {
         let (src, dst) = triangle_graph();
-        let result = compute_eigenvector(&src, &dst, 100, 1e-6).unwrap();
+        let result = compute_eigenvector(&src, &dst, 100, 1e-6, false).unwrap();
 
         assert_eq!(result.node_ids.len(), 3);
         assert!(!result.centralities.is_empty());
     }
 
+    #[test]
+    fn test_eigenvector_weighted_normalization() {
+        // Star with a heavy spoke: the hub scores highest, then node 2
+        let src = vec![1, 1, 1];
+        let dst = vec![2, 3, 4];
+        let weights = vec![3.0, 1.0, 1.0];
+        let score = |r: &EigenvectorResult, node: i64| {
+            let i = r.node_ids.iter().position(|&n| n == node).unwrap();
+            r.centralities[i]
+        };
+
+        let run = |normalization| {
+            compute_eigenvector_weighted(&src, &dst, &weights, 1000, 1e-9, false, normalization)
+                .unwrap()
+        };
+        let l1 = run(EigenvectorNormalization::L1);
+        let l2 = run(EigenvectorNormalization::L2);
+        let max = run(EigenvectorNormalization::Max);
+        assert!((l1.centralities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((l2.centralities.iter().map(|c| c * c).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((score(&max, 1) - 1.0).abs() < 1e-9);
+        assert!(score(&l2, 1) > score(&l2, 2) && score(&l2, 2) > score(&l2, 3));
+        // Hub to spoke ratio is the leading eigenvalue sqrt(11) over the spoke weight
+        assert!((score(&max, 2) - 3.0 / 11f64.sqrt()).abs() < 1e-6);
+        assert!(l2.centralities.iter().all(|&c| c >= 0.0));
+    }
+
+    #[test]
+    fn test_eigenvector_sign_and_errors() {
+        let mut flipped = vec![-0.6, -0.8, 0.0];
+        normalize_eigenvector(&mut flipped, EigenvectorNormalization::L2);
+        assert_eq!(flipped, vec![0.6, 0.8, 0.0]);
+        assert!(flipped[2].is_sign_positive());
+
+        let mut balanced = vec![1.0, -2.0, 1.0];
+        normalize_eigenvector(&mut balanced, EigenvectorNormalization::Max);
+        assert_eq!(balanced, vec![-0.5, 1.0, -0.5]);
+
+        // A directed cycle spreads the score evenly
+        let result = compute_eigenvector_weighted(
+            &[1, 2, 3],
+            &[2, 3, 1],
+            &[],
+            100,
+            1e-6,
+            true,
+            EigenvectorNormalization::L1,
+        )
+        .unwrap();
+        assert!(result
+            .centralities
+            .iter()
+            .all(|&c| (c - 1.0 / 3.0).abs() < 1e-9));
+
+        let (src, dst) = triangle_graph();
+        let l2 = EigenvectorNormalization::L2;
+        assert!(compute_eigenvector_weighted(&src, &dst, &[], 100, 0.0, false, l2).is_err());
+        assert!(compute_eigenvector_weighted(&src, &dst, &[1.0], 100, 1e-6, false, l2).is_err());
+        assert!(compute_eigenvector_weighted(&[], &[], &[], 100, 1e-6, false, l2).is_err());
+        assert!("l3".parse::<EigenvectorNormalization>().is_err());
+        assert_eq!(
+            "MAX".parse::<EigenvectorNormalization>().unwrap(),
+            EigenvectorNormalization::Max
+        );
+    }
+
     #[test]
     fn test_katz() {
         let (src, dst) = triangle_graph();
@@ -771,10 +1454,34 @@ mod tests {
         assert!(!result.centralities.is_empty());
     }
 
+    #[test]
+    fn test_spectral_radius_and_katz_alpha() {
+        let (src, dst) = triangle_graph();
+        let radius = estimate_spectral_radius(&src, &dst, &[]).unwrap();
+        assert!((radius - 2.0).abs() < 1e-6);
+
+        // Star with spoke weights 3, 1, 1 has lambda_max = sqrt(11)
+        let radius = estimate_spectral_radius(&[1, 1, 1], &[2, 3, 4], &[3.0, 1.0, 1.0]).unwrap();
+        assert!((radius - 11f64.sqrt()).abs() < 1e-6);
+
+        // A triangle next to a single edge takes the triangle's radius
+        let radius = estimate_spectral_radius(&[1, 2, 3, 7], &[2, 3, 1, 8], &[]).unwrap();
+        assert!((radius - 2.0).abs() < 1e-6);
+
+        // Path on six nodes has lambda_max = 2 cos(pi / 7)
+        let radius = estimate_spectral_radius(&[1, 2, 3, 4, 5], &[2, 3, 4, 5, 6], &[]).unwrap();
+        assert!((radius - 2.0 * (std::f64::consts::PI / 7.0).cos()).abs() < 1e-6);
+
+        let err = compute_katz(&src, &dst, 0.5, 100, 1e-6).unwrap_err();
+        assert!(err.to_string().contains("1 / lambda_max = 0.5"));
+        assert!(compute_katz(&src, &dst, f64::NAN, 100, 1e-6).is_err());
+        assert!(check_katz_alpha(&src, &dst, &[], 0.45).is_ok());
+    }
+
     #[test]
     fn test_harmonic() {
         let (src, dst) = triangle_graph();
-        let result = compute_harmonic(&src, &dst).unwrap();
+        let result = compute_harmonic(&src, &dst, false).unwrap();
 
         assert_eq!(result.node_ids.len(), 3);
         assert!(!result.centralities.is_empty());
@@ -849,4 +1556,192 @@ mod tests {
         let result = compute_laplacian(&[], &[]).unwrap();
         assert!(result.node_ids.is_empty());
     }
+
+    #[test]
+    fn test_closeness_cutoff_path() {
+        // Path: 1-2-3
+        let result = compute_closeness_cutoff(&[1, 2], &[2, 3], &[], None).unwrap();
+        let idx = result.node_ids.iter().position(|&n| n == 2).unwrap();
+        assert!((result.centralities[idx] - 1.0).abs() < 1e-9);
+
+        // With cutoff 1, node 1 only reaches node 2
+        let result = compute_closeness_cutoff(&[1, 2], &[2, 3], &[], Some(1.0)).unwrap();
+        let idx = result.node_ids.iter().position(|&n| n == 1).unwrap();
+        assert!((result.centralities[idx] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_harmonic_cutoff_weighted() {
+        let result = compute_harmonic_cutoff(&[1, 2], &[2, 3], &[2.0, 2.0], None).unwrap();
+        let idx = result.node_ids.iter().position(|&n| n == 1).unwrap();
+        // 1/2 + 1/4
+        assert!((result.centralities[idx] - 0.75).abs() < 1e-9);
+
+        let result = compute_harmonic_cutoff(&[1, 2], &[2, 3], &[2.0, 2.0], Some(2.0)).unwrap();
+        let idx = result.node_ids.iter().position(|&n| n == 1).unwrap();
+        assert!((result.centralities[idx] - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_betweenness_cutoff_star() {
+        let (src, dst) = star_graph();
+        let result = compute_betweenness_cutoff(&src, &dst, &[], false, None).unwrap();
+        let center = result.node_ids.iter().position(|&n| n == 1).unwrap();
+        // Three leaf pairs all pass through the center
+        assert!((result.centralities[center] - 3.0).abs() < 1e-9);
+
+        let result = compute_betweenness_cutoff(&src, &dst, &[], false, Some(1.0)).unwrap();
+        let center = result.node_ids.iter().position(|&n| n == 1).unwrap();
+        assert!(result.centralities[center].abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_closeness_and_harmonic_to_targets() {
+        // Path 1-2-3-4 with targets 1 and 4
+        let (src, dst) = (vec![1, 2, 3], vec![2, 3, 4]);
+        let closeness = compute_closeness_to_targets(&src, &dst, &[], &[1, 4, 4], None).unwrap();
+        let harmonic = compute_harmonic_to_targets(&src, &dst, &[], &[1, 4], None).unwrap();
+        let at = |ids: &[i64], node: i64| ids.iter().position(|&id| id == node).unwrap();
+
+        // Node 2 is 1 away from target 1 and 2 away from target 4
+        assert!((closeness.centralities[at(&closeness.node_ids, 2)] - 2.0 / 3.0).abs() < 1e-12);
+        assert!((harmonic.centralities[at(&harmonic.node_ids, 2)] - 1.5).abs() < 1e-12);
+        // A target does not count its distance to itself
+        assert!((closeness.centralities[at(&closeness.node_ids, 1)] - 1.0 / 3.0).abs() < 1e-12);
+
+        // With a cutoff of 1, node 2 only reaches target 1
+        let bounded = compute_closeness_to_targets(&src, &dst, &[], &[1, 4], Some(1.0)).unwrap();
+        assert_eq!(bounded.centralities[at(&bounded.node_ids, 2)], 1.0);
+
+        assert!(compute_closeness_to_targets(&src, &dst, &[], &[99], None).is_err());
+        assert!(compute_harmonic_to_targets(&src, &dst, &[], &[], None).is_err());
+    }
+
+    #[test]
+    fn test_closeness_and_harmonic_general_directed() {
+        // Directed path 1 -> 2 -> 3 -> 4
+        let (src, dst) = (vec![1, 2, 3], vec![2, 3, 4]);
+        let at = |ids: &[i64], node: i64| ids.iter().position(|&id| id == node).unwrap();
+
+        // Node 1 reaches 2, 3 and 4 at total distance 6, node 4 reaches nothing
+        let all = compute_closeness_general(&src, &dst, &[], true, &[], None).unwrap();
+        assert!((all.centralities[at(&all.node_ids, 1)] - 0.5).abs() < 1e-12);
+        assert_eq!(all.centralities[at(&all.node_ids, 4)], 0.0);
+
+        // Distances to target 4 follow the edges, and node 1 is beyond the cutoff
+        let closeness = compute_closeness_general(&src, &dst, &[], true, &[4], Some(2.0)).unwrap();
+        let harmonic = compute_harmonic_general(&src, &dst, &[], true, &[4], Some(2.0)).unwrap();
+        for (node, expected) in [(1, 0.0), (2, 0.5), (3, 1.0), (4, 0.0)] {
+            assert_eq!(
+                closeness.centralities[at(&closeness.node_ids, node)],
+                expected
+            );
+            assert_eq!(
+                harmonic.centralities[at(&harmonic.node_ids, node)],
+                expected
+            );
+        }
+
+        // No node can reach target 1 against the edge direction
+        let upstream = compute_harmonic_general(&src, &dst, &[], true, &[1], None).unwrap();
+        assert!(upstream.centralities.iter().all(|&c| c == 0.0));
+    }
+
+    #[test]
+    fn test_top_k_indices() {
+        let scores = [0.2, f64::NAN, 0.9, 0.5, 0.9];
+        assert_eq!(top_k_indices(&scores, 3), vec![2, 4, 3]);
+        assert_eq!(top_k_indices(&scores, 10), vec![2, 4, 3, 0, 1]);
+        assert!(top_k_indices(&scores, 0).is_empty());
+    }
+
+    #[test]
+    fn test_min_value_indices() {
+        let scores = [0.2, f64::NAN, 0.9, 0.5];
+        assert_eq!(min_value_indices(&scores, 0.5), vec![2, 3]);
+        assert_eq!(min_value_indices(&scores, f64::NEG_INFINITY), vec![0, 2, 3]);
+        assert!(min_value_indices(&scores, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_betweenness_general_directed_cycle() {
+        // Directed cycle 1->2->3->1: each node lies on exactly one shortest path
+        let result = compute_betweenness_general(
+            &[1, 2, 3],
+            &[2, 3, 1],
+            &[],
+            true,
+            false,
+            ParallelEdges::Min,
+            None,
+        )
+        .unwrap();
+        for c in &result.centralities {
+            assert!((c - 1.0).abs() < 1e-9);
+        }
+
+        let undirected = compute_betweenness_general(
+            &[1, 2, 3],
+            &[2, 3, 1],
+            &[],
+            false,
+            false,
+            ParallelEdges::Min,
+            None,
+        )
+        .unwrap();
+        assert!(undirected.centralities.iter().all(|c| c.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_betweenness_general_parallel_edges() {
+        // Square 1-2-3-4-1 where 1-2 is doubled (once in reverse)
+        let src = [1, 2, 2, 3, 4];
+        let dst = [2, 1, 3, 4, 1];
+        let score = |parallel| {
+            let r =
+                compute_betweenness_general(&src, &dst, &[], false, false, parallel, None).unwrap();
+            let at =
+                |node: i64| r.centralities[r.node_ids.iter().position(|&n| n == node).unwrap()];
+            (at(1), at(2), at(3), at(4))
+        };
+        let (b1, b2, b3, b4) = score(ParallelEdges::Min);
+        for b in [b1, b2, b3, b4] {
+            assert!((b - 0.5).abs() < 1e-9);
+        }
+        // Two of the three shortest paths for pairs (1, 3) and (2, 4) use the double edge
+        let (b1, b2, b3, b4) = score(ParallelEdges::Count);
+        assert!((b1 - 2.0 / 3.0).abs() < 1e-9);
+        assert!((b2 - 2.0 / 3.0).abs() < 1e-9);
+        assert!((b3 - 1.0 / 3.0).abs() < 1e-9);
+        assert!((b4 - 1.0 / 3.0).abs() < 1e-9);
+        assert!("sum".parse::<ParallelEdges>().is_err());
+    }
+
+    #[test]
+    fn test_betweenness_scaling_recovers_raw_scores() {
+        // Path 1-2-3-4
+        let (src, dst) = ([1, 2, 3], [2, 3, 4]);
+        let raw =
+            compute_betweenness_general(&src, &dst, &[], false, false, ParallelEdges::Min, None)
+                .unwrap();
+        let norm =
+            compute_betweenness_general(&src, &dst, &[], false, true, ParallelEdges::Min, None)
+                .unwrap();
+        let scaling = betweenness_scaling(4, false, true);
+        assert_eq!(scaling.normalizer, 3.0);
+        for (r, n) in raw.centralities.iter().zip(&norm.centralities) {
+            assert!((r - n * scaling.normalizer).abs() < 1e-9);
+        }
+
+        assert_eq!(betweenness_scaling(4, true, true).normalizer, 6.0);
+        assert_eq!(betweenness_scaling(4, true, false).normalizer, 1.0);
+        assert_eq!(betweenness_scaling(2, false, true).normalizer, 1.0);
+    }
+
+    #[test]
+    fn test_cutoff_rejects_negative_weights() {
+        let result = compute_closeness_cutoff(&[1, 2], &[2, 3], &[1.0, -1.0], None);
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,306 @@
+//! Graph coarsening module.
+//!
+//! Multilevel coarsening by edge matching, which shrinks a graph level by level
+//! while keeping track of which fine nodes each coarse node stands for.
+
+use serde::{Deserialize, Serialize};
+
+use super::sbm::SplitMix64;
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+
+/// How nodes are paired up at each coarsening level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoarsenMethod {
+    /// Pair each node with the unmatched neighbor behind its heaviest edge.
+    HeavyEdge,
+    /// Pair each node with a random unmatched neighbor.
+    Random,
+}
+
+impl FromStr for CoarsenMethod {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "heavy_edge" | "heavy-edge" | "hem" => Ok(CoarsenMethod::HeavyEdge),
+            "random" => Ok(CoarsenMethod::Random),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown coarsening method '{}', expected 'heavy_edge' or 'random'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Coarse graphs and node mappings for every coarsening level.
+///
+/// Row `i` of the edge columns is an edge of the level `edge_levels[i]` graph,
+/// and row `j` of the mapping columns says that node `fine_nodes[j]` of level
+/// `map_levels[j] - 1` became node `coarse_nodes[j]` of level `map_levels[j]`.
+/// Level 0 is the input graph. A coarse node takes the smallest ID of the
+/// nodes merged into it, so it also carries the smallest input node ID it
+/// stands for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoarsenResult {
+    /// Number of levels built, which is smaller than requested when no edges
+    /// are left to contract
+    pub levels: usize,
+    pub edge_levels: Vec<i64>,
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    pub weights: Vec<f64>,
+    pub map_levels: Vec<i64>,
+    pub fine_nodes: Vec<i64>,
+    pub coarse_nodes: Vec<i64>,
+}
+
+/// Undirected graph over dense indices with merged parallel edges.
+struct LevelGraph {
+    nodes: Vec<i64>,
+    neighbors: Vec<Vec<(usize, f64)>>,
+}
+
+impl LevelGraph {
+    /// Builds the graph from `(u, v, weight)` rows over indices into `nodes`,
+    /// summing the weights of parallel edges and dropping self-loops.
+    fn new(nodes: NodeIndex, edges: impl Iterator<Item = (usize, usize, f64)>) -> Self {
+        let mut merged: HashMap<(usize, usize), f64> = HashMap::new();
+        for (a, b, w) in edges {
+            if a != b {
+                *merged.entry((a.min(b), a.max(b))).or_insert(0.0) += w;
+            }
+        }
+        let mut neighbors = vec![Vec::new(); nodes.len()];
+        for (&(a, b), &w) in &merged {
+            neighbors[a].push((b, w));
+            neighbors[b].push((a, w));
+        }
+        // Sort so matching does not depend on hash order
+        for list in &mut neighbors {
+            list.sort_unstable_by_key(|&(v, _)| v);
+        }
+        LevelGraph {
+            nodes: nodes.node_ids,
+            neighbors,
+        }
+    }
+
+    /// Pairs up nodes and returns each node's partner, or itself when unmatched.
+    fn match_nodes(&self, method: CoarsenMethod, rng: &mut SplitMix64) -> Vec<usize> {
+        let n = self.nodes.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            order.swap(i, rng.next_below(i + 1));
+        }
+        let mut partner: Vec<Option<usize>> = vec![None; n];
+        for u in order {
+            if partner[u].is_some() {
+                continue;
+            }
+            let free = self.neighbors[u]
+                .iter()
+                .filter(|&&(v, _)| partner[v].is_none());
+            let chosen = match method {
+                // The first of several equally heavy edges wins
+                CoarsenMethod::HeavyEdge => free
+                    .fold(None, |best: Option<(usize, f64)>, &(v, w)| match best {
+                        Some((_, bw)) if bw >= w => best,
+                        _ => Some((v, w)),
+                    })
+                    .map(|(v, _)| v),
+                CoarsenMethod::Random => {
+                    let candidates: Vec<usize> = free.map(|&(v, _)| v).collect();
+                    (!candidates.is_empty()).then(|| candidates[rng.next_below(candidates.len())])
+                }
+            };
+            if let Some(v) = chosen {
+                partner[u] = Some(v);
+                partner[v] = Some(u);
+            }
+        }
+        partner
+            .into_iter()
+            .enumerate()
+            .map(|(u, p)| p.unwrap_or(u))
+            .collect()
+    }
+}
+
+/// Coarsen a graph for up to `levels` levels by edge matching.
+///
+/// Each level pairs up adjacent nodes with `method`, visiting nodes in an
+/// order shuffled by `seed`, and merges every pair into one coarse node. Edges
+/// between merged nodes are summed into one coarse edge, and edges inside a
+/// pair disappear. The graph is treated as undirected, self-loops are
+/// ignored, and empty weights mean unit weights. Coarsening stops early once
+/// no edges are left.
+pub fn coarsen_graph(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    levels: usize,
+    method: CoarsenMethod,
+    seed: Option<u64>,
+) -> Result<CoarsenResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights must be empty or same length as edges".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite and non-negative, got {}",
+            w
+        )));
+    }
+    if levels == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "levels must be positive".to_string(),
+        ));
+    }
+
+    let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let mut rng = SplitMix64(seed);
+    let nodes = NodeIndex::ascending(src, dst);
+    let edges: Vec<(usize, usize, f64)> = (0..src.len())
+        .map(|i| {
+            let w = weights.get(i).copied().unwrap_or(1.0);
+            (nodes.of(src[i]), nodes.of(dst[i]), w)
+        })
+        .collect();
+    let mut graph = LevelGraph::new(nodes, edges.into_iter());
+
+    let mut result = CoarsenResult::default();
+    for level in 1..=levels {
+        if graph.neighbors.iter().all(Vec::is_empty) {
+            break;
+        }
+        let partner = graph.match_nodes(method, &mut rng);
+        let coarse: Vec<i64> = (0..graph.nodes.len())
+            .map(|u| graph.nodes[u].min(graph.nodes[partner[u]]))
+            .collect();
+        for (&fine, &c) in graph.nodes.iter().zip(&coarse) {
+            result.map_levels.push(level as i64);
+            result.fine_nodes.push(fine);
+            result.coarse_nodes.push(c);
+        }
+
+        let nodes = NodeIndex::ascending(&coarse, &[]);
+        let coarse_of: Vec<usize> = coarse.iter().map(|&c| nodes.of(c)).collect();
+        let coarse_of = &coarse_of;
+        let edges = graph.neighbors.iter().enumerate().flat_map(|(u, list)| {
+            list.iter()
+                .filter(move |&&(v, _)| u < v)
+                .map(move |&(v, w)| (coarse_of[u], coarse_of[v], w))
+        });
+        let next = LevelGraph::new(nodes, edges);
+        for (a, list) in next.neighbors.iter().enumerate() {
+            for &(b, w) in list.iter().filter(|&&(b, _)| a < b) {
+                result.edge_levels.push(level as i64);
+                result.src.push(next.nodes[a]);
+                result.dst.push(next.nodes[b]);
+                result.weights.push(w);
+            }
+        }
+        result.levels = level;
+        graph = next;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coarse_of(result: &CoarsenResult, level: i64, node: i64) -> i64 {
+        (0..result.fine_nodes.len())
+            .find(|&j| result.map_levels[j] == level && result.fine_nodes[j] == node)
+            .map(|j| result.coarse_nodes[j])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_heavy_edge_merges_heavy_pairs() {
+        // Two heavy pairs joined by light edges
+        let src = vec![1, 3, 1, 2];
+        let dst = vec![2, 4, 3, 4];
+        let weights = vec![10.0, 10.0, 1.0, 1.0];
+
+        let result =
+            coarsen_graph(&src, &dst, &weights, 1, CoarsenMethod::HeavyEdge, Some(7)).unwrap();
+        assert_eq!(result.levels, 1);
+        assert_eq!(result.fine_nodes, vec![1, 2, 3, 4]);
+        assert_eq!(result.coarse_nodes, vec![1, 1, 3, 3]);
+        assert_eq!(result.src, vec![1]);
+        assert_eq!(result.dst, vec![3]);
+        assert_eq!(result.weights, vec![2.0]);
+    }
+
+    #[test]
+    fn test_coarsening_stops_without_edges() {
+        let src = vec![1, 3, 1, 2];
+        let dst = vec![2, 4, 3, 4];
+        let weights = vec![10.0, 10.0, 1.0, 1.0];
+
+        let result =
+            coarsen_graph(&src, &dst, &weights, 5, CoarsenMethod::HeavyEdge, Some(1)).unwrap();
+        assert_eq!(result.levels, 2);
+        assert_eq!(coarse_of(&result, 2, 1), 1);
+        assert_eq!(coarse_of(&result, 2, 3), 1);
+        assert!(result.edge_levels.iter().all(|&l| l == 1));
+    }
+
+    #[test]
+    fn test_random_matching_keeps_weight_and_is_seeded() {
+        // Path 1-2-3-4-5-6 with unit weights and a parallel edge
+        let src = vec![1, 2, 3, 4, 5, 1];
+        let dst = vec![2, 3, 4, 5, 6, 2];
+
+        let a = coarsen_graph(&src, &dst, &[], 3, CoarsenMethod::Random, Some(42)).unwrap();
+        let b = coarsen_graph(&src, &dst, &[], 3, CoarsenMethod::Random, Some(42)).unwrap();
+        assert_eq!(a.coarse_nodes, b.coarse_nodes);
+        assert_eq!(a.src, b.src);
+
+        // Every coarse node is the smallest of the nodes merged into it
+        for (&fine, &coarse) in a.fine_nodes.iter().zip(&a.coarse_nodes) {
+            assert!(coarse <= fine);
+        }
+        // Edges inside merged pairs drop out of level 1
+        let level1: f64 = (0..a.src.len())
+            .filter(|&i| a.edge_levels[i] == 1)
+            .map(|i| a.weights[i])
+            .sum();
+        assert!(level1 < 6.0);
+    }
+
+    #[test]
+    fn test_coarsen_rejects_bad_input() {
+        let src = vec![1, 2];
+        let dst = vec![2, 3];
+        let method = CoarsenMethod::HeavyEdge;
+        assert!(coarsen_graph(&src, &dst, &[], 0, method, Some(1)).is_err());
+        assert!(coarsen_graph(&src, &dst, &[1.0], 1, method, Some(1)).is_err());
+        assert!(coarsen_graph(&src, &dst, &[1.0, f64::NAN], 1, method, Some(1)).is_err());
+        assert!(coarsen_graph(&[], &[], &[], 1, method, Some(1)).is_err());
+        assert!("metis".parse::<CoarsenMethod>().is_err());
+        assert_eq!(
+            "Heavy_Edge".parse::<CoarsenMethod>().unwrap(),
+            CoarsenMethod::HeavyEdge
+        );
+    }
+}
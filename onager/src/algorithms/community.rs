@@ -1,6 +1,8 @@
 //! Community detection algorithms module.
 //!
-//! Louvain, Connected Components, Label Propagation, Girvan-Newman, Spectral Clustering, Infomap.
+//! Louvain, Connected Components, Label Propagation, Girvan-Newman, Spectral Clustering, Infomap,
+//! similarity-threshold components, Fluid Communities, greedy modularity, community bridges,
+//! partition modularity, partition comparison.
 
 use graphina::community::connected_components::connected_components;
 use graphina::community::girvan_newman::girvan_newman;
@@ -9,11 +11,22 @@ use graphina::community::label_propagation::label_propagation;
 use graphina::community::louvain::louvain;
 use graphina::community::spectral::spectral_clustering;
 use graphina::core::types::{Graph, NodeId};
+use serde::{Deserialize, Serialize};
 
+use super::flow::simple_undirected;
+use super::mst::find_root;
+use super::sbm::SplitMix64;
+use crate::builder::{GraphBuilder, NodeIndex};
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 /// Result of Louvain community detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LouvainResult {
     pub node_ids: Vec<i64>,
     pub community_ids: Vec<i64>,
@@ -32,28 +45,11 @@ pub fn compute_louvain(src: &[i64], dst: &[i64], seed: Option<u64>) -> Result<Lo
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let communities = louvain(&graph, seed).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let communities =
+        louvain(&built.graph, seed).map_err(|e| OnagerError::GraphError(e.to_string()))?;
     let mut result_nodes = Vec::new();
     let mut result_comms = Vec::new();
     for (comm_id, community) in communities.iter().enumerate() {
@@ -71,6 +67,7 @@ pub fn compute_louvain(src: &[i64], dst: &[i64], seed: Option<u64>) -> Result<Lo
 }
 
 /// Result of connected components computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectedComponentsResult {
     pub node_ids: Vec<i64>,
     pub component_ids: Vec<i64>,
@@ -89,28 +86,10 @@ pub fn compute_connected_components(src: &[i64], dst: &[i64]) -> Result<Connecte
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let components = connected_components(&graph);
+    let components = connected_components(&built.graph);
     let mut result_nodes = Vec::new();
     let mut result_comps = Vec::new();
     for (comp_id, component) in components.iter().enumerate() {
@@ -127,7 +106,237 @@ pub fn compute_connected_components(src: &[i64], dst: &[i64]) -> Result<Connecte
     })
 }
 
+/// Result of a single node's component lookup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentOfResult {
+    pub node_ids: Vec<i64>,
+}
+
+/// Find the members of one node's connected component.
+///
+/// Runs a breadth-first search from `node` with edges treated as undirected,
+/// so only that component is visited. Members are returned in visit order,
+/// starting with `node`.
+pub fn compute_component_of(src: &[i64], dst: &[i64], node: i64) -> Result<ComponentOfResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+
+    let mut adj: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (&s, &d) in src.iter().zip(dst) {
+        adj.entry(s).or_default().push(d);
+        adj.entry(d).or_default().push(s);
+    }
+    if !adj.contains_key(&node) {
+        return Err(OnagerError::NodeNotFound(node));
+    }
+
+    let mut seen: HashSet<i64> = HashSet::from([node]);
+    let mut node_ids = vec![node];
+    let mut head = 0;
+    while head < node_ids.len() {
+        let current = node_ids[head];
+        head += 1;
+        if let Some(neighbors) = adj.get(&current) {
+            for &next in neighbors {
+                if seen.insert(next) {
+                    node_ids.push(next);
+                }
+            }
+        }
+    }
+    Ok(ComponentOfResult { node_ids })
+}
+
+/// How edge direction is read when computing components.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComponentMode {
+    /// Edges have no direction.
+    #[default]
+    Undirected,
+    /// Edges are directed, and two nodes share a component when they are
+    /// joined by a path that ignores edge direction.
+    Weak,
+    /// Edges are directed, and two nodes share a component when each can
+    /// reach the other.
+    Strong,
+}
+
+impl FromStr for ComponentMode {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "undirected" => Ok(ComponentMode::Undirected),
+            "weak" => Ok(ComponentMode::Weak),
+            "strong" => Ok(ComponentMode::Strong),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown component mode '{}', expected 'undirected', 'weak', or 'strong'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Compute connected components with the given edge direction mode.
+///
+/// Weak components of a directed graph are the components of the same graph
+/// with direction ignored, so `Weak` and `Undirected` give the same partition.
+pub fn compute_connected_components_with_mode(
+    src: &[i64],
+    dst: &[i64],
+    mode: ComponentMode,
+) -> Result<ConnectedComponentsResult> {
+    match mode {
+        ComponentMode::Undirected => compute_connected_components(src, dst),
+        ComponentMode::Weak => compute_weakly_connected_components(src, dst),
+        ComponentMode::Strong => compute_strongly_connected_components(src, dst),
+    }
+}
+
+/// Compute weakly connected components of a directed edge list.
+///
+/// Two nodes share a component when they are joined by a path that ignores
+/// edge direction, so reversed edges do not need to be added to the input.
+/// Nodes are returned in the order they first appear in the edges, and
+/// components are numbered from 0 in the order their first node appears.
+pub fn compute_weakly_connected_components(
+    src: &[i64],
+    dst: &[i64],
+) -> Result<ConnectedComponentsResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+
+    let nodes = NodeIndex::in_edge_order(src, dst);
+    let mut parent: Vec<usize> = (0..nodes.len()).collect();
+    for (&s, &d) in src.iter().zip(dst) {
+        let (u, v) = (nodes.of(s), nodes.of(d));
+        let (ru, rv) = (find_root(&mut parent, u), find_root(&mut parent, v));
+        // The root with the earlier first appearance names the component
+        if ru < rv {
+            parent[rv] = ru;
+        } else if rv < ru {
+            parent[ru] = rv;
+        }
+    }
+
+    let mut component_of_root: HashMap<usize, i64> = HashMap::new();
+    let mut component_ids = Vec::with_capacity(nodes.len());
+    for i in 0..nodes.len() {
+        let root = find_root(&mut parent, i);
+        let next = component_of_root.len() as i64;
+        component_ids.push(*component_of_root.entry(root).or_insert(next));
+    }
+    Ok(ConnectedComponentsResult {
+        node_ids: nodes.node_ids,
+        component_ids,
+    })
+}
+
+/// Compute strongly connected components with an iterative Tarjan's algorithm.
+///
+/// Components are numbered in the order Tarjan's algorithm completes them,
+/// which is a reverse topological order of the condensation.
+fn compute_strongly_connected_components(
+    src: &[i64],
+    dst: &[i64],
+) -> Result<ConnectedComponentsResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let n = nodes.len();
+    let mut adj: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (&s, &d) in src.iter().zip(dst) {
+        adj[nodes.of(s)].push(nodes.of(d));
+    }
+
+    const UNVISITED: usize = usize::MAX;
+    let mut order = vec![UNVISITED; n];
+    let mut low = vec![0usize; n];
+    let mut on_stack = vec![false; n];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut next_order = 0usize;
+    let mut next_component = 0i64;
+    let mut result_nodes = Vec::with_capacity(n);
+    let mut result_comps = Vec::with_capacity(n);
+
+    for root in 0..n {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        // Each frame holds a node and the position of its next edge to visit.
+        let mut frames: Vec<(usize, usize)> = vec![(root, 0)];
+        order[root] = next_order;
+        low[root] = next_order;
+        next_order += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (v, ref mut edge)) = frames.last_mut() {
+            if let Some(&w) = adj[v].get(*edge) {
+                *edge += 1;
+                if order[w] == UNVISITED {
+                    order[w] = next_order;
+                    low[w] = next_order;
+                    next_order += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    frames.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(order[w]);
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == order[v] {
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    result_nodes.push(nodes.node_ids[w]);
+                    result_comps.push(next_component);
+                    if w == v {
+                        break;
+                    }
+                }
+                next_component += 1;
+            }
+        }
+    }
+
+    Ok(ConnectedComponentsResult {
+        node_ids: result_nodes,
+        component_ids: result_comps,
+    })
+}
+
 /// Result of label propagation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LabelPropagationResult {
     pub node_ids: Vec<i64>,
     pub labels: Vec<i64>,
@@ -146,29 +355,12 @@ pub fn compute_label_propagation(src: &[i64], dst: &[i64]) -> Result<LabelPropag
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let labels_vec =
-        label_propagation(&graph, 100, None).map_err(|e| OnagerError::GraphError(e.to_string()))?;
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
-    let node_list: Vec<NodeId> = graph.nodes().map(|(id, _)| id).collect();
+    let labels_vec = label_propagation(&built.graph, 100, None)
+        .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let reverse_map = built.reverse_map();
+    let node_list: Vec<NodeId> = built.graph.nodes().map(|(id, _)| id).collect();
 
     let mut node_ids = Vec::with_capacity(labels_vec.len());
     let mut labels = Vec::with_capacity(labels_vec.len());
@@ -182,6 +374,7 @@ pub fn compute_label_propagation(src: &[i64], dst: &[i64]) -> Result<LabelPropag
 }
 
 /// Result of Girvan-Newman community detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GirvanNewmanResult {
     pub node_ids: Vec<i64>,
     pub community_ids: Vec<i64>,
@@ -210,28 +403,10 @@ pub fn compute_girvan_newman(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let communities = girvan_newman(&graph, target_communities as usize)
+    let communities = girvan_newman(&built.graph, target_communities as usize)
         .map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
     let mut result_nodes = Vec::new();
@@ -250,7 +425,237 @@ pub fn compute_girvan_newman(
     })
 }
 
+/// When Girvan-Newman stops removing edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GirvanNewmanStop {
+    /// Stop once the graph falls apart into this many communities.
+    Communities(usize),
+    /// Remove every edge and keep the partition with the highest modularity.
+    MaxModularity,
+}
+
+/// Result of Girvan-Newman with a stopping rule and limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GirvanNewmanLimitedResult {
+    pub node_ids: Vec<i64>,
+    pub community_ids: Vec<i64>,
+    /// Modularity of the returned partition on the original graph.
+    pub modularity: f64,
+    /// Number of edges removed.
+    pub iterations: usize,
+    /// Whether `max_iterations` or `time_budget` ended the run before the
+    /// stopping rule did.
+    pub stopped_early: bool,
+}
+
+/// Adds the undirected edge betweenness from each of `sources` to `scores`
+/// with Brandes' algorithm, skipping removed edges.
+///
+/// `neighbors` holds `(neighbor, edge index)` pairs. Each shortest path is
+/// counted once from each end, which does not change which edge is highest.
+fn accumulate_edge_betweenness(
+    neighbors: &[Vec<(usize, usize)>],
+    removed: &[bool],
+    sources: impl Iterator<Item = usize>,
+    scores: &mut [f64],
+) {
+    let n = neighbors.len();
+    let mut dist = vec![usize::MAX; n];
+    let mut sigma = vec![0.0; n];
+    let mut delta = vec![0.0; n];
+    let mut preds: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    let mut order: Vec<usize> = Vec::new();
+    let mut queue = VecDeque::new();
+    for s in sources {
+        for &u in &order {
+            dist[u] = usize::MAX;
+            sigma[u] = 0.0;
+            delta[u] = 0.0;
+            preds[u].clear();
+        }
+        order.clear();
+        dist[s] = 0;
+        sigma[s] = 1.0;
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &(v, e) in &neighbors[u] {
+                if removed[e] {
+                    continue;
+                }
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+                if dist[v] == dist[u] + 1 {
+                    sigma[v] += sigma[u];
+                    preds[v].push((u, e));
+                }
+            }
+        }
+        for &w in order.iter().rev() {
+            for &(v, e) in &preds[w] {
+                let credit = sigma[v] / sigma[w] * (1.0 + delta[w]);
+                scores[e] += credit;
+                delta[v] += credit;
+            }
+        }
+    }
+}
+
+/// Labels the components left after removing edges, returning the label of
+/// each node and the number of components.
+fn remaining_components(
+    neighbors: &[Vec<(usize, usize)>],
+    removed: &[bool],
+) -> (Vec<usize>, usize) {
+    let mut labels = vec![usize::MAX; neighbors.len()];
+    let mut count = 0;
+    let mut stack = Vec::new();
+    for start in 0..neighbors.len() {
+        if labels[start] != usize::MAX {
+            continue;
+        }
+        labels[start] = count;
+        stack.push(start);
+        while let Some(u) = stack.pop() {
+            for &(v, e) in &neighbors[u] {
+                if !removed[e] && labels[v] == usize::MAX {
+                    labels[v] = count;
+                    stack.push(v);
+                }
+            }
+        }
+        count += 1;
+    }
+    (labels, count)
+}
+
+/// Compute Girvan-Newman community detection with a stopping rule and
+/// optional limits on the work done.
+///
+/// Each iteration removes the edge with the highest betweenness, with ties
+/// going to the edge seen first. Betweenness is only recomputed inside the
+/// component that lost the edge. The run ends when `stop` is met, when no
+/// edges are left, or when `max_iterations` edges have been removed or
+/// `time_budget` has passed. After a limit, `Communities` returns the current
+/// partition and `MaxModularity` the best one found so far. A time budget
+/// makes the result depend on machine speed. Repeated edges and self-loops
+/// are ignored.
+pub fn compute_girvan_newman_with_limits(
+    src: &[i64],
+    dst: &[i64],
+    stop: GirvanNewmanStop,
+    max_iterations: Option<usize>,
+    time_budget: Option<Duration>,
+) -> Result<GirvanNewmanLimitedResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if stop == GirvanNewmanStop::Communities(0) {
+        return Err(OnagerError::InvalidArgument(
+            "target_communities must be positive".to_string(),
+        ));
+    }
+    if max_iterations == Some(0) {
+        return Err(OnagerError::InvalidArgument(
+            "max_iterations must be positive".to_string(),
+        ));
+    }
+
+    let start = Instant::now();
+    let (node_ids, simple) = simple_neighbors(src, dst);
+    let n = node_ids.len();
+    let mut edges = Vec::new();
+    let mut neighbors = vec![Vec::new(); n];
+    for (u, adj) in simple.iter().enumerate() {
+        for &v in adj.iter().filter(|&&v| u < v) {
+            neighbors[u].push((v, edges.len()));
+            neighbors[v].push((u, edges.len()));
+            edges.push((u, v));
+        }
+    }
+
+    let mut removed = vec![false; edges.len()];
+    let mut scores = vec![0.0; edges.len()];
+    accumulate_edge_betweenness(&neighbors, &removed, 0..n, &mut scores);
+    let (mut labels, mut count) = remaining_components(&neighbors, &removed);
+    let mut best = (modularity(&simple, &labels), labels.clone());
+    let mut iterations = 0;
+    let mut stopped_early = false;
+
+    loop {
+        let reached = match stop {
+            GirvanNewmanStop::Communities(target) => count >= target,
+            GirvanNewmanStop::MaxModularity => false,
+        };
+        if reached || iterations == edges.len() {
+            break;
+        }
+        if max_iterations.is_some_and(|cap| iterations >= cap)
+            || time_budget.is_some_and(|budget| start.elapsed() >= budget)
+        {
+            stopped_early = true;
+            break;
+        }
+
+        let Some(cut) = (0..edges.len())
+            .filter(|&e| !removed[e])
+            .max_by(|&a, &b| scores[a].total_cmp(&scores[b]).then(b.cmp(&a)))
+        else {
+            break;
+        };
+        removed[cut] = true;
+        iterations += 1;
+
+        let (u, v) = edges[cut];
+        let (next_labels, next_count) = remaining_components(&neighbors, &removed);
+        let affected =
+            |x: usize| next_labels[x] == next_labels[u] || next_labels[x] == next_labels[v];
+        for (e, &(a, _)) in edges.iter().enumerate() {
+            if affected(a) {
+                scores[e] = 0.0;
+            }
+        }
+        accumulate_edge_betweenness(
+            &neighbors,
+            &removed,
+            (0..n).filter(|&x| affected(x)),
+            &mut scores,
+        );
+
+        if next_count > count && stop == GirvanNewmanStop::MaxModularity {
+            let q = modularity(&simple, &next_labels);
+            if q > best.0 {
+                best = (q, next_labels.clone());
+            }
+        }
+        labels = next_labels;
+        count = next_count;
+    }
+
+    let (q, labels) = match stop {
+        GirvanNewmanStop::Communities(_) => (modularity(&simple, &labels), labels),
+        GirvanNewmanStop::MaxModularity => best,
+    };
+    Ok(GirvanNewmanLimitedResult {
+        community_ids: relabel_by_appearance(&labels),
+        modularity: q,
+        iterations,
+        stopped_early,
+        node_ids,
+    })
+}
+
 /// Result of spectral clustering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SpectralClusteringResult {
     pub node_ids: Vec<i64>,
     pub community_ids: Vec<i64>,
@@ -280,29 +685,11 @@ pub fn compute_spectral_clustering(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let communities =
-        spectral_clustering(&graph, k, seed).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let communities = spectral_clustering(&built.graph, k, seed)
+        .map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
     let mut result_nodes = Vec::new();
     let mut result_comms = Vec::new();
@@ -321,6 +708,7 @@ pub fn compute_spectral_clustering(
 }
 
 /// Result of infomap community detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InfomapResult {
     pub node_ids: Vec<i64>,
     pub community_ids: Vec<i64>,
@@ -350,29 +738,12 @@ pub fn compute_infomap(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    let modules =
-        infomap(&graph, max_iter, seed).map_err(|e| OnagerError::GraphError(e.to_string()))?;
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
-    let node_list: Vec<NodeId> = graph.nodes().map(|(id, _)| id).collect();
+    let modules = infomap(&built.graph, max_iter, seed)
+        .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let reverse_map = built.reverse_map();
+    let node_list: Vec<NodeId> = built.graph.nodes().map(|(id, _)| id).collect();
 
     let mut node_ids = Vec::with_capacity(modules.len());
     let mut community_ids = Vec::with_capacity(modules.len());
@@ -388,6 +759,747 @@ pub fn compute_infomap(
     })
 }
 
+/// Result of similarity-threshold components, with one row per node.
+///
+/// Cluster statistics are repeated on every node of the cluster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarityComponentsResult {
+    pub node_ids: Vec<i64>,
+    /// Smallest node ID in the node's cluster.
+    pub cluster_ids: Vec<i64>,
+    pub cluster_sizes: Vec<i64>,
+    /// Lowest weight among edges inside the cluster, or NaN if it has none.
+    pub min_similarities: Vec<f64>,
+    /// Highest weight among edges inside the cluster, or NaN if it has none.
+    pub max_similarities: Vec<f64>,
+}
+
+/// Group nodes joined by edges with a similarity weight above `threshold`.
+///
+/// Edges at or below the threshold are not traversed, but they still count
+/// toward the minimum and maximum similarity of a cluster when both endpoints
+/// end up in it. Nodes are returned in ascending order.
+pub fn compute_similarity_components(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    threshold: f64,
+) -> Result<SimilarityComponentsResult> {
+    if src.len() != dst.len() || src.len() != weights.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and weights arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if threshold.is_nan() {
+        return Err(OnagerError::InvalidArgument(
+            "threshold must not be NaN".to_string(),
+        ));
+    }
+    if weights.iter().any(|w| w.is_nan()) {
+        return Err(OnagerError::InvalidArgument(
+            "weights must not be NaN".to_string(),
+        ));
+    }
+
+    let index = NodeIndex::ascending(src, dst);
+    let endpoints: Vec<(usize, usize)> = src
+        .iter()
+        .zip(dst)
+        .map(|(&s, &d)| (index.of(s), index.of(d)))
+        .collect();
+    let nodes = index.node_ids;
+
+    let mut parent: Vec<usize> = (0..nodes.len()).collect();
+    for (&(u, v), &w) in endpoints.iter().zip(weights) {
+        if w > threshold {
+            let ru = find_root(&mut parent, u);
+            let rv = find_root(&mut parent, v);
+            // Nodes are sorted, so the smaller index is the smaller node ID.
+            if ru < rv {
+                parent[rv] = ru;
+            } else if rv < ru {
+                parent[ru] = rv;
+            }
+        }
+    }
+    let roots: Vec<usize> = (0..nodes.len())
+        .map(|i| find_root(&mut parent, i))
+        .collect();
+
+    let mut sizes = vec![0i64; nodes.len()];
+    for &r in &roots {
+        sizes[r] += 1;
+    }
+    let mut min_sim = vec![f64::NAN; nodes.len()];
+    let mut max_sim = vec![f64::NAN; nodes.len()];
+    for (&(u, v), &w) in endpoints.iter().zip(weights) {
+        let r = roots[u];
+        if r == roots[v] {
+            // f64::min and f64::max ignore the NaN starting value.
+            min_sim[r] = min_sim[r].min(w);
+            max_sim[r] = max_sim[r].max(w);
+        }
+    }
+
+    Ok(SimilarityComponentsResult {
+        cluster_ids: roots.iter().map(|&r| nodes[r]).collect(),
+        cluster_sizes: roots.iter().map(|&r| sizes[r]).collect(),
+        min_similarities: roots.iter().map(|&r| min_sim[r]).collect(),
+        max_similarities: roots.iter().map(|&r| max_sim[r]).collect(),
+        node_ids: nodes,
+    })
+}
+
+/// Maximum number of sweeps over all nodes in Fluid Communities.
+const FLUID_MAX_SWEEPS: usize = 100;
+
+/// Distinct undirected neighbors of each node, without self-loops, with
+/// nodes in order of first appearance.
+fn simple_neighbors(src: &[i64], dst: &[i64]) -> (Vec<i64>, Vec<Vec<usize>>) {
+    let nodes = NodeIndex::new(src, dst);
+    let mut seen = HashSet::new();
+    let mut neighbors = vec![Vec::new(); nodes.len()];
+    for (&s, &d) in src.iter().zip(dst) {
+        let (u, v) = (nodes.of(s), nodes.of(d));
+        if u != v && seen.insert((u.min(v), u.max(v))) {
+            neighbors[u].push(v);
+            neighbors[v].push(u);
+        }
+    }
+    (nodes.node_ids, neighbors)
+}
+
+/// Renumbers community labels by order of first appearance.
+pub(crate) fn relabel_by_appearance(labels: &[usize]) -> Vec<i64> {
+    let mut ids: HashMap<usize, i64> = HashMap::new();
+    labels
+        .iter()
+        .map(|&label| {
+            let next = ids.len() as i64;
+            *ids.entry(label).or_insert(next)
+        })
+        .collect()
+}
+
+/// Newman-Girvan modularity of a partition of a simple undirected graph.
+///
+/// Returns 0 for a graph without edges.
+pub(crate) fn modularity(neighbors: &[Vec<usize>], communities: &[usize]) -> f64 {
+    let two_m: usize = neighbors.iter().map(Vec::len).sum();
+    if two_m == 0 {
+        return 0.0;
+    }
+    let mut internal: HashMap<usize, f64> = HashMap::new();
+    let mut degree: HashMap<usize, f64> = HashMap::new();
+    for (u, adj) in neighbors.iter().enumerate() {
+        let c = communities[u];
+        *degree.entry(c).or_insert(0.0) += adj.len() as f64;
+        let inside = adj.iter().filter(|&&v| communities[v] == c).count();
+        *internal.entry(c).or_insert(0.0) += inside as f64;
+    }
+    let two_m = two_m as f64;
+    degree
+        .iter()
+        .map(|(c, &d)| internal[c] / two_m - (d / two_m).powi(2))
+        .sum()
+}
+
+/// Result of Fluid Communities detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FluidCommunitiesResult {
+    pub node_ids: Vec<i64>,
+    pub community_ids: Vec<i64>,
+}
+
+/// Compute Fluid Communities (Parés et al., 2017) with exactly `k` communities.
+///
+/// Each community starts from one random node and spreads with a density of
+/// one over its size. Nodes join the community with the highest total density
+/// over themselves and their neighbors, with ties broken at random, until no
+/// node changes. The graph must be connected. Repeated edges and self-loops
+/// are ignored.
+pub fn compute_fluid_communities(
+    src: &[i64],
+    dst: &[i64],
+    k: usize,
+    seed: Option<u64>,
+) -> Result<FluidCommunitiesResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    let (node_ids, neighbors) = simple_neighbors(src, dst);
+    let n = node_ids.len();
+    if k == 0 || k > n {
+        return Err(OnagerError::InvalidArgument(format!(
+            "k must be in [1, {n}]"
+        )));
+    }
+    let mut reached = vec![false; n];
+    reached[0] = true;
+    let mut stack = vec![0];
+    while let Some(u) = stack.pop() {
+        for &v in &neighbors[u] {
+            if !reached[v] {
+                reached[v] = true;
+                stack.push(v);
+            }
+        }
+    }
+    if reached.contains(&false) {
+        return Err(OnagerError::InvalidArgument(
+            "Fluid Communities requires a connected graph".to_string(),
+        ));
+    }
+
+    let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let mut rng = SplitMix64(seed);
+    let mut order: Vec<usize> = (0..n).collect();
+    let shuffle = |order: &mut Vec<usize>, rng: &mut SplitMix64| {
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.next_below(i + 1));
+        }
+    };
+
+    // Seed each community on a distinct random node
+    shuffle(&mut order, &mut rng);
+    let mut community: Vec<Option<usize>> = vec![None; n];
+    let mut sizes = vec![1usize; k];
+    for (c, &u) in order.iter().take(k).enumerate() {
+        community[u] = Some(c);
+    }
+
+    let mut density = vec![0.0; k];
+    let mut best = Vec::with_capacity(k);
+    for _ in 0..FLUID_MAX_SWEEPS {
+        let mut changed = false;
+        shuffle(&mut order, &mut rng);
+        for &u in &order {
+            density.iter_mut().for_each(|d| *d = 0.0);
+            for c in std::iter::once(u)
+                .chain(neighbors[u].iter().copied())
+                .filter_map(|v| community[v])
+            {
+                density[c] += 1.0 / sizes[c] as f64;
+            }
+            let max = density.iter().copied().fold(0.0, f64::max);
+            if max <= 0.0 {
+                continue;
+            }
+            best.clear();
+            best.extend((0..k).filter(|&c| max - density[c] <= 1e-9 * max));
+            // Staying put wins ties, so settled nodes do not flip back and forth
+            if community[u].is_some_and(|c| best.contains(&c)) {
+                continue;
+            }
+            let next = best[rng.next_below(best.len())];
+            if let Some(prev) = community[u] {
+                sizes[prev] -= 1;
+            }
+            sizes[next] += 1;
+            community[u] = Some(next);
+            changed = true;
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    // Every node is reached on a connected graph, so this only guards the sweep cap.
+    let labels: Vec<usize> = community.iter().map(|c| c.unwrap_or(k)).collect();
+    Ok(FluidCommunitiesResult {
+        community_ids: relabel_by_appearance(&labels),
+        node_ids,
+    })
+}
+
+/// Result of greedy modularity community detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GreedyModularityResult {
+    pub node_ids: Vec<i64>,
+    pub community_ids: Vec<i64>,
+    /// Modularity of the returned partition.
+    pub modularity: f64,
+}
+
+/// Candidate merge of communities `i` and `j`, valid while neither has
+/// changed since it was queued.
+struct Merge {
+    gain: f64,
+    i: usize,
+    j: usize,
+    version_i: usize,
+    version_j: usize,
+}
+
+impl PartialEq for Merge {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Merge {}
+
+impl PartialOrd for Merge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Merge {
+    /// Larger gains first, then smaller community indices.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.gain
+            .total_cmp(&other.gain)
+            .then_with(|| (other.i, other.j).cmp(&(self.i, self.j)))
+    }
+}
+
+/// Compute communities by greedy modularity maximization (Clauset, Newman,
+/// and Moore, 2004).
+///
+/// Starts from singleton communities and repeatedly merges the pair of
+/// connected communities with the largest modularity gain, stopping when no
+/// merge increases modularity. Ties go to the pair with the smallest indices,
+/// so the result is deterministic. Repeated edges and self-loops are ignored.
+pub fn compute_greedy_modularity(src: &[i64], dst: &[i64]) -> Result<GreedyModularityResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    let (node_ids, neighbors) = simple_neighbors(src, dst);
+    let n = node_ids.len();
+    let two_m: usize = neighbors.iter().map(Vec::len).sum();
+    let mut parent: Vec<usize> = (0..n).collect();
+    if two_m == 0 {
+        return Ok(GreedyModularityResult {
+            community_ids: relabel_by_appearance(&parent),
+            modularity: 0.0,
+            node_ids,
+        });
+    }
+    let two_m = two_m as f64;
+
+    // e[i][j] is the fraction of edge ends in i whose other end is in j, and
+    // a[i] is the fraction of all edge ends in i.
+    let mut e: Vec<HashMap<usize, f64>> = neighbors
+        .iter()
+        .map(|adj| adj.iter().map(|&v| (v, 1.0 / two_m)).collect())
+        .collect();
+    let mut a: Vec<f64> = neighbors
+        .iter()
+        .map(|adj| adj.len() as f64 / two_m)
+        .collect();
+    let mut version = vec![0usize; n];
+    let mut alive = vec![true; n];
+
+    let mut heap = BinaryHeap::new();
+    for (i, row) in e.iter().enumerate() {
+        for (&j, &e_ij) in row.iter().filter(|(&j, _)| i < j) {
+            heap.push(Merge {
+                gain: 2.0 * (e_ij - a[i] * a[j]),
+                i,
+                j,
+                version_i: 0,
+                version_j: 0,
+            });
+        }
+    }
+
+    while let Some(merge) = heap.pop() {
+        let Merge {
+            gain,
+            i,
+            j,
+            version_i,
+            version_j,
+        } = merge;
+        if !alive[i] || !alive[j] || version[i] != version_i || version[j] != version_j {
+            continue;
+        }
+        if gain <= 0.0 {
+            break;
+        }
+        // Fold j into i
+        let row_j = std::mem::take(&mut e[j]);
+        for (&k, &e_jk) in &row_j {
+            if k == i {
+                continue;
+            }
+            *e[i].entry(k).or_insert(0.0) += e_jk;
+            if let Some(e_kj) = e[k].remove(&j) {
+                *e[k].entry(i).or_insert(0.0) += e_kj;
+            }
+        }
+        e[i].remove(&j);
+        a[i] += a[j];
+        a[j] = 0.0;
+        alive[j] = false;
+        parent[j] = i;
+        version[i] += 1;
+        for (&k, &e_ik) in &e[i] {
+            let (lo, hi) = (i.min(k), i.max(k));
+            heap.push(Merge {
+                gain: 2.0 * (e_ik - a[i] * a[k]),
+                i: lo,
+                j: hi,
+                version_i: version[lo],
+                version_j: version[hi],
+            });
+        }
+    }
+
+    let labels: Vec<usize> = (0..n).map(|u| find_root(&mut parent, u)).collect();
+    Ok(GreedyModularityResult {
+        community_ids: relabel_by_appearance(&labels),
+        modularity: modularity(&neighbors, &labels),
+        node_ids,
+    })
+}
+
+/// Map each node of a user-supplied partition to its community.
+///
+/// A node may be listed more than once as long as its community agrees.
+fn partition_map(node_ids: &[i64], communities: &[i64]) -> Result<HashMap<i64, i64>> {
+    if node_ids.len() != communities.len() {
+        return Err(OnagerError::InvalidArgument(
+            "node_ids and communities arrays must have same length".to_string(),
+        ));
+    }
+    let mut community_of: HashMap<i64, i64> = HashMap::new();
+    for (&node, &community) in node_ids.iter().zip(communities) {
+        if let Some(previous) = community_of.insert(node, community) {
+            if previous != community {
+                return Err(OnagerError::InvalidArgument(format!(
+                    "Node {} has conflicting communities {} and {}",
+                    node, previous, community
+                )));
+            }
+        }
+    }
+    Ok(community_of)
+}
+
+/// Look up the community of each of `nodes` in a user-supplied partition.
+///
+/// Listed nodes that are not in `nodes` are ignored.
+fn community_of_nodes(nodes: &[i64], node_ids: &[i64], communities: &[i64]) -> Result<Vec<i64>> {
+    let community_of = partition_map(node_ids, communities)?;
+    nodes
+        .iter()
+        .map(|node| {
+            community_of.get(node).copied().ok_or_else(|| {
+                OnagerError::InvalidArgument(format!("Node {} has no community", node))
+            })
+        })
+        .collect()
+}
+
+/// Compute the modularity of a given partition.
+///
+/// `node_ids[i]` belongs to community `community_ids[i]`, and every node of
+/// the graph must be listed, so partitions such as ground-truth labels or the
+/// output of different algorithms can be scored on the same scale. Modularity
+/// is the fraction of edges inside communities minus the fraction expected if
+/// edges were rewired at random with the same degrees, and lies between -0.5
+/// and 1. Edges are undirected and unweighted, and repeated edges and
+/// self-loops are ignored.
+pub fn compute_modularity(
+    src: &[i64],
+    dst: &[i64],
+    node_ids: &[i64],
+    community_ids: &[i64],
+) -> Result<f64> {
+    let graph = simple_undirected(src, dst)?;
+    let community = community_of_nodes(&graph.nodes, node_ids, community_ids)?;
+    let mut index: HashMap<i64, usize> = HashMap::new();
+    let labels: Vec<usize> = community
+        .iter()
+        .map(|&c| {
+            let next = index.len();
+            *index.entry(c).or_insert(next)
+        })
+        .collect();
+    let mut neighbors = vec![Vec::new(); graph.nodes.len()];
+    for &(u, v) in &graph.edges {
+        neighbors[u].push(v);
+        neighbors[v].push(u);
+    }
+    Ok(modularity(&neighbors, &labels))
+}
+
+/// Score used to compare two partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartitionMetric {
+    /// Normalized mutual information, with the arithmetic mean of the two
+    /// entropies as normalizer.
+    Nmi,
+    /// Adjusted Rand index.
+    Ari,
+    /// Harmonic mean of homogeneity and completeness.
+    VMeasure,
+}
+
+impl FromStr for PartitionMetric {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "nmi" => Ok(PartitionMetric::Nmi),
+            "ari" => Ok(PartitionMetric::Ari),
+            "v_measure" | "vmeasure" => Ok(PartitionMetric::VMeasure),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown partition metric '{}', expected 'nmi', 'ari', or 'v_measure'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Result of comparing two partitions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionSimilarity {
+    /// Similarity of the two partitions over the nodes in both
+    pub similarity: f64,
+    /// Number of nodes assigned a community in both partitions
+    pub common_count: usize,
+}
+
+/// Entropy of a distribution given by counts that sum to `total`.
+fn count_entropy(counts: impl Iterator<Item = f64>, total: f64) -> f64 {
+    counts
+        .filter(|&c| c > 0.0)
+        .map(|c| -(c / total) * (c / total).ln())
+        .sum()
+}
+
+/// Number of unordered pairs among `n` items.
+fn pairs(n: f64) -> f64 {
+    n * (n - 1.0) / 2.0
+}
+
+/// Compare two partitions of the same nodes, such as community assignments
+/// from two runs or two algorithms.
+///
+/// `nodes_a[i]` belongs to community `comms_a[i]` in the first partition and
+/// likewise for the second, and a node may be listed more than once as long
+/// as its community agrees. Only nodes in both partitions are compared, and
+/// community IDs need not match between the two. NMI and V-measure range
+/// from 0 for independent partitions to 1 for identical ones, and with equal
+/// weight on homogeneity and completeness they give the same value. ARI is 0
+/// on average for random partitions, 1 for identical ones, and can be
+/// negative. Two partitions that both put every node in one community score 1.
+pub fn compute_partition_similarity(
+    nodes_a: &[i64],
+    comms_a: &[i64],
+    nodes_b: &[i64],
+    comms_b: &[i64],
+    metric: PartitionMetric,
+) -> Result<PartitionSimilarity> {
+    let a = partition_map(nodes_a, comms_a)?;
+    let b = partition_map(nodes_b, comms_b)?;
+
+    let mut joint: HashMap<(i64, i64), f64> = HashMap::new();
+    let mut sizes_a: HashMap<i64, f64> = HashMap::new();
+    let mut sizes_b: HashMap<i64, f64> = HashMap::new();
+    for (node, &ca) in &a {
+        if let Some(&cb) = b.get(node) {
+            *joint.entry((ca, cb)).or_insert(0.0) += 1.0;
+            *sizes_a.entry(ca).or_insert(0.0) += 1.0;
+            *sizes_b.entry(cb).or_insert(0.0) += 1.0;
+        }
+    }
+    let common_count: usize = sizes_a.values().map(|&c| c as usize).sum();
+    if common_count == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "Partitions have no nodes in common".to_string(),
+        ));
+    }
+    let n = common_count as f64;
+
+    let similarity = match metric {
+        PartitionMetric::Nmi | PartitionMetric::VMeasure => {
+            let h_a = count_entropy(sizes_a.values().copied(), n);
+            let h_b = count_entropy(sizes_b.values().copied(), n);
+            let mutual: f64 = joint
+                .iter()
+                .map(|(&(ca, cb), &c)| c / n * (c * n / (sizes_a[&ca] * sizes_b[&cb])).ln())
+                .sum::<f64>()
+                .max(0.0);
+            if metric == PartitionMetric::Nmi {
+                if h_a + h_b == 0.0 {
+                    1.0
+                } else {
+                    (mutual / ((h_a + h_b) / 2.0)).min(1.0)
+                }
+            } else {
+                // A partition with one community is trivially homogeneous
+                // or complete
+                let homogeneity = if h_a == 0.0 { 1.0 } else { mutual / h_a };
+                let completeness = if h_b == 0.0 { 1.0 } else { mutual / h_b };
+                if homogeneity + completeness == 0.0 {
+                    0.0
+                } else {
+                    (2.0 * homogeneity * completeness / (homogeneity + completeness)).min(1.0)
+                }
+            }
+        }
+        PartitionMetric::Ari => {
+            let index: f64 = joint.values().map(|&c| pairs(c)).sum();
+            let pairs_a: f64 = sizes_a.values().map(|&c| pairs(c)).sum();
+            let pairs_b: f64 = sizes_b.values().map(|&c| pairs(c)).sum();
+            let total = pairs(n);
+            let expected = if total > 0.0 {
+                pairs_a * pairs_b / total
+            } else {
+                0.0
+            };
+            let max_index = (pairs_a + pairs_b) / 2.0;
+            // Only identical partitions leave no room above the expected index
+            if max_index == expected {
+                1.0
+            } else {
+                (index - expected) / (max_index - expected)
+            }
+        }
+    };
+    Ok(PartitionSimilarity {
+        similarity,
+        common_count,
+    })
+}
+
+/// Edges and nodes on the shortest paths between communities.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunityBridgesResult {
+    /// Edges with `src < dst` in ascending order, with the communities of
+    /// their ends
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    pub src_communities: Vec<i64>,
+    pub dst_communities: Vec<i64>,
+    /// Number of node pairs in different communities whose shortest paths run
+    /// over each edge
+    pub edge_paths: Vec<f64>,
+    /// Nodes in ascending order of ID, with their community
+    pub node_ids: Vec<i64>,
+    pub communities: Vec<i64>,
+    /// Number of node pairs in different communities whose shortest paths run
+    /// through each node, not counting pairs that start or end at the node
+    pub node_paths: Vec<f64>,
+}
+
+/// Find the edges and nodes that carry the shortest paths between
+/// communities.
+///
+/// `node_ids[i]` belongs to community `communities[i]`, and a node may be
+/// listed more than once as long as its community agrees. Every pair of
+/// nodes in different communities spreads one unit evenly over its shortest
+/// paths, as in betweenness centrality, so the edges and nodes with the most
+/// paths are the bridges that hold the communities together. Edges are
+/// undirected and unweighted, and repeated edges and self-loops are ignored.
+pub fn compute_community_bridges(
+    src: &[i64],
+    dst: &[i64],
+    node_ids: &[i64],
+    communities: &[i64],
+) -> Result<CommunityBridgesResult> {
+    let graph = simple_undirected(src, dst)?;
+    let community = community_of_nodes(&graph.nodes, node_ids, communities)?;
+
+    let n = graph.nodes.len();
+    let mut neighbors: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    for (e, &(u, v)) in graph.edges.iter().enumerate() {
+        neighbors[u].push((v, e));
+        neighbors[v].push((u, e));
+    }
+
+    // Brandes accumulation where only targets outside the source's community
+    // add a path
+    let mut edge_paths = vec![0.0; graph.edges.len()];
+    let mut node_paths = vec![0.0; n];
+    let mut dist = vec![usize::MAX; n];
+    let mut sigma = vec![0.0; n];
+    let mut delta = vec![0.0; n];
+    let mut preds: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+    let mut order: Vec<usize> = Vec::new();
+    let mut queue = VecDeque::new();
+    for s in 0..n {
+        for &u in &order {
+            dist[u] = usize::MAX;
+            sigma[u] = 0.0;
+            delta[u] = 0.0;
+            preds[u].clear();
+        }
+        order.clear();
+        dist[s] = 0;
+        sigma[s] = 1.0;
+        queue.push_back(s);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &(v, e) in &neighbors[u] {
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+                if dist[v] == dist[u] + 1 {
+                    sigma[v] += sigma[u];
+                    preds[v].push((u, e));
+                }
+            }
+        }
+        for &w in order.iter().rev() {
+            let target = if community[w] != community[s] {
+                1.0
+            } else {
+                0.0
+            };
+            for &(v, e) in &preds[w] {
+                let credit = sigma[v] / sigma[w] * (target + delta[w]);
+                edge_paths[e] += credit;
+                delta[v] += credit;
+            }
+            if w != s {
+                node_paths[w] += delta[w];
+            }
+        }
+    }
+
+    // Every pair was counted once from each end
+    let (src, dst): (Vec<i64>, Vec<i64>) = graph
+        .edges
+        .iter()
+        .map(|&(u, v)| (graph.nodes[u], graph.nodes[v]))
+        .unzip();
+    Ok(CommunityBridgesResult {
+        src,
+        dst,
+        src_communities: graph.edges.iter().map(|&(u, _)| community[u]).collect(),
+        dst_communities: graph.edges.iter().map(|&(_, v)| community[v]).collect(),
+        edge_paths: edge_paths.iter().map(|p| p / 2.0).collect(),
+        node_ids: graph.nodes,
+        communities: community,
+        node_paths: node_paths.iter().map(|p| p / 2.0).collect(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -505,4 +1617,383 @@ mod tests {
     fn test_mismatched_arrays_error() {
         assert!(compute_louvain(&[1, 2], &[2], None).is_err());
     }
+
+    #[test]
+    fn test_similarity_components_threshold() {
+        // 1-2-3 chained by strong edges, 4-5 strong, 3-4 weak
+        let src = vec![1, 2, 1, 4, 3, 6];
+        let dst = vec![2, 3, 3, 5, 4, 6];
+        let weights = vec![0.9, 0.8, 0.2, 0.95, 0.1, 0.0];
+
+        let result = compute_similarity_components(&src, &dst, &weights, 0.5).unwrap();
+
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(result.cluster_ids, vec![1, 1, 1, 4, 4, 6]);
+        assert_eq!(result.cluster_sizes, vec![3, 3, 3, 2, 2, 1]);
+        // The weak 1-3 edge lies inside the first cluster
+        assert_eq!(result.min_similarities[0], 0.2);
+        assert_eq!(result.max_similarities[0], 0.9);
+        assert_eq!(result.min_similarities[3], 0.95);
+        // The self-loop on 6 is below the threshold but still internal
+        assert_eq!(result.max_similarities[5], 0.0);
+    }
+
+    #[test]
+    fn test_similarity_components_singleton_has_no_similarity() {
+        let result = compute_similarity_components(&[1], &[2], &[0.3], 0.5).unwrap();
+
+        assert_eq!(result.cluster_ids, vec![1, 2]);
+        assert!(result.min_similarities.iter().all(|s| s.is_nan()));
+    }
+
+    #[test]
+    fn test_similarity_components_invalid_input() {
+        assert!(compute_similarity_components(&[1], &[2], &[], 0.5).is_err());
+        assert!(compute_similarity_components(&[1], &[2], &[0.5], f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_strong_components_cycle_and_tail() {
+        // Cycle 1 -> 2 -> 3 -> 1 with a tail 3 -> 4 -> 5
+        let src = vec![1, 2, 3, 3, 4];
+        let dst = vec![2, 3, 1, 4, 5];
+
+        let result =
+            compute_connected_components_with_mode(&src, &dst, ComponentMode::Strong).unwrap();
+
+        let comp: HashMap<i64, i64> = result
+            .node_ids
+            .iter()
+            .copied()
+            .zip(result.component_ids.iter().copied())
+            .collect();
+        assert_eq!(comp.len(), 5);
+        assert_eq!(comp[&1], comp[&2]);
+        assert_eq!(comp[&2], comp[&3]);
+        assert_ne!(comp[&3], comp[&4]);
+        assert_ne!(comp[&4], comp[&5]);
+        let unique: std::collections::HashSet<_> = result.component_ids.iter().collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[test]
+    fn test_weak_components_ignore_direction() {
+        // 1 -> 2 <- 3 share a component without reversed edges, and 5 -> 4 is separate
+        let src = vec![1, 3, 5];
+        let dst = vec![2, 2, 4];
+
+        let result = compute_weakly_connected_components(&src, &dst).unwrap();
+
+        assert_eq!(result.node_ids, vec![1, 2, 3, 5, 4]);
+        assert_eq!(result.component_ids, vec![0, 0, 0, 1, 1]);
+        let by_mode =
+            compute_connected_components_with_mode(&src, &dst, ComponentMode::Weak).unwrap();
+        assert_eq!(by_mode.component_ids, result.component_ids);
+        assert!(compute_weakly_connected_components(&[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_component_mode_from_str() {
+        assert_eq!(
+            "Strong".parse::<ComponentMode>().unwrap(),
+            ComponentMode::Strong
+        );
+        assert_eq!(
+            "weak".parse::<ComponentMode>().unwrap(),
+            ComponentMode::Weak
+        );
+        assert!("directed".parse::<ComponentMode>().is_err());
+    }
+
+    #[test]
+    fn test_component_of_only_visits_own_component() {
+        let src = vec![1, 2, 3, 10, 11];
+        let dst = vec![2, 3, 4, 11, 12];
+
+        let result = compute_component_of(&src, &dst, 3).unwrap();
+
+        assert_eq!(result.node_ids[0], 3);
+        let mut members = result.node_ids.clone();
+        members.sort_unstable();
+        assert_eq!(members, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_component_of_missing_node() {
+        assert!(matches!(
+            compute_component_of(&[1], &[2], 99),
+            Err(OnagerError::NodeNotFound(99))
+        ));
+    }
+
+    fn two_cliques() -> (Vec<i64>, Vec<i64>) {
+        // Two 4-cliques joined by the edge 4-5
+        let mut src = Vec::new();
+        let mut dst = Vec::new();
+        for clique in [[1, 2, 3, 4], [5, 6, 7, 8]] {
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    src.push(clique[i]);
+                    dst.push(clique[j]);
+                }
+            }
+        }
+        src.push(4);
+        dst.push(5);
+        (src, dst)
+    }
+
+    fn community_of(node_ids: &[i64], community_ids: &[i64], node: i64) -> i64 {
+        let i = node_ids.iter().position(|&n| n == node).unwrap();
+        community_ids[i]
+    }
+
+    #[test]
+    fn test_fluid_communities_two_cliques() {
+        let (src, dst) = two_cliques();
+        for seed in 0..20 {
+            let result = compute_fluid_communities(&src, &dst, 2, Some(seed)).unwrap();
+            assert_eq!(result.node_ids.len(), 8);
+            let of = |node| community_of(&result.node_ids, &result.community_ids, node);
+            assert!((2..=4).all(|node| of(node) == of(1)), "seed {seed}");
+            assert!((6..=8).all(|node| of(node) == of(5)), "seed {seed}");
+            assert_ne!(of(1), of(5), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_fluid_communities_is_reproducible() {
+        let (src, dst) = two_cliques();
+        let a = compute_fluid_communities(&src, &dst, 3, Some(7)).unwrap();
+        let b = compute_fluid_communities(&src, &dst, 3, Some(7)).unwrap();
+        assert_eq!(a.community_ids, b.community_ids);
+        let distinct: HashSet<i64> = a.community_ids.iter().copied().collect();
+        assert_eq!(distinct.len(), 3);
+    }
+
+    #[test]
+    fn test_fluid_communities_invalid_input() {
+        let (src, dst) = two_cliques();
+        assert!(compute_fluid_communities(&src, &dst, 0, Some(1)).is_err());
+        assert!(compute_fluid_communities(&src, &dst, 9, Some(1)).is_err());
+        // Two separate edges are not connected
+        assert!(compute_fluid_communities(&[1, 3], &[2, 4], 2, Some(1)).is_err());
+    }
+
+    #[test]
+    fn test_greedy_modularity_two_cliques() {
+        let (src, dst) = two_cliques();
+        let result = compute_greedy_modularity(&src, &dst).unwrap();
+        let of = |node| community_of(&result.node_ids, &result.community_ids, node);
+        assert!((2..=4).all(|node| of(node) == of(1)));
+        assert!((6..=8).all(|node| of(node) == of(5)));
+        assert_ne!(of(1), of(5));
+        // Two communities of 6 internal edges each out of 13, each with degree 13
+        let expected = 2.0 * (6.0 / 13.0 - 0.25);
+        assert!((result.modularity - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_greedy_modularity_separate_components() {
+        // Components never merge, since joining them lowers modularity
+        let result = compute_greedy_modularity(&[1, 2, 10, 11], &[2, 3, 11, 12]).unwrap();
+        assert_eq!(result.community_ids, vec![0, 0, 1, 1, 0, 1]);
+        assert!((result.modularity - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_modularity_of_single_community_is_zero() {
+        let (src, dst) = two_cliques();
+        let (_, neighbors) = simple_neighbors(&src, &dst);
+        let all = vec![0; neighbors.len()];
+        assert!(modularity(&neighbors, &all).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_girvan_newman_with_limits_cuts_the_bridge_first() {
+        let (src, dst) = two_cliques();
+        let result = compute_girvan_newman_with_limits(
+            &src,
+            &dst,
+            GirvanNewmanStop::Communities(2),
+            Some(1),
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.iterations, 1);
+        assert!(!result.stopped_early);
+        let of = |node| community_of(&result.node_ids, &result.community_ids, node);
+        assert!((2..=4).all(|node| of(node) == of(1)));
+        assert!((6..=8).all(|node| of(node) == of(5)));
+        assert_ne!(of(1), of(5));
+    }
+
+    #[test]
+    fn test_girvan_newman_max_modularity_keeps_best_partition() {
+        let (src, dst) = two_cliques();
+        let result = compute_girvan_newman_with_limits(
+            &src,
+            &dst,
+            GirvanNewmanStop::MaxModularity,
+            None,
+            None,
+        )
+        .unwrap();
+        // Every edge is removed, but the two-clique split scores highest
+        assert_eq!(result.iterations, 13);
+        assert!(!result.stopped_early);
+        let distinct: HashSet<i64> = result.community_ids.iter().copied().collect();
+        assert_eq!(distinct.len(), 2);
+        assert!((result.modularity - 2.0 * (6.0 / 13.0 - 0.25)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_girvan_newman_iteration_cap_stops_early() {
+        let (src, dst) = two_cliques();
+        let result = compute_girvan_newman_with_limits(
+            &src,
+            &dst,
+            GirvanNewmanStop::Communities(8),
+            Some(3),
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.iterations, 3);
+        assert!(result.stopped_early);
+
+        let result = compute_girvan_newman_with_limits(
+            &src,
+            &dst,
+            GirvanNewmanStop::MaxModularity,
+            None,
+            Some(Duration::ZERO),
+        )
+        .unwrap();
+        assert_eq!(result.iterations, 0);
+        assert!(result.stopped_early);
+        assert!(result.community_ids.iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn test_girvan_newman_with_limits_invalid_input() {
+        let (src, dst) = two_cliques();
+        let stop = GirvanNewmanStop::Communities(0);
+        assert!(compute_girvan_newman_with_limits(&src, &dst, stop, None, None).is_err());
+        let stop = GirvanNewmanStop::MaxModularity;
+        assert!(compute_girvan_newman_with_limits(&src, &dst, stop, Some(0), None).is_err());
+    }
+
+    #[test]
+    fn test_community_bridges_find_the_bridge() {
+        // Triangles 1-2-3 and 4-5-6 joined by the bridge 3-4
+        let src = vec![1, 2, 3, 3, 4, 5, 6];
+        let dst = vec![2, 3, 1, 4, 5, 6, 4];
+        let nodes = vec![1, 2, 3, 4, 5, 6];
+        let communities = vec![0, 0, 0, 1, 1, 1];
+        let result = compute_community_bridges(&src, &dst, &nodes, &communities).unwrap();
+
+        // All nine pairs across the communities cross the bridge
+        let bridge = (0..result.src.len())
+            .find(|&i| (result.src[i], result.dst[i]) == (3, 4))
+            .unwrap();
+        assert_eq!(result.edge_paths[bridge], 9.0);
+        assert_eq!(result.src_communities[bridge], 0);
+        assert_eq!(result.dst_communities[bridge], 1);
+        // Edge 1-3 carries the pairs from node 1 to the other side
+        let inner = (0..result.src.len())
+            .find(|&i| (result.src[i], result.dst[i]) == (1, 3))
+            .unwrap();
+        assert_eq!(result.edge_paths[inner], 3.0);
+        // Edge 1-2 stays inside a community and carries nothing
+        assert_eq!(result.edge_paths[0], 0.0);
+
+        assert_eq!(result.node_ids, nodes);
+        assert_eq!(result.node_paths, vec![0.0, 0.0, 6.0, 6.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_community_bridges_split_equal_paths() {
+        // Square 1-2-4-3-1 where node 1 is alone in its community
+        let src = vec![1, 1, 2, 3];
+        let dst = vec![2, 3, 4, 4];
+        let nodes = vec![1, 2, 3, 4];
+        let communities = vec![0, 1, 1, 1];
+        let result = compute_community_bridges(&src, &dst, &nodes, &communities).unwrap();
+        // Pair 1-4 splits over both routes, and pairs 1-2 and 1-3 use their edge
+        assert_eq!(result.edge_paths, vec![1.5, 1.5, 0.5, 0.5]);
+        assert_eq!(result.node_paths, vec![0.0, 0.5, 0.5, 0.0]);
+
+        assert!(compute_community_bridges(&src, &dst, &[1, 2, 3], &[0, 1, 1]).is_err());
+        assert!(compute_community_bridges(&src, &dst, &[1, 1], &[0, 1]).is_err());
+        assert!(compute_community_bridges(&src, &dst, &[1], &[0, 1]).is_err());
+    }
+
+    #[test]
+    fn test_modularity_of_given_partition() {
+        // Two triangles joined by the edge 3-4
+        let src = vec![1, 2, 1, 4, 5, 4, 3];
+        let dst = vec![2, 3, 3, 5, 6, 6, 4];
+        let nodes = vec![1, 2, 3, 4, 5, 6];
+        let q = compute_modularity(&src, &dst, &nodes, &[7, 7, 7, 9, 9, 9]).unwrap();
+        assert!((q - 5.0 / 14.0).abs() < 1e-12);
+        // One community scores 0, and singletons score minus the degree term
+        let q = compute_modularity(&src, &dst, &nodes, &[0; 6]).unwrap();
+        assert!(q.abs() < 1e-12);
+        let q = compute_modularity(&src, &dst, &nodes, &nodes).unwrap();
+        assert!((q + 34.0 / 196.0).abs() < 1e-12);
+
+        assert!(compute_modularity(&src, &dst, &nodes[..5], &[0; 5]).is_err());
+        assert!(compute_modularity(&src, &dst, &[1, 1], &[0, 1]).is_err());
+        assert!(compute_modularity(&[], &[], &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_partition_similarity() {
+        let nodes = vec![1, 2, 3, 4, 5, 6];
+        let a = vec![0, 0, 0, 1, 1, 1];
+        // Same partition with other community IDs, plus a node only in one side
+        let b_nodes = vec![6, 5, 4, 3, 2, 1, 9];
+        let b = vec![7, 7, 7, 8, 8, 8, 7];
+        for metric in [
+            PartitionMetric::Nmi,
+            PartitionMetric::Ari,
+            PartitionMetric::VMeasure,
+        ] {
+            let result = compute_partition_similarity(&nodes, &a, &b_nodes, &b, metric).unwrap();
+            assert!((result.similarity - 1.0).abs() < 1e-12);
+            assert_eq!(result.common_count, 6);
+        }
+
+        // Splitting one community in two
+        let c = vec![0, 0, 0, 1, 1, 2];
+        let ari = compute_partition_similarity(&nodes, &a, &nodes, &c, PartitionMetric::Ari)
+            .unwrap()
+            .similarity;
+        // Index 4, expected 6 * 4 / 15, maximum 5
+        assert!((ari - (4.0 - 1.6) / (5.0 - 1.6)).abs() < 1e-12);
+        let nmi = compute_partition_similarity(&nodes, &a, &nodes, &c, PartitionMetric::Nmi)
+            .unwrap()
+            .similarity;
+        let v = compute_partition_similarity(&nodes, &a, &nodes, &c, PartitionMetric::VMeasure)
+            .unwrap()
+            .similarity;
+        assert!(nmi > 0.0 && nmi < 1.0);
+        assert!((nmi - v).abs() < 1e-12);
+
+        // Independent partitions share no information
+        let d = vec![0, 1, 2, 0, 1, 2];
+        let nmi = compute_partition_similarity(&nodes, &a, &nodes, &d, PartitionMetric::Nmi)
+            .unwrap()
+            .similarity;
+        assert!(nmi.abs() < 1e-12);
+
+        assert!(
+            compute_partition_similarity(&[1], &[0], &[2], &[0], PartitionMetric::Nmi).is_err()
+        );
+        assert!(
+            compute_partition_similarity(&[1, 1], &[0, 1], &[1], &[0], PartitionMetric::Ari)
+                .is_err()
+        );
+        assert!("purity".parse::<PartitionMetric>().is_err());
+    }
 }
@@ -0,0 +1,362 @@
+//! Contraction hierarchy module.
+//!
+//! Preprocessing and queries for fast point-to-point shortest distances on
+//! large sparse graphs such as road networks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Maximum number of nodes settled by one witness search during preprocessing.
+///
+/// A search that hits the limit adds the shortcut anyway, which keeps the
+/// hierarchy correct at the cost of a few unneeded shortcuts.
+const WITNESS_SETTLE_LIMIT: usize = 500;
+
+/// Total order over non-negative distances for use in binary heaps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Dist(f64);
+
+impl Eq for Dist {}
+
+impl PartialOrd for Dist {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Dist {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Contraction hierarchy over a weighted graph.
+///
+/// Every node gets a rank, and each edge (original or shortcut) is stored at
+/// its lower-ranked endpoint. A query searches upwards from both endpoints,
+/// which only touches a small part of the graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractionHierarchy {
+    node_index: NodeIndex,
+    /// Edges `u -> v` with `rank[v] > rank[u]`, stored at `u`.
+    up_out: Vec<Vec<(usize, f64)>>,
+    /// Edges `v -> u` with `rank[v] > rank[u]`, stored at `u` as `(v, weight)`.
+    up_in: Vec<Vec<(usize, f64)>>,
+    /// Number of shortcut edges added during preprocessing.
+    pub shortcut_count: usize,
+}
+
+/// Remaining graph during contraction, with parallel edges merged.
+struct Overlay {
+    out: Vec<HashMap<usize, f64>>,
+    inc: Vec<HashMap<usize, f64>>,
+}
+
+impl Overlay {
+    fn insert(&mut self, u: usize, v: usize, w: f64) {
+        let entry = self.out[u].entry(v).or_insert(w);
+        *entry = entry.min(w);
+        let entry = self.inc[v].entry(u).or_insert(w);
+        *entry = entry.min(w);
+    }
+
+    /// Shortest distance from `source` to `target` avoiding `skip`, or
+    /// infinity if it exceeds `limit` or the search gives up.
+    fn witness(&self, source: usize, target: usize, skip: usize, limit: f64) -> f64 {
+        let mut dist: HashMap<usize, f64> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        dist.insert(source, 0.0);
+        heap.push(Reverse((Dist(0.0), source)));
+        let mut settled = 0;
+        while let Some(Reverse((Dist(d), u))) = heap.pop() {
+            if dist.get(&u).is_some_and(|&best| d > best) {
+                continue;
+            }
+            if u == target {
+                return d;
+            }
+            if d > limit {
+                break;
+            }
+            settled += 1;
+            if settled > WITNESS_SETTLE_LIMIT {
+                break;
+            }
+            for (&v, &w) in &self.out[u] {
+                if v == skip {
+                    continue;
+                }
+                let alt = d + w;
+                if dist.get(&v).is_none_or(|&best| alt < best) {
+                    dist.insert(v, alt);
+                    heap.push(Reverse((Dist(alt), v)));
+                }
+            }
+        }
+        f64::INFINITY
+    }
+
+    /// Shortcuts needed to contract `v` without changing any distance.
+    fn shortcuts(&self, v: usize) -> Vec<(usize, usize, f64)> {
+        let mut shortcuts = Vec::new();
+        for (&u, &w_in) in &self.inc[v] {
+            let limit = self.out[v]
+                .iter()
+                .filter(|&(&x, _)| x != u)
+                .map(|(_, &w_out)| w_in + w_out)
+                .fold(0.0, f64::max);
+            for (&x, &w_out) in &self.out[v] {
+                if x == u {
+                    continue;
+                }
+                let via = w_in + w_out;
+                if self.witness(u, x, v, limit) > via {
+                    shortcuts.push((u, x, via));
+                }
+            }
+        }
+        shortcuts
+    }
+
+    /// Edge difference priority: shortcuts added minus edges removed.
+    fn priority(&self, v: usize, contracted_neighbors: usize) -> i64 {
+        let added = self.shortcuts(v).len() as i64;
+        let removed = (self.out[v].len() + self.inc[v].len()) as i64;
+        added - removed + contracted_neighbors as i64
+    }
+}
+
+/// Build a contraction hierarchy.
+///
+/// `node_ids` lists every node, including isolated ones, and may be empty when
+/// all nodes appear in an edge. Undirected graphs store each edge in both
+/// directions. Weights must be finite and non-negative, and empty `weights`
+/// means unit weights. Nodes are contracted in order of edge difference with
+/// lazy priority updates.
+pub fn build_contraction_hierarchy(
+    node_ids: &[i64],
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+) -> Result<ContractionHierarchy> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights array must be empty or match edge count".to_string(),
+        ));
+    }
+
+    // Listed nodes come first, so isolated nodes keep their place in the order
+    let node_index = NodeIndex::new(&[node_ids, src].concat(), dst);
+    let n = node_index.len();
+    let mut overlay = Overlay {
+        out: vec![HashMap::new(); n],
+        inc: vec![HashMap::new(); n],
+    };
+    for i in 0..src.len() {
+        let w = if weights.is_empty() { 1.0 } else { weights[i] };
+        if !w.is_finite() || w < 0.0 {
+            return Err(OnagerError::InvalidArgument(format!(
+                "Edge weights must be finite and non-negative, got {}",
+                w
+            )));
+        }
+        let (u, v) = (node_index.of(src[i]), node_index.of(dst[i]));
+        if u == v {
+            continue;
+        }
+        overlay.insert(u, v, w);
+        if !directed {
+            overlay.insert(v, u, w);
+        }
+    }
+
+    let mut contracted_neighbors = vec![0usize; n];
+    let mut heap: BinaryHeap<Reverse<(i64, usize)>> = (0..n)
+        .map(|v| Reverse((overlay.priority(v, 0), v)))
+        .collect();
+    let mut up_out = vec![Vec::new(); n];
+    let mut up_in = vec![Vec::new(); n];
+    let mut shortcut_count = 0;
+    while let Some(Reverse((priority, v))) = heap.pop() {
+        // Lazy update: re-queue the node if its priority went up since it was pushed
+        let current = overlay.priority(v, contracted_neighbors[v]);
+        if current > priority {
+            if let Some(Reverse((next, _))) = heap.peek() {
+                if current > *next {
+                    heap.push(Reverse((current, v)));
+                    continue;
+                }
+            }
+        }
+
+        let shortcuts = overlay.shortcuts(v);
+        let out = std::mem::take(&mut overlay.out[v]);
+        let inc = std::mem::take(&mut overlay.inc[v]);
+        for (&x, &w) in &out {
+            overlay.inc[x].remove(&v);
+            contracted_neighbors[x] += 1;
+            up_out[v].push((x, w));
+        }
+        for (&u, &w) in &inc {
+            overlay.out[u].remove(&v);
+            contracted_neighbors[u] += 1;
+            up_in[v].push((u, w));
+        }
+        for (u, x, w) in shortcuts {
+            if overlay.out[u].get(&x).is_none_or(|&old| w < old) {
+                shortcut_count += 1;
+            }
+            overlay.insert(u, x, w);
+        }
+    }
+
+    Ok(ContractionHierarchy {
+        node_index,
+        up_out,
+        up_in,
+        shortcut_count,
+    })
+}
+
+impl ContractionHierarchy {
+    /// Shortest distance from `source_node` to `target_node`.
+    ///
+    /// Returns f64::INFINITY if the target is unreachable.
+    pub fn distance(&self, source_node: i64, target_node: i64) -> Result<f64> {
+        let source = self
+            .node_index
+            .get(source_node)
+            .ok_or(OnagerError::NodeNotFound(source_node))?;
+        let target = self
+            .node_index
+            .get(target_node)
+            .ok_or(OnagerError::NodeNotFound(target_node))?;
+
+        let mut dist: [HashMap<usize, f64>; 2] = [HashMap::new(), HashMap::new()];
+        let mut heaps = [BinaryHeap::new(), BinaryHeap::new()];
+        let adjacency = [&self.up_out, &self.up_in];
+        dist[0].insert(source, 0.0);
+        dist[1].insert(target, 0.0);
+        heaps[0].push(Reverse((Dist(0.0), source)));
+        heaps[1].push(Reverse((Dist(0.0), target)));
+        let mut best = f64::INFINITY;
+
+        // Both searches only go upwards. The forward search runs to completion,
+        // then the backward search stops once its frontier passes the best
+        // meeting distance found so far.
+        for side in 0..2 {
+            while let Some(Reverse((Dist(d), u))) = heaps[side].pop() {
+                if d >= best {
+                    break;
+                }
+                if dist[side].get(&u).is_some_and(|&known| d > known) {
+                    continue;
+                }
+                if let Some(&other) = dist[1 - side].get(&u) {
+                    best = best.min(d + other);
+                }
+                for &(v, w) in &adjacency[side][u] {
+                    let alt = d + w;
+                    if dist[side].get(&v).is_none_or(|&known| alt < known) {
+                        dist[side].insert(v, alt);
+                        heaps[side].push(Reverse((Dist(alt), v)));
+                    }
+                }
+            }
+        }
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain Dijkstra used as a reference.
+    fn reference(n: usize, edges: &[(usize, usize, f64)], source: usize) -> Vec<f64> {
+        let mut adj = vec![Vec::new(); n];
+        for &(u, v, w) in edges {
+            adj[u].push((v, w));
+        }
+        let mut dist = vec![f64::INFINITY; n];
+        let mut heap = BinaryHeap::new();
+        dist[source] = 0.0;
+        heap.push(Reverse((Dist(0.0), source)));
+        while let Some(Reverse((Dist(d), u))) = heap.pop() {
+            if d > dist[u] {
+                continue;
+            }
+            for &(v, w) in &adj[u] {
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    heap.push(Reverse((Dist(d + w), v)));
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_ch_matches_dijkstra_on_directed_grid() {
+        // 6x6 grid with one-way rows and two-way columns of varying cost, so
+        // nodes to the left of the source in the same row may be unreachable
+        let side = 6;
+        let mut edges = Vec::new();
+        for r in 0..side {
+            for c in 0..side {
+                let v = r * side + c;
+                if c + 1 < side {
+                    edges.push((v, v + 1, 1.0 + ((r + c) % 3) as f64));
+                }
+                if r + 1 < side {
+                    let w = 1.5 + (c % 2) as f64;
+                    edges.push((v, v + side, w));
+                    edges.push((v + side, v, w));
+                }
+            }
+        }
+        let src: Vec<i64> = edges.iter().map(|e| e.0 as i64).collect();
+        let dst: Vec<i64> = edges.iter().map(|e| e.1 as i64).collect();
+        let weights: Vec<f64> = edges.iter().map(|e| e.2).collect();
+        let ch = build_contraction_hierarchy(&[], &src, &dst, &weights, true).unwrap();
+
+        let n = side * side;
+        for source in 0..n {
+            let expected = reference(n, &edges, source);
+            for (target, &d) in expected.iter().enumerate() {
+                let got = ch.distance(source as i64, target as i64).unwrap();
+                assert!(
+                    got == d || (got - d).abs() < 1e-9,
+                    "{} -> {}",
+                    source,
+                    target
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_ch_undirected_and_unreachable() {
+        let ch = build_contraction_hierarchy(&[9], &[1, 2, 3], &[2, 3, 4], &[1.0, 2.0, 1.5], false)
+            .unwrap();
+        assert_eq!(ch.distance(4, 1).unwrap(), 4.5);
+        assert_eq!(ch.distance(2, 2).unwrap(), 0.0);
+        assert_eq!(ch.distance(1, 9).unwrap(), f64::INFINITY);
+        assert!(ch.distance(1, 42).is_err());
+    }
+
+    #[test]
+    fn test_ch_rejects_negative_weights() {
+        assert!(build_contraction_hierarchy(&[], &[1], &[2], &[-1.0], true).is_err());
+    }
+}
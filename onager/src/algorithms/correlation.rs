@@ -0,0 +1,264 @@
+//! Correlation clustering module.
+//!
+//! Partitioning nodes from signed edge weights, where a positive weight says
+//! two nodes belong together and a negative weight says they belong apart.
+
+use serde::{Deserialize, Serialize};
+
+use super::sbm::SplitMix64;
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+/// Number of random pivot orders, of which the partition with the fewest
+/// disagreements is kept.
+const NUM_RESTARTS: usize = 10;
+
+/// Maximum number of local improvement passes per restart.
+const MAX_PASSES: usize = 50;
+
+/// Result of correlation clustering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationClusteringResult {
+    pub node_ids: Vec<i64>,
+    pub cluster_ids: Vec<i64>,
+    /// Total weight of positive edges between clusters plus the total absolute
+    /// weight of negative edges inside clusters.
+    pub disagreements: f64,
+}
+
+/// Cost of a partition: positive weight cut plus negative weight kept inside.
+fn disagreements(neighbors: &[Vec<(usize, f64)>], labels: &[usize]) -> f64 {
+    let mut cost = 0.0;
+    for (u, adj) in neighbors.iter().enumerate() {
+        for &(v, w) in adj.iter().filter(|&&(v, _)| u < v) {
+            if w > 0.0 && labels[u] != labels[v] {
+                cost += w;
+            } else if w < 0.0 && labels[u] == labels[v] {
+                cost -= w;
+            }
+        }
+    }
+    cost
+}
+
+/// Pivot clustering: a random pivot takes every unclustered node it is
+/// positively linked to, then the next pivot is drawn from the rest.
+fn pivot_clusters(neighbors: &[Vec<(usize, f64)>], rng: &mut SplitMix64) -> Vec<usize> {
+    let n = neighbors.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        order.swap(i, rng.next_below(i + 1));
+    }
+    let mut labels = vec![usize::MAX; n];
+    for (cluster, &pivot) in order.iter().enumerate() {
+        if labels[pivot] != usize::MAX {
+            continue;
+        }
+        labels[pivot] = cluster;
+        for &(v, w) in &neighbors[pivot] {
+            if w > 0.0 && labels[v] == usize::MAX {
+                labels[v] = cluster;
+            }
+        }
+    }
+    labels
+}
+
+/// Move single nodes to the cluster they agree with most until no move helps.
+///
+/// A node's cost in cluster `c` is its total positive weight minus its signed
+/// weight into `c`, so the best cluster is the one with the largest signed
+/// weight, or a new singleton when every cluster has a negative total.
+fn improve(neighbors: &[Vec<(usize, f64)>], labels: &mut [usize]) {
+    let n = neighbors.len();
+    let mut sizes = vec![0usize; n];
+    for &c in labels.iter() {
+        sizes[c] += 1;
+    }
+    let mut free: Vec<usize> = (0..n).filter(|&c| sizes[c] == 0).collect();
+    let mut weight_to: HashMap<usize, f64> = HashMap::new();
+    for _ in 0..MAX_PASSES {
+        let mut moved = false;
+        for v in 0..n {
+            weight_to.clear();
+            for &(u, w) in &neighbors[v] {
+                *weight_to.entry(labels[u]).or_insert(0.0) += w;
+            }
+            let current = labels[v];
+            let stay = weight_to.get(&current).copied().unwrap_or(0.0);
+            // A new singleton has no weight to anyone, so it is worth 0
+            let mut best: Option<(usize, f64)> = None;
+            if sizes[current] > 1 {
+                best = free.last().map(|&empty| (empty, 0.0));
+            }
+            for (&c, &w) in &weight_to {
+                let better = best.is_none_or(|(bc, bw)| w > bw || (w == bw && c < bc));
+                if c != current && better {
+                    best = Some((c, w));
+                }
+            }
+            let Some((target, gain)) = best else {
+                continue;
+            };
+            if gain > stay + 1e-12 {
+                if sizes[target] == 0 {
+                    free.pop();
+                }
+                sizes[current] -= 1;
+                sizes[target] += 1;
+                if sizes[current] == 0 {
+                    free.push(current);
+                }
+                labels[v] = target;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+}
+
+/// Partition nodes using signed edge weights.
+///
+/// Positive weights mark pairs that should share a cluster and negative
+/// weights mark pairs that should be apart, with larger magnitudes counting
+/// more. The partition minimizes the total disagreement: positive weight
+/// between clusters plus absolute negative weight inside clusters. The number
+/// of clusters is not fixed in advance. Pairs without an edge have no
+/// preference, and parallel edges between the same pair are summed.
+///
+/// This runs the randomized pivot algorithm from several seeded starts and
+/// improves each result with single node moves, keeping the best partition.
+/// The pivot algorithm is a 3-approximation on complete unweighted instances;
+/// on sparse or weighted graphs it is a heuristic. Weights must be finite.
+pub fn compute_correlation_clustering(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    seed: Option<u64>,
+) -> Result<CorrelationClusteringResult> {
+    if src.len() != dst.len() || src.len() != weights.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and weights arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite()) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite, got {}",
+            w
+        )));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let n = nodes.len();
+    let mut pairs: HashMap<(usize, usize), f64> = HashMap::new();
+    for i in 0..src.len() {
+        let (u, v) = (nodes.of(src[i]), nodes.of(dst[i]));
+        if u != v {
+            *pairs.entry((u.min(v), u.max(v))).or_insert(0.0) += weights[i];
+        }
+    }
+    let mut neighbors: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+    for (&(u, v), &w) in &pairs {
+        if w != 0.0 {
+            neighbors[u].push((v, w));
+            neighbors[v].push((u, w));
+        }
+    }
+    // Pair iteration order is random, so sort to keep seeded runs reproducible
+    for adj in &mut neighbors {
+        adj.sort_unstable_by_key(|&(v, _)| v);
+    }
+
+    let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let mut rng = SplitMix64(seed);
+    let mut best: Option<(f64, Vec<usize>)> = None;
+    for _ in 0..NUM_RESTARTS {
+        let mut labels = pivot_clusters(&neighbors, &mut rng);
+        improve(&neighbors, &mut labels);
+        let cost = disagreements(&neighbors, &labels);
+        if best.as_ref().is_none_or(|(b, _)| cost < *b - 1e-12) {
+            best = Some((cost, labels));
+        }
+    }
+    let (cost, labels) = best.ok_or_else(|| {
+        OnagerError::GraphError("Correlation clustering found no partition".to_string())
+    })?;
+
+    // Number clusters by first appearance so the output does not depend on pivots
+    let mut renumber: HashMap<usize, i64> = HashMap::new();
+    let cluster_ids = labels
+        .iter()
+        .map(|&c| {
+            let next = renumber.len() as i64;
+            *renumber.entry(c).or_insert(next)
+        })
+        .collect();
+    Ok(CorrelationClusteringResult {
+        node_ids: nodes.node_ids,
+        cluster_ids,
+        disagreements: cost,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation_clustering_splits_on_negative_edges() {
+        // Records 1-2-3 agree, records 4-5 agree, and the groups conflict
+        let src = vec![1, 2, 1, 4, 3, 2];
+        let dst = vec![2, 3, 3, 5, 4, 5];
+        let weights = vec![1.0, 1.0, 1.0, 1.0, -2.0, -1.0];
+        let result = compute_correlation_clustering(&src, &dst, &weights, Some(7)).unwrap();
+
+        let cluster = |node: i64| {
+            let i = result.node_ids.iter().position(|&v| v == node).unwrap();
+            result.cluster_ids[i]
+        };
+        assert_eq!(cluster(1), cluster(2));
+        assert_eq!(cluster(1), cluster(3));
+        assert_eq!(cluster(4), cluster(5));
+        assert_ne!(cluster(1), cluster(4));
+        assert_eq!(result.disagreements, 0.0);
+    }
+
+    #[test]
+    fn test_correlation_clustering_resolves_conflicts() {
+        // 1 and 3 are both strongly linked to 2 but weakly repel each other
+        let src = vec![1, 2, 1];
+        let dst = vec![2, 3, 3];
+        let weights = vec![3.0, 3.0, -1.0];
+        let result = compute_correlation_clustering(&src, &dst, &weights, Some(1)).unwrap();
+        assert_eq!(result.cluster_ids, vec![0, 0, 0]);
+        assert_eq!(result.disagreements, 1.0);
+    }
+
+    #[test]
+    fn test_correlation_clustering_is_reproducible_with_seed() {
+        let src = vec![1, 2, 3, 4, 5, 6, 1, 4];
+        let dst = vec![2, 3, 1, 5, 6, 4, 4, 2];
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, -0.5];
+        let a = compute_correlation_clustering(&src, &dst, &weights, Some(3)).unwrap();
+        let b = compute_correlation_clustering(&src, &dst, &weights, Some(3)).unwrap();
+        assert_eq!(a.cluster_ids, b.cluster_ids);
+        assert_eq!(a.disagreements, b.disagreements);
+    }
+
+    #[test]
+    fn test_correlation_clustering_invalid_input() {
+        assert!(compute_correlation_clustering(&[], &[], &[], None).is_err());
+        assert!(compute_correlation_clustering(&[1], &[2], &[], None).is_err());
+        assert!(compute_correlation_clustering(&[1], &[2], &[f64::NAN], None).is_err());
+    }
+}
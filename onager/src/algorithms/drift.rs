@@ -0,0 +1,559 @@
+//! Graph statistics drift module.
+//!
+//! Compares structural statistics of two snapshots of a graph, such as two
+//! loads of the same edge table, and the node rankings computed on them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{OnagerError, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+
+/// Hub change code for a node that became a hub in the new snapshot.
+pub const HUB_ADDED: i64 = 1;
+/// Hub change code for a node that stopped being a hub in the new snapshot.
+pub const HUB_REMOVED: i64 = -1;
+
+/// Structural statistics of one graph snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphSnapshotStats {
+    pub node_count: usize,
+    pub edge_count: usize,
+    pub density: f64,
+    /// Mean local clustering coefficient over all nodes
+    pub average_clustering: f64,
+}
+
+/// Result of comparing two graph snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphStatsComparison {
+    pub old: GraphSnapshotStats,
+    pub new: GraphSnapshotStats,
+    /// Two-sample Kolmogorov-Smirnov distance between the degree distributions
+    pub degree_ks_distance: f64,
+    /// Nodes that entered or left the set of hubs
+    pub hub_node_ids: Vec<i64>,
+    /// `HUB_ADDED` or `HUB_REMOVED` for each node in `hub_node_ids`
+    pub hub_changes: Vec<i64>,
+    pub hub_old_degrees: Vec<i64>,
+    pub hub_new_degrees: Vec<i64>,
+}
+
+/// Simple undirected view of a snapshot, with distinct neighbors per node.
+///
+/// Nodes are kept in ID order so that float sums over them do not depend on
+/// hash order, and the same snapshot always gives the same statistics.
+struct Snapshot {
+    neighbors: BTreeMap<i64, HashSet<i64>>,
+}
+
+impl Snapshot {
+    fn new(src: &[i64], dst: &[i64]) -> Self {
+        let mut neighbors: BTreeMap<i64, HashSet<i64>> = BTreeMap::new();
+        for (&s, &d) in src.iter().zip(dst) {
+            neighbors.entry(s).or_default();
+            neighbors.entry(d).or_default();
+            if s != d {
+                neighbors.entry(s).or_default().insert(d);
+                neighbors.entry(d).or_default().insert(s);
+            }
+        }
+        Snapshot { neighbors }
+    }
+
+    fn degree(&self, node: i64) -> usize {
+        self.neighbors.get(&node).map_or(0, HashSet::len)
+    }
+
+    fn stats(&self) -> GraphSnapshotStats {
+        let n = self.neighbors.len();
+        let m = self.neighbors.values().map(HashSet::len).sum::<usize>() / 2;
+        let possible = n * n.saturating_sub(1) / 2;
+        let clustering_sum: f64 = self
+            .neighbors
+            .values()
+            .map(|adj| {
+                let d = adj.len();
+                if d < 2 {
+                    return 0.0;
+                }
+                let links: usize = adj
+                    .iter()
+                    .map(|u| {
+                        self.neighbors
+                            .get(u)
+                            .map_or(0, |nu| nu.iter().filter(|w| adj.contains(w)).count())
+                    })
+                    .sum();
+                // Each link between two neighbors is seen from both ends
+                links as f64 / (d * (d - 1)) as f64
+            })
+            .sum();
+        GraphSnapshotStats {
+            node_count: n,
+            edge_count: m,
+            density: if possible > 0 {
+                m as f64 / possible as f64
+            } else {
+                0.0
+            },
+            average_clustering: if n > 0 {
+                clustering_sum / n as f64
+            } else {
+                0.0
+            },
+        }
+    }
+
+    fn sorted_degrees(&self) -> Vec<usize> {
+        let mut degrees: Vec<usize> = self.neighbors.values().map(HashSet::len).collect();
+        degrees.sort_unstable();
+        degrees
+    }
+
+    /// Returns the `k` nodes with the highest degree, breaking ties by smaller ID.
+    fn hubs(&self, k: usize) -> HashSet<i64> {
+        let mut nodes: Vec<(usize, i64)> = self
+            .neighbors
+            .iter()
+            .map(|(&node, adj)| (adj.len(), node))
+            .collect();
+        nodes.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        nodes.into_iter().take(k).map(|(_, node)| node).collect()
+    }
+}
+
+/// Largest gap between the empirical distribution functions of two sorted samples.
+fn ks_distance(a: &[usize], b: &[usize]) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return if a.len() == b.len() { 0.0 } else { 1.0 };
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut max_gap: f64 = 0.0;
+    while i < a.len() && j < b.len() {
+        let x = a[i].min(b[j]);
+        while i < a.len() && a[i] == x {
+            i += 1;
+        }
+        while j < b.len() && b[j] == x {
+            j += 1;
+        }
+        let gap = (i as f64 / a.len() as f64 - j as f64 / b.len() as f64).abs();
+        max_gap = max_gap.max(gap);
+    }
+    max_gap
+}
+
+/// Compare structural statistics of an old and a new graph snapshot.
+///
+/// Edges are treated as undirected, and parallel edges and self-loops are
+/// ignored. Hubs are the `hub_count` nodes with the highest degree in each
+/// snapshot, and the result lists nodes that entered or left that set, ordered
+/// by node ID.
+pub fn compare_graph_stats(
+    old_src: &[i64],
+    old_dst: &[i64],
+    new_src: &[i64],
+    new_dst: &[i64],
+    hub_count: usize,
+) -> Result<GraphStatsComparison> {
+    if old_src.len() != old_dst.len() || new_src.len() != new_dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+
+    let old = Snapshot::new(old_src, old_dst);
+    let new = Snapshot::new(new_src, new_dst);
+    let old_hubs = old.hubs(hub_count);
+    let new_hubs = new.hubs(hub_count);
+
+    let mut changed: Vec<(i64, i64)> = new_hubs
+        .difference(&old_hubs)
+        .map(|&node| (node, HUB_ADDED))
+        .chain(
+            old_hubs
+                .difference(&new_hubs)
+                .map(|&node| (node, HUB_REMOVED)),
+        )
+        .collect();
+    changed.sort_unstable();
+
+    Ok(GraphStatsComparison {
+        old: old.stats(),
+        new: new.stats(),
+        degree_ks_distance: ks_distance(&old.sorted_degrees(), &new.sorted_degrees()),
+        hub_old_degrees: changed
+            .iter()
+            .map(|&(node, _)| old.degree(node) as i64)
+            .collect(),
+        hub_new_degrees: changed
+            .iter()
+            .map(|&(node, _)| new.degree(node) as i64)
+            .collect(),
+        hub_changes: changed.iter().map(|&(_, change)| change).collect(),
+        hub_node_ids: changed.into_iter().map(|(node, _)| node).collect(),
+    })
+}
+
+/// Correlation coefficient used to compare two node rankings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RankCorrelation {
+    /// Pearson correlation of the ranks, with tied scores sharing their mean rank.
+    #[default]
+    Spearman,
+    /// Kendall's tau-b, which compares the order of every pair of nodes.
+    Kendall,
+}
+
+impl FromStr for RankCorrelation {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "spearman" => Ok(RankCorrelation::Spearman),
+            "kendall" => Ok(RankCorrelation::Kendall),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown rank correlation '{}', expected 'spearman' or 'kendall'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Result of comparing two node rankings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankingComparison {
+    /// Rank correlation over the nodes scored in both rankings, or NaN when
+    /// there are fewer than two such nodes or one side gives them all the same score
+    pub correlation: f64,
+    /// Number of nodes scored in both rankings
+    pub common_count: usize,
+    /// Nodes in the top `top_k` of both rankings, divided by the size of the
+    /// larger of the two top sets
+    pub top_k_overlap: f64,
+}
+
+/// Checks one ranking and maps each node to its score.
+fn ranking_scores(nodes: &[i64], scores: &[f64], which: &str) -> Result<HashMap<i64, f64>> {
+    if nodes.len() != scores.len() {
+        return Err(OnagerError::InvalidArgument(format!(
+            "{} ranking nodes and scores must have same length",
+            which
+        )));
+    }
+    if nodes.is_empty() {
+        return Err(OnagerError::InvalidArgument(format!(
+            "{} ranking is empty",
+            which
+        )));
+    }
+    let mut map = HashMap::with_capacity(nodes.len());
+    for (&node, &score) in nodes.iter().zip(scores) {
+        if !score.is_finite() {
+            return Err(OnagerError::InvalidArgument(format!(
+                "Scores must be finite, got {} for node {}",
+                score, node
+            )));
+        }
+        if map.insert(node, score).is_some() {
+            return Err(OnagerError::InvalidArgument(format!(
+                "Node {} appears more than once in the {} ranking",
+                node,
+                which.to_ascii_lowercase()
+            )));
+        }
+    }
+    Ok(map)
+}
+
+/// Returns the `k` nodes with the highest score, breaking ties by smaller ID.
+fn top_nodes(scores: &HashMap<i64, f64>, k: usize) -> HashSet<i64> {
+    let mut nodes: Vec<(i64, f64)> = scores.iter().map(|(&node, &score)| (node, score)).collect();
+    nodes.sort_unstable_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    nodes.into_iter().take(k).map(|(node, _)| node).collect()
+}
+
+/// Ranks values from 1, giving tied values the mean of the ranks they span.
+fn average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_unstable_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i + 1;
+        while j < order.len() && values[order[j]] == values[order[i]] {
+            j += 1;
+        }
+        // Positions i..j hold ranks i + 1 through j
+        let rank = (i + j + 1) as f64 / 2.0;
+        for &idx in &order[i..j] {
+            ranks[idx] = rank;
+        }
+        i = j;
+    }
+    ranks
+}
+
+fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (&a, &b) in x.iter().zip(y) {
+        cov += (a - mean_x) * (b - mean_y);
+        var_x += (a - mean_x) * (a - mean_x);
+        var_y += (b - mean_y) * (b - mean_y);
+    }
+    if var_x == 0.0 || var_y == 0.0 {
+        return f64::NAN;
+    }
+    cov / (var_x * var_y).sqrt()
+}
+
+fn tied_pairs(run: usize) -> u64 {
+    (run as u64) * (run as u64).saturating_sub(1) / 2
+}
+
+/// Sums `tied_pairs` over runs of equal keys in a sorted sequence.
+fn count_tied_pairs<T: PartialEq>(sorted: &[T]) -> u64 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < sorted.len() {
+        let mut j = i + 1;
+        while j < sorted.len() && sorted[j] == sorted[i] {
+            j += 1;
+        }
+        total += tied_pairs(j - i);
+        i = j;
+    }
+    total
+}
+
+/// Sorts `values` with a bottom-up merge sort and returns the number of
+/// pairs that were strictly out of order.
+fn count_inversions(values: &mut [f64]) -> u64 {
+    let n = values.len();
+    let mut buffer = values.to_vec();
+    let mut inversions = 0;
+    let mut width = 1;
+    while width < n {
+        let mut start = 0;
+        while start < n {
+            let mid = (start + width).min(n);
+            let end = (start + 2 * width).min(n);
+            let (mut i, mut j, mut k) = (start, mid, start);
+            while i < mid && j < end {
+                if values[j] < values[i] {
+                    buffer[k] = values[j];
+                    inversions += (mid - i) as u64;
+                    j += 1;
+                } else {
+                    buffer[k] = values[i];
+                    i += 1;
+                }
+                k += 1;
+            }
+            buffer[k..k + mid - i].copy_from_slice(&values[i..mid]);
+            k += mid - i;
+            buffer[k..k + end - j].copy_from_slice(&values[j..end]);
+            start = end;
+        }
+        values.copy_from_slice(&buffer);
+        width *= 2;
+    }
+    inversions
+}
+
+/// Kendall's tau-b in O(n log n) using Knight's merge sort method.
+fn kendall_tau_b(x: &[f64], y: &[f64]) -> f64 {
+    let mut pairs: Vec<(f64, f64)> = x.iter().copied().zip(y.iter().copied()).collect();
+    pairs.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let total = tied_pairs(pairs.len());
+    let x_ties = count_tied_pairs(&pairs.iter().map(|p| p.0).collect::<Vec<_>>());
+    let joint_ties = count_tied_pairs(&pairs);
+
+    let mut ys: Vec<f64> = pairs.iter().map(|p| p.1).collect();
+    let discordant = count_inversions(&mut ys);
+    let y_ties = count_tied_pairs(&ys);
+
+    let denominator = ((total - x_ties) as f64 * (total - y_ties) as f64).sqrt();
+    if denominator == 0.0 {
+        return f64::NAN;
+    }
+    // Concordant minus discordant pairs, leaving out pairs tied on either side
+    let net = (total + joint_ties) as f64 - (x_ties + y_ties) as f64 - 2.0 * discordant as f64;
+    net / denominator
+}
+
+/// Compare two node rankings, such as centrality scores computed before and
+/// after a change to a graph.
+///
+/// The correlation uses the nodes scored in both rankings. The top-k overlap
+/// uses every node, so a node that enters or leaves the top `top_k` counts
+/// against the overlap even when the other ranking does not score it.
+pub fn compare_rankings(
+    a_nodes: &[i64],
+    a_scores: &[f64],
+    b_nodes: &[i64],
+    b_scores: &[f64],
+    method: RankCorrelation,
+    top_k: usize,
+) -> Result<RankingComparison> {
+    let a = ranking_scores(a_nodes, a_scores, "First")?;
+    let b = ranking_scores(b_nodes, b_scores, "Second")?;
+    if top_k == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "top_k must be positive".to_string(),
+        ));
+    }
+
+    let (xs, ys): (Vec<f64>, Vec<f64>) = a_nodes
+        .iter()
+        .zip(a_scores)
+        .filter_map(|(node, &x)| b.get(node).map(|&y| (x, y)))
+        .unzip();
+    let correlation = if xs.len() < 2 {
+        f64::NAN
+    } else {
+        match method {
+            RankCorrelation::Spearman => pearson(&average_ranks(&xs), &average_ranks(&ys)),
+            RankCorrelation::Kendall => kendall_tau_b(&xs, &ys),
+        }
+    };
+
+    let top_a = top_nodes(&a, top_k);
+    let top_b = top_nodes(&b, top_k);
+    let shared = top_a.intersection(&top_b).count();
+
+    Ok(RankingComparison {
+        correlation,
+        common_count: xs.len(),
+        top_k_overlap: shared as f64 / top_a.len().max(top_b.len()) as f64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_snapshots_have_no_drift() {
+        let src = vec![1, 2, 3, 3];
+        let dst = vec![2, 3, 1, 4];
+
+        let result = compare_graph_stats(&src, &dst, &src, &dst, 2).unwrap();
+
+        assert_eq!(result.old, result.new);
+        assert_eq!(result.degree_ks_distance, 0.0);
+        assert!(result.hub_node_ids.is_empty());
+    }
+
+    #[test]
+    fn test_triangle_to_star() {
+        // Old: triangle 1-2-3 plus a parallel edge and a self-loop
+        let old_src = vec![1, 2, 3, 2, 3];
+        let old_dst = vec![2, 3, 1, 1, 3];
+        // New: star around 4
+        let new_src = vec![4, 4, 4];
+        let new_dst = vec![1, 2, 3];
+
+        let result = compare_graph_stats(&old_src, &old_dst, &new_src, &new_dst, 1).unwrap();
+
+        assert_eq!(result.old.node_count, 3);
+        assert_eq!(result.old.edge_count, 3);
+        assert_eq!(result.old.density, 1.0);
+        assert_eq!(result.old.average_clustering, 1.0);
+        assert_eq!(result.new.node_count, 4);
+        assert_eq!(result.new.density, 0.5);
+        assert_eq!(result.new.average_clustering, 0.0);
+        // Old degrees are all 2, new degrees are 1, 1, 1, and 3
+        assert_eq!(result.degree_ks_distance, 0.75);
+        assert_eq!(result.hub_node_ids, vec![1, 4]);
+        assert_eq!(result.hub_changes, vec![HUB_REMOVED, HUB_ADDED]);
+        assert_eq!(result.hub_old_degrees, vec![2, 0]);
+        assert_eq!(result.hub_new_degrees, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_empty_old_snapshot() {
+        let result = compare_graph_stats(&[], &[], &[1], &[2], 1).unwrap();
+
+        assert_eq!(result.old.node_count, 0);
+        assert_eq!(result.degree_ks_distance, 1.0);
+        assert!(compare_graph_stats(&[1], &[], &[1], &[2], 1).is_err());
+    }
+
+    #[test]
+    fn test_compare_identical_and_reversed_rankings() {
+        let nodes = vec![1, 2, 3, 4];
+        let scores = vec![0.4, 0.3, 0.2, 0.1];
+        let reversed = vec![0.1, 0.2, 0.3, 0.4];
+
+        for method in [RankCorrelation::Spearman, RankCorrelation::Kendall] {
+            let same = compare_rankings(&nodes, &scores, &nodes, &scores, method, 2).unwrap();
+            assert!((same.correlation - 1.0).abs() < 1e-12);
+            assert_eq!(same.common_count, 4);
+            assert_eq!(same.top_k_overlap, 1.0);
+
+            let flipped = compare_rankings(&nodes, &scores, &nodes, &reversed, method, 2).unwrap();
+            assert!((flipped.correlation + 1.0).abs() < 1e-12);
+            assert_eq!(flipped.top_k_overlap, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_compare_rankings_with_ties_and_missing_nodes() {
+        // Node 5 is only in the first ranking and node 6 only in the second
+        let a_nodes = vec![1, 2, 3, 4, 5];
+        let a_scores = vec![1.0, 2.0, 3.0, 4.0, 9.0];
+        let b_nodes = vec![6, 4, 3, 2, 1];
+        let b_scores = vec![9.0, 3.0, 3.0, 2.0, 1.0];
+
+        let kendall = compare_rankings(
+            &a_nodes,
+            &a_scores,
+            &b_nodes,
+            &b_scores,
+            RankCorrelation::Kendall,
+            2,
+        )
+        .unwrap();
+        // Five concordant pairs, one pair tied in the second ranking
+        assert_eq!(kendall.common_count, 4);
+        assert!((kendall.correlation - 5.0 / 30f64.sqrt()).abs() < 1e-12);
+        // Top 2 are {5, 4} and {6, 3} under the smaller-ID tie break
+        assert_eq!(kendall.top_k_overlap, 0.0);
+
+        let spearman = compare_rankings(
+            &a_nodes,
+            &a_scores,
+            &b_nodes,
+            &b_scores,
+            RankCorrelation::Spearman,
+            3,
+        )
+        .unwrap();
+        // Ranks 1, 2, 3, 4 against 1, 2, 3.5, 3.5
+        assert!((spearman.correlation - 4.5 / (5.0f64 * 4.5).sqrt()).abs() < 1e-12);
+        assert!((spearman.top_k_overlap - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compare_rankings_rejects_bad_input() {
+        let spearman = RankCorrelation::Spearman;
+        assert!(compare_rankings(&[1], &[1.0], &[2], &[1.0], spearman, 1)
+            .unwrap()
+            .correlation
+            .is_nan());
+        assert!(compare_rankings(&[1, 1], &[1.0, 2.0], &[1], &[1.0], spearman, 1).is_err());
+        assert!(compare_rankings(&[1], &[f64::NAN], &[1], &[1.0], spearman, 1).is_err());
+        assert!(compare_rankings(&[1], &[1.0], &[], &[], spearman, 1).is_err());
+        assert!(compare_rankings(&[1], &[1.0], &[1], &[1.0], spearman, 0).is_err());
+        assert!("pearson".parse::<RankCorrelation>().is_err());
+        assert_eq!(
+            "Kendall".parse::<RankCorrelation>().unwrap(),
+            RankCorrelation::Kendall
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Output size estimation module.
+//!
+//! Upper bounds on the number of rows an algorithm can return, computed from
+//! the edge arrays alone without running the algorithm. Callers use these to
+//! size output buffers before the real call.
+
+use std::collections::HashSet;
+
+use crate::error::{OnagerError, Result};
+
+fn check_lengths(src: &[i64], dst: &[i64]) -> Result<()> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn distinct_node_count(src: &[i64], dst: &[i64]) -> usize {
+    src.iter().chain(dst.iter()).collect::<HashSet<_>>().len()
+}
+
+/// Upper bound for per-node outputs such as centrality scores, community
+/// assignments, traversal orders, and node sets.
+///
+/// This is the number of distinct nodes in the edge list.
+pub fn estimate_node_output_size(src: &[i64], dst: &[i64]) -> Result<usize> {
+    check_lengths(src, dst)?;
+    Ok(distinct_node_count(src, dst))
+}
+
+/// Upper bound for node-pair outputs such as link prediction scores and
+/// all-pairs shortest paths.
+///
+/// This is the number of ordered node pairs, including self pairs.
+pub fn estimate_node_pair_output_size(src: &[i64], dst: &[i64]) -> Result<usize> {
+    check_lengths(src, dst)?;
+    let n = distinct_node_count(src, dst);
+    n.checked_mul(n).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Node pair count overflows for {} nodes", n))
+    })
+}
+
+/// Upper bound for edge outputs such as spanning trees, ego graphs, and
+/// induced subgraphs.
+///
+/// These never contain more edges than the input.
+pub fn estimate_edge_output_size(src: &[i64], dst: &[i64]) -> Result<usize> {
+    check_lengths(src, dst)?;
+    Ok(src.len())
+}
+
+/// Upper bound for path outputs such as a TSP tour.
+///
+/// A path visits each node at most once, plus one entry for a tour that
+/// returns to its start node.
+pub fn estimate_path_output_size(src: &[i64], dst: &[i64]) -> Result<usize> {
+    check_lengths(src, dst)?;
+    let n = distinct_node_count(src, dst);
+    Ok(if n == 0 { 0 } else { n + 1 })
+}
+
+/// Upper bound for the number of edges a generator can produce for `n` nodes.
+///
+/// Generators produce simple undirected graphs, so this is `n * (n - 1) / 2`.
+pub fn estimate_generated_edge_count(n: usize) -> Result<usize> {
+    n.checked_mul(n.saturating_sub(1))
+        .map(|pairs| pairs / 2)
+        .ok_or_else(|| {
+            OnagerError::InvalidArgument(format!("Edge count overflows for {} nodes", n))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_node_output_size() {
+        let src = vec![1, 2, 3, 1];
+        let dst = vec![2, 3, 1, 4];
+        assert_eq!(estimate_node_output_size(&src, &dst).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_estimate_node_pair_output_size() {
+        let src = vec![1, 2];
+        let dst = vec![2, 3];
+        assert_eq!(estimate_node_pair_output_size(&src, &dst).unwrap(), 9);
+    }
+
+    #[test]
+    fn test_estimate_edge_output_size() {
+        let src = vec![1, 2, 3];
+        let dst = vec![2, 3, 1];
+        assert_eq!(estimate_edge_output_size(&src, &dst).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_estimate_path_output_size() {
+        let src = vec![1, 2, 3];
+        let dst = vec![2, 3, 1];
+        assert_eq!(estimate_path_output_size(&src, &dst).unwrap(), 4);
+        assert_eq!(estimate_path_output_size(&[], &[]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_estimate_generated_edge_count() {
+        assert_eq!(estimate_generated_edge_count(0).unwrap(), 0);
+        assert_eq!(estimate_generated_edge_count(1).unwrap(), 0);
+        assert_eq!(estimate_generated_edge_count(10).unwrap(), 45);
+        assert!(estimate_generated_edge_count(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_estimate_mismatched_lengths() {
+        let result = estimate_node_output_size(&[1, 2], &[2]);
+        assert!(result.is_err());
+    }
+}
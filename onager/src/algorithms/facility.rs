@@ -0,0 +1,297 @@
+//! Facility location module.
+//!
+//! Choosing facility nodes that serve the rest of the graph: p-median and
+//! Voronoi partitions around given seed nodes.
+
+use serde::{Deserialize, Serialize};
+
+use super::centrality::{bounded_dijkstra, build_weighted_adjacency, WeightedAdjacency};
+use crate::builder::index_edges;
+use crate::error::{OnagerError, Result};
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Result of a p-median computation.
+///
+/// `facilities` holds the selected nodes. Row `i` assigns `node_ids[i]` to its
+/// nearest facility `assignments[i]` at distance `distances[i]`, and every
+/// facility is assigned to itself. Nodes that cannot reach any facility are
+/// left out of the assignment rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PMedianResult {
+    pub facilities: Vec<i64>,
+    pub node_ids: Vec<i64>,
+    pub assignments: Vec<i64>,
+    pub distances: Vec<f64>,
+    pub total_distance: f64,
+}
+
+/// Result of a graph Voronoi partition.
+///
+/// Row `i` assigns `node_ids[i]` to its nearest seed `seeds[i]` at distance
+/// `distances[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoronoiResult {
+    pub node_ids: Vec<i64>,
+    pub seeds: Vec<i64>,
+    pub distances: Vec<f64>,
+}
+
+/// Cost of serving every node from a set of facilities.
+///
+/// Unserved nodes are compared first, so a set that reaches more nodes always
+/// wins over one with a smaller total distance.
+fn service_cost(nearest: impl Iterator<Item = f64>) -> (usize, f64) {
+    nearest.fold((0, 0.0), |(unserved, total), d| {
+        if d.is_finite() {
+            (unserved, total + d)
+        } else {
+            (unserved + 1, total)
+        }
+    })
+}
+
+fn is_better(a: (usize, f64), b: (usize, f64)) -> bool {
+    a.0 < b.0 || (a.0 == b.0 && a.1 < b.1 - 1e-12)
+}
+
+/// Select `p` facility nodes that minimize the total distance from every node
+/// to its nearest facility.
+///
+/// Each node counts as one unit of demand. Facilities are first added greedily,
+/// one at a time, and then improved by swapping a facility for a non-facility
+/// node while the swap lowers the total distance (Teitz-Bart interchange).
+/// Edges are undirected and weights must be non-negative (empty `weights`
+/// means unit weights). The heuristic keeps the distance matrix in memory, so
+/// it needs O(n^2) memory and is meant for graphs with up to a few thousand
+/// nodes.
+pub fn compute_p_median(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    p: usize,
+) -> Result<PMedianResult> {
+    let adj = build_weighted_adjacency(src, dst, weights)?;
+    let n = adj.node_ids.len();
+    if p == 0 || p > n {
+        return Err(OnagerError::InvalidArgument(format!(
+            "p must be between 1 and the number of nodes ({}), got {}",
+            n, p
+        )));
+    }
+
+    // dist[c * n + v] is the distance between candidate c and node v
+    let mut dist = vec![f64::INFINITY; n * n];
+    for c in 0..n {
+        let tree = bounded_dijkstra(&adj, c, None);
+        for &v in &tree.order {
+            dist[c * n + v] = tree.dist[v];
+        }
+    }
+    let row = |c: usize| &dist[c * n..(c + 1) * n];
+
+    // Greedy construction
+    let mut facilities: Vec<usize> = Vec::with_capacity(p);
+    let mut is_facility = vec![false; n];
+    let mut nearest = vec![f64::INFINITY; n];
+    for _ in 0..p {
+        let mut best: Option<(usize, (usize, f64))> = None;
+        for c in (0..n).filter(|&c| !is_facility[c]) {
+            let cost = service_cost(nearest.iter().zip(row(c)).map(|(&a, &b)| a.min(b)));
+            if best.is_none_or(|(_, b)| is_better(cost, b)) {
+                best = Some((c, cost));
+            }
+        }
+        let Some((c, _)) = best else {
+            break;
+        };
+        facilities.push(c);
+        is_facility[c] = true;
+        for (d, &dc) in nearest.iter_mut().zip(row(c)) {
+            *d = d.min(dc);
+        }
+    }
+
+    // Interchange until no single swap improves the cost
+    let mut current = service_cost(nearest.iter().copied());
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for slot in 0..facilities.len() {
+            // Nearest distance to the other facilities
+            let mut without = vec![f64::INFINITY; n];
+            for (other, &f) in facilities.iter().enumerate() {
+                if other != slot {
+                    for (d, &df) in without.iter_mut().zip(row(f)) {
+                        *d = d.min(df);
+                    }
+                }
+            }
+            for c in (0..n).filter(|&c| !is_facility[c]) {
+                let cost = service_cost(without.iter().zip(row(c)).map(|(&a, &b)| a.min(b)));
+                if is_better(cost, current) {
+                    is_facility[facilities[slot]] = false;
+                    is_facility[c] = true;
+                    facilities[slot] = c;
+                    current = cost;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut result = PMedianResult {
+        facilities: facilities.iter().map(|&f| adj.node_ids[f]).collect(),
+        node_ids: Vec::with_capacity(n),
+        assignments: Vec::with_capacity(n),
+        distances: Vec::with_capacity(n),
+        total_distance: current.1,
+    };
+    for v in 0..n {
+        // Ties go to the facility selected first
+        let mut assigned: Option<(usize, f64)> = None;
+        for &f in &facilities {
+            let d = dist[f * n + v];
+            if d.is_finite() && assigned.is_none_or(|(_, best)| d < best) {
+                assigned = Some((f, d));
+            }
+        }
+        if let Some((f, d)) = assigned {
+            result.node_ids.push(adj.node_ids[v]);
+            result.assignments.push(adj.node_ids[f]);
+            result.distances.push(d);
+        }
+    }
+    Ok(result)
+}
+
+/// Partition the graph into Voronoi cells around the given seed nodes.
+///
+/// Each node is assigned to the seed with the smallest shortest path
+/// distance, using a single multi-source Dijkstra run. Ties go to the seed
+/// listed first, and duplicate seeds are ignored. Edges are undirected and
+/// weights must be non-negative (empty `weights` means unit weights). Nodes
+/// that cannot reach any seed are left out of the result.
+pub fn compute_voronoi(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    seeds: &[i64],
+) -> Result<VoronoiResult> {
+    let indexed = index_edges(src, dst, weights)?;
+    if seeds.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "seeds must not be empty".to_string(),
+        ));
+    }
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+
+    let n = adj.node_ids.len();
+    let mut dist = vec![f64::INFINITY; n];
+    // owner[v] is the rank of the seed that currently claims v
+    let mut owner = vec![usize::MAX; n];
+    let mut settled = vec![false; n];
+    let mut heap = BinaryHeap::new();
+    for (rank, &seed) in seeds.iter().enumerate() {
+        let s = indexed
+            .nodes
+            .get(seed)
+            .ok_or_else(|| OnagerError::InvalidArgument(format!("Seed node {} not found", seed)))?;
+        if owner[s] == usize::MAX {
+            dist[s] = 0.0;
+            owner[s] = rank;
+            heap.push(Reverse((OrderedFloat(0.0), rank, s)));
+        }
+    }
+    while let Some(Reverse((OrderedFloat(d), rank, u))) = heap.pop() {
+        if settled[u] || (d, rank) != (dist[u], owner[u]) {
+            continue;
+        }
+        settled[u] = true;
+        for &(v, w) in &adj.neighbors[u] {
+            let alt = d + w;
+            if !settled[v] && (alt < dist[v] || (alt == dist[v] && rank < owner[v])) {
+                dist[v] = alt;
+                owner[v] = rank;
+                heap.push(Reverse((OrderedFloat(alt), rank, v)));
+            }
+        }
+    }
+
+    let mut result = VoronoiResult {
+        node_ids: Vec::with_capacity(n),
+        seeds: Vec::with_capacity(n),
+        distances: Vec::with_capacity(n),
+    };
+    for v in (0..n).filter(|&v| settled[v]) {
+        result.node_ids.push(adj.node_ids[v]);
+        result.seeds.push(seeds[owner[v]]);
+        result.distances.push(dist[v]);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_p_median_picks_cluster_centers() {
+        // Two stars centered at 1 and 10, joined by a long edge between leaves
+        let src = vec![1, 1, 1, 10, 10, 10, 4];
+        let dst = vec![2, 3, 4, 11, 12, 13, 13];
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 10.0];
+        let result = compute_p_median(&src, &dst, &weights, 2).unwrap();
+
+        let mut facilities = result.facilities.clone();
+        facilities.sort_unstable();
+        assert_eq!(facilities, vec![1, 10]);
+        assert_eq!(result.node_ids.len(), 8);
+        assert_eq!(result.total_distance, 6.0);
+        let at = |node: i64| result.node_ids.iter().position(|&v| v == node).unwrap();
+        assert_eq!(result.assignments[at(2)], 1);
+        assert_eq!(result.assignments[at(13)], 10);
+        assert_eq!(result.distances[at(10)], 0.0);
+    }
+
+    #[test]
+    fn test_p_median_single_facility_is_path_median() {
+        let result = compute_p_median(&[1, 2, 3, 4], &[2, 3, 4, 5], &[], 1).unwrap();
+        assert_eq!(result.facilities, vec![3]);
+        assert_eq!(result.total_distance, 6.0);
+    }
+
+    #[test]
+    fn test_p_median_invalid_p() {
+        assert!(compute_p_median(&[1], &[2], &[], 0).is_err());
+        assert!(compute_p_median(&[1], &[2], &[], 3).is_err());
+        assert!(compute_p_median(&[], &[], &[], 1).is_err());
+    }
+
+    #[test]
+    fn test_voronoi_assigns_nearest_seed() {
+        // Path 1-2-3-4-5 plus an isolated edge 8-9
+        let src = vec![1, 2, 3, 4, 8];
+        let dst = vec![2, 3, 4, 5, 9];
+        let weights = vec![1.0, 1.0, 1.0, 2.0, 1.0];
+        let result = compute_voronoi(&src, &dst, &weights, &[1, 4, 1]).unwrap();
+
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!(result.seeds, vec![1, 1, 4, 4, 4]);
+        assert_eq!(result.distances, vec![0.0, 1.0, 1.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_voronoi_ties_go_to_first_seed() {
+        let result = compute_voronoi(&[1, 2], &[2, 3], &[], &[3, 1]).unwrap();
+        assert_eq!(result.seeds, vec![1, 3, 3]);
+    }
+
+    #[test]
+    fn test_voronoi_invalid_seeds() {
+        assert!(compute_voronoi(&[1], &[2], &[], &[]).is_err());
+        assert!(compute_voronoi(&[1], &[2], &[], &[7]).is_err());
+    }
+}
@@ -0,0 +1,1088 @@
+//! Maximum flow module.
+//!
+//! Dinic's algorithm on a residual network, exposed as the maximum flow and
+//! minimum cut of a directed network with edge capacities, and shared by the
+//! algorithms that reduce to minimum cuts, minimum edge and vertex cuts
+//! between two nodes, edge and node connectivity, and the global minimum cut
+//! of a weighted graph.
+
+use serde::{Deserialize, Serialize};
+
+use super::centrality::build_weighted_adjacency;
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Residual capacity at or below which an edge counts as saturated.
+const EPSILON: f64 = 1e-9;
+
+/// Residual network over nodes `0..n`, where edge `e` and its reverse `e ^ 1`
+/// are stored next to each other.
+pub(crate) struct FlowGraph {
+    edges_of: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    residual: Vec<f64>,
+}
+
+impl FlowGraph {
+    pub(crate) fn new(n: usize) -> Self {
+        FlowGraph {
+            edges_of: vec![Vec::new(); n],
+            to: Vec::new(),
+            residual: Vec::new(),
+        }
+    }
+
+    /// Adds an edge from `u` to `v` with the given capacity and returns its index.
+    pub(crate) fn add_edge(&mut self, u: usize, v: usize, capacity: f64) -> usize {
+        let e = self.to.len();
+        self.edges_of[u].push(e);
+        self.to.push(v);
+        self.residual.push(capacity);
+        self.edges_of[v].push(e + 1);
+        self.to.push(u);
+        self.residual.push(0.0);
+        e
+    }
+
+    /// Breadth-first levels from `s` over unsaturated edges, or `None` when
+    /// `t` cannot be reached.
+    fn levels(&self, s: usize, t: usize) -> Option<Vec<usize>> {
+        let mut level = vec![usize::MAX; self.edges_of.len()];
+        level[s] = 0;
+        let mut queue = std::collections::VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for &e in &self.edges_of[u] {
+                let v = self.to[e];
+                if self.residual[e] > EPSILON && level[v] == usize::MAX {
+                    level[v] = level[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        (level[t] != usize::MAX).then_some(level)
+    }
+
+    /// Sends flow from `s` to `t` until no augmenting path is left and
+    /// returns the total. Calling it again continues from the current flow.
+    pub(crate) fn max_flow(&mut self, s: usize, t: usize) -> f64 {
+        if s == t {
+            return 0.0;
+        }
+        let mut total = 0.0;
+        while let Some(level) = self.levels(s, t) {
+            // Blocking flow with an explicit path stack, so long paths do not
+            // overflow the call stack
+            let mut next = vec![0usize; self.edges_of.len()];
+            let mut dead = vec![false; self.edges_of.len()];
+            let mut path: Vec<usize> = Vec::new();
+            let mut u = s;
+            loop {
+                if u == t {
+                    let bottleneck = path
+                        .iter()
+                        .map(|&e| self.residual[e])
+                        .fold(f64::INFINITY, f64::min);
+                    for &e in &path {
+                        self.residual[e] -= bottleneck;
+                        self.residual[e ^ 1] += bottleneck;
+                    }
+                    total += bottleneck;
+                    let keep = path
+                        .iter()
+                        .position(|&e| self.residual[e] <= EPSILON)
+                        .unwrap_or(path.len());
+                    path.truncate(keep);
+                    u = path.last().map_or(s, |&e| self.to[e]);
+                    continue;
+                }
+                let mut advanced = false;
+                while next[u] < self.edges_of[u].len() {
+                    let e = self.edges_of[u][next[u]];
+                    let v = self.to[e];
+                    if self.residual[e] > EPSILON && level[v] == level[u] + 1 && !dead[v] {
+                        path.push(e);
+                        u = v;
+                        advanced = true;
+                        break;
+                    }
+                    next[u] += 1;
+                }
+                if advanced {
+                    continue;
+                }
+                dead[u] = true;
+                match path.pop() {
+                    Some(e) => {
+                        u = self.to[e ^ 1];
+                        next[u] += 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+        total
+    }
+
+    /// Nodes reachable from `s` over unsaturated edges, which after
+    /// `max_flow` form the source side of a minimum cut.
+    pub(crate) fn source_side(&self, s: usize) -> Vec<bool> {
+        let mut reached = vec![false; self.edges_of.len()];
+        reached[s] = true;
+        let mut stack = vec![s];
+        while let Some(u) = stack.pop() {
+            for &e in &self.edges_of[u] {
+                let v = self.to[e];
+                if self.residual[e] > EPSILON && !reached[v] {
+                    reached[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        reached
+    }
+}
+
+/// Simple undirected view of an edge list.
+pub(crate) struct SimpleGraph {
+    /// Node IDs in ascending order.
+    pub(crate) nodes: Vec<i64>,
+    /// Deduplicated edges `(u, v)` with `u < v` as indices into `nodes`,
+    /// without self-loops, in ascending order.
+    pub(crate) edges: Vec<(usize, usize)>,
+}
+
+/// Builds the simple undirected view of an edge list.
+pub(crate) fn simple_undirected(src: &[i64], dst: &[i64]) -> Result<SimpleGraph> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    let index = NodeIndex::ascending(src, dst);
+    let mut edges: Vec<(usize, usize)> = src
+        .iter()
+        .zip(dst)
+        .filter(|(u, v)| u != v)
+        .map(|(&u, &v)| {
+            let (a, b) = (index.of(u), index.of(v));
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+    Ok(SimpleGraph {
+        nodes: index.node_ids,
+        edges,
+    })
+}
+
+/// Indices of two distinct nodes to separate.
+fn cut_endpoints(nodes: &[i64], a: i64, b: i64) -> Result<(usize, usize)> {
+    if a == b {
+        return Err(OnagerError::InvalidArgument(
+            "Cut endpoints must be different nodes".to_string(),
+        ));
+    }
+    let find = |v: i64| {
+        nodes
+            .binary_search(&v)
+            .map_err(|_| OnagerError::NodeNotFound(v))
+    };
+    Ok((find(a)?, find(b)?))
+}
+
+/// Network where every edge of a simple graph carries one unit each way, so
+/// the maximum flow between two nodes counts edge-disjoint paths.
+fn edge_network(n: usize, edges: &[(usize, usize)]) -> FlowGraph {
+    let mut network = FlowGraph::new(n);
+    for &(u, v) in edges {
+        network.add_edge(u, v, 1.0);
+        network.add_edge(v, u, 1.0);
+    }
+    network
+}
+
+/// Network where node v enters at 2v and exits at 2v + 1 through a unit
+/// edge, so the maximum flow from `2s + 1` to `2t` counts paths between `s`
+/// and `t` that share no other node. A direct edge between `s` and `t` counts
+/// as one path.
+fn split_network(n: usize, edges: &[(usize, usize)], s: usize, t: usize) -> FlowGraph {
+    // Such a flow is below n, so n works as the capacity of uncuttable edges
+    let unbounded = n as f64;
+    let mut network = FlowGraph::new(2 * n);
+    for v in 0..n {
+        let capacity = if v == s || v == t { unbounded } else { 1.0 };
+        network.add_edge(2 * v, 2 * v + 1, capacity);
+    }
+    for &(u, v) in edges {
+        let capacity = if (u, v) == (s.min(t), s.max(t)) {
+            1.0
+        } else {
+            unbounded
+        };
+        network.add_edge(2 * u + 1, 2 * v, capacity);
+        network.add_edge(2 * v + 1, 2 * u, capacity);
+    }
+    network
+}
+
+/// Result of a maximum flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaxFlowResult {
+    /// Total flow from the source to the sink.
+    pub flow_value: f64,
+    /// Input edges in input order, with the flow each one carries.
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    pub flows: Vec<f64>,
+}
+
+/// Result of a minimum cut in a directed network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinCutResult {
+    /// Total capacity of the cut edges, which equals the maximum flow.
+    pub cut_value: f64,
+    /// Cut edges from the source side to the sink side, in input order.
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    pub capacities: Vec<f64>,
+}
+
+/// Directed network built from an edge list with capacities.
+struct CapacityNetwork {
+    network: FlowGraph,
+    /// Index of the forward residual edge of every input edge.
+    arcs: Vec<usize>,
+    capacities: Vec<f64>,
+    source: usize,
+    sink: usize,
+}
+
+/// Builds the network, checking capacities and the source and sink.
+fn capacity_network(
+    src: &[i64],
+    dst: &[i64],
+    capacities: &[f64],
+    source: i64,
+    sink: i64,
+) -> Result<CapacityNetwork> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if !capacities.is_empty() && capacities.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "capacities must be empty or same length as edges".to_string(),
+        ));
+    }
+    if let Some(&c) = capacities.iter().find(|c| !c.is_finite() || **c < 0.0) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge capacities must be finite and non-negative, got {}",
+            c
+        )));
+    }
+    if source == sink {
+        return Err(OnagerError::InvalidArgument(
+            "Source and sink must be different nodes".to_string(),
+        ));
+    }
+
+    let index = NodeIndex::ascending(src, dst);
+    let find = |v: i64| index.get(v).ok_or(OnagerError::NodeNotFound(v));
+    let (s, t) = (find(source)?, find(sink)?);
+
+    let capacities: Vec<f64> = if capacities.is_empty() {
+        vec![1.0; src.len()]
+    } else {
+        capacities.to_vec()
+    };
+    let mut network = FlowGraph::new(index.len());
+    let arcs = src
+        .iter()
+        .zip(dst)
+        .zip(&capacities)
+        .map(|((&u, &v), &c)| network.add_edge(index.of(u), index.of(v), c))
+        .collect();
+    Ok(CapacityNetwork {
+        network,
+        arcs,
+        capacities,
+        source: s,
+        sink: t,
+    })
+}
+
+/// Compute a maximum flow from `source` to `sink`.
+///
+/// Edges are directed and carry at most their capacity, where empty
+/// capacities give every edge capacity 1.0. Parallel edges are kept apart, so
+/// each input edge reports its own flow. Uses Dinic's algorithm, so the flow
+/// is one of possibly several maximum flows.
+pub fn compute_max_flow(
+    src: &[i64],
+    dst: &[i64],
+    capacities: &[f64],
+    source: i64,
+    sink: i64,
+) -> Result<MaxFlowResult> {
+    let CapacityNetwork {
+        mut network,
+        arcs,
+        capacities,
+        source,
+        sink,
+    } = capacity_network(src, dst, capacities, source, sink)?;
+    let flow_value = network.max_flow(source, sink);
+    Ok(MaxFlowResult {
+        flow_value,
+        src: src.to_vec(),
+        dst: dst.to_vec(),
+        flows: arcs
+            .iter()
+            .zip(&capacities)
+            .map(|(&e, &c)| (c - network.residual[e]).max(0.0))
+            .collect(),
+    })
+}
+
+/// Find a minimum cut separating `sink` from `source`.
+///
+/// Edges are directed, and empty capacities give every edge capacity 1.0.
+/// The cut is read off the source side of a maximum flow, so it is the
+/// minimum cut closest to `source`, and consists of the edges that leave
+/// that side. The cut is empty when `sink` cannot be reached from `source`.
+pub fn compute_min_cut(
+    src: &[i64],
+    dst: &[i64],
+    capacities: &[f64],
+    source: i64,
+    sink: i64,
+) -> Result<MinCutResult> {
+    let CapacityNetwork {
+        mut network,
+        arcs,
+        capacities,
+        source,
+        sink,
+    } = capacity_network(src, dst, capacities, source, sink)?;
+    network.max_flow(source, sink);
+    let side = network.source_side(source);
+
+    let mut result = MinCutResult {
+        cut_value: 0.0,
+        src: Vec::new(),
+        dst: Vec::new(),
+        capacities: Vec::new(),
+    };
+    for (i, &e) in arcs.iter().enumerate() {
+        if side[network.to[e ^ 1]] && !side[network.to[e]] {
+            result.cut_value += capacities[i];
+            result.src.push(src[i]);
+            result.dst.push(dst[i]);
+            result.capacities.push(capacities[i]);
+        }
+    }
+    Ok(result)
+}
+
+/// Result of a minimum edge cut.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinEdgeCutResult {
+    /// Cut edges, each oriented from the side of `a` to the side of `b`.
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+}
+
+/// Result of a minimum vertex cut.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinVertexCutResult {
+    /// Cut nodes in ascending order.
+    pub node_ids: Vec<i64>,
+}
+
+/// Find a smallest set of edges whose removal disconnects `a` from `b`.
+///
+/// The graph is treated as simple and undirected. Each edge gets unit
+/// capacity in both directions, and the cut is read off the source side of a
+/// maximum flow from `a` to `b`, so it is the minimum cut closest to `a`. The
+/// cut is empty when `a` and `b` are already disconnected.
+pub fn compute_min_edge_cut(src: &[i64], dst: &[i64], a: i64, b: i64) -> Result<MinEdgeCutResult> {
+    let SimpleGraph { nodes, edges } = simple_undirected(src, dst)?;
+    let (s, t) = cut_endpoints(&nodes, a, b)?;
+
+    let mut network = edge_network(nodes.len(), &edges);
+    network.max_flow(s, t);
+    let side = network.source_side(s);
+
+    let mut cut: Vec<(i64, i64)> = edges
+        .iter()
+        .filter(|&&(u, v)| side[u] != side[v])
+        .map(|&(u, v)| {
+            if side[u] {
+                (nodes[u], nodes[v])
+            } else {
+                (nodes[v], nodes[u])
+            }
+        })
+        .collect();
+    cut.sort_unstable();
+    Ok(MinEdgeCutResult {
+        src: cut.iter().map(|&(u, _)| u).collect(),
+        dst: cut.iter().map(|&(_, v)| v).collect(),
+    })
+}
+
+/// Find a smallest set of nodes whose removal disconnects `a` from `b`.
+///
+/// The graph is treated as simple and undirected. Every node other than `a`
+/// and `b` is split into an entry and an exit joined by a unit-capacity edge,
+/// so a maximum flow from `a` to `b` counts node-disjoint paths and its
+/// minimum cut, taken closest to `a`, consists of split nodes. Adjacent `a`
+/// and `b` cannot be separated by removing other nodes and return an error.
+pub fn compute_min_vertex_cut(
+    src: &[i64],
+    dst: &[i64],
+    a: i64,
+    b: i64,
+) -> Result<MinVertexCutResult> {
+    let SimpleGraph { nodes, edges } = simple_undirected(src, dst)?;
+    let (s, t) = cut_endpoints(&nodes, a, b)?;
+    if edges.binary_search(&(s.min(t), s.max(t))).is_ok() {
+        return Err(OnagerError::InvalidArgument(
+            "Adjacent nodes cannot be separated by a vertex cut".to_string(),
+        ));
+    }
+
+    let n = nodes.len();
+    let mut network = split_network(n, &edges, s, t);
+    network.max_flow(2 * s + 1, 2 * t);
+    let side = network.source_side(2 * s + 1);
+
+    Ok(MinVertexCutResult {
+        node_ids: (0..n)
+            .filter(|&v| side[2 * v] && !side[2 * v + 1])
+            .map(|v| nodes[v])
+            .collect(),
+    })
+}
+
+/// Count the edges that must be removed to disconnect the graph.
+///
+/// With `pair`, counts the edges that must be removed to disconnect those two
+/// nodes instead, which equals the number of edge-disjoint paths between
+/// them. The graph is treated as simple and undirected. The global value is
+/// the smallest pairwise value from one node to every other, and is 0 for a
+/// disconnected graph.
+pub fn compute_edge_connectivity(
+    src: &[i64],
+    dst: &[i64],
+    pair: Option<(i64, i64)>,
+) -> Result<i64> {
+    let SimpleGraph { nodes, edges } = simple_undirected(src, dst)?;
+    let n = nodes.len();
+    let flow = |s: usize, t: usize| edge_network(n, &edges).max_flow(s, t).round() as i64;
+    if let Some((a, b)) = pair {
+        let (s, t) = cut_endpoints(&nodes, a, b)?;
+        return Ok(flow(s, t));
+    }
+    if n < 2 {
+        return Err(OnagerError::InvalidArgument(
+            "Connectivity requires at least two nodes".to_string(),
+        ));
+    }
+    Ok((1..n).map(|t| flow(0, t)).min().unwrap_or(0))
+}
+
+/// Count the nodes that must be removed to disconnect the graph.
+///
+/// With `pair`, counts the paths between those two nodes that share no other
+/// node instead, which for non-adjacent nodes equals the size of their
+/// minimum vertex cut. The graph is treated as simple and undirected. The
+/// global value follows Esfahanian and Hakimi: from a node `v` of minimum
+/// degree, it is the smallest pairwise value between `v` and a non-neighbor
+/// or between two non-adjacent neighbors of `v`. A complete graph on n nodes
+/// has connectivity n - 1, and a disconnected graph has 0.
+pub fn compute_node_connectivity(
+    src: &[i64],
+    dst: &[i64],
+    pair: Option<(i64, i64)>,
+) -> Result<i64> {
+    let SimpleGraph { nodes, edges } = simple_undirected(src, dst)?;
+    let n = nodes.len();
+    let flow = |s: usize, t: usize| {
+        split_network(n, &edges, s, t)
+            .max_flow(2 * s + 1, 2 * t)
+            .round() as i64
+    };
+    if let Some((a, b)) = pair {
+        let (s, t) = cut_endpoints(&nodes, a, b)?;
+        return Ok(flow(s, t));
+    }
+    if n < 2 {
+        return Err(OnagerError::InvalidArgument(
+            "Connectivity requires at least two nodes".to_string(),
+        ));
+    }
+
+    let mut adjacent = vec![Vec::new(); n];
+    for &(u, v) in &edges {
+        adjacent[u].push(v);
+        adjacent[v].push(u);
+    }
+    let is_edge = |u: usize, v: usize| edges.binary_search(&(u.min(v), u.max(v))).is_ok();
+    let v = (0..n).min_by_key(|&u| adjacent[u].len()).unwrap_or(0);
+    let mut connectivity = n as i64 - 1;
+    for w in 0..n {
+        if w != v && !is_edge(v, w) {
+            connectivity = connectivity.min(flow(v, w));
+        }
+    }
+    let around = &adjacent[v];
+    for (i, &x) in around.iter().enumerate() {
+        for &y in &around[i + 1..] {
+            if !is_edge(x, y) {
+                connectivity = connectivity.min(flow(x, y));
+            }
+        }
+    }
+    Ok(connectivity)
+}
+
+/// Result of a global minimum cut.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalMinCutResult {
+    /// Node IDs in ascending order.
+    pub node_ids: Vec<i64>,
+    /// Side of each node, 0 or 1. The smallest node ID is always on side 0.
+    pub sides: Vec<i64>,
+    /// Total weight of the edges between the two sides.
+    pub cut_weight: f64,
+}
+
+/// Heap entry of the maximum adjacency ordering, ordered by connection weight
+/// and then by node index so ties break the same way on every run.
+struct Attachment {
+    weight: f64,
+    node: usize,
+}
+
+impl PartialEq for Attachment {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Attachment {}
+
+impl PartialOrd for Attachment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Attachment {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight
+            .total_cmp(&other.weight)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+/// Split the nodes into two sides joined by the least total edge weight.
+///
+/// Uses the Stoer-Wagner algorithm on the undirected graph. Parallel edges add
+/// their weights, self-loops are ignored, and empty weights mean every edge
+/// has weight 1.0. A disconnected graph has a cut of weight 0 that separates
+/// one component from the rest.
+pub fn compute_global_min_cut(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+) -> Result<GlobalMinCutResult> {
+    let graph = build_weighted_adjacency(src, dst, weights)?;
+    let n = graph.node_ids.len();
+    if n < 2 {
+        return Err(OnagerError::InvalidArgument(
+            "Global minimum cut requires at least two nodes".to_string(),
+        ));
+    }
+
+    let mut adjacency: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
+    for (u, list) in graph.neighbors.iter().enumerate() {
+        for &(v, w) in list {
+            if u != v {
+                *adjacency[u].entry(v).or_insert(0.0) += w;
+            }
+        }
+    }
+    let mut members: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+    let mut best_weight = f64::INFINITY;
+    let mut best_side: Vec<usize> = Vec::new();
+
+    while active.len() > 1 {
+        // Maximum adjacency ordering: repeatedly add the node most strongly
+        // connected to the nodes added so far
+        let mut attached = vec![0.0; n];
+        let mut added = vec![false; n];
+        let mut heap: BinaryHeap<Attachment> = active
+            .iter()
+            .map(|&node| Attachment { weight: 0.0, node })
+            .collect();
+        let (mut previous, mut last) = (usize::MAX, usize::MAX);
+        let mut count = 0;
+        while let Some(Attachment { weight, node }) = heap.pop() {
+            if added[node] || weight != attached[node] {
+                continue;
+            }
+            added[node] = true;
+            (previous, last) = (last, node);
+            count += 1;
+            if count == active.len() {
+                break;
+            }
+            for (&u, &w) in &adjacency[node] {
+                if !added[u] {
+                    attached[u] += w;
+                    heap.push(Attachment {
+                        weight: attached[u],
+                        node: u,
+                    });
+                }
+            }
+        }
+
+        // The last node against everything else is the cut of this phase
+        if attached[last] < best_weight {
+            best_weight = attached[last];
+            best_side = members[last].clone();
+        }
+
+        // Merge the last node into the one added before it
+        let edges = std::mem::take(&mut adjacency[last]);
+        for (u, w) in edges {
+            adjacency[u].remove(&last);
+            if u != previous {
+                *adjacency[previous].entry(u).or_insert(0.0) += w;
+                *adjacency[u].entry(previous).or_insert(0.0) += w;
+            }
+        }
+        let moved = std::mem::take(&mut members[last]);
+        members[previous].extend(moved);
+        active.retain(|&v| v != last);
+    }
+
+    let mut on_side = vec![false; n];
+    for v in best_side {
+        on_side[v] = true;
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_unstable_by_key(|&v| graph.node_ids[v]);
+    let flip = on_side[order[0]];
+    Ok(GlobalMinCutResult {
+        node_ids: order.iter().map(|&v| graph.node_ids[v]).collect(),
+        sides: order
+            .iter()
+            .map(|&v| i64::from(on_side[v] != flip))
+            .collect(),
+        cut_weight: best_weight,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_flow_classic_network() {
+        // CLRS figure 26.1, whose maximum flow is 23
+        let mut g = FlowGraph::new(6);
+        for (u, v, c) in [
+            (0, 1, 16.0),
+            (0, 2, 13.0),
+            (2, 1, 4.0),
+            (1, 3, 12.0),
+            (3, 2, 9.0),
+            (2, 4, 14.0),
+            (4, 3, 7.0),
+            (3, 5, 20.0),
+            (4, 5, 4.0),
+        ] {
+            g.add_edge(u, v, c);
+        }
+        assert!((g.max_flow(0, 5) - 23.0).abs() < 1e-9);
+        let side = g.source_side(0);
+        assert!(side[0] && !side[5]);
+    }
+
+    #[test]
+    fn test_max_flow_long_path() {
+        let n = 100_000;
+        let mut g = FlowGraph::new(n);
+        for u in 0..n - 1 {
+            g.add_edge(u, u + 1, 1.0);
+        }
+        assert!((g.max_flow(0, n - 1) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_max_flow_per_edge_flows() {
+        // CLRS figure 26.1 with node IDs 1 to 6 and a parallel edge 5 -> 6
+        let src = vec![1, 1, 3, 2, 4, 3, 5, 4, 5, 5];
+        let dst = vec![2, 3, 2, 4, 3, 5, 4, 6, 6, 6];
+        let caps = vec![16.0, 13.0, 4.0, 12.0, 9.0, 14.0, 7.0, 20.0, 4.0, 1.0];
+        let result = compute_max_flow(&src, &dst, &caps, 1, 6).unwrap();
+        assert!((result.flow_value - 24.0).abs() < 1e-9);
+        assert_eq!(result.src, src);
+
+        // Capacity and conservation hold at every node
+        let mut net = [0.0; 7];
+        for i in 0..src.len() {
+            assert!(result.flows[i] <= caps[i] + 1e-9);
+            net[src[i] as usize] -= result.flows[i];
+            net[dst[i] as usize] += result.flows[i];
+        }
+        assert!((net[6] - 24.0).abs() < 1e-9);
+        assert!((2..6).all(|v| net[v].abs() < 1e-9));
+
+        // Edges point one way, so nothing flows back
+        let reversed = compute_max_flow(&src, &dst, &caps, 6, 1).unwrap();
+        assert_eq!(reversed.flow_value, 0.0);
+    }
+
+    #[test]
+    fn test_min_cut_matches_max_flow() {
+        let src = vec![1, 1, 3, 2, 4, 3, 5, 4, 5];
+        let dst = vec![2, 3, 2, 4, 3, 5, 4, 6, 6];
+        let caps = vec![16.0, 13.0, 4.0, 12.0, 9.0, 14.0, 7.0, 20.0, 4.0];
+        let result = compute_min_cut(&src, &dst, &caps, 1, 6).unwrap();
+        assert!((result.cut_value - 23.0).abs() < 1e-9);
+        assert_eq!(result.src, vec![2, 5, 5]);
+        assert_eq!(result.dst, vec![4, 4, 6]);
+        assert_eq!(result.capacities, vec![12.0, 7.0, 4.0]);
+
+        // Unit capacities count edge-disjoint directed paths
+        let unit = compute_min_cut(&[1, 1, 2, 3], &[2, 3, 4, 4], &[], 1, 4).unwrap();
+        assert_eq!(unit.cut_value, 2.0);
+        assert_eq!(unit.src, vec![1, 1]);
+    }
+
+    #[test]
+    fn test_max_flow_errors() {
+        let (src, dst) = (vec![1, 2], vec![2, 3]);
+        assert!(compute_max_flow(&src, &dst, &[], 1, 1).is_err());
+        assert!(compute_max_flow(&src, &dst, &[1.0], 1, 3).is_err());
+        assert!(compute_min_cut(&src, &dst, &[1.0, -2.0], 1, 3).is_err());
+        assert!(compute_min_cut(&src, &dst, &[1.0, f64::INFINITY], 1, 3).is_err());
+        assert!(matches!(
+            compute_max_flow(&src, &dst, &[], 1, 9),
+            Err(OnagerError::NodeNotFound(9))
+        ));
+        assert!(compute_max_flow(&[], &[], &[], 1, 2).is_err());
+    }
+
+    fn bridged_triangles() -> (Vec<i64>, Vec<i64>) {
+        // Triangles 1-2-3 and 4-5-6 joined by the bridge 3-4
+        (vec![1, 2, 3, 3, 4, 5, 6], vec![2, 3, 1, 4, 5, 6, 4])
+    }
+
+    #[test]
+    fn test_min_edge_cut_bridge() {
+        let (src, dst) = bridged_triangles();
+        let result = compute_min_edge_cut(&src, &dst, 1, 6).unwrap();
+        assert_eq!((result.src, result.dst), (vec![3], vec![4]));
+        let reversed = compute_min_edge_cut(&src, &dst, 6, 1).unwrap();
+        assert_eq!((reversed.src, reversed.dst), (vec![4], vec![3]));
+    }
+
+    #[test]
+    fn test_min_edge_cut_cycle_and_disconnected() {
+        // 4-cycle with a duplicate edge, plus a separate edge 7-8
+        let src = vec![1, 2, 3, 4, 2, 7];
+        let dst = vec![2, 3, 4, 1, 1, 8];
+        let result = compute_min_edge_cut(&src, &dst, 1, 3).unwrap();
+        assert_eq!(result.src, vec![1, 1]);
+        assert_eq!(result.dst, vec![2, 4]);
+        let apart = compute_min_edge_cut(&src, &dst, 1, 7).unwrap();
+        assert!(apart.src.is_empty());
+    }
+
+    #[test]
+    fn test_min_vertex_cut() {
+        let (src, dst) = bridged_triangles();
+        let result = compute_min_vertex_cut(&src, &dst, 1, 6).unwrap();
+        assert_eq!(result.node_ids, vec![3]);
+
+        // Two node-disjoint routes 1-2-4 and 1-3-4
+        let result = compute_min_vertex_cut(&[1, 1, 2, 3], &[2, 3, 4, 4], 1, 4).unwrap();
+        assert_eq!(result.node_ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_min_cut_errors() {
+        let (src, dst) = bridged_triangles();
+        assert!(compute_min_vertex_cut(&src, &dst, 3, 4).is_err());
+        assert!(compute_min_edge_cut(&src, &dst, 1, 1).is_err());
+        assert!(matches!(
+            compute_min_edge_cut(&src, &dst, 1, 99),
+            Err(OnagerError::NodeNotFound(99))
+        ));
+        assert!(compute_min_vertex_cut(&[], &[], 1, 2).is_err());
+        assert!(compute_min_edge_cut(&[1, 2], &[2], 1, 2).is_err());
+    }
+
+    #[test]
+    fn test_global_min_cut_stoer_wagner_example() {
+        // Example graph from the Stoer-Wagner paper, whose minimum cut is 4
+        let edges = [
+            (1, 2, 2.0),
+            (1, 5, 3.0),
+            (2, 3, 3.0),
+            (2, 5, 2.0),
+            (2, 6, 2.0),
+            (3, 4, 4.0),
+            (3, 7, 2.0),
+            (4, 7, 2.0),
+            (4, 8, 2.0),
+            (5, 6, 3.0),
+            (6, 7, 1.0),
+            (7, 8, 3.0),
+        ];
+        let src: Vec<i64> = edges.iter().map(|e| e.0).collect();
+        let dst: Vec<i64> = edges.iter().map(|e| e.1).collect();
+        let weights: Vec<f64> = edges.iter().map(|e| e.2).collect();
+        let result = compute_global_min_cut(&src, &dst, &weights).unwrap();
+        assert!((result.cut_weight - 4.0).abs() < 1e-12);
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(result.sides, vec![0, 0, 1, 1, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_global_min_cut_matches_brute_force() {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..30 {
+            let (mut src, mut dst, mut weights) = (Vec::new(), Vec::new(), Vec::new());
+            for u in 0..7i64 {
+                for v in u + 1..7 {
+                    if next().is_multiple_of(2) {
+                        src.push(u);
+                        dst.push(v);
+                        weights.push((next() % 10) as f64);
+                    }
+                }
+            }
+            if src.is_empty() {
+                continue;
+            }
+            let result = compute_global_min_cut(&src, &dst, &weights).unwrap();
+            let side_of = |v: i64| result.sides[result.node_ids.binary_search(&v).unwrap_or(0)];
+            let crossing: f64 = (0..src.len())
+                .filter(|&i| side_of(src[i]) != side_of(dst[i]))
+                .map(|i| weights[i])
+                .sum();
+            assert!((crossing - result.cut_weight).abs() < 1e-9);
+            assert!(result.sides.contains(&1));
+
+            let n = result.node_ids.len();
+            let mut optimum = f64::INFINITY;
+            for mask in 1u32..(1 << n) - 1 {
+                let inside = |v: i64| {
+                    let i = result.node_ids.binary_search(&v).unwrap_or(0);
+                    mask & (1 << i) != 0
+                };
+                let cut: f64 = (0..src.len())
+                    .filter(|&i| inside(src[i]) != inside(dst[i]))
+                    .map(|i| weights[i])
+                    .sum();
+                optimum = optimum.min(cut);
+            }
+            assert!((result.cut_weight - optimum).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_global_min_cut_matches_smallest_st_cut() {
+        // The global cut separates node 1 from some t, so it is the lightest
+        // of the cuts between 1 and every other node
+        let edges = [
+            (1, 2, 3.0),
+            (2, 3, 1.5),
+            (3, 4, 4.0),
+            (4, 1, 2.0),
+            (2, 4, 0.5),
+            (4, 5, 1.0),
+            (5, 3, 2.5),
+        ];
+        let src: Vec<i64> = edges.iter().flat_map(|e| [e.0, e.1]).collect();
+        let dst: Vec<i64> = edges.iter().flat_map(|e| [e.1, e.0]).collect();
+        let caps: Vec<f64> = edges.iter().flat_map(|e| [e.2, e.2]).collect();
+        let smallest = (2..=5)
+            .map(|t| compute_min_cut(&src, &dst, &caps, 1, t).unwrap().cut_value)
+            .fold(f64::INFINITY, f64::min);
+
+        let result = compute_global_min_cut(
+            &edges.iter().map(|e| e.0).collect::<Vec<_>>(),
+            &edges.iter().map(|e| e.1).collect::<Vec<_>>(),
+            &edges.iter().map(|e| e.2).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        assert!((result.cut_weight - smallest).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_global_min_cut_disconnected_and_errors() {
+        // Parallel edges 1-2 add up, and the components {1, 2} and {3, 4} are apart
+        let result = compute_global_min_cut(&[1, 2, 3], &[2, 1, 4], &[]).unwrap();
+        assert_eq!(result.cut_weight, 0.0);
+        assert_eq!(result.sides, vec![0, 0, 1, 1]);
+
+        assert!(compute_global_min_cut(&[1], &[1], &[]).is_err());
+        assert!(compute_global_min_cut(&[1, 2], &[2, 3], &[1.0]).is_err());
+        assert!(compute_global_min_cut(&[1, 2], &[2, 3], &[1.0, -1.0]).is_err());
+    }
+
+    #[test]
+    fn test_connectivity_bridged_triangles() {
+        let (src, dst) = bridged_triangles();
+        assert_eq!(compute_edge_connectivity(&src, &dst, None).unwrap(), 1);
+        assert_eq!(compute_node_connectivity(&src, &dst, None).unwrap(), 1);
+        assert_eq!(
+            compute_edge_connectivity(&src, &dst, Some((1, 2))).unwrap(),
+            2
+        );
+        // The edge 1-2 and the path through 3 share no node
+        assert_eq!(
+            compute_node_connectivity(&src, &dst, Some((1, 2))).unwrap(),
+            2
+        );
+        assert_eq!(
+            compute_node_connectivity(&src, &dst, Some((1, 6))).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_connectivity_complete_and_cycle() {
+        // K5 has connectivity 4, and a 6-cycle has connectivity 2
+        let (mut src, mut dst) = (Vec::new(), Vec::new());
+        for u in 0..5 {
+            for v in u + 1..5 {
+                src.push(u);
+                dst.push(v);
+            }
+        }
+        assert_eq!(compute_node_connectivity(&src, &dst, None).unwrap(), 4);
+        assert_eq!(compute_edge_connectivity(&src, &dst, None).unwrap(), 4);
+        assert_eq!(
+            compute_node_connectivity(&src, &dst, Some((0, 1))).unwrap(),
+            4
+        );
+
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let dst = vec![2, 3, 4, 5, 6, 1];
+        assert_eq!(compute_node_connectivity(&src, &dst, None).unwrap(), 2);
+        assert_eq!(compute_edge_connectivity(&src, &dst, None).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_connectivity_disconnected_and_errors() {
+        let (src, dst) = (vec![1, 3], vec![2, 4]);
+        assert_eq!(compute_edge_connectivity(&src, &dst, None).unwrap(), 0);
+        assert_eq!(compute_node_connectivity(&src, &dst, None).unwrap(), 0);
+        assert_eq!(
+            compute_node_connectivity(&src, &dst, Some((1, 4))).unwrap(),
+            0
+        );
+
+        assert!(compute_node_connectivity(&[1], &[1], None).is_err());
+        assert!(compute_edge_connectivity(&src, &dst, Some((1, 1))).is_err());
+        assert!(compute_edge_connectivity(&src, &dst, Some((1, 99))).is_err());
+    }
+
+    #[test]
+    fn test_connectivity_matches_brute_force() {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..30 {
+            let (mut src, mut dst) = (Vec::new(), Vec::new());
+            for u in 0..7i64 {
+                for v in u + 1..7 {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    if !state.is_multiple_of(3) {
+                        src.push(u);
+                        dst.push(v);
+                    }
+                }
+            }
+            let n = 7;
+            let nodes: Vec<i64> = {
+                let mut all: Vec<i64> = src.iter().chain(&dst).copied().collect();
+                all.sort_unstable();
+                all.dedup();
+                all
+            };
+            if nodes.len() < n {
+                continue;
+            }
+
+            // Smallest node set whose removal leaves a disconnected graph
+            let connected_without = |removed: u32| {
+                let kept: Vec<i64> = (0..n as i64).filter(|&v| removed & (1 << v) == 0).collect();
+                let mut reached = vec![kept[0]];
+                let mut stack = vec![kept[0]];
+                while let Some(u) = stack.pop() {
+                    for i in 0..src.len() {
+                        let w = if src[i] == u {
+                            dst[i]
+                        } else if dst[i] == u {
+                            src[i]
+                        } else {
+                            continue;
+                        };
+                        if removed & (1 << w) == 0 && !reached.contains(&w) {
+                            reached.push(w);
+                            stack.push(w);
+                        }
+                    }
+                }
+                reached.len() == kept.len()
+            };
+            let mut expected = n as i64 - 1;
+            for removed in 0u32..1 << n {
+                if (removed.count_ones() as usize) < n - 1 && !connected_without(removed) {
+                    expected = expected.min(removed.count_ones() as i64);
+                }
+            }
+            assert_eq!(
+                compute_node_connectivity(&src, &dst, None).unwrap(),
+                expected
+            );
+
+            let min_cut = compute_global_min_cut(&src, &dst, &[]).unwrap();
+            assert_eq!(
+                compute_edge_connectivity(&src, &dst, None).unwrap(),
+                min_cut.cut_weight as i64
+            );
+        }
+    }
+}
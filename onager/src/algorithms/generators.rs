@@ -1,16 +1,80 @@
 //! Graph generators module.
 //!
-//! Erdős-Rényi, Barabási-Albert, Watts-Strogatz.
+//! Erdős-Rényi (G(n, p) and G(n, m)), Barabási-Albert, Watts-Strogatz, LFR,
+//! random geometric graphs, and k-nearest-neighbor and similarity graphs built
+//! from existing data.
 
 use graphina::core::generators::{barabasi_albert_graph, erdos_renyi_graph, watts_strogatz_graph};
 use graphina::core::types::{Graph, Undirected};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
+use super::sbm::SplitMix64;
 use crate::error::{OnagerError, Result};
 
 /// Result of graph generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneratorResult {
     pub src: Vec<i64>,
     pub dst: Vec<i64>,
+    /// Nodes `0..n` in order, including nodes without edges.
+    pub node_ids: Vec<i64>,
+}
+
+/// Adds `start_id` to the node IDs `0..n` in each array, failing if the last
+/// one would overflow.
+fn offset_ids(n: usize, start_id: i64, arrays: [&mut Vec<i64>; 3]) -> Result<()> {
+    i64::try_from(n)
+        .ok()
+        .and_then(|n| start_id.checked_add(n - 1))
+        .ok_or_else(|| {
+            OnagerError::InvalidArgument(format!(
+                "start_id {} leaves no room for {} node IDs",
+                start_id, n
+            ))
+        })?;
+    for ids in arrays {
+        ids.iter_mut().for_each(|id| *id += start_id);
+    }
+    Ok(())
+}
+
+impl GeneratorResult {
+    /// Shifts the node IDs from `0..n` to `start_id..start_id + n`, so the
+    /// graph can be merged with existing data without ID collisions.
+    pub fn with_start_id(mut self, start_id: i64) -> Result<Self> {
+        let n = self.node_ids.len();
+        offset_ids(
+            n,
+            start_id,
+            [&mut self.src, &mut self.dst, &mut self.node_ids],
+        )?;
+        Ok(self)
+    }
+
+    /// Drops self-loops unless `self_loops` is set and repeated edges unless
+    /// `parallel_edges` is set, keeping the first copy of each edge. Without
+    /// `directed`, `(u, v)` and `(v, u)` are the same edge.
+    pub fn simplify(mut self, directed: bool, self_loops: bool, parallel_edges: bool) -> Self {
+        let mut seen = HashSet::new();
+        let (src, dst): (Vec<i64>, Vec<i64>) = self
+            .src
+            .iter()
+            .zip(&self.dst)
+            .filter(|&(&u, &v)| {
+                let key = if directed {
+                    (u, v)
+                } else {
+                    (u.min(v), u.max(v))
+                };
+                (self_loops || u != v) && (parallel_edges || seen.insert(key))
+            })
+            .unzip();
+        self.src = src;
+        self.dst = dst;
+        self
+    }
 }
 
 /// Generate an Erdős-Rényi random graph.
@@ -33,7 +97,11 @@ pub fn generate_erdos_renyi(n: usize, p: f64, seed: u64) -> Result<GeneratorResu
         src.push(u.index() as i64);
         dst.push(v.index() as i64);
     }
-    Ok(GeneratorResult { src, dst })
+    Ok(GeneratorResult {
+        src,
+        dst,
+        node_ids: (0..n as i64).collect(),
+    })
 }
 
 /// Generate a Barabási-Albert preferential attachment graph.
@@ -56,7 +124,11 @@ pub fn generate_barabasi_albert(n: usize, m: usize, seed: u64) -> Result<Generat
         src.push(u.index() as i64);
         dst.push(v.index() as i64);
     }
-    Ok(GeneratorResult { src, dst })
+    Ok(GeneratorResult {
+        src,
+        dst,
+        node_ids: (0..n as i64).collect(),
+    })
 }
 
 /// Generate a Watts-Strogatz small-world graph.
@@ -89,7 +161,755 @@ pub fn generate_watts_strogatz(
         src.push(u.index() as i64);
         dst.push(v.index() as i64);
     }
-    Ok(GeneratorResult { src, dst })
+    Ok(GeneratorResult {
+        src,
+        dst,
+        node_ids: (0..n as i64).collect(),
+    })
+}
+
+/// Node pairs that a random edge may join.
+///
+/// Pairs are numbered row by row: row `u` holds the pairs starting at `u` in
+/// increasing order of the other node. Undirected pairs only list `v >= u`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EdgeSpace {
+    /// Treat `(u, v)` and `(v, u)` as different edges.
+    pub directed: bool,
+    /// Allow edges from a node to itself.
+    pub self_loops: bool,
+}
+
+impl EdgeSpace {
+    /// Number of pairs on `n` nodes, or `None` if it overflows.
+    fn size(self, n: u64) -> Option<u64> {
+        let ordered = n.checked_mul(n.saturating_sub(1))?;
+        let pairs = if self.directed { ordered } else { ordered / 2 };
+        pairs.checked_add(if self.self_loops { n } else { 0 })
+    }
+
+    /// Number of pairs in row `u`.
+    fn row_len(self, n: u64, u: u64) -> u64 {
+        match (self.directed, self.self_loops) {
+            (true, true) => n,
+            (true, false) => n - 1,
+            (false, true) => n - u,
+            (false, false) => n - 1 - u,
+        }
+    }
+
+    /// Other node of the `r`-th pair in row `u`.
+    fn column(self, u: u64, r: u64) -> u64 {
+        match (self.directed, self.self_loops) {
+            (true, true) => r,
+            (true, false) if r >= u => r + 1,
+            (true, false) => r,
+            (false, true) => u + r,
+            (false, false) => u + 1 + r,
+        }
+    }
+
+    /// Turns pair indices, sorted in increasing order, into a graph on `n` nodes.
+    fn decode(self, n: usize, indices: &[u64]) -> GeneratorResult {
+        let mut src = Vec::with_capacity(indices.len());
+        let mut dst = Vec::with_capacity(indices.len());
+        let (mut u, mut row_start) = (0u64, 0u64);
+        for &k in indices {
+            while k >= row_start + self.row_len(n as u64, u) {
+                row_start += self.row_len(n as u64, u);
+                u += 1;
+            }
+            src.push(u as i64);
+            dst.push(self.column(u, k - row_start) as i64);
+        }
+        GeneratorResult {
+            src,
+            dst,
+            node_ids: (0..n as i64).collect(),
+        }
+    }
+}
+
+fn edge_space_size(n: usize, space: EdgeSpace) -> Result<u64> {
+    space
+        .size(n as u64)
+        .ok_or_else(|| OnagerError::InvalidArgument(format!("n = {} has too many node pairs", n)))
+}
+
+/// Generate an Erdős-Rényi G(n, p) graph over the pairs in `space`.
+///
+/// Unlike [`generate_erdos_renyi`], this can generate directed edges and
+/// self-loops. Each pair is an edge with probability `p`, and the gaps between
+/// edges are drawn from a geometric distribution, so the time grows with the
+/// number of edges rather than the number of pairs.
+pub fn generate_gnp(n: usize, p: f64, space: EdgeSpace, seed: u64) -> Result<GeneratorResult> {
+    if n == 0 {
+        return Err(OnagerError::InvalidArgument("n must be > 0".to_string()));
+    }
+    if !(0.0..=1.0).contains(&p) {
+        return Err(OnagerError::InvalidArgument(
+            "p must be in [0, 1]".to_string(),
+        ));
+    }
+    let size = edge_space_size(n, space)?;
+
+    let mut indices = Vec::new();
+    if p == 1.0 {
+        indices.extend(0..size);
+    } else if p > 0.0 {
+        let mut rng = SplitMix64(seed);
+        let log_q = (-p).ln_1p();
+        let mut next = 0u64;
+        loop {
+            let skip = ((-rng.next_f64()).ln_1p() / log_q).floor();
+            if skip >= (size - next) as f64 {
+                break;
+            }
+            next += skip as u64;
+            indices.push(next);
+            next += 1;
+        }
+    }
+    Ok(space.decode(n, &indices))
+}
+
+/// Generate an Erdős-Rényi G(n, m) graph with exactly `m` edges over the pairs
+/// in `space`.
+///
+/// Without `parallel_edges`, the edges are `m` distinct pairs chosen uniformly
+/// with Floyd's algorithm. With it, each edge is drawn independently, so a
+/// pair can appear more than once.
+pub fn generate_gnm(
+    n: usize,
+    m: usize,
+    space: EdgeSpace,
+    parallel_edges: bool,
+    seed: u64,
+) -> Result<GeneratorResult> {
+    if n == 0 {
+        return Err(OnagerError::InvalidArgument("n must be > 0".to_string()));
+    }
+    let size = edge_space_size(n, space)?;
+    let m64 = m as u64;
+    if (m > 0 && size == 0) || (!parallel_edges && m64 > size) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "m must be <= {}, the number of node pairs",
+            size
+        )));
+    }
+
+    let mut rng = SplitMix64(seed);
+    let mut indices: Vec<u64> = if parallel_edges {
+        (0..m).map(|_| rng.next_u64() % size).collect()
+    } else {
+        let mut chosen = HashSet::with_capacity(m);
+        for j in size - m64..size {
+            let t = rng.next_u64() % (j + 1);
+            if !chosen.insert(t) {
+                chosen.insert(j);
+            }
+        }
+        chosen.into_iter().collect()
+    };
+    indices.sort_unstable();
+    Ok(space.decode(n, &indices))
+}
+
+/// Result of random geometric graph generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeometricGraphResult {
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    /// Nodes `0..n` in order.
+    pub node_ids: Vec<i64>,
+    /// Position of each node in `node_ids` in the unit square.
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+}
+
+impl GeometricGraphResult {
+    /// Shifts the node IDs from `0..n` to `start_id..start_id + n`, keeping
+    /// the positions.
+    pub fn with_start_id(mut self, start_id: i64) -> Result<Self> {
+        let n = self.node_ids.len();
+        offset_ids(
+            n,
+            start_id,
+            [&mut self.src, &mut self.dst, &mut self.node_ids],
+        )?;
+        Ok(self)
+    }
+}
+
+/// Generate a random geometric graph.
+///
+/// Places `n` nodes uniformly at random in the unit square and joins every
+/// pair at Euclidean distance at most `radius`. Nodes are bucketed into a grid
+/// of cells at least `radius` wide, so only neighboring cells are compared.
+/// Each edge is listed once with `src < dst`.
+pub fn generate_random_geometric(n: usize, radius: f64, seed: u64) -> Result<GeometricGraphResult> {
+    if n == 0 {
+        return Err(OnagerError::InvalidArgument("n must be > 0".to_string()));
+    }
+    if !(radius >= 0.0 && radius.is_finite()) {
+        return Err(OnagerError::InvalidArgument(
+            "radius must be a finite number >= 0".to_string(),
+        ));
+    }
+
+    let mut rng = SplitMix64(seed);
+    let (x, y): (Vec<f64>, Vec<f64>) = (0..n).map(|_| (rng.next_f64(), rng.next_f64())).unzip();
+
+    // Cells per side, capped so the grid never has many more cells than nodes
+    let max_side = (n as f64).sqrt().ceil() as usize;
+    let side = if radius > 0.0 {
+        ((1.0 / radius).floor() as usize).clamp(1, max_side.max(1))
+    } else {
+        max_side.max(1)
+    };
+    let cell = |v: f64| ((v * side as f64) as usize).min(side - 1);
+    let mut cells: Vec<Vec<usize>> = vec![Vec::new(); side * side];
+    for i in 0..n {
+        cells[cell(y[i]) * side + cell(x[i])].push(i);
+    }
+
+    let r2 = radius * radius;
+    let mut src = Vec::new();
+    let mut dst = Vec::new();
+    let mut near = Vec::new();
+    for u in 0..n {
+        let (cx, cy) = (cell(x[u]), cell(y[u]));
+        near.clear();
+        for gy in cy.saturating_sub(1)..=(cy + 1).min(side - 1) {
+            for gx in cx.saturating_sub(1)..=(cx + 1).min(side - 1) {
+                for &v in &cells[gy * side + gx] {
+                    let (dx, dy) = (x[u] - x[v], y[u] - y[v]);
+                    if v > u && dx * dx + dy * dy <= r2 {
+                        near.push(v);
+                    }
+                }
+            }
+        }
+        near.sort_unstable();
+        for &v in &near {
+            src.push(u as i64);
+            dst.push(v as i64);
+        }
+    }
+    Ok(GeometricGraphResult {
+        src,
+        dst,
+        node_ids: (0..n as i64).collect(),
+        x,
+        y,
+    })
+}
+
+/// Result of building a k-nearest-neighbor graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnnGraphResult {
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    /// Euclidean distance between `src` and `dst`.
+    pub distances: Vec<f64>,
+}
+
+/// Candidate neighbor ordered by squared distance, then by point index.
+#[derive(PartialEq)]
+struct Neighbor {
+    dist2: f64,
+    index: usize,
+}
+
+impl Eq for Neighbor {}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist2
+            .total_cmp(&other.dist2)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+/// 2-d tree stored as a permutation of the points: each range is split at its
+/// median, alternating between the x and y axes.
+struct KdTree<'a> {
+    coords: [&'a [f64]; 2],
+    order: Vec<usize>,
+}
+
+impl<'a> KdTree<'a> {
+    fn new(x: &'a [f64], y: &'a [f64]) -> Self {
+        let mut tree = KdTree {
+            coords: [x, y],
+            order: (0..x.len()).collect(),
+        };
+        tree.build(0, x.len(), 0);
+        tree
+    }
+
+    fn build(&mut self, lo: usize, hi: usize, axis: usize) {
+        if hi - lo <= 1 {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let c = self.coords[axis];
+        self.order[lo..hi].select_nth_unstable_by(mid - lo, |&a, &b| c[a].total_cmp(&c[b]));
+        self.build(lo, mid, 1 - axis);
+        self.build(mid + 1, hi, 1 - axis);
+    }
+
+    /// Collects the `k` points nearest to point `p`, excluding `p`, into `heap`.
+    fn nearest(
+        &self,
+        p: usize,
+        k: usize,
+        lo: usize,
+        hi: usize,
+        axis: usize,
+        heap: &mut BinaryHeap<Neighbor>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        let q = self.order[mid];
+        if q != p {
+            let (dx, dy) = (
+                self.coords[0][p] - self.coords[0][q],
+                self.coords[1][p] - self.coords[1][q],
+            );
+            let candidate = Neighbor {
+                dist2: dx * dx + dy * dy,
+                index: q,
+            };
+            if heap.len() < k {
+                heap.push(candidate);
+            } else if heap.peek().is_some_and(|worst| candidate < *worst) {
+                heap.pop();
+                heap.push(candidate);
+            }
+        }
+        let diff = self.coords[axis][p] - self.coords[axis][q];
+        let (near, far) = if diff < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.nearest(p, k, near.0, near.1, 1 - axis, heap);
+        if heap.len() < k || heap.peek().is_some_and(|worst| diff * diff <= worst.dist2) {
+            self.nearest(p, k, far.0, far.1, 1 - axis, heap);
+        }
+    }
+}
+
+/// Build a k-nearest-neighbor graph from 2-d points.
+///
+/// Adds a directed edge from every point to each of its `k` nearest other
+/// points by Euclidean distance, nearest first. Ties go to the point listed
+/// first. With fewer than `k + 1` points, every point links to all others.
+/// Points at the same position are still separate neighbors at distance 0.
+pub fn build_knn_graph(node_ids: &[i64], x: &[f64], y: &[f64], k: usize) -> Result<KnnGraphResult> {
+    if x.len() != node_ids.len() || y.len() != node_ids.len() {
+        return Err(OnagerError::InvalidArgument(
+            "node_ids, x, and y must have the same length".to_string(),
+        ));
+    }
+    if k == 0 {
+        return Err(OnagerError::InvalidArgument("k must be > 0".to_string()));
+    }
+    if x.iter().chain(y).any(|v| !v.is_finite()) {
+        return Err(OnagerError::InvalidArgument(
+            "Coordinates must be finite".to_string(),
+        ));
+    }
+    let mut seen = HashSet::with_capacity(node_ids.len());
+    if let Some(&id) = node_ids.iter().find(|&&id| !seen.insert(id)) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Node {} appears more than once",
+            id
+        )));
+    }
+
+    let n = node_ids.len();
+    let k = k.min(n.saturating_sub(1));
+    let tree = KdTree::new(x, y);
+    let mut result = KnnGraphResult {
+        src: Vec::with_capacity(n * k),
+        dst: Vec::with_capacity(n * k),
+        distances: Vec::with_capacity(n * k),
+    };
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for p in 0..n {
+        heap.clear();
+        tree.nearest(p, k, 0, n, 0, &mut heap);
+        for neighbor in std::mem::take(&mut heap).into_sorted_vec() {
+            result.src.push(node_ids[p]);
+            result.dst.push(node_ids[neighbor.index]);
+            result.distances.push(neighbor.dist2.sqrt());
+        }
+    }
+    Ok(result)
+}
+
+/// Result of building a graph from pairwise similarities.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SimilarityGraphResult {
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    /// Similarity of `src` to `dst`.
+    pub similarities: Vec<f64>,
+}
+
+impl SimilarityGraphResult {
+    /// Adds edges from `node` to the candidates at or above `threshold`, most
+    /// similar first and at most `top_k` of them. Ties keep the candidate
+    /// order.
+    fn push_row(&mut self, node: i64, mut row: Vec<(i64, f64)>, threshold: f64, top_k: usize) {
+        row.retain(|&(_, sim)| sim >= threshold);
+        row.sort_by(|a, b| b.1.total_cmp(&a.1));
+        if top_k > 0 {
+            row.truncate(top_k);
+        }
+        for (other, sim) in row {
+            self.src.push(node);
+            self.dst.push(other);
+            self.similarities.push(sim);
+        }
+    }
+}
+
+fn check_threshold(threshold: f64) -> Result<()> {
+    if threshold.is_nan() {
+        return Err(OnagerError::InvalidArgument(
+            "threshold must not be NaN".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Build a graph from a dense similarity matrix.
+///
+/// `matrix` holds `n * n` similarities in row-major order, where row `i` and
+/// column `j` refer to `node_ids[i]` and `node_ids[j]`. Each node gets an edge
+/// to every other node with similarity at least `threshold`, keeping only the
+/// `top_k` most similar when `top_k` is positive. The diagonal and NaN entries
+/// are skipped. Edges are directed, since the matrix need not be symmetric.
+pub fn build_graph_from_similarity(
+    node_ids: &[i64],
+    matrix: &[f64],
+    threshold: f64,
+    top_k: usize,
+) -> Result<SimilarityGraphResult> {
+    check_threshold(threshold)?;
+    let n = node_ids.len();
+    if n.checked_mul(n) != Some(matrix.len()) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Similarity matrix has {} entries, expected {} for {} nodes",
+            matrix.len(),
+            n.saturating_mul(n),
+            n
+        )));
+    }
+    let mut seen = HashSet::with_capacity(n);
+    if let Some(&id) = node_ids.iter().find(|&&id| !seen.insert(id)) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Node {} appears more than once",
+            id
+        )));
+    }
+
+    let mut result = SimilarityGraphResult::default();
+    for (i, row) in matrix.chunks(n.max(1)).enumerate() {
+        let candidates = row
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(j, &sim)| (node_ids[j], sim))
+            .collect();
+        result.push_row(node_ids[i], candidates, threshold, top_k);
+    }
+    Ok(result)
+}
+
+/// Build a graph from similarities given as `(src, dst, similarity)` triples,
+/// the coordinate (COO) form of a sparse similarity matrix.
+///
+/// Applies the same `threshold` and per-node `top_k` as
+/// [`build_graph_from_similarity`]. Self-pairs are skipped, and sources appear
+/// in the order they are first listed.
+pub fn build_graph_from_similarity_pairs(
+    src: &[i64],
+    dst: &[i64],
+    similarities: &[f64],
+    threshold: f64,
+    top_k: usize,
+) -> Result<SimilarityGraphResult> {
+    check_threshold(threshold)?;
+    if src.len() != dst.len() || src.len() != similarities.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and similarities must have the same length".to_string(),
+        ));
+    }
+
+    let mut row_of: HashMap<i64, usize> = HashMap::new();
+    let mut rows: Vec<(i64, Vec<(i64, f64)>)> = Vec::new();
+    for ((&u, &v), &sim) in src.iter().zip(dst).zip(similarities) {
+        if u == v {
+            continue;
+        }
+        let row = *row_of.entry(u).or_insert_with(|| {
+            rows.push((u, Vec::new()));
+            rows.len() - 1
+        });
+        rows[row].1.push((v, sim));
+    }
+
+    let mut result = SimilarityGraphResult::default();
+    for (node, candidates) in rows {
+        result.push_row(node, candidates, threshold, top_k);
+    }
+    Ok(result)
+}
+
+/// Exponent of the LFR degree distribution.
+const LFR_DEGREE_EXPONENT: f64 = 2.5;
+
+/// Exponent of the LFR community size distribution.
+const LFR_COMMUNITY_EXPONENT: f64 = 1.5;
+
+/// Maximum LFR degree as a multiple of the average degree.
+const LFR_MAX_DEGREE_RATIO: f64 = 2.5;
+
+/// Number of reshuffles used to pair stubs left over by rejected edges.
+const LFR_PAIRING_ROUNDS: usize = 10;
+
+/// Result of LFR benchmark generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LfrResult {
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    /// Nodes `0..n` in order.
+    pub node_ids: Vec<i64>,
+    /// Planted community of each node in `node_ids`.
+    pub community_ids: Vec<i64>,
+}
+
+impl LfrResult {
+    /// Shifts the node IDs from `0..n` to `start_id..start_id + n`, keeping
+    /// the community IDs.
+    pub fn with_start_id(mut self, start_id: i64) -> Result<Self> {
+        let n = self.node_ids.len();
+        offset_ids(
+            n,
+            start_id,
+            [&mut self.src, &mut self.dst, &mut self.node_ids],
+        )?;
+        Ok(self)
+    }
+}
+
+/// Sample a continuous power law `x^-exponent` on `[min, max]` by inverting its CDF.
+fn power_law_sample(rng: &mut SplitMix64, exponent: f64, min: f64, max: f64) -> f64 {
+    let e = 1.0 - exponent;
+    let (a, b) = (min.powf(e), max.powf(e));
+    (a + rng.next_f64() * (b - a)).powf(1.0 / e)
+}
+
+/// Mean of a continuous power law `x^-exponent` on `[min, max]`, for an
+/// exponent other than 1 and 2.
+fn power_law_mean(exponent: f64, min: f64, max: f64) -> f64 {
+    let (e1, e2) = (1.0 - exponent, 2.0 - exponent);
+    ((max.powf(e2) - min.powf(e2)) / e2) / ((max.powf(e1) - min.powf(e1)) / e1)
+}
+
+/// Minimum degree that gives the degree distribution the requested mean,
+/// found by bisection since the mean grows with the minimum.
+fn lfr_min_degree(avg_degree: f64, max_degree: f64) -> f64 {
+    if max_degree <= avg_degree {
+        return max_degree;
+    }
+    let (mut lo, mut hi) = (1.0, avg_degree);
+    if power_law_mean(LFR_DEGREE_EXPONENT, lo, max_degree) >= avg_degree {
+        return lo;
+    }
+    for _ in 0..100 {
+        let mid = 0.5 * (lo + hi);
+        if power_law_mean(LFR_DEGREE_EXPONENT, mid, max_degree) < avg_degree {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Community sizes from the size power law, summing to exactly `n`. A
+/// remainder too small to be a community joins the smallest one.
+fn lfr_community_sizes(rng: &mut SplitMix64, n: usize, min: usize, max: usize) -> Vec<usize> {
+    let mut sizes = Vec::new();
+    let mut remaining = n;
+    while remaining > 0 {
+        let size = power_law_sample(rng, LFR_COMMUNITY_EXPONENT, min as f64, max as f64 + 1.0)
+            .floor() as usize;
+        let size = size.clamp(min, max);
+        if size <= remaining {
+            sizes.push(size);
+            remaining -= size;
+        } else if remaining >= min {
+            sizes.push(remaining);
+            remaining = 0;
+        } else if let Some(smallest) = sizes.iter_mut().min() {
+            *smallest += remaining;
+            remaining = 0;
+        } else {
+            sizes.push(remaining);
+            remaining = 0;
+        }
+    }
+    sizes
+}
+
+/// Shuffle stubs and join them in pairs, keeping pairs that `accept` allows
+/// and that are not yet edges. Rejected stubs are reshuffled for a few
+/// rounds, and any still unpaired after that are dropped.
+fn pair_stubs(
+    rng: &mut SplitMix64,
+    mut stubs: Vec<usize>,
+    seen: &mut HashSet<(usize, usize)>,
+    accept: impl Fn(usize, usize) -> bool,
+    edges: &mut Vec<(usize, usize)>,
+) {
+    for _ in 0..LFR_PAIRING_ROUNDS {
+        for i in (1..stubs.len()).rev() {
+            stubs.swap(i, rng.next_below(i + 1));
+        }
+        let mut rejected = Vec::new();
+        for pair in stubs.chunks_exact(2) {
+            let (u, v) = (pair[0], pair[1]);
+            if u != v && accept(u, v) && seen.insert((u.min(v), u.max(v))) {
+                edges.push((u, v));
+            } else {
+                rejected.extend_from_slice(pair);
+            }
+        }
+        if rejected.len() < 2 {
+            return;
+        }
+        stubs = rejected;
+    }
+}
+
+/// Generate an LFR (Lancichinetti-Fortunato-Radicchi) benchmark graph with
+/// planted communities.
+///
+/// Degrees follow a power law with exponent 2.5 and community sizes a power
+/// law with exponent 1.5, with the largest degree 2.5 times `avg_degree`.
+/// Each node keeps about `1 - mu` of its edges inside its community and
+/// sends the rest to other communities, so `mu = 0` gives disconnected
+/// communities and larger `mu` makes them harder to recover. Self-loops and
+/// repeated edges are dropped, so realized degrees and mixing are close to,
+/// but not exactly, the requested values. Returns the edges along with the
+/// ground-truth community of every node.
+pub fn generate_lfr(n: usize, mu: f64, avg_degree: f64, seed: u64) -> Result<LfrResult> {
+    if n == 0 {
+        return Err(OnagerError::InvalidArgument("n must be > 0".to_string()));
+    }
+    if !(0.0..=1.0).contains(&mu) {
+        return Err(OnagerError::InvalidArgument(
+            "mu must be in [0, 1]".to_string(),
+        ));
+    }
+    if !(1.0..=(n - 1) as f64).contains(&avg_degree) {
+        return Err(OnagerError::InvalidArgument(
+            "avg_degree must be in [1, n - 1]".to_string(),
+        ));
+    }
+
+    let mut rng = SplitMix64(seed);
+    let max_degree = (avg_degree * LFR_MAX_DEGREE_RATIO).min((n - 1) as f64);
+    let min_degree = lfr_min_degree(avg_degree, max_degree);
+    let degrees: Vec<usize> = (0..n)
+        .map(|_| {
+            let d = power_law_sample(&mut rng, LFR_DEGREE_EXPONENT, min_degree, max_degree);
+            (d.round() as usize).clamp(1, n - 1)
+        })
+        .collect();
+    let mut internal: Vec<usize> = degrees
+        .iter()
+        .map(|&d| ((1.0 - mu) * d as f64).round() as usize)
+        .collect();
+    let external: Vec<usize> = (0..n)
+        .flat_map(|u| std::iter::repeat_n(u, degrees[u] - internal[u]))
+        .collect();
+
+    // Communities must be larger than the internal degree of their members
+    let max_size = (max_degree.round() as usize + 1).min(n);
+    let min_size = (min_degree.round() as usize + 1).min(max_size);
+    let mut sizes = lfr_community_sizes(&mut rng, n, min_size, max_size);
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+
+    // Place nodes with the largest internal degree first, each in a random
+    // community big enough for it. A node that fits nowhere goes to the
+    // largest community with room and loses the edges that do not fit.
+    let mut order: Vec<usize> = (0..n).collect();
+    for i in (1..n).rev() {
+        order.swap(i, rng.next_below(i + 1));
+    }
+    order.sort_by(|&a, &b| internal[b].cmp(&internal[a]));
+    let mut free = sizes.clone();
+    let mut community = vec![0usize; n];
+    for &u in &order {
+        let fits = sizes.partition_point(|&s| s > internal[u]);
+        let picked = (0..32)
+            .map(|_| rng.next_below(fits.max(1)))
+            .find(|&c| c < fits && free[c] > 0)
+            .or_else(|| (0..fits).find(|&c| free[c] > 0))
+            .or_else(|| (0..sizes.len()).find(|&c| free[c] > 0))
+            .unwrap_or(0);
+        free[picked] = free[picked].saturating_sub(1);
+        community[u] = picked;
+        internal[u] = internal[u].min(sizes[picked] - 1);
+    }
+
+    let mut members = vec![Vec::new(); sizes.len()];
+    for (u, &c) in community.iter().enumerate() {
+        members[c].push(u);
+    }
+    let mut seen = HashSet::new();
+    let mut edges = Vec::new();
+    for nodes in &members {
+        let mut stubs: Vec<usize> = nodes
+            .iter()
+            .flat_map(|&u| std::iter::repeat_n(u, internal[u]))
+            .collect();
+        // An odd stub count cannot be paired, so one stub is dropped
+        if !stubs.len().is_multiple_of(2) {
+            stubs.pop();
+        }
+        pair_stubs(&mut rng, stubs, &mut seen, |_, _| true, &mut edges);
+    }
+    pair_stubs(
+        &mut rng,
+        external,
+        &mut seen,
+        |u, v| community[u] != community[v],
+        &mut edges,
+    );
+
+    let (src, dst) = edges.iter().map(|&(u, v)| (u as i64, v as i64)).unzip();
+    Ok(LfrResult {
+        src,
+        dst,
+        node_ids: (0..n as i64).collect(),
+        community_ids: community.iter().map(|&c| c as i64).collect(),
+    })
 }
 
 #[cfg(test)]
@@ -205,4 +1025,320 @@ mod tests {
         assert_eq!(result1.src, result2.src);
         assert_eq!(result1.dst, result2.dst);
     }
+
+    /// Fraction of edges whose endpoints are in different communities.
+    fn lfr_mixing(result: &LfrResult) -> f64 {
+        let crossing = result
+            .src
+            .iter()
+            .zip(&result.dst)
+            .filter(|&(&u, &v)| {
+                result.community_ids[u as usize] != result.community_ids[v as usize]
+            })
+            .count();
+        crossing as f64 / result.src.len() as f64
+    }
+
+    #[test]
+    fn test_lfr_basic() {
+        let result = generate_lfr(500, 0.2, 10.0, 42).unwrap();
+
+        assert_eq!(result.node_ids, (0..500).collect::<Vec<i64>>());
+        assert_eq!(result.community_ids.len(), 500);
+        assert_eq!(result.src.len(), result.dst.len());
+
+        // Simple graph with an average degree near the requested one
+        let mut pairs = HashSet::new();
+        for (&u, &v) in result.src.iter().zip(&result.dst) {
+            assert_ne!(u, v);
+            assert!(pairs.insert((u.min(v), u.max(v))));
+        }
+        let avg = 2.0 * result.src.len() as f64 / 500.0;
+        assert!((avg - 10.0).abs() < 2.0, "average degree {}", avg);
+
+        let communities: HashSet<i64> = result.community_ids.iter().copied().collect();
+        assert!(communities.len() > 1);
+    }
+
+    #[test]
+    fn test_lfr_mixing_parameter() {
+        let separate = generate_lfr(300, 0.0, 8.0, 7).unwrap();
+        assert_eq!(lfr_mixing(&separate), 0.0);
+
+        let mixed = generate_lfr(1000, 0.4, 12.0, 7).unwrap();
+        let mu = lfr_mixing(&mixed);
+        assert!((mu - 0.4).abs() < 0.1, "realized mixing {}", mu);
+
+        let all_external = generate_lfr(300, 1.0, 8.0, 7).unwrap();
+        assert_eq!(lfr_mixing(&all_external), 1.0);
+    }
+
+    #[test]
+    fn test_lfr_deterministic_with_same_seed() {
+        let result1 = generate_lfr(200, 0.3, 6.0, 99).unwrap();
+        let result2 = generate_lfr(200, 0.3, 6.0, 99).unwrap();
+
+        assert_eq!(result1.src, result2.src);
+        assert_eq!(result1.dst, result2.dst);
+        assert_eq!(result1.community_ids, result2.community_ids);
+    }
+
+    #[test]
+    fn test_lfr_invalid_params() {
+        assert!(generate_lfr(0, 0.2, 5.0, 42).is_err());
+        assert!(generate_lfr(100, -0.1, 5.0, 42).is_err());
+        assert!(generate_lfr(100, 1.5, 5.0, 42).is_err());
+        assert!(generate_lfr(100, 0.2, 0.5, 42).is_err());
+        assert!(generate_lfr(10, 0.2, 10.0, 42).is_err());
+        assert!(generate_lfr(100, 0.2, f64::NAN, 42).is_err());
+    }
+
+    #[test]
+    fn test_generator_start_id_offsets_nodes() {
+        let base = generate_erdos_renyi(5, 1.0, 42).unwrap();
+        assert_eq!(base.node_ids, vec![0, 1, 2, 3, 4]);
+        let shifted = base.clone().with_start_id(1000).unwrap();
+        assert_eq!(shifted.node_ids, vec![1000, 1001, 1002, 1003, 1004]);
+        let offset = |v: &[i64]| v.iter().map(|x| x + 1000).collect::<Vec<_>>();
+        assert_eq!(shifted.src, offset(&base.src));
+        assert_eq!(shifted.dst, offset(&base.dst));
+
+        let lfr = generate_lfr(50, 0.2, 5.0, 7).unwrap();
+        let shifted = lfr.clone().with_start_id(-25).unwrap();
+        assert_eq!(shifted.node_ids[0], -25);
+        assert_eq!(shifted.community_ids, lfr.community_ids);
+
+        assert!(generate_erdos_renyi(5, 0.5, 1)
+            .unwrap()
+            .with_start_id(i64::MAX - 3)
+            .is_err());
+        assert!(generate_erdos_renyi(5, 0.5, 1)
+            .unwrap()
+            .with_start_id(i64::MAX - 4)
+            .is_ok());
+    }
+
+    const SPACES: [EdgeSpace; 4] = [
+        EdgeSpace {
+            directed: false,
+            self_loops: false,
+        },
+        EdgeSpace {
+            directed: false,
+            self_loops: true,
+        },
+        EdgeSpace {
+            directed: true,
+            self_loops: false,
+        },
+        EdgeSpace {
+            directed: true,
+            self_loops: true,
+        },
+    ];
+
+    #[test]
+    fn test_edge_space_decodes_every_pair_once() {
+        for space in SPACES {
+            let size = space.size(6).unwrap();
+            let all: Vec<u64> = (0..size).collect();
+            let result = space.decode(6, &all);
+            let mut pairs = HashSet::new();
+            for (&u, &v) in result.src.iter().zip(&result.dst) {
+                assert!((0..6).contains(&u) && (0..6).contains(&v));
+                assert!(space.self_loops || u != v);
+                assert!(space.directed || u <= v);
+                assert!(pairs.insert((u, v)));
+            }
+            let expected = match (space.directed, space.self_loops) {
+                (false, false) => 15,
+                (false, true) => 21,
+                (true, false) => 30,
+                (true, true) => 36,
+            };
+            assert_eq!(pairs.len(), expected);
+        }
+    }
+
+    #[test]
+    fn test_gnm_exact_edge_count() {
+        for space in SPACES {
+            let result = generate_gnm(30, 100, space, false, 42).unwrap();
+            assert_eq!(result.src.len(), 100);
+            let simple = result
+                .clone()
+                .simplify(space.directed, space.self_loops, false);
+            assert_eq!(simple.src.len(), 100);
+        }
+
+        // Every pair, so the complete graph
+        let complete = generate_gnm(5, 10, EdgeSpace::default(), false, 1).unwrap();
+        assert_eq!(complete.src.len(), 10);
+        assert!(generate_gnm(5, 11, EdgeSpace::default(), false, 1).is_err());
+
+        // Parallel edges allow more edges than pairs
+        let multi = generate_gnm(3, 10, EdgeSpace::default(), true, 1).unwrap();
+        assert_eq!(multi.src.len(), 10);
+        assert!(generate_gnm(1, 1, EdgeSpace::default(), true, 1).is_err());
+        assert!(generate_gnm(0, 0, EdgeSpace::default(), false, 1).is_err());
+    }
+
+    #[test]
+    fn test_gnp_directed_and_self_loops() {
+        let all = EdgeSpace {
+            directed: true,
+            self_loops: true,
+        };
+        assert_eq!(generate_gnp(7, 1.0, all, 1).unwrap().src.len(), 49);
+        assert!(generate_gnp(7, 0.0, all, 1).unwrap().src.is_empty());
+
+        let result = generate_gnp(200, 0.1, all, 42).unwrap();
+        let expected = 0.1 * 200.0 * 200.0;
+        assert!((result.src.len() as f64 - expected).abs() < 0.1 * expected);
+        assert!(result.src.iter().zip(&result.dst).any(|(u, v)| u > v));
+        assert!(result.src.iter().zip(&result.dst).any(|(u, v)| u == v));
+
+        let again = generate_gnp(200, 0.1, all, 42).unwrap();
+        assert_eq!(result.src, again.src);
+        assert_eq!(result.dst, again.dst);
+        assert!(generate_gnp(10, 1.5, all, 1).is_err());
+    }
+
+    #[test]
+    fn test_simplify_drops_loops_and_repeats() {
+        let result = GeneratorResult {
+            src: vec![0, 1, 2, 0, 1],
+            dst: vec![1, 0, 2, 1, 2],
+            node_ids: vec![0, 1, 2],
+        };
+        let simple = result.clone().simplify(false, false, false);
+        assert_eq!((simple.src, simple.dst), (vec![0, 1], vec![1, 2]));
+        let directed = result.clone().simplify(true, false, false);
+        assert_eq!((directed.src, directed.dst), (vec![0, 1, 1], vec![1, 0, 2]));
+        let kept = result.clone().simplify(false, true, true);
+        assert_eq!(kept.src.len(), 5);
+    }
+
+    #[test]
+    fn test_random_geometric_matches_brute_force() {
+        for radius in [0.0, 0.05, 0.2, 2.0] {
+            let result = generate_random_geometric(200, radius, 42).unwrap();
+            let mut expected = Vec::new();
+            for u in 0..200 {
+                for v in (u + 1)..200 {
+                    let (dx, dy) = (result.x[u] - result.x[v], result.y[u] - result.y[v]);
+                    if dx * dx + dy * dy <= radius * radius {
+                        expected.push((u as i64, v as i64));
+                    }
+                }
+            }
+            let edges: Vec<(i64, i64)> = result
+                .src
+                .iter()
+                .copied()
+                .zip(result.dst.iter().copied())
+                .collect();
+            assert_eq!(edges, expected, "radius {}", radius);
+        }
+        let placed = generate_random_geometric(50, 0.1, 1).unwrap();
+        assert!(placed
+            .x
+            .iter()
+            .chain(&placed.y)
+            .all(|v| (0.0..1.0).contains(v)));
+        assert!(generate_random_geometric(0, 0.1, 1).is_err());
+        assert!(generate_random_geometric(10, -0.1, 1).is_err());
+        assert!(generate_random_geometric(10, f64::NAN, 1).is_err());
+    }
+
+    #[test]
+    fn test_knn_graph_matches_brute_force() {
+        let mut rng = SplitMix64(7);
+        let n = 300;
+        let ids: Vec<i64> = (0..n as i64).map(|i| i * 10).collect();
+        // Rounded coordinates so that some points tie
+        let x: Vec<f64> = (0..n).map(|_| (rng.next_f64() * 20.0).round()).collect();
+        let y: Vec<f64> = (0..n).map(|_| (rng.next_f64() * 20.0).round()).collect();
+        let result = build_knn_graph(&ids, &x, &y, 5).unwrap();
+        assert_eq!(result.src.len(), n * 5);
+        for p in 0..n {
+            let mut all: Vec<Neighbor> = (0..n)
+                .filter(|&q| q != p)
+                .map(|q| Neighbor {
+                    dist2: (x[p] - x[q]).powi(2) + (y[p] - y[q]).powi(2),
+                    index: q,
+                })
+                .collect();
+            all.sort();
+            let expected: Vec<i64> = all[..5].iter().map(|nb| ids[nb.index]).collect();
+            assert_eq!(result.dst[p * 5..(p + 1) * 5], expected[..]);
+            assert!(result.src[p * 5..(p + 1) * 5].iter().all(|&s| s == ids[p]));
+        }
+    }
+
+    #[test]
+    fn test_knn_graph_small_and_invalid() {
+        let result = build_knn_graph(&[1, 2, 3], &[0.0, 3.0, 0.0], &[0.0, 4.0, 1.0], 10).unwrap();
+        assert_eq!(result.src, vec![1, 1, 2, 2, 3, 3]);
+        assert_eq!(result.dst, vec![3, 2, 3, 1, 1, 2]);
+        assert_eq!(result.distances[1], 5.0);
+
+        assert!(build_knn_graph(&[1], &[0.0], &[0.0], 1)
+            .unwrap()
+            .src
+            .is_empty());
+        assert!(build_knn_graph(&[1, 2], &[0.0], &[0.0, 1.0], 1).is_err());
+        assert!(build_knn_graph(&[1, 2], &[0.0, 1.0], &[0.0, 1.0], 0).is_err());
+        assert!(build_knn_graph(&[1, 2], &[0.0, f64::NAN], &[0.0, 1.0], 1).is_err());
+        assert!(build_knn_graph(&[1, 1], &[0.0, 1.0], &[0.0, 1.0], 1).is_err());
+    }
+
+    #[test]
+    fn test_similarity_graph_dense() {
+        let ids = [10, 20, 30];
+        #[rustfmt::skip]
+        let matrix = [
+            1.0, 0.9, 0.2,
+            0.9, 1.0, 0.5,
+            0.2, f64::NAN, 1.0,
+        ];
+        let result = build_graph_from_similarity(&ids, &matrix, 0.4, 0).unwrap();
+        assert_eq!(result.src, vec![10, 20, 20]);
+        assert_eq!(result.dst, vec![20, 10, 30]);
+        assert_eq!(result.similarities, vec![0.9, 0.9, 0.5]);
+
+        let top = build_graph_from_similarity(&ids, &matrix, f64::NEG_INFINITY, 1).unwrap();
+        assert_eq!(top.src, vec![10, 20, 30]);
+        assert_eq!(top.dst, vec![20, 10, 10]);
+
+        assert!(build_graph_from_similarity(&ids, &matrix[..8], 0.0, 0).is_err());
+        assert!(build_graph_from_similarity(&ids, &matrix, f64::NAN, 0).is_err());
+        assert!(build_graph_from_similarity(&[1, 1], &[0.0; 4], 0.0, 0).is_err());
+        assert!(build_graph_from_similarity(&[], &[], 0.0, 0)
+            .unwrap()
+            .src
+            .is_empty());
+    }
+
+    #[test]
+    fn test_similarity_graph_pairs_match_dense() {
+        let ids = [1, 2, 3, 4];
+        let mut rng = SplitMix64(3);
+        let matrix: Vec<f64> = (0..16).map(|_| rng.next_f64() * 2.0 - 1.0).collect();
+        let (mut src, mut dst) = (Vec::new(), Vec::new());
+        for &u in &ids {
+            for &v in &ids {
+                src.push(u);
+                dst.push(v);
+            }
+        }
+        for top_k in 0..4 {
+            let dense = build_graph_from_similarity(&ids, &matrix, 0.0, top_k).unwrap();
+            let pairs = build_graph_from_similarity_pairs(&src, &dst, &matrix, 0.0, top_k).unwrap();
+            assert_eq!(dense.src, pairs.src);
+            assert_eq!(dense.dst, pairs.dst);
+            assert_eq!(dense.similarities, pairs.similarities);
+        }
+        assert!(build_graph_from_similarity_pairs(&[1], &[2], &[], 0.0, 0).is_err());
+    }
 }
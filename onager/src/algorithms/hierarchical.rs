@@ -0,0 +1,279 @@
+//! Hierarchical clustering module.
+//!
+//! Agglomerative clustering of nodes by shared neighbors, producing a dendrogram.
+
+use serde::{Deserialize, Serialize};
+
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+use std::str::FromStr;
+
+/// How the distance between two clusters is derived from node distances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Linkage {
+    /// Distance between the closest pair of nodes.
+    Single,
+    /// Distance between the farthest pair of nodes.
+    Complete,
+    /// Mean distance over all pairs of nodes.
+    Average,
+}
+
+impl FromStr for Linkage {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "single" => Ok(Linkage::Single),
+            "complete" => Ok(Linkage::Complete),
+            "average" => Ok(Linkage::Average),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown linkage '{}', expected 'single', 'complete', or 'average'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Dendrogram produced by agglomerative clustering.
+///
+/// Row `i` merges clusters `child_a[i]` and `child_b[i]` into `merged_ids[i]`
+/// at distance `heights[i]`. Leaf clusters use the original node IDs, and
+/// merged clusters get new IDs larger than every node ID. Rows are sorted by
+/// height, so cutting the dendrogram at a height keeps a prefix of the rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DendrogramResult {
+    pub child_a: Vec<i64>,
+    pub child_b: Vec<i64>,
+    pub merged_ids: Vec<i64>,
+    pub heights: Vec<f64>,
+}
+
+/// Condensed symmetric distance matrix storing only pairs `i < j`.
+struct CondensedMatrix {
+    n: usize,
+    values: Vec<f64>,
+}
+
+impl CondensedMatrix {
+    fn index(&self, i: usize, j: usize) -> usize {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        i * (2 * self.n - i - 1) / 2 + (j - i - 1)
+    }
+
+    fn get(&self, i: usize, j: usize) -> f64 {
+        self.values[self.index(i, j)]
+    }
+
+    fn set(&mut self, i: usize, j: usize, value: f64) {
+        let idx = self.index(i, j);
+        self.values[idx] = value;
+    }
+}
+
+fn find_root(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+/// Compute an agglomerative clustering of nodes.
+///
+/// Edges are treated as undirected. The distance between two nodes is one
+/// minus the Jaccard similarity of their closed neighborhoods (neighbors plus
+/// the node itself), so adjacent nodes with many shared neighbors are close.
+/// Clusters are merged with the nearest-neighbor chain algorithm, which needs
+/// O(n^2) time and memory.
+pub fn compute_hierarchical_clustering(
+    src: &[i64],
+    dst: &[i64],
+    linkage: Linkage,
+) -> Result<DendrogramResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let n = nodes.len();
+    let mut neighborhoods: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    for i in 0..src.len() {
+        let (u, v) = (nodes.of(src[i]), nodes.of(dst[i]));
+        neighborhoods[u].push(v);
+        neighborhoods[v].push(u);
+    }
+    for adj in &mut neighborhoods {
+        adj.sort_unstable();
+        adj.dedup();
+    }
+
+    let mut dist = CondensedMatrix {
+        n,
+        values: vec![0.0; n * n.saturating_sub(1) / 2],
+    };
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (a, b) = (&neighborhoods[i], &neighborhoods[j]);
+            let (mut x, mut y, mut shared) = (0, 0, 0);
+            while x < a.len() && y < b.len() {
+                match a[x].cmp(&b[y]) {
+                    std::cmp::Ordering::Less => x += 1,
+                    std::cmp::Ordering::Greater => y += 1,
+                    std::cmp::Ordering::Equal => {
+                        shared += 1;
+                        x += 1;
+                        y += 1;
+                    }
+                }
+            }
+            let union = a.len() + b.len() - shared;
+            dist.set(i, j, 1.0 - shared as f64 / union as f64);
+        }
+    }
+
+    // Nearest-neighbor chain. Each cluster lives in the slot of one of its
+    // leaves, so a merge is recorded as a pair of leaf slots.
+    let mut active = vec![true; n];
+    let mut sizes = vec![1.0; n];
+    let mut merges: Vec<(usize, usize, f64)> = Vec::with_capacity(n.saturating_sub(1));
+    let mut chain: Vec<usize> = Vec::new();
+    while merges.len() + 1 < n {
+        if chain.is_empty() {
+            if let Some(start) = (0..n).find(|&i| active[i]) {
+                chain.push(start);
+            }
+        }
+        let Some(&a) = chain.last() else {
+            break;
+        };
+        let previous = chain.len().checked_sub(2).map(|i| chain[i]);
+        // Prefer the previous chain element on ties so the chain terminates
+        let mut nearest = previous;
+        let mut nearest_dist = previous.map_or(f64::INFINITY, |p| dist.get(a, p));
+        for k in (0..n).filter(|&k| active[k] && k != a) {
+            let d = dist.get(a, k);
+            if d < nearest_dist {
+                nearest = Some(k);
+                nearest_dist = d;
+            }
+        }
+        let Some(b) = nearest else {
+            break;
+        };
+        if Some(b) != previous {
+            chain.push(b);
+            continue;
+        }
+
+        chain.truncate(chain.len() - 2);
+        merges.push((a, b, nearest_dist));
+        // Keep the merged cluster in slot `a` and update distances with Lance-Williams
+        for k in (0..n).filter(|&k| active[k] && k != a && k != b) {
+            let (da, db) = (dist.get(a, k), dist.get(b, k));
+            let merged = match linkage {
+                Linkage::Single => da.min(db),
+                Linkage::Complete => da.max(db),
+                Linkage::Average => (sizes[a] * da + sizes[b] * db) / (sizes[a] + sizes[b]),
+            };
+            dist.set(a, k, merged);
+        }
+        active[b] = false;
+        sizes[a] += sizes[b];
+    }
+
+    // The chain finds merges out of order, so sort by height and relabel
+    merges.sort_by(|x, y| x.2.total_cmp(&y.2));
+    let max_id = nodes.node_ids.iter().copied().max().unwrap_or(0);
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut labels = nodes.node_ids;
+    let mut result = DendrogramResult {
+        child_a: Vec::with_capacity(merges.len()),
+        child_b: Vec::with_capacity(merges.len()),
+        merged_ids: Vec::with_capacity(merges.len()),
+        heights: Vec::with_capacity(merges.len()),
+    };
+    for (step, &(a, b, height)) in merges.iter().enumerate() {
+        let merged_id = max_id
+            .checked_add(1 + step as i64)
+            .ok_or_else(|| OnagerError::InvalidArgument("Node IDs are too large".to_string()))?;
+        let (ra, rb) = (find_root(&mut parent, a), find_root(&mut parent, b));
+        result.child_a.push(labels[ra]);
+        result.child_b.push(labels[rb]);
+        result.merged_ids.push(merged_id);
+        result.heights.push(height);
+        parent[rb] = ra;
+        labels[ra] = merged_id;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn two_triangles() -> (Vec<i64>, Vec<i64>) {
+        // Triangles 1-2-3 and 4-5-6 joined by edge 3-4
+        (vec![1, 2, 3, 4, 5, 6, 3], vec![2, 3, 1, 5, 6, 4, 4])
+    }
+
+    #[test]
+    fn test_hierarchical_dendrogram_shape() {
+        let (src, dst) = two_triangles();
+        let result = compute_hierarchical_clustering(&src, &dst, Linkage::Average).unwrap();
+        assert_eq!(result.merged_ids.len(), 5);
+        assert_eq!(result.merged_ids, vec![7, 8, 9, 10, 11]);
+        for w in result.heights.windows(2) {
+            assert!(w[0] <= w[1]);
+        }
+        // Every cluster except the root is merged exactly once
+        let mut children: Vec<i64> = result.child_a.clone();
+        children.extend(&result.child_b);
+        children.sort_unstable();
+        assert_eq!(children, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_hierarchical_last_merge_joins_triangles() {
+        let (src, dst) = two_triangles();
+        for linkage in [Linkage::Single, Linkage::Complete, Linkage::Average] {
+            let result = compute_hierarchical_clustering(&src, &dst, linkage).unwrap();
+            // Cutting below the last merge leaves the two triangles
+            let mut parent: HashMap<i64, i64> = HashMap::new();
+            for i in 0..result.merged_ids.len() - 1 {
+                parent.insert(result.child_a[i], result.merged_ids[i]);
+                parent.insert(result.child_b[i], result.merged_ids[i]);
+            }
+            let root = |mut x: i64| {
+                while let Some(&p) = parent.get(&x) {
+                    x = p;
+                }
+                x
+            };
+            assert_eq!(root(1), root(2));
+            assert_eq!(root(1), root(3));
+            assert_eq!(root(4), root(6));
+            assert_ne!(root(1), root(4));
+        }
+    }
+
+    #[test]
+    fn test_linkage_from_str() {
+        assert_eq!("average".parse::<Linkage>().unwrap(), Linkage::Average);
+        assert_eq!("SINGLE".parse::<Linkage>().unwrap(), Linkage::Single);
+        assert!("ward".parse::<Linkage>().is_err());
+    }
+
+    #[test]
+    fn test_hierarchical_empty_graph_error() {
+        assert!(compute_hierarchical_clustering(&[], &[], Linkage::Average).is_err());
+    }
+}
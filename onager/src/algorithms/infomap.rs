@@ -0,0 +1,501 @@
+//! Flow-based Infomap module.
+//!
+//! Two-level Infomap over weighted edges, either undirected or directed with
+//! PageRank flow, which minimizes the map equation by moving nodes between
+//! modules and merging modules into larger nodes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+use super::community::relabel_by_appearance;
+use super::sbm::SplitMix64;
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+
+/// Maximum number of power iteration steps for directed flow.
+const FLOW_MAX_ITER: usize = 1000;
+
+/// Total change of the visit rates at which the power iteration stops.
+const FLOW_TOLERANCE: f64 = 1e-15;
+
+/// Smallest decrease of the codelength, in bits, that a move must bring.
+const MIN_IMPROVEMENT: f64 = 1e-10;
+
+/// Result of weighted Infomap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightedInfomapResult {
+    pub node_ids: Vec<i64>,
+    pub community_ids: Vec<i64>,
+    /// Map equation codelength of the partition, in bits per step.
+    pub codelength: f64,
+}
+
+fn plogp(p: f64) -> f64 {
+    if p > 0.0 {
+        p * p.log2()
+    } else {
+        0.0
+    }
+}
+
+/// Flow network over compact node indices, where `flow[u]` is the visit
+/// rate of node `u` and `links` holds `(source, target, flow)` triples
+/// between distinct nodes.
+struct FlowNetwork {
+    node_ids: Vec<i64>,
+    flow: Vec<f64>,
+    links: Vec<(usize, usize, f64)>,
+}
+
+/// Builds the flow network of the edges.
+///
+/// Undirected flow is proportional to edge weight. Directed flow comes from
+/// PageRank, where the walker teleports to a random node with probability
+/// `teleportation` and always from nodes without outgoing weight. Link flow
+/// is the visit rate of the source times the link's share of its outgoing
+/// weight, so teleportation steps are not encoded.
+fn flow_network(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+    teleportation: f64,
+) -> Result<FlowNetwork> {
+    let nodes = NodeIndex::new(src, dst);
+    let n = nodes.len();
+
+    let mut arc_weights: HashMap<(usize, usize), f64> = HashMap::new();
+    let mut arcs = Vec::new();
+    for i in 0..src.len() {
+        let w = if weights.is_empty() { 1.0 } else { weights[i] };
+        if !w.is_finite() || w < 0.0 {
+            return Err(OnagerError::InvalidArgument(format!(
+                "Edge weights must be finite and non-negative, got {}",
+                w
+            )));
+        }
+        let (u, v) = (nodes.of(src[i]), nodes.of(dst[i]));
+        if u == v || w == 0.0 {
+            continue;
+        }
+        let key = if directed {
+            (u, v)
+        } else {
+            (u.min(v), u.max(v))
+        };
+        // Parallel edges add up, and arcs keep the order they first appear in
+        let entry = arc_weights.entry(key).or_insert_with(|| {
+            arcs.push(key);
+            0.0
+        });
+        *entry += w;
+    }
+
+    let mut strength = vec![0.0; n];
+    for &(u, v) in &arcs {
+        strength[u] += arc_weights[&(u, v)];
+        if !directed {
+            strength[v] += arc_weights[&(u, v)];
+        }
+    }
+
+    if !directed {
+        let total: f64 = strength.iter().sum();
+        if total == 0.0 {
+            return Ok(FlowNetwork {
+                node_ids: nodes.node_ids,
+                flow: vec![0.0; n],
+                links: Vec::new(),
+            });
+        }
+        let mut links = Vec::with_capacity(2 * arcs.len());
+        for &(u, v) in &arcs {
+            let f = arc_weights[&(u, v)] / total;
+            links.push((u, v, f));
+            links.push((v, u, f));
+        }
+        let flow = strength.iter().map(|s| s / total).collect();
+        return Ok(FlowNetwork {
+            node_ids: nodes.node_ids,
+            flow,
+            links,
+        });
+    }
+
+    let mut rank = vec![1.0 / n as f64; n];
+    for _ in 0..FLOW_MAX_ITER {
+        let dangling: f64 = (0..n)
+            .filter(|&u| strength[u] == 0.0)
+            .map(|u| rank[u])
+            .sum();
+        let linked = 1.0 - dangling;
+        let base = (teleportation * linked + dangling) / n as f64;
+        let mut next = vec![base; n];
+        for &(u, v) in &arcs {
+            next[v] += (1.0 - teleportation) * rank[u] * arc_weights[&(u, v)] / strength[u];
+        }
+        let sum: f64 = next.iter().sum();
+        next.iter_mut().for_each(|r| *r /= sum);
+        let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < FLOW_TOLERANCE * n as f64 {
+            break;
+        }
+    }
+    let links = arcs
+        .iter()
+        .map(|&(u, v)| (u, v, rank[u] * arc_weights[&(u, v)] / strength[u]))
+        .collect();
+    Ok(FlowNetwork {
+        node_ids: nodes.node_ids,
+        flow: rank,
+        links,
+    })
+}
+
+/// Flow, exit flow, and enter flow of every module, with the sums the map
+/// equation needs.
+struct ModuleFlows {
+    flow: Vec<f64>,
+    exit: Vec<f64>,
+    enter: Vec<f64>,
+    enter_total: f64,
+}
+
+impl ModuleFlows {
+    /// Codelength term of one module, without the shared index codebook term.
+    fn module_term(flow: f64, exit: f64, enter: f64) -> f64 {
+        plogp(exit + flow) - plogp(exit) - plogp(enter)
+    }
+}
+
+/// One level of the hierarchy, where each node may stand for a module of the
+/// level below.
+struct Level {
+    flow: Vec<f64>,
+    out_links: Vec<Vec<(usize, f64)>>,
+    in_links: Vec<Vec<(usize, f64)>>,
+}
+
+impl Level {
+    fn new(flow: Vec<f64>, links: impl Iterator<Item = (usize, usize, f64)>) -> Self {
+        let n = flow.len();
+        let mut merged: HashMap<(usize, usize), f64> = HashMap::new();
+        let mut order = Vec::new();
+        for (u, v, f) in links.filter(|&(u, v, _)| u != v) {
+            let entry = merged.entry((u, v)).or_insert_with(|| {
+                order.push((u, v));
+                0.0
+            });
+            *entry += f;
+        }
+        let mut out_links = vec![Vec::new(); n];
+        let mut in_links = vec![Vec::new(); n];
+        for (u, v) in order {
+            let f = merged[&(u, v)];
+            out_links[u].push((v, f));
+            in_links[v].push((u, f));
+        }
+        Level {
+            flow,
+            out_links,
+            in_links,
+        }
+    }
+
+    /// Moves nodes to the neighboring module that lowers the codelength most
+    /// until no move helps or `max_iter` sweeps are done. Returns the module
+    /// of each node and whether any node moved.
+    fn move_nodes(&self, max_iter: usize, rng: &mut SplitMix64) -> (Vec<usize>, bool) {
+        let n = self.flow.len();
+        let out_total: Vec<f64> = self
+            .out_links
+            .iter()
+            .map(|l| l.iter().map(|&(_, f)| f).sum())
+            .collect();
+        let in_total: Vec<f64> = self
+            .in_links
+            .iter()
+            .map(|l| l.iter().map(|&(_, f)| f).sum())
+            .collect();
+        let mut modules: Vec<usize> = (0..n).collect();
+        let mut m = ModuleFlows {
+            flow: self.flow.clone(),
+            exit: out_total.clone(),
+            enter: in_total.clone(),
+            enter_total: in_total.iter().sum(),
+        };
+
+        let mut order: Vec<usize> = (0..n).collect();
+        let mut out_to: HashMap<usize, f64> = HashMap::new();
+        let mut in_from: HashMap<usize, f64> = HashMap::new();
+        let mut candidates = Vec::new();
+        let mut any_moved = false;
+        for _ in 0..max_iter {
+            for i in (1..n).rev() {
+                order.swap(i, rng.next_below(i + 1));
+            }
+            let mut moved = false;
+            for &u in &order {
+                out_to.clear();
+                in_from.clear();
+                candidates.clear();
+                for &(v, f) in &self.out_links[u] {
+                    *out_to.entry(modules[v]).or_insert(0.0) += f;
+                }
+                for &(v, f) in &self.in_links[u] {
+                    *in_from.entry(modules[v]).or_insert(0.0) += f;
+                }
+                let a = modules[u];
+                for &(v, _) in self.out_links[u].iter().chain(&self.in_links[u]) {
+                    if modules[v] != a && !candidates.contains(&modules[v]) {
+                        candidates.push(modules[v]);
+                    }
+                }
+                let to = |b: usize| out_to.get(&b).copied().unwrap_or(0.0);
+                let from = |b: usize| in_from.get(&b).copied().unwrap_or(0.0);
+
+                // Module a without u
+                let flow_a = m.flow[a] - self.flow[u];
+                let exit_a = m.exit[a] - (out_total[u] - to(a)) + from(a);
+                let enter_a = m.enter[a] - (in_total[u] - from(a)) + to(a);
+                let old_a = ModuleFlows::module_term(m.flow[a], m.exit[a], m.enter[a]);
+                let new_a = ModuleFlows::module_term(flow_a, exit_a, enter_a);
+
+                let mut best: Option<(usize, f64, [f64; 3], f64)> = None;
+                for &b in &candidates {
+                    let flow_b = m.flow[b] + self.flow[u];
+                    let exit_b = m.exit[b] + (out_total[u] - to(b)) - from(b);
+                    let enter_b = m.enter[b] + (in_total[u] - from(b)) - to(b);
+                    let enter_total = m.enter_total - m.enter[a] - m.enter[b] + enter_a + enter_b;
+                    let old_b = ModuleFlows::module_term(m.flow[b], m.exit[b], m.enter[b]);
+                    let new_b = ModuleFlows::module_term(flow_b, exit_b, enter_b);
+                    let delta =
+                        plogp(enter_total) - plogp(m.enter_total) + new_a + new_b - old_a - old_b;
+                    if delta < -MIN_IMPROVEMENT && best.is_none_or(|(_, d, _, _)| delta < d) {
+                        best = Some((b, delta, [flow_b, exit_b, enter_b], enter_total));
+                    }
+                }
+                if let Some((b, _, [flow_b, exit_b, enter_b], enter_total)) = best {
+                    m.flow[a] = flow_a;
+                    m.exit[a] = exit_a;
+                    m.enter[a] = enter_a;
+                    m.flow[b] = flow_b;
+                    m.exit[b] = exit_b;
+                    m.enter[b] = enter_b;
+                    m.enter_total = enter_total;
+                    modules[u] = b;
+                    moved = true;
+                    any_moved = true;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+        (modules, any_moved)
+    }
+}
+
+/// Two-level map equation codelength of a partition of the flow network.
+fn codelength(network: &FlowNetwork, modules: &[usize]) -> f64 {
+    let k = modules.iter().max().map_or(0, |&m| m + 1);
+    let mut flow = vec![0.0; k];
+    let mut exit = vec![0.0; k];
+    let mut enter = vec![0.0; k];
+    for (u, &p) in network.flow.iter().enumerate() {
+        flow[modules[u]] += p;
+    }
+    for &(u, v, f) in &network.links {
+        if modules[u] != modules[v] {
+            exit[modules[u]] += f;
+            enter[modules[v]] += f;
+        }
+    }
+    let node_term: f64 = network.flow.iter().map(|&p| plogp(p)).sum();
+    let module_terms: f64 = (0..k)
+        .map(|i| ModuleFlows::module_term(flow[i], exit[i], enter[i]))
+        .sum();
+    plogp(enter.iter().sum()) + module_terms - node_term
+}
+
+/// Compute two-level Infomap over weighted, directed or undirected edges.
+///
+/// Empty weights mean unit weights, and parallel edges add up. Self-loops and
+/// zero-weight edges are ignored. In a directed graph the flow comes from
+/// PageRank with the given `teleportation` probability, which is ignored for
+/// undirected graphs. Nodes are moved between modules in a random order for
+/// at most `max_iter` sweeps per level, and modules are then merged into
+/// nodes of the next level until no move lowers the codelength. When no
+/// partition beats a single module, every node is put in one module.
+pub fn compute_weighted_infomap(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+    teleportation: f64,
+    max_iter: usize,
+    seed: Option<u64>,
+) -> Result<WeightedInfomapResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights array must be empty or match edge count".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if !(teleportation > 0.0 && teleportation < 1.0) {
+        return Err(OnagerError::InvalidArgument(
+            "teleportation must be in (0, 1)".to_string(),
+        ));
+    }
+    if max_iter == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "max_iter must be positive".to_string(),
+        ));
+    }
+
+    let network = flow_network(src, dst, weights, directed, teleportation)?;
+    let n = network.node_ids.len();
+    let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let mut rng = SplitMix64(seed);
+
+    let mut assignment: Vec<usize> = (0..n).collect();
+    let mut level = Level::new(network.flow.clone(), network.links.iter().copied());
+    loop {
+        let (modules, moved) = level.move_nodes(max_iter, &mut rng);
+        if !moved {
+            break;
+        }
+        // Renumber modules densely and merge each into a node of the next level
+        let mut dense: HashMap<usize, usize> = HashMap::new();
+        let modules: Vec<usize> = modules
+            .iter()
+            .map(|&m| {
+                let next = dense.len();
+                *dense.entry(m).or_insert(next)
+            })
+            .collect();
+        for a in assignment.iter_mut() {
+            *a = modules[*a];
+        }
+        let mut flow = vec![0.0; dense.len()];
+        for (u, &p) in level.flow.iter().enumerate() {
+            flow[modules[u]] += p;
+        }
+        let links = level
+            .out_links
+            .iter()
+            .enumerate()
+            .flat_map(|(u, l)| l.iter().map(move |&(v, f)| (u, v, f)))
+            .map(|(u, v, f)| (modules[u], modules[v], f));
+        level = Level::new(flow, links.collect::<Vec<_>>().into_iter());
+    }
+
+    let mut length = codelength(&network, &assignment);
+    let one_module = vec![0; n];
+    let one_length = codelength(&network, &one_module);
+    if one_length <= length + MIN_IMPROVEMENT {
+        assignment = one_module;
+        length = one_length;
+    }
+    Ok(WeightedInfomapResult {
+        community_ids: relabel_by_appearance(&assignment),
+        codelength: length,
+        node_ids: network.node_ids,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same(result: &WeightedInfomapResult, a: i64, b: i64) -> bool {
+        let of = |node| {
+            let i = result.node_ids.iter().position(|&n| n == node).unwrap();
+            result.community_ids[i]
+        };
+        of(a) == of(b)
+    }
+
+    fn two_cliques() -> (Vec<i64>, Vec<i64>) {
+        // Two 5-cliques joined by the edge 5-6
+        let mut src = Vec::new();
+        let mut dst = Vec::new();
+        for base in [0, 5] {
+            for i in 1..=5 {
+                for j in i + 1..=5 {
+                    src.push(base + i);
+                    dst.push(base + j);
+                }
+            }
+        }
+        src.push(5);
+        dst.push(6);
+        (src, dst)
+    }
+
+    #[test]
+    fn test_undirected_cliques_form_modules() {
+        let (src, dst) = two_cliques();
+        let result = compute_weighted_infomap(&src, &dst, &[], false, 0.15, 100, Some(1)).unwrap();
+        assert!((2..=5).all(|node| same(&result, 1, node)));
+        assert!((7..=10).all(|node| same(&result, 6, node)));
+        assert!(!same(&result, 1, 6));
+        assert!(result.codelength > 0.0);
+    }
+
+    #[test]
+    fn test_weights_decide_modules() {
+        // A 4-cycle where only the heavy edges stay inside modules
+        let src = vec![1, 2, 3, 4];
+        let dst = vec![2, 3, 4, 1];
+        let weights = vec![10.0, 0.1, 10.0, 0.1];
+        let result =
+            compute_weighted_infomap(&src, &dst, &weights, false, 0.15, 100, Some(1)).unwrap();
+        assert!(same(&result, 1, 2));
+        assert!(same(&result, 3, 4));
+        assert!(!same(&result, 1, 3));
+    }
+
+    #[test]
+    fn test_directed_cycles_form_modules() {
+        // Two directed 4-cycles joined by single arcs both ways
+        let src = vec![1, 2, 3, 4, 5, 6, 7, 8, 4, 8];
+        let dst = vec![2, 3, 4, 1, 6, 7, 8, 5, 5, 1];
+        let result = compute_weighted_infomap(&src, &dst, &[], true, 0.15, 100, Some(3)).unwrap();
+        assert!((2..=4).all(|node| same(&result, 1, node)));
+        assert!((6..=8).all(|node| same(&result, 5, node)));
+        assert!(!same(&result, 1, 5));
+    }
+
+    #[test]
+    fn test_single_clique_is_one_module() {
+        let src = vec![1, 1, 1, 2, 2, 3];
+        let dst = vec![2, 3, 4, 3, 4, 4];
+        let result = compute_weighted_infomap(&src, &dst, &[], false, 0.15, 100, Some(1)).unwrap();
+        assert!(result.community_ids.iter().all(|&c| c == 0));
+        // One module costs the entropy of the visit rates, here 2 bits
+        assert!((result.codelength - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let (src, dst) = (vec![1, 2], vec![2, 3]);
+        assert!(compute_weighted_infomap(&src, &dst, &[], true, 0.0, 100, None).is_err());
+        assert!(compute_weighted_infomap(&src, &dst, &[], true, 1.0, 100, None).is_err());
+        assert!(compute_weighted_infomap(&src, &dst, &[], true, 0.15, 0, None).is_err());
+        assert!(compute_weighted_infomap(&src, &dst, &[1.0], true, 0.15, 100, None).is_err());
+        assert!(compute_weighted_infomap(&src, &dst, &[1.0, -1.0], true, 0.15, 100, None).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! Node ID interning module.
+//!
+//! Maps string node IDs, such as usernames or URLs, to dense integer IDs so
+//! the integer algorithms can run on them, and maps results back.
+
+use crate::error::{OnagerError, Result};
+use std::collections::HashMap;
+
+/// Dictionary from string node IDs to dense integer IDs.
+///
+/// IDs are assigned in order of first appearance, starting at 0. The
+/// interner borrows the names, so it lives no longer than the input.
+#[derive(Debug, Clone, Default)]
+pub struct NodeInterner<'a> {
+    ids: HashMap<&'a str, i64>,
+    names: Vec<&'a str>,
+}
+
+impl<'a> NodeInterner<'a> {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the ID of `name`, assigning the next free ID if it is new.
+    pub fn intern(&mut self, name: &'a str) -> i64 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len() as i64;
+        self.ids.insert(name, id);
+        self.names.push(name);
+        id
+    }
+
+    /// Interns both endpoints of every edge and returns the integer edges.
+    pub fn intern_edges(
+        &mut self,
+        src: &[&'a str],
+        dst: &[&'a str],
+    ) -> Result<(Vec<i64>, Vec<i64>)> {
+        if src.len() != dst.len() {
+            return Err(OnagerError::InvalidArgument(
+                "src and dst arrays must have same length".to_string(),
+            ));
+        }
+        let src_ids = src.iter().map(|name| self.intern(name)).collect();
+        let dst_ids = dst.iter().map(|name| self.intern(name)).collect();
+        Ok((src_ids, dst_ids))
+    }
+
+    /// Returns the ID of `name` if it has been interned.
+    pub fn id(&self, name: &str) -> Option<i64> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name behind `id` if it was assigned by this interner.
+    pub fn name(&self, id: i64) -> Option<&'a str> {
+        usize::try_from(id)
+            .ok()
+            .and_then(|i| self.names.get(i))
+            .copied()
+    }
+
+    /// Maps a list of IDs back to names, failing on an unknown ID.
+    pub fn names(&self, ids: &[i64]) -> Result<Vec<&'a str>> {
+        ids.iter()
+            .map(|&id| self.name(id).ok_or(OnagerError::NodeNotFound(id)))
+            .collect()
+    }
+
+    /// Returns the number of interned names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns true if no name has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_edges_assigns_ids_in_order() {
+        let mut interner = NodeInterner::new();
+        let (src, dst) = interner
+            .intern_edges(&["alice", "bob", "alice"], &["bob", "carol", "carol"])
+            .unwrap();
+
+        // Sources are interned before destinations
+        assert_eq!(src, vec![0, 1, 0]);
+        assert_eq!(dst, vec![1, 2, 2]);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.id("carol"), Some(2));
+        assert_eq!(interner.names(&[2, 0]).unwrap(), vec!["carol", "alice"]);
+    }
+
+    #[test]
+    fn test_unknown_ids_and_mismatched_edges() {
+        let mut interner = NodeInterner::new();
+        assert!(interner.is_empty());
+        assert!(interner.intern_edges(&["a"], &[]).is_err());
+        interner.intern("a");
+        assert_eq!(interner.name(-1), None);
+        assert!(interner.names(&[0, 1]).is_err());
+        assert_eq!(interner.id("b"), None);
+    }
+}
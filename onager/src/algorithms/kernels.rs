@@ -0,0 +1,353 @@
+//! Graph kernel module.
+//!
+//! Weisfeiler-Lehman subtree labels, which hash the neighborhood of each node
+//! into a label that can be compared across graphs, and pairwise kernel
+//! similarities between many small graphs.
+
+use serde::{Deserialize, Serialize};
+
+use super::flow::simple_undirected;
+use crate::error::{OnagerError, Result};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::str::FromStr;
+
+/// Starting state of every label hash.
+const WL_SEED: u64 = 0x5745_4953_4645_494C;
+
+/// Kernel used to compare graphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GraphKernel {
+    /// Count pairs of nodes with the same Weisfeiler-Lehman label, summed over
+    /// all iterations.
+    WlSubtree,
+    /// Count pairs of node pairs with the same shortest-path distance.
+    ShortestPath,
+}
+
+impl FromStr for GraphKernel {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "wl" | "wl_subtree" | "weisfeiler_lehman" => Ok(GraphKernel::WlSubtree),
+            "sp" | "shortest_path" => Ok(GraphKernel::ShortestPath),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown graph kernel '{}', expected 'wl_subtree' or 'shortest_path'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Kernel similarities between every pair of graphs.
+///
+/// Rows hold each pair with `graph_a < graph_b`, in ascending order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphKernelResult {
+    pub graph_a: Vec<i64>,
+    pub graph_b: Vec<i64>,
+    pub similarities: Vec<f64>,
+}
+
+/// Weisfeiler-Lehman labels, one row per node and iteration.
+///
+/// Rows are ordered by iteration and then by node ID. Iteration 0 labels
+/// encode the degree of each node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WlLabelsResult {
+    pub node_ids: Vec<i64>,
+    pub iterations: Vec<i64>,
+    pub labels: Vec<i64>,
+}
+
+/// Mix `value` into the hash `state`, using the SplitMix64 finalizer.
+fn wl_hash(state: u64, value: u64) -> u64 {
+    let mut z = (state.rotate_left(7) ^ value).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Run `iterations` rounds of Weisfeiler-Lehman refinement from `initial`.
+///
+/// Each round hashes a node's label together with the sorted labels of its
+/// neighbors. Returns the labels of every round, starting with `initial`.
+pub(crate) fn wl_refine(
+    neighbors: &[Vec<usize>],
+    initial: Vec<u64>,
+    iterations: usize,
+) -> Vec<Vec<u64>> {
+    let mut rounds = Vec::with_capacity(iterations + 1);
+    rounds.push(initial);
+    for _ in 0..iterations {
+        let previous = &rounds[rounds.len() - 1];
+        let mut scratch = Vec::new();
+        let next: Vec<u64> = neighbors
+            .iter()
+            .enumerate()
+            .map(|(v, list)| {
+                scratch.clear();
+                scratch.extend(list.iter().map(|&u| previous[u]));
+                scratch.sort_unstable();
+                scratch
+                    .iter()
+                    .fold(wl_hash(WL_SEED, previous[v]), |h, &l| wl_hash(h, l))
+            })
+            .collect();
+        rounds.push(next);
+    }
+    rounds
+}
+
+/// Iteration 0 labels, which encode the degree of each node.
+fn degree_labels(neighbors: &[Vec<usize>]) -> Vec<u64> {
+    neighbors
+        .iter()
+        .map(|list| wl_hash(WL_SEED, list.len() as u64))
+        .collect()
+}
+
+/// Adjacency lists of `n` nodes from undirected edges between node indices.
+pub(crate) fn adjacency_lists(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); n];
+    for &(u, v) in edges {
+        neighbors[u].push(v);
+        neighbors[v].push(u);
+    }
+    neighbors
+}
+
+/// Compute Weisfeiler-Lehman subtree labels for `iterations` rounds.
+///
+/// The label of a node at iteration `k` is a hash of its depth-`k`
+/// neighborhood tree, so two nodes with the same label at iteration `k` cannot
+/// be told apart by `k` rounds of color refinement. Labels depend only on
+/// structure, not on node IDs, so they can be compared across graphs and used
+/// as hashed features for graph kernels. Iteration 0 labels encode the
+/// degree. The graph is treated as undirected, and self-loops and parallel
+/// edges are ignored.
+pub fn compute_wl_labels(src: &[i64], dst: &[i64], iterations: usize) -> Result<WlLabelsResult> {
+    let graph = simple_undirected(src, dst)?;
+    let n = graph.nodes.len();
+    let neighbors = adjacency_lists(n, &graph.edges);
+
+    let rounds = wl_refine(&neighbors, degree_labels(&neighbors), iterations);
+    let mut result = WlLabelsResult::default();
+    for (k, labels) in rounds.iter().enumerate() {
+        result.node_ids.extend_from_slice(&graph.nodes);
+        result
+            .iterations
+            .resize(result.iterations.len() + n, k as i64);
+        result.labels.extend(labels.iter().map(|&l| l as i64));
+    }
+    Ok(result)
+}
+
+/// Feature counts of one graph under `kernel`, sorted by feature.
+fn graph_features(
+    src: &[i64],
+    dst: &[i64],
+    kernel: GraphKernel,
+    iterations: usize,
+) -> Result<Vec<(u64, f64)>> {
+    let graph = simple_undirected(src, dst)?;
+    let n = graph.nodes.len();
+    let neighbors = adjacency_lists(n, &graph.edges);
+    let mut counts: HashMap<u64, f64> = HashMap::new();
+    match kernel {
+        GraphKernel::WlSubtree => {
+            for labels in wl_refine(&neighbors, degree_labels(&neighbors), iterations) {
+                for label in labels {
+                    *counts.entry(label).or_insert(0.0) += 1.0;
+                }
+            }
+        }
+        GraphKernel::ShortestPath => {
+            let mut dist = vec![usize::MAX; n];
+            let mut queue = VecDeque::new();
+            for s in 0..n {
+                dist.fill(usize::MAX);
+                dist[s] = 0;
+                queue.push_back(s);
+                while let Some(u) = queue.pop_front() {
+                    for &v in &neighbors[u] {
+                        if dist[v] == usize::MAX {
+                            dist[v] = dist[u] + 1;
+                            queue.push_back(v);
+                        }
+                    }
+                }
+                // Count each unordered pair once, from its smaller end
+                for &d in dist[s + 1..].iter().filter(|&&d| d != usize::MAX) {
+                    *counts.entry(d as u64).or_insert(0.0) += 1.0;
+                }
+            }
+        }
+    }
+    let mut features: Vec<(u64, f64)> = counts.into_iter().collect();
+    features.sort_unstable_by_key(|&(f, _)| f);
+    Ok(features)
+}
+
+/// Dot product of two sorted feature vectors.
+fn feature_dot(a: &[(u64, f64)], b: &[(u64, f64)]) -> f64 {
+    let (mut i, mut j, mut dot) = (0, 0, 0.0);
+    while i < a.len() && j < b.len() {
+        match a[i].0.cmp(&b[j].0) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                dot += a[i].1 * b[j].1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    dot
+}
+
+/// Compute kernel similarities between every pair of graphs in a batch.
+///
+/// Edge `i` belongs to graph `graph_ids[i]`, so many small graphs such as
+/// molecules can be compared in one call. The Weisfeiler-Lehman subtree
+/// kernel counts pairs of nodes, one from each graph, that share a label at
+/// the same iteration from 0 to `iterations`. The shortest-path kernel counts
+/// pairs of node pairs, one from each graph, at the same distance, and
+/// ignores `iterations`. With `normalize`, each kernel value is divided by the
+/// square root of both graphs' self-similarities, so similarities lie between
+/// 0 and 1 and isomorphic graphs score 1. Graphs are treated as undirected,
+/// self-loops and parallel edges are ignored, and isolated nodes cannot be
+/// represented.
+pub fn compute_graph_kernel(
+    src: &[i64],
+    dst: &[i64],
+    graph_ids: &[i64],
+    kernel: GraphKernel,
+    iterations: usize,
+    normalize: bool,
+) -> Result<GraphKernelResult> {
+    if src.len() != dst.len() || src.len() != graph_ids.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and graph_ids arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+
+    let mut edges: BTreeMap<i64, (Vec<i64>, Vec<i64>)> = BTreeMap::new();
+    for i in 0..src.len() {
+        let (s, d) = edges.entry(graph_ids[i]).or_default();
+        s.push(src[i]);
+        d.push(dst[i]);
+    }
+    let ids: Vec<i64> = edges.keys().copied().collect();
+    let features = edges
+        .values()
+        .map(|(s, d)| graph_features(s, d, kernel, iterations))
+        .collect::<Result<Vec<_>>>()?;
+    let norms: Vec<f64> = features.iter().map(|f| feature_dot(f, f).sqrt()).collect();
+
+    let mut result = GraphKernelResult::default();
+    for a in 0..ids.len() {
+        for b in a + 1..ids.len() {
+            let mut value = feature_dot(&features[a], &features[b]);
+            if normalize {
+                let scale = norms[a] * norms[b];
+                value = if scale > 0.0 { value / scale } else { 0.0 };
+            }
+            result.graph_a.push(ids[a]);
+            result.graph_b.push(ids[b]);
+            result.similarities.push(value);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels_at(result: &WlLabelsResult, iteration: i64) -> Vec<i64> {
+        (0..result.labels.len())
+            .filter(|&i| result.iterations[i] == iteration)
+            .map(|i| result.labels[i])
+            .collect()
+    }
+
+    #[test]
+    fn test_wl_labels_separate_structural_roles() {
+        // Path 1-2-3-4-5: nodes mirror around the middle
+        let result = compute_wl_labels(&[1, 2, 3, 4], &[2, 3, 4, 5], 2).unwrap();
+        assert_eq!(result.node_ids.len(), 15);
+        assert_eq!(result.node_ids[..5], [1, 2, 3, 4, 5]);
+
+        let round0 = labels_at(&result, 0);
+        assert_eq!(round0[0], round0[4]);
+        assert_eq!(round0[1], round0[2]);
+        // One round tells the middle node from its neighbors
+        let round1 = labels_at(&result, 1);
+        assert_eq!(round1[0], round1[4]);
+        assert_eq!(round1[1], round1[3]);
+        assert_ne!(round1[1], round1[2]);
+    }
+
+    #[test]
+    fn test_wl_labels_compare_across_graphs() {
+        // The same star with different node IDs gets the same labels
+        let a = compute_wl_labels(&[1, 1, 1], &[2, 3, 4], 3).unwrap();
+        let b = compute_wl_labels(&[40, 40, 40], &[10, 20, 30], 3).unwrap();
+        let mut la = a.labels.clone();
+        let mut lb = b.labels.clone();
+        la.sort_unstable();
+        lb.sort_unstable();
+        assert_eq!(la, lb);
+
+        // A 6-cycle and two triangles are both 2-regular, which WL cannot tell apart
+        let cycle = compute_wl_labels(&[1, 2, 3, 4, 5, 6], &[2, 3, 4, 5, 6, 1], 2).unwrap();
+        let triangles = compute_wl_labels(&[1, 2, 3, 4, 5, 6], &[2, 3, 1, 5, 6, 4], 2).unwrap();
+        assert_eq!(cycle.labels, triangles.labels);
+
+        assert!(compute_wl_labels(&[], &[], 2).is_err());
+        assert_eq!(compute_wl_labels(&[1], &[2], 0).unwrap().labels.len(), 2);
+    }
+
+    #[test]
+    fn test_wl_kernel_matches_isomorphic_graphs() {
+        // Graph 1 and 3 are the same path with different IDs, graph 2 is a triangle
+        let src = vec![1, 2, 10, 11, 12, 7, 8];
+        let dst = vec![2, 3, 11, 12, 10, 8, 9];
+        let ids = vec![1, 1, 2, 2, 2, 3, 3];
+        let kernel = GraphKernel::WlSubtree;
+        let result = compute_graph_kernel(&src, &dst, &ids, kernel, 2, true).unwrap();
+        assert_eq!(result.graph_a, vec![1, 1, 2]);
+        assert_eq!(result.graph_b, vec![2, 3, 3]);
+        assert!((result.similarities[1] - 1.0).abs() < 1e-12);
+
+        // Only the path middle and the triangle nodes match, on degree 2 at
+        // iteration 0. Each round the path matches itself on 2 * 2 + 1 pairs
+        let raw = compute_graph_kernel(&src, &dst, &ids, kernel, 2, false).unwrap();
+        assert_eq!(raw.similarities, vec![3.0, 15.0, 3.0]);
+        assert!((result.similarities[0] - 3.0 / (15.0f64 * 27.0).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_shortest_path_kernel() {
+        // Path 1-2-3 has distances {1, 1, 2}, the triangle has {1, 1, 1}
+        let src = vec![1, 2, 4, 5, 6];
+        let dst = vec![2, 3, 5, 6, 4];
+        let ids = vec![0, 0, 1, 1, 1];
+        let kernel = GraphKernel::ShortestPath;
+        let raw = compute_graph_kernel(&src, &dst, &ids, kernel, 0, false).unwrap();
+        assert_eq!(raw.similarities, vec![6.0]);
+        let result = compute_graph_kernel(&src, &dst, &ids, kernel, 0, true).unwrap();
+        assert!((result.similarities[0] - 6.0 / 45f64.sqrt()).abs() < 1e-12);
+
+        assert!(compute_graph_kernel(&src, &dst, &ids[..4], kernel, 0, true).is_err());
+        assert!(compute_graph_kernel(&[], &[], &[], kernel, 0, true).is_err());
+        assert!("graphlet".parse::<GraphKernel>().is_err());
+        assert_eq!("WL".parse::<GraphKernel>().unwrap(), GraphKernel::WlSubtree);
+    }
+}
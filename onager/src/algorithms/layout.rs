@@ -0,0 +1,555 @@
+//! Graph layout module.
+//!
+//! Two-dimensional node coordinates for drawing a graph, from a force-directed
+//! layout and the deterministic spectral, circular, and shell layouts, and a
+//! community layout that also places every community.
+
+use serde::{Deserialize, Serialize};
+
+use super::centrality::{build_weighted_adjacency, WeightedAdjacency};
+use super::community::compute_louvain;
+use super::sbm::SplitMix64;
+use super::spectral::{normalized_adjacency, top_eigenpairs};
+use crate::error::{OnagerError, Result};
+use std::collections::hash_map::RandomState;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::f64::consts::TAU;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
+
+/// Initial largest step of a node in the force layout, relative to the
+/// `[-1, 1]` square the nodes start in.
+const INITIAL_TEMPERATURE: f64 = 0.2;
+
+/// Smallest distance used between two nodes, so coincident nodes do not
+/// divide by zero.
+const MIN_DISTANCE: f64 = 1e-9;
+
+/// Extra vectors iterated for the spectral layout, which speeds up convergence.
+const EXTRA_VECTORS: usize = 2;
+
+/// Radius around its center of a community that holds every node, in the
+/// community layout. Smaller communities get a radius that grows with the
+/// square root of their size.
+const COMMUNITY_RADIUS: f64 = 0.5;
+
+/// How node coordinates are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutMethod {
+    /// Fruchterman-Reingold: edges pull their ends together while all nodes
+    /// push each other apart.
+    Force,
+    /// Eigenvectors of the two smallest nonzero normalized Laplacian
+    /// eigenvalues.
+    Spectral,
+    /// All nodes on one circle in order of node ID.
+    Circular,
+    /// Concentric circles by breadth-first distance from the node of highest
+    /// degree.
+    Shell,
+}
+
+impl FromStr for LayoutMethod {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "force" | "fruchterman_reingold" => Ok(LayoutMethod::Force),
+            "spectral" => Ok(LayoutMethod::Spectral),
+            "circular" | "circle" => Ok(LayoutMethod::Circular),
+            "shell" => Ok(LayoutMethod::Shell),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown layout method '{}', expected 'force', 'spectral', 'circular', or 'shell'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Node coordinates of a layout, in ascending order of node ID.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutResult {
+    pub node_ids: Vec<i64>,
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+}
+
+/// Node and community coordinates of a community layout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommunityLayoutResult {
+    /// Nodes in ascending order of node ID, with their community
+    pub node_ids: Vec<i64>,
+    pub communities: Vec<i64>,
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    /// Communities in ascending order of ID, with their number of members
+    /// and the centroid of the members
+    pub community_ids: Vec<i64>,
+    pub community_sizes: Vec<i64>,
+    pub community_x: Vec<f64>,
+    pub community_y: Vec<f64>,
+}
+
+/// Compute two-dimensional coordinates for every node.
+///
+/// Edges are undirected, parallel edges add up, and empty weights mean unit
+/// weights. `iterations` and `seed` only affect the force layout, which starts
+/// from random positions; the other methods always return the same layout for
+/// the same graph. Coordinates are centered on the origin and scaled so the
+/// largest absolute coordinate is 1, and the outer circle of the circular and
+/// shell layouts has radius 1.
+pub fn compute_layout(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    method: LayoutMethod,
+    iterations: usize,
+    seed: Option<u64>,
+) -> Result<LayoutResult> {
+    let adj = build_weighted_adjacency(src, dst, weights)?;
+    let n = adj.node_ids.len();
+    let (x, y) = match method {
+        LayoutMethod::Force => {
+            if iterations == 0 {
+                return Err(OnagerError::InvalidArgument(
+                    "iterations must be positive".to_string(),
+                ));
+            }
+            let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+            let (x, y) = force_layout(&adj, iterations, &mut SplitMix64(seed));
+            normalize(x, y)
+        }
+        LayoutMethod::Spectral => {
+            let (x, y) = spectral_layout(&adj);
+            normalize(x, y)
+        }
+        LayoutMethod::Circular => {
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_unstable_by_key(|&u| adj.node_ids[u]);
+            let (mut x, mut y) = (vec![0.0; n], vec![0.0; n]);
+            // A single node sits at the center
+            let radius = if n > 1 { 1.0 } else { 0.0 };
+            place_on_circle(&order, radius, &mut x, &mut y);
+            (x, y)
+        }
+        LayoutMethod::Shell => shell_layout(&adj),
+    };
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_unstable_by_key(|&u| adj.node_ids[u]);
+    Ok(LayoutResult {
+        node_ids: order.iter().map(|&u| adj.node_ids[u]).collect(),
+        x: order.iter().map(|&u| x[u]).collect(),
+        y: order.iter().map(|&u| y[u]).collect(),
+    })
+}
+
+/// Compute coordinates that keep every community together.
+///
+/// Communities are found with Louvain, using the same community IDs as
+/// `compute_louvain` with the same seed. Each community is contracted into one
+/// node, and the contracted graph, with the weights of the edges between two
+/// communities summed, gets a force layout. The members of each community then
+/// get a force layout of their own edges, scaled down by the square root of
+/// the community's share of the nodes and centered on the community's
+/// position. Coordinates are centered and scaled like `compute_layout`, and
+/// every community's coordinates are the centroid of its members.
+pub fn compute_community_layout(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    iterations: usize,
+    seed: Option<u64>,
+) -> Result<CommunityLayoutResult> {
+    let adj = build_weighted_adjacency(src, dst, weights)?;
+    if iterations == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "iterations must be positive".to_string(),
+        ));
+    }
+    let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let louvain = compute_louvain(src, dst, Some(seed))?;
+    let assigned: HashMap<i64, i64> = louvain
+        .node_ids
+        .iter()
+        .copied()
+        .zip(louvain.community_ids.iter().copied())
+        .collect();
+    let mut community_ids = louvain.community_ids;
+    community_ids.sort_unstable();
+    community_ids.dedup();
+    let index: HashMap<i64, usize> = community_ids
+        .iter()
+        .enumerate()
+        .map(|(c, &id)| (id, c))
+        .collect();
+    let n = adj.node_ids.len();
+    let k = community_ids.len();
+    let community = adj
+        .node_ids
+        .iter()
+        .map(|node| {
+            assigned.get(node).map(|id| index[id]).ok_or_else(|| {
+                OnagerError::GraphError(format!("Louvain left node {} without a community", node))
+            })
+        })
+        .collect::<Result<Vec<usize>>>()?;
+    let mut rng = SplitMix64(seed);
+
+    // Contract every community into one node, summing the edges between them
+    let mut contracted = vec![BTreeMap::new(); k];
+    for (u, list) in adj.neighbors.iter().enumerate() {
+        for &(v, w) in list {
+            let (a, b) = (community[u], community[v]);
+            if a != b {
+                *contracted[a].entry(b).or_insert(0.0) += w;
+            }
+        }
+    }
+    let meta = WeightedAdjacency {
+        node_ids: community_ids.clone(),
+        neighbors: contracted
+            .into_iter()
+            .map(|m| m.into_iter().collect())
+            .collect(),
+    };
+    let (cx, cy) = force_layout(&meta, iterations, &mut rng);
+    let (cx, cy) = normalize(cx, cy);
+
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); k];
+    for u in 0..n {
+        members[community[u]].push(u);
+    }
+    let (mut x, mut y) = (vec![0.0; n], vec![0.0; n]);
+    for (c, list) in members.iter().enumerate() {
+        let local: HashMap<usize, usize> = list.iter().enumerate().map(|(i, &u)| (u, i)).collect();
+        let inner = WeightedAdjacency {
+            node_ids: list.iter().map(|&u| adj.node_ids[u]).collect(),
+            neighbors: list
+                .iter()
+                .map(|&u| {
+                    adj.neighbors[u]
+                        .iter()
+                        .filter_map(|&(v, w)| local.get(&v).map(|&j| (j, w)))
+                        .collect()
+                })
+                .collect(),
+        };
+        let (lx, ly) = force_layout(&inner, iterations, &mut rng);
+        let (lx, ly) = normalize(lx, ly);
+        let radius = COMMUNITY_RADIUS * (list.len() as f64 / n as f64).sqrt();
+        for (i, &u) in list.iter().enumerate() {
+            x[u] = cx[c] + radius * lx[i];
+            y[u] = cy[c] + radius * ly[i];
+        }
+    }
+    let (x, y) = normalize(x, y);
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_unstable_by_key(|&u| adj.node_ids[u]);
+    let centroid = |coords: &[f64], list: &[usize]| {
+        list.iter().map(|&u| coords[u]).sum::<f64>() / list.len() as f64
+    };
+    Ok(CommunityLayoutResult {
+        node_ids: order.iter().map(|&u| adj.node_ids[u]).collect(),
+        communities: order.iter().map(|&u| community_ids[community[u]]).collect(),
+        x: order.iter().map(|&u| x[u]).collect(),
+        y: order.iter().map(|&u| y[u]).collect(),
+        community_sizes: members.iter().map(|list| list.len() as i64).collect(),
+        community_x: members.iter().map(|list| centroid(&x, list)).collect(),
+        community_y: members.iter().map(|list| centroid(&y, list)).collect(),
+        community_ids,
+    })
+}
+
+/// Fruchterman-Reingold iterations with a linearly cooling step limit.
+fn force_layout(
+    adj: &WeightedAdjacency,
+    iterations: usize,
+    rng: &mut SplitMix64,
+) -> (Vec<f64>, Vec<f64>) {
+    let n = adj.node_ids.len();
+    let mut x: Vec<f64> = (0..n).map(|_| 2.0 * rng.next_f64() - 1.0).collect();
+    let mut y: Vec<f64> = (0..n).map(|_| 2.0 * rng.next_f64() - 1.0).collect();
+    // Ideal edge length for n nodes spread over the starting square
+    let k = (4.0 / n as f64).sqrt();
+
+    for step in 0..iterations {
+        let temperature = INITIAL_TEMPERATURE * (1.0 - step as f64 / iterations as f64);
+        let (mut dx, mut dy) = (vec![0.0; n], vec![0.0; n]);
+        for u in 0..n {
+            for v in u + 1..n {
+                let (ex, ey) = (x[u] - x[v], y[u] - y[v]);
+                let d = (ex * ex + ey * ey).sqrt().max(MIN_DISTANCE);
+                let push = k * k / (d * d);
+                dx[u] += ex * push;
+                dy[u] += ey * push;
+                dx[v] -= ex * push;
+                dy[v] -= ey * push;
+            }
+        }
+        for (u, list) in adj.neighbors.iter().enumerate() {
+            // Each edge is listed at both ends, so only its first end applies it
+            for &(v, w) in list.iter().filter(|&&(v, _)| u < v) {
+                let (ex, ey) = (x[u] - x[v], y[u] - y[v]);
+                let d = (ex * ex + ey * ey).sqrt().max(MIN_DISTANCE);
+                let pull = w * d / k;
+                dx[u] -= ex * pull;
+                dy[u] -= ey * pull;
+                dx[v] += ex * pull;
+                dy[v] += ey * pull;
+            }
+        }
+        for u in 0..n {
+            let length = (dx[u] * dx[u] + dy[u] * dy[u]).sqrt();
+            if length > 0.0 {
+                let scale = length.min(temperature) / length;
+                x[u] += dx[u] * scale;
+                y[u] += dy[u] * scale;
+            }
+        }
+    }
+    (x, y)
+}
+
+/// Laplacian eigenmap on the two smallest nonzero normalized Laplacian
+/// eigenvalues, started from fixed vectors so the layout is reproducible.
+fn spectral_layout(adj: &WeightedAdjacency) -> (Vec<f64>, Vec<f64>) {
+    let n = adj.node_ids.len();
+    let (op, sqrt_degree) = normalized_adjacency(adj);
+    let needed = n.min(3);
+    let m = (needed + EXTRA_VECTORS).min(n);
+    let (_, vectors) = top_eigenpairs(&op, &sqrt_degree, m, needed, &mut SplitMix64(0));
+
+    // Eigenvector 0 belongs to eigenvalue 0 and carries no layout information
+    let mut axes = (1..3).map(|j| {
+        let Some(vector) = vectors.get(j) else {
+            return vec![0.0; n];
+        };
+        let mut axis: Vec<f64> = vector
+            .iter()
+            .zip(&sqrt_degree)
+            .map(|(&v, &s)| if s > 0.0 { v / s } else { 0.0 })
+            .collect();
+        // Eigenvectors have no sign, so make the largest entry positive
+        let largest = axis.iter().copied().fold(
+            0.0,
+            |best: f64, v| if v.abs() > best.abs() { v } else { best },
+        );
+        if largest < 0.0 {
+            axis.iter_mut().for_each(|v| *v = -*v);
+        }
+        axis
+    });
+    let x = axes.next().unwrap_or_default();
+    let y = axes.next().unwrap_or_default();
+    (x, y)
+}
+
+/// Concentric circles by breadth-first distance from the node of highest
+/// degree, with the smallest ID breaking ties. Nodes it cannot reach go on
+/// one extra outer circle.
+fn shell_layout(adj: &WeightedAdjacency) -> (Vec<f64>, Vec<f64>) {
+    let n = adj.node_ids.len();
+    let degree = |u: usize| adj.neighbors[u].iter().filter(|&&(v, _)| v != u).count();
+    let center = (0..n)
+        .min_by_key(|&u| (std::cmp::Reverse(degree(u)), adj.node_ids[u]))
+        .unwrap_or(0);
+
+    let mut shell = vec![usize::MAX; n];
+    shell[center] = 0;
+    let mut queue = VecDeque::from([center]);
+    while let Some(u) = queue.pop_front() {
+        for &(v, _) in &adj.neighbors[u] {
+            if shell[v] == usize::MAX {
+                shell[v] = shell[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    let reached = shell.iter().filter(|&&s| s != usize::MAX).max().copied();
+    let outer = reached.unwrap_or(0) + 1;
+    for s in shell.iter_mut().filter(|s| **s == usize::MAX) {
+        *s = outer;
+    }
+    let last = shell.iter().copied().max().unwrap_or(0);
+
+    let (mut x, mut y) = (vec![0.0; n], vec![0.0; n]);
+    for ring in 0..=last {
+        let mut members: Vec<usize> = (0..n).filter(|&u| shell[u] == ring).collect();
+        members.sort_unstable_by_key(|&u| adj.node_ids[u]);
+        let radius = if last == 0 {
+            0.0
+        } else {
+            ring as f64 / last as f64
+        };
+        place_on_circle(&members, radius, &mut x, &mut y);
+    }
+    (x, y)
+}
+
+/// Spreads `nodes` evenly over a circle around the origin, starting at angle 0.
+fn place_on_circle(nodes: &[usize], radius: f64, x: &mut [f64], y: &mut [f64]) {
+    let count = nodes.len();
+    for (i, &u) in nodes.iter().enumerate() {
+        let angle = TAU * i as f64 / count as f64;
+        x[u] = radius * angle.cos();
+        y[u] = radius * angle.sin();
+    }
+}
+
+/// Centers coordinates on the origin and scales the largest absolute
+/// coordinate to 1.
+fn normalize(mut x: Vec<f64>, mut y: Vec<f64>) -> (Vec<f64>, Vec<f64>) {
+    let n = x.len().max(1) as f64;
+    let (mx, my) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+    x.iter_mut().for_each(|v| *v -= mx);
+    y.iter_mut().for_each(|v| *v -= my);
+    let extent = x.iter().chain(&y).fold(0.0, |m: f64, v| m.max(v.abs()));
+    if extent > 0.0 {
+        x.iter_mut().for_each(|v| *v /= extent);
+        y.iter_mut().for_each(|v| *v /= extent);
+    }
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(result: &LayoutResult, node: i64) -> (f64, f64) {
+        let i = result.node_ids.binary_search(&node).unwrap();
+        (result.x[i], result.y[i])
+    }
+
+    fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    /// Two 4-cliques joined by the single edge 4-5.
+    fn two_cliques() -> (Vec<i64>, Vec<i64>) {
+        let (mut src, mut dst) = (Vec::new(), Vec::new());
+        for base in [1, 5] {
+            for u in base..base + 4 {
+                for v in u + 1..base + 4 {
+                    src.push(u);
+                    dst.push(v);
+                }
+            }
+        }
+        src.push(4);
+        dst.push(5);
+        (src, dst)
+    }
+
+    #[test]
+    fn test_circular_layout() {
+        let result = compute_layout(
+            &[3, 1, 2, 4],
+            &[1, 2, 4, 3],
+            &[],
+            LayoutMethod::Circular,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        let expected = [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)];
+        for (i, (ex, ey)) in expected.iter().enumerate() {
+            assert!((result.x[i] - ex).abs() < 1e-12);
+            assert!((result.y[i] - ey).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_shell_layout_puts_hub_in_center() {
+        // Star around 1, a path 2-5 one step further, and a separate edge 8-9
+        let src = vec![1, 1, 1, 2, 8];
+        let dst = vec![2, 3, 4, 5, 9];
+        let result = compute_layout(&src, &dst, &[], LayoutMethod::Shell, 0, None).unwrap();
+        let radius = |node| distance(position(&result, node), (0.0, 0.0));
+        assert!(radius(1) < 1e-12);
+        for node in [2, 3, 4] {
+            assert!((radius(node) - 1.0 / 3.0).abs() < 1e-12);
+        }
+        assert!((radius(5) - 2.0 / 3.0).abs() < 1e-12);
+        assert!((radius(8) - 1.0).abs() < 1e-12);
+        assert!((radius(9) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_spectral_layout_separates_cliques() {
+        let (src, dst) = two_cliques();
+        let a = compute_layout(&src, &dst, &[], LayoutMethod::Spectral, 0, None).unwrap();
+        let b = compute_layout(&src, &dst, &[], LayoutMethod::Spectral, 0, Some(9)).unwrap();
+        assert_eq!(a.x, b.x);
+        assert_eq!(a.y, b.y);
+        // The Fiedler axis puts the two cliques on opposite sides
+        assert!((1..=4).all(|v| position(&a, v).0 * position(&a, 8).0 < 0.0));
+        assert!((5..=8).all(|v| position(&a, v).0 * position(&a, 8).0 > 0.0));
+        let extent = a.x.iter().chain(&a.y).fold(0.0, |m: f64, v| m.max(v.abs()));
+        assert!((extent - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_force_layout_is_seeded_and_keeps_cliques_together() {
+        let (src, dst) = two_cliques();
+        let a = compute_layout(&src, &dst, &[], LayoutMethod::Force, 200, Some(3)).unwrap();
+        let b = compute_layout(&src, &dst, &[], LayoutMethod::Force, 200, Some(3)).unwrap();
+        assert_eq!(a.x, b.x);
+        assert_eq!(a.y, b.y);
+        let within = distance(position(&a, 1), position(&a, 2));
+        let across = distance(position(&a, 1), position(&a, 8));
+        assert!(within < across);
+    }
+
+    #[test]
+    fn test_community_layout_places_members_around_centroid() {
+        let (src, dst) = two_cliques();
+        let a = compute_community_layout(&src, &dst, &[], 100, Some(5)).unwrap();
+        let b = compute_community_layout(&src, &dst, &[], 100, Some(5)).unwrap();
+        assert_eq!(a.x, b.x);
+        assert_eq!(a.communities, b.communities);
+
+        // Each clique is one community
+        assert_eq!(a.community_ids.len(), 2);
+        assert_eq!(a.community_sizes, vec![4, 4]);
+        assert!(a.communities[..4].iter().all(|&c| c == a.communities[0]));
+        assert!(a.communities[4..].iter().all(|&c| c == a.communities[4]));
+        assert_ne!(a.communities[0], a.communities[4]);
+
+        for (c, &id) in a.community_ids.iter().enumerate() {
+            let members: Vec<usize> = (0..8).filter(|&i| a.communities[i] == id).collect();
+            let mean = |coords: &[f64]| members.iter().map(|&i| coords[i]).sum::<f64>() / 4.0;
+            assert!((a.community_x[c] - mean(&a.x)).abs() < 1e-12);
+            assert!((a.community_y[c] - mean(&a.y)).abs() < 1e-12);
+        }
+        // Members sit closer to their own center than to the other one
+        let (c1, c2) = if a.community_ids[0] == a.communities[0] {
+            (0, 1)
+        } else {
+            (1, 0)
+        };
+        let center = |c: usize| (a.community_x[c], a.community_y[c]);
+        for i in 0..4 {
+            let p = (a.x[i], a.y[i]);
+            assert!(distance(p, center(c1)) < distance(p, center(c2)));
+        }
+        let extent = a.x.iter().chain(&a.y).fold(0.0, |m: f64, v| m.max(v.abs()));
+        assert!((extent - 1.0).abs() < 1e-12);
+        assert!(compute_community_layout(&src, &dst, &[], 0, Some(5)).is_err());
+    }
+
+    #[test]
+    fn test_layout_rejects_bad_input() {
+        let (src, dst) = (vec![1, 2], vec![2, 3]);
+        assert!(compute_layout(&src, &dst, &[], LayoutMethod::Force, 0, Some(1)).is_err());
+        assert!(compute_layout(&src, &dst, &[1.0, -1.0], LayoutMethod::Shell, 0, None).is_err());
+        assert!(compute_layout(&[], &[], &[], LayoutMethod::Circular, 0, None).is_err());
+        assert!("kamada_kawai".parse::<LayoutMethod>().is_err());
+        assert_eq!(
+            "Circle".parse::<LayoutMethod>().unwrap(),
+            LayoutMethod::Circular
+        );
+    }
+}
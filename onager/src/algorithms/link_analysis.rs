@@ -0,0 +1,234 @@
+//! Link analysis module.
+//!
+//! Hub and authority scores for directed and bipartite graphs: SALSA and co-HITS.
+
+use serde::{Deserialize, Serialize};
+
+use crate::builder::index_edges;
+use crate::error::{OnagerError, Result};
+
+/// Result of a hub and authority computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HubAuthorityResult {
+    pub node_ids: Vec<i64>,
+    pub hubs: Vec<f64>,
+    pub authorities: Vec<f64>,
+}
+
+/// Rejects a zero iteration limit or a negative tolerance.
+pub(crate) fn check_iteration_params(max_iter: usize, tolerance: f64) -> Result<()> {
+    if max_iter == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "max_iter must be positive".to_string(),
+        ));
+    }
+    if tolerance.is_nan() || tolerance < 0.0 {
+        return Err(OnagerError::InvalidArgument(
+            "tolerance must be non-negative".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Uniform distribution over the nodes where `mask` is true.
+fn uniform_over(mask: &[bool]) -> Vec<f64> {
+    let count = mask.iter().filter(|&&m| m).count();
+    mask.iter()
+        .map(|&m| if m { 1.0 / count as f64 } else { 0.0 })
+        .collect()
+}
+
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+}
+
+/// Compute SALSA hub and authority scores.
+///
+/// SALSA runs two random walks that alternate between following an edge
+/// forwards and backwards. Authority scores are the stationary distribution of
+/// the walk that starts on edge targets, and hub scores of the walk that starts
+/// on edge sources. Both score vectors sum to 1. Nodes without incoming edges
+/// have zero authority and nodes without outgoing edges have zero hub score.
+pub fn compute_salsa(
+    src: &[i64],
+    dst: &[i64],
+    max_iter: usize,
+    tolerance: f64,
+) -> Result<HubAuthorityResult> {
+    check_iteration_params(max_iter, tolerance)?;
+    let graph = index_edges(src, dst, &[])?;
+    let n = graph.nodes.len();
+
+    let mut out_deg = vec![0.0; n];
+    let mut in_deg = vec![0.0; n];
+    for &(u, v, _) in &graph.edges {
+        out_deg[u] += 1.0;
+        in_deg[v] += 1.0;
+    }
+
+    let mut hubs = uniform_over(&out_deg.iter().map(|&d| d > 0.0).collect::<Vec<_>>());
+    let mut authorities = uniform_over(&in_deg.iter().map(|&d| d > 0.0).collect::<Vec<_>>());
+    for _ in 0..max_iter {
+        // Authority walk: step back to a source, then forward to a target
+        let mut via_hub = vec![0.0; n];
+        for &(u, v, _) in &graph.edges {
+            via_hub[u] += authorities[v] / in_deg[v];
+        }
+        let mut next_auth = vec![0.0; n];
+        for &(u, v, _) in &graph.edges {
+            next_auth[v] += via_hub[u] / out_deg[u];
+        }
+
+        // Hub walk: step forward to a target, then back to a source
+        let mut via_auth = vec![0.0; n];
+        for &(u, v, _) in &graph.edges {
+            via_auth[v] += hubs[u] / out_deg[u];
+        }
+        let mut next_hubs = vec![0.0; n];
+        for &(u, v, _) in &graph.edges {
+            next_hubs[u] += via_auth[v] / in_deg[v];
+        }
+
+        let delta = l1_distance(&next_auth, &authorities) + l1_distance(&next_hubs, &hubs);
+        authorities = next_auth;
+        hubs = next_hubs;
+        if delta < tolerance {
+            break;
+        }
+    }
+
+    Ok(HubAuthorityResult {
+        node_ids: graph.nodes.node_ids,
+        hubs,
+        authorities,
+    })
+}
+
+/// Compute co-HITS scores on a bipartite graph.
+///
+/// Edge sources form one side (for example queries or users) and edge targets
+/// the other (documents or items). Each side mixes its uniform prior with the
+/// scores propagated from the other side:
+/// `hub = (1 - lambda_u) * prior + lambda_u * propagated authority` and
+/// `authority = (1 - lambda_v) * prior + lambda_v * propagated hub`.
+/// Propagation follows edge weights normalized over each sending node.
+/// Empty `weights` means unit weights.
+pub fn compute_cohits(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    lambda_u: f64,
+    lambda_v: f64,
+    max_iter: usize,
+    tolerance: f64,
+) -> Result<HubAuthorityResult> {
+    if !(0.0..=1.0).contains(&lambda_u) || !(0.0..=1.0).contains(&lambda_v) {
+        return Err(OnagerError::InvalidArgument(
+            "lambda_u and lambda_v must be in [0, 1]".to_string(),
+        ));
+    }
+    check_iteration_params(max_iter, tolerance)?;
+    let graph = index_edges(src, dst, weights)?;
+    let n = graph.nodes.len();
+
+    let mut out_weight = vec![0.0; n];
+    let mut in_weight = vec![0.0; n];
+    for &(u, v, w) in &graph.edges {
+        out_weight[u] += w;
+        in_weight[v] += w;
+    }
+
+    let hub_prior = uniform_over(&out_weight.iter().map(|&w| w > 0.0).collect::<Vec<_>>());
+    let auth_prior = uniform_over(&in_weight.iter().map(|&w| w > 0.0).collect::<Vec<_>>());
+    let mut hubs = hub_prior.clone();
+    let mut authorities = auth_prior.clone();
+    for _ in 0..max_iter {
+        let mut next_hubs: Vec<f64> = hub_prior.iter().map(|&p| (1.0 - lambda_u) * p).collect();
+        let mut next_auth: Vec<f64> = auth_prior.iter().map(|&p| (1.0 - lambda_v) * p).collect();
+        for &(u, v, w) in &graph.edges {
+            if w > 0.0 {
+                next_hubs[u] += lambda_u * authorities[v] * w / in_weight[v];
+                next_auth[v] += lambda_v * hubs[u] * w / out_weight[u];
+            }
+        }
+
+        let delta = l1_distance(&next_hubs, &hubs) + l1_distance(&next_auth, &authorities);
+        hubs = next_hubs;
+        authorities = next_auth;
+        if delta < tolerance {
+            break;
+        }
+    }
+
+    Ok(HubAuthorityResult {
+        node_ids: graph.nodes.node_ids,
+        hubs,
+        authorities,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(result: &HubAuthorityResult, node: i64) -> (f64, f64) {
+        let idx = result.node_ids.iter().position(|&n| n == node).unwrap();
+        (result.hubs[idx], result.authorities[idx])
+    }
+
+    #[test]
+    fn test_salsa_authority_follows_in_degree() {
+        // 1 and 2 both point to 3, only 1 points to 4
+        let result = compute_salsa(&[1, 2, 1], &[3, 3, 4], 100, 1e-12).unwrap();
+        let (_, auth3) = score(&result, 3);
+        let (_, auth4) = score(&result, 4);
+        assert!((auth3 - 2.0 / 3.0).abs() < 1e-9);
+        assert!((auth4 - 1.0 / 3.0).abs() < 1e-9);
+
+        let (hub1, auth1) = score(&result, 1);
+        assert!((hub1 - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(auth1, 0.0);
+    }
+
+    #[test]
+    fn test_salsa_scores_sum_to_one() {
+        let result = compute_salsa(&[1, 2, 3, 3], &[2, 3, 1, 4], 200, 1e-12).unwrap();
+        let hub_sum: f64 = result.hubs.iter().sum();
+        let auth_sum: f64 = result.authorities.iter().sum();
+        assert!((hub_sum - 1.0).abs() < 1e-9);
+        assert!((auth_sum - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cohits_lambda_zero_returns_prior() {
+        let result = compute_cohits(&[1, 1, 2], &[10, 11, 10], &[], 0.0, 0.0, 50, 1e-12).unwrap();
+        let (hub1, _) = score(&result, 1);
+        let (_, auth10) = score(&result, 10);
+        assert!((hub1 - 0.5).abs() < 1e-12);
+        assert!((auth10 - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cohits_propagates_scores() {
+        // Item 10 is shared by both users, item 11 only by user 1
+        let result = compute_cohits(&[1, 1, 2], &[10, 11, 10], &[], 0.8, 0.8, 200, 1e-12).unwrap();
+        let (_, auth10) = score(&result, 10);
+        let (_, auth11) = score(&result, 11);
+        assert!(auth10 > auth11);
+        let (hub1, _) = score(&result, 1);
+        let (hub2, _) = score(&result, 2);
+        assert!(hub1 > hub2);
+    }
+
+    #[test]
+    fn test_cohits_invalid_lambda() {
+        let result = compute_cohits(&[1], &[2], &[], 1.5, 0.5, 10, 1e-6);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_link_analysis_empty_graph_error() {
+        assert!(compute_salsa(&[], &[], 10, 1e-6).is_err());
+        assert!(compute_cohits(&[], &[], &[], 0.5, 0.5, 10, 1e-6).is_err());
+    }
+}
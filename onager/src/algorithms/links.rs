@@ -1,16 +1,25 @@
 //! Link prediction algorithms module.
 //!
-//! Jaccard, Adamic-Adar, Preferential Attachment, Resource Allocation, Common Neighbors.
+//! Jaccard, Adamic-Adar, Preferential Attachment, Resource Allocation, Common Neighbors,
+//! and negative edge sampling.
 
 use graphina::core::types::{Graph, NodeId};
 use graphina::links::allocation::resource_allocation_index;
 use graphina::links::attachment::preferential_attachment;
 use graphina::links::similarity::{adamic_adar_index, common_neighbors, jaccard_coefficient};
+use serde::{Deserialize, Serialize};
 
+use super::flow::simple_undirected;
+use super::sbm::SplitMix64;
+use crate::builder::GraphBuilder;
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hasher};
+use std::str::FromStr;
 
 /// Result of link prediction computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkPredictionResult {
     pub node1: Vec<i64>,
     pub node2: Vec<i64>,
@@ -33,26 +42,10 @@ pub fn compute_jaccard(src: &[i64], dst: &[i64]) -> Result<LinkPredictionResult>
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    let results = jaccard_coefficient(&graph, None);
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
+    let results = jaccard_coefficient(&built.graph, None);
+    let reverse_map = built.reverse_map();
 
     let mut node1 = Vec::with_capacity(results.len());
     let mut node2 = Vec::with_capacity(results.len());
@@ -84,26 +77,10 @@ pub fn compute_adamic_adar(src: &[i64], dst: &[i64]) -> Result<LinkPredictionRes
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    let results = adamic_adar_index(&graph, None);
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
+    let results = adamic_adar_index(&built.graph, None);
+    let reverse_map = built.reverse_map();
 
     let mut node1 = Vec::with_capacity(results.len());
     let mut node2 = Vec::with_capacity(results.len());
@@ -135,26 +112,10 @@ pub fn compute_preferential_attachment(src: &[i64], dst: &[i64]) -> Result<LinkP
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    let results = preferential_attachment(&graph, None);
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
+    let results = preferential_attachment(&built.graph, None);
+    let reverse_map = built.reverse_map();
 
     let mut node1 = Vec::with_capacity(results.len());
     let mut node2 = Vec::with_capacity(results.len());
@@ -186,26 +147,10 @@ pub fn compute_resource_allocation(src: &[i64], dst: &[i64]) -> Result<LinkPredi
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    let results = resource_allocation_index(&graph, None);
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
+    let results = resource_allocation_index(&built.graph, None);
+    let reverse_map = built.reverse_map();
 
     let mut node1 = Vec::with_capacity(results.len());
     let mut node2 = Vec::with_capacity(results.len());
@@ -225,6 +170,7 @@ pub fn compute_resource_allocation(src: &[i64], dst: &[i64]) -> Result<LinkPredi
 }
 
 /// Result of common neighbors computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommonNeighborsResult {
     pub node1: Vec<i64>,
     pub node2: Vec<i64>,
@@ -244,26 +190,10 @@ pub fn compute_common_neighbors(src: &[i64], dst: &[i64]) -> Result<CommonNeighb
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
-    let nodes: Vec<NodeId> = node_set.values().cloned().collect();
+    let reverse_map = built.reverse_map();
+    let nodes: Vec<NodeId> = built.graph_ids.clone();
 
     let mut node1 = Vec::new();
     let mut node2 = Vec::new();
@@ -274,7 +204,7 @@ pub fn compute_common_neighbors(src: &[i64], dst: &[i64]) -> Result<CommonNeighb
         for j in (i + 1)..nodes.len() {
             let u = nodes[i];
             let v = nodes[j];
-            let count = common_neighbors(&graph, u, v);
+            let count = common_neighbors(&built.graph, u, v);
             if let (Some(&ext_u), Some(&ext_v)) = (reverse_map.get(&u), reverse_map.get(&v)) {
                 node1.push(ext_u);
                 node2.push(ext_v);
@@ -290,6 +220,130 @@ pub fn compute_common_neighbors(src: &[i64], dst: &[i64]) -> Result<CommonNeighb
     })
 }
 
+/// Random draws allowed per requested sample before sampling gives up.
+const DRAWS_PER_SAMPLE: usize = 100;
+
+/// How `sample_negative_edges` draws candidate node pairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NegativeSampling {
+    /// Both endpoints are drawn uniformly from all nodes.
+    Uniform,
+    /// Each endpoint is drawn in proportion to its degree, matching the
+    /// degree bias of the positive edges.
+    Degree,
+    /// Pairs two hops apart, drawn in proportion to their number of common
+    /// neighbors, which a link predictor finds hardest to reject.
+    Hard,
+}
+
+impl FromStr for NegativeSampling {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "uniform" => Ok(NegativeSampling::Uniform),
+            "degree" => Ok(NegativeSampling::Degree),
+            "hard" | "common_neighbors" => Ok(NegativeSampling::Hard),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown negative sampling strategy '{}', expected 'uniform', 'degree', or 'hard'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Node pairs that are not joined by an edge, with `node1 < node2`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NegativeEdgesResult {
+    pub node1: Vec<i64>,
+    pub node2: Vec<i64>,
+}
+
+/// Index of the item whose share of `cumulative` contains `r`.
+fn pick_weighted(cumulative: &[u64], r: u64) -> usize {
+    cumulative.partition_point(|&c| c <= r)
+}
+
+/// Sample up to `n_samples` distinct node pairs that are not joined by an edge.
+///
+/// These are the negative examples for training a link predictor. The graph
+/// is treated as undirected, only nodes that appear in the edges are
+/// candidates, and a node is never paired with itself. `strategy` sets how
+/// pairs are drawn, and the same `seed` always gives the same pairs. Sampling
+/// gives up after 100 draws per requested pair, so a dense graph, or one with
+/// few pairs two hops apart for `Hard`, can yield fewer pairs than requested.
+pub fn sample_negative_edges(
+    src: &[i64],
+    dst: &[i64],
+    n_samples: usize,
+    strategy: NegativeSampling,
+    seed: Option<u64>,
+) -> Result<NegativeEdgesResult> {
+    let graph = simple_undirected(src, dst)?;
+    let n = graph.nodes.len();
+    let edges: HashSet<(usize, usize)> = graph.edges.iter().copied().collect();
+    let mut neighbors = vec![Vec::new(); n];
+    for &(u, v) in &graph.edges {
+        neighbors[u].push(v);
+        neighbors[v].push(u);
+    }
+
+    // Cumulative draw weights, per node for `Degree` and per center node of a
+    // two-edge path for `Hard`
+    let mut total = 0u64;
+    let cumulative: Vec<u64> = neighbors
+        .iter()
+        .map(|list| {
+            let d = list.len() as u64;
+            total += match strategy {
+                NegativeSampling::Uniform => 1,
+                NegativeSampling::Degree => d,
+                NegativeSampling::Hard => d * d.saturating_sub(1) / 2,
+            };
+            total
+        })
+        .collect();
+
+    let non_edges = (n * n.saturating_sub(1) / 2).saturating_sub(edges.len());
+    let target = n_samples.min(non_edges);
+    let mut result = NegativeEdgesResult {
+        node1: Vec::with_capacity(target),
+        node2: Vec::with_capacity(target),
+    };
+    if target == 0 || total == 0 {
+        return Ok(result);
+    }
+
+    let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let mut rng = SplitMix64(seed);
+    let mut chosen: HashSet<(usize, usize)> = HashSet::with_capacity(target);
+    for _ in 0..target.saturating_mul(DRAWS_PER_SAMPLE) {
+        let (a, b) = match strategy {
+            NegativeSampling::Uniform | NegativeSampling::Degree => (
+                pick_weighted(&cumulative, rng.next_u64() % total),
+                pick_weighted(&cumulative, rng.next_u64() % total),
+            ),
+            NegativeSampling::Hard => {
+                let center = &neighbors[pick_weighted(&cumulative, rng.next_u64() % total)];
+                let i = rng.next_below(center.len());
+                // Skip over `i` so the two neighbors differ
+                let j = (i + 1 + rng.next_below(center.len() - 1)) % center.len();
+                (center[i], center[j])
+            }
+        };
+        let pair = (a.min(b), a.max(b));
+        if a == b || edges.contains(&pair) || !chosen.insert(pair) {
+            continue;
+        }
+        result.node1.push(graph.nodes[pair.0]);
+        result.node2.push(graph.nodes[pair.1]);
+        if result.node1.len() == target {
+            break;
+        }
+    }
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,6 +440,67 @@ mod tests {
         assert!(!result.scores.is_empty());
     }
 
+    #[test]
+    fn test_sample_negative_edges_avoids_edges() {
+        // Path 1-2-3-4-5 with a chord 1-3
+        let src = vec![1, 2, 3, 4, 1];
+        let dst = vec![2, 3, 4, 5, 3];
+        let edges: HashSet<(i64, i64)> = src.iter().zip(&dst).map(|(&u, &v)| (u, v)).collect();
+
+        for strategy in [
+            NegativeSampling::Uniform,
+            NegativeSampling::Degree,
+            NegativeSampling::Hard,
+        ] {
+            let a = sample_negative_edges(&src, &dst, 4, strategy, Some(3)).unwrap();
+            let b = sample_negative_edges(&src, &dst, 4, strategy, Some(3)).unwrap();
+            assert_eq!(a.node1, b.node1);
+            assert_eq!(a.node2, b.node2);
+            let pairs: HashSet<(i64, i64)> = a
+                .node1
+                .iter()
+                .zip(&a.node2)
+                .map(|(&u, &v)| (u, v))
+                .collect();
+            assert_eq!(pairs.len(), a.node1.len());
+            for &(u, v) in &pairs {
+                assert!(u < v);
+                assert!(!edges.contains(&(u, v)) && !edges.contains(&(v, u)));
+            }
+        }
+
+        // The five non-edges are all there is, even when more are requested
+        let all =
+            sample_negative_edges(&src, &dst, 50, NegativeSampling::Uniform, Some(1)).unwrap();
+        assert_eq!(all.node1.len(), 5);
+
+        // Pairs two hops apart are 1-4, 2-4, and 3-5
+        let hard = sample_negative_edges(&src, &dst, 50, NegativeSampling::Hard, Some(1)).unwrap();
+        let mut pairs: Vec<(i64, i64)> = hard.node1.into_iter().zip(hard.node2).collect();
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(1, 4), (2, 4), (3, 5)]);
+    }
+
+    #[test]
+    fn test_sample_negative_edges_edge_cases() {
+        // A triangle has no non-edges
+        let result = sample_negative_edges(
+            &[1, 2, 3],
+            &[2, 3, 1],
+            5,
+            NegativeSampling::Uniform,
+            Some(1),
+        )
+        .unwrap();
+        assert!(result.node1.is_empty());
+        assert!(sample_negative_edges(&[], &[], 5, NegativeSampling::Uniform, Some(1)).is_err());
+        assert!("random".parse::<NegativeSampling>().is_err());
+        assert_eq!(
+            "Common_Neighbors".parse::<NegativeSampling>().unwrap(),
+            NegativeSampling::Hard
+        );
+    }
+
     #[test]
     fn test_common_neighbors() {
         // Triangle graph: nodes 1-2-3 all connected
@@ -0,0 +1,405 @@
+//! Markov chain module.
+//!
+//! Stationary distribution, expected hitting times, and removal-effect
+//! attribution of the random walk on a weighted directed graph, where each
+//! step follows an outgoing edge with probability proportional to its weight.
+
+use crate::builder::{index_edges, IndexedEdges, NodeIndex};
+use crate::error::{OnagerError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Iteration limit for the iterative solvers in this module.
+const MAX_ITER: usize = 10_000;
+/// Convergence tolerance for the iterative solvers in this module.
+const TOLERANCE: f64 = 1e-10;
+
+/// Result of stationary distribution computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationaryResult {
+    pub node_ids: Vec<i64>,
+    pub probabilities: Vec<f64>,
+}
+
+/// Result of hitting time computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HittingTimeResult {
+    pub node_ids: Vec<i64>,
+    pub hitting_times: Vec<f64>,
+}
+
+/// Result of Markov chain attribution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributionResult {
+    pub node_ids: Vec<i64>,
+    pub removal_effects: Vec<f64>,
+    pub attributions: Vec<f64>,
+}
+
+/// Transition probabilities over compact node indices.
+struct Chain {
+    nodes: NodeIndex,
+    /// `(next, probability)` pairs for each node. Parallel edges are kept as
+    /// separate pairs, and nodes without outgoing weight have none.
+    transitions: Vec<Vec<(usize, f64)>>,
+}
+
+fn build_chain(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<Chain> {
+    let IndexedEdges { nodes, edges } = index_edges(src, dst, weights)?;
+    let n = nodes.len();
+    let mut strength = vec![0.0; n];
+    for &(u, _, w) in &edges {
+        strength[u] += w;
+    }
+    let mut transitions = vec![Vec::new(); n];
+    for (u, v, w) in edges {
+        if w > 0.0 {
+            transitions[u].push((v, w / strength[u]));
+        }
+    }
+    Ok(Chain { nodes, transitions })
+}
+
+/// Compute the stationary distribution of the random walk on a weighted digraph.
+///
+/// Empty weights mean unit weights. A node without outgoing weight keeps the
+/// walk, so it absorbs the probability that reaches it. When the chain has
+/// more than one closed class, the result is the long-run distribution of a
+/// walk that starts at a uniformly random node. The walk is made lazy so that
+/// periodic chains converge too, which does not change the distribution.
+pub fn compute_stationary_distribution(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+) -> Result<StationaryResult> {
+    let chain = build_chain(src, dst, weights)?;
+    let n = chain.nodes.len();
+
+    let mut probabilities = vec![1.0 / n as f64; n];
+    for _ in 0..MAX_ITER {
+        let mut next: Vec<f64> = probabilities.iter().map(|p| 0.5 * p).collect();
+        for (u, out) in chain.transitions.iter().enumerate() {
+            let moving = 0.5 * probabilities[u];
+            if out.is_empty() {
+                next[u] += moving;
+            }
+            for &(v, p) in out {
+                next[v] += moving * p;
+            }
+        }
+
+        let delta: f64 = next
+            .iter()
+            .zip(&probabilities)
+            .map(|(x, y)| (x - y).abs())
+            .sum();
+        probabilities = next;
+        if delta < TOLERANCE {
+            break;
+        }
+    }
+
+    Ok(StationaryResult {
+        node_ids: chain.nodes.node_ids,
+        probabilities,
+    })
+}
+
+/// Compute the expected number of steps for the random walk on a weighted
+/// digraph to first reach `target` from each node.
+///
+/// Empty weights mean unit weights. The target has hitting time 0. Nodes from
+/// which the walk can get stuck away from the target, for example by entering
+/// a node without outgoing weight, have an infinite hitting time.
+pub fn compute_hitting_times(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    target: i64,
+) -> Result<HittingTimeResult> {
+    let chain = build_chain(src, dst, weights)?;
+    let t = chain
+        .nodes
+        .get(target)
+        .ok_or(OnagerError::NodeNotFound(target))?;
+    let n = chain.nodes.len();
+
+    let mut predecessors = vec![Vec::new(); n];
+    for (u, out) in chain.transitions.iter().enumerate() {
+        for &(v, _) in out {
+            predecessors[v].push(u);
+        }
+    }
+
+    // Nodes with a path to the target
+    let mut reaches = vec![false; n];
+    reaches[t] = true;
+    let mut stack = vec![t];
+    while let Some(v) = stack.pop() {
+        for &u in &predecessors[v] {
+            if !reaches[u] {
+                reaches[u] = true;
+                stack.push(u);
+            }
+        }
+    }
+
+    // A node is finite only if the walk cannot leave the nodes that reach the
+    // target before it arrives
+    let mut finite = reaches.clone();
+    let mut stack: Vec<usize> = (0..n).filter(|&v| !reaches[v]).collect();
+    while let Some(v) = stack.pop() {
+        for &u in &predecessors[v] {
+            if u != t && finite[u] {
+                finite[u] = false;
+                stack.push(u);
+            }
+        }
+    }
+
+    // Gauss-Seidel on h(u) = 1 + sum of p(u, v) * h(v), with h(target) = 0.
+    // Every successor of a finite node is finite, so the sums stay finite.
+    let mut hitting_times = vec![0.0; n];
+    for _ in 0..MAX_ITER {
+        let mut change: f64 = 0.0;
+        for u in 0..n {
+            if u == t || !finite[u] {
+                continue;
+            }
+            let next = 1.0
+                + chain.transitions[u]
+                    .iter()
+                    .map(|&(v, p)| p * hitting_times[v])
+                    .sum::<f64>();
+            change = change.max((next - hitting_times[u]).abs() / next);
+            hitting_times[u] = next;
+        }
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    for (h, &ok) in hitting_times.iter_mut().zip(&finite) {
+        if !ok {
+            *h = f64::INFINITY;
+        }
+    }
+
+    Ok(HittingTimeResult {
+        node_ids: chain.nodes.node_ids,
+        hitting_times,
+    })
+}
+
+/// Probability of reaching `conversion` from each node before any absorbing
+/// state. Nodes in `absorbing` and nodes without outgoing weight never convert.
+fn conversion_probabilities(chain: &Chain, conversion: usize, absorbing: &[bool]) -> Vec<f64> {
+    // Gauss-Seidel from 0 increases towards the smallest solution, which is
+    // the absorption probability even when some nodes never get absorbed
+    let mut probabilities = vec![0.0; chain.nodes.len()];
+    probabilities[conversion] = 1.0;
+    for _ in 0..MAX_ITER {
+        let mut change: f64 = 0.0;
+        for (u, out) in chain.transitions.iter().enumerate() {
+            if u == conversion || absorbing[u] {
+                continue;
+            }
+            let next: f64 = out.iter().map(|&(v, p)| p * probabilities[v]).sum();
+            change = change.max((next - probabilities[u]).abs());
+            probabilities[u] = next;
+        }
+        if change < TOLERANCE {
+            break;
+        }
+    }
+    probabilities
+}
+
+/// Compute removal-effect attribution of an absorbing Markov chain over
+/// customer journeys.
+///
+/// Edges are transitions between channels, usually weighted by how many
+/// journeys made them. Journeys begin at `start` and end at `conversion` or at
+/// one of `null_states`. Empty weights mean unit weights. Every other node is
+/// a channel. The removal effect of a channel is the share of the conversion
+/// probability from `start` that is lost when journeys entering the channel
+/// end there without converting. Attributions are the removal effects scaled
+/// to sum to 1, so multiplying them by the number of conversions gives the
+/// conversions credited to each channel.
+pub fn compute_markov_attribution(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    start: i64,
+    conversion: i64,
+    null_states: &[i64],
+) -> Result<AttributionResult> {
+    let chain = build_chain(src, dst, weights)?;
+    let s = chain
+        .nodes
+        .get(start)
+        .ok_or(OnagerError::NodeNotFound(start))?;
+    let c = chain
+        .nodes
+        .get(conversion)
+        .ok_or(OnagerError::NodeNotFound(conversion))?;
+    if s == c {
+        return Err(OnagerError::InvalidArgument(
+            "start and conversion must be different nodes".to_string(),
+        ));
+    }
+    let n = chain.nodes.len();
+
+    // Null states that are not in the graph are ignored
+    let mut absorbing = vec![false; n];
+    for node in null_states {
+        if *node == start || *node == conversion {
+            return Err(OnagerError::InvalidArgument(format!(
+                "Null state {} must differ from start and conversion",
+                node
+            )));
+        }
+        if let Some(i) = chain.nodes.get(*node) {
+            absorbing[i] = true;
+        }
+    }
+
+    let base = conversion_probabilities(&chain, c, &absorbing)[s];
+    if base <= 0.0 {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Conversion node {} cannot be reached from start node {}",
+            conversion, start
+        )));
+    }
+
+    let mut node_ids = Vec::new();
+    let mut removal_effects = Vec::new();
+    for u in 0..n {
+        if u == s || u == c || absorbing[u] {
+            continue;
+        }
+        absorbing[u] = true;
+        let removed = conversion_probabilities(&chain, c, &absorbing)[s];
+        absorbing[u] = false;
+        node_ids.push(chain.nodes.node_ids[u]);
+        removal_effects.push((1.0 - removed / base).max(0.0));
+    }
+
+    let total: f64 = removal_effects.iter().sum();
+    let attributions = removal_effects
+        .iter()
+        .map(|&effect| if total > 0.0 { effect / total } else { 0.0 })
+        .collect();
+    Ok(AttributionResult {
+        node_ids,
+        removal_effects,
+        attributions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(node_ids: &[i64], values: &[f64], node: i64) -> f64 {
+        let i = node_ids.iter().position(|&n| n == node).unwrap();
+        values[i]
+    }
+
+    #[test]
+    fn test_stationary_distribution_follows_weights() {
+        // 1 always moves to 2, and 2 moves to 1 or stays with equal weight
+        let result = compute_stationary_distribution(&[1, 2, 2], &[2, 1, 2], &[]).unwrap();
+        let p = |node| value(&result.node_ids, &result.probabilities, node);
+        assert!((p(1) - 1.0 / 3.0).abs() < 1e-8);
+        assert!((p(2) - 2.0 / 3.0).abs() < 1e-8);
+
+        // Doubling the weight back to 1 shifts probability to it
+        let weighted =
+            compute_stationary_distribution(&[1, 2, 2], &[2, 1, 2], &[1.0, 2.0, 1.0]).unwrap();
+        let p1 = value(&weighted.node_ids, &weighted.probabilities, 1);
+        assert!((p1 - 0.4).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_stationary_distribution_periodic_chain_converges() {
+        // Star 1 - 2 - 3 walked both ways has period 2
+        let result = compute_stationary_distribution(&[1, 2, 2, 3], &[2, 1, 3, 2], &[]).unwrap();
+        let p = |node| value(&result.node_ids, &result.probabilities, node);
+        assert!((p(1) - 0.25).abs() < 1e-8);
+        assert!((p(2) - 0.5).abs() < 1e-8);
+        assert!((p(3) - 0.25).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_stationary_distribution_absorbing_node() {
+        let result = compute_stationary_distribution(&[1], &[2], &[]).unwrap();
+        assert!(value(&result.node_ids, &result.probabilities, 1) < 1e-8);
+        assert!((value(&result.node_ids, &result.probabilities, 2) - 1.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_hitting_times_with_return_edge() {
+        // From 2 the walk reaches 3 or falls back to 1
+        let result = compute_hitting_times(&[1, 2, 2], &[2, 1, 3], &[], 3).unwrap();
+        let h = |node| value(&result.node_ids, &result.hitting_times, node);
+        assert!((h(1) - 4.0).abs() < 1e-6);
+        assert!((h(2) - 3.0).abs() < 1e-6);
+        assert_eq!(h(3), 0.0);
+    }
+
+    #[test]
+    fn test_hitting_times_infinite_when_walk_can_get_stuck() {
+        // From 1 the walk may move to 4, which has no way out
+        let result = compute_hitting_times(&[1, 1, 2], &[2, 4, 3], &[], 3).unwrap();
+        let h = |node| value(&result.node_ids, &result.hitting_times, node);
+        assert!(h(1).is_infinite());
+        assert!((h(2) - 1.0).abs() < 1e-9);
+        assert!(h(4).is_infinite());
+
+        // With zero weight the edge to 4 is never taken
+        let result = compute_hitting_times(&[1, 1, 2], &[2, 4, 3], &[1.0, 0.0, 1.0], 3).unwrap();
+        assert!((value(&result.node_ids, &result.hitting_times, 1) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_markov_attribution_removal_effects() {
+        // Journeys: start (0) -> search (1) -> conversion (9), start -> social (2)
+        // -> search -> conversion, and start -> social -> null (8)
+        let src = [0, 1, 0, 2, 1, 0, 2];
+        let dst = [1, 9, 2, 1, 9, 2, 8];
+        let result = compute_markov_attribution(&src, &dst, &[], 0, 9, &[8]).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2]);
+
+        // P(conversion) = 1/3 + 2/3 * 1/2 = 2/3. Without search nothing
+        // converts, and without social only the direct 1/3 does.
+        let effect = |node| value(&result.node_ids, &result.removal_effects, node);
+        assert!((effect(1) - 1.0).abs() < 1e-9);
+        assert!((effect(2) - 0.5).abs() < 1e-9);
+        let share = |node| value(&result.node_ids, &result.attributions, node);
+        assert!((share(1) - 2.0 / 3.0).abs() < 1e-9);
+        assert!((share(2) - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_markov_attribution_invalid_input() {
+        let (src, dst) = ([0, 1], [1, 9]);
+        assert!(matches!(
+            compute_markov_attribution(&src, &dst, &[], 0, 7, &[]),
+            Err(OnagerError::NodeNotFound(7))
+        ));
+        assert!(compute_markov_attribution(&src, &dst, &[], 0, 0, &[]).is_err());
+        assert!(compute_markov_attribution(&src, &dst, &[], 0, 9, &[9]).is_err());
+        // Conversion only reachable through a null state
+        assert!(compute_markov_attribution(&src, &dst, &[], 0, 9, &[1]).is_err());
+    }
+
+    #[test]
+    fn test_markov_invalid_input() {
+        assert!(matches!(
+            compute_hitting_times(&[1], &[2], &[], 9),
+            Err(OnagerError::NodeNotFound(9))
+        ));
+        assert!(compute_stationary_distribution(&[1], &[2], &[-1.0]).is_err());
+        assert!(compute_stationary_distribution(&[1, 2], &[2, 1], &[1.0]).is_err());
+        assert!(compute_stationary_distribution(&[], &[], &[]).is_err());
+    }
+}
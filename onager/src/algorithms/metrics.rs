@@ -1,17 +1,21 @@
 //! Graph metrics module.
 //!
-//! Diameter, Radius, Average Clustering, Average Path Length, Transitivity, Triangle Count, Assortativity.
+//! Diameter, Radius, Average Clustering, Average Path Length, Transitivity, Triangle Count, Assortativity,
+//! Category Mixing Matrix, Directed Triangle Census, Weighted Triangle Intensity, Center and
+//! Periphery.
 
-use graphina::core::types::{Graph, NodeId};
+use graphina::core::types::Graph;
 use graphina::metrics::{
     assortativity, average_clustering_coefficient, average_path_length, diameter, radius,
     transitivity,
 };
 use graphina::parallel::triangles_parallel;
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 
+use crate::builder::{index_edges, GraphBuilder, IndexedEdges};
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Compute graph diameter (longest shortest path).
 pub fn compute_diameter(src: &[i64], dst: &[i64]) -> Result<i64> {
@@ -26,24 +30,9 @@ pub fn compute_diameter(src: &[i64], dst: &[i64]) -> Result<i64> {
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
-    }
-    Ok(diameter(&graph).map(|d| d as i64).unwrap_or(-1))
+    let built =
+        GraphBuilder::new(src, dst)?.build::<Graph<i64, OrderedFloat<f64>>>(|_| OrderedFloat(1.0));
+    Ok(diameter(&built.graph).map(|d| d as i64).unwrap_or(-1))
 }
 
 /// Compute graph radius.
@@ -59,24 +48,9 @@ pub fn compute_radius(src: &[i64], dst: &[i64]) -> Result<i64> {
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
-    }
-    Ok(radius(&graph).map(|v| v as i64).unwrap_or(-1))
+    let built =
+        GraphBuilder::new(src, dst)?.build::<Graph<i64, OrderedFloat<f64>>>(|_| OrderedFloat(1.0));
+    Ok(radius(&built.graph).map(|v| v as i64).unwrap_or(-1))
 }
 
 /// Compute average clustering coefficient.
@@ -92,24 +66,8 @@ pub fn compute_avg_clustering(src: &[i64], dst: &[i64]) -> Result<f64> {
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-    Ok(average_clustering_coefficient(&graph))
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    Ok(average_clustering_coefficient(&built.graph))
 }
 
 /// Compute average path length.
@@ -125,24 +83,9 @@ pub fn compute_avg_path_length(src: &[i64], dst: &[i64]) -> Result<f64> {
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
-    }
-    Ok(average_path_length(&graph).unwrap_or(f64::NAN))
+    let built =
+        GraphBuilder::new(src, dst)?.build::<Graph<i64, OrderedFloat<f64>>>(|_| OrderedFloat(1.0));
+    Ok(average_path_length(&built.graph).unwrap_or(f64::NAN))
 }
 
 /// Compute transitivity (global clustering coefficient).
@@ -158,27 +101,12 @@ pub fn compute_transitivity(src: &[i64], dst: &[i64]) -> Result<f64> {
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-    Ok(transitivity(&graph))
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    Ok(transitivity(&built.graph))
 }
 
 /// Result of triangle counting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TriangleResult {
     pub node_ids: Vec<i64>,
     pub triangle_counts: Vec<i64>,
@@ -197,27 +125,10 @@ pub fn compute_triangle_count(src: &[i64], dst: &[i64]) -> Result<TriangleResult
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let triangles = triangles_parallel(&graph);
+    let triangles = triangles_parallel(&built.graph);
 
     let mut result_nodes = Vec::with_capacity(triangles.len());
     let mut result_counts = Vec::with_capacity(triangles.len());
@@ -248,24 +159,8 @@ pub fn compute_assortativity(src: &[i64], dst: &[i64]) -> Result<f64> {
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-    Ok(assortativity(&graph))
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    Ok(assortativity(&built.graph))
 }
 
 /// Compute graph density.
@@ -308,6 +203,344 @@ pub fn compute_graph_density(src: &[i64], dst: &[i64], directed: bool) -> Result
     }
 }
 
+/// Edge counts between node categories, with the categorical assortativity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MixingMatrixResult {
+    /// Categories in ascending order, indexing the rows and columns of `counts`.
+    pub categories: Vec<i64>,
+    /// Row-major k x k matrix where entry (i, j) counts edges from category i
+    /// to category j.
+    pub counts: Vec<i64>,
+    /// Newman's assortativity coefficient, NaN when every edge stays within
+    /// one category and the coefficient is undefined.
+    pub assortativity: f64,
+}
+
+/// Compute the category mixing matrix and assortativity of a graph.
+///
+/// `node_ids[i]` belongs to category `node_categories[i]`, and a node may be
+/// listed more than once as long as its category agrees. Every category given
+/// gets a row and a column, even if no edge touches it. Undirected graphs add
+/// each edge to both (i, j) and (j, i), so the matrix is symmetric. The
+/// assortativity is (sum e_ii - sum a_i b_i) / (1 - sum a_i b_i) over the
+/// normalized matrix e with row sums a and column sums b, ranging from -1
+/// (edges only join different categories) to 1 (edges only join equal ones).
+pub fn compute_mixing_matrix(
+    src: &[i64],
+    dst: &[i64],
+    node_ids: &[i64],
+    node_categories: &[i64],
+    directed: bool,
+) -> Result<MixingMatrixResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if node_ids.len() != node_categories.len() {
+        return Err(OnagerError::InvalidArgument(
+            "node_ids and node_categories arrays must have same length".to_string(),
+        ));
+    }
+
+    let mut category_of: HashMap<i64, i64> = HashMap::new();
+    for (&node, &category) in node_ids.iter().zip(node_categories) {
+        if let Some(&previous) = category_of.get(&node) {
+            if previous != category {
+                return Err(OnagerError::InvalidArgument(format!(
+                    "Node {} has conflicting categories {} and {}",
+                    node, previous, category
+                )));
+            }
+        }
+        category_of.insert(node, category);
+    }
+    let mut categories: Vec<i64> = node_categories.to_vec();
+    categories.sort_unstable();
+    categories.dedup();
+    let k = categories.len();
+    let index = |node: i64| -> Result<usize> {
+        let category = category_of.get(&node).ok_or_else(|| {
+            OnagerError::InvalidArgument(format!("Node {} has no category", node))
+        })?;
+        Ok(categories.partition_point(|c| c < category))
+    };
+
+    let mut counts = vec![0i64; k * k];
+    for (&u, &v) in src.iter().zip(dst) {
+        let (i, j) = (index(u)?, index(v)?);
+        counts[i * k + j] += 1;
+        if !directed {
+            counts[j * k + i] += 1;
+        }
+    }
+
+    let total: i64 = counts.iter().sum();
+    let e = |i: usize, j: usize| counts[i * k + j] as f64 / total as f64;
+    let trace: f64 = (0..k).map(|i| e(i, i)).sum();
+    let expected: f64 = (0..k)
+        .map(|i| {
+            let a: f64 = (0..k).map(|j| e(i, j)).sum();
+            let b: f64 = (0..k).map(|j| e(j, i)).sum();
+            a * b
+        })
+        .sum();
+    let assortativity = if (1.0 - expected).abs() < 1e-12 {
+        f64::NAN
+    } else {
+        (trace - expected) / (1.0 - expected)
+    };
+    Ok(MixingMatrixResult {
+        categories,
+        counts,
+        assortativity,
+    })
+}
+
+/// Names of the directed triangle types in the order of
+/// `TriangleCensusResult::counts`, using the MAN labels of the triad census.
+pub const TRIANGLE_CENSUS_TYPES: [&str; 7] = ["030T", "030C", "120D", "120U", "120C", "210", "300"];
+
+/// Counts of each directed triangle type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriangleCensusResult {
+    /// Number of triangles of each type, ordered as `TRIANGLE_CENSUS_TYPES`.
+    pub counts: Vec<i64>,
+}
+
+/// Result of weighted triangle counting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightedTriangleResult {
+    pub node_ids: Vec<i64>,
+    pub triangle_counts: Vec<i64>,
+    /// Sum of the intensities of the triangles each node belongs to.
+    pub intensities: Vec<f64>,
+}
+
+/// Calls `visit(u, v, w)` once for every triangle u < v < w of an undirected
+/// graph given as sorted, deduplicated neighbor lists.
+fn for_each_triangle(neighbors: &[Vec<usize>], mut visit: impl FnMut(usize, usize, usize)) {
+    for (u, adjacent) in neighbors.iter().enumerate() {
+        for &v in adjacent.iter().filter(|&&v| v > u) {
+            for &w in neighbors[v].iter().filter(|&&w| w > v) {
+                if adjacent.binary_search(&w).is_ok() {
+                    visit(u, v, w);
+                }
+            }
+        }
+    }
+}
+
+/// Count the directed triangles of each type.
+///
+/// A triangle is a triple of nodes where every pair is joined by at least one
+/// arc. Each triangle falls into one of the seven connected, fully linked
+/// triad types: 030T (transitive), 030C (cyclic), 120D (a mutual pair both
+/// reached from the third node), 120U (a mutual pair both pointing to the third
+/// node), 120C (a mutual pair closing a two-step path), 210 (two mutual pairs),
+/// and 300 (all pairs mutual). Parallel arcs and self-loops are ignored.
+pub fn compute_triangle_census(src: &[i64], dst: &[i64]) -> Result<TriangleCensusResult> {
+    let graph = index_edges(src, dst, &[])?;
+    let mut arcs: HashSet<(usize, usize)> = HashSet::new();
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); graph.nodes.len()];
+    for &(u, v, _) in &graph.edges {
+        if u != v && arcs.insert((u, v)) {
+            neighbors[u].push(v);
+            neighbors[v].push(u);
+        }
+    }
+    for adjacent in &mut neighbors {
+        adjacent.sort_unstable();
+        adjacent.dedup();
+    }
+
+    let mut counts = vec![0i64; TRIANGLE_CENSUS_TYPES.len()];
+    for_each_triangle(&neighbors, |u, v, w| {
+        let triple = [u, v, w];
+        let mut out_degree = [0usize; 3];
+        let mut in_degree = [0usize; 3];
+        let mut mutual = 0;
+        for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+            let forward = arcs.contains(&(triple[a], triple[b]));
+            let backward = arcs.contains(&(triple[b], triple[a]));
+            if forward {
+                out_degree[a] += 1;
+                in_degree[b] += 1;
+            }
+            if backward {
+                out_degree[b] += 1;
+                in_degree[a] += 1;
+            }
+            if forward && backward {
+                mutual += 1;
+            }
+        }
+        let kind = match mutual {
+            3 => 6,
+            2 => 5,
+            1 => {
+                // The node outside the mutual pair has one arc to each end of it
+                let outside = (0..3)
+                    .find(|&i| out_degree[i] + in_degree[i] == 2)
+                    .unwrap_or(0);
+                match out_degree[outside] {
+                    2 => 2,
+                    0 => 3,
+                    _ => 4,
+                }
+            }
+            _ => {
+                if out_degree.iter().all(|&d| d == 1) {
+                    1
+                } else {
+                    0
+                }
+            }
+        };
+        counts[kind] += 1;
+    });
+    Ok(TriangleCensusResult { counts })
+}
+
+/// Count triangles and their weighted intensity for each node.
+///
+/// Edges are undirected, parallel edges add their weights, and self-loops are
+/// ignored. The intensity of a triangle is the geometric mean of its three
+/// edge weights divided by the largest edge weight in the graph, following
+/// Onnela et al. (2005), so it lies between 0 and 1. Weights must be finite
+/// and non-negative, and a triangle with a zero-weight edge counts with zero
+/// intensity.
+pub fn compute_weighted_triangles(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+) -> Result<WeightedTriangleResult> {
+    if weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights array must have same length as src and dst".to_string(),
+        ));
+    }
+    let IndexedEdges { nodes, edges } = index_edges(src, dst, weights)?;
+    let mut edge_weights: HashMap<(usize, usize), f64> = HashMap::new();
+    for (u, v, w) in edges {
+        if u != v {
+            *edge_weights.entry((u.min(v), u.max(v))).or_insert(0.0) += w;
+        }
+    }
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for &(u, v) in edge_weights.keys() {
+        neighbors[u].push(v);
+        neighbors[v].push(u);
+    }
+    for adjacent in &mut neighbors {
+        adjacent.sort_unstable();
+    }
+    let max_weight = edge_weights.values().copied().fold(0.0, f64::max);
+
+    let mut triangle_counts = vec![0i64; nodes.len()];
+    let mut intensities = vec![0.0; nodes.len()];
+    let weight = |a: usize, b: usize| edge_weights.get(&(a, b)).copied().unwrap_or(0.0);
+    for_each_triangle(&neighbors, |u, v, w| {
+        let intensity = if max_weight > 0.0 {
+            (weight(u, v) * weight(v, w) * weight(u, w)).cbrt() / max_weight
+        } else {
+            0.0
+        };
+        for node in [u, v, w] {
+            triangle_counts[node] += 1;
+            intensities[node] += intensity;
+        }
+    });
+    Ok(WeightedTriangleResult {
+        node_ids: nodes.node_ids,
+        triangle_counts,
+        intensities,
+    })
+}
+
+/// Nodes whose eccentricity attains the radius or the diameter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EccentricityNodesResult {
+    pub node_ids: Vec<i64>,
+    /// The shared eccentricity of the returned nodes.
+    pub eccentricity: i64,
+}
+
+/// Computes the eccentricity of every node of a connected undirected graph by
+/// breadth-first search from each node.
+fn eccentricities(src: &[i64], dst: &[i64]) -> Result<(Vec<i64>, Vec<i64>)> {
+    let IndexedEdges { nodes, edges } = index_edges(src, dst, &[])?;
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for (u, v, _) in edges {
+        neighbors[u].push(v);
+        neighbors[v].push(u);
+    }
+
+    let mut result = Vec::with_capacity(nodes.len());
+    let mut distance = vec![usize::MAX; nodes.len()];
+    let mut queue = VecDeque::new();
+    for start in 0..nodes.len() {
+        distance.iter_mut().for_each(|d| *d = usize::MAX);
+        distance[start] = 0;
+        queue.push_back(start);
+        let (mut reached, mut farthest) = (1, 0);
+        while let Some(u) = queue.pop_front() {
+            farthest = farthest.max(distance[u]);
+            for &v in &neighbors[u] {
+                if distance[v] == usize::MAX {
+                    distance[v] = distance[u] + 1;
+                    reached += 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        if reached < nodes.len() {
+            return Err(OnagerError::InvalidArgument(
+                "Eccentricity is undefined for a disconnected graph".to_string(),
+            ));
+        }
+        result.push(farthest as i64);
+    }
+    Ok((nodes.node_ids, result))
+}
+
+/// Compute the center of a graph, the nodes whose eccentricity equals the
+/// radius. Edges are undirected and the graph must be connected.
+pub fn compute_center_nodes(src: &[i64], dst: &[i64]) -> Result<EccentricityNodesResult> {
+    let (nodes, ecc) = eccentricities(src, dst)?;
+    let radius = ecc.iter().copied().min().unwrap_or(0);
+    Ok(EccentricityNodesResult {
+        node_ids: nodes
+            .into_iter()
+            .zip(ecc)
+            .filter(|&(_, e)| e == radius)
+            .map(|(node, _)| node)
+            .collect(),
+        eccentricity: radius,
+    })
+}
+
+/// Compute the periphery of a graph, the nodes whose eccentricity equals the
+/// diameter. Edges are undirected and the graph must be connected.
+pub fn compute_periphery_nodes(src: &[i64], dst: &[i64]) -> Result<EccentricityNodesResult> {
+    let (nodes, ecc) = eccentricities(src, dst)?;
+    let diameter = ecc.iter().copied().max().unwrap_or(0);
+    Ok(EccentricityNodesResult {
+        node_ids: nodes
+            .into_iter()
+            .zip(ecc)
+            .filter(|&(_, e)| e == diameter)
+            .map(|(node, _)| node)
+            .collect(),
+        eccentricity: diameter,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,4 +682,138 @@ mod tests {
     fn test_density_empty_error() {
         assert!(compute_graph_density(&[], &[], false).is_err());
     }
+
+    #[test]
+    fn test_mixing_matrix_two_groups() {
+        // Triangles 1-2-3 (category 10) and 4-5-6 (category 20) joined by 3-4
+        let src = vec![1, 2, 3, 4, 5, 6, 3];
+        let dst = vec![2, 3, 1, 5, 6, 4, 4];
+        let nodes = vec![1, 2, 3, 4, 5, 6];
+        let categories = vec![10, 10, 10, 20, 20, 20];
+        let result = compute_mixing_matrix(&src, &dst, &nodes, &categories, false).unwrap();
+        assert_eq!(result.categories, vec![10, 20]);
+        assert_eq!(result.counts, vec![6, 1, 1, 6]);
+        // e = [[6, 1], [1, 6]] / 14, so r = (12/14 - 1/2) / (1 - 1/2) = 5/7
+        assert!((result.assortativity - 5.0 / 7.0).abs() < 1e-12);
+
+        let directed = compute_mixing_matrix(&src, &dst, &nodes, &categories, true).unwrap();
+        assert_eq!(directed.counts, vec![3, 1, 0, 3]);
+    }
+
+    #[test]
+    fn test_mixing_matrix_disassortative_and_undefined() {
+        // Every edge of a bipartite star joins different categories
+        let src = vec![1, 1, 1];
+        let dst = vec![2, 3, 4];
+        let result =
+            compute_mixing_matrix(&src, &dst, &[1, 2, 3, 4, 5], &[0, 1, 1, 1, 2], false).unwrap();
+        assert_eq!(result.categories, vec![0, 1, 2]);
+        assert_eq!(result.counts, vec![0, 3, 0, 3, 0, 0, 0, 0, 0]);
+        assert!((result.assortativity + 1.0).abs() < 1e-12);
+
+        let same = compute_mixing_matrix(&src, &dst, &[1, 2, 3, 4], &[7, 7, 7, 7], false).unwrap();
+        assert!(same.assortativity.is_nan());
+    }
+
+    #[test]
+    fn test_mixing_matrix_errors() {
+        let (src, dst) = path_graph();
+        assert!(compute_mixing_matrix(&src, &dst, &[1, 2, 3], &[0, 0, 1], false).is_err());
+        assert!(
+            compute_mixing_matrix(&src, &dst, &[1, 2, 3, 4, 1], &[0, 0, 1, 1, 1], false).is_err()
+        );
+        assert!(compute_mixing_matrix(&src, &dst, &[1, 2], &[0], false).is_err());
+        assert!(compute_mixing_matrix(&[], &[], &[], &[], false).is_err());
+    }
+
+    #[test]
+    fn test_triangle_census_counts_each_type() {
+        // One triad of each type on its own nodes, plus an open path
+        let arcs: Vec<(i64, i64)> = vec![
+            // 030T
+            (1, 2),
+            (2, 3),
+            (1, 3),
+            // 030C
+            (11, 12),
+            (12, 13),
+            (13, 11),
+            // 120D
+            (21, 22),
+            (22, 21),
+            (23, 21),
+            (23, 22),
+            // 120U
+            (31, 32),
+            (32, 31),
+            (31, 33),
+            (32, 33),
+            // 120C
+            (41, 43),
+            (43, 41),
+            (41, 42),
+            (42, 43),
+            // 210
+            (51, 52),
+            (52, 51),
+            (52, 53),
+            (53, 52),
+            (51, 53),
+            // 300
+            (61, 62),
+            (62, 61),
+            (62, 63),
+            (63, 62),
+            (61, 63),
+            (63, 61),
+            // Open path and repeated arc
+            (71, 72),
+            (72, 73),
+            (71, 72),
+        ];
+        let (src, dst): (Vec<i64>, Vec<i64>) = arcs.into_iter().unzip();
+        let result = compute_triangle_census(&src, &dst).unwrap();
+        assert_eq!(result.counts, vec![1; 7]);
+    }
+
+    #[test]
+    fn test_weighted_triangles_intensity() {
+        // Triangle 1-2-3 with weights 1, 1, and 8 (given as 5 + 3), and pendant 4
+        let src = vec![1, 2, 1, 1, 3];
+        let dst = vec![2, 3, 3, 3, 4];
+        let weights = vec![1.0, 1.0, 5.0, 3.0, 2.0];
+        let result = compute_weighted_triangles(&src, &dst, &weights).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert_eq!(result.triangle_counts, vec![1, 1, 1, 0]);
+        assert!((result.intensities[0] - 0.25).abs() < 1e-12);
+        assert!((result.intensities[2] - 0.25).abs() < 1e-12);
+        assert_eq!(result.intensities[3], 0.0);
+    }
+
+    #[test]
+    fn test_triangle_modes_invalid_input() {
+        assert!(compute_triangle_census(&[], &[]).is_err());
+        assert!(compute_weighted_triangles(&[1, 2], &[2, 3], &[1.0]).is_err());
+        assert!(compute_weighted_triangles(&[1], &[2], &[-1.0]).is_err());
+        assert!(compute_weighted_triangles(&[1], &[2], &[f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_center_and_periphery_of_path() {
+        let (src, dst) = path_graph();
+        let center = compute_center_nodes(&src, &dst).unwrap();
+        assert_eq!(center.node_ids, vec![2, 3]);
+        assert_eq!(center.eccentricity, 2);
+        let periphery = compute_periphery_nodes(&src, &dst).unwrap();
+        assert_eq!(periphery.node_ids, vec![1, 4]);
+        assert_eq!(periphery.eccentricity, 3);
+    }
+
+    #[test]
+    fn test_center_and_periphery_invalid_input() {
+        // Two components
+        assert!(compute_center_nodes(&[1, 3], &[2, 4]).is_err());
+        assert!(compute_periphery_nodes(&[1, 3], &[2, 4]).is_err());
+        assert!(compute_center_nodes(&[], &[]).is_err());
+    }
 }
@@ -1,38 +1,109 @@
 //! Graph algorithms module.
 //!
 //! This module provides graph algorithm implementations organized into submodules.
-//!
-//! Note: We use contains_key + insert pattern intentionally here because we need
-//! to call graph.add_node() to get the internal NodeId before inserting into the map.
-#![allow(clippy::map_entry)]
+//! Graphina graphs are built from edge arrays with [`crate::builder::GraphBuilder`], or with
+//! [`crate::builder::graph_from_edges`] when the direction is chosen at runtime.
+
+use serde::Serialize;
+
+use crate::error::Result;
 
 pub mod approximation;
+pub mod bipartite;
 pub mod centrality;
+pub mod coarsening;
 pub mod community;
+pub mod contraction;
+pub mod correlation;
+pub mod drift;
+pub mod estimate;
+pub mod facility;
+pub mod flow;
 pub mod generators;
+pub mod hierarchical;
+pub mod infomap;
+pub mod interning;
+pub mod kernels;
+pub mod layout;
+pub mod link_analysis;
 pub mod links;
+pub mod markov;
 pub mod metrics;
 pub mod mst;
+pub mod multilayer;
 pub mod parallel;
+pub mod patterns;
 pub mod personalized;
+pub mod roles;
+pub mod sbm;
+pub mod spectral;
+pub mod spill;
 pub mod subgraphs;
+pub mod temporal;
 pub mod traversal;
+pub mod weighted_rank;
+pub mod weights;
 
+mod parquet;
+#[cfg(test)]
+mod reference_tests;
 #[cfg(test)]
 mod regression_tests;
 
 // Re-export all public items for backward compatibility
 pub use approximation::*;
+pub use bipartite::*;
 pub use centrality::*;
+pub use coarsening::*;
 pub use community::*;
+pub use contraction::*;
+pub use correlation::*;
+pub use drift::*;
+pub use estimate::*;
+pub use facility::*;
+pub use flow::*;
 pub use generators::*;
+pub use hierarchical::*;
+pub use infomap::*;
+pub use interning::*;
+pub use kernels::*;
+pub use layout::*;
+pub use link_analysis::*;
 pub use links::*;
+pub use markov::*;
 pub use metrics::*;
 pub use mst::*;
+pub use multilayer::*;
 pub use parallel::*;
+pub use patterns::*;
 pub use personalized::*;
+pub use roles::*;
+pub use sbm::*;
+pub use spectral::*;
+pub use spill::*;
 pub use subgraphs::*;
+pub use temporal::*;
 pub use traversal::*;
+pub use weighted_rank::*;
+pub use weights::*;
 
 // Backward compatibility alias
 pub use links::LinkPredictionResult as JaccardResult;
+
+/// JSON serialization for algorithm results.
+///
+/// Implemented for every type that derives `Serialize`, so all result structs
+/// can be turned into JSON with `result.to_json()`.
+pub trait ToJson: Serialize {
+    /// Serializes the value to a compact JSON string.
+    fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Serializes the value to a pretty-printed JSON string.
+    fn to_json_pretty(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+impl<T: Serialize + ?Sized> ToJson for T {}
@@ -1,20 +1,78 @@
 //! Minimum Spanning Tree algorithms module.
 //!
-//! Prim's and Kruskal's MST algorithms.
+//! Prim's and Kruskal's MST algorithms, plus a degree-constrained spanning tree heuristic.
 
-use graphina::core::types::{Graph, NodeId};
+use graphina::core::types::Graph;
 use graphina::mst::algorithms::{kruskal_mst, prim_mst};
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 
+use crate::builder::{GraphBuilder, NodeIndex};
 use crate::error::{OnagerError, Result};
 use std::collections::HashMap;
 
 /// Result of MST computation.
+///
+/// On a disconnected graph the edges form a spanning forest. Edge `i` belongs
+/// to the tree `component_ids[i]`, labeled by its smallest node ID, whose total
+/// weight is `component_weights[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MstResult {
     pub src_nodes: Vec<i64>,
     pub dst_nodes: Vec<i64>,
     pub weights: Vec<f64>,
     pub total_weight: f64,
+    pub component_ids: Vec<i64>,
+    pub component_weights: Vec<f64>,
+}
+
+impl MstResult {
+    /// Build a result from spanning forest edges, labeling each edge with its tree.
+    pub(crate) fn from_edges(src_nodes: Vec<i64>, dst_nodes: Vec<i64>, weights: Vec<f64>) -> Self {
+        let nodes = NodeIndex::new(&src_nodes, &dst_nodes);
+        let labels = &nodes.node_ids;
+        let mut parent: Vec<usize> = (0..nodes.len()).collect();
+        for (&s, &d) in src_nodes.iter().zip(&dst_nodes) {
+            let (rs, rd) = (
+                find_root(&mut parent, nodes.of(s)),
+                find_root(&mut parent, nodes.of(d)),
+            );
+            if rs != rd {
+                // Keep the smallest node ID as the label of the merged tree
+                let (keep, drop) = if labels[rs] <= labels[rd] {
+                    (rs, rd)
+                } else {
+                    (rd, rs)
+                };
+                parent[drop] = keep;
+            }
+        }
+
+        let mut totals: HashMap<i64, f64> = HashMap::new();
+        let component_ids: Vec<i64> = src_nodes
+            .iter()
+            .map(|&s| labels[find_root(&mut parent, nodes.of(s))])
+            .collect();
+        for (&c, &w) in component_ids.iter().zip(&weights) {
+            *totals.entry(c).or_insert(0.0) += w;
+        }
+        MstResult {
+            total_weight: weights.iter().sum(),
+            component_weights: component_ids.iter().map(|c| totals[c]).collect(),
+            component_ids,
+            src_nodes,
+            dst_nodes,
+            weights,
+        }
+    }
+}
+
+pub(crate) fn find_root(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
 }
 
 /// Compute Prim's MST.
@@ -30,29 +88,12 @@ pub fn compute_prim_mst(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<Mst
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(weights[i]));
-    }
+    let built = GraphBuilder::new(src, dst)?
+        .build::<Graph<i64, OrderedFloat<f64>>>(|i| OrderedFloat(weights[i]));
+    let reverse_map = built.reverse_map();
 
-    let (mst_edges, total_weight) =
-        prim_mst(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let (mst_edges, _) =
+        prim_mst(&built.graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
     let mut result_src = Vec::with_capacity(mst_edges.len());
     let mut result_dst = Vec::with_capacity(mst_edges.len());
@@ -66,12 +107,11 @@ pub fn compute_prim_mst(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<Mst
         }
     }
 
-    Ok(MstResult {
-        src_nodes: result_src,
-        dst_nodes: result_dst,
-        weights: result_weights,
-        total_weight: total_weight.into_inner(),
-    })
+    Ok(MstResult::from_edges(
+        result_src,
+        result_dst,
+        result_weights,
+    ))
 }
 
 /// Compute Kruskal's MST.
@@ -87,29 +127,12 @@ pub fn compute_kruskal_mst(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?
+        .build::<Graph<i64, OrderedFloat<f64>>>(|i| OrderedFloat(weights[i]));
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(weights[i]));
-    }
-
-    let (mst_edges, total_weight) =
-        kruskal_mst(&graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let (mst_edges, _) =
+        kruskal_mst(&built.graph).map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
     let mut result_src = Vec::with_capacity(mst_edges.len());
     let mut result_dst = Vec::with_capacity(mst_edges.len());
@@ -123,12 +146,73 @@ pub fn compute_kruskal_mst(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<
         }
     }
 
-    Ok(MstResult {
-        src_nodes: result_src,
-        dst_nodes: result_dst,
-        weights: result_weights,
-        total_weight: total_weight.into_inner(),
-    })
+    Ok(MstResult::from_edges(
+        result_src,
+        result_dst,
+        result_weights,
+    ))
+}
+
+/// Compute a spanning tree where no node has more than `max_degree` tree edges.
+///
+/// Finding the cheapest such tree is NP-hard, so this uses a greedy variant of
+/// Kruskal's algorithm: edges are taken in order of weight (ties by input
+/// order) and added when they join two trees without pushing either endpoint
+/// past the budget. Edges are undirected and weights must be finite. When the
+/// budget leaves no way to join some trees, the result is a forest, and
+/// `component_ids` shows which nodes stayed apart.
+pub fn compute_dcmst(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    max_degree: usize,
+) -> Result<MstResult> {
+    if src.len() != dst.len() || src.len() != weights.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and weights arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if max_degree == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "max_degree must be at least 1".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite()) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite, got {}",
+            w
+        )));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let mut order: Vec<usize> = (0..src.len()).collect();
+    order.sort_by(|&a, &b| weights[a].total_cmp(&weights[b]).then(a.cmp(&b)));
+
+    let mut parent: Vec<usize> = (0..nodes.len()).collect();
+    let mut degree = vec![0usize; nodes.len()];
+    let (mut tree_src, mut tree_dst, mut tree_weights) = (Vec::new(), Vec::new(), Vec::new());
+    for e in order {
+        let (u, v) = (nodes.of(src[e]), nodes.of(dst[e]));
+        if degree[u] >= max_degree || degree[v] >= max_degree {
+            continue;
+        }
+        let (ru, rv) = (find_root(&mut parent, u), find_root(&mut parent, v));
+        if ru == rv {
+            continue;
+        }
+        parent[ru] = rv;
+        degree[u] += 1;
+        degree[v] += 1;
+        tree_src.push(src[e]);
+        tree_dst.push(dst[e]);
+        tree_weights.push(weights[e]);
+    }
+    Ok(MstResult::from_edges(tree_src, tree_dst, tree_weights))
 }
 
 #[cfg(test)]
@@ -162,6 +246,61 @@ mod tests {
         assert!((result.total_weight - 4.5).abs() < 0.01);
     }
 
+    #[test]
+    fn test_mst_components_on_forest() {
+        // Two trees: 1-2-3 and 7-5
+        let src = vec![2, 1, 1, 7];
+        let dst = vec![3, 2, 3, 5];
+        let weights = vec![2.0, 1.0, 4.0, 3.0];
+
+        for result in [
+            compute_kruskal_mst(&src, &dst, &weights).unwrap(),
+            compute_prim_mst(&src, &dst, &weights).unwrap(),
+        ] {
+            for i in 0..result.src_nodes.len() {
+                let (s, c, w) = (
+                    result.src_nodes[i],
+                    result.component_ids[i],
+                    result.component_weights[i],
+                );
+                if s == 5 || s == 7 {
+                    assert_eq!((c, w), (5, 3.0));
+                } else {
+                    assert_eq!((c, w), (1, 3.0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dcmst_respects_degree_budget() {
+        // Star around 1 with cheap spokes, plus a more expensive path 2-3-4-5
+        let src = vec![1, 1, 1, 1, 2, 3, 4];
+        let dst = vec![2, 3, 4, 5, 3, 4, 5];
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0];
+
+        let result = compute_dcmst(&src, &dst, &weights, 2).unwrap();
+        assert_eq!(result.src_nodes.len(), 4);
+        assert!((result.total_weight - 6.0).abs() < 1e-12);
+        let mut degree: HashMap<i64, usize> = HashMap::new();
+        for (&s, &d) in result.src_nodes.iter().zip(&result.dst_nodes) {
+            *degree.entry(s).or_insert(0) += 1;
+            *degree.entry(d).or_insert(0) += 1;
+        }
+        assert!(degree.values().all(|&d| d <= 2));
+
+        // A large budget gives the plain MST
+        let unconstrained = compute_dcmst(&src, &dst, &weights, 10).unwrap();
+        assert!((unconstrained.total_weight - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_dcmst_invalid_input() {
+        assert!(compute_dcmst(&[1], &[2], &[1.0], 0).is_err());
+        assert!(compute_dcmst(&[], &[], &[], 2).is_err());
+        assert!(compute_dcmst(&[1], &[2], &[f64::INFINITY], 2).is_err());
+    }
+
     #[test]
     fn test_mst_empty_graph() {
         let src: Vec<i64> = vec![];
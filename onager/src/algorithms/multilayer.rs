@@ -0,0 +1,312 @@
+//! Multilayer network module.
+//!
+//! Multiplex degree, layer overlap, and multiplex PageRank for graphs whose
+//! edges carry a layer ID, for example call, message, and payment layers over
+//! the same set of people.
+
+use super::weighted_rank::compute_weighted_pagerank;
+use crate::error::{OnagerError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+fn check_layered_edges(src: &[i64], dst: &[i64], layers: &[i64]) -> Result<()> {
+    if src.len() != dst.len() || src.len() != layers.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and layers arrays must have same length".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Result of multiplex degree computation.
+///
+/// `node_ids`, `degrees`, `layer_counts`, and `participation` hold one row per
+/// node. The `layer_` fields hold one row per node and layer it appears in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiplexDegreeResult {
+    pub node_ids: Vec<i64>,
+    pub degrees: Vec<i64>,
+    pub layer_counts: Vec<i64>,
+    pub participation: Vec<f64>,
+    pub layer_node_ids: Vec<i64>,
+    pub layers: Vec<i64>,
+    pub layer_degrees: Vec<i64>,
+}
+
+/// Compute the degree of every node in each layer and across all layers.
+///
+/// Edges are counted as undirected, so each edge adds one to the degree of
+/// both endpoints in its layer. The participation coefficient measures how
+/// evenly a node's edges are spread over the layers: 0 when they all sit in
+/// one layer, and 1 when every layer holds the same share. Rows are sorted by
+/// node and then by layer.
+pub fn compute_multiplex_degree(
+    src: &[i64],
+    dst: &[i64],
+    layers: &[i64],
+) -> Result<MultiplexDegreeResult> {
+    check_layered_edges(src, dst, layers)?;
+
+    let mut per_layer: BTreeMap<(i64, i64), i64> = BTreeMap::new();
+    for i in 0..src.len() {
+        *per_layer.entry((src[i], layers[i])).or_insert(0) += 1;
+        *per_layer.entry((dst[i], layers[i])).or_insert(0) += 1;
+    }
+    let layer_total = layers.iter().collect::<HashSet<_>>().len() as f64;
+
+    let mut result = MultiplexDegreeResult {
+        node_ids: Vec::new(),
+        degrees: Vec::new(),
+        layer_counts: Vec::new(),
+        participation: Vec::new(),
+        layer_node_ids: Vec::with_capacity(per_layer.len()),
+        layers: Vec::with_capacity(per_layer.len()),
+        layer_degrees: Vec::with_capacity(per_layer.len()),
+    };
+    for (&(node, layer), &degree) in &per_layer {
+        result.layer_node_ids.push(node);
+        result.layers.push(layer);
+        result.layer_degrees.push(degree);
+    }
+
+    // Rows for one node are contiguous because the map is ordered by node first
+    let mut start = 0;
+    while start < result.layer_node_ids.len() {
+        let node = result.layer_node_ids[start];
+        let mut end = start;
+        while end < result.layer_node_ids.len() && result.layer_node_ids[end] == node {
+            end += 1;
+        }
+        let counts = &result.layer_degrees[start..end];
+        let total: i64 = counts.iter().sum();
+        let participation = if layer_total > 1.0 {
+            let concentration: f64 = counts
+                .iter()
+                .map(|&k| (k as f64 / total as f64).powi(2))
+                .sum();
+            layer_total / (layer_total - 1.0) * (1.0 - concentration)
+        } else {
+            0.0
+        };
+        result.node_ids.push(node);
+        result.degrees.push(total);
+        result.layer_counts.push((end - start) as i64);
+        result.participation.push(participation);
+        start = end;
+    }
+    Ok(result)
+}
+
+/// Result of layer overlap computation, one row per pair of layers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerOverlapResult {
+    pub layer_a: Vec<i64>,
+    pub layer_b: Vec<i64>,
+    pub shared_edges: Vec<i64>,
+    pub jaccard: Vec<f64>,
+}
+
+/// Compare the edge sets of every pair of layers.
+///
+/// For each pair with `layer_a < layer_b`, reports how many distinct edges
+/// appear in both layers and the Jaccard similarity of the two edge sets.
+/// Repeated edges within a layer count once. When `directed` is false, `(1, 2)`
+/// and `(2, 1)` are the same edge.
+pub fn compute_layer_overlap(
+    src: &[i64],
+    dst: &[i64],
+    layers: &[i64],
+    directed: bool,
+) -> Result<LayerOverlapResult> {
+    check_layered_edges(src, dst, layers)?;
+
+    let mut edge_sets: BTreeMap<i64, HashSet<(i64, i64)>> = BTreeMap::new();
+    for i in 0..src.len() {
+        let edge = if directed || src[i] <= dst[i] {
+            (src[i], dst[i])
+        } else {
+            (dst[i], src[i])
+        };
+        edge_sets.entry(layers[i]).or_default().insert(edge);
+    }
+
+    let sets: Vec<(i64, HashSet<(i64, i64)>)> = edge_sets.into_iter().collect();
+    let mut result = LayerOverlapResult {
+        layer_a: Vec::new(),
+        layer_b: Vec::new(),
+        shared_edges: Vec::new(),
+        jaccard: Vec::new(),
+    };
+    for (i, (layer_a, edges_a)) in sets.iter().enumerate() {
+        for (layer_b, edges_b) in &sets[i + 1..] {
+            let shared = edges_a.intersection(edges_b).count();
+            let union = edges_a.len() + edges_b.len() - shared;
+            result.layer_a.push(*layer_a);
+            result.layer_b.push(*layer_b);
+            result.shared_edges.push(shared as i64);
+            result.jaccard.push(shared as f64 / union as f64);
+        }
+    }
+    Ok(result)
+}
+
+/// Result of multiplex PageRank computation.
+///
+/// `node_ids` and `ranks` hold the aggregate rank of every node. The `layer_`
+/// fields hold one row per node and layer it appears in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiplexPageRankResult {
+    pub node_ids: Vec<i64>,
+    pub ranks: Vec<f64>,
+    pub layer_node_ids: Vec<i64>,
+    pub layers: Vec<i64>,
+    pub layer_ranks: Vec<f64>,
+}
+
+/// Compute PageRank within each layer and over the overlay of all layers.
+///
+/// Layer ranks come from PageRank on the edges of that layer alone and sum to
+/// 1 within each layer. The aggregate rank comes from PageRank on the overlay
+/// graph, where an edge present in several layers carries weight equal to the
+/// number of layers, so ties that span channels pass more rank. Rows are
+/// sorted by node and then by layer.
+pub fn compute_multiplex_pagerank(
+    src: &[i64],
+    dst: &[i64],
+    layers: &[i64],
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+) -> Result<MultiplexPageRankResult> {
+    check_layered_edges(src, dst, layers)?;
+    let mut result = MultiplexPageRankResult {
+        node_ids: Vec::new(),
+        ranks: Vec::new(),
+        layer_node_ids: Vec::new(),
+        layers: Vec::new(),
+        layer_ranks: Vec::new(),
+    };
+    if src.is_empty() {
+        return Ok(result);
+    }
+
+    let overlay = compute_weighted_pagerank(
+        src,
+        dst,
+        &vec![1.0; src.len()],
+        damping,
+        iterations,
+        directed,
+    )?;
+    let mut ranked: Vec<(i64, f64)> = overlay.node_ids.into_iter().zip(overlay.ranks).collect();
+    ranked.sort_by_key(|&(node, _)| node);
+    (result.node_ids, result.ranks) = ranked.into_iter().unzip();
+
+    let mut by_layer: BTreeMap<i64, (Vec<i64>, Vec<i64>)> = BTreeMap::new();
+    for i in 0..src.len() {
+        let (layer_src, layer_dst) = by_layer.entry(layers[i]).or_default();
+        layer_src.push(src[i]);
+        layer_dst.push(dst[i]);
+    }
+    let mut rows: Vec<(i64, i64, f64)> = Vec::new();
+    for (layer, (layer_src, layer_dst)) in &by_layer {
+        let ranks = compute_weighted_pagerank(
+            layer_src,
+            layer_dst,
+            &vec![1.0; layer_src.len()],
+            damping,
+            iterations,
+            directed,
+        )?;
+        rows.extend(
+            ranks
+                .node_ids
+                .into_iter()
+                .zip(ranks.ranks)
+                .map(|(node, rank)| (node, *layer, rank)),
+        );
+    }
+    rows.sort_by_key(|&(node, layer, _)| (node, layer));
+    for (node, layer, rank) in rows {
+        result.layer_node_ids.push(node);
+        result.layers.push(layer);
+        result.layer_ranks.push(rank);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Layer 1 is a triangle 1-2-3, layer 2 repeats 1-2 and adds 3-4
+    fn two_layers() -> (Vec<i64>, Vec<i64>, Vec<i64>) {
+        (
+            vec![1, 2, 3, 1, 3],
+            vec![2, 3, 1, 2, 4],
+            vec![1, 1, 1, 2, 2],
+        )
+    }
+
+    #[test]
+    fn test_multiplex_degree() {
+        let (src, dst, layers) = two_layers();
+        let result = compute_multiplex_degree(&src, &dst, &layers).unwrap();
+
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert_eq!(result.degrees, vec![3, 3, 3, 1]);
+        assert_eq!(result.layer_counts, vec![2, 2, 2, 1]);
+        // Node 4 only appears in layer 2
+        assert_eq!(result.participation[3], 0.0);
+        // Node 1 has degree 2 in layer 1 and 1 in layer 2: 2 * (1 - 5/9)
+        assert!((result.participation[0] - 8.0 / 9.0).abs() < 1e-12);
+
+        assert_eq!(result.layer_node_ids, vec![1, 1, 2, 2, 3, 3, 4]);
+        assert_eq!(result.layers, vec![1, 2, 1, 2, 1, 2, 2]);
+        assert_eq!(result.layer_degrees, vec![2, 1, 2, 1, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_layer_overlap() {
+        let (src, dst, layers) = two_layers();
+        let result = compute_layer_overlap(&src, &dst, &layers, false).unwrap();
+
+        assert_eq!(result.layer_a, vec![1]);
+        assert_eq!(result.layer_b, vec![2]);
+        assert_eq!(result.shared_edges, vec![1]);
+        assert!((result.jaccard[0] - 0.25).abs() < 1e-12);
+
+        // Reversing the shared edge keeps it shared only when undirected
+        let result = compute_layer_overlap(&[1, 2], &[2, 1], &[1, 2], true).unwrap();
+        assert_eq!(result.shared_edges, vec![0]);
+        let result = compute_layer_overlap(&[1, 2], &[2, 1], &[1, 2], false).unwrap();
+        assert_eq!(result.shared_edges, vec![1]);
+    }
+
+    #[test]
+    fn test_multiplex_pagerank() {
+        let (src, dst, layers) = two_layers();
+        let result = compute_multiplex_pagerank(&src, &dst, &layers, 0.85, 100, false).unwrap();
+
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        let total: f64 = result.ranks.iter().sum();
+        assert!((total - 1.0).abs() < 1e-6);
+
+        assert_eq!(result.layer_node_ids, vec![1, 1, 2, 2, 3, 3, 4]);
+        for layer in [1, 2] {
+            let total: f64 = (0..result.layers.len())
+                .filter(|&i| result.layers[i] == layer)
+                .map(|i| result.layer_ranks[i])
+                .sum();
+            assert!((total - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_multiplex_empty_and_mismatched() {
+        let result = compute_multiplex_pagerank(&[], &[], &[], 0.85, 100, true).unwrap();
+        assert!(result.node_ids.is_empty());
+        assert!(compute_multiplex_degree(&[1], &[2], &[]).is_err());
+        assert!(compute_layer_overlap(&[1], &[2], &[1, 2], true).is_err());
+    }
+}
@@ -1,19 +1,24 @@
 //! Parallel algorithms module.
 //!
-//! Parallel PageRank, BFS, shortest paths, connected components, clustering, triangles.
+//! Parallel PageRank, BFS, shortest paths, connected components, clustering, triangles,
+//! and Borůvka minimum spanning trees.
 
-use graphina::core::types::{Digraph, Graph, NodeId};
+use graphina::core::types::Graph;
 use graphina::parallel::{
     bfs_parallel, clustering_coefficients_parallel, connected_components_parallel,
     pagerank_parallel, shortest_paths_parallel, triangles_parallel,
 };
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::algorithms::centrality::PageRankResult;
 use crate::algorithms::community::ConnectedComponentsResult;
 use crate::algorithms::metrics::TriangleResult;
+use crate::algorithms::mst::MstResult;
 use crate::algorithms::traversal::BfsResult;
+use crate::builder::{graph_from_edges, EdgeGraph, GraphBuilder, NodeIndex};
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Compute PageRank using parallel algorithm.
 pub fn compute_pagerank_parallel(
@@ -29,94 +34,25 @@ pub fn compute_pagerank_parallel(
             "src and dst arrays must have same length".to_string(),
         ));
     }
-    if !weights.is_empty() && weights.len() != src.len() {
-        return Err(OnagerError::InvalidArgument(
-            "weights must be empty or same length as edges".to_string(),
-        ));
-    }
     if src.is_empty() {
         return Err(OnagerError::InvalidArgument(
             "Cannot compute on empty graph".to_string(),
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-
-    if directed {
-        let mut graph: Digraph<i64, f64> = Digraph::new();
-        for &node in src.iter().chain(dst.iter()) {
-            if !node_set.contains_key(&node) {
-                let id = graph.add_node(node);
-                node_set.insert(node, id);
-            }
-        }
-        for i in 0..src.len() {
-            let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-            })?;
-            let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!(
-                    "Destination node {} not found in graph",
-                    dst[i]
-                ))
-            })?;
-            let weight = if weights.is_empty() { 1.0 } else { weights[i] };
-            graph.add_edge(src_id, dst_id, weight);
-        }
-
-        let ranks = pagerank_parallel(&graph, damping, iterations, 1e-6, None);
-        let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
-
-        let mut node_ids = Vec::with_capacity(ranks.len());
-        let mut rank_values = Vec::with_capacity(ranks.len());
-        for (node_id, rank) in ranks {
-            if let Some(&ext_id) = reverse_map.get(&node_id) {
-                node_ids.push(ext_id);
-                rank_values.push(rank);
-            }
-        }
-        Ok(PageRankResult {
-            node_ids,
-            ranks: rank_values,
-        })
-    } else {
-        let mut graph: Graph<i64, f64> = Graph::new();
-        for &node in src.iter().chain(dst.iter()) {
-            if !node_set.contains_key(&node) {
-                let id = graph.add_node(node);
-                node_set.insert(node, id);
-            }
+    let (node_ids, ranks) = match graph_from_edges(src, dst, weights, directed)? {
+        EdgeGraph::Directed(built) => {
+            let ranks = pagerank_parallel(&built.graph, damping, iterations, 1e-6, None);
+            let values = built.scores(|id| ranks.get(&id).copied());
+            (built.node_ids, values)
         }
-        for i in 0..src.len() {
-            let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-            })?;
-            let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-                OnagerError::InvalidArgument(format!(
-                    "Destination node {} not found in graph",
-                    dst[i]
-                ))
-            })?;
-            let weight = if weights.is_empty() { 1.0 } else { weights[i] };
-            graph.add_edge(src_id, dst_id, weight);
+        EdgeGraph::Undirected(built) => {
+            let ranks = pagerank_parallel(&built.graph, damping, iterations, 1e-6, None);
+            let values = built.scores(|id| ranks.get(&id).copied());
+            (built.node_ids, values)
         }
-
-        let ranks = pagerank_parallel(&graph, damping, iterations, 1e-6, None);
-        let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
-
-        let mut node_ids = Vec::with_capacity(ranks.len());
-        let mut rank_values = Vec::with_capacity(ranks.len());
-        for (node_id, rank) in ranks {
-            if let Some(&ext_id) = reverse_map.get(&node_id) {
-                node_ids.push(ext_id);
-                rank_values.push(rank);
-            }
-        }
-        Ok(PageRankResult {
-            node_ids,
-            ranks: rank_values,
-        })
-    }
+    };
+    Ok(PageRankResult { node_ids, ranks })
 }
 
 /// Compute parallel BFS traversal from a single source.
@@ -132,33 +68,15 @@ pub fn compute_bfs_parallel(src: &[i64], dst: &[i64], source: i64) -> Result<Bfs
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let source_id = node_set
-        .get(&source)
+    let source_id = built
+        .graph_id(source)
         .ok_or(OnagerError::NodeNotFound(source))?;
 
     // bfs_parallel takes a slice of sources - we pass a single source
-    let results = bfs_parallel(&graph, &[*source_id]);
+    let results = bfs_parallel(&built.graph, &[source_id]);
     // Get the first (and only) result
     let visit_order = results.into_iter().next().unwrap_or_default();
 
@@ -174,6 +92,7 @@ pub fn compute_bfs_parallel(src: &[i64], dst: &[i64], source: i64) -> Result<Bfs
 }
 
 /// Result of parallel shortest paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShortestPathsParallelResult {
     pub node_ids: Vec<i64>,
     pub distances: Vec<f64>,
@@ -196,33 +115,15 @@ pub fn compute_shortest_paths_parallel(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let source_id = node_set
-        .get(&source)
+    let source_id = built
+        .graph_id(source)
         .ok_or(OnagerError::NodeNotFound(source))?;
 
     // shortest_paths_parallel takes a slice of sources, returns Vec<HashMap<NodeId, usize>>
-    let results = shortest_paths_parallel(&graph, &[*source_id]);
+    let results = shortest_paths_parallel(&built.graph, &[source_id]);
     let distances_map = results.into_iter().next().unwrap_or_default();
 
     let mut node_ids = Vec::with_capacity(distances_map.len());
@@ -252,29 +153,11 @@ pub fn compute_components_parallel(src: &[i64], dst: &[i64]) -> Result<Connected
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
     // connected_components_parallel returns HashMap<NodeId, usize>
-    let components = connected_components_parallel(&graph);
+    let components = connected_components_parallel(&built.graph);
 
     let mut node_ids = Vec::with_capacity(components.len());
     let mut component_ids = Vec::with_capacity(components.len());
@@ -291,6 +174,7 @@ pub fn compute_components_parallel(src: &[i64], dst: &[i64]) -> Result<Connected
 }
 
 /// Result of parallel clustering coefficients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClusteringParallelResult {
     pub node_ids: Vec<i64>,
     pub coefficients: Vec<f64>,
@@ -309,28 +193,10 @@ pub fn compute_clustering_parallel(src: &[i64], dst: &[i64]) -> Result<Clusterin
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let coefficients = clustering_coefficients_parallel(&graph);
+    let coefficients = clustering_coefficients_parallel(&built.graph);
 
     let mut node_ids = Vec::with_capacity(coefficients.len());
     let mut coef_values = Vec::with_capacity(coefficients.len());
@@ -359,28 +225,10 @@ pub fn compute_triangles_parallel(src: &[i64], dst: &[i64]) -> Result<TriangleRe
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let triangles = triangles_parallel(&graph);
+    let triangles = triangles_parallel(&built.graph);
 
     let mut node_ids = Vec::with_capacity(triangles.len());
     let mut triangle_counts = Vec::with_capacity(triangles.len());
@@ -396,6 +244,109 @@ pub fn compute_triangles_parallel(src: &[i64], dst: &[i64]) -> Result<TriangleRe
     })
 }
 
+fn find_root(parent: &mut [usize], mut x: usize) -> usize {
+    while parent[x] != x {
+        parent[x] = parent[parent[x]];
+        x = parent[x];
+    }
+    x
+}
+
+/// Compute a minimum spanning tree with a parallel version of Borůvka's algorithm.
+///
+/// Every round finds the lightest edge leaving each component in parallel and
+/// adds all of them at once, so the number of components at least halves per
+/// round. Ties between equal weights are broken by input order, which keeps the
+/// result deterministic. Edges are undirected and weights must be finite. On a
+/// disconnected graph the result is a minimum spanning forest.
+pub fn compute_boruvka_mst(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<MstResult> {
+    if src.len() != dst.len() || src.len() != weights.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and weights arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite()) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite, got {}",
+            w
+        )));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let n = nodes.len();
+    let edges: Vec<(usize, usize)> = src
+        .iter()
+        .zip(dst)
+        .map(|(&s, &d)| (nodes.of(s), nodes.of(d)))
+        .collect();
+    let lighter = |a: usize, b: usize| weights[a].total_cmp(&weights[b]).then(a.cmp(&b)).is_lt();
+
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut component: Vec<usize> = (0..n).collect();
+    let mut candidates: Vec<usize> = (0..edges.len())
+        .filter(|&e| edges[e].0 != edges[e].1)
+        .collect();
+    let mut tree_edges: Vec<usize> = Vec::with_capacity(n.saturating_sub(1));
+    while !candidates.is_empty() {
+        // Lightest edge leaving each component, or usize::MAX for none
+        let lightest: Vec<AtomicUsize> = (0..n).map(|_| AtomicUsize::new(usize::MAX)).collect();
+        candidates.par_iter().for_each(|&e| {
+            let (u, v) = edges[e];
+            for c in [component[u], component[v]] {
+                let slot = &lightest[c];
+                let mut current = slot.load(Ordering::Relaxed);
+                while current == usize::MAX || lighter(e, current) {
+                    match slot.compare_exchange_weak(
+                        current,
+                        e,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    ) {
+                        Ok(_) => break,
+                        Err(seen) => current = seen,
+                    }
+                }
+            }
+        });
+
+        for slot in &lightest {
+            let e = slot.load(Ordering::Relaxed);
+            if e == usize::MAX {
+                continue;
+            }
+            let (ru, rv) = (
+                find_root(&mut parent, edges[e].0),
+                find_root(&mut parent, edges[e].1),
+            );
+            // Two components can pick the same edge, so skip it the second time
+            if ru != rv {
+                parent[ru] = rv;
+                tree_edges.push(e);
+            }
+        }
+
+        for (v, c) in component.iter_mut().enumerate() {
+            *c = find_root(&mut parent, v);
+        }
+        candidates = candidates
+            .into_par_iter()
+            .filter(|&e| component[edges[e].0] != component[edges[e].1])
+            .collect();
+    }
+
+    tree_edges.sort_unstable();
+    Ok(MstResult::from_edges(
+        tree_edges.iter().map(|&e| src[e]).collect(),
+        tree_edges.iter().map(|&e| dst[e]).collect(),
+        tree_edges.iter().map(|&e| weights[e]).collect(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -544,4 +495,35 @@ mod tests {
     fn test_mismatched_weights_error() {
         assert!(compute_pagerank_parallel(&[1, 2], &[2, 3], &[1.0], 0.85, 100, false).is_err());
     }
+
+    #[test]
+    fn test_boruvka_mst() {
+        // Triangle with one extra edge: 1-2 (1.0), 2-3 (2.0), 1-3 (4.0), 3-4 (1.5)
+        let result =
+            compute_boruvka_mst(&[1, 2, 1, 3], &[2, 3, 3, 4], &[1.0, 2.0, 4.0, 1.5]).unwrap();
+        assert_eq!(result.src_nodes, vec![1, 2, 3]);
+        assert_eq!(result.dst_nodes, vec![2, 3, 4]);
+        assert!((result.total_weight - 4.5).abs() < 1e-12);
+        assert_eq!(result.component_ids, vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_boruvka_mst_forest_and_ties() {
+        // Square 1-2-3-4 with equal weights, plus a separate edge 8-9
+        let src = vec![1, 2, 3, 4, 8, 9];
+        let dst = vec![2, 3, 4, 1, 9, 9];
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 2.0, 0.5];
+        let result = compute_boruvka_mst(&src, &dst, &weights).unwrap();
+        // Ties go to the earlier edges, and the self-loop is ignored
+        assert_eq!(result.src_nodes, vec![1, 2, 3, 8]);
+        assert_eq!(result.component_ids, vec![1, 1, 1, 8]);
+        assert_eq!(result.component_weights, vec![3.0, 3.0, 3.0, 2.0]);
+    }
+
+    #[test]
+    fn test_boruvka_mst_invalid_input() {
+        assert!(compute_boruvka_mst(&[], &[], &[]).is_err());
+        assert!(compute_boruvka_mst(&[1], &[2], &[]).is_err());
+        assert!(compute_boruvka_mst(&[1], &[2], &[f64::NAN]).is_err());
+    }
 }
@@ -0,0 +1,397 @@
+//! Minimal Parquet writer for spilled results.
+//!
+//! Writes files with two `INT64` columns and one `DOUBLE` column, all
+//! required, with uncompressed PLAIN pages and min/max statistics. Rows are
+//! buffered one row group at a time and the footer is written at the end, so
+//! the output never needs to be seeked and memory stays bounded.
+
+use crate::error::Result;
+use std::io::Write;
+
+/// Rows per row group, matching the DuckDB row group size.
+const ROW_GROUP_ROWS: usize = 122_880;
+
+const MAGIC: &[u8] = b"PAR1";
+
+// Parquet enum values
+const TYPE_INT64: i32 = 2;
+const TYPE_DOUBLE: i32 = 5;
+const REPETITION_REQUIRED: i32 = 0;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const PAGE_DATA: i32 = 0;
+const CODEC_UNCOMPRESSED: i32 = 0;
+
+// Thrift compact protocol field types
+const CT_I32: u8 = 5;
+const CT_I64: u8 = 6;
+const CT_BINARY: u8 = 8;
+const CT_LIST: u8 = 9;
+const CT_STRUCT: u8 = 12;
+
+/// Encoder for the Thrift compact protocol, which Parquet uses for metadata.
+#[derive(Default)]
+struct Thrift {
+    buf: Vec<u8>,
+    last_field: Vec<i16>,
+}
+
+impl Thrift {
+    fn varint(&mut self, mut v: u64) {
+        while v >= 0x80 {
+            self.buf.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        self.buf.push(v as u8);
+    }
+
+    fn zigzag(&mut self, v: i64) {
+        self.varint(((v << 1) ^ (v >> 63)) as u64);
+    }
+
+    fn field(&mut self, id: i16, kind: u8) {
+        let last = self.last_field.last_mut().expect("field outside a struct");
+        let delta = id - std::mem::replace(last, id);
+        if (1..=15).contains(&delta) {
+            self.buf.push(((delta as u8) << 4) | kind);
+        } else {
+            self.buf.push(kind);
+            self.zigzag(i64::from(id));
+        }
+    }
+
+    fn begin(&mut self) {
+        self.last_field.push(0);
+    }
+
+    fn end(&mut self) {
+        self.buf.push(0);
+        self.last_field.pop();
+    }
+
+    fn i32_field(&mut self, id: i16, v: i32) {
+        self.field(id, CT_I32);
+        self.zigzag(i64::from(v));
+    }
+
+    fn i64_field(&mut self, id: i16, v: i64) {
+        self.field(id, CT_I64);
+        self.zigzag(v);
+    }
+
+    fn binary_field(&mut self, id: i16, v: &[u8]) {
+        self.field(id, CT_BINARY);
+        self.varint(v.len() as u64);
+        self.buf.extend_from_slice(v);
+    }
+
+    fn struct_field(&mut self, id: i16) {
+        self.field(id, CT_STRUCT);
+        self.begin();
+    }
+
+    fn list_field(&mut self, id: i16, kind: u8, len: usize) {
+        self.field(id, CT_LIST);
+        if len < 15 {
+            self.buf.push(((len as u8) << 4) | kind);
+        } else {
+            self.buf.push(0xF0 | kind);
+            self.varint(len as u64);
+        }
+    }
+}
+
+/// Metadata of one written column chunk.
+struct ChunkMeta {
+    offset: u64,
+    size: u64,
+    min: Vec<u8>,
+    max: Vec<u8>,
+}
+
+/// Buffered values of one column in the current row group.
+#[derive(Default)]
+struct Column {
+    data: Vec<u8>,
+    min: Option<[u8; 8]>,
+    max: Option<[u8; 8]>,
+}
+
+impl Column {
+    fn push_i64(&mut self, v: i64) {
+        self.data.extend_from_slice(&v.to_le_bytes());
+        let below = self.min.is_none_or(|m| v < i64::from_le_bytes(m));
+        let above = self.max.is_none_or(|m| v > i64::from_le_bytes(m));
+        if below {
+            self.min = Some(v.to_le_bytes());
+        }
+        if above {
+            self.max = Some(v.to_le_bytes());
+        }
+    }
+
+    fn push_f64(&mut self, v: f64) {
+        self.data.extend_from_slice(&v.to_le_bytes());
+        if v.is_nan() {
+            return;
+        }
+        // Zeros are widened to -0.0 and +0.0 so either sign stays in range
+        let low = if v == 0.0 { -0.0 } else { v };
+        let high = if v == 0.0 { 0.0 } else { v };
+        if self.min.is_none_or(|m| low < f64::from_le_bytes(m)) {
+            self.min = Some(low.to_le_bytes());
+        }
+        if self.max.is_none_or(|m| high > f64::from_le_bytes(m)) {
+            self.max = Some(high.to_le_bytes());
+        }
+    }
+}
+
+/// Streaming writer for a Parquet file of `(INT64, INT64, DOUBLE)` rows.
+pub(crate) struct ParquetWriter<W: Write> {
+    out: W,
+    names: [String; 3],
+    offset: u64,
+    columns: [Column; 3],
+    buffered: usize,
+    row_groups: Vec<(usize, Vec<ChunkMeta>)>,
+    row_count: usize,
+}
+
+impl<W: Write> ParquetWriter<W> {
+    /// Starts a file with the given column names.
+    pub(crate) fn new(mut out: W, names: [&str; 3]) -> Result<Self> {
+        out.write_all(MAGIC)?;
+        Ok(ParquetWriter {
+            out,
+            names: names.map(str::to_string),
+            offset: MAGIC.len() as u64,
+            columns: Default::default(),
+            buffered: 0,
+            row_groups: Vec::new(),
+            row_count: 0,
+        })
+    }
+
+    /// Appends one row.
+    pub(crate) fn write_row(&mut self, a: i64, b: i64, value: f64) -> Result<()> {
+        self.columns[0].push_i64(a);
+        self.columns[1].push_i64(b);
+        self.columns[2].push_f64(value);
+        self.buffered += 1;
+        self.row_count += 1;
+        if self.buffered == ROW_GROUP_ROWS {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the buffered rows as one row group with one page per column.
+    fn flush_row_group(&mut self) -> Result<()> {
+        if self.buffered == 0 {
+            return Ok(());
+        }
+        let rows = self.buffered;
+        let mut chunks = Vec::with_capacity(self.columns.len());
+        for column in &mut self.columns {
+            let mut header = Thrift::default();
+            header.begin();
+            header.i32_field(1, PAGE_DATA);
+            header.i32_field(2, column.data.len() as i32);
+            header.i32_field(3, column.data.len() as i32);
+            header.struct_field(5);
+            header.i32_field(1, rows as i32);
+            header.i32_field(2, ENCODING_PLAIN);
+            header.i32_field(3, ENCODING_RLE);
+            header.i32_field(4, ENCODING_RLE);
+            header.end();
+            header.end();
+
+            self.out.write_all(&header.buf)?;
+            self.out.write_all(&column.data)?;
+            let size = (header.buf.len() + column.data.len()) as u64;
+            chunks.push(ChunkMeta {
+                offset: self.offset,
+                size,
+                min: column.min.map(|m| m.to_vec()).unwrap_or_default(),
+                max: column.max.map(|m| m.to_vec()).unwrap_or_default(),
+            });
+            self.offset += size;
+            *column = Column::default();
+        }
+        self.row_groups.push((rows, chunks));
+        self.buffered = 0;
+        Ok(())
+    }
+
+    /// Writes the last row group and the footer, and returns the number of rows.
+    pub(crate) fn finish(mut self) -> Result<usize> {
+        self.flush_row_group()?;
+        let types = [TYPE_INT64, TYPE_INT64, TYPE_DOUBLE];
+
+        let mut meta = Thrift::default();
+        meta.begin();
+        meta.i32_field(1, 1);
+        meta.list_field(2, CT_STRUCT, 4);
+        meta.begin();
+        meta.binary_field(4, b"schema");
+        meta.i32_field(5, 3);
+        meta.end();
+        for (name, &kind) in self.names.iter().zip(&types) {
+            meta.begin();
+            meta.i32_field(1, kind);
+            meta.i32_field(3, REPETITION_REQUIRED);
+            meta.binary_field(4, name.as_bytes());
+            meta.end();
+        }
+        meta.i64_field(3, self.row_count as i64);
+        meta.list_field(4, CT_STRUCT, self.row_groups.len());
+        for (rows, chunks) in &self.row_groups {
+            meta.begin();
+            meta.list_field(1, CT_STRUCT, 3);
+            for ((chunk, name), &kind) in chunks.iter().zip(&self.names).zip(&types) {
+                meta.begin();
+                meta.i64_field(2, chunk.offset as i64);
+                meta.struct_field(3);
+                meta.i32_field(1, kind);
+                meta.list_field(2, CT_I32, 2);
+                meta.zigzag(i64::from(ENCODING_PLAIN));
+                meta.zigzag(i64::from(ENCODING_RLE));
+                meta.list_field(3, CT_BINARY, 1);
+                meta.varint(name.len() as u64);
+                meta.buf.extend_from_slice(name.as_bytes());
+                meta.i32_field(4, CODEC_UNCOMPRESSED);
+                meta.i64_field(5, *rows as i64);
+                meta.i64_field(6, chunk.size as i64);
+                meta.i64_field(7, chunk.size as i64);
+                meta.i64_field(9, chunk.offset as i64);
+                if !chunk.min.is_empty() {
+                    meta.struct_field(12);
+                    meta.binary_field(5, &chunk.max);
+                    meta.binary_field(6, &chunk.min);
+                    meta.end();
+                }
+                meta.end();
+                meta.end();
+            }
+            let total: u64 = chunks.iter().map(|c| c.size).sum();
+            meta.i64_field(2, total as i64);
+            meta.i64_field(3, *rows as i64);
+            meta.end();
+        }
+        meta.binary_field(6, b"onager");
+        meta.end();
+
+        self.out.write_all(&meta.buf)?;
+        self.out.write_all(&(meta.buf.len() as u32).to_le_bytes())?;
+        self.out.write_all(MAGIC)?;
+        self.out.flush()?;
+        Ok(self.row_count)
+    }
+}
+
+/// Reads back the rows of a file written by [`ParquetWriter`].
+#[cfg(test)]
+pub(crate) fn read_rows(file: &[u8]) -> Vec<(i64, i64, f64)> {
+    fn varint(file: &[u8], pos: &mut usize) -> i64 {
+        let (mut value, mut shift) = (0u64, 0);
+        loop {
+            let byte = file[*pos];
+            *pos += 1;
+            value |= u64::from(byte & 0x7F) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return ((value >> 1) as i64) ^ -((value & 1) as i64);
+            }
+        }
+    }
+
+    /// Skips a page header and returns its `compressed_page_size` field.
+    fn page_size(file: &[u8], pos: &mut usize) -> usize {
+        let mut size = 0;
+        let mut depth = 0;
+        let mut last = vec![0i64];
+        loop {
+            let byte = file[*pos];
+            *pos += 1;
+            if byte == 0 {
+                last.pop();
+                if depth == 0 {
+                    return size;
+                }
+                depth -= 1;
+                continue;
+            }
+            let delta = i64::from(byte >> 4);
+            let id = if delta == 0 {
+                varint(file, pos)
+            } else {
+                last[depth] + delta
+            };
+            last[depth] = id;
+            match byte & 0x0F {
+                CT_I32 | CT_I64 => {
+                    let value = varint(file, pos);
+                    if depth == 0 && id == 3 {
+                        size = value as usize;
+                    }
+                }
+                CT_STRUCT => {
+                    depth += 1;
+                    last.push(0);
+                }
+                kind => panic!("unexpected field type {}", kind),
+            }
+        }
+    }
+
+    assert_eq!(&file[..4], MAGIC);
+    assert_eq!(&file[file.len() - 4..], MAGIC);
+    let footer_len = u32::from_le_bytes(file[file.len() - 8..file.len() - 4].try_into().unwrap());
+    let footer_start = file.len() - 8 - footer_len as usize;
+
+    let mut rows = Vec::new();
+    let mut pos = MAGIC.len();
+    while pos < footer_start {
+        let mut pages: Vec<&[u8]> = Vec::new();
+        for _ in 0..3 {
+            let size = page_size(file, &mut pos);
+            pages.push(&file[pos..pos + size]);
+            pos += size;
+        }
+        let word =
+            |page: &[u8], i: usize| -> [u8; 8] { page[i * 8..i * 8 + 8].try_into().unwrap() };
+        for i in 0..pages[0].len() / 8 {
+            rows.push((
+                i64::from_le_bytes(word(pages[0], i)),
+                i64::from_le_bytes(word(pages[1], i)),
+                f64::from_le_bytes(word(pages[2], i)),
+            ));
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parquet_writer_row_groups() {
+        let mut file = Vec::new();
+        let mut writer = ParquetWriter::new(&mut file, ["a", "b", "value"]).unwrap();
+        let total = ROW_GROUP_ROWS + 10;
+        for i in 0..total as i64 {
+            writer.write_row(i, -i, i as f64 / 2.0).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), total);
+
+        let rows = read_rows(&file);
+        assert_eq!(rows.len(), total);
+        assert_eq!(rows[0], (0, 0, 0.0));
+        assert_eq!(
+            rows[total - 1],
+            (total as i64 - 1, 1 - total as i64, (total - 1) as f64 / 2.0)
+        );
+    }
+}
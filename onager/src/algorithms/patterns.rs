@@ -0,0 +1,567 @@
+//! Transaction pattern module.
+//!
+//! Detectors for structural patterns in transaction graphs that often point
+//! to fraud, such as fan-in and fan-out bursts, activity spikes, and
+//! amount-preserving cycles.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{OnagerError, Result};
+use std::collections::{BTreeMap, HashMap};
+
+/// Direction of a fan pattern.
+///
+/// The discriminants are the direction codes used across the FFI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FanDirection {
+    /// Many distinct senders to one node, such as money mules collecting funds.
+    In = 0,
+    /// One node to many distinct receivers, such as funds being dispersed.
+    Out = 1,
+}
+
+impl FanDirection {
+    /// Returns the lowercase direction name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FanDirection::In => "in",
+            FanDirection::Out => "out",
+        }
+    }
+}
+
+/// Result of fan pattern detection.
+///
+/// Row `i` describes the busiest window of `node_ids[i]` in direction
+/// `directions[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanPatternResult {
+    pub node_ids: Vec<i64>,
+    pub directions: Vec<FanDirection>,
+    /// Number of distinct counterparties within the window
+    pub fan_sizes: Vec<i64>,
+    /// Number of edges within the window, counting repeated counterparties
+    pub edge_counts: Vec<i64>,
+    pub window_starts: Vec<i64>,
+    pub window_ends: Vec<i64>,
+}
+
+/// Busiest window found for one node in one direction.
+struct FanWindow {
+    fan_size: usize,
+    edge_count: usize,
+    start: i64,
+    end: i64,
+}
+
+/// Slides a window over `(timestamp, counterparty)` events sorted by time and
+/// returns the window with the most distinct counterparties, preferring the
+/// earliest on ties.
+fn busiest_window(events: &[(i64, i64)], window: i64) -> FanWindow {
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+    let mut best = FanWindow {
+        fan_size: 0,
+        edge_count: 0,
+        start: 0,
+        end: 0,
+    };
+    let mut left = 0;
+    for right in 0..events.len() {
+        *counts.entry(events[right].1).or_insert(0) += 1;
+        while events[right].0.saturating_sub(events[left].0) > window {
+            let party = events[left].1;
+            if let Some(count) = counts.get_mut(&party) {
+                *count -= 1;
+                if *count == 0 {
+                    counts.remove(&party);
+                }
+            }
+            left += 1;
+        }
+        if counts.len() > best.fan_size {
+            best = FanWindow {
+                fan_size: counts.len(),
+                edge_count: right - left + 1,
+                start: events[left].0,
+                end: events[right].0,
+            };
+        }
+    }
+    best
+}
+
+/// Find nodes with fan-in or fan-out bursts in a timestamped edge list.
+///
+/// Edge `i` goes from `src[i]` to `dst[i]` at time `ts[i]`, in any unit such
+/// as epoch seconds. A node is reported in a direction when, within some
+/// window of at most `window` time units, it receives edges from (fan-in) or
+/// sends edges to (fan-out) at least `min_fan` distinct counterparties. Each
+/// reported row holds the window with the most distinct counterparties, and
+/// rows are ordered by node ID and direction. Self-loops are ignored.
+pub fn compute_fan_patterns(
+    src: &[i64],
+    dst: &[i64],
+    ts: &[i64],
+    min_fan: usize,
+    window: i64,
+) -> Result<FanPatternResult> {
+    if src.len() != dst.len() || src.len() != ts.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and ts arrays must have same length".to_string(),
+        ));
+    }
+    if min_fan == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "min_fan must be at least 1".to_string(),
+        ));
+    }
+    if window < 0 {
+        return Err(OnagerError::InvalidArgument(
+            "window must be non-negative".to_string(),
+        ));
+    }
+
+    let mut events: HashMap<(i64, FanDirection), Vec<(i64, i64)>> = HashMap::new();
+    for i in 0..src.len() {
+        if src[i] == dst[i] {
+            continue;
+        }
+        events
+            .entry((src[i], FanDirection::Out))
+            .or_default()
+            .push((ts[i], dst[i]));
+        events
+            .entry((dst[i], FanDirection::In))
+            .or_default()
+            .push((ts[i], src[i]));
+    }
+
+    let mut rows: Vec<((i64, FanDirection), FanWindow)> = events
+        .into_iter()
+        .filter_map(|(key, mut node_events)| {
+            node_events.sort_unstable();
+            let best = busiest_window(&node_events, window);
+            (best.fan_size >= min_fan).then_some((key, best))
+        })
+        .collect();
+    rows.sort_unstable_by_key(|&((node, direction), _)| (node, direction as i64));
+
+    let mut result = FanPatternResult {
+        node_ids: Vec::with_capacity(rows.len()),
+        directions: Vec::with_capacity(rows.len()),
+        fan_sizes: Vec::with_capacity(rows.len()),
+        edge_counts: Vec::with_capacity(rows.len()),
+        window_starts: Vec::with_capacity(rows.len()),
+        window_ends: Vec::with_capacity(rows.len()),
+    };
+    for ((node, direction), best) in rows {
+        result.node_ids.push(node);
+        result.directions.push(direction);
+        result.fan_sizes.push(best.fan_size as i64);
+        result.edge_counts.push(best.edge_count as i64);
+        result.window_starts.push(best.start);
+        result.window_ends.push(best.end);
+    }
+    Ok(result)
+}
+
+/// Result of burst detection, one row per node and window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BurstResult {
+    pub node_ids: Vec<i64>,
+    pub window_starts: Vec<i64>,
+    /// Number of edges touching the node within the window
+    pub activities: Vec<i64>,
+    pub zscores: Vec<f64>,
+}
+
+/// Find windows in which a node is unusually active.
+///
+/// Edge `i` goes from `src[i]` to `dst[i]` at time `ts[i]`. Time is cut into
+/// fixed windows of `window` units starting at multiples of `window`, and the
+/// activity of a node in a window is the number of edges touching it there,
+/// in either direction. Each node's activity is compared with its own mean and
+/// standard deviation over every window from the first to the last edge of the
+/// whole stream, counting windows without edges as zero. A row is reported
+/// when the z-score reaches `zscore_threshold`. Nodes with constant activity
+/// are never reported. Rows are ordered by node ID and window start.
+pub fn detect_bursts(
+    src: &[i64],
+    dst: &[i64],
+    ts: &[i64],
+    window: i64,
+    zscore_threshold: f64,
+) -> Result<BurstResult> {
+    if src.len() != dst.len() || src.len() != ts.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and ts arrays must have same length".to_string(),
+        ));
+    }
+    if window <= 0 {
+        return Err(OnagerError::InvalidArgument(
+            "window must be positive".to_string(),
+        ));
+    }
+    if !zscore_threshold.is_finite() {
+        return Err(OnagerError::InvalidArgument(
+            "zscore_threshold must be finite".to_string(),
+        ));
+    }
+
+    let mut result = BurstResult {
+        node_ids: Vec::new(),
+        window_starts: Vec::new(),
+        activities: Vec::new(),
+        zscores: Vec::new(),
+    };
+    let (Some(&first), Some(&last)) = (ts.iter().min(), ts.iter().max()) else {
+        return Ok(result);
+    };
+    let window_count =
+        (last.div_euclid(window) as i128 - first.div_euclid(window) as i128 + 1) as f64;
+
+    let mut activity: BTreeMap<(i64, i64), i64> = BTreeMap::new();
+    for i in 0..src.len() {
+        let bucket = ts[i].div_euclid(window);
+        *activity.entry((src[i], bucket)).or_insert(0) += 1;
+        if dst[i] != src[i] {
+            *activity.entry((dst[i], bucket)).or_insert(0) += 1;
+        }
+    }
+
+    // Sums over the active windows of each node; idle windows add nothing
+    let mut moments: HashMap<i64, (f64, f64)> = HashMap::new();
+    for (&(node, _), &count) in &activity {
+        let entry = moments.entry(node).or_insert((0.0, 0.0));
+        entry.0 += count as f64;
+        entry.1 += (count as f64).powi(2);
+    }
+
+    for (&(node, bucket), &count) in &activity {
+        let (sum, sum_sq) = moments[&node];
+        let mean = sum / window_count;
+        let variance = (sum_sq / window_count - mean * mean).max(0.0);
+        if variance <= f64::EPSILON * mean * mean {
+            continue;
+        }
+        let zscore = (count as f64 - mean) / variance.sqrt();
+        if zscore >= zscore_threshold {
+            result.node_ids.push(node);
+            result.window_starts.push(bucket.saturating_mul(window));
+            result.activities.push(count);
+            result.zscores.push(zscore);
+        }
+    }
+    Ok(result)
+}
+
+/// Result of weighted cycle detection.
+///
+/// Cycles are flattened into rows. Row `i` is the node at `positions[i]`
+/// (starting at 0) of cycle `cycle_ids[i]`, and `weights[i]` is the weight of
+/// the edge leaving that node towards the next node of the cycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightedCycleResult {
+    pub cycle_ids: Vec<i64>,
+    pub positions: Vec<i64>,
+    pub node_ids: Vec<i64>,
+    pub weights: Vec<f64>,
+}
+
+/// Depth-first search state for weighted cycle enumeration.
+struct CycleSearch<'a> {
+    /// Outgoing `(target, weight)` pairs per node index.
+    adj: &'a [Vec<(usize, f64)>],
+    node_ids: &'a [i64],
+    max_len: usize,
+    tolerance: f64,
+    max_cycles: usize,
+    on_path: Vec<bool>,
+    path: Vec<usize>,
+    path_weights: Vec<f64>,
+    result: WeightedCycleResult,
+    cycles: usize,
+}
+
+impl CycleSearch<'_> {
+    fn within_tolerance(&self, min_w: f64, max_w: f64) -> bool {
+        max_w - min_w <= self.tolerance * max_w
+    }
+
+    fn record(&mut self) {
+        let cycle_id = self.cycles as i64;
+        for (pos, (&node, &weight)) in self.path.iter().zip(&self.path_weights).enumerate() {
+            self.result.cycle_ids.push(cycle_id);
+            self.result.positions.push(pos as i64);
+            self.result.node_ids.push(self.node_ids[node]);
+            self.result.weights.push(weight);
+        }
+        self.cycles += 1;
+    }
+
+    /// Extends the current path from `node`, only visiting nodes with an index
+    /// above `start` so each cycle is found once, from its smallest node.
+    fn extend(&mut self, start: usize, node: usize, min_w: f64, max_w: f64) {
+        let adj = self.adj;
+        for &(next, weight) in &adj[node] {
+            if self.cycles >= self.max_cycles {
+                return;
+            }
+            let (lo, hi) = (min_w.min(weight), max_w.max(weight));
+            if !self.within_tolerance(lo, hi) {
+                continue;
+            }
+            if next == start {
+                self.path_weights.push(weight);
+                self.record();
+                self.path_weights.pop();
+            } else if next > start && !self.on_path[next] && self.path.len() < self.max_len {
+                self.on_path[next] = true;
+                self.path.push(next);
+                self.path_weights.push(weight);
+                self.extend(start, next, lo, hi);
+                self.path_weights.pop();
+                self.path.pop();
+                self.on_path[next] = false;
+            }
+        }
+    }
+}
+
+/// Find directed cycles whose edge weights approximately conserve.
+///
+/// A cycle of 2 to `max_len` edges is reported when its largest and smallest
+/// edge weights differ by at most `tolerance` times the largest, so an amount
+/// that travels around the loop keeps roughly the same size, as in layering
+/// schemes for money laundering. Cycles are simple (no repeated nodes) and
+/// start at their smallest node ID. Parallel edges yield separate cycles.
+/// Self-loops are ignored. The search stops after `max_cycles` cycles.
+pub fn find_weighted_cycles(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    max_len: usize,
+    tolerance: f64,
+    max_cycles: usize,
+) -> Result<WeightedCycleResult> {
+    if src.len() != dst.len() || src.len() != weights.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and weights arrays must have same length".to_string(),
+        ));
+    }
+    if max_len < 2 {
+        return Err(OnagerError::InvalidArgument(
+            "max_len must be at least 2".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&tolerance) {
+        return Err(OnagerError::InvalidArgument(
+            "tolerance must be between 0 and 1".to_string(),
+        ));
+    }
+    if max_cycles == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "max_cycles must be at least 1".to_string(),
+        ));
+    }
+    if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err(OnagerError::InvalidArgument(
+            "weights must be finite and non-negative".to_string(),
+        ));
+    }
+
+    // Index nodes in ascending ID order so cycles start at their smallest ID
+    let mut index: BTreeMap<i64, usize> = BTreeMap::new();
+    for &node in src.iter().chain(dst) {
+        index.insert(node, 0);
+    }
+    let node_ids: Vec<i64> = index.keys().copied().collect();
+    for (i, slot) in index.values_mut().enumerate() {
+        *slot = i;
+    }
+    let mut adj: Vec<Vec<(usize, f64)>> = vec![Vec::new(); node_ids.len()];
+    for i in 0..src.len() {
+        if src[i] != dst[i] {
+            adj[index[&src[i]]].push((index[&dst[i]], weights[i]));
+        }
+    }
+    for targets in &mut adj {
+        targets.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    }
+
+    let mut search = CycleSearch {
+        adj: &adj,
+        node_ids: &node_ids,
+        max_len,
+        tolerance,
+        max_cycles,
+        on_path: vec![false; node_ids.len()],
+        path: Vec::with_capacity(max_len),
+        path_weights: Vec::with_capacity(max_len),
+        result: WeightedCycleResult {
+            cycle_ids: Vec::new(),
+            positions: Vec::new(),
+            node_ids: Vec::new(),
+            weights: Vec::new(),
+        },
+        cycles: 0,
+    };
+    for start in 0..node_ids.len() {
+        if search.cycles >= max_cycles {
+            break;
+        }
+        search.on_path[start] = true;
+        search.path.push(start);
+        search.extend(start, start, f64::INFINITY, 0.0);
+        search.path.pop();
+        search.on_path[start] = false;
+    }
+    Ok(search.result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fan_in_burst_and_spread_out_fan_out() {
+        // Accounts 10-13 send to 1 within 5 seconds, then 1 pays 20, 21, and 22
+        // an hour apart each
+        let src = vec![10, 11, 12, 13, 1, 1, 1];
+        let dst = vec![1, 1, 1, 1, 20, 21, 22];
+        let ts = vec![100, 101, 103, 105, 1000, 4600, 8200];
+
+        let result = compute_fan_patterns(&src, &dst, &ts, 3, 60).unwrap();
+
+        assert_eq!(result.node_ids, vec![1]);
+        assert_eq!(result.directions, vec![FanDirection::In]);
+        assert_eq!(result.fan_sizes, vec![4]);
+        assert_eq!(result.window_starts, vec![100]);
+        assert_eq!(result.window_ends, vec![105]);
+
+        // A window of two hours also catches the fan-out
+        let wide = compute_fan_patterns(&src, &dst, &ts, 3, 7200).unwrap();
+        assert_eq!(wide.directions, vec![FanDirection::In, FanDirection::Out]);
+        assert_eq!(wide.fan_sizes, vec![4, 3]);
+    }
+
+    #[test]
+    fn test_fan_counts_distinct_counterparties() {
+        // Node 1 pays 2 three times and 3 once within the window
+        let src = vec![1, 1, 1, 1, 4];
+        let dst = vec![2, 2, 2, 3, 4];
+        let ts = vec![0, 1, 2, 3, 4];
+
+        let result = compute_fan_patterns(&src, &dst, &ts, 2, 10).unwrap();
+
+        assert_eq!(result.node_ids, vec![1]);
+        assert_eq!(result.fan_sizes, vec![2]);
+        assert_eq!(result.edge_counts, vec![4]);
+    }
+
+    #[test]
+    fn test_fan_patterns_invalid_input() {
+        assert!(compute_fan_patterns(&[1], &[2], &[], 1, 10).is_err());
+        assert!(compute_fan_patterns(&[1], &[2], &[0], 0, 10).is_err());
+        assert!(compute_fan_patterns(&[1], &[2], &[0], 1, -1).is_err());
+        assert!(compute_fan_patterns(&[], &[], &[], 1, 10)
+            .unwrap()
+            .node_ids
+            .is_empty());
+    }
+
+    #[test]
+    fn test_bursts_flag_spike_against_own_baseline() {
+        // Node 1 sends one edge per hour for five hours, then six in hour 5
+        let mut src = vec![1, 1, 1, 1, 1];
+        let mut dst = vec![2, 3, 2, 3, 2];
+        let mut ts = vec![0, 3600, 7200, 10800, 14400];
+        for k in 0..6 {
+            src.push(1);
+            dst.push(2 + k % 2);
+            ts.push(18000 + k);
+        }
+
+        // Nodes 2 and 3 also peak in hour 5 but stay just below the threshold
+        let result = detect_bursts(&src, &dst, &ts, 3600, 2.1).unwrap();
+
+        assert_eq!(result.node_ids, vec![1]);
+        assert_eq!(result.window_starts, vec![18000]);
+        assert_eq!(result.activities, vec![6]);
+        // Activities 1, 1, 1, 1, 1, 6 have mean 11/6 and deviation sqrt(125)/6
+        assert!((result.zscores[0] - 25.0 / 125f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bursts_count_idle_windows_and_skip_constant_nodes() {
+        // Node 1 is active only in the first of three windows, so it bursts
+        // there, while node 5 has one edge in every window
+        let src = vec![1, 1, 5, 5, 5];
+        let dst = vec![2, 3, 6, 6, 6];
+        let ts = vec![0, 1, 0, 10, 20];
+
+        let result = detect_bursts(&src, &dst, &ts, 10, 1.0).unwrap();
+
+        assert!(!result.node_ids.contains(&5));
+        assert!(!result.node_ids.contains(&6));
+        assert_eq!(result.node_ids, vec![1, 2, 3]);
+        assert!(result.window_starts.iter().all(|&w| w == 0));
+    }
+
+    #[test]
+    fn test_bursts_invalid_input() {
+        assert!(detect_bursts(&[1], &[2], &[], 10, 2.0).is_err());
+        assert!(detect_bursts(&[1], &[2], &[0], 0, 2.0).is_err());
+        assert!(detect_bursts(&[1], &[2], &[0], 10, f64::NAN).is_err());
+        assert!(detect_bursts(&[], &[], &[], 10, 2.0)
+            .unwrap()
+            .node_ids
+            .is_empty());
+    }
+
+    #[test]
+    fn test_weighted_cycles_within_tolerance() {
+        // 1 -> 2 -> 3 -> 1 moves about 1000 each hop, while 1 -> 4 -> 1 halves
+        let src = vec![1, 2, 3, 1, 4];
+        let dst = vec![2, 3, 1, 4, 1];
+        let weights = vec![1000.0, 980.0, 990.0, 1000.0, 500.0];
+
+        let result = find_weighted_cycles(&src, &dst, &weights, 4, 0.05, 10).unwrap();
+
+        assert_eq!(result.cycle_ids, vec![0, 0, 0]);
+        assert_eq!(result.positions, vec![0, 1, 2]);
+        assert_eq!(result.node_ids, vec![1, 2, 3]);
+        assert_eq!(result.weights, vec![1000.0, 980.0, 990.0]);
+
+        // A loose tolerance admits the halving loop too
+        let loose = find_weighted_cycles(&src, &dst, &weights, 4, 0.5, 10).unwrap();
+        assert_eq!(loose.cycle_ids.iter().max(), Some(&1));
+    }
+
+    #[test]
+    fn test_weighted_cycles_length_and_cap() {
+        // A 4-cycle and two 2-cycles with equal weights
+        let src = vec![1, 2, 3, 4, 1, 5, 2, 6];
+        let dst = vec![2, 3, 4, 1, 5, 1, 6, 2];
+        let weights = vec![1.0; 8];
+
+        let short = find_weighted_cycles(&src, &dst, &weights, 3, 0.0, 10).unwrap();
+        assert_eq!(short.cycle_ids.iter().max(), Some(&1));
+        assert!(short.node_ids.iter().all(|&n| n != 3));
+
+        let all = find_weighted_cycles(&src, &dst, &weights, 4, 0.0, 10).unwrap();
+        assert_eq!(all.cycle_ids.iter().max(), Some(&2));
+
+        let capped = find_weighted_cycles(&src, &dst, &weights, 4, 0.0, 1).unwrap();
+        assert!(capped.cycle_ids.iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn test_weighted_cycles_invalid_input() {
+        assert!(find_weighted_cycles(&[1], &[2], &[], 3, 0.1, 10).is_err());
+        assert!(find_weighted_cycles(&[1], &[2], &[1.0], 1, 0.1, 10).is_err());
+        assert!(find_weighted_cycles(&[1], &[2], &[1.0], 3, 1.5, 10).is_err());
+        assert!(find_weighted_cycles(&[1], &[2], &[1.0], 3, 0.1, 0).is_err());
+        assert!(find_weighted_cycles(&[1], &[2], &[-1.0], 3, 0.1, 10).is_err());
+    }
+}
@@ -4,11 +4,14 @@
 
 use graphina::community::personalized_pagerank::personalized_page_rank;
 use graphina::core::types::{Graph, NodeId};
+use serde::{Deserialize, Serialize};
 
+use crate::builder::GraphBuilder;
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::collections::HashSet;
 
 /// Result of personalized PageRank computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PersonalizedPageRankResult {
     pub node_ids: Vec<i64>,
     pub scores: Vec<f64>,
@@ -19,7 +22,10 @@ pub struct PersonalizedPageRankResult {
 /// # Arguments
 /// * `src` - Source node IDs for edges
 /// * `dst` - Destination node IDs for edges
-/// * `personalization` - Map of node_id -> weight for personalization (biases random jumps)
+/// * `personalization` - Sparse (node_id, weight) pairs that bias random jumps.
+///   Weights must be finite and non-negative. Repeated nodes have their weights
+///   summed, nodes not in the graph are ignored, and the result is normalized
+///   to sum to 1. An empty slice means uniform teleportation over all nodes.
 /// * `damping` - Damping factor (typically 0.85)
 /// * `max_iter` - Maximum iterations
 /// * `tolerance` - Convergence tolerance
@@ -52,28 +58,11 @@ pub fn compute_personalized_pagerank(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
 
     // Build personalization vector aligned with node indices
-    let n = graph.node_count();
-    let node_list: Vec<NodeId> = graph.nodes().map(|(id, _)| id).collect();
+    let n = built.graph.node_count();
+    let node_list: Vec<NodeId> = built.graph.nodes().map(|(id, _)| id).collect();
 
     let personalization_vec = if personalization.is_empty() {
         None
@@ -81,20 +70,41 @@ pub fn compute_personalized_pagerank(
         // Create a personalization vector aligned with node order
         let mut p_vec = vec![0.0; n];
         for &(ext_id, weight) in personalization {
-            if let Some(&node_idx) = node_set.get(&ext_id) {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(OnagerError::InvalidArgument(format!(
+                    "Personalization weight for node {} must be finite and non-negative, got {}",
+                    ext_id, weight
+                )));
+            }
+            if let Some(node_idx) = built.graph_id(ext_id) {
                 // Find position of node_idx in node_list
                 if let Some(pos) = node_list.iter().position(|&id| id == node_idx) {
-                    p_vec[pos] = weight;
+                    p_vec[pos] += weight;
                 }
             }
         }
+        let total: f64 = p_vec.iter().sum();
+        if total <= 0.0 {
+            return Err(OnagerError::InvalidArgument(
+                "Personalization weights must have a positive sum over graph nodes".to_string(),
+            ));
+        }
+        for w in &mut p_vec {
+            *w /= total;
+        }
         Some(p_vec)
     };
 
-    let ranks = personalized_page_rank(&graph, personalization_vec, damping, tolerance, max_iter)
-        .map_err(|e| OnagerError::GraphError(e.to_string()))?;
+    let ranks = personalized_page_rank(
+        &built.graph,
+        personalization_vec,
+        damping,
+        tolerance,
+        max_iter,
+    )
+    .map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
-    let reverse_map: HashMap<NodeId, i64> = node_set.iter().map(|(&k, &v)| (v, k)).collect();
+    let reverse_map = built.reverse_map();
 
     let mut node_ids = Vec::with_capacity(ranks.len());
     let mut scores = Vec::with_capacity(ranks.len());
@@ -107,6 +117,33 @@ pub fn compute_personalized_pagerank(
     Ok(PersonalizedPageRankResult { node_ids, scores })
 }
 
+/// Compute Personalized PageRank with uniform teleportation over a node subset.
+///
+/// Every node in `subset` gets the same teleport probability, which is useful
+/// for biasing towards a group of nodes such as all verified accounts.
+/// Repeated nodes in `subset` are counted once.
+pub fn compute_personalized_pagerank_uniform(
+    src: &[i64],
+    dst: &[i64],
+    subset: &[i64],
+    damping: f64,
+    max_iter: usize,
+    tolerance: f64,
+) -> Result<PersonalizedPageRankResult> {
+    if subset.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Teleport subset must not be empty".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    let personalization: Vec<(i64, f64)> = subset
+        .iter()
+        .filter(|&&node| seen.insert(node))
+        .map(|&node| (node, 1.0))
+        .collect();
+    compute_personalized_pagerank(src, dst, &personalization, damping, max_iter, tolerance)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,4 +232,50 @@ mod tests {
         let result = compute_personalized_pagerank(&[1, 2], &[2], &[], 0.85, 100, 1e-6);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_personalized_pagerank_negative_weight_error() {
+        let (src, dst) = triangle_graph();
+        let result = compute_personalized_pagerank(&src, &dst, &[(1, -0.5)], 0.85, 100, 1e-6);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_personalized_pagerank_zero_sum_error() {
+        let (src, dst) = triangle_graph();
+        // Node 99 is not in the graph, so no weight lands on graph nodes
+        let result = compute_personalized_pagerank(&src, &dst, &[(99, 1.0)], 0.85, 100, 1e-6);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_personalized_pagerank_weights_are_normalized() {
+        let (src, dst) = triangle_graph();
+        let a = compute_personalized_pagerank(&src, &dst, &[(1, 1.0)], 0.85, 100, 1e-9).unwrap();
+        let b = compute_personalized_pagerank(&src, &dst, &[(1, 5.0)], 0.85, 100, 1e-9).unwrap();
+        for (x, y) in a.scores.iter().zip(b.scores.iter()) {
+            assert!((x - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_personalized_pagerank_uniform_subset() {
+        let src = vec![1, 1, 1];
+        let dst = vec![2, 3, 4];
+        let uniform =
+            compute_personalized_pagerank_uniform(&src, &dst, &[2, 3, 3], 0.85, 100, 1e-9).unwrap();
+        let weighted =
+            compute_personalized_pagerank(&src, &dst, &[(2, 1.0), (3, 1.0)], 0.85, 100, 1e-9)
+                .unwrap();
+        for (x, y) in uniform.scores.iter().zip(weighted.scores.iter()) {
+            assert!((x - y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_personalized_pagerank_uniform_empty_subset_error() {
+        let (src, dst) = triangle_graph();
+        let result = compute_personalized_pagerank_uniform(&src, &dst, &[], 0.85, 100, 1e-6);
+        assert!(result.is_err());
+    }
 }
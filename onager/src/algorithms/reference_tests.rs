@@ -0,0 +1,330 @@
+//! # Reference Checks
+//!
+//! Property-based tests that compare algorithms with small dense reference
+//! implementations following the NetworkX definitions. Generated graphs avoid
+//! dangling nodes, parallel edges, and self-loops wherever the definitions
+//! would otherwise differ between libraries.
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use std::collections::{BTreeSet, HashMap};
+
+    use crate::algorithms::centrality::{
+        compute_closeness, compute_closeness_general, compute_pagerank,
+    };
+    use crate::algorithms::community::compute_connected_components;
+    use crate::algorithms::parallel::{compute_components_parallel, compute_pagerank_parallel};
+    use crate::algorithms::weighted_rank::compute_weighted_pagerank;
+
+    const DAMPING: f64 = 0.85;
+    const TOLERANCE: f64 = 1e-4;
+
+    /// Maps a node index to a node ID that is neither dense nor zero-based.
+    fn node_id(i: usize) -> i64 {
+        i as i64 * 3 - 5
+    }
+
+    /// Graph over nodes `0..n` as index pairs.
+    #[derive(Debug, Clone)]
+    struct TestGraph {
+        n: usize,
+        edges: Vec<(usize, usize)>,
+    }
+
+    impl TestGraph {
+        fn edge_arrays(&self) -> (Vec<i64>, Vec<i64>) {
+            self.edges
+                .iter()
+                .map(|&(u, v)| (node_id(u), node_id(v)))
+                .unzip()
+        }
+
+        /// Arcs followed by a random walk, both ways when undirected.
+        fn arcs(&self, weights: &[f64], directed: bool) -> Vec<(usize, usize, f64)> {
+            let mut arcs = Vec::new();
+            for (&(u, v), &w) in self.edges.iter().zip(weights) {
+                arcs.push((u, v, w));
+                if !directed {
+                    arcs.push((v, u, w));
+                }
+            }
+            arcs
+        }
+    }
+
+    /// Graph where every node has an outgoing edge, with no parallel edges or
+    /// self-loops. As an undirected graph every node has a neighbor.
+    fn graph_without_dangling(directed: bool) -> impl Strategy<Value = TestGraph> {
+        (2usize..10)
+            .prop_flat_map(|n| {
+                (
+                    Just(n),
+                    prop::collection::vec(1..n, n),
+                    prop::collection::vec((0..n, 0..n), 0..2 * n),
+                )
+            })
+            .prop_map(move |(n, forced, extra)| {
+                let mut seen = BTreeSet::new();
+                let mut edges = Vec::new();
+                // Node u always links to some other node
+                let candidates = forced
+                    .iter()
+                    .enumerate()
+                    .map(|(u, &step)| (u, (u + step) % n))
+                    .chain(extra);
+                for (u, v) in candidates {
+                    let key = if directed {
+                        (u, v)
+                    } else {
+                        (u.min(v), u.max(v))
+                    };
+                    if u != v && seen.insert(key) {
+                        edges.push((u, v));
+                    }
+                }
+                TestGraph { n, edges }
+            })
+    }
+
+    /// Connected undirected graph built from a random spanning tree plus
+    /// extra edges, without parallel edges or self-loops.
+    fn connected_graph() -> impl Strategy<Value = TestGraph> {
+        (2usize..10)
+            .prop_flat_map(|n| {
+                (
+                    Just(n),
+                    prop::collection::vec(any::<prop::sample::Index>(), n - 1),
+                    prop::collection::vec((0..n, 0..n), 0..n),
+                )
+            })
+            .prop_map(|(n, parents, extra)| {
+                let mut seen = BTreeSet::new();
+                let mut edges = Vec::new();
+                let tree = parents
+                    .iter()
+                    .enumerate()
+                    .map(|(i, parent)| (i + 1, parent.index(i + 1)));
+                for (u, v) in tree.chain(extra) {
+                    if u != v && seen.insert((u.min(v), u.max(v))) {
+                        edges.push((u, v));
+                    }
+                }
+                TestGraph { n, edges }
+            })
+    }
+
+    /// Arbitrary edge list over a few nodes, with repeats and self-loops.
+    fn any_graph() -> impl Strategy<Value = TestGraph> {
+        prop::collection::vec((0usize..12, 0usize..12), 1..20).prop_map(|edges| {
+            let n = edges.iter().map(|&(u, v)| u.max(v) + 1).max().unwrap_or(0);
+            TestGraph { n, edges }
+        })
+    }
+
+    /// Solves `a x = b` by Gaussian elimination with partial pivoting.
+    fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+        let n = b.len();
+        for col in 0..n {
+            let pivot = (col..n)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap();
+            a.swap(col, pivot);
+            b.swap(col, pivot);
+            for row in col + 1..n {
+                let factor = a[row][col] / a[col][col];
+                for k in col..n {
+                    a[row][k] -= factor * a[col][k];
+                }
+                b[row] -= factor * b[col];
+            }
+        }
+        let mut x = vec![0.0; n];
+        for row in (0..n).rev() {
+            let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+            x[row] = (b[row] - tail) / a[row][row];
+        }
+        x
+    }
+
+    /// PageRank as the exact solution of `(I - d M) r = (1 - d) / n`, where
+    /// `M` follows arcs in proportion to their weight and sends the rank of
+    /// nodes without outgoing weight to every node.
+    fn reference_pagerank(n: usize, arcs: &[(usize, usize, f64)], damping: f64) -> Vec<f64> {
+        let mut strength = vec![0.0; n];
+        for &(u, _, w) in arcs {
+            strength[u] += w;
+        }
+        let mut a = vec![vec![0.0; n]; n];
+        for (v, row) in a.iter_mut().enumerate() {
+            row[v] = 1.0;
+        }
+        for &(u, v, w) in arcs {
+            a[v][u] -= damping * w / strength[u];
+        }
+        for u in (0..n).filter(|&u| strength[u] <= 0.0) {
+            for row in a.iter_mut() {
+                row[u] -= damping / n as f64;
+            }
+        }
+        solve(a, vec![(1.0 - damping) / n as f64; n])
+    }
+
+    /// Closeness as `(n - 1) / total distance` from breadth-first search,
+    /// which all common definitions agree on for connected graphs.
+    fn reference_closeness(graph: &TestGraph) -> Vec<f64> {
+        let mut neighbors = vec![Vec::new(); graph.n];
+        for &(u, v) in &graph.edges {
+            neighbors[u].push(v);
+            neighbors[v].push(u);
+        }
+        (0..graph.n)
+            .map(|source| {
+                let mut dist = vec![usize::MAX; graph.n];
+                dist[source] = 0;
+                let mut queue = std::collections::VecDeque::from([source]);
+                while let Some(u) = queue.pop_front() {
+                    for &v in &neighbors[u] {
+                        if dist[v] == usize::MAX {
+                            dist[v] = dist[u] + 1;
+                            queue.push_back(v);
+                        }
+                    }
+                }
+                (graph.n - 1) as f64 / dist.iter().sum::<usize>() as f64
+            })
+            .collect()
+    }
+
+    /// Component representative of every node that appears in an edge.
+    fn reference_components(graph: &TestGraph) -> HashMap<i64, usize> {
+        let mut parent: Vec<usize> = (0..graph.n).collect();
+        fn find(parent: &mut [usize], mut x: usize) -> usize {
+            while parent[x] != x {
+                parent[x] = parent[parent[x]];
+                x = parent[x];
+            }
+            x
+        }
+        for &(u, v) in &graph.edges {
+            let (ru, rv) = (find(&mut parent, u), find(&mut parent, v));
+            parent[ru] = rv;
+        }
+        graph
+            .edges
+            .iter()
+            .flat_map(|&(u, v)| [u, v])
+            .map(|u| (node_id(u), find(&mut parent, u)))
+            .collect()
+    }
+
+    /// Values keyed by node ID, so results in any order can be compared.
+    fn by_node(node_ids: &[i64], values: &[f64]) -> HashMap<i64, f64> {
+        node_ids
+            .iter()
+            .copied()
+            .zip(values.iter().copied())
+            .collect()
+    }
+
+    fn assert_close(actual: &HashMap<i64, f64>, expected: &[f64], what: &str) {
+        assert_eq!(actual.len(), expected.len(), "{what}: node count");
+        for (i, &want) in expected.iter().enumerate() {
+            let got = actual[&node_id(i)];
+            assert!(
+                (got - want).abs() < TOLERANCE,
+                "{what}: node {} got {got}, expected {want}",
+                node_id(i)
+            );
+        }
+    }
+
+    /// Checks that two labelings split the same nodes into the same groups.
+    fn assert_same_partition(node_ids: &[i64], labels: &[i64], expected: &HashMap<i64, usize>) {
+        assert_eq!(node_ids.len(), expected.len());
+        let mut forward: HashMap<i64, usize> = HashMap::new();
+        let mut backward: HashMap<usize, i64> = HashMap::new();
+        for (node, &label) in node_ids.iter().zip(labels) {
+            let want = expected[node];
+            assert_eq!(
+                *forward.entry(label).or_insert(want),
+                want,
+                "label {label} spans components"
+            );
+            assert_eq!(
+                *backward.entry(want).or_insert(label),
+                label,
+                "component split across labels"
+            );
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn pagerank_matches_reference(
+            (graph, directed) in any::<bool>()
+                .prop_flat_map(|directed| (graph_without_dangling(directed), Just(directed))),
+        ) {
+            let (src, dst) = graph.edge_arrays();
+            let unit = vec![1.0; graph.edges.len()];
+            let expected = reference_pagerank(graph.n, &graph.arcs(&unit, directed), DAMPING);
+
+            let result = compute_pagerank(&src, &dst, &[], DAMPING, 200, directed).unwrap();
+            assert_close(&by_node(&result.node_ids, &result.ranks), &expected, "pagerank");
+
+            let result = compute_pagerank_parallel(&src, &dst, &[], DAMPING, 200, directed).unwrap();
+            assert_close(&by_node(&result.node_ids, &result.ranks), &expected, "parallel pagerank");
+        }
+
+        #[test]
+        fn weighted_pagerank_follows_weights(
+            (graph, weights, directed) in any::<bool>().prop_flat_map(|directed| {
+                graph_without_dangling(directed).prop_flat_map(move |g| {
+                    let m = g.edges.len();
+                    (Just(g), prop::collection::vec(0.1f64..10.0, m), Just(directed))
+                })
+            }),
+        ) {
+            let (src, dst) = graph.edge_arrays();
+            let expected = reference_pagerank(graph.n, &graph.arcs(&weights, directed), DAMPING);
+
+            let result =
+                compute_weighted_pagerank(&src, &dst, &weights, DAMPING, 500, directed).unwrap();
+            assert_close(&by_node(&result.node_ids, &result.ranks), &expected, "weighted pagerank");
+        }
+
+        #[test]
+        fn closeness_matches_reference(graph in connected_graph()) {
+            let (src, dst) = graph.edge_arrays();
+            let expected = reference_closeness(&graph);
+
+            let result = compute_closeness(&src, &dst, &[], false).unwrap();
+            assert_close(&by_node(&result.node_ids, &result.centralities), &expected, "closeness");
+
+            // Arcs in both directions give the same distances as undirected edges
+            let both_src = [&src[..], &dst[..]].concat();
+            let both_dst = [&dst[..], &src[..]].concat();
+            let result =
+                compute_closeness_general(&both_src, &both_dst, &[], true, &[], None).unwrap();
+            assert_close(
+                &by_node(&result.node_ids, &result.centralities),
+                &expected,
+                "directed closeness",
+            );
+        }
+
+        #[test]
+        fn components_match_reference(graph in any_graph()) {
+            let (src, dst) = graph.edge_arrays();
+            let expected = reference_components(&graph);
+
+            let result = compute_connected_components(&src, &dst).unwrap();
+            assert_same_partition(&result.node_ids, &result.component_ids, &expected);
+
+            let result = compute_components_parallel(&src, &dst).unwrap();
+            assert_same_partition(&result.node_ids, &result.component_ids, &expected);
+        }
+    }
+}
@@ -157,4 +157,174 @@ mod tests {
         let lv = result.unwrap();
         assert_eq!(lv.node_ids.len(), 20_000, "Should return all 20k nodes");
     }
+
+    // SNAP-scale checks, ignored by default since each takes a few seconds in
+    // release builds. Run them with `cargo test --release -- --ignored`. Set
+    // `ONAGER_SNAP_DIR` to a directory holding `wiki-Vote.txt` and
+    // `ca-CondMat.txt` from https://snap.stanford.edu/data/ to use the real
+    // edge lists instead of synthetic graphs of the same size.
+
+    /// Size of a SNAP dataset as listed on its download page.
+    struct SnapDataset {
+        name: &'static str,
+        nodes: usize,
+        edges: usize,
+    }
+
+    const WIKI_VOTE: SnapDataset = SnapDataset {
+        name: "wiki-Vote",
+        nodes: 7_115,
+        edges: 103_689,
+    };
+
+    const CA_CONDMAT: SnapDataset = SnapDataset {
+        name: "ca-CondMat",
+        nodes: 23_133,
+        edges: 93_497,
+    };
+
+    /// Parse a SNAP edge list, skipping `#` comment lines.
+    fn read_snap_edges(path: &std::path::Path) -> (Vec<i64>, Vec<i64>) {
+        let text = std::fs::read_to_string(path).unwrap();
+        let mut src = Vec::new();
+        let mut dst = Vec::new();
+        for line in text.lines().filter(|l| !l.starts_with('#')) {
+            let mut fields = line.split_whitespace();
+            if let (Some(s), Some(d)) = (fields.next(), fields.next()) {
+                src.push(s.parse().unwrap());
+                dst.push(d.parse().unwrap());
+            }
+        }
+        (src, dst)
+    }
+
+    /// Generate a connected graph with exactly the dataset's node and edge
+    /// counts. Endpoints are mostly picked by preferential attachment, which
+    /// gives the heavy-tailed degrees of the real networks.
+    fn synthetic_snap_edges(dataset: &SnapDataset) -> (Vec<i64>, Vec<i64>) {
+        let mut seed: u64 = 42;
+        let mut next = |bound: usize| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((seed >> 33) % bound as u64) as usize
+        };
+        let mut seen = std::collections::HashSet::new();
+        let mut endpoints: Vec<usize> = vec![0];
+        let mut src = Vec::with_capacity(dataset.edges);
+        let mut dst = Vec::with_capacity(dataset.edges);
+
+        // Attach every node to an earlier one so the graph is connected
+        for node in 1..dataset.nodes {
+            let target = endpoints[next(endpoints.len())];
+            seen.insert((node.min(target), node.max(target)));
+            src.push(node as i64);
+            dst.push(target as i64);
+            endpoints.extend([node, target]);
+        }
+        while src.len() < dataset.edges {
+            let s = next(dataset.nodes);
+            let d = if next(4) == 0 {
+                next(dataset.nodes)
+            } else {
+                endpoints[next(endpoints.len())]
+            };
+            if s != d && seen.insert((s.min(d), s.max(d))) {
+                src.push(s as i64);
+                dst.push(d as i64);
+                endpoints.extend([s, d]);
+            }
+        }
+        (src, dst)
+    }
+
+    /// Load the real dataset if `ONAGER_SNAP_DIR` has it, otherwise generate
+    /// a synthetic one. Returns the edges and whether they are synthetic.
+    fn snap_edges(dataset: &SnapDataset) -> (Vec<i64>, Vec<i64>, bool) {
+        if let Some(dir) = std::env::var_os("ONAGER_SNAP_DIR") {
+            let path = std::path::Path::new(&dir).join(format!("{}.txt", dataset.name));
+            if path.exists() {
+                let (src, dst) = read_snap_edges(&path);
+                return (src, dst, false);
+            }
+        }
+        let (src, dst) = synthetic_snap_edges(dataset);
+        (src, dst, true)
+    }
+
+    fn check_snap_pagerank(dataset: &SnapDataset, directed: bool) {
+        let (src, dst, _) = snap_edges(dataset);
+        let pr = compute_pagerank(&src, &dst, &[], 0.85, 100, directed).unwrap();
+        assert_eq!(pr.node_ids.len(), dataset.nodes, "{}", dataset.name);
+        assert!(pr.ranks.iter().all(|r| r.is_finite() && *r >= 0.0));
+        assert!(
+            pr.ranks.iter().any(|r| *r > 0.0),
+            "{} has no positive rank",
+            dataset.name
+        );
+    }
+
+    fn check_snap_louvain(dataset: &SnapDataset) {
+        let (src, dst, _) = snap_edges(dataset);
+        let lv = compute_louvain(&src, &dst, Some(42)).unwrap();
+        assert_eq!(lv.node_ids.len(), dataset.nodes, "{}", dataset.name);
+        assert_eq!(lv.community_ids.len(), dataset.nodes);
+        let communities: std::collections::HashSet<_> = lv.community_ids.iter().collect();
+        assert!(
+            communities.len() > 1,
+            "{} collapsed into one community",
+            dataset.name
+        );
+    }
+
+    fn check_snap_components(dataset: &SnapDataset) {
+        let (src, dst, synthetic) = snap_edges(dataset);
+        let cc = compute_connected_components(&src, &dst).unwrap();
+        assert_eq!(cc.node_ids.len(), dataset.nodes, "{}", dataset.name);
+        if synthetic {
+            let components: std::collections::HashSet<_> = cc.component_ids.iter().collect();
+            assert_eq!(
+                components.len(),
+                1,
+                "synthetic {} is connected",
+                dataset.name
+            );
+        }
+    }
+
+    #[test]
+    #[ignore = "SNAP-scale, run with --ignored"]
+    fn test_snap_wiki_vote_pagerank() {
+        check_snap_pagerank(&WIKI_VOTE, true);
+    }
+
+    #[test]
+    #[ignore = "SNAP-scale, run with --ignored"]
+    fn test_snap_wiki_vote_louvain() {
+        check_snap_louvain(&WIKI_VOTE);
+    }
+
+    #[test]
+    #[ignore = "SNAP-scale, run with --ignored"]
+    fn test_snap_wiki_vote_components() {
+        check_snap_components(&WIKI_VOTE);
+    }
+
+    #[test]
+    #[ignore = "SNAP-scale, run with --ignored"]
+    fn test_snap_ca_condmat_pagerank() {
+        check_snap_pagerank(&CA_CONDMAT, false);
+    }
+
+    #[test]
+    #[ignore = "SNAP-scale, run with --ignored"]
+    fn test_snap_ca_condmat_louvain() {
+        check_snap_louvain(&CA_CONDMAT);
+    }
+
+    #[test]
+    #[ignore = "SNAP-scale, run with --ignored"]
+    fn test_snap_ca_condmat_components() {
+        check_snap_components(&CA_CONDMAT);
+    }
 }
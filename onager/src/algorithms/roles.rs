@@ -0,0 +1,244 @@
+//! Node role classification module.
+//!
+//! Labels each node as a hub, connector, peripheral node, or isolate from its
+//! degree, approximate betweenness, and local clustering coefficient.
+
+use serde::{Deserialize, Serialize};
+
+use super::centrality::{bounded_dijkstra, WeightedAdjacency};
+use super::sbm::SplitMix64;
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// Structural role of a node.
+///
+/// The discriminants are the role codes used across the FFI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeRole {
+    /// No edges to other nodes.
+    Isolate = 0,
+    /// Neither a hub nor a connector.
+    Peripheral = 1,
+    /// Lies on many shortest paths between otherwise loosely linked neighbors.
+    Connector = 2,
+    /// Has many more neighbors than a typical node.
+    Hub = 3,
+}
+
+impl NodeRole {
+    /// Returns the lowercase role name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NodeRole::Isolate => "isolate",
+            NodeRole::Peripheral => "peripheral",
+            NodeRole::Connector => "connector",
+            NodeRole::Hub => "hub",
+        }
+    }
+}
+
+/// Result of node role classification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeRolesResult {
+    pub node_ids: Vec<i64>,
+    pub roles: Vec<NodeRole>,
+    pub degrees: Vec<i64>,
+    /// Normalized betweenness, estimated from sampled sources
+    pub betweenness: Vec<f64>,
+    pub clustering: Vec<f64>,
+}
+
+/// Classify nodes into hubs, connectors, peripheral nodes, and isolates.
+///
+/// Edges are treated as undirected, and parallel edges and self-loops are
+/// ignored. Betweenness is estimated with Brandes' algorithm from `samples`
+/// random sources, or from every node when `samples` is at least the node
+/// count. Roles are assigned in order:
+///
+/// - isolate: degree 0
+/// - hub: degree at least 3 and at least twice the mean degree
+/// - connector: betweenness above zero and at least twice the mean, with
+///   clustering below the mean clustering
+/// - peripheral: every other node
+pub fn classify_node_roles(
+    src: &[i64],
+    dst: &[i64],
+    samples: usize,
+    seed: Option<u64>,
+) -> Result<NodeRolesResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if samples == 0 {
+        return Err(OnagerError::InvalidArgument(
+            "samples must be at least 1".to_string(),
+        ));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let n = nodes.len();
+    let mut simple: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (&s, &d) in src.iter().zip(dst) {
+        let (u, v) = (nodes.of(s), nodes.of(d));
+        if u != v {
+            simple[u].push(v);
+            simple[v].push(u);
+        }
+    }
+    for adj in &mut simple {
+        adj.sort_unstable();
+        adj.dedup();
+    }
+
+    let degrees: Vec<i64> = simple.iter().map(|adj| adj.len() as i64).collect();
+    let clustering: Vec<f64> = simple
+        .iter()
+        .map(|adj| {
+            let d = adj.len();
+            if d < 2 {
+                return 0.0;
+            }
+            let links: usize = adj
+                .iter()
+                .map(|&u| {
+                    simple[u]
+                        .iter()
+                        .filter(|w| adj.binary_search(w).is_ok())
+                        .count()
+                })
+                .sum();
+            // Each link between two neighbors is seen from both ends
+            links as f64 / (d * (d - 1)) as f64
+        })
+        .collect();
+
+    let adj = WeightedAdjacency {
+        node_ids: nodes.node_ids.clone(),
+        neighbors: simple
+            .iter()
+            .map(|list| list.iter().map(|&v| (v, 1.0)).collect())
+            .collect(),
+    };
+    let sources: Vec<usize> = if samples >= n {
+        (0..n).collect()
+    } else {
+        let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+        let mut rng = SplitMix64(seed);
+        let mut order: Vec<usize> = (0..n).collect();
+        for i in 0..samples {
+            let j = i + rng.next_below(n - i);
+            order.swap(i, j);
+        }
+        order.truncate(samples);
+        order
+    };
+    let mut betweenness = vec![0.0; n];
+    for &source in &sources {
+        let tree = bounded_dijkstra(&adj, source, None);
+        let mut delta = vec![0.0; n];
+        for &w in tree.order.iter().rev() {
+            for &v in &tree.preds[w] {
+                delta[v] += tree.sigma[v] / tree.sigma[w] * (1.0 + delta[w]);
+            }
+            if w != source {
+                betweenness[w] += delta[w];
+            }
+        }
+    }
+    // Scale the sample up to all sources, then normalize by the number of
+    // ordered pairs of other nodes, since each undirected path counts twice.
+    let scale = if n > 2 {
+        n as f64 / sources.len() as f64 / ((n - 1) * (n - 2)) as f64
+    } else {
+        0.0
+    };
+    for b in &mut betweenness {
+        *b *= scale;
+    }
+
+    let mean_degree = degrees.iter().sum::<i64>() as f64 / n as f64;
+    let mean_betweenness = betweenness.iter().sum::<f64>() / n as f64;
+    let mean_clustering = clustering.iter().sum::<f64>() / n as f64;
+    let roles = (0..n)
+        .map(|i| {
+            let degree = degrees[i] as f64;
+            if degrees[i] == 0 {
+                NodeRole::Isolate
+            } else if degrees[i] >= 3 && degree >= 2.0 * mean_degree {
+                NodeRole::Hub
+            } else if betweenness[i] > 0.0
+                && betweenness[i] >= 2.0 * mean_betweenness
+                && clustering[i] < mean_clustering
+            {
+                NodeRole::Connector
+            } else {
+                NodeRole::Peripheral
+            }
+        })
+        .collect();
+
+    Ok(NodeRolesResult {
+        node_ids: nodes.node_ids,
+        roles,
+        degrees,
+        betweenness,
+        clustering,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role_of(result: &NodeRolesResult, node: i64) -> NodeRole {
+        let i = result.node_ids.iter().position(|&n| n == node).unwrap();
+        result.roles[i]
+    }
+
+    #[test]
+    fn test_roles_star_and_bridge() {
+        // Hub 1 with leaves 2-7, triangle 10-11-12, and 7-8-10 joining the two
+        let mut src = vec![1, 1, 1, 1, 1, 1, 10, 11, 12, 7, 8];
+        let mut dst = vec![2, 3, 4, 5, 6, 7, 11, 12, 10, 8, 10];
+        // Node 20 only has a self-loop
+        src.push(20);
+        dst.push(20);
+
+        let result = classify_node_roles(&src, &dst, 100, Some(1)).unwrap();
+
+        assert_eq!(role_of(&result, 1), NodeRole::Hub);
+        assert_eq!(role_of(&result, 8), NodeRole::Connector);
+        assert_eq!(role_of(&result, 2), NodeRole::Peripheral);
+        assert_eq!(role_of(&result, 20), NodeRole::Isolate);
+    }
+
+    #[test]
+    fn test_roles_exact_betweenness_on_path() {
+        // Path 1-2-3: node 2 lies on the only path between 1 and 3
+        let result = classify_node_roles(&[1, 2], &[2, 3], 10, None).unwrap();
+
+        assert_eq!(result.betweenness, vec![0.0, 1.0, 0.0]);
+        assert_eq!(result.degrees, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_roles_sampling_is_reproducible() {
+        let src: Vec<i64> = (0..50).collect();
+        let dst: Vec<i64> = (1..51).collect();
+
+        let a = classify_node_roles(&src, &dst, 5, Some(7)).unwrap();
+        let b = classify_node_roles(&src, &dst, 5, Some(7)).unwrap();
+
+        assert_eq!(a.betweenness, b.betweenness);
+        assert!(classify_node_roles(&src, &dst, 0, None).is_err());
+    }
+}
@@ -0,0 +1,497 @@
+//! Weighted spectral clustering module.
+//!
+//! Normalized spectral clustering over edge weights, with an eigengap-based
+//! choice of the number of clusters.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use super::centrality::{build_weighted_adjacency, WeightedAdjacency};
+use super::sbm::SplitMix64;
+use crate::error::{OnagerError, Result};
+
+/// Maximum number of orthogonal iteration steps for the eigenvectors.
+const MAX_ITER: usize = 1000;
+
+/// Largest change of a needed eigenvalue estimate at which the iteration stops.
+const TOLERANCE: f64 = 1e-6;
+
+/// Extra vectors iterated beyond those needed, which speeds up convergence.
+const EXTRA_VECTORS: usize = 2;
+
+/// Number of k-means++ initializations, of which the tightest clustering is kept.
+const KMEANS_RESTARTS: usize = 10;
+
+/// Maximum number of Lloyd iterations per k-means run.
+const KMEANS_MAX_ITER: usize = 100;
+
+/// Result of weighted spectral clustering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightedSpectralResult {
+    pub node_ids: Vec<i64>,
+    pub community_ids: Vec<i64>,
+    /// Number of clusters, either the requested `k` or the one picked by the eigengap.
+    pub k: usize,
+}
+
+/// Normalized adjacency `D^-1/2 A D^-1/2` in adjacency-list form.
+pub(crate) struct NormalizedAdjacency {
+    neighbors: Vec<Vec<(usize, f64)>>,
+}
+
+/// Builds the normalized adjacency of a graph, together with the square roots
+/// of the weighted degrees.
+pub(crate) fn normalized_adjacency(adj: &WeightedAdjacency) -> (NormalizedAdjacency, Vec<f64>) {
+    let degree: Vec<f64> = adj
+        .neighbors
+        .iter()
+        .map(|list| list.iter().map(|&(_, w)| w).sum())
+        .collect();
+    let inv_sqrt: Vec<f64> = degree
+        .iter()
+        .map(|&d| if d > 0.0 { 1.0 / d.sqrt() } else { 0.0 })
+        .collect();
+    let op = NormalizedAdjacency {
+        neighbors: adj
+            .neighbors
+            .iter()
+            .enumerate()
+            .map(|(u, list)| {
+                list.iter()
+                    .map(|&(v, w)| (v, w * inv_sqrt[u] * inv_sqrt[v]))
+                    .collect()
+            })
+            .collect(),
+    };
+    (op, degree.iter().map(|d| d.sqrt()).collect())
+}
+
+impl NormalizedAdjacency {
+    /// Applies `(I + D^-1/2 A D^-1/2) / 2`, whose eigenvalues lie in `[0, 1]`
+    /// and are largest for the smallest normalized Laplacian eigenvalues.
+    fn apply(&self, x: &[f64]) -> Vec<f64> {
+        self.neighbors
+            .iter()
+            .enumerate()
+            .map(|(u, adj)| {
+                let ax: f64 = adj.iter().map(|&(v, w)| w * x[v]).sum();
+                0.5 * (x[u] + ax)
+            })
+            .collect()
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Orthonormalizes vectors in place with modified Gram-Schmidt. A vector
+/// that becomes numerically zero is replaced by a random one.
+fn orthonormalize(vectors: &mut [Vec<f64>], rng: &mut SplitMix64) {
+    for j in 0..vectors.len() {
+        for attempt in 0..2 {
+            for i in 0..j {
+                let (done, rest) = vectors.split_at_mut(j);
+                let proj = dot(&done[i], &rest[0]);
+                for (x, q) in rest[0].iter_mut().zip(&done[i]) {
+                    *x -= proj * q;
+                }
+            }
+            let norm = dot(&vectors[j], &vectors[j]).sqrt();
+            if norm > 1e-12 {
+                vectors[j].iter_mut().for_each(|x| *x /= norm);
+                break;
+            }
+            if attempt == 0 {
+                vectors[j]
+                    .iter_mut()
+                    .for_each(|x| *x = rng.next_f64() - 0.5);
+            }
+        }
+    }
+}
+
+/// Eigenvalues and eigenvectors of a small symmetric matrix by cyclic Jacobi
+/// rotations. Eigenvector `j` is column `j` of the returned matrix.
+fn symmetric_eigen(mut a: Vec<Vec<f64>>) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let m = a.len();
+    let mut v: Vec<Vec<f64>> = (0..m)
+        .map(|i| (0..m).map(|j| f64::from(u8::from(i == j))).collect())
+        .collect();
+    for _ in 0..100 {
+        let off: f64 = (0..m)
+            .flat_map(|i| (0..m).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off < 1e-24 {
+            break;
+        }
+        for p in 0..m {
+            for q in p + 1..m {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (rp, rq) = (row[p], row[q]);
+                    row[p] = c * rp - s * rq;
+                    row[q] = s * rp + c * rq;
+                }
+                let (head, tail) = a.split_at_mut(q);
+                for (ap, aq) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    (*ap, *aq) = (c * *ap - s * *aq, s * *ap + c * *aq);
+                }
+                for row in v.iter_mut() {
+                    let (vp, vq) = (row[p], row[q]);
+                    row[p] = c * vp - s * vq;
+                    row[q] = s * vp + c * vq;
+                }
+            }
+        }
+    }
+    ((0..m).map(|i| a[i][i]).collect(), v)
+}
+
+/// Top `m` eigenpairs of the shifted operator by orthogonal iteration
+/// followed by a Rayleigh-Ritz step, sorted by decreasing eigenvalue. The
+/// iteration stops once the first `needed` eigenvalues settle.
+pub(crate) fn top_eigenpairs(
+    op: &NormalizedAdjacency,
+    sqrt_degree: &[f64],
+    m: usize,
+    needed: usize,
+    rng: &mut SplitMix64,
+) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = sqrt_degree.len();
+    // The square-root degree vector is the top eigenvector of a connected graph
+    let mut basis: Vec<Vec<f64>> = (0..m)
+        .map(|j| {
+            if j == 0 {
+                sqrt_degree.to_vec()
+            } else {
+                (0..n).map(|_| rng.next_f64() - 0.5).collect()
+            }
+        })
+        .collect();
+    orthonormalize(&mut basis, rng);
+
+    let mut previous = vec![f64::INFINITY; m];
+    for _ in 0..MAX_ITER {
+        let mut next: Vec<Vec<f64>> = basis.iter().map(|x| op.apply(x)).collect();
+        // Ritz values settle even when eigenvalues are close together,
+        // unlike the Rayleigh quotients of single basis vectors
+        let h: Vec<Vec<f64>> = basis
+            .iter()
+            .map(|x| next.iter().map(|y| dot(x, y)).collect())
+            .collect();
+        let mut estimates = symmetric_eigen(h).0;
+        estimates.sort_by(|a, b| b.total_cmp(a));
+        orthonormalize(&mut next, rng);
+        basis = next;
+        let change = estimates[..needed]
+            .iter()
+            .zip(&previous)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f64::max);
+        previous = estimates;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    let images: Vec<Vec<f64>> = basis.iter().map(|x| op.apply(x)).collect();
+    let h: Vec<Vec<f64>> = basis
+        .iter()
+        .map(|x| images.iter().map(|y| dot(x, y)).collect())
+        .collect();
+    let (values, rotation) = symmetric_eigen(h);
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|&a, &b| values[b].total_cmp(&values[a]));
+    let vectors = order
+        .iter()
+        .map(|&j| {
+            (0..n)
+                .map(|u| (0..m).map(|i| rotation[i][j] * basis[i][u]).sum())
+                .collect()
+        })
+        .collect();
+    (order.iter().map(|&j| values[j]).collect(), vectors)
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// One k-means run with k-means++ seeding. Returns labels and inertia.
+fn kmeans(points: &[Vec<f64>], k: usize, rng: &mut SplitMix64) -> (Vec<usize>, f64) {
+    let n = points.len();
+    let mut centers = vec![points[rng.next_below(n)].clone()];
+    let mut nearest: Vec<f64> = points
+        .iter()
+        .map(|p| squared_distance(p, &centers[0]))
+        .collect();
+    while centers.len() < k {
+        let total: f64 = nearest.iter().sum();
+        let pick = if total > 0.0 {
+            let mut target = rng.next_f64() * total;
+            let mut chosen = n - 1;
+            for (i, &d) in nearest.iter().enumerate() {
+                if target < d {
+                    chosen = i;
+                    break;
+                }
+                target -= d;
+            }
+            chosen
+        } else {
+            rng.next_below(n)
+        };
+        centers.push(points[pick].clone());
+        for (d, p) in nearest.iter_mut().zip(points) {
+            *d = d.min(squared_distance(p, &points[pick]));
+        }
+    }
+
+    let mut labels = vec![usize::MAX; n];
+    for _ in 0..KMEANS_MAX_ITER {
+        let mut changed = false;
+        for (label, p) in labels.iter_mut().zip(points) {
+            let best = (0..k)
+                .min_by(|&a, &b| {
+                    squared_distance(p, &centers[a]).total_cmp(&squared_distance(p, &centers[b]))
+                })
+                .unwrap_or(0);
+            if *label != best {
+                *label = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        let dim = points[0].len();
+        let mut sums = vec![vec![0.0; dim]; k];
+        let mut counts = vec![0usize; k];
+        for (&label, p) in labels.iter().zip(points) {
+            counts[label] += 1;
+            for (s, x) in sums[label].iter_mut().zip(p) {
+                *s += x;
+            }
+        }
+        // A cluster that lost all its points keeps its old center
+        for c in (0..k).filter(|&c| counts[c] > 0) {
+            centers[c] = sums[c].iter().map(|s| s / counts[c] as f64).collect();
+        }
+    }
+    let inertia = labels
+        .iter()
+        .zip(points)
+        .map(|(&label, p)| squared_distance(p, &centers[label]))
+        .sum();
+    (labels, inertia)
+}
+
+/// Compute normalized spectral clustering over weighted edges.
+///
+/// Edges are undirected and parallel edges add up. Empty weights mean unit
+/// weights, and larger weights tie nodes more strongly. Nodes are embedded
+/// with the eigenvectors of the `k` smallest eigenvalues of the normalized
+/// Laplacian, rows are scaled to unit length, and the rows are grouped by
+/// k-means. With `k` set to `None`, `k` is chosen in `1..=max_k` at the
+/// largest gap between consecutive Laplacian eigenvalues, which counts the
+/// well-separated clusters. Community IDs follow the order in which nodes
+/// first appear in the edges.
+pub fn compute_weighted_spectral_clustering(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    k: Option<usize>,
+    max_k: usize,
+    seed: Option<u64>,
+) -> Result<WeightedSpectralResult> {
+    let adj = build_weighted_adjacency(src, dst, weights)?;
+    let n = adj.node_ids.len();
+    let needed = match k {
+        Some(0) => {
+            return Err(OnagerError::InvalidArgument(
+                "k must be positive".to_string(),
+            ))
+        }
+        Some(k) if k > n => {
+            return Err(OnagerError::InvalidArgument(format!(
+                "k must be at most the number of nodes ({})",
+                n
+            )))
+        }
+        Some(k) => k,
+        None if max_k < 2 => {
+            return Err(OnagerError::InvalidArgument(
+                "max_k must be at least 2".to_string(),
+            ))
+        }
+        None => max_k + 1,
+    }
+    .min(n);
+    let m = (needed + EXTRA_VECTORS).min(n);
+
+    let (op, sqrt_degree) = normalized_adjacency(&adj);
+
+    let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+    let mut rng = SplitMix64(seed);
+    let (values, vectors) = top_eigenpairs(&op, &sqrt_degree, m, needed, &mut rng);
+
+    let k = k.unwrap_or_else(|| {
+        // Laplacian eigenvalue i is 2 - 2 * values[i], so gaps keep their size
+        let candidates = max_k.min(m - 1);
+        (1..=candidates)
+            .max_by(|&a, &b| {
+                let gap = |j: usize| values[j - 1] - values[j];
+                gap(a).total_cmp(&gap(b)).then(b.cmp(&a))
+            })
+            .unwrap_or(1)
+    });
+
+    let points: Vec<Vec<f64>> = (0..n)
+        .map(|u| {
+            let row: Vec<f64> = vectors[..k].iter().map(|v| v[u]).collect();
+            let norm = dot(&row, &row).sqrt();
+            if norm > 0.0 {
+                row.iter().map(|x| x / norm).collect()
+            } else {
+                row
+            }
+        })
+        .collect();
+    let mut best: Option<(Vec<usize>, f64)> = None;
+    for _ in 0..KMEANS_RESTARTS {
+        let (labels, inertia) = kmeans(&points, k, &mut rng);
+        if best.as_ref().is_none_or(|(_, b)| inertia < *b) {
+            best = Some((labels, inertia));
+        }
+    }
+    let labels = best.map(|(labels, _)| labels).unwrap_or_default();
+
+    // Number clusters in order of first appearance
+    let mut renumber = vec![usize::MAX; k];
+    let mut next_id = 0;
+    let community_ids = labels
+        .iter()
+        .map(|&label| {
+            if renumber[label] == usize::MAX {
+                renumber[label] = next_id;
+                next_id += 1;
+            }
+            renumber[label] as i64
+        })
+        .collect();
+
+    Ok(WeightedSpectralResult {
+        node_ids: adj.node_ids,
+        community_ids,
+        k,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Groups node IDs by community, with groups and members sorted.
+    fn groups(result: &WeightedSpectralResult) -> Vec<Vec<i64>> {
+        let mut groups = vec![Vec::new(); result.k];
+        for (&node, &c) in result.node_ids.iter().zip(&result.community_ids) {
+            groups[c as usize].push(node);
+        }
+        groups.iter_mut().for_each(|g| g.sort_unstable());
+        groups.sort();
+        groups
+    }
+
+    #[test]
+    fn test_weights_decide_the_split() {
+        // A 4-cycle where heavy edges pull pairs together
+        let src = vec![1, 2, 3, 4];
+        let dst = vec![2, 3, 4, 1];
+
+        let result = compute_weighted_spectral_clustering(
+            &src,
+            &dst,
+            &[10.0, 0.1, 10.0, 0.1],
+            Some(2),
+            10,
+            Some(42),
+        )
+        .unwrap();
+        assert_eq!(groups(&result), vec![vec![1, 2], vec![3, 4]]);
+
+        let result = compute_weighted_spectral_clustering(
+            &src,
+            &dst,
+            &[0.1, 10.0, 0.1, 10.0],
+            Some(2),
+            10,
+            Some(42),
+        )
+        .unwrap();
+        assert_eq!(groups(&result), vec![vec![1, 4], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_auto_k_finds_clusters() {
+        // Three 4-cliques joined in a ring by weak edges
+        let mut src = Vec::new();
+        let mut dst = Vec::new();
+        let mut weights = Vec::new();
+        for c in 0..3i64 {
+            for a in 0..4 {
+                for b in a + 1..4 {
+                    src.push(c * 10 + a);
+                    dst.push(c * 10 + b);
+                    weights.push(1.0);
+                }
+            }
+            src.push(c * 10);
+            dst.push((c + 1) % 3 * 10 + 1);
+            weights.push(0.05);
+        }
+
+        let result =
+            compute_weighted_spectral_clustering(&src, &dst, &weights, None, 6, Some(7)).unwrap();
+        assert_eq!(result.k, 3);
+        assert_eq!(
+            groups(&result),
+            vec![vec![0, 1, 2, 3], vec![10, 11, 12, 13], vec![20, 21, 22, 23]]
+        );
+    }
+
+    #[test]
+    fn test_auto_k_counts_components() {
+        // Two disjoint triangles
+        let src = vec![1, 2, 3, 4, 5, 6];
+        let dst = vec![2, 3, 1, 5, 6, 4];
+
+        let result =
+            compute_weighted_spectral_clustering(&src, &dst, &[], None, 4, Some(1)).unwrap();
+        assert_eq!(result.k, 2);
+        assert_eq!(groups(&result), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn test_weighted_spectral_invalid_params() {
+        let src = vec![1, 2];
+        let dst = vec![2, 3];
+
+        assert!(compute_weighted_spectral_clustering(&src, &dst, &[], Some(0), 10, None).is_err());
+        assert!(compute_weighted_spectral_clustering(&src, &dst, &[], Some(4), 10, None).is_err());
+        assert!(compute_weighted_spectral_clustering(&src, &dst, &[], None, 1, None).is_err());
+        assert!(
+            compute_weighted_spectral_clustering(&src, &dst, &[1.0, -1.0], Some(2), 10, None)
+                .is_err()
+        );
+        assert!(compute_weighted_spectral_clustering(&[], &[], &[], Some(2), 10, None).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Result spilling module.
+//!
+//! Streams all-pairs outputs that may not fit in memory as Parquet, one
+//! source node at a time, so DuckDB can scan them lazily. The caller owns the
+//! output stream, which keeps file access and cleanup on the DuckDB side.
+
+use serde::{Deserialize, Serialize};
+
+use super::centrality::{bounded_dijkstra, build_weighted_adjacency};
+use super::parquet::ParquetWriter;
+use crate::error::{OnagerError, Result};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::str::FromStr;
+
+/// Link prediction score written by [`spill_link_scores`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinkScore {
+    Jaccard,
+    AdamicAdar,
+    ResourceAllocation,
+    CommonNeighbors,
+    PreferentialAttachment,
+}
+
+impl FromStr for LinkScore {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "jaccard" => Ok(LinkScore::Jaccard),
+            "adamic_adar" => Ok(LinkScore::AdamicAdar),
+            "resource_alloc" | "resource_allocation" => Ok(LinkScore::ResourceAllocation),
+            "common_neighbors" => Ok(LinkScore::CommonNeighbors),
+            "pref_attach" | "preferential_attachment" => Ok(LinkScore::PreferentialAttachment),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown link score '{}', expected 'jaccard', 'adamic_adar', 'resource_alloc', \
+                 'common_neighbors', or 'pref_attach'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Write all-pairs shortest distances to `out` as Parquet.
+///
+/// Runs Dijkstra from each node and writes its row of distances before moving
+/// on, so memory stays linear in the graph size. Edges are undirected, and
+/// weights must be non-negative (empty `weights` means unit weights). The file
+/// has the columns `src`, `dst` and `distance` and holds one row per ordered
+/// pair of distinct nodes that are connected. Unreachable pairs are omitted.
+/// Returns the number of rows written.
+pub fn spill_all_pairs_distances(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    out: impl Write,
+) -> Result<usize> {
+    let adj = build_weighted_adjacency(src, dst, weights)?;
+    let mut out = ParquetWriter::new(out, ["src", "dst", "distance"])?;
+    for source in 0..adj.node_ids.len() {
+        let tree = bounded_dijkstra(&adj, source, None);
+        for &v in &tree.order {
+            if v != source {
+                out.write_row(adj.node_ids[source], adj.node_ids[v], tree.dist[v])?;
+            }
+        }
+    }
+    out.finish()
+}
+
+/// Write link prediction scores for all node pairs to `out` as Parquet.
+///
+/// Edges are undirected, and parallel edges and self-loops are ignored. The
+/// file has the columns `node1`, `node2` and `score` with one row per
+/// unordered pair `node1 < node2`. Neighborhood scores only list pairs with at least one
+/// common neighbor, while preferential attachment lists every pair with a
+/// non-zero score. Pairs are produced one node at a time, so memory stays
+/// linear in the graph size. Returns the number of rows written.
+pub fn spill_link_scores(
+    src: &[i64],
+    dst: &[i64],
+    score: LinkScore,
+    out: impl Write,
+) -> Result<usize> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+
+    let mut neighbors: HashMap<i64, HashSet<i64>> = HashMap::new();
+    for i in 0..src.len() {
+        neighbors.entry(src[i]).or_default();
+        neighbors.entry(dst[i]).or_default();
+        if src[i] != dst[i] {
+            neighbors.entry(src[i]).or_default().insert(dst[i]);
+            neighbors.entry(dst[i]).or_default().insert(src[i]);
+        }
+    }
+    let mut node_ids: Vec<i64> = neighbors.keys().copied().collect();
+    node_ids.sort_unstable();
+    let degree = |node: i64| neighbors.get(&node).map_or(0, HashSet::len);
+
+    let mut out = ParquetWriter::new(out, ["node1", "node2", "score"])?;
+    for (i, &u) in node_ids.iter().enumerate() {
+        if score == LinkScore::PreferentialAttachment {
+            for &v in &node_ids[i + 1..] {
+                let s = (degree(u) * degree(v)) as f64;
+                if s > 0.0 {
+                    out.write_row(u, v, s)?;
+                }
+            }
+            continue;
+        }
+
+        // Accumulate over common neighbors `w` for every partner `v > u`
+        let mut partners: HashMap<i64, f64> = HashMap::new();
+        for &w in &neighbors[&u] {
+            let contribution = match score {
+                LinkScore::AdamicAdar => 1.0 / (degree(w) as f64).ln(),
+                LinkScore::ResourceAllocation => 1.0 / degree(w) as f64,
+                _ => 1.0,
+            };
+            for &v in &neighbors[&w] {
+                if v > u {
+                    *partners.entry(v).or_insert(0.0) += contribution;
+                }
+            }
+        }
+        let mut partner_ids: Vec<i64> = partners.keys().copied().collect();
+        partner_ids.sort_unstable();
+        for v in partner_ids {
+            let common = partners[&v];
+            let s = match score {
+                LinkScore::Jaccard => common / ((degree(u) + degree(v)) as f64 - common),
+                _ => common,
+            };
+            out.write_row(u, v, s)?;
+        }
+    }
+    out.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parquet::read_rows;
+    use super::*;
+
+    #[test]
+    fn test_spill_all_pairs_distances() {
+        // Path 1-2-3 plus an isolated edge 4-5
+        let mut file = Vec::new();
+        let rows =
+            spill_all_pairs_distances(&[1, 2, 4], &[2, 3, 5], &[1.0, 2.0, 1.0], &mut file).unwrap();
+        assert_eq!(rows, 8);
+        let rows = read_rows(&file);
+        assert_eq!(rows.len(), 8);
+        assert!(rows.contains(&(1, 3, 3.0)));
+        assert!(rows.contains(&(3, 1, 3.0)));
+        assert!(rows.contains(&(5, 4, 1.0)));
+        assert!(!rows.iter().any(|r| r.0 == 1 && r.1 == 4));
+    }
+
+    #[test]
+    fn test_spill_link_scores() {
+        // Square 1-2-3-4-1
+        let (src, dst) = ([1, 2, 3, 4], [2, 3, 4, 1]);
+        let mut file = Vec::new();
+        spill_link_scores(&src, &dst, LinkScore::Jaccard, &mut file).unwrap();
+        assert_eq!(read_rows(&file), vec![(1, 3, 1.0), (2, 4, 1.0)]);
+
+        let mut file = Vec::new();
+        spill_link_scores(&src, &dst, LinkScore::CommonNeighbors, &mut file).unwrap();
+        assert_eq!(read_rows(&file), vec![(1, 3, 2.0), (2, 4, 2.0)]);
+
+        let mut file = Vec::new();
+        let rows =
+            spill_link_scores(&src, &dst, LinkScore::PreferentialAttachment, &mut file).unwrap();
+        assert_eq!(rows, 6);
+        assert_eq!(read_rows(&file).len(), 6);
+
+        assert!("katz".parse::<LinkScore>().is_err());
+    }
+}
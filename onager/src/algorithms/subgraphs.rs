@@ -1,14 +1,19 @@
 //! Subgraph operations module.
 //!
-//! Ego graph, k-hop neighbors, induced subgraph.
+//! Ego graph, ego network metrics, k-hop neighbors, induced subgraph, forbidden
+//! node and edge removal, and densest subgraph.
 
 use graphina::core::types::{Graph, NodeId};
 use graphina::subgraphs::SubgraphOps;
+use serde::{Deserialize, Serialize};
 
+use super::flow::{simple_undirected, FlowGraph, SimpleGraph};
+use crate::builder::GraphBuilder;
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Result of ego graph extraction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EgoGraphResult {
     pub src: Vec<i64>,
     pub dst: Vec<i64>,
@@ -33,33 +38,16 @@ pub fn compute_ego_graph(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let center_id = node_set
-        .get(&center)
+    let center_id = built
+        .graph_id(center)
         .ok_or(OnagerError::NodeNotFound(center))?;
 
-    let ego = graph
-        .ego_graph(*center_id, radius)
+    let ego = built
+        .graph
+        .ego_graph(center_id, radius)
         .map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
     let mut result_src = Vec::new();
@@ -76,7 +64,153 @@ pub fn compute_ego_graph(
     })
 }
 
+/// Ego network profile of one node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EgoMetricsResult {
+    /// Number of alters, the nodes within `radius` hops of the center
+    pub size: usize,
+    /// Ties among the alters divided by the number of possible ties
+    pub density: f64,
+    /// Borgatti's effective size: the alters minus the mean number of other
+    /// alters each one is tied to
+    pub broker_score: f64,
+    /// Number of ties from the center to its neighbors
+    pub tie_count: usize,
+    /// Mean strength of the center's ties, NaN without ties
+    pub tie_strength_mean: f64,
+    /// Weakest of the center's ties, NaN without ties
+    pub tie_strength_min: f64,
+    /// Strongest of the center's ties, NaN without ties
+    pub tie_strength_max: f64,
+}
+
+/// Compute the ego network profile of `center` in one pass.
+///
+/// The ego network holds the center and every node within `radius` hops of
+/// it. Parallel edges are merged into one tie whose strength is the sum of
+/// their weights, and self-loops are ignored. Empty weights mean unit
+/// weights. With `directed`, hops and the center's ties follow out-edges, and
+/// ties between alters are counted once per direction.
+pub fn compute_ego_metrics(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    center: i64,
+    radius: usize,
+    directed: bool,
+) -> Result<EgoMetricsResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights must be empty or same length as edges".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite and non-negative, got {}",
+            w
+        )));
+    }
+    if !src.contains(&center) && !dst.contains(&center) {
+        return Err(OnagerError::NodeNotFound(center));
+    }
+
+    let mut ties: HashMap<(i64, i64), f64> = HashMap::new();
+    for (i, (&u, &v)) in src.iter().zip(dst).enumerate() {
+        if u == v {
+            continue;
+        }
+        let key = if directed || u < v { (u, v) } else { (v, u) };
+        *ties.entry(key).or_insert(0.0) += weights.get(i).copied().unwrap_or(1.0);
+    }
+    let mut adjacency: HashMap<i64, Vec<(i64, f64)>> = HashMap::new();
+    for (&(u, v), &strength) in &ties {
+        adjacency.entry(u).or_default().push((v, strength));
+        if !directed {
+            adjacency.entry(v).or_default().push((u, strength));
+        }
+    }
+
+    let mut members: HashSet<i64> = HashSet::from([center]);
+    let mut frontier = vec![center];
+    for _ in 0..radius {
+        let mut next = Vec::new();
+        for u in frontier {
+            for &(v, _) in adjacency.get(&u).map_or(&[][..], Vec::as_slice) {
+                if members.insert(v) {
+                    next.push(v);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    let size = members.len() - 1;
+    let alter_ties = ties
+        .keys()
+        .filter(|&&(u, v)| {
+            u != center && v != center && members.contains(&u) && members.contains(&v)
+        })
+        .count();
+    // Each tie between alters adds to the degree of both ends when undirected
+    let (possible, degree_sum) = if directed {
+        (size * size.saturating_sub(1), alter_ties)
+    } else {
+        (size * size.saturating_sub(1) / 2, 2 * alter_ties)
+    };
+
+    let mut strengths: Vec<(i64, f64)> = if radius == 0 {
+        Vec::new()
+    } else {
+        adjacency.get(&center).cloned().unwrap_or_default()
+    };
+    // Sum in node order so the mean does not depend on hash order
+    strengths.sort_unstable_by_key(|&(node, _)| node);
+    let tie_count = strengths.len();
+    let (tie_strength_mean, tie_strength_min, tie_strength_max) = if tie_count == 0 {
+        (f64::NAN, f64::NAN, f64::NAN)
+    } else {
+        let values = strengths.iter().map(|&(_, strength)| strength);
+        (
+            values.clone().sum::<f64>() / tie_count as f64,
+            values.clone().fold(f64::INFINITY, f64::min),
+            values.fold(f64::NEG_INFINITY, f64::max),
+        )
+    };
+
+    Ok(EgoMetricsResult {
+        size,
+        density: if possible > 0 {
+            alter_ties as f64 / possible as f64
+        } else {
+            0.0
+        },
+        broker_score: if size > 0 {
+            size as f64 - degree_sum as f64 / size as f64
+        } else {
+            0.0
+        },
+        tie_count,
+        tie_strength_mean,
+        tie_strength_min,
+        tie_strength_max,
+    })
+}
+
 /// Result of k-hop neighbor computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KHopNeighborsResult {
     pub node_ids: Vec<i64>,
 }
@@ -99,32 +233,14 @@ pub fn compute_k_hop_neighbors(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
-
-    let start_id = node_set
-        .get(&start)
+    let start_id = built
+        .graph_id(start)
         .ok_or(OnagerError::NodeNotFound(start))?;
 
-    let neighbors = graph.k_hop_neighbors(*start_id, k);
+    let neighbors = built.graph.k_hop_neighbors(start_id, k);
     let node_ids: Vec<i64> = neighbors
         .into_iter()
         .filter_map(|id| reverse_map.get(&id).copied())
@@ -134,6 +250,7 @@ pub fn compute_k_hop_neighbors(
 }
 
 /// Result of induced subgraph extraction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InducedSubgraphResult {
     pub src: Vec<i64>,
     pub dst: Vec<i64>,
@@ -161,34 +278,17 @@ pub fn compute_induced_subgraph(
         ));
     }
 
-    let mut node_map: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_map.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_map.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_map.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_map.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
     // Convert external node IDs to internal NodeIds
     let selected_nodes: std::collections::HashSet<NodeId> = node_ids
         .iter()
-        .filter_map(|&ext_id| node_map.get(&ext_id).copied())
+        .filter_map(|&ext_id| built.graph_id(ext_id))
         .collect();
 
-    let subgraph = graph
+    let subgraph = built
+        .graph
         .induced_subgraph(&selected_nodes)
         .map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
@@ -206,6 +306,191 @@ pub fn compute_induced_subgraph(
     })
 }
 
+/// Edge list left after removing forbidden nodes and edges.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilteredEdgesResult {
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    /// Weights of the kept edges, empty when no weights were given.
+    pub weights: Vec<f64>,
+}
+
+/// Remove forbidden nodes and edges from an edge list.
+///
+/// Drops every edge touching a node in `forbidden_nodes`, and every edge that
+/// matches a `(forbidden_src[i], forbidden_dst[i])` pair in either direction,
+/// so a closed road is closed both ways. Kept edges stay in input order. This
+/// lets path queries run what-if scenarios without rebuilding the edge table.
+pub fn remove_forbidden(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    forbidden_nodes: &[i64],
+    forbidden_src: &[i64],
+    forbidden_dst: &[i64],
+) -> Result<FilteredEdgesResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights array must be empty or match edge count".to_string(),
+        ));
+    }
+    if forbidden_src.len() != forbidden_dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "forbidden edge arrays must have same length".to_string(),
+        ));
+    }
+
+    let nodes: HashSet<i64> = forbidden_nodes.iter().copied().collect();
+    let edges: HashSet<(i64, i64)> = forbidden_src
+        .iter()
+        .zip(forbidden_dst)
+        .flat_map(|(&u, &v)| [(u, v), (v, u)])
+        .collect();
+    let mut result = FilteredEdgesResult {
+        src: Vec::new(),
+        dst: Vec::new(),
+        weights: Vec::new(),
+    };
+    for i in 0..src.len() {
+        let (u, v) = (src[i], dst[i]);
+        if nodes.contains(&u) || nodes.contains(&v) || edges.contains(&(u, v)) {
+            continue;
+        }
+        result.src.push(u);
+        result.dst.push(v);
+        if !weights.is_empty() {
+            result.weights.push(weights[i]);
+        }
+    }
+    Ok(result)
+}
+
+/// Densest subgraph and its density.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DensestSubgraphResult {
+    /// Nodes of the subgraph in ascending order.
+    pub node_ids: Vec<i64>,
+    /// Edges per node of the induced subgraph, |E(S)| / |S|.
+    pub density: f64,
+}
+
+/// Find the subgraph with the most edges per node.
+///
+/// The graph is treated as simple and undirected, so duplicate edges and
+/// self-loops are ignored. Charikar's peeling repeatedly removes a node of
+/// minimum degree and keeps the densest set seen, which is at least half the
+/// optimum. With `exact`, Goldberg's reduction then binary searches the
+/// density with one maximum flow per step and returns the optimum.
+pub fn compute_densest_subgraph(
+    src: &[i64],
+    dst: &[i64],
+    exact: bool,
+) -> Result<DensestSubgraphResult> {
+    let SimpleGraph { nodes, edges } = simple_undirected(src, dst)?;
+    let n = nodes.len();
+    let mut neighbors = vec![Vec::new(); n];
+    for &(u, v) in &edges {
+        neighbors[u].push(v);
+        neighbors[v].push(u);
+    }
+
+    let (mut best, mut best_density) = densest_by_peeling(&neighbors, edges.len());
+    if exact && !edges.is_empty() {
+        let m = edges.len() as f64;
+        // Densities of different node sets differ by at least 1 / (n (n - 1)),
+        // and the optimum is at most twice the peeling result
+        let gap = 1.0 / (n as f64 * (n as f64 - 1.0));
+        let (mut lo, mut hi) = (best_density, 2.0 * best_density + gap);
+        while hi - lo >= gap {
+            let guess = (lo + hi) / 2.0;
+            let (s, t) = (n, n + 1);
+            let mut network = FlowGraph::new(n + 2);
+            for (v, adj) in neighbors.iter().enumerate() {
+                network.add_edge(s, v, m);
+                network.add_edge(v, t, m + 2.0 * guess - adj.len() as f64);
+            }
+            for &(u, v) in &edges {
+                network.add_edge(u, v, 1.0);
+                network.add_edge(v, u, 1.0);
+            }
+            network.max_flow(s, t);
+            let side = network.source_side(s);
+            let candidate: Vec<usize> = (0..n).filter(|&v| side[v]).collect();
+            if candidate.is_empty() {
+                hi = guess;
+                continue;
+            }
+            lo = guess;
+            let inside = edges.iter().filter(|&&(u, v)| side[u] && side[v]).count();
+            let density = inside as f64 / candidate.len() as f64;
+            if density > best_density {
+                best = candidate;
+                best_density = density;
+            }
+        }
+    }
+
+    let mut node_ids: Vec<i64> = best.into_iter().map(|v| nodes[v]).collect();
+    node_ids.sort_unstable();
+    Ok(DensestSubgraphResult {
+        node_ids,
+        density: best_density,
+    })
+}
+
+/// Charikar's peeling over a bucket queue of degrees, returning the densest
+/// set seen and its density.
+fn densest_by_peeling(neighbors: &[Vec<usize>], edge_count: usize) -> (Vec<usize>, f64) {
+    let n = neighbors.len();
+    let mut degree: Vec<usize> = neighbors.iter().map(Vec::len).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); max_degree + 1];
+    for (v, &d) in degree.iter().enumerate() {
+        buckets[d].push(v);
+    }
+
+    let mut removed = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut edges_left = edge_count;
+    let mut best_density = edge_count as f64 / n as f64;
+    let mut best_removed = 0;
+    let mut lowest = 0;
+    while order.len() < n {
+        // Buckets hold stale entries for nodes whose degree has since dropped
+        let Some(v) = buckets[lowest].pop() else {
+            lowest += 1;
+            continue;
+        };
+        if removed[v] || degree[v] != lowest {
+            continue;
+        }
+        removed[v] = true;
+        order.push(v);
+        edges_left -= degree[v];
+        for &u in &neighbors[v] {
+            if !removed[u] {
+                degree[u] -= 1;
+                buckets[degree[u]].push(u);
+                lowest = lowest.min(degree[u]);
+            }
+        }
+        let remaining = n - order.len();
+        if remaining > 0 {
+            let density = edges_left as f64 / remaining as f64;
+            if density > best_density {
+                best_density = density;
+                best_removed = order.len();
+            }
+        }
+    }
+    (order[best_removed..].to_vec(), best_density)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +540,64 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_ego_metrics_weighted_undirected() {
+        // Ties 1-2 (2 + 1 from a parallel edge), 1-3, 1-4, 2-3, and 4-5, plus a self-loop
+        let src = vec![1, 1, 2, 1, 2, 4, 3];
+        let dst = vec![2, 3, 1, 4, 3, 5, 3];
+        let weights = vec![2.0, 1.0, 1.0, 4.0, 0.5, 1.0, 7.0];
+
+        let ego = compute_ego_metrics(&src, &dst, &weights, 1, 1, false).unwrap();
+        assert_eq!(ego.size, 3);
+        assert!((ego.density - 1.0 / 3.0).abs() < 1e-12);
+        assert!((ego.broker_score - 7.0 / 3.0).abs() < 1e-12);
+        assert_eq!(ego.tie_count, 3);
+        assert!((ego.tie_strength_mean - 8.0 / 3.0).abs() < 1e-12);
+        assert_eq!(ego.tie_strength_min, 1.0);
+        assert_eq!(ego.tie_strength_max, 4.0);
+
+        // Node 5 joins at two hops along with the 4-5 tie
+        let wider = compute_ego_metrics(&src, &dst, &weights, 1, 2, false).unwrap();
+        assert_eq!(wider.size, 4);
+        assert!((wider.density - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(wider.broker_score, 3.0);
+        assert_eq!(wider.tie_count, 3);
+    }
+
+    #[test]
+    fn test_ego_metrics_directed() {
+        let src = vec![1, 1, 2, 3, 4];
+        let dst = vec![2, 3, 3, 2, 1];
+
+        let ego = compute_ego_metrics(&src, &dst, &[], 1, 1, true).unwrap();
+        assert_eq!(ego.size, 2);
+        assert_eq!(ego.density, 1.0);
+        assert_eq!(ego.broker_score, 1.0);
+        assert_eq!(ego.tie_count, 2);
+        assert_eq!(ego.tie_strength_mean, 1.0);
+
+        // The in-edge from 4 does not make it an alter of 1
+        let undirected = compute_ego_metrics(&src, &dst, &[], 1, 1, false).unwrap();
+        assert_eq!(undirected.size, 3);
+
+        let alone = compute_ego_metrics(&src, &dst, &[], 2, 0, true).unwrap();
+        assert_eq!(alone.size, 0);
+        assert_eq!(alone.density, 0.0);
+        assert!(alone.tie_strength_mean.is_nan());
+    }
+
+    #[test]
+    fn test_ego_metrics_rejects_bad_input() {
+        let (src, dst) = star_graph();
+        assert!(matches!(
+            compute_ego_metrics(&src, &dst, &[], 999, 1, false),
+            Err(OnagerError::NodeNotFound(999))
+        ));
+        assert!(compute_ego_metrics(&src, &dst, &[1.0, -1.0, 1.0], 1, 1, false).is_err());
+        assert!(compute_ego_metrics(&src, &dst, &[1.0], 1, 1, false).is_err());
+        assert!(compute_ego_metrics(&[], &[], &[], 1, 1, false).is_err());
+    }
+
     #[test]
     fn test_k_hop_neighbors_star_hub() {
         let (src, dst) = star_graph();
@@ -344,4 +687,82 @@ mod tests {
         assert!(compute_k_hop_neighbors(&[1, 2], &[2], 1, 1).is_err());
         assert!(compute_induced_subgraph(&[1, 2], &[2], &[1, 2]).is_err());
     }
+
+    #[test]
+    fn test_remove_forbidden_nodes_and_edges() {
+        let (src, dst) = path_graph();
+        let weights = [1.0, 2.0, 3.0, 4.0];
+        let result = remove_forbidden(&src, &dst, &weights, &[5], &[3], &[2]).unwrap();
+        // Edge 2-3 is forbidden in reverse and node 5 removes edge 4-5
+        assert_eq!(result.src, vec![1, 3]);
+        assert_eq!(result.dst, vec![2, 4]);
+        assert_eq!(result.weights, vec![1.0, 3.0]);
+
+        let unweighted = remove_forbidden(&src, &dst, &[], &[], &[], &[]).unwrap();
+        assert_eq!(unweighted.src.len(), 4);
+        assert!(unweighted.weights.is_empty());
+        assert!(remove_forbidden(&src, &dst, &[], &[], &[1], &[]).is_err());
+    }
+
+    #[test]
+    fn test_densest_subgraph_clique_with_tail() {
+        // K4 on 1-4 with a tail 4-5-6, plus a duplicate edge and a self-loop
+        let src = vec![1, 1, 1, 2, 2, 3, 4, 5, 2, 6];
+        let dst = vec![2, 3, 4, 3, 4, 4, 5, 6, 1, 6];
+        for exact in [false, true] {
+            let result = compute_densest_subgraph(&src, &dst, exact).unwrap();
+            assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+            assert!((result.density - 1.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_densest_subgraph_exact_matches_brute_force() {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..40 {
+            let (mut src, mut dst) = (Vec::new(), Vec::new());
+            for u in 0..8i64 {
+                for v in u + 1..8 {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    if state.is_multiple_of(3) {
+                        src.push(u);
+                        dst.push(v);
+                    }
+                }
+            }
+            if src.is_empty() {
+                continue;
+            }
+            let mut nodes: Vec<i64> = src.iter().chain(&dst).copied().collect();
+            nodes.sort_unstable();
+            nodes.dedup();
+            let mut optimum: f64 = 0.0;
+            for mask in 1u32..1 << nodes.len() {
+                let inside = |v: &i64| {
+                    let i = nodes.binary_search(v).unwrap_or(0);
+                    mask & (1 << i) != 0
+                };
+                let edges = src
+                    .iter()
+                    .zip(&dst)
+                    .filter(|(u, v)| inside(u) && inside(v))
+                    .count();
+                optimum = optimum.max(edges as f64 / mask.count_ones() as f64);
+            }
+
+            let exact = compute_densest_subgraph(&src, &dst, true).unwrap();
+            assert!((exact.density - optimum).abs() < 1e-9);
+            let peeled = compute_densest_subgraph(&src, &dst, false).unwrap();
+            assert!(peeled.density * 2.0 >= optimum - 1e-9);
+            assert!(peeled.density <= optimum + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_densest_subgraph_errors() {
+        assert!(compute_densest_subgraph(&[], &[], false).is_err());
+        assert!(compute_densest_subgraph(&[1, 2], &[2], true).is_err());
+    }
 }
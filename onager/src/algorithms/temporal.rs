@@ -0,0 +1,341 @@
+//! Temporal network module.
+//!
+//! Algorithms on timestamped edges where a path must move forward in time,
+//! so that spreading processes such as infections or rumors are only traced
+//! along contacts in the order they happened, and session paths such as
+//! clickstreams.
+//!
+//! Edge `i` goes from `src[i]` to `dst[i]` at time `ts[i]`. A time-respecting
+//! path uses edges with strictly increasing timestamps, so contacts at the same
+//! instant never chain.
+
+use super::centrality::BetweennessResult;
+use crate::builder::NodeIndex;
+use crate::error::{OnagerError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+fn check_timestamped_edges(src: &[i64], dst: &[i64], ts: &[i64]) -> Result<()> {
+    if src.len() != dst.len() || src.len() != ts.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and ts arrays must have same length".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Compute betweenness centrality over time-respecting shortest paths.
+///
+/// A shortest path from `s` to `t` is a time-respecting path with the fewest
+/// edges. The score of a node is the sum, over all pairs it is not an endpoint
+/// of, of the fraction of shortest paths between the pair that pass through
+/// it. Edges are directed; add both directions for symmetric contacts. When
+/// `normalized` is set, scores are divided by `(n - 1)(n - 2)`. Rows are
+/// sorted by node ID.
+pub fn compute_temporal_betweenness(
+    src: &[i64],
+    dst: &[i64],
+    ts: &[i64],
+    normalized: bool,
+) -> Result<BetweennessResult> {
+    check_timestamped_edges(src, dst, ts)?;
+
+    let index = NodeIndex::ascending(src, dst);
+    let n = index.len();
+
+    // Self-loops never shorten a path, so they are dropped
+    let edges: Vec<(usize, usize, i64)> = (0..src.len())
+        .filter(|&i| src[i] != dst[i])
+        .map(|i| (index.of(src[i]), index.of(dst[i]), ts[i]))
+        .collect();
+    let m = edges.len();
+    let mut out_edges: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (e, &(u, _, _)) in edges.iter().enumerate() {
+        out_edges[u].push(e);
+    }
+    for list in &mut out_edges {
+        list.sort_by_key(|&e| edges[e].2);
+    }
+    // Edges leaving the head of `e` strictly after `e`
+    let successors = |e: usize| {
+        let (_, v, t) = edges[e];
+        let list = &out_edges[v];
+        &list[list.partition_point(|&f| edges[f].2 <= t)..]
+    };
+
+    let mut centralities = vec![0.0; n];
+    let mut dist = vec![usize::MAX; m];
+    let mut sigma = vec![0.0; m];
+    let mut delta = vec![0.0; m];
+    let mut node_dist = vec![usize::MAX; n];
+    let mut node_sigma = vec![0.0; n];
+    for (s, starts) in out_edges.iter().enumerate() {
+        // Breadth-first search over edges, where each edge stands for arriving
+        // at its head at its timestamp
+        let mut order: Vec<usize> = starts.clone();
+        for &e in &order {
+            dist[e] = 1;
+            sigma[e] = 1.0;
+        }
+        let mut next = 0;
+        while next < order.len() {
+            let e = order[next];
+            next += 1;
+            for &f in successors(e) {
+                if dist[f] == usize::MAX {
+                    dist[f] = dist[e] + 1;
+                    order.push(f);
+                }
+                if dist[f] == dist[e] + 1 {
+                    sigma[f] += sigma[e];
+                }
+            }
+        }
+
+        for &e in &order {
+            let v = edges[e].1;
+            if dist[e] < node_dist[v] {
+                node_dist[v] = dist[e];
+                node_sigma[v] = 0.0;
+            }
+            if dist[e] == node_dist[v] {
+                node_sigma[v] += sigma[e];
+            }
+        }
+
+        for &e in order.iter().rev() {
+            let v = edges[e].1;
+            let mut through = 0.0;
+            for &f in successors(e) {
+                if dist[f] == dist[e] + 1 {
+                    through += sigma[e] / sigma[f] * delta[f];
+                }
+            }
+            let ends_here = if v != s && dist[e] == node_dist[v] {
+                sigma[e] / node_sigma[v]
+            } else {
+                0.0
+            };
+            delta[e] = ends_here + through;
+            if v != s {
+                centralities[v] += through;
+            }
+        }
+
+        for &e in &order {
+            dist[e] = usize::MAX;
+            sigma[e] = 0.0;
+            delta[e] = 0.0;
+            node_dist[edges[e].1] = usize::MAX;
+            node_sigma[edges[e].1] = 0.0;
+        }
+    }
+
+    if normalized && n > 2 {
+        let scale = ((n - 1) * (n - 2)) as f64;
+        for c in &mut centralities {
+            *c /= scale;
+        }
+    }
+    Ok(BetweennessResult {
+        node_ids: index.node_ids,
+        centralities,
+    })
+}
+
+/// Result of temporal reachability computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemporalReachabilityResult {
+    pub node_ids: Vec<i64>,
+    /// Earliest time at which each node can be reached
+    pub arrival_times: Vec<i64>,
+}
+
+/// Find the nodes reachable from `source` by time-respecting paths.
+///
+/// Only edges with `t_start <= ts <= t_end` are used, and `source` is present
+/// from `t_start`. Each reached node is reported with the earliest time a path
+/// can arrive there, and the number of rows is the size of the set the source
+/// can reach. Edges are directed; add both directions for symmetric contacts.
+/// The source itself is not reported. Rows are sorted by node ID.
+pub fn temporal_reachability(
+    src: &[i64],
+    dst: &[i64],
+    ts: &[i64],
+    source: i64,
+    t_start: i64,
+    t_end: i64,
+) -> Result<TemporalReachabilityResult> {
+    check_timestamped_edges(src, dst, ts)?;
+    if t_start > t_end {
+        return Err(OnagerError::InvalidArgument(
+            "t_start must not be after t_end".to_string(),
+        ));
+    }
+    if !src.contains(&source) && !dst.contains(&source) {
+        return Err(OnagerError::NodeNotFound(source));
+    }
+
+    let mut order: Vec<usize> = (0..src.len())
+        .filter(|&i| (t_start..=t_end).contains(&ts[i]))
+        .collect();
+    order.sort_by_key(|&i| ts[i]);
+
+    // Edges are scanned in time order, so the first arrival is the earliest
+    let mut arrival: HashMap<i64, i64> = HashMap::new();
+    for i in order {
+        let departs = src[i] == source || arrival.get(&src[i]).is_some_and(|&t| t < ts[i]);
+        if departs && dst[i] != source {
+            arrival.entry(dst[i]).or_insert(ts[i]);
+        }
+    }
+
+    let mut reached: Vec<(i64, i64)> = arrival.into_iter().collect();
+    reached.sort_unstable();
+    Ok(TemporalReachabilityResult {
+        node_ids: reached.iter().map(|&(node, _)| node).collect(),
+        arrival_times: reached.iter().map(|&(_, t)| t).collect(),
+    })
+}
+
+/// Result of session path extraction.
+///
+/// Paths are flattened into rows. Row `i` is the node at `steps[i]` (starting
+/// at 0) of session `session_ids[i]`, which belongs to `entity_ids[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPathResult {
+    pub session_ids: Vec<i64>,
+    pub entity_ids: Vec<i64>,
+    pub steps: Vec<i64>,
+    pub node_ids: Vec<i64>,
+}
+
+/// Split the edges of each source entity into sessions and return the
+/// sequence of nodes each session visits.
+///
+/// Edge `i` records that entity `src[i]` visited node `dst[i]` at time `ts[i]`,
+/// for example a user opening a page. Each entity's edges are ordered by time,
+/// and a new session starts whenever more than `session_gap` time units pass
+/// between two consecutive edges. Edges with the same timestamp keep their
+/// input order. Sessions are numbered from 0, ordered by entity and then by
+/// start time.
+pub fn extract_paths(
+    src: &[i64],
+    dst: &[i64],
+    ts: &[i64],
+    session_gap: i64,
+) -> Result<SessionPathResult> {
+    check_timestamped_edges(src, dst, ts)?;
+    if session_gap < 0 {
+        return Err(OnagerError::InvalidArgument(
+            "session_gap must not be negative".to_string(),
+        ));
+    }
+
+    let mut order: Vec<usize> = (0..src.len()).collect();
+    order.sort_by_key(|&i| (src[i], ts[i]));
+
+    let mut result = SessionPathResult {
+        session_ids: Vec::with_capacity(order.len()),
+        entity_ids: Vec::with_capacity(order.len()),
+        steps: Vec::with_capacity(order.len()),
+        node_ids: Vec::with_capacity(order.len()),
+    };
+    let mut session = -1;
+    let mut step = 0;
+    let mut previous: Option<usize> = None;
+    for i in order {
+        let continues = previous.is_some_and(|p| {
+            src[p] == src[i] && (ts[i] as i128 - ts[p] as i128) <= session_gap as i128
+        });
+        if continues {
+            step += 1;
+        } else {
+            session += 1;
+            step = 0;
+        }
+        result.session_ids.push(session);
+        result.entity_ids.push(src[i]);
+        result.steps.push(step);
+        result.node_ids.push(dst[i]);
+        previous = Some(i);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(result: &BetweennessResult, node: i64) -> f64 {
+        let i = result.node_ids.iter().position(|&n| n == node).unwrap();
+        result.centralities[i]
+    }
+
+    #[test]
+    fn test_temporal_betweenness_follows_time_order() {
+        // 1 -> 2 -> 3 respects time, 3 -> 4 happens before anyone reaches 3
+        let src = vec![1, 2, 3];
+        let dst = vec![2, 3, 4];
+        let ts = vec![1, 2, 0];
+        let result = compute_temporal_betweenness(&src, &dst, &ts, false).unwrap();
+
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert!((score(&result, 2) - 1.0).abs() < 1e-9);
+        assert_eq!(score(&result, 3), 0.0);
+    }
+
+    #[test]
+    fn test_temporal_betweenness_splits_between_paths() {
+        // Two shortest paths from 1 to 4, through 2 and through 3
+        let src = vec![1, 1, 2, 3];
+        let dst = vec![2, 3, 4, 4];
+        let ts = vec![1, 1, 2, 2];
+        let result = compute_temporal_betweenness(&src, &dst, &ts, false).unwrap();
+        assert!((score(&result, 2) - 0.5).abs() < 1e-9);
+        assert!((score(&result, 3) - 0.5).abs() < 1e-9);
+
+        let normalized = compute_temporal_betweenness(&src, &dst, &ts, true).unwrap();
+        assert!((score(&normalized, 2) - 0.5 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_temporal_reachability() {
+        let src = vec![1, 2, 3, 2, 5];
+        let dst = vec![2, 3, 4, 5, 6];
+        let ts = vec![5, 6, 4, 6, 6];
+        let result = temporal_reachability(&src, &dst, &ts, 1, 0, 10).unwrap();
+
+        // 3 -> 4 happens before 3 is reached, and 5 -> 6 at the same time as 2 -> 5
+        assert_eq!(result.node_ids, vec![2, 3, 5]);
+        assert_eq!(result.arrival_times, vec![5, 6, 6]);
+
+        let late = temporal_reachability(&src, &dst, &ts, 1, 6, 10).unwrap();
+        assert!(late.node_ids.is_empty());
+    }
+
+    #[test]
+    fn test_extract_paths_splits_sessions() {
+        // User 7 visits 10, 11, then 12 after a long pause; user 8 visits 20
+        let src = vec![7, 8, 7, 7];
+        let dst = vec![11, 20, 10, 12];
+        let ts = vec![5, 1, 0, 100];
+        let result = extract_paths(&src, &dst, &ts, 30).unwrap();
+
+        assert_eq!(result.session_ids, vec![0, 0, 1, 2]);
+        assert_eq!(result.entity_ids, vec![7, 7, 7, 8]);
+        assert_eq!(result.steps, vec![0, 1, 0, 0]);
+        assert_eq!(result.node_ids, vec![10, 11, 12, 20]);
+
+        let single = extract_paths(&src, &dst, &ts, 1000).unwrap();
+        assert_eq!(single.session_ids, vec![0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_temporal_invalid_input() {
+        assert!(compute_temporal_betweenness(&[1], &[2], &[], false).is_err());
+        assert!(temporal_reachability(&[1], &[2], &[0], 1, 5, 0).is_err());
+        assert!(temporal_reachability(&[1], &[2], &[0], 9, 0, 5).is_err());
+        assert!(extract_paths(&[1], &[2], &[0], -1).is_err());
+    }
+}
@@ -1,16 +1,22 @@
 //! Graph traversal and path algorithms module.
 //!
-//! Dijkstra, Bellman-Ford, BFS, DFS.
+//! Dijkstra, Bellman-Ford, shortest path reconstruction, BFS, DFS, topological
+//! sort, and cost-bounded reachability.
 
 use graphina::core::paths::{bellman_ford, dijkstra, floyd_warshall};
-use graphina::core::types::{Graph, NodeId};
+use graphina::core::types::Graph;
 use graphina::traversal::algorithms::{bfs, dfs};
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 
+use super::centrality::{bounded_dijkstra, WeightedAdjacency};
+use crate::builder::{index_edges, GraphBuilder, NodeIndex};
 use crate::error::{OnagerError, Result};
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 
 /// Result of Dijkstra shortest path computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DijkstraResult {
     pub node_ids: Vec<i64>,
     pub distances: Vec<f64>,
@@ -29,35 +35,20 @@ pub fn compute_dijkstra(src: &[i64], dst: &[i64], source_node: i64) -> Result<Di
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
-    }
+    let built =
+        GraphBuilder::new(src, dst)?.build::<Graph<i64, OrderedFloat<f64>>>(|_| OrderedFloat(1.0));
 
-    let source_id = node_set.get(&source_node).ok_or_else(|| {
+    let source_id = built.graph_id(source_node).ok_or_else(|| {
         OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
     })?;
     let distances =
-        dijkstra(&graph, *source_id).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+        dijkstra(&built.graph, source_id).map_err(|e| OnagerError::GraphError(e.to_string()))?;
 
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_dist = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        let dist = distances.get(int_id).and_then(|d| *d);
+    let mut result_nodes = Vec::with_capacity(built.node_count());
+    let mut result_dist = Vec::with_capacity(built.node_count());
+    for (ext_id, int_id) in built.nodes() {
+        result_nodes.push(ext_id);
+        let dist = distances.get(&int_id).and_then(|d| *d);
         result_dist.push(dist.map(|d| d.into_inner()).unwrap_or(f64::INFINITY));
     }
     Ok(DijkstraResult {
@@ -67,6 +58,7 @@ pub fn compute_dijkstra(src: &[i64], dst: &[i64], source_node: i64) -> Result<Di
 }
 
 /// Result of BFS traversal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BfsResult {
     pub node_ids: Vec<i64>,
     pub order: Vec<i64>,
@@ -85,30 +77,13 @@ pub fn compute_bfs(src: &[i64], dst: &[i64], source_node: i64) -> Result<BfsResu
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let source_id = node_set.get(&source_node).ok_or_else(|| {
+    let source_id = built.graph_id(source_node).ok_or_else(|| {
         OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
     })?;
-    let traversal = bfs(&graph, *source_id);
+    let traversal = bfs(&built.graph, source_id);
 
     let mut order = Vec::new();
     for internal_id in &traversal {
@@ -123,6 +98,7 @@ pub fn compute_bfs(src: &[i64], dst: &[i64], source_node: i64) -> Result<BfsResu
 }
 
 /// Result of DFS traversal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DfsResult {
     pub node_ids: Vec<i64>,
     pub order: Vec<i64>,
@@ -141,30 +117,13 @@ pub fn compute_dfs(src: &[i64], dst: &[i64], source_node: i64) -> Result<DfsResu
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, f64> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, 1.0);
-    }
+    let built = GraphBuilder::new(src, dst)?.build::<Graph<i64, f64>>(|_| 1.0);
+    let reverse_map = built.reverse_map();
 
-    let source_id = node_set.get(&source_node).ok_or_else(|| {
+    let source_id = built.graph_id(source_node).ok_or_else(|| {
         OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
     })?;
-    let traversal = dfs(&graph, *source_id);
+    let traversal = dfs(&built.graph, source_id);
 
     let mut order = Vec::new();
     for internal_id in &traversal {
@@ -178,14 +137,19 @@ pub fn compute_dfs(src: &[i64], dst: &[i64], source_node: i64) -> Result<DfsResu
     })
 }
 
-/// Compute shortest distance between two specific nodes.
-/// Returns f64::INFINITY if unreachable, or the distance if reachable.
-pub fn compute_shortest_distance(
-    src: &[i64],
-    dst: &[i64],
-    source_node: i64,
-    target_node: i64,
-) -> Result<f64> {
+/// Result of a topological sort.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologicalSortResult {
+    pub node_ids: Vec<i64>,
+}
+
+/// Directed edges over compact node indices, numbered by first appearance.
+struct DirectedEdges {
+    nodes: NodeIndex,
+    edges: Vec<(usize, usize)>,
+}
+
+fn index_directed_edges(src: &[i64], dst: &[i64]) -> Result<DirectedEdges> {
     if src.len() != dst.len() {
         return Err(OnagerError::InvalidArgument(
             "src and dst arrays must have same length".to_string(),
@@ -197,39 +161,187 @@ pub fn compute_shortest_distance(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
+    let nodes = NodeIndex::in_edge_order(src, dst);
+    let edges = src
+        .iter()
+        .zip(dst)
+        .map(|(&s, &d)| (nodes.of(s), nodes.of(d)))
+        .collect();
+    Ok(DirectedEdges { nodes, edges })
+}
+
+/// Kahn's algorithm. Returns the nodes that can be ordered, which is every
+/// node exactly when the graph has no cycle.
+fn kahn_order(graph: &DirectedEdges) -> Vec<usize> {
+    let n = graph.nodes.len();
+    let mut out: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut in_degree = vec![0usize; n];
+    for &(u, v) in &graph.edges {
+        out[u].push(v);
+        in_degree[v] += 1;
+    }
+
+    // Among ready nodes, take the one that appeared first in the edges
+    let mut ready: BinaryHeap<Reverse<usize>> =
+        (0..n).filter(|&v| in_degree[v] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(u)) = ready.pop() {
+        order.push(u);
+        for &v in &out[u] {
+            in_degree[v] -= 1;
+            if in_degree[v] == 0 {
+                ready.push(Reverse(v));
+            }
         }
     }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(1.0));
+    order
+}
+
+/// Compute a topological order of a directed graph.
+///
+/// Every edge goes from a node to a later node in the result. Among nodes
+/// that can come next, the one that appears first in the edges is chosen, so
+/// the order is deterministic. Returns an error naming one cycle if the graph
+/// is not a DAG.
+pub fn compute_topological_sort(src: &[i64], dst: &[i64]) -> Result<TopologicalSortResult> {
+    let graph = index_directed_edges(src, dst)?;
+    let order = kahn_order(&graph);
+    let n = graph.nodes.len();
+    if order.len() == n {
+        return Ok(TopologicalSortResult {
+            node_ids: order.iter().map(|&v| graph.nodes.node_ids[v]).collect(),
+        });
+    }
+
+    // Every node left out has a predecessor that was also left out, so
+    // walking back through them must eventually repeat a node
+    let mut ordered = vec![false; n];
+    for &v in &order {
+        ordered[v] = true;
+    }
+    let mut pred = vec![usize::MAX; n];
+    for &(u, v) in &graph.edges {
+        if !ordered[u] && !ordered[v] {
+            pred[v] = u;
+        }
+    }
+    let start = (0..n).find(|&v| !ordered[v]).unwrap_or_default();
+    let mut position = vec![usize::MAX; n];
+    let mut walk = Vec::new();
+    let mut v = start;
+    while position[v] == usize::MAX {
+        position[v] = walk.len();
+        walk.push(v);
+        v = pred[v];
     }
+    // The walk follows edges backwards, so reverse it and close the cycle at v
+    let mut cycle = vec![v];
+    cycle.extend(walk[position[v] + 1..].iter().rev());
+    cycle.push(v);
+    let names: Vec<String> = cycle
+        .iter()
+        .map(|&v| graph.nodes.node_ids[v].to_string())
+        .collect();
+    Err(OnagerError::GraphError(format!(
+        "Graph has a cycle: {}",
+        names.join(" -> ")
+    )))
+}
+
+/// Returns whether a directed graph has no cycles.
+pub fn is_dag(src: &[i64], dst: &[i64]) -> Result<bool> {
+    let graph = index_directed_edges(src, dst)?;
+    Ok(kahn_order(&graph).len() == graph.nodes.len())
+}
+
+/// Compute shortest distance between two specific nodes.
+/// Returns f64::INFINITY if unreachable, or the distance if reachable.
+///
+/// Uses bidirectional Dijkstra, so only the neighborhoods of the two nodes
+/// are explored instead of the whole graph.
+pub fn compute_shortest_distance(
+    src: &[i64],
+    dst: &[i64],
+    source_node: i64,
+    target_node: i64,
+) -> Result<f64> {
+    compute_shortest_distance_weighted(src, dst, &[], source_node, target_node)
+}
 
-    let source_id = node_set.get(&source_node).ok_or_else(|| {
+/// Compute the weighted shortest distance between two specific nodes.
+///
+/// Edges are treated as undirected and weights must be non-negative. Empty
+/// `weights` means unit weights. Returns f64::INFINITY if unreachable.
+pub fn compute_shortest_distance_weighted(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    source_node: i64,
+    target_node: i64,
+) -> Result<f64> {
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+    let find = |node: i64| indexed.nodes.get(node);
+    let source = find(source_node).ok_or_else(|| {
         OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
     })?;
-    let target_id = node_set.get(&target_node).ok_or_else(|| {
+    let target = find(target_node).ok_or_else(|| {
         OnagerError::InvalidArgument(format!("Target node {} not found", target_node))
     })?;
+    Ok(bidirectional_dijkstra(&adj, source, target))
+}
 
-    let distances =
-        dijkstra(&graph, *source_id).map_err(|e| OnagerError::GraphError(e.to_string()))?;
+/// Run Dijkstra from both ends at once and stop when the searches meet.
+///
+/// Each step expands the side with the smaller frontier. The search stops
+/// once the two smallest frontier distances add up to at least the best
+/// path seen, which then is the shortest.
+fn bidirectional_dijkstra(adj: &WeightedAdjacency, source: usize, target: usize) -> f64 {
+    if source == target {
+        return 0.0;
+    }
+    let n = adj.node_ids.len();
+    let mut dist = [vec![f64::INFINITY; n], vec![f64::INFINITY; n]];
+    let mut settled = [vec![false; n], vec![false; n]];
+    let mut heaps = [BinaryHeap::new(), BinaryHeap::new()];
+    for (side, start) in [(0, source), (1, target)] {
+        dist[side][start] = 0.0;
+        heaps[side].push(Reverse((OrderedFloat(0.0), start)));
+    }
 
-    let dist = distances.get(target_id).and_then(|d| *d);
-    Ok(dist.map(|d| d.into_inner()).unwrap_or(f64::INFINITY))
+    let mut best = f64::INFINITY;
+    while let (Some(Reverse((OrderedFloat(top_f), _))), Some(Reverse((OrderedFloat(top_b), _)))) =
+        (heaps[0].peek(), heaps[1].peek())
+    {
+        if top_f + top_b >= best {
+            break;
+        }
+        let side = if heaps[0].len() <= heaps[1].len() {
+            0
+        } else {
+            1
+        };
+        let Some(Reverse((OrderedFloat(d), u))) = heaps[side].pop() else {
+            break;
+        };
+        if settled[side][u] || d > dist[side][u] {
+            continue;
+        }
+        settled[side][u] = true;
+        for &(v, w) in &adj.neighbors[u] {
+            let alt = d + w;
+            if alt < dist[side][v] {
+                dist[side][v] = alt;
+                heaps[side].push(Reverse((OrderedFloat(alt), v)));
+            }
+            best = best.min(alt + dist[1 - side][v]);
+        }
+    }
+    best
 }
 
 /// Result of Bellman-Ford shortest path computation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BellmanFordResult {
     pub node_ids: Vec<i64>,
     pub distances: Vec<f64>,
@@ -254,36 +366,21 @@ pub fn compute_bellman_ford(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(weights[i]));
-    }
+    let built = GraphBuilder::new(src, dst)?
+        .build::<Graph<i64, OrderedFloat<f64>>>(|i| OrderedFloat(weights[i]));
 
-    let source_id = node_set.get(&source_node).ok_or_else(|| {
+    let source_id = built.graph_id(source_node).ok_or_else(|| {
         OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
     })?;
 
-    let distances = bellman_ford(&graph, *source_id)
+    let distances = bellman_ford(&built.graph, source_id)
         .ok_or_else(|| OnagerError::GraphError("Negative cycle detected".to_string()))?;
 
-    let mut result_nodes = Vec::with_capacity(node_set.len());
-    let mut result_dist = Vec::with_capacity(node_set.len());
-    for (ext_id, int_id) in &node_set {
-        result_nodes.push(*ext_id);
-        let dist = distances.get(int_id).and_then(|d| *d);
+    let mut result_nodes = Vec::with_capacity(built.node_count());
+    let mut result_dist = Vec::with_capacity(built.node_count());
+    for (ext_id, int_id) in built.nodes() {
+        result_nodes.push(ext_id);
+        let dist = distances.get(&int_id).and_then(|d| *d);
         result_dist.push(dist.map(|d| d.into_inner()).unwrap_or(f64::INFINITY));
     }
     Ok(BellmanFordResult {
@@ -292,7 +389,167 @@ pub fn compute_bellman_ford(
     })
 }
 
+/// Result of a single-pair shortest path computation.
+///
+/// `node_ids` is the path from source to target, and `distances[i]` is the
+/// distance from the source to `node_ids[i]`, so the last row holds the
+/// length of the whole path. Both are empty if the target is unreachable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortestPathResult {
+    pub node_ids: Vec<i64>,
+    pub distances: Vec<f64>,
+}
+
+/// Compute the shortest path between two nodes as an ordered node sequence.
+///
+/// Uses Dijkstra when every weight is non-negative and Bellman-Ford
+/// otherwise, keeping a predecessor per node to rebuild the path. Empty
+/// `weights` means unit weights. Without `directed`, every edge can be used
+/// both ways, so a negative weight forms a negative cycle with itself.
+/// Returns an error if a negative cycle is reachable from the source.
+pub fn compute_shortest_path(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    source_node: i64,
+    target_node: i64,
+    directed: bool,
+) -> Result<ShortestPathResult> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights array must be empty or match edge count".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite()) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite, got {}",
+            w
+        )));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let source = nodes.get(source_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
+    })?;
+    let target = nodes.get(target_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Target node {} not found", target_node))
+    })?;
+    let mut arcs = Vec::with_capacity(2 * src.len());
+    for (i, (&u, &v)) in src.iter().zip(dst).enumerate() {
+        let (u, v, w) = (
+            nodes.of(u),
+            nodes.of(v),
+            weights.get(i).copied().unwrap_or(1.0),
+        );
+        arcs.push((u, v, w));
+        if !directed && u != v {
+            arcs.push((v, u, w));
+        }
+    }
+
+    let n = nodes.len();
+    let (dist, parent) = if arcs.iter().all(|&(_, _, w)| w >= 0.0) {
+        dijkstra_parents(n, &arcs, source, target)
+    } else {
+        bellman_ford_parents(n, &arcs, source)?
+    };
+
+    let mut path = Vec::new();
+    if dist[target].is_finite() {
+        let mut current = Some(target);
+        while let Some(v) = current {
+            path.push(v);
+            current = parent[v];
+        }
+        path.reverse();
+    }
+    Ok(ShortestPathResult {
+        node_ids: path.iter().map(|&v| nodes.node_ids[v]).collect(),
+        distances: path.iter().map(|&v| dist[v]).collect(),
+    })
+}
+
+/// Dijkstra over `(from, to, weight)` arcs with non-negative weights, stopping
+/// once `target` is settled. Returns distances and predecessors.
+fn dijkstra_parents(
+    n: usize,
+    arcs: &[(usize, usize, f64)],
+    source: usize,
+    target: usize,
+) -> (Vec<f64>, Vec<Option<usize>>) {
+    let mut out: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+    for &(u, v, w) in arcs {
+        out[u].push((v, w));
+    }
+    let mut dist = vec![f64::INFINITY; n];
+    let mut parent = vec![None; n];
+    let mut settled = vec![false; n];
+    let mut heap = BinaryHeap::new();
+    dist[source] = 0.0;
+    heap.push(Reverse((OrderedFloat(0.0), source)));
+    while let Some(Reverse((OrderedFloat(d), u))) = heap.pop() {
+        if settled[u] {
+            continue;
+        }
+        settled[u] = true;
+        if u == target {
+            break;
+        }
+        for &(v, w) in &out[u] {
+            let alt = d + w;
+            if alt < dist[v] {
+                dist[v] = alt;
+                parent[v] = Some(u);
+                heap.push(Reverse((OrderedFloat(alt), v)));
+            }
+        }
+    }
+    (dist, parent)
+}
+
+/// Bellman-Ford over `(from, to, weight)` arcs. Returns distances and
+/// predecessors, or an error if a negative cycle is reachable from `source`.
+fn bellman_ford_parents(
+    n: usize,
+    arcs: &[(usize, usize, f64)],
+    source: usize,
+) -> Result<(Vec<f64>, Vec<Option<usize>>)> {
+    let mut dist = vec![f64::INFINITY; n];
+    let mut parent = vec![None; n];
+    dist[source] = 0.0;
+    for _ in 1..n {
+        let mut changed = false;
+        for &(u, v, w) in arcs {
+            if dist[u] + w < dist[v] {
+                dist[v] = dist[u] + w;
+                parent[v] = Some(u);
+                changed = true;
+            }
+        }
+        if !changed {
+            return Ok((dist, parent));
+        }
+    }
+    if arcs.iter().any(|&(u, v, w)| dist[u] + w < dist[v]) {
+        return Err(OnagerError::GraphError(
+            "Negative cycle detected".to_string(),
+        ));
+    }
+    Ok((dist, parent))
+}
+
 /// Result of Floyd-Warshall all-pairs shortest paths.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FloydWarshallResult {
     pub src_nodes: Vec<i64>,
     pub dst_nodes: Vec<i64>,
@@ -316,28 +573,11 @@ pub fn compute_floyd_warshall(
         ));
     }
 
-    let mut node_set: HashMap<i64, NodeId> = HashMap::new();
-    let mut reverse_map: HashMap<NodeId, i64> = HashMap::new();
-    let mut graph: Graph<i64, OrderedFloat<f64>> = Graph::new();
-
-    for &node in src.iter().chain(dst.iter()) {
-        if !node_set.contains_key(&node) {
-            let id = graph.add_node(node);
-            node_set.insert(node, id);
-            reverse_map.insert(id, node);
-        }
-    }
-    for i in 0..src.len() {
-        let src_id = *node_set.get(&src[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Source node {} not found in graph", src[i]))
-        })?;
-        let dst_id = *node_set.get(&dst[i]).ok_or_else(|| {
-            OnagerError::InvalidArgument(format!("Destination node {} not found in graph", dst[i]))
-        })?;
-        graph.add_edge(src_id, dst_id, OrderedFloat(weights[i]));
-    }
+    let built = GraphBuilder::new(src, dst)?
+        .build::<Graph<i64, OrderedFloat<f64>>>(|i| OrderedFloat(weights[i]));
+    let reverse_map = built.reverse_map();
 
-    let distances = floyd_warshall(&graph)
+    let distances = floyd_warshall(&built.graph)
         .ok_or_else(|| OnagerError::GraphError("Negative cycle detected".to_string()))?;
 
     let mut result_src = Vec::new();
@@ -365,6 +605,388 @@ pub fn compute_floyd_warshall(
     })
 }
 
+/// Result of a shortest path tree computation.
+///
+/// Each row is a tree edge from `parents[i]` to `children[i]`, where
+/// `distances[i]` is the shortest distance from the source to the child.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortestPathTreeResult {
+    pub parents: Vec<i64>,
+    pub children: Vec<i64>,
+    pub distances: Vec<f64>,
+}
+
+/// Compute the shortest path tree from a source node with Dijkstra.
+///
+/// Edges are treated as undirected. Every node reachable from the source
+/// other than the source itself appears once as a child, so following parents
+/// from any node leads back to the source along a shortest path. Rows are in
+/// order of non-decreasing distance. When several shortest paths exist, the
+/// parent found first is kept. Empty `weights` means unit weights.
+pub fn compute_shortest_path_tree(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    source_node: i64,
+) -> Result<ShortestPathTreeResult> {
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+    let source = indexed.nodes.get(source_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
+    })?;
+    let tree = bounded_dijkstra(&adj, source, None);
+
+    let mut result = ShortestPathTreeResult {
+        parents: Vec::with_capacity(tree.order.len()),
+        children: Vec::with_capacity(tree.order.len()),
+        distances: Vec::with_capacity(tree.order.len()),
+    };
+    for &v in &tree.order {
+        if let Some(&parent) = tree.preds[v].first() {
+            result.parents.push(adj.node_ids[parent]);
+            result.children.push(adj.node_ids[v]);
+            result.distances.push(tree.dist[v]);
+        }
+    }
+    Ok(result)
+}
+
+/// Compute the nodes within a path cost budget of a source node.
+///
+/// Returns every node whose shortest distance from the source is at most
+/// `max_cost`, as in an isochrone around a depot or a store. Edges are
+/// treated as undirected, and empty `weights` means unit weights, so the
+/// budget then counts hops. Nodes are in order of non-decreasing distance,
+/// starting with the source.
+pub fn nodes_within_cost(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    source_node: i64,
+    max_cost: f64,
+) -> Result<DijkstraResult> {
+    if max_cost.is_nan() || max_cost < 0.0 {
+        return Err(OnagerError::InvalidArgument(format!(
+            "max_cost must be non-negative, got {}",
+            max_cost
+        )));
+    }
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+    let source = indexed.nodes.get(source_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
+    })?;
+    let tree = bounded_dijkstra(&adj, source, Some(max_cost));
+    Ok(DijkstraResult {
+        node_ids: tree.order.iter().map(|&v| adj.node_ids[v]).collect(),
+        distances: tree.order.iter().map(|&v| tree.dist[v]).collect(),
+    })
+}
+
+/// Result of a widest path computation.
+///
+/// `widths[i]` is the largest bottleneck (minimum edge weight) over all paths
+/// from the source to `node_ids[i]`. The source itself has infinite width.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WidestPathResult {
+    pub node_ids: Vec<i64>,
+    pub widths: Vec<f64>,
+}
+
+/// Widest path tree from a single source.
+struct WidestTree {
+    /// Nodes in the order they were settled (non-increasing width).
+    order: Vec<usize>,
+    /// Bottleneck width per node, `NEG_INFINITY` when unreachable.
+    width: Vec<f64>,
+    parent: Vec<Option<usize>>,
+}
+
+/// Modified Dijkstra that maximizes the minimum edge weight instead of
+/// minimizing the sum.
+fn widest_tree(adj: &WeightedAdjacency, source: usize) -> WidestTree {
+    let n = adj.node_ids.len();
+    let mut width = vec![f64::NEG_INFINITY; n];
+    let mut parent = vec![None; n];
+    let mut settled = vec![false; n];
+    let mut order = Vec::new();
+    let mut heap = BinaryHeap::new();
+    width[source] = f64::INFINITY;
+    heap.push((OrderedFloat(f64::INFINITY), source));
+    while let Some((OrderedFloat(w), u)) = heap.pop() {
+        if settled[u] {
+            continue;
+        }
+        settled[u] = true;
+        order.push(u);
+        for &(v, edge_w) in &adj.neighbors[u] {
+            let alt = w.min(edge_w);
+            if !settled[v] && alt > width[v] {
+                width[v] = alt;
+                parent[v] = Some(u);
+                heap.push((OrderedFloat(alt), v));
+            }
+        }
+    }
+    WidestTree {
+        order,
+        width,
+        parent,
+    }
+}
+
+/// Compute the widest path between two nodes.
+///
+/// The widest (or maximum bottleneck) path maximizes the minimum edge weight
+/// along the path, as in bandwidth or capacity routing. Edges are treated as
+/// undirected and weights must be non-negative. The result lists the path
+/// from source to target, with the width of each prefix, so the last row
+/// holds the width of the whole path. It is empty if the target is
+/// unreachable.
+pub fn compute_widest_path(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    source_node: i64,
+    target_node: i64,
+) -> Result<WidestPathResult> {
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+    let find = |node: i64| indexed.nodes.get(node);
+    let source = find(source_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
+    })?;
+    let target = find(target_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Target node {} not found", target_node))
+    })?;
+    let tree = widest_tree(&adj, source);
+
+    let mut path = Vec::new();
+    if tree.width[target] > f64::NEG_INFINITY {
+        let mut current = Some(target);
+        while let Some(v) = current {
+            path.push(v);
+            current = tree.parent[v];
+        }
+        path.reverse();
+    }
+    Ok(WidestPathResult {
+        node_ids: path.iter().map(|&v| adj.node_ids[v]).collect(),
+        widths: path.iter().map(|&v| tree.width[v]).collect(),
+    })
+}
+
+/// Compute widest path widths from a source to every reachable node.
+///
+/// Edges are treated as undirected and weights must be non-negative. Nodes
+/// are returned in order of non-increasing width, starting with the source.
+pub fn compute_widest_paths(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    source_node: i64,
+) -> Result<WidestPathResult> {
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+    let source = indexed.nodes.get(source_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
+    })?;
+    let tree = widest_tree(&adj, source);
+    Ok(WidestPathResult {
+        node_ids: tree.order.iter().map(|&v| adj.node_ids[v]).collect(),
+        widths: tree.order.iter().map(|&v| tree.width[v]).collect(),
+    })
+}
+
+/// Result of a path search restricted to some edge types.
+///
+/// `node_ids` is the path from source to target, and `edge_types[i]` is the
+/// type of the edge between `node_ids[i]` and `node_ids[i + 1]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypedPathResult {
+    pub node_ids: Vec<i64>,
+    pub edge_types: Vec<i64>,
+}
+
+/// Compute a path that only uses edges of the allowed types.
+///
+/// Answers queries such as "reachable using only road and ferry edges"
+/// without filtering the edge table first. Edges are treated as undirected,
+/// and the path has the fewest edges among paths of allowed types. The result
+/// is empty if the target is unreachable.
+pub fn compute_path_with_allowed_types(
+    src: &[i64],
+    dst: &[i64],
+    types: &[i64],
+    allowed_types: &[i64],
+    source_node: i64,
+    target_node: i64,
+) -> Result<TypedPathResult> {
+    if src.len() != dst.len() || src.len() != types.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and types arrays must have same length".to_string(),
+        ));
+    }
+    let allowed: HashSet<i64> = allowed_types.iter().copied().collect();
+    let nodes = NodeIndex::new(src, dst);
+    let mut neighbors: Vec<Vec<(usize, i64)>> = vec![Vec::new(); nodes.len()];
+    for i in 0..src.len() {
+        let (u, v) = (nodes.of(src[i]), nodes.of(dst[i]));
+        if allowed.contains(&types[i]) {
+            neighbors[u].push((v, types[i]));
+            neighbors[v].push((u, types[i]));
+        }
+    }
+    let source = nodes.get(source_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Source node {} not found", source_node))
+    })?;
+    let target = nodes.get(target_node).ok_or_else(|| {
+        OnagerError::InvalidArgument(format!("Target node {} not found", target_node))
+    })?;
+
+    // Breadth-first search, remembering the node and edge type each node was reached by
+    let mut parent: Vec<Option<(usize, i64)>> = vec![None; nodes.len()];
+    let mut visited = vec![false; nodes.len()];
+    let mut queue = VecDeque::from([source]);
+    visited[source] = true;
+    while let Some(u) = queue.pop_front() {
+        if u == target {
+            break;
+        }
+        for &(v, edge_type) in &neighbors[u] {
+            if !visited[v] {
+                visited[v] = true;
+                parent[v] = Some((u, edge_type));
+                queue.push_back(v);
+            }
+        }
+    }
+
+    let mut path = TypedPathResult {
+        node_ids: Vec::new(),
+        edge_types: Vec::new(),
+    };
+    if visited[target] {
+        let mut current = target;
+        path.node_ids.push(nodes.node_ids[current]);
+        while let Some((prev, edge_type)) = parent[current] {
+            path.edge_types.push(edge_type);
+            path.node_ids.push(nodes.node_ids[prev]);
+            current = prev;
+        }
+        path.node_ids.reverse();
+        path.edge_types.reverse();
+    }
+    Ok(path)
+}
+
+/// Result of a pairwise distance computation over a node subset.
+///
+/// Row `i` is the shortest distance from `src_nodes[i]` to `dst_nodes[i]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairwiseDistanceResult {
+    pub src_nodes: Vec<i64>,
+    pub dst_nodes: Vec<i64>,
+    pub distances: Vec<f64>,
+}
+
+/// Run Dijkstra from `source` and stop once `remaining` target nodes are settled.
+///
+/// Distances of settled targets are final, and unreachable targets keep
+/// f64::INFINITY.
+fn dijkstra_to_targets(
+    adj: &WeightedAdjacency,
+    source: usize,
+    is_target: impl Fn(usize) -> bool,
+    mut remaining: usize,
+) -> Vec<f64> {
+    let mut dist = vec![f64::INFINITY; adj.node_ids.len()];
+    let mut settled = vec![false; adj.node_ids.len()];
+    let mut heap = BinaryHeap::new();
+    dist[source] = 0.0;
+    heap.push(Reverse((OrderedFloat(0.0), source)));
+    while let Some(Reverse((OrderedFloat(d), u))) = heap.pop() {
+        if settled[u] || d > dist[u] {
+            continue;
+        }
+        settled[u] = true;
+        if is_target(u) {
+            remaining -= 1;
+            if remaining == 0 {
+                break;
+            }
+        }
+        for &(v, w) in &adj.neighbors[u] {
+            let alt = d + w;
+            if alt < dist[v] {
+                dist[v] = alt;
+                heap.push(Reverse((OrderedFloat(alt), v)));
+            }
+        }
+    }
+    dist
+}
+
+/// Compute shortest distances between every pair of nodes in a subset.
+///
+/// Edges are treated as undirected and weights must be non-negative (empty
+/// `weights` means unit weights). Each Dijkstra search stops as soon as the
+/// remaining subset nodes are settled, so the cost depends on the subset size
+/// and how far apart its nodes are rather than on all pairs of the graph. One
+/// row is returned per ordered pair of distinct subset nodes, in subset order,
+/// with f64::INFINITY for unreachable pairs. Duplicate subset nodes are ignored.
+pub fn compute_pairwise_distances(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    nodes: &[i64],
+) -> Result<PairwiseDistanceResult> {
+    let indexed = index_edges(src, dst, weights)?;
+    let adj = WeightedAdjacency::from_indexed(&indexed);
+    // Position of each graph node in the subset, if it is part of it
+    let mut slot: Vec<Option<usize>> = vec![None; adj.node_ids.len()];
+    let mut subset = Vec::new();
+    for &node in nodes {
+        let v = indexed
+            .nodes
+            .get(node)
+            .ok_or_else(|| OnagerError::InvalidArgument(format!("Node {} not found", node)))?;
+        if slot[v].is_none() {
+            slot[v] = Some(subset.len());
+            subset.push(v);
+        }
+    }
+
+    // Distances are symmetric, so each search only needs the later subset nodes
+    let k = subset.len();
+    let mut matrix = vec![f64::INFINITY; k * k];
+    for (i, &source) in subset.iter().enumerate() {
+        if i + 1 == k {
+            break;
+        }
+        let dist = dijkstra_to_targets(&adj, source, |v| slot[v].is_some_and(|j| j > i), k - i - 1);
+        for j in (i + 1)..k {
+            matrix[i * k + j] = dist[subset[j]];
+            matrix[j * k + i] = dist[subset[j]];
+        }
+    }
+
+    let rows = k * k.saturating_sub(1);
+    let mut result = PairwiseDistanceResult {
+        src_nodes: Vec::with_capacity(rows),
+        dst_nodes: Vec::with_capacity(rows),
+        distances: Vec::with_capacity(rows),
+    };
+    for i in 0..k {
+        for j in (0..k).filter(|&j| j != i) {
+            result.src_nodes.push(adj.node_ids[subset[i]]);
+            result.dst_nodes.push(adj.node_ids[subset[j]]);
+            result.distances.push(matrix[i * k + j]);
+        }
+    }
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -390,6 +1012,51 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_shortest_path_follows_predecessors() {
+        let src = vec![1, 1, 2, 3];
+        let dst = vec![2, 3, 3, 4];
+        let weights = vec![1.0, 4.0, 2.0, 1.0];
+
+        let result = compute_shortest_path(&src, &dst, &weights, 1, 4, false).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert_eq!(result.distances, vec![0.0, 1.0, 3.0, 4.0]);
+
+        // Walking the edges backwards is only possible when undirected
+        let back = compute_shortest_path(&src, &dst, &weights, 4, 1, false).unwrap();
+        assert_eq!(back.node_ids, vec![4, 3, 2, 1]);
+        let blocked = compute_shortest_path(&src, &dst, &weights, 4, 1, true).unwrap();
+        assert!(blocked.node_ids.is_empty());
+
+        let unit = compute_shortest_path(&src, &dst, &[], 1, 4, false).unwrap();
+        assert_eq!(unit.node_ids, vec![1, 3, 4]);
+        assert_eq!(unit.distances, vec![0.0, 1.0, 2.0]);
+
+        let same = compute_shortest_path(&src, &dst, &weights, 2, 2, false).unwrap();
+        assert_eq!(same.node_ids, vec![2]);
+        assert_eq!(same.distances, vec![0.0]);
+    }
+
+    #[test]
+    fn test_shortest_path_negative_weights() {
+        // The negative edge 3 -> 2 makes the longer way around shorter
+        let src = vec![1, 1, 3, 2];
+        let dst = vec![2, 3, 2, 4];
+        let weights = vec![4.0, 1.0, -2.0, 1.0];
+
+        let result = compute_shortest_path(&src, &dst, &weights, 1, 4, true).unwrap();
+        assert_eq!(result.node_ids, vec![1, 3, 2, 4]);
+        assert_eq!(result.distances, vec![0.0, 1.0, -1.0, 0.0]);
+
+        // Undirected, the negative edge is a cycle with itself
+        assert!(matches!(
+            compute_shortest_path(&src, &dst, &weights, 1, 4, false),
+            Err(OnagerError::GraphError(_))
+        ));
+        assert!(compute_shortest_path(&src, &dst, &weights, 99, 4, true).is_err());
+        assert!(compute_shortest_path(&src, &dst, &[1.0], 1, 4, true).is_err());
+    }
+
     #[test]
     fn test_dijkstra_simple() {
         let src = vec![1, 2, 3];
@@ -474,6 +1141,176 @@ mod tests {
         assert!(dist.is_infinite());
     }
 
+    #[test]
+    fn test_shortest_distance_weighted() {
+        // Direct edge 1-4 is heavier than the path 1-2-3-4
+        let src = vec![1, 2, 3, 1];
+        let dst = vec![2, 3, 4, 4];
+        let weights = vec![1.0, 1.0, 1.0, 5.0];
+        let dist = compute_shortest_distance_weighted(&src, &dst, &weights, 1, 4).unwrap();
+        assert_eq!(dist, 3.0);
+        let dist = compute_shortest_distance_weighted(&src, &dst, &weights, 4, 4).unwrap();
+        assert_eq!(dist, 0.0);
+    }
+
+    #[test]
+    fn test_shortest_distance_matches_shortest_path_tree() {
+        // Grid-like graph with varied weights
+        let mut src = Vec::new();
+        let mut dst = Vec::new();
+        let mut weights = Vec::new();
+        for r in 0..6i64 {
+            for c in 0..6i64 {
+                let node = r * 6 + c;
+                if c < 5 {
+                    src.push(node);
+                    dst.push(node + 1);
+                    weights.push(((node * 7) % 5 + 1) as f64);
+                }
+                if r < 5 {
+                    src.push(node);
+                    dst.push(node + 6);
+                    weights.push(((node * 3) % 4 + 1) as f64);
+                }
+            }
+        }
+        let tree = compute_shortest_path_tree(&src, &dst, &weights, 0).unwrap();
+        for (i, &child) in tree.children.iter().enumerate() {
+            let dist = compute_shortest_distance_weighted(&src, &dst, &weights, 0, child).unwrap();
+            assert!((dist - tree.distances[i]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_shortest_path_tree_weighted() {
+        // 1-2 (1.0), 2-3 (1.0), 1-3 (5.0), 3-4 (1.0)
+        let src = vec![1, 2, 1, 3];
+        let dst = vec![2, 3, 3, 4];
+        let weights = vec![1.0, 1.0, 5.0, 1.0];
+        let result = compute_shortest_path_tree(&src, &dst, &weights, 1).unwrap();
+        assert_eq!(result.children, vec![2, 3, 4]);
+        assert_eq!(result.parents, vec![1, 2, 3]);
+        assert_eq!(result.distances, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_shortest_path_tree_skips_unreachable() {
+        let result = compute_shortest_path_tree(&[1, 3], &[2, 4], &[], 1).unwrap();
+        assert_eq!(result.children, vec![2]);
+        assert!(compute_shortest_path_tree(&[1], &[2], &[], 9).is_err());
+    }
+
+    #[test]
+    fn test_topological_sort_orders_dependencies() {
+        // 1 -> 3, 2 -> 3, 3 -> 4, and 1 -> 4
+        let result = compute_topological_sort(&[1, 2, 3, 1], &[3, 3, 4, 4]).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert!(is_dag(&[1, 2, 3, 1], &[3, 3, 4, 4]).unwrap());
+    }
+
+    #[test]
+    fn test_topological_sort_names_cycle() {
+        // 1 -> 2 -> 3 -> 4 -> 2 with 5 hanging off the cycle
+        let (src, dst) = ([1, 2, 3, 4, 4], [2, 3, 4, 2, 5]);
+        let err = compute_topological_sort(&src, &dst)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("2 -> 3 -> 4 -> 2"), "{}", err);
+        assert!(!is_dag(&src, &dst).unwrap());
+
+        let err = compute_topological_sort(&[1], &[1])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1 -> 1"), "{}", err);
+        assert!(is_dag(&[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_nodes_within_cost() {
+        // 1-2 (1.0), 2-3 (1.0), 1-3 (5.0), 3-4 (1.0)
+        let src = vec![1, 2, 1, 3];
+        let dst = vec![2, 3, 3, 4];
+        let weights = vec![1.0, 1.0, 5.0, 1.0];
+        let result = nodes_within_cost(&src, &dst, &weights, 1, 2.0).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3]);
+        assert_eq!(result.distances, vec![0.0, 1.0, 2.0]);
+
+        // Unit weights count hops
+        let hops = nodes_within_cost(&src, &dst, &[], 4, 1.0).unwrap();
+        assert_eq!(hops.node_ids, vec![4, 3]);
+
+        assert!(nodes_within_cost(&src, &dst, &weights, 9, 1.0).is_err());
+        assert!(nodes_within_cost(&src, &dst, &weights, 1, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_widest_path_prefers_wide_detour() {
+        // Direct edge 1-4 is narrow, the detour 1-2-3-4 has bottleneck 5
+        let src = [1, 1, 2, 3, 5];
+        let dst = [4, 2, 3, 4, 6];
+        let weights = [2.0, 8.0, 5.0, 7.0, 9.0];
+        let result = compute_widest_path(&src, &dst, &weights, 1, 4).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert_eq!(result.widths, vec![f64::INFINITY, 8.0, 5.0, 5.0]);
+
+        let unreachable = compute_widest_path(&src, &dst, &weights, 1, 6).unwrap();
+        assert!(unreachable.node_ids.is_empty());
+        assert!(compute_widest_path(&src, &dst, &weights, 1, 99).is_err());
+    }
+
+    #[test]
+    fn test_widest_paths_to_all() {
+        let src = [1, 1, 2, 3, 5];
+        let dst = [4, 2, 3, 4, 6];
+        let weights = [2.0, 8.0, 5.0, 7.0, 9.0];
+        let result = compute_widest_paths(&src, &dst, &weights, 1).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert_eq!(result.widths, vec![f64::INFINITY, 8.0, 5.0, 5.0]);
+    }
+
+    #[test]
+    fn test_path_with_allowed_types() {
+        const ROAD: i64 = 1;
+        const FERRY: i64 = 2;
+        const RAIL: i64 = 3;
+        // Rail shortcut 1-4, road 1-2, ferry 2-3, road 3-4
+        let src = [1, 1, 2, 3];
+        let dst = [4, 2, 3, 4];
+        let types = [RAIL, ROAD, FERRY, ROAD];
+
+        let result =
+            compute_path_with_allowed_types(&src, &dst, &types, &[ROAD, FERRY], 1, 4).unwrap();
+        assert_eq!(result.node_ids, vec![1, 2, 3, 4]);
+        assert_eq!(result.edge_types, vec![ROAD, FERRY, ROAD]);
+
+        let shortcut = compute_path_with_allowed_types(&src, &dst, &types, &[RAIL], 4, 1).unwrap();
+        assert_eq!(shortcut.node_ids, vec![4, 1]);
+
+        let roads_only =
+            compute_path_with_allowed_types(&src, &dst, &types, &[ROAD], 1, 4).unwrap();
+        assert!(roads_only.node_ids.is_empty());
+        assert!(compute_path_with_allowed_types(&src, &dst, &types, &[ROAD], 1, 9).is_err());
+    }
+
+    #[test]
+    fn test_pairwise_distances_subset() {
+        // Path 1-2-3-4 with a heavy shortcut 1-4, plus a separate edge 8-9
+        let src = vec![1, 2, 3, 1, 8];
+        let dst = vec![2, 3, 4, 4, 9];
+        let weights = vec![1.0, 1.0, 1.0, 5.0, 1.0];
+        let result = compute_pairwise_distances(&src, &dst, &weights, &[4, 1, 8, 1]).unwrap();
+        assert_eq!(result.src_nodes, vec![4, 4, 1, 1, 8, 8]);
+        assert_eq!(result.dst_nodes, vec![1, 8, 4, 8, 4, 1]);
+        assert_eq!(result.distances[0], 3.0);
+        assert_eq!(result.distances[2], 3.0);
+        assert!(result.distances[1].is_infinite());
+        assert!(result.distances[5].is_infinite());
+
+        assert!(compute_pairwise_distances(&src, &dst, &weights, &[1, 99]).is_err());
+        let single = compute_pairwise_distances(&src, &dst, &weights, &[2]).unwrap();
+        assert!(single.src_nodes.is_empty());
+    }
+
     #[test]
     fn test_empty_graph_errors() {
         assert!(compute_dijkstra(&[], &[], 1).is_err());
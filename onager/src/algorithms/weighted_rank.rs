@@ -0,0 +1,299 @@
+//! Weighted ranking module.
+//!
+//! PageRank, personalized PageRank, and Katz centrality that follow edge
+//! weights, for example per-edge-type multipliers from `edge_type_weights`.
+
+use super::centrality::{check_katz_alpha, KatzResult, PageRankResult};
+use super::link_analysis::check_iteration_params;
+use super::personalized::PersonalizedPageRankResult;
+use crate::builder::{index_edges, IndexedEdges};
+use crate::error::{OnagerError, Result};
+
+/// Indexes edges that must carry one weight each.
+fn index_weighted_edges(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<IndexedEdges> {
+    if weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src, dst, and weights arrays must have same length".to_string(),
+        ));
+    }
+    index_edges(src, dst, weights)
+}
+
+fn l1_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum()
+}
+
+/// Weighted PageRank power iteration with teleport distribution `teleport`.
+///
+/// Undirected edges are followed both ways. The rank of nodes without
+/// outgoing weight is redistributed by `teleport`.
+fn power_iteration(
+    graph: &IndexedEdges,
+    directed: bool,
+    teleport: &[f64],
+    damping: f64,
+    max_iter: usize,
+    tolerance: f64,
+) -> Vec<f64> {
+    let n = graph.nodes.len();
+    let mut arcs = Vec::with_capacity(graph.edges.len() * 2);
+    for &(u, v, w) in &graph.edges {
+        arcs.push((u, v, w));
+        if !directed && u != v {
+            arcs.push((v, u, w));
+        }
+    }
+    let mut strength = vec![0.0; n];
+    for &(u, _, w) in &arcs {
+        strength[u] += w;
+    }
+
+    let mut ranks = vec![1.0 / n as f64; n];
+    for _ in 0..max_iter {
+        let dangling: f64 = (0..n)
+            .filter(|&u| strength[u] <= 0.0)
+            .map(|u| ranks[u])
+            .sum();
+        let mut next: Vec<f64> = teleport
+            .iter()
+            .map(|&p| (1.0 - damping + damping * dangling) * p)
+            .collect();
+        for &(u, v, w) in &arcs {
+            if w > 0.0 {
+                next[v] += damping * ranks[u] * w / strength[u];
+            }
+        }
+
+        let delta = l1_distance(&next, &ranks);
+        ranks = next;
+        if delta < tolerance {
+            break;
+        }
+    }
+    ranks
+}
+
+/// Compute PageRank over weighted edges.
+///
+/// Each node passes its rank to its neighbors in proportion to the edge
+/// weights, and parallel edges add up. With weights from `edge_type_weights`,
+/// a retweet with multiplier 3 passes three times as much rank as a follow
+/// with multiplier 1. Converges to a tolerance of 1e-6 like `compute_pagerank`.
+pub fn compute_weighted_pagerank(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+) -> Result<PageRankResult> {
+    if !(0.0..1.0).contains(&damping) {
+        return Err(OnagerError::InvalidArgument(
+            "damping must be in [0, 1)".to_string(),
+        ));
+    }
+    check_iteration_params(iterations, 0.0)?;
+    let graph = index_weighted_edges(src, dst, weights)?;
+    let n = graph.nodes.len();
+    let teleport = vec![1.0 / n as f64; n];
+    let ranks = power_iteration(&graph, directed, &teleport, damping, iterations, 1e-6);
+    Ok(PageRankResult {
+        node_ids: graph.nodes.node_ids,
+        ranks,
+    })
+}
+
+/// Compute personalized PageRank over weighted edges.
+///
+/// Edges are treated as undirected, as in `compute_personalized_pagerank`,
+/// and `personalization` follows the same rules: weights must be finite and
+/// non-negative, repeated nodes are summed, nodes not in the graph are
+/// ignored, and an empty slice means uniform teleportation.
+pub fn compute_weighted_personalized_pagerank(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    personalization: &[(i64, f64)],
+    damping: f64,
+    max_iter: usize,
+    tolerance: f64,
+) -> Result<PersonalizedPageRankResult> {
+    if !(0.0..1.0).contains(&damping) {
+        return Err(OnagerError::InvalidArgument(
+            "damping must be in [0, 1)".to_string(),
+        ));
+    }
+    check_iteration_params(max_iter, tolerance)?;
+    let graph = index_weighted_edges(src, dst, weights)?;
+    let n = graph.nodes.len();
+
+    let teleport = if personalization.is_empty() {
+        vec![1.0 / n as f64; n]
+    } else {
+        let mut p = vec![0.0; n];
+        for &(node, weight) in personalization {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(OnagerError::InvalidArgument(format!(
+                    "Personalization weight for node {} must be finite and non-negative, got {}",
+                    node, weight
+                )));
+            }
+            if let Some(i) = graph.nodes.get(node) {
+                p[i] += weight;
+            }
+        }
+        let total: f64 = p.iter().sum();
+        if total <= 0.0 {
+            return Err(OnagerError::InvalidArgument(
+                "Personalization weights must have a positive sum over graph nodes".to_string(),
+            ));
+        }
+        p.iter().map(|w| w / total).collect()
+    };
+
+    let scores = power_iteration(&graph, false, &teleport, damping, max_iter, tolerance);
+    Ok(PersonalizedPageRankResult {
+        node_ids: graph.nodes.node_ids,
+        scores,
+    })
+}
+
+/// Compute Katz centrality over weighted edges.
+///
+/// Edges are treated as undirected, as in `compute_katz`. Each node scores
+/// `alpha` times the weighted sum of its neighbors' scores plus 1, and the
+/// scores are scaled to unit Euclidean length. Fails up front when `alpha` is
+/// at least `1 / lambda_max` of the weighted graph, where the scores diverge,
+/// and otherwise if the iteration does not converge within `max_iter` steps.
+pub fn compute_weighted_katz(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    alpha: f64,
+    max_iter: usize,
+    tolerance: f64,
+) -> Result<KatzResult> {
+    if !alpha.is_finite() || alpha <= 0.0 {
+        return Err(OnagerError::InvalidArgument(
+            "alpha must be positive".to_string(),
+        ));
+    }
+    check_iteration_params(max_iter, tolerance)?;
+    let graph = index_weighted_edges(src, dst, weights)?;
+    check_katz_alpha(src, dst, weights, alpha)?;
+    let n = graph.nodes.len();
+
+    let mut scores = vec![0.0; n];
+    let mut converged = false;
+    for _ in 0..max_iter {
+        let mut next = vec![1.0; n];
+        for &(u, v, w) in &graph.edges {
+            next[v] += alpha * w * scores[u];
+            if u != v {
+                next[u] += alpha * w * scores[v];
+            }
+        }
+
+        let delta = l1_distance(&next, &scores);
+        if !delta.is_finite() {
+            return Err(OnagerError::GraphError(
+                "Katz centrality overflowed, try smaller edge weights".to_string(),
+            ));
+        }
+        scores = next;
+        if delta < n as f64 * tolerance {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(OnagerError::GraphError(format!(
+            "Katz centrality did not converge in {} iterations",
+            max_iter
+        )));
+    }
+
+    let norm = scores.iter().map(|x| x * x).sum::<f64>().sqrt();
+    Ok(KatzResult {
+        node_ids: graph.nodes.node_ids,
+        centralities: scores.iter().map(|x| x / norm).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::edge_type_weights;
+
+    const FOLLOW: i64 = 1;
+    const RETWEET: i64 = 2;
+
+    fn score(node_ids: &[i64], scores: &[f64], node: i64) -> f64 {
+        let i = node_ids.iter().position(|&n| n == node).unwrap();
+        scores[i]
+    }
+
+    #[test]
+    fn test_weighted_pagerank_type_multiplier_shifts_rank() {
+        // Node 1 follows 2 and retweets 3
+        let src = vec![1, 1];
+        let dst = vec![2, 3];
+        let types = vec![FOLLOW, RETWEET];
+
+        let weights = edge_type_weights(&types, &[(RETWEET, 3.0)]).unwrap();
+        let result = compute_weighted_pagerank(&src, &dst, &weights, 0.85, 100, true).unwrap();
+
+        let sum: f64 = result.ranks.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6);
+        let (r2, r3) = (
+            score(&result.node_ids, &result.ranks, 2),
+            score(&result.node_ids, &result.ranks, 3),
+        );
+        assert!(r3 > r2);
+
+        // With unit weights both targets are symmetric
+        let plain = compute_weighted_pagerank(&src, &dst, &[1.0, 1.0], 0.85, 100, true).unwrap();
+        let (r2, r3) = (
+            score(&plain.node_ids, &plain.ranks, 2),
+            score(&plain.node_ids, &plain.ranks, 3),
+        );
+        assert!((r2 - r3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weighted_personalized_pagerank_zero_weight_drops_edge() {
+        // Path 1-2-3 where the 2-3 edge has weight 0
+        let result = compute_weighted_personalized_pagerank(
+            &[1, 2],
+            &[2, 3],
+            &[1.0, 0.0],
+            &[(1, 1.0)],
+            0.85,
+            100,
+            1e-9,
+        )
+        .unwrap();
+
+        assert!(score(&result.node_ids, &result.scores, 3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_weighted_katz_triangle() {
+        let result =
+            compute_weighted_katz(&[1, 2, 3], &[2, 3, 1], &[1.0; 3], 0.1, 1000, 1e-9).unwrap();
+
+        let expected = 1.0 / 3.0_f64.sqrt();
+        for c in result.centralities {
+            assert!((c - expected).abs() < 1e-9);
+        }
+        // alpha above 1 / largest eigenvalue (2) diverges
+        assert!(compute_weighted_katz(&[1, 2, 3], &[2, 3, 1], &[1.0; 3], 0.6, 100, 1e-9).is_err());
+    }
+
+    #[test]
+    fn test_weighted_rank_invalid_input() {
+        assert!(compute_weighted_pagerank(&[1], &[2], &[-1.0], 0.85, 100, true).is_err());
+        assert!(compute_weighted_pagerank(&[1], &[2], &[], 0.85, 100, true).is_err());
+        assert!(compute_weighted_katz(&[1], &[2], &[1.0], 0.1, 0, 1e-6).is_err());
+    }
+}
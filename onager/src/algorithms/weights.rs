@@ -0,0 +1,383 @@
+//! Edge weight module.
+//!
+//! Transformations for preparing edge weights before weighted algorithms such
+//! as shortest paths and minimum spanning trees, and aggregation of repeated
+//! edges into one weighted edge.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{OnagerError, Result};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Transformation applied to every edge weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeightTransform {
+    /// `ln(1 + w)`, which compresses heavy-tailed weights. Requires `w >= 0`.
+    Log,
+    /// `1 / w`, which turns a similarity into a distance. Requires `w > 0`.
+    Inverse,
+    /// `(w - min) / (max - min)`, which rescales weights to `[0, 1]`.
+    MinMax,
+    /// `(w - mean) / std`, using the population standard deviation.
+    ZScore,
+}
+
+impl FromStr for WeightTransform {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "log" => Ok(WeightTransform::Log),
+            "inverse" => Ok(WeightTransform::Inverse),
+            "minmax" | "min_max" => Ok(WeightTransform::MinMax),
+            "zscore" | "z_score" => Ok(WeightTransform::ZScore),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown weight transform '{}', expected 'log', 'inverse', 'minmax', or 'zscore'",
+                s
+            ))),
+        }
+    }
+}
+
+/// How an algorithm should interpret edge weights.
+///
+/// Shortest path, minimum spanning tree, TSP, and distance-based centrality
+/// algorithms expect distances, where a smaller weight means a closer pair.
+/// Weights given as similarities, where a larger weight means a closer pair,
+/// must be converted with [`WeightSemantics::to_distances`] first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeightSemantics {
+    /// Weights are distances or costs.
+    #[default]
+    Distance,
+    /// Weights are similarities or strengths.
+    Similarity,
+}
+
+impl FromStr for WeightSemantics {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "distance" => Ok(WeightSemantics::Distance),
+            "similarity" => Ok(WeightSemantics::Similarity),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown weight semantics '{}', expected 'distance' or 'similarity'",
+                s
+            ))),
+        }
+    }
+}
+
+impl WeightSemantics {
+    /// Convert weights to distances.
+    ///
+    /// Distances are returned unchanged. Similarities are inverted with
+    /// `1 / w`, so they must be positive and finite.
+    pub fn to_distances(self, weights: &[f64]) -> Result<Vec<f64>> {
+        match self {
+            WeightSemantics::Distance => Ok(weights.to_vec()),
+            WeightSemantics::Similarity => transform_weights(weights, WeightTransform::Inverse),
+        }
+    }
+}
+
+/// Transform edge weights, returning a new array in the same order.
+///
+/// All weights must be finite. `MinMax` and `ZScore` map every weight to 0
+/// when all weights are equal. An empty input gives an empty output.
+pub fn transform_weights(weights: &[f64], method: WeightTransform) -> Result<Vec<f64>> {
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite()) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite, got {}",
+            w
+        )));
+    }
+    if weights.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match method {
+        WeightTransform::Log => weights
+            .iter()
+            .map(|&w| {
+                if w < 0.0 {
+                    Err(OnagerError::InvalidArgument(format!(
+                        "Log transform requires non-negative weights, got {}",
+                        w
+                    )))
+                } else {
+                    Ok(w.ln_1p())
+                }
+            })
+            .collect(),
+        WeightTransform::Inverse => weights
+            .iter()
+            .map(|&w| {
+                if w <= 0.0 {
+                    Err(OnagerError::InvalidArgument(format!(
+                        "Inverse transform requires positive weights, got {}",
+                        w
+                    )))
+                } else {
+                    Ok(1.0 / w)
+                }
+            })
+            .collect(),
+        WeightTransform::MinMax => {
+            let min = weights.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = weights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let range = max - min;
+            Ok(weights
+                .iter()
+                .map(|&w| if range > 0.0 { (w - min) / range } else { 0.0 })
+                .collect())
+        }
+        WeightTransform::ZScore => {
+            let n = weights.len() as f64;
+            let mean = weights.iter().sum::<f64>() / n;
+            let std = (weights.iter().map(|&w| (w - mean).powi(2)).sum::<f64>() / n).sqrt();
+            Ok(weights
+                .iter()
+                .map(|&w| if std > 0.0 { (w - mean) / std } else { 0.0 })
+                .collect())
+        }
+    }
+}
+
+/// Map each edge's type to the weight multiplier of that type.
+///
+/// `multipliers` holds `(edge_type, multiplier)` pairs, for example to count a
+/// retweet three times as much as a follow. Multipliers must be finite and
+/// non-negative, and each type may appear once. Edges whose type has no
+/// multiplier get weight 1.
+pub fn edge_type_weights(types: &[i64], multipliers: &[(i64, f64)]) -> Result<Vec<f64>> {
+    let mut by_type: HashMap<i64, f64> = HashMap::with_capacity(multipliers.len());
+    for &(edge_type, multiplier) in multipliers {
+        if !multiplier.is_finite() || multiplier < 0.0 {
+            return Err(OnagerError::InvalidArgument(format!(
+                "Multiplier for edge type {} must be finite and non-negative, got {}",
+                edge_type, multiplier
+            )));
+        }
+        if by_type.insert(edge_type, multiplier).is_some() {
+            return Err(OnagerError::InvalidArgument(format!(
+                "Edge type {} has more than one multiplier",
+                edge_type
+            )));
+        }
+    }
+    Ok(types
+        .iter()
+        .map(|t| by_type.get(t).copied().unwrap_or(1.0))
+        .collect())
+}
+
+/// How the weights of repeated edges between the same pair are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EdgeAggregation {
+    /// Total weight of the repeated edges.
+    Sum,
+    /// Number of repeated edges, ignoring their weights.
+    Count,
+    /// Average weight of the repeated edges.
+    Mean,
+    /// Largest weight of the repeated edges.
+    Max,
+}
+
+impl FromStr for EdgeAggregation {
+    type Err = OnagerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "sum" => Ok(EdgeAggregation::Sum),
+            "count" => Ok(EdgeAggregation::Count),
+            "mean" | "avg" => Ok(EdgeAggregation::Mean),
+            "max" => Ok(EdgeAggregation::Max),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown edge aggregation '{}', expected 'sum', 'count', 'mean', or 'max'",
+                s
+            ))),
+        }
+    }
+}
+
+/// Edges left after collapsing repeated pairs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AggregatedEdges {
+    pub src: Vec<i64>,
+    pub dst: Vec<i64>,
+    pub weights: Vec<f64>,
+}
+
+/// Collapse repeated `(src, dst)` pairs into one weighted edge each.
+///
+/// Works like `GROUP BY src, dst`: pairs are ordered, so `(u, v)` and `(v, u)`
+/// stay separate, and each pair is listed in the order it first appears.
+/// Empty weights mean every edge has weight 1. Weights must be finite.
+pub fn aggregate_parallel_edges(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    method: EdgeAggregation,
+) -> Result<AggregatedEdges> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Expected {} weights, got {}",
+            src.len(),
+            weights.len()
+        )));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite()) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite, got {}",
+            w
+        )));
+    }
+
+    let mut index: HashMap<(i64, i64), usize> = HashMap::new();
+    let mut result = AggregatedEdges::default();
+    let mut counts: Vec<usize> = Vec::new();
+    for (i, (&u, &v)) in src.iter().zip(dst).enumerate() {
+        let w = weights.get(i).copied().unwrap_or(1.0);
+        match index.get(&(u, v)) {
+            Some(&k) => {
+                counts[k] += 1;
+                let total = &mut result.weights[k];
+                *total = match method {
+                    EdgeAggregation::Max => total.max(w),
+                    _ => *total + w,
+                };
+            }
+            None => {
+                index.insert((u, v), result.src.len());
+                result.src.push(u);
+                result.dst.push(v);
+                result.weights.push(w);
+                counts.push(1);
+            }
+        }
+    }
+    match method {
+        EdgeAggregation::Count => {
+            for (w, &c) in result.weights.iter_mut().zip(&counts) {
+                *w = c as f64;
+            }
+        }
+        EdgeAggregation::Mean => {
+            for (w, &c) in result.weights.iter_mut().zip(&counts) {
+                *w /= c as f64;
+            }
+        }
+        EdgeAggregation::Sum | EdgeAggregation::Max => {}
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_log_and_inverse() {
+        let log = transform_weights(&[0.0, 1.0], WeightTransform::Log).unwrap();
+        assert_eq!(log[0], 0.0);
+        assert!((log[1] - 2f64.ln()).abs() < 1e-12);
+
+        let inverse = transform_weights(&[2.0, 0.5], WeightTransform::Inverse).unwrap();
+        assert_eq!(inverse, vec![0.5, 2.0]);
+        assert!(transform_weights(&[1.0, 0.0], WeightTransform::Inverse).is_err());
+        assert!(transform_weights(&[-1.0], WeightTransform::Log).is_err());
+    }
+
+    #[test]
+    fn test_transform_min_max() {
+        let result = transform_weights(&[2.0, 4.0, 6.0], WeightTransform::MinMax).unwrap();
+        assert_eq!(result, vec![0.0, 0.5, 1.0]);
+        let constant = transform_weights(&[3.0, 3.0], WeightTransform::MinMax).unwrap();
+        assert_eq!(constant, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_transform_z_score() {
+        let result = transform_weights(&[1.0, 3.0], WeightTransform::ZScore).unwrap();
+        assert!((result[0] + 1.0).abs() < 1e-12);
+        assert!((result[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_weight_semantics_to_distances() {
+        let weights = [4.0, 0.5];
+        assert_eq!(
+            WeightSemantics::Distance.to_distances(&weights).unwrap(),
+            vec![4.0, 0.5]
+        );
+        assert_eq!(
+            WeightSemantics::Similarity.to_distances(&weights).unwrap(),
+            vec![0.25, 2.0]
+        );
+        assert!(WeightSemantics::Similarity.to_distances(&[0.0]).is_err());
+        assert_eq!(
+            "Similarity".parse::<WeightSemantics>().unwrap(),
+            WeightSemantics::Similarity
+        );
+        assert!("cost".parse::<WeightSemantics>().is_err());
+    }
+
+    #[test]
+    fn test_transform_rejects_non_finite_and_unknown_method() {
+        assert!(transform_weights(&[f64::NAN], WeightTransform::MinMax).is_err());
+        assert!("sqrt".parse::<WeightTransform>().is_err());
+        assert_eq!(
+            "Z_SCORE".parse::<WeightTransform>().unwrap(),
+            WeightTransform::ZScore
+        );
+    }
+
+    #[test]
+    fn test_edge_type_weights() {
+        let weights = edge_type_weights(&[1, 2, 3, 2], &[(2, 3.0), (3, 0.0)]).unwrap();
+        assert_eq!(weights, vec![1.0, 3.0, 0.0, 3.0]);
+        assert!(edge_type_weights(&[1], &[(1, -1.0)]).is_err());
+        assert!(edge_type_weights(&[1], &[(1, 1.0), (1, 2.0)]).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_parallel_edges() {
+        let src = [1, 2, 1, 2, 1];
+        let dst = [2, 1, 2, 1, 3];
+        let weights = [1.0, 4.0, 3.0, 2.0, 5.0];
+        let aggregate = |method| aggregate_parallel_edges(&src, &dst, &weights, method).unwrap();
+
+        let sum = aggregate(EdgeAggregation::Sum);
+        assert_eq!((sum.src, sum.dst), (vec![1, 2, 1], vec![2, 1, 3]));
+        assert_eq!(sum.weights, vec![4.0, 6.0, 5.0]);
+        assert_eq!(
+            aggregate(EdgeAggregation::Count).weights,
+            vec![2.0, 2.0, 1.0]
+        );
+        assert_eq!(
+            aggregate(EdgeAggregation::Mean).weights,
+            vec![2.0, 3.0, 5.0]
+        );
+        assert_eq!(aggregate(EdgeAggregation::Max).weights, vec![3.0, 4.0, 5.0]);
+
+        let unweighted = aggregate_parallel_edges(&src, &dst, &[], EdgeAggregation::Sum).unwrap();
+        assert_eq!(unweighted.weights, vec![2.0, 2.0, 1.0]);
+
+        assert!(aggregate_parallel_edges(&src, &dst, &[1.0], EdgeAggregation::Sum).is_err());
+        assert!(aggregate_parallel_edges(&[1], &[2], &[f64::NAN], EdgeAggregation::Max).is_err());
+        assert_eq!(
+            "AVG".parse::<EdgeAggregation>().unwrap(),
+            EdgeAggregation::Mean
+        );
+        assert!("min".parse::<EdgeAggregation>().is_err());
+    }
+}
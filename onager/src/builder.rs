@@ -0,0 +1,445 @@
+//! Shared graph builder.
+//!
+//! Converts edge arrays into graphina graphs. Node IDs are collected by
+//! sorting and deduplicating the endpoints once, and every endpoint is then
+//! resolved by binary search instead of a hash map lookup per edge, so graphs
+//! with millions of edges are built in one pass over the arrays. Algorithms
+//! that work on plain arrays share the same indexing through [`NodeIndex`] and
+//! [`index_edges`].
+
+use graphina::core::types::{Digraph, Graph, NodeId};
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::error::{OnagerError, Result};
+
+/// Graph types the builder can fill.
+pub trait BuildTarget: Sized {
+    type Weight;
+
+    /// Creates an empty graph.
+    fn empty() -> Self;
+    /// Adds a node labelled with its external ID.
+    fn insert_node(&mut self, node: i64) -> NodeId;
+    /// Adds an edge between two nodes of this graph.
+    fn insert_edge(&mut self, src: NodeId, dst: NodeId, weight: Self::Weight);
+}
+
+macro_rules! impl_build_target {
+    ($graph:ident<$weight:ty>) => {
+        impl BuildTarget for $graph<i64, $weight> {
+            type Weight = $weight;
+
+            fn empty() -> Self {
+                $graph::new()
+            }
+
+            fn insert_node(&mut self, node: i64) -> NodeId {
+                self.add_node(node)
+            }
+
+            fn insert_edge(&mut self, src: NodeId, dst: NodeId, weight: $weight) {
+                self.add_edge(src, dst, weight);
+            }
+        }
+    };
+}
+
+impl_build_target!(Graph<f64>);
+impl_build_target!(Digraph<f64>);
+impl_build_target!(Graph<OrderedFloat<f64>>);
+impl_build_target!(Digraph<OrderedFloat<f64>>);
+
+/// Graph built from edge arrays, with the mapping between external node IDs
+/// and graph node IDs.
+pub struct BuiltGraph<G> {
+    pub graph: G,
+    /// External node IDs in ascending order
+    pub node_ids: Vec<i64>,
+    /// Graph node ID of each entry of `node_ids`
+    pub graph_ids: Vec<NodeId>,
+}
+
+impl<G> BuiltGraph<G> {
+    /// Returns the graph node ID of an external node ID.
+    pub fn graph_id(&self, node: i64) -> Option<NodeId> {
+        self.node_ids
+            .binary_search(&node)
+            .ok()
+            .map(|i| self.graph_ids[i])
+    }
+
+    /// Iterates over `(external ID, graph node ID)` pairs in ascending
+    /// external ID order.
+    pub fn nodes(&self) -> impl Iterator<Item = (i64, NodeId)> + '_ {
+        self.node_ids
+            .iter()
+            .copied()
+            .zip(self.graph_ids.iter().copied())
+    }
+
+    /// Returns a map from graph node IDs back to external node IDs.
+    pub fn reverse_map(&self) -> HashMap<NodeId, i64> {
+        self.graph_ids
+            .iter()
+            .copied()
+            .zip(self.node_ids.iter().copied())
+            .collect()
+    }
+
+    /// Returns a map from external node IDs to graph node IDs.
+    pub fn node_map(&self) -> HashMap<i64, NodeId> {
+        self.nodes().collect()
+    }
+
+    /// Returns the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.node_ids.len()
+    }
+
+    /// Looks up a score for every node, in `node_ids` order, with 0.0 for
+    /// nodes `score` has no value for.
+    pub fn scores(&self, score: impl Fn(NodeId) -> Option<f64>) -> Vec<f64> {
+        self.graph_ids
+            .iter()
+            .map(|&id| score(id).unwrap_or(0.0))
+            .collect()
+    }
+}
+
+/// Weighted graph built by [`graph_from_edges`].
+pub enum EdgeGraph {
+    Directed(BuiltGraph<Digraph<i64, f64>>),
+    Undirected(BuiltGraph<Graph<i64, f64>>),
+}
+
+/// Builds a directed or undirected weighted graph from edge arrays.
+///
+/// `weights` may be empty, in which case every edge has weight 1.0. The
+/// returned graph carries the maps between external and graph node IDs in
+/// both directions, see [`BuiltGraph::graph_id`] and [`BuiltGraph::reverse_map`].
+pub fn graph_from_edges(
+    src: &[i64],
+    dst: &[i64],
+    weights: &[f64],
+    directed: bool,
+) -> Result<EdgeGraph> {
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights must be empty or same length as edges".to_string(),
+        ));
+    }
+    let builder = GraphBuilder::new(src, dst)?;
+    let weight = |i: usize| if weights.is_empty() { 1.0 } else { weights[i] };
+    Ok(if directed {
+        EdgeGraph::Directed(builder.build(weight))
+    } else {
+        EdgeGraph::Undirected(builder.build(weight))
+    })
+}
+
+/// Builds graphina graphs from edge arrays.
+///
+/// Nodes are added in order of first appearance over `src` and then `dst`,
+/// the same order as adding them edge by edge, so seeded algorithms see the
+/// same graph.
+pub struct GraphBuilder<'a> {
+    src: &'a [i64],
+    dst: &'a [i64],
+    extra_nodes: &'a [i64],
+    dedup_edges: bool,
+}
+
+impl<'a> GraphBuilder<'a> {
+    /// Creates a builder over edges from `src[i]` to `dst[i]`.
+    pub fn new(src: &'a [i64], dst: &'a [i64]) -> Result<Self> {
+        if src.len() != dst.len() {
+            return Err(OnagerError::InvalidArgument(
+                "src and dst arrays must have same length".to_string(),
+            ));
+        }
+        Ok(Self {
+            src,
+            dst,
+            extra_nodes: &[],
+            dedup_edges: false,
+        })
+    }
+
+    /// Also adds these nodes, after the edge endpoints, even if no edge
+    /// touches them.
+    pub fn with_nodes(mut self, nodes: &'a [i64]) -> Self {
+        self.extra_nodes = nodes;
+        self
+    }
+
+    /// Keeps only the first of repeated `(src, dst)` edges.
+    pub fn dedup_edges(mut self, dedup: bool) -> Self {
+        self.dedup_edges = dedup;
+        self
+    }
+
+    /// Builds the graph, taking the weight of edge `i` from `weight(i)`.
+    pub fn build<G: BuildTarget>(&self, weight: impl Fn(usize) -> G::Weight) -> BuiltGraph<G> {
+        let endpoints = || {
+            self.src
+                .iter()
+                .chain(self.dst)
+                .chain(self.extra_nodes)
+                .copied()
+        };
+        let mut node_ids: Vec<i64> = endpoints().collect();
+        node_ids.sort_unstable();
+        node_ids.dedup();
+
+        let mut graph = G::empty();
+        let mut slots: Vec<Option<NodeId>> = vec![None; node_ids.len()];
+        for node in endpoints() {
+            if let Ok(i) = node_ids.binary_search(&node) {
+                if slots[i].is_none() {
+                    slots[i] = Some(graph.insert_node(node));
+                }
+            }
+        }
+        let graph_ids: Vec<NodeId> = slots.into_iter().flatten().collect();
+
+        let index = |node: i64| node_ids.binary_search(&node).ok();
+        let mut kept: Vec<usize> = (0..self.src.len()).collect();
+        if self.dedup_edges {
+            kept.sort_by_key(|&i| (self.src[i], self.dst[i], i));
+            kept.dedup_by_key(|i| (self.src[*i], self.dst[*i]));
+            kept.sort_unstable();
+        }
+        for i in kept {
+            if let (Some(s), Some(d)) = (index(self.src[i]), index(self.dst[i])) {
+                graph.insert_edge(graph_ids[s], graph_ids[d], weight(i));
+            }
+        }
+
+        BuiltGraph {
+            graph,
+            node_ids,
+            graph_ids,
+        }
+    }
+}
+
+/// Compact indices of the nodes of an edge list, for algorithms that run on
+/// plain arrays instead of graphina graphs.
+///
+/// [`NodeIndex::new`] numbers nodes in the same order as [`GraphBuilder`] adds
+/// them, by first appearance over `src` and then `dst`, while
+/// [`NodeIndex::in_edge_order`] numbers them edge by edge and
+/// [`NodeIndex::ascending`] by ID. Nodes are looked up by binary search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct NodeIndex {
+    /// External node IDs, in index order
+    pub(crate) node_ids: Vec<i64>,
+    /// External node IDs in ascending order
+    sorted: Vec<i64>,
+    /// Index of each entry of `sorted`
+    slots: Vec<usize>,
+}
+
+impl NodeIndex {
+    /// Indexes every node of `src` and `dst`.
+    pub(crate) fn new(src: &[i64], dst: &[i64]) -> Self {
+        Self::numbered(src, dst, src.iter().chain(dst).copied())
+    }
+
+    /// Indexes every node of `src` and `dst`, numbering them by first
+    /// appearance edge by edge, `src[0]`, `dst[0]`, `src[1]`, and so on.
+    pub(crate) fn in_edge_order(src: &[i64], dst: &[i64]) -> Self {
+        let order = src.iter().zip(dst).flat_map(|(&s, &d)| [s, d]);
+        Self::numbered(src, dst, order)
+    }
+
+    /// Indexes every node of `src` and `dst`, numbering them in ascending ID
+    /// order.
+    pub(crate) fn ascending(src: &[i64], dst: &[i64]) -> Self {
+        let mut sorted: Vec<i64> = src.iter().chain(dst).copied().collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+        NodeIndex {
+            node_ids: sorted.clone(),
+            slots: (0..sorted.len()).collect(),
+            sorted,
+        }
+    }
+
+    /// Numbers the nodes of `src` and `dst` in the order `order` yields them.
+    fn numbered(src: &[i64], dst: &[i64], order: impl Iterator<Item = i64>) -> Self {
+        let mut sorted: Vec<i64> = src.iter().chain(dst).copied().collect();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let mut slots = vec![usize::MAX; sorted.len()];
+        let mut node_ids = Vec::with_capacity(sorted.len());
+        for node in order {
+            if let Ok(i) = sorted.binary_search(&node) {
+                if slots[i] == usize::MAX {
+                    slots[i] = node_ids.len();
+                    node_ids.push(node);
+                }
+            }
+        }
+        NodeIndex {
+            node_ids,
+            sorted,
+            slots,
+        }
+    }
+
+    /// Returns the index of a node, or `None` if it is not in the edges.
+    pub(crate) fn get(&self, node: i64) -> Option<usize> {
+        self.sorted.binary_search(&node).ok().map(|i| self.slots[i])
+    }
+
+    /// Returns the index of a node taken from the indexed edges.
+    ///
+    /// # Panics
+    /// Panics if `node` is not in the edges.
+    pub(crate) fn of(&self, node: i64) -> usize {
+        self.get(node).expect("node is not in the indexed edges")
+    }
+
+    /// Returns the number of nodes.
+    pub(crate) fn len(&self) -> usize {
+        self.node_ids.len()
+    }
+}
+
+/// Weighted edges over compact node indices, built by [`index_edges`].
+pub(crate) struct IndexedEdges {
+    pub(crate) nodes: NodeIndex,
+    /// `(src, dst, weight)` of each input edge, in input order
+    pub(crate) edges: Vec<(usize, usize, f64)>,
+}
+
+/// Indexes a non-empty edge list with finite, non-negative weights.
+///
+/// `weights` may be empty, in which case every edge has weight 1.0.
+pub(crate) fn index_edges(src: &[i64], dst: &[i64], weights: &[f64]) -> Result<IndexedEdges> {
+    if src.len() != dst.len() {
+        return Err(OnagerError::InvalidArgument(
+            "src and dst arrays must have same length".to_string(),
+        ));
+    }
+    if !weights.is_empty() && weights.len() != src.len() {
+        return Err(OnagerError::InvalidArgument(
+            "weights must be empty or same length as edges".to_string(),
+        ));
+    }
+    if src.is_empty() {
+        return Err(OnagerError::InvalidArgument(
+            "Cannot compute on empty graph".to_string(),
+        ));
+    }
+    if let Some(&w) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Edge weights must be finite and non-negative, got {}",
+            w
+        )));
+    }
+
+    let nodes = NodeIndex::new(src, dst);
+    let edges = (0..src.len())
+        .map(|i| {
+            let w = if weights.is_empty() { 1.0 } else { weights[i] };
+            (nodes.of(src[i]), nodes.of(dst[i]), w)
+        })
+        .collect();
+    Ok(IndexedEdges { nodes, edges })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_maps_nodes_and_edges() {
+        let src = vec![30, 10, 30];
+        let dst = vec![10, 20, 20];
+        let built: BuiltGraph<Graph<i64, f64>> =
+            GraphBuilder::new(&src, &dst).unwrap().build(|i| i as f64);
+
+        assert_eq!(built.node_ids, vec![10, 20, 30]);
+        assert_eq!(built.graph.node_count(), 3);
+        assert_eq!(built.graph.edge_count(), 3);
+        let reverse = built.reverse_map();
+        for (node, id) in built.nodes() {
+            assert_eq!(built.graph_id(node), Some(id));
+            assert_eq!(reverse[&id], node);
+        }
+        assert_eq!(built.graph_id(40), None);
+    }
+
+    #[test]
+    fn test_build_dedups_edges_and_adds_isolated_nodes() {
+        let src = vec![1, 1, 2];
+        let dst = vec![2, 2, 1];
+        let isolated = vec![5];
+        let built: BuiltGraph<Digraph<i64, f64>> = GraphBuilder::new(&src, &dst)
+            .unwrap()
+            .with_nodes(&isolated)
+            .dedup_edges(true)
+            .build(|_| 1.0);
+
+        assert_eq!(built.node_ids, vec![1, 2, 5]);
+        // (1, 2) is kept once, while (2, 1) is a different directed edge
+        assert_eq!(built.graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_builder_rejects_mismatched_arrays() {
+        assert!(GraphBuilder::new(&[1], &[]).is_err());
+        assert!(graph_from_edges(&[1], &[2], &[1.0, 2.0], true).is_err());
+    }
+
+    #[test]
+    fn test_index_edges_matches_builder_order() {
+        let src = vec![30, 10, 30];
+        let dst = vec![10, 20, 20];
+        let indexed = index_edges(&src, &dst, &[]).unwrap();
+        assert_eq!(indexed.nodes.node_ids, vec![30, 10, 20]);
+        assert_eq!(indexed.edges, vec![(0, 1, 1.0), (1, 2, 1.0), (0, 2, 1.0)]);
+        assert_eq!(indexed.nodes.get(20), Some(2));
+        assert_eq!(indexed.nodes.get(40), None);
+
+        assert!(index_edges(&src, &dst, &[1.0, -1.0, 1.0]).is_err());
+        assert!(index_edges(&[], &[], &[]).is_err());
+
+        let in_edge_order = NodeIndex::in_edge_order(&src, &dst);
+        assert_eq!(in_edge_order.node_ids, vec![30, 10, 20]);
+        let in_edge_order = NodeIndex::in_edge_order(&[1, 2], &[3, 4]);
+        assert_eq!(in_edge_order.node_ids, vec![1, 3, 2, 4]);
+        assert_eq!(in_edge_order.of(4), 3);
+
+        let ascending = NodeIndex::ascending(&src, &dst);
+        assert_eq!(ascending.node_ids, vec![10, 20, 30]);
+        assert_eq!(ascending.of(30), 2);
+        assert_eq!(ascending.get(40), None);
+    }
+
+    #[test]
+    fn test_graph_from_edges_maps_both_ways() {
+        let src = vec![5, 6];
+        let dst = vec![6, 7];
+        let EdgeGraph::Directed(built) = graph_from_edges(&src, &dst, &[], true).unwrap() else {
+            panic!("expected a directed graph");
+        };
+        let forward = built.node_map();
+        let reverse = built.reverse_map();
+        for node in [5, 6, 7] {
+            assert_eq!(reverse[&forward[&node]], node);
+        }
+        let scores = built.scores(|id| (reverse[&id] != 6).then_some(1.0));
+        assert_eq!(scores, vec![1.0, 0.0, 1.0]);
+
+        assert!(matches!(
+            graph_from_edges(&src, &dst, &[2.0, 3.0], false),
+            Ok(EdgeGraph::Undirected(_))
+        ));
+    }
+}
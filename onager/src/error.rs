@@ -31,6 +31,10 @@ pub enum OnagerError {
     /// Serialization error.
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    /// File system error while writing results to disk.
+    #[error("I/O error: {0}")]
+    IoError(String),
 }
 
 impl From<serde_json::Error> for OnagerError {
@@ -38,3 +42,9 @@ impl From<serde_json::Error> for OnagerError {
         OnagerError::SerializationError(err.to_string())
     }
 }
+
+impl From<std::io::Error> for OnagerError {
+    fn from(err: std::io::Error) -> Self {
+        OnagerError::IoError(err.to_string())
+    }
+}
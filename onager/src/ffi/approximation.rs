@@ -3,7 +3,7 @@
 //! Maximum Clique, Independent Set, Vertex Cover.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
+use super::common::{edge_slices, input_slice};
 use crate::algorithms;
 
 /// Compute Maximum Clique Approximation.
@@ -14,28 +14,25 @@ pub extern "C" fn onager_compute_max_clique(
     edge_count: usize,
     out_nodes: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_max_clique(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_max_clique_v2(src_ptr, dst_ptr, edge_count, out_nodes, usize::MAX)
+}
+
+/// Capacity-checked variant of `onager_compute_max_clique`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_max_clique_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_max_clique(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -47,28 +44,25 @@ pub extern "C" fn onager_compute_independent_set(
     edge_count: usize,
     out_nodes: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_independent_set(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_independent_set_v2(src_ptr, dst_ptr, edge_count, out_nodes, usize::MAX)
+}
+
+/// Capacity-checked variant of `onager_compute_independent_set`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_independent_set_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_independent_set(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -80,28 +74,25 @@ pub extern "C" fn onager_compute_vertex_cover(
     edge_count: usize,
     out_nodes: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_vertex_cover(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_vertex_cover_v2(src_ptr, dst_ptr, edge_count, out_nodes, usize::MAX)
+}
+
+/// Capacity-checked variant of `onager_compute_vertex_cover`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_vertex_cover_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_vertex_cover(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -116,31 +107,38 @@ pub extern "C" fn onager_compute_tsp(
     out_tour: *mut i64,
     out_cost: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() || weight_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        let weights = unsafe { std::slice::from_raw_parts(weight_ptr, edge_count) };
-        match algorithms::compute_tsp(src, dst, weights) {
-            Ok(result) => {
-                let n = result.tour.len();
-                if !out_tour.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_tour, n) }
-                        .copy_from_slice(&result.tour);
-                }
-                if !out_cost.is_null() {
-                    unsafe { *out_cost = result.cost };
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
+    onager_compute_tsp_v2(
+        src_ptr,
+        dst_ptr,
+        weight_ptr,
+        edge_count,
+        out_tour,
+        out_cost,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_tsp`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_tsp_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    out_tour: *mut i64,
+    out_cost: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_tsp(src, dst, weights)?;
+        crate::ffi_write_outputs!(out_capacity; out_tour => result.tour);
+        if !out_cost.is_null() {
+            unsafe { *out_cost = result.cost };
         }
+        Ok(result.tour.len() as i64)
     })
 }
@@ -0,0 +1,76 @@
+//! Bipartite graph FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, optional_slice};
+use crate::algorithms;
+
+/// Check whether the graph is bipartite.
+///
+/// Returns 1 if it is, 0 if it is not, and -1 on error.
+#[no_mangle]
+pub extern "C" fn onager_compute_is_bipartite(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(i64::from(algorithms::compute_is_bipartite(src, dst)?))
+    })
+}
+
+/// Project a bipartite graph onto the nodes in `nodes_ptr`.
+///
+/// Each projected edge is written to `out_src` and `out_dst` with the number
+/// of shared neighbors in `out_weights`. Returns the number of projected
+/// edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_bipartite_projection(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    nodes_ptr: *const i64,
+    node_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weights: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let nodes = unsafe { optional_slice(nodes_ptr, node_count) };
+        let result = algorithms::compute_bipartite_projection(src, dst, nodes)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_weights => result.weights,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Compute a maximum matching of a bipartite graph.
+///
+/// Each matched pair is written to `out_src` and `out_dst` as the first
+/// input edge that joins it. Returns the number of matched pairs.
+#[no_mangle]
+pub extern "C" fn onager_compute_bipartite_matching(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_bipartite_matching(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
@@ -3,8 +3,10 @@
 //! PageRank, Degree, Betweenness, Closeness, Eigenvector, Katz, Harmonic.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
-use crate::algorithms;
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, input_slice, optional_slice, read_str};
+use crate::algorithms::{self, EigenvectorNormalization, ParallelEdges};
 
 /// Compute PageRank on edge arrays.
 #[no_mangle]
@@ -18,30 +20,46 @@ pub extern "C" fn onager_compute_pagerank(
     out_nodes: *mut i64,
     out_ranks: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer for src or dst");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_pagerank(src, dst, &[], damping, iterations, directed) {
-            Ok(result) => {
-                let node_count = result.node_ids.len();
-                if !out_nodes.is_null() && !out_ranks.is_null() {
-                    let out_n = unsafe { std::slice::from_raw_parts_mut(out_nodes, node_count) };
-                    let out_r = unsafe { std::slice::from_raw_parts_mut(out_ranks, node_count) };
-                    out_n.copy_from_slice(&result.node_ids);
-                    out_r.copy_from_slice(&result.ranks);
-                }
-                node_count as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_pagerank_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        std::ptr::null(),
+        0,
+        damping,
+        iterations,
+        directed,
+        out_nodes,
+        out_ranks,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_pagerank` with optional edge weights.
+///
+/// Empty weights mean every edge has weight 1.0. Returns the required output
+/// size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_pagerank_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+    out_nodes: *mut i64,
+    out_ranks: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result =
+            algorithms::compute_pagerank(src, dst, weights, damping, iterations, directed)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_ranks => result.ranks);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -59,37 +77,46 @@ pub extern "C" fn onager_compute_pagerank_parallel(
     out_node_ids: *mut i64,
     out_ranks: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        let weights = if weights_ptr.is_null() || weights_count == 0 {
-            &[]
-        } else {
-            unsafe { std::slice::from_raw_parts(weights_ptr, weights_count) }
-        };
-        match algorithms::compute_pagerank_parallel(
+    onager_compute_pagerank_parallel_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        weights_ptr,
+        weights_count,
+        damping,
+        iterations,
+        directed,
+        out_node_ids,
+        out_ranks,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_pagerank_parallel`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_pagerank_parallel_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+    out_node_ids: *mut i64,
+    out_ranks: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_pagerank_parallel(
             src, dst, weights, damping, iterations, directed,
-        ) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_node_ids.is_null() && !out_ranks.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_node_ids, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_ranks, n) }
-                        .copy_from_slice(&result.ranks);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+        )?;
+        crate::ffi_write_outputs!(out_capacity; out_node_ids => result.node_ids, out_ranks => result.ranks);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -104,32 +131,42 @@ pub extern "C" fn onager_compute_degree(
     out_in_degree: *mut f64,
     out_out_degree: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_degree(src, dst, directed) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_in_degree.is_null() && !out_out_degree.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_in_degree, n) }
-                        .copy_from_slice(&result.in_degrees);
-                    unsafe { std::slice::from_raw_parts_mut(out_out_degree, n) }
-                        .copy_from_slice(&result.out_degrees);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_degree_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        directed,
+        out_nodes,
+        out_in_degree,
+        out_out_degree,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_degree`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_degree_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    directed: bool,
+    out_nodes: *mut i64,
+    out_in_degree: *mut f64,
+    out_out_degree: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_degree(src, dst, directed)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_in_degree => result.in_degrees,
+            out_out_degree => result.out_degrees,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -141,21 +178,9 @@ pub extern "C" fn onager_compute_node_in_degree(
     edge_count: usize,
     node: i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_node_degree(src, dst, node) {
-            Ok(r) => r.in_degree,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(algorithms::compute_node_degree(src, dst, node)?.in_degree)
     })
 }
 
@@ -167,21 +192,9 @@ pub extern "C" fn onager_compute_node_out_degree(
     edge_count: usize,
     node: i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_node_degree(src, dst, node) {
-            Ok(r) => r.out_degree,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(algorithms::compute_node_degree(src, dst, node)?.out_degree)
     })
 }
 
@@ -195,30 +208,39 @@ pub extern "C" fn onager_compute_betweenness(
     out_nodes: *mut i64,
     out_centralities: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_betweenness(src, dst, normalized) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_centralities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_centralities, n) }
-                        .copy_from_slice(&result.centralities);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_betweenness_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        normalized,
+        out_nodes,
+        out_centralities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_betweenness`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_betweenness_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    normalized: bool,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_betweenness(src, dst, &[], normalized, false)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -231,30 +253,37 @@ pub extern "C" fn onager_compute_closeness(
     out_nodes: *mut i64,
     out_centralities: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_closeness(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_centralities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_centralities, n) }
-                        .copy_from_slice(&result.centralities);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_closeness_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_centralities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_closeness`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_closeness_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_closeness(src, dst, &[], false)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -269,30 +298,113 @@ pub extern "C" fn onager_compute_eigenvector(
     out_nodes: *mut i64,
     out_centralities: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_eigenvector(src, dst, max_iter, tolerance) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_centralities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_centralities, n) }
-                        .copy_from_slice(&result.centralities);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_eigenvector_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        max_iter,
+        tolerance,
+        out_nodes,
+        out_centralities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_eigenvector`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_eigenvector_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_eigenvector(src, dst, max_iter, tolerance, false)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute eigenvector centrality, following edge direction when `directed` is set.
+///
+/// Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_eigenvector_directed(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    max_iter: usize,
+    tolerance: f64,
+    directed: bool,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_eigenvector(src, dst, max_iter, tolerance, directed)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute eigenvector centrality on a weighted graph with an explicit normalization.
+///
+/// `normalization` is `l1`, `l2`, or `max`. Empty weights mean unit weights.
+/// Scores never sum to a negative value. Returns the number of nodes.
+///
+/// # Safety
+/// `normalization` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_eigenvector_weighted(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    max_iter: usize,
+    tolerance: f64,
+    directed: bool,
+    normalization: *const c_char,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let normalization: EigenvectorNormalization =
+            unsafe { read_str(normalization, "eigenvector normalization")? }.parse()?;
+        let result = algorithms::compute_eigenvector_weighted(
+            src,
+            dst,
+            weights,
+            max_iter,
+            tolerance,
+            directed,
+            normalization,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -308,30 +420,43 @@ pub extern "C" fn onager_compute_katz(
     out_nodes: *mut i64,
     out_centralities: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_katz(src, dst, alpha, max_iter, tolerance) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_centralities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_centralities, n) }
-                        .copy_from_slice(&result.centralities);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_katz_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        alpha,
+        max_iter,
+        tolerance,
+        out_nodes,
+        out_centralities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_katz`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_katz_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    alpha: f64,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_katz(src, dst, alpha, max_iter, tolerance)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -344,30 +469,281 @@ pub extern "C" fn onager_compute_harmonic(
     out_nodes: *mut i64,
     out_centralities: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
+    onager_compute_harmonic_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_centralities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_harmonic`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_harmonic_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_harmonic(src, dst, false)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute closeness centrality on weighted, optionally directed edges.
+///
+/// Empty weights mean unit weights and empty targets mean every node. A
+/// negative `cutoff` means no cutoff. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_closeness_general(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    directed: bool,
+    targets_ptr: *const i64,
+    target_count: usize,
+    cutoff: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let targets = unsafe { optional_slice(targets_ptr, target_count) };
+        let cutoff_opt = if cutoff < 0.0 { None } else { Some(cutoff) };
+        let result = algorithms::compute_closeness_general(
+            src, dst, weights, directed, targets, cutoff_opt,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute harmonic centrality on weighted, optionally directed edges.
+///
+/// Empty weights mean unit weights and empty targets mean every node. A
+/// negative `cutoff` means no cutoff. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_harmonic_general(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    directed: bool,
+    targets_ptr: *const i64,
+    target_count: usize,
+    cutoff: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let targets = unsafe { optional_slice(targets_ptr, target_count) };
+        let cutoff_opt = if cutoff < 0.0 { None } else { Some(cutoff) };
+        let result =
+            algorithms::compute_harmonic_general(src, dst, weights, directed, targets, cutoff_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute weighted betweenness centrality bounded by a distance cutoff.
+///
+/// Empty weights mean unit weights. A negative `cutoff` means no cutoff.
+#[no_mangle]
+pub extern "C" fn onager_compute_betweenness_cutoff(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    normalized: bool,
+    cutoff: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let cutoff_opt = if cutoff < 0.0 { None } else { Some(cutoff) };
+        let result =
+            algorithms::compute_betweenness_cutoff(src, dst, weights, normalized, cutoff_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute betweenness centrality on directed or undirected weighted multigraphs.
+///
+/// `parallel` is `min` or `count`. Empty weights mean unit weights. A negative
+/// `cutoff` means no cutoff.
+///
+/// # Safety
+/// `parallel` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_betweenness_general(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    directed: bool,
+    normalized: bool,
+    parallel: *const c_char,
+    cutoff: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let parallel: ParallelEdges =
+            unsafe { read_str(parallel, "parallel edge mode")? }.parse()?;
+        let cutoff_opt = if cutoff < 0.0 { None } else { Some(cutoff) };
+        let result = algorithms::compute_betweenness_general(
+            src, dst, weights, directed, normalized, parallel, cutoff_opt,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute betweenness centrality like `onager_compute_betweenness_general`
+/// and report how the scores were normalized.
+///
+/// `out_node_count` receives the number of nodes and `out_normalizer` the
+/// divisor applied to unnormalized scores (1 when not normalized), so
+/// `score * normalizer` recovers the unnormalized score. Either may be null.
+/// They are written whenever the computation succeeds, including size queries.
+///
+/// # Safety
+/// `parallel` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_betweenness_scaled(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    directed: bool,
+    normalized: bool,
+    parallel: *const c_char,
+    cutoff: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+    out_node_count: *mut i64,
+    out_normalizer: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let parallel: ParallelEdges =
+            unsafe { read_str(parallel, "parallel edge mode")? }.parse()?;
+        let cutoff_opt = if cutoff < 0.0 { None } else { Some(cutoff) };
+        let result = algorithms::compute_betweenness_general(
+            src, dst, weights, directed, normalized, parallel, cutoff_opt,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        let scaling = algorithms::betweenness_scaling(result.node_ids.len(), directed, normalized);
+        if !out_node_count.is_null() {
+            unsafe { *out_node_count = scaling.node_count as i64 };
         }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_harmonic(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_centralities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_centralities, n) }
-                        .copy_from_slice(&result.centralities);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
+        if !out_normalizer.is_null() {
+            unsafe { *out_normalizer = scaling.normalizer };
         }
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Select the indices of the `k` highest scores, sorted by descending score.
+///
+/// Returns the number of indices, which is `min(k, score_count)`.
+#[no_mangle]
+pub extern "C" fn onager_top_k_indices(
+    scores_ptr: *const f64,
+    score_count: usize,
+    k: usize,
+    out_indices: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let scores = unsafe { input_slice(scores_ptr, score_count, "scores")? };
+        let indices: Vec<i64> = algorithms::top_k_indices(scores, k)
+            .into_iter()
+            .map(|i| i as i64)
+            .collect();
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_indices => indices,
+        );
+        Ok(indices.len() as i64)
+    })
+}
+
+/// Select the indices of scores at or above `min_value`, in their original order.
+///
+/// Returns the number of indices. Call with null `out_indices` to get the count.
+#[no_mangle]
+pub extern "C" fn onager_min_value_indices(
+    scores_ptr: *const f64,
+    score_count: usize,
+    min_value: f64,
+    out_indices: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let scores = unsafe { input_slice(scores_ptr, score_count, "scores")? };
+        let indices: Vec<i64> = algorithms::min_value_indices(scores, min_value)
+            .into_iter()
+            .map(|i| i as i64)
+            .collect();
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_indices => indices,
+        );
+        Ok(indices.len() as i64)
     })
 }
 
@@ -380,28 +756,33 @@ pub extern "C" fn onager_compute_voterank(
     num_seeds: usize,
     out_nodes: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_voterank(src, dst, num_seeds) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_voterank_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        num_seeds,
+        out_nodes,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_voterank`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_voterank_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    num_seeds: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_voterank(src, dst, num_seeds)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -415,30 +796,39 @@ pub extern "C" fn onager_compute_local_reaching(
     out_nodes: *mut i64,
     out_centralities: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_local_reaching(src, dst, distance) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_centralities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_centralities, n) }
-                        .copy_from_slice(&result.centrality);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_local_reaching_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        distance,
+        out_nodes,
+        out_centralities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_local_reaching`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_local_reaching_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    distance: usize,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_local_reaching(src, dst, distance)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centrality,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -451,29 +841,36 @@ pub extern "C" fn onager_compute_laplacian(
     out_nodes: *mut i64,
     out_centralities: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_laplacian(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_centralities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_centralities, n) }
-                        .copy_from_slice(&result.centrality);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_laplacian_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_centralities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_laplacian`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_laplacian_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_laplacian(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centrality,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
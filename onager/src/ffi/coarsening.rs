@@ -0,0 +1,66 @@
+//! Graph coarsening FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, optional_slice, read_str};
+use crate::algorithms::{self, CoarsenMethod};
+
+/// Coarsen a graph by edge matching for up to `levels` levels.
+///
+/// `method` is `heavy_edge` or `random`, and a negative `seed` means a random
+/// seed. Empty weights mean unit weights. Each coarse edge is written as a row
+/// of `out_edge_levels`, `out_src`, `out_dst`, and `out_weights`. Each fine to
+/// coarse node mapping is written as a row of `out_map_levels`, `out_fine`,
+/// and `out_coarse` when `out_map_capacity` is large enough, and the number of
+/// mapping rows is written to `out_map_count` when non-null. Returns the
+/// number of coarse edges.
+///
+/// # Safety
+/// `method` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_coarsen_graph(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    levels: usize,
+    method: *const c_char,
+    seed: i64,
+    out_edge_levels: *mut i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weights: *mut f64,
+    out_capacity: usize,
+    out_map_levels: *mut i64,
+    out_fine: *mut i64,
+    out_coarse: *mut i64,
+    out_map_capacity: usize,
+    out_map_count: *mut i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let method: CoarsenMethod = unsafe { read_str(method, "coarsening method")? }.parse()?;
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::coarsen_graph(src, dst, weights, levels, method, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_edge_levels => result.edge_levels,
+            out_src => result.src,
+            out_dst => result.dst,
+            out_weights => result.weights,
+        );
+        crate::ffi_write_outputs!(
+            out_map_capacity;
+            out_map_levels => result.map_levels,
+            out_fine => result.fine_nodes,
+            out_coarse => result.coarse_nodes,
+        );
+        if !out_map_count.is_null() {
+            unsafe { *out_map_count = result.fine_nodes.len() as i64 };
+        }
+        Ok(result.src.len() as i64)
+    })
+}
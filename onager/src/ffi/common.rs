@@ -7,6 +7,7 @@ use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::panic;
 
+use crate::error::{OnagerError, Result};
 use crate::graph;
 
 /// Wraps an FFI function body with catch_unwind to prevent panics from crossing FFI boundary.
@@ -45,9 +46,146 @@ macro_rules! ffi_catch_unwind {
     };
 }
 
+/// Runs an FFI function body that returns a `Result`.
+///
+/// Clears the last error, catches panics, and converts an `Err` into the
+/// provided error value after recording its message with `set_last_error`.
+/// The body is evaluated inside a closure, so `?` can be used freely.
+#[macro_export]
+macro_rules! ffi_call {
+    ($error_val:expr, $body:block) => {{
+        $crate::ffi::common::clear_last_error();
+        $crate::ffi_catch_unwind!($error_val, {
+            #[allow(clippy::redundant_closure_call)]
+            let result: $crate::error::Result<_> = (|| $body)();
+            match result {
+                Ok(value) => value,
+                Err(e) => {
+                    $crate::ffi::common::set_last_error(&e.to_string());
+                    $error_val
+                }
+            }
+        })
+    }};
+}
+
+/// Copies results into caller-provided output buffers.
+///
+/// Nothing is written unless every buffer pointer is non-null, which lets
+/// callers pass null pointers to query the result size first. With a leading
+/// capacity argument, a result longer than the capacity returns an error from
+/// the enclosing function before anything is written, so buffers are never
+/// written past their end.
+#[macro_export]
+macro_rules! ffi_write_outputs {
+    ($capacity:expr; $($ptr:expr => $data:expr),+ $(,)?) => {
+        if $(!$ptr.is_null())&&+ {
+            let capacity: usize = $capacity;
+            let needed = 0usize $(.max($data.len()))+;
+            if needed > capacity {
+                return Err($crate::error::OnagerError::InvalidArgument(format!(
+                    "Output buffers hold {} rows but the result needs {}",
+                    capacity, needed
+                )));
+            }
+            $(unsafe { $crate::ffi::common::write_output($ptr, &$data) };)+
+        }
+    };
+    ($($ptr:expr => $data:expr),+ $(,)?) => {
+        if $(!$ptr.is_null())&&+ {
+            $(unsafe { $crate::ffi::common::write_output($ptr, &$data) };)+
+        }
+    };
+}
+
+/// Borrows the edge source and destination arrays.
+///
+/// # Safety
+/// Non-null pointers must point to at least `edge_count` initialized values.
+pub(crate) unsafe fn edge_slices<'a>(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+) -> Result<(&'a [i64], &'a [i64])> {
+    if src_ptr.is_null() || dst_ptr.is_null() {
+        return Err(OnagerError::InvalidArgument(
+            "Null pointer for src or dst".to_string(),
+        ));
+    }
+    let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
+    let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
+    Ok((src, dst))
+}
+
+/// Borrows a required input array, failing on a null pointer.
+///
+/// # Safety
+/// A non-null pointer must point to at least `len` initialized values.
+pub(crate) unsafe fn input_slice<'a, T>(ptr: *const T, len: usize, name: &str) -> Result<&'a [T]> {
+    if ptr.is_null() {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Null pointer for {}",
+            name
+        )));
+    }
+    Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+}
+
+/// Borrows an optional input array, returning an empty slice for a null
+/// pointer or a zero length.
+///
+/// # Safety
+/// A non-null pointer must point to at least `len` initialized values.
+pub(crate) unsafe fn optional_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(ptr, len) }
+    }
+}
+
+/// Copies `data` into an output buffer.
+///
+/// # Safety
+/// `ptr` must be valid for `data.len()` writes.
+pub(crate) unsafe fn write_output<T: Copy>(ptr: *mut T, data: &[T]) {
+    unsafe { std::slice::from_raw_parts_mut(ptr, data.len()) }.copy_from_slice(data);
+}
+
+/// Reads a graph name from a C string.
+///
+/// # Safety
+/// A non-null pointer must point to a valid null-terminated C string.
+pub(crate) unsafe fn read_graph_name<'a>(ptr: *const c_char) -> Result<&'a str> {
+    unsafe { read_str(ptr, "graph name") }
+}
+
+/// Reads a UTF-8 string argument from a C string, naming it `what` in errors.
+///
+/// # Safety
+/// A non-null pointer must point to a valid null-terminated C string.
+pub(crate) unsafe fn read_str<'a>(ptr: *const c_char, what: &str) -> Result<&'a str> {
+    if ptr.is_null() {
+        return Err(OnagerError::InvalidArgument(format!(
+            "Null pointer for {}",
+            what
+        )));
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| OnagerError::InvalidArgument(format!("Invalid UTF-8 in {}", what)))
+}
+
 /// Version string for the extension.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Version of the C ABI.
+///
+/// Bumped on every breaking change to an exported function signature or
+/// behavior. The replaced symbols stay exported as forwarding shims for one
+/// release. Must match `ONAGER_ABI_VERSION` in `functions.hpp`.
+pub const ABI_VERSION: u32 = 1;
+
 // Thread-local last error message.
 // Each thread has its own error state, preventing concurrent queries from
 // overwriting each other's errors.
@@ -106,27 +244,22 @@ pub extern "C" fn onager_get_version() -> *mut c_char {
         .unwrap_or(std::ptr::null_mut())
 }
 
+/// Returns the C ABI version, which callers compare against the version they
+/// were built for before calling any other function.
+#[no_mangle]
+pub extern "C" fn onager_abi_version() -> u32 {
+    ABI_VERSION
+}
+
 /// Creates a new graph with the given name.
 /// # Safety
 /// The name pointer must be a valid null-terminated C string.
 #[no_mangle]
 pub unsafe extern "C" fn onager_create_graph(name: *const c_char, directed: bool) -> i32 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::create_graph(name, directed) {
-            Ok(()) => 0,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(name)? };
+        graph::create_graph(name, directed)?;
+        Ok(0)
     })
 }
 
@@ -135,41 +268,21 @@ pub unsafe extern "C" fn onager_create_graph(name: *const c_char, directed: bool
 /// The name pointer must be a valid null-terminated C string.
 #[no_mangle]
 pub unsafe extern "C" fn onager_drop_graph(name: *const c_char) -> i32 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::drop_graph(name) {
-            Ok(()) => 0,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(name)? };
+        graph::drop_graph(name)?;
+        Ok(0)
     })
 }
 
 /// Returns a JSON array of all graph names.
 #[no_mangle]
 pub extern "C" fn onager_list_graphs() -> *mut c_char {
-    clear_last_error();
-    crate::ffi_catch_unwind!(std::ptr::null_mut(), {
-        let graphs = graph::list_graphs();
-        let json = match serde_json::to_string(&graphs) {
-            Ok(s) => s,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                return std::ptr::null_mut();
-            }
-        };
-        CString::new(json)
+    crate::ffi_call!(std::ptr::null_mut(), {
+        let json = serde_json::to_string(&graph::list_graphs())?;
+        Ok(CString::new(json)
             .map(|s| s.into_raw())
-            .unwrap_or(std::ptr::null_mut())
+            .unwrap_or(std::ptr::null_mut()))
     })
 }
 
@@ -178,22 +291,10 @@ pub extern "C" fn onager_list_graphs() -> *mut c_char {
 /// The graph_name pointer must be a valid null-terminated C string.
 #[no_mangle]
 pub unsafe extern "C" fn onager_add_node(graph_name: *const c_char, node_id: i64) -> i32 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(graph_name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::add_node(name, node_id) {
-            Ok(()) => 0,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        graph::add_node(name, node_id)?;
+        Ok(0)
     })
 }
 
@@ -207,22 +308,10 @@ pub unsafe extern "C" fn onager_add_edge(
     dst: i64,
     weight: f64,
 ) -> i32 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(graph_name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::add_edge(name, src, dst, weight) {
-            Ok(()) => 0,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        graph::add_edge(name, src, dst, weight)?;
+        Ok(0)
     })
 }
 
@@ -231,22 +320,9 @@ pub unsafe extern "C" fn onager_add_edge(
 /// The graph_name pointer must be a valid null-terminated C string.
 #[no_mangle]
 pub unsafe extern "C" fn onager_node_count(graph_name: *const c_char) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(graph_name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::node_count(name) {
-            Ok(count) => count as i64,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(graph::node_count(name)? as i64)
     })
 }
 
@@ -255,22 +331,9 @@ pub unsafe extern "C" fn onager_node_count(graph_name: *const c_char) -> i64 {
 /// The graph_name pointer must be a valid null-terminated C string.
 #[no_mangle]
 pub unsafe extern "C" fn onager_edge_count(graph_name: *const c_char) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(graph_name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::edge_count(name) {
-            Ok(count) => count as i64,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(graph::edge_count(name)? as i64)
     })
 }
 
@@ -279,22 +342,9 @@ pub unsafe extern "C" fn onager_edge_count(graph_name: *const c_char) -> i64 {
 /// The graph_name pointer must be a valid null-terminated C string.
 #[no_mangle]
 pub unsafe extern "C" fn onager_graph_node_in_degree(graph_name: *const c_char, node: i64) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(graph_name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::get_node_in_degree(name, node) {
-            Ok(degree) => degree as i64,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(graph::get_node_in_degree(name, node)? as i64)
     })
 }
 
@@ -303,21 +353,241 @@ pub unsafe extern "C" fn onager_graph_node_in_degree(graph_name: *const c_char,
 /// The graph_name pointer must be a valid null-terminated C string.
 #[no_mangle]
 pub unsafe extern "C" fn onager_graph_node_out_degree(graph_name: *const c_char, node: i64) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        let name = match unsafe { CStr::from_ptr(graph_name) }.to_str() {
-            Ok(s) => s,
-            Err(_) => {
-                set_last_error("Invalid UTF-8 in graph name");
-                return -1;
-            }
-        };
-        match graph::get_node_out_degree(name, node) {
-            Ok(degree) => degree as i64,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(graph::get_node_out_degree(name, node)? as i64)
+    })
+}
+
+/// Returns the summary metrics of the named graph as a JSON object.
+/// # Safety
+/// The graph_name pointer must be a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_graph_info(graph_name: *const c_char) -> *mut c_char {
+    crate::ffi_call!(std::ptr::null_mut(), {
+        let name = unsafe { read_graph_name(graph_name)? };
+        let json = serde_json::to_string(&graph::graph_info(name)?)?;
+        Ok(CString::new(json)
+            .map(|s| s.into_raw())
+            .unwrap_or(std::ptr::null_mut()))
+    })
+}
+
+/// Returns 1 if two nodes of the named graph are in the same connected
+/// component, 0 if not, or -1 on error. Edge direction is ignored.
+/// # Safety
+/// The graph_name pointer must be a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_same_component(graph_name: *const c_char, a: i64, b: i64) -> i32 {
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(i32::from(graph::same_component(name, a, b)?))
+    })
+}
+
+/// Returns the number of connected components in the named graph, ignoring
+/// edge direction.
+/// # Safety
+/// The graph_name pointer must be a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_component_count(graph_name: *const c_char) -> i64 {
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(graph::component_count(name)? as i64)
+    })
+}
+
+/// Returns the version of the named graph, or -1 on error.
+///
+/// The version increases whenever a node or edge is added, so callers can
+/// cache results and compare versions to detect that the graph changed.
+/// # Safety
+/// The graph_name pointer must be a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_graph_version(graph_name: *const c_char) -> i64 {
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(graph::graph_version(name)? as i64)
+    })
+}
+
+/// Returns centrality scores of the named graph as a JSON object with
+/// `node_ids` and `scores` arrays, or null on error.
+///
+/// Results are cached per algorithm and parameters until the graph changes.
+/// # Safety
+/// All pointers must be valid null-terminated C strings.
+#[no_mangle]
+pub unsafe extern "C" fn onager_graph_centrality(
+    graph_name: *const c_char,
+    algorithm: *const c_char,
+    params: *const c_char,
+) -> *mut c_char {
+    crate::ffi_call!(std::ptr::null_mut(), {
+        let name = unsafe { read_graph_name(graph_name)? };
+        let algorithm = unsafe { read_str(algorithm, "centrality algorithm")? };
+        let params = unsafe { read_str(params, "centrality parameters")? };
+        let json = serde_json::to_string(&*graph::centrality(name, algorithm, params)?)?;
+        Ok(CString::new(json)
+            .map(|s| s.into_raw())
+            .unwrap_or(std::ptr::null_mut()))
+    })
+}
+
+/// Builds a contraction hierarchy index for the named graph.
+/// Returns the number of shortcut edges added, or -1 on error.
+/// # Safety
+/// The graph_name pointer must be a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_build_ch_index(graph_name: *const c_char) -> i64 {
+    crate::ffi_call!(-1, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        Ok(graph::build_ch_index(name)? as i64)
+    })
+}
+
+/// Returns the shortest distance between two nodes using the graph's
+/// contraction hierarchy index, infinity if unreachable, or NaN on error.
+/// # Safety
+/// The graph_name pointer must be a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_ch_distance(
+    graph_name: *const c_char,
+    source: i64,
+    target: i64,
+) -> f64 {
+    crate::ffi_call!(f64::NAN, {
+        let name = unsafe { read_graph_name(graph_name)? };
+        graph::ch_distance(name, source, target)
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error_string() -> Option<String> {
+        let ptr = onager_last_error();
+        if ptr.is_null() {
+            None
+        } else {
+            Some(
+                unsafe { CStr::from_ptr(ptr) }
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_ffi_call_records_error() {
+        let value: i64 = crate::ffi_call!(-1, {
+            let (_src, _dst) = unsafe { edge_slices(std::ptr::null(), std::ptr::null(), 0)? };
+            Ok(0)
+        });
+        assert_eq!(value, -1);
+        let msg = last_error_string().unwrap();
+        assert!(msg.contains("Null pointer"));
+    }
+
+    #[test]
+    fn test_ffi_call_clears_error_on_success() {
+        set_last_error("stale");
+        let value: i64 = crate::ffi_call!(-1, { Ok(7) });
+        assert_eq!(value, 7);
+        assert!(last_error_string().is_none());
+    }
+
+    #[test]
+    fn test_ffi_write_outputs_skips_null() {
+        let data = vec![1i64, 2, 3];
+        let mut out = vec![0i64; 3];
+        let out_ptr = out.as_mut_ptr();
+        let null_ptr: *mut i64 = std::ptr::null_mut();
+
+        crate::ffi_write_outputs!(out_ptr => data, null_ptr => data);
+        assert_eq!(out, vec![0, 0, 0]);
+
+        crate::ffi_write_outputs!(out_ptr => data);
+        assert_eq!(out, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_ffi_write_outputs_respects_capacity() {
+        let data = vec![1i64, 2, 3];
+        let mut out = vec![0i64; 3];
+        let out_ptr = out.as_mut_ptr();
+        let write = |capacity: usize| -> Result<()> {
+            crate::ffi_write_outputs!(capacity; out_ptr => data);
+            Ok(())
+        };
+
+        assert!(write(2).is_err());
+        assert_eq!(out, vec![0, 0, 0]);
+
+        assert!(write(3).is_ok());
+        assert_eq!(out, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_two_phase_call_on_large_graph() {
+        // A path over 13,000 nodes, larger than any fixed-size buffer guess
+        let n = 13_000i64;
+        let src: Vec<i64> = (0..n - 1).collect();
+        let dst: Vec<i64> = (1..n).collect();
+        let degree = |nodes: &mut [i64], ins: &mut [f64], outs: &mut [f64], capacity| {
+            crate::ffi::onager_compute_degree_v2(
+                src.as_ptr(),
+                dst.as_ptr(),
+                src.len(),
+                true,
+                nodes.as_mut_ptr(),
+                ins.as_mut_ptr(),
+                outs.as_mut_ptr(),
+                capacity,
+            )
+        };
+
+        let required = crate::ffi::onager_compute_degree_v2(
+            src.as_ptr(),
+            dst.as_ptr(),
+            src.len(),
+            true,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            0,
+        );
+        assert_eq!(required, n);
+
+        // Too small: fails with an error and leaves the buffers alone
+        let short = (n / 2) as usize;
+        let (mut nodes, mut ins, mut outs) =
+            (vec![-1i64; short], vec![-1.0; short], vec![-1.0; short]);
+        assert_eq!(degree(&mut nodes, &mut ins, &mut outs, short), -1);
+        assert!(last_error_string().unwrap().contains("Output buffers"));
+        assert!(nodes.iter().all(|&v| v == -1));
+
+        let full = required as usize;
+        let (mut nodes, mut ins, mut outs) =
+            (vec![-1i64; full], vec![-1.0; full], vec![-1.0; full]);
+        assert_eq!(degree(&mut nodes, &mut ins, &mut outs, full), n);
+        assert_eq!(ins.iter().sum::<f64>(), (n - 1) as f64);
+    }
+
+    #[test]
+    fn test_last_error_is_thread_local() {
+        set_last_error("main thread error");
+
+        let handle = std::thread::spawn(|| {
+            assert!(last_error_string().is_none());
+            set_last_error("worker thread error");
+            last_error_string()
+        });
+        let worker_msg = handle.join().unwrap();
+
+        assert_eq!(worker_msg.as_deref(), Some("worker thread error"));
+        assert_eq!(last_error_string().as_deref(), Some("main thread error"));
+        clear_last_error();
+    }
+}
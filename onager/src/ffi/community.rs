@@ -1,10 +1,15 @@
 //! Community detection FFI exports.
 //!
-//! Louvain, Connected Components, Label Propagation, Girvan-Newman.
+//! Louvain, Connected Components, Label Propagation, Girvan-Newman, similarity-threshold components,
+//! Fluid Communities, greedy modularity.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
-use crate::algorithms;
+use std::os::raw::c_char;
+use std::time::Duration;
+
+use super::common::{edge_slices, input_slice, read_str};
+use crate::algorithms::{self, ComponentMode, GirvanNewmanStop, PartitionMetric};
+use crate::error::OnagerError;
 
 /// Compute Louvain community detection.
 #[no_mangle]
@@ -16,31 +21,40 @@ pub extern "C" fn onager_compute_louvain(
     out_nodes: *mut i64,
     out_communities: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
+    onager_compute_louvain_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        seed,
+        out_nodes,
+        out_communities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_louvain`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_louvain_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
         let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
-        match algorithms::compute_louvain(src, dst, seed_opt) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_communities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_communities, n) }
-                        .copy_from_slice(&result.community_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+        let result = algorithms::compute_louvain(src, dst, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -53,30 +67,93 @@ pub extern "C" fn onager_compute_connected_components(
     out_nodes: *mut i64,
     out_components: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_connected_components(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_components.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_components, n) }
-                        .copy_from_slice(&result.component_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_connected_components_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_components,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_connected_components`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_connected_components_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_components: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_connected_components(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_components => result.component_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute weakly connected components of directed edges.
+///
+/// Edge direction is ignored when joining nodes, so reversed edges do not
+/// need to be passed. Each node is written as a row of `out_nodes` and
+/// `out_components`. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_weakly_connected_components(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_components: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_weakly_connected_components(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_components => result.component_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute connected components with a direction mode.
+///
+/// `mode` is one of `undirected`, `weak`, or `strong`. Each node is written
+/// as a row of `out_nodes` and `out_components`. Returns the number of nodes.
+///
+/// # Safety
+/// `mode` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_components_with_mode(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    mode: *const c_char,
+    out_nodes: *mut i64,
+    out_components: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let mode: ComponentMode = unsafe { read_str(mode, "mode")? }.parse()?;
+        let result = algorithms::compute_connected_components_with_mode(src, dst, mode)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_components => result.component_ids,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -89,30 +166,33 @@ pub extern "C" fn onager_compute_label_propagation(
     out_node_ids: *mut i64,
     out_labels: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_label_propagation(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_node_ids.is_null() && !out_labels.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_node_ids, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_labels, n) }
-                        .copy_from_slice(&result.labels);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_label_propagation_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_node_ids,
+        out_labels,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_label_propagation`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_label_propagation_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_node_ids: *mut i64,
+    out_labels: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_label_propagation(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_node_ids => result.node_ids, out_labels => result.labels);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -126,30 +206,99 @@ pub extern "C" fn onager_compute_girvan_newman(
     out_nodes: *mut i64,
     out_communities: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_girvan_newman(src, dst, target_communities) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_communities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_communities, n) }
-                        .copy_from_slice(&result.community_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
+    onager_compute_girvan_newman_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        target_communities,
+        out_nodes,
+        out_communities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_girvan_newman`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_girvan_newman_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    target_communities: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_girvan_newman(src, dst, target_communities)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute Girvan-Newman with a stopping rule and limits.
+///
+/// A `target_communities` of 0 removes edges until none are left and keeps
+/// the partition with the highest modularity. A `max_iterations` or
+/// `time_budget_ms` of 0 means no limit. Writes the modularity of the returned
+/// partition to `out_modularity` when it is not null. Returns the number of
+/// nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_girvan_newman_with_limits(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    target_communities: i64,
+    max_iterations: usize,
+    time_budget_ms: u64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+    out_modularity: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let stop = match target_communities {
+            0 => GirvanNewmanStop::MaxModularity,
+            t if t > 0 => GirvanNewmanStop::Communities(t as usize),
+            _ => {
+                return Err(OnagerError::InvalidArgument(
+                    "target_communities must not be negative".to_string(),
+                ))
             }
+        };
+        let max_iter_opt = if max_iterations == 0 {
+            None
+        } else {
+            Some(max_iterations)
+        };
+        let budget_opt = if time_budget_ms == 0 {
+            None
+        } else {
+            Some(Duration::from_millis(time_budget_ms))
+        };
+        let result = algorithms::compute_girvan_newman_with_limits(
+            src,
+            dst,
+            stop,
+            max_iter_opt,
+            budget_opt,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        if !out_modularity.is_null() {
+            unsafe { *out_modularity = result.modularity };
         }
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -164,31 +313,42 @@ pub extern "C" fn onager_compute_spectral_clustering(
     out_nodes: *mut i64,
     out_communities: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
+    onager_compute_spectral_clustering_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        k,
+        seed,
+        out_nodes,
+        out_communities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_spectral_clustering`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_spectral_clustering_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    k: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
         let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
-        match algorithms::compute_spectral_clustering(src, dst, k, seed_opt) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_communities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_communities, n) }
-                        .copy_from_slice(&result.community_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+        let result = algorithms::compute_spectral_clustering(src, dst, k, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -203,30 +363,280 @@ pub extern "C" fn onager_compute_infomap(
     out_nodes: *mut i64,
     out_communities: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
+    onager_compute_infomap_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        max_iter,
+        seed,
+        out_nodes,
+        out_communities,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_infomap`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_infomap_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    max_iter: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
         let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
-        match algorithms::compute_infomap(src, dst, max_iter, seed_opt) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_communities.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_communities, n) }
-                        .copy_from_slice(&result.community_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
+        let result = algorithms::compute_infomap(src, dst, max_iter, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute components over edges with a similarity weight above `threshold`.
+///
+/// Each node is written as a row of `out_nodes`, `out_clusters`, `out_sizes`,
+/// `out_min_similarity`, and `out_max_similarity`, where the cluster is the
+/// smallest node ID in it. The similarities are NaN for a cluster with no
+/// internal edges. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_similarity_components(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    threshold: f64,
+    out_nodes: *mut i64,
+    out_clusters: *mut i64,
+    out_sizes: *mut i64,
+    out_min_similarity: *mut f64,
+    out_max_similarity: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_similarity_components(src, dst, weights, threshold)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_clusters => result.cluster_ids,
+            out_sizes => result.cluster_sizes,
+            out_min_similarity => result.min_similarities,
+            out_max_similarity => result.max_similarities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Find the members of one node's connected component.
+///
+/// Writes the members to `out_nodes`, starting with `node`. Returns the number
+/// of members.
+#[no_mangle]
+pub extern "C" fn onager_compute_component_of(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    node: i64,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_component_of(src, dst, node)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute Fluid Communities with exactly `k` communities.
+///
+/// The graph must be connected. A negative `seed` means a random seed.
+/// Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_fluid_communities(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    k: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::compute_fluid_communities(src, dst, k, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute communities by Clauset-Newman-Moore greedy modularity maximization.
+///
+/// Writes the modularity of the partition to `out_modularity` when it is not
+/// null. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_greedy_modularity(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+    out_modularity: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_greedy_modularity(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        if !out_modularity.is_null() {
+            unsafe { *out_modularity = result.modularity };
+        }
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Find the edges and nodes that carry the shortest paths between
+/// communities.
+///
+/// `node_ids[i]` belongs to community `node_communities[i]` for
+/// `i < node_count`. Each edge is written as a row of `out_src`, `out_dst`,
+/// `out_src_communities`, `out_dst_communities`, and `out_edge_paths`. Each
+/// node is written as a row of `out_nodes`, `out_communities`, and
+/// `out_node_paths` when `out_node_capacity` is large enough, and the number
+/// of nodes is written to `out_node_count` when non-null. Returns the number
+/// of edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_community_bridges(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    node_ids_ptr: *const i64,
+    node_communities_ptr: *const i64,
+    node_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_src_communities: *mut i64,
+    out_dst_communities: *mut i64,
+    out_edge_paths: *mut f64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_node_paths: *mut f64,
+    out_node_capacity: usize,
+    out_node_count: *mut i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let node_ids = unsafe { input_slice(node_ids_ptr, node_count, "node_ids")? };
+        let node_communities =
+            unsafe { input_slice(node_communities_ptr, node_count, "node_communities")? };
+        let result = algorithms::compute_community_bridges(src, dst, node_ids, node_communities)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_src_communities => result.src_communities,
+            out_dst_communities => result.dst_communities,
+            out_edge_paths => result.edge_paths,
+        );
+        crate::ffi_write_outputs!(
+            out_node_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.communities,
+            out_node_paths => result.node_paths,
+        );
+        if !out_node_count.is_null() {
+            unsafe { *out_node_count = result.node_ids.len() as i64 };
+        }
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Compute the modularity of a given partition.
+///
+/// `node_ids[i]` belongs to community `node_communities[i]` for
+/// `i < node_count`, and every node of the graph must be listed. Returns NaN
+/// on error.
+#[no_mangle]
+pub extern "C" fn onager_compute_modularity(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    node_ids_ptr: *const i64,
+    node_communities_ptr: *const i64,
+    node_count: usize,
+) -> f64 {
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let node_ids = unsafe { input_slice(node_ids_ptr, node_count, "node_ids")? };
+        let node_communities =
+            unsafe { input_slice(node_communities_ptr, node_count, "node_communities")? };
+        algorithms::compute_modularity(src, dst, node_ids, node_communities)
+    })
+}
+
+/// Compare two partitions of the same nodes, such as community assignments
+/// from two runs or two algorithms.
+///
+/// `metric` is `nmi`, `ari`, or `v_measure`. The similarity over nodes in
+/// both partitions is written to `out_similarity` when non-null. Returns the
+/// number of nodes in both partitions.
+///
+/// # Safety
+/// `metric` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_partition_similarity(
+    a_nodes_ptr: *const i64,
+    a_communities_ptr: *const i64,
+    a_count: usize,
+    b_nodes_ptr: *const i64,
+    b_communities_ptr: *const i64,
+    b_count: usize,
+    metric: *const c_char,
+    out_similarity: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let a_nodes = unsafe { input_slice(a_nodes_ptr, a_count, "first partition nodes")? };
+        let a_communities =
+            unsafe { input_slice(a_communities_ptr, a_count, "first partition communities")? };
+        let b_nodes = unsafe { input_slice(b_nodes_ptr, b_count, "second partition nodes")? };
+        let b_communities =
+            unsafe { input_slice(b_communities_ptr, b_count, "second partition communities")? };
+        let metric: PartitionMetric = unsafe { read_str(metric, "metric")? }.parse()?;
+        let result = algorithms::compute_partition_similarity(
+            a_nodes,
+            a_communities,
+            b_nodes,
+            b_communities,
+            metric,
+        )?;
+        if !out_similarity.is_null() {
+            unsafe { *out_similarity = result.similarity };
         }
+        Ok(result.common_count as i64)
     })
 }
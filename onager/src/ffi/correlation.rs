@@ -0,0 +1,34 @@
+//! Correlation clustering FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, input_slice};
+use crate::algorithms;
+
+/// Partition nodes from signed edge weights with correlation clustering.
+///
+/// Writes node cluster assignments to `out_nodes` and `out_clusters`. A
+/// negative `seed` means a random seed. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_correlation_clustering(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weight_ptr: *const f64,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_clusters: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::compute_correlation_clustering(src, dst, weights, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_clusters => result.cluster_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
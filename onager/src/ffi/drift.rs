@@ -0,0 +1,116 @@
+//! Graph statistics drift and ranking comparison FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, input_slice, read_str};
+use crate::algorithms::{self, RankCorrelation};
+
+/// Compare structural statistics of an old and a new graph snapshot.
+///
+/// `out_node_counts`, `out_edge_counts`, `out_densities`, and
+/// `out_clustering` each take two values, old then new, and are written when
+/// non-null. Nodes that entered or left the top `hub_count` nodes by degree
+/// are written as rows of `out_hub_nodes`, `out_hub_changes` (1 for added,
+/// -1 for removed), `out_hub_old_degrees`, and `out_hub_new_degrees`.
+/// Returns the number of hub rows.
+#[no_mangle]
+pub extern "C" fn onager_compare_graph_stats(
+    old_src_ptr: *const i64,
+    old_dst_ptr: *const i64,
+    old_edge_count: usize,
+    new_src_ptr: *const i64,
+    new_dst_ptr: *const i64,
+    new_edge_count: usize,
+    hub_count: usize,
+    out_node_counts: *mut i64,
+    out_edge_counts: *mut i64,
+    out_densities: *mut f64,
+    out_clustering: *mut f64,
+    out_degree_ks: *mut f64,
+    out_hub_nodes: *mut i64,
+    out_hub_changes: *mut i64,
+    out_hub_old_degrees: *mut i64,
+    out_hub_new_degrees: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (old_src, old_dst) = unsafe { edge_slices(old_src_ptr, old_dst_ptr, old_edge_count)? };
+        let (new_src, new_dst) = unsafe { edge_slices(new_src_ptr, new_dst_ptr, new_edge_count)? };
+        let result =
+            algorithms::compare_graph_stats(old_src, old_dst, new_src, new_dst, hub_count)?;
+        let (old, new) = (&result.old, &result.new);
+        unsafe {
+            if !out_node_counts.is_null() {
+                *out_node_counts = old.node_count as i64;
+                *out_node_counts.add(1) = new.node_count as i64;
+            }
+            if !out_edge_counts.is_null() {
+                *out_edge_counts = old.edge_count as i64;
+                *out_edge_counts.add(1) = new.edge_count as i64;
+            }
+            if !out_densities.is_null() {
+                *out_densities = old.density;
+                *out_densities.add(1) = new.density;
+            }
+            if !out_clustering.is_null() {
+                *out_clustering = old.average_clustering;
+                *out_clustering.add(1) = new.average_clustering;
+            }
+            if !out_degree_ks.is_null() {
+                *out_degree_ks = result.degree_ks_distance;
+            }
+        }
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_hub_nodes => result.hub_node_ids,
+            out_hub_changes => result.hub_changes,
+            out_hub_old_degrees => result.hub_old_degrees,
+            out_hub_new_degrees => result.hub_new_degrees,
+        );
+        Ok(result.hub_node_ids.len() as i64)
+    })
+}
+
+/// Compare two node rankings, such as centrality scores before and after a
+/// change to a graph.
+///
+/// `method` is `spearman` or `kendall`. The correlation over nodes scored in
+/// both rankings and the overlap of the top `top_k` nodes of each ranking are
+/// written to `out_correlation` and `out_top_k_overlap` when non-null.
+/// Returns the number of nodes scored in both rankings.
+///
+/// # Safety
+/// `method` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compare_rankings(
+    a_nodes_ptr: *const i64,
+    a_scores_ptr: *const f64,
+    a_count: usize,
+    b_nodes_ptr: *const i64,
+    b_scores_ptr: *const f64,
+    b_count: usize,
+    method: *const c_char,
+    top_k: usize,
+    out_correlation: *mut f64,
+    out_top_k_overlap: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let a_nodes = unsafe { input_slice(a_nodes_ptr, a_count, "first ranking nodes")? };
+        let a_scores = unsafe { input_slice(a_scores_ptr, a_count, "first ranking scores")? };
+        let b_nodes = unsafe { input_slice(b_nodes_ptr, b_count, "second ranking nodes")? };
+        let b_scores = unsafe { input_slice(b_scores_ptr, b_count, "second ranking scores")? };
+        let method: RankCorrelation = unsafe { read_str(method, "method")? }.parse()?;
+        let result =
+            algorithms::compare_rankings(a_nodes, a_scores, b_nodes, b_scores, method, top_k)?;
+        unsafe {
+            if !out_correlation.is_null() {
+                *out_correlation = result.correlation;
+            }
+            if !out_top_k_overlap.is_null() {
+                *out_top_k_overlap = result.top_k_overlap;
+            }
+        }
+        Ok(result.common_count as i64)
+    })
+}
@@ -0,0 +1,67 @@
+//! Output size estimation FFI exports.
+//!
+//! Upper bounds for sizing output buffers before calling an algorithm.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::edge_slices;
+use crate::algorithms;
+
+/// Estimate the output size of per-node algorithms (distinct node count).
+#[no_mangle]
+pub extern "C" fn onager_estimate_output_size_nodes(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(algorithms::estimate_node_output_size(src, dst)? as i64)
+    })
+}
+
+/// Estimate the output size of node-pair algorithms (ordered node pairs).
+#[no_mangle]
+pub extern "C" fn onager_estimate_output_size_node_pairs(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(algorithms::estimate_node_pair_output_size(src, dst)? as i64)
+    })
+}
+
+/// Estimate the output size of edge-returning algorithms (input edge count).
+#[no_mangle]
+pub extern "C" fn onager_estimate_output_size_edges(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(algorithms::estimate_edge_output_size(src, dst)? as i64)
+    })
+}
+
+/// Estimate the output size of path-returning algorithms (node count plus one).
+#[no_mangle]
+pub extern "C" fn onager_estimate_output_size_path(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(algorithms::estimate_path_output_size(src, dst)? as i64)
+    })
+}
+
+/// Estimate the number of edges a generator can produce for n nodes.
+#[no_mangle]
+pub extern "C" fn onager_estimate_output_size_generated_edges(n: usize) -> i64 {
+    crate::ffi_call!(-1, {
+        Ok(algorithms::estimate_generated_edge_count(n)? as i64)
+    })
+}
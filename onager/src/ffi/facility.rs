@@ -0,0 +1,72 @@
+//! Facility location FFI exports.
+//!
+//! P-Median, Voronoi Partition.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, optional_slice};
+use crate::algorithms;
+
+/// Select `p` facility nodes with the p-median heuristic.
+///
+/// Empty weights mean unit weights. Each node is written as a row of
+/// `out_nodes`, `out_facilities`, and `out_distances`, and facilities are the
+/// rows where the node is its own facility. Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_p_median(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    p: usize,
+    out_nodes: *mut i64,
+    out_facilities: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_p_median(src, dst, weights, p)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_facilities => result.assignments,
+            out_distances => result.distances,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Assign every node to its nearest seed node.
+///
+/// Empty weights mean unit weights. Each reachable node is written as a row of
+/// `out_nodes`, `out_seeds`, and `out_distances`. Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_voronoi(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    seeds_ptr: *const i64,
+    seed_count: usize,
+    out_nodes: *mut i64,
+    out_seeds: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let seeds = unsafe { optional_slice(seeds_ptr, seed_count) };
+        let result = algorithms::compute_voronoi(src, dst, weights, seeds)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_seeds => result.seeds,
+            out_distances => result.distances,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
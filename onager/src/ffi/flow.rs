@@ -0,0 +1,194 @@
+//! Flow and cut FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, optional_slice};
+use crate::algorithms;
+
+/// Compute a maximum flow from `source` to `sink` over directed edges.
+///
+/// Empty capacities mean every edge has capacity 1.0. Each input edge is
+/// written to `out_src` and `out_dst` with its flow in `out_flows`, in input
+/// order. Writes the total flow to `out_flow_value` when it is not null.
+/// Returns the number of edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_max_flow(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    capacities_ptr: *const f64,
+    capacities_count: usize,
+    source: i64,
+    sink: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_flows: *mut f64,
+    out_capacity: usize,
+    out_flow_value: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let capacities = unsafe { optional_slice(capacities_ptr, capacities_count) };
+        let result = algorithms::compute_max_flow(src, dst, capacities, source, sink)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_flows => result.flows,
+        );
+        if !out_flow_value.is_null() {
+            unsafe { *out_flow_value = result.flow_value };
+        }
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Find a minimum cut between `source` and `sink` over directed edges.
+///
+/// Empty capacities mean every edge has capacity 1.0. Each cut edge is
+/// written to `out_src` and `out_dst` with its capacity in `out_capacities`.
+/// Writes the cut value to `out_cut_value` when it is not null. Returns the
+/// number of cut edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_min_cut(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    capacities_ptr: *const f64,
+    capacities_count: usize,
+    source: i64,
+    sink: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacities: *mut f64,
+    out_capacity: usize,
+    out_cut_value: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let capacities = unsafe { optional_slice(capacities_ptr, capacities_count) };
+        let result = algorithms::compute_min_cut(src, dst, capacities, source, sink)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_capacities => result.capacities,
+        );
+        if !out_cut_value.is_null() {
+            unsafe { *out_cut_value = result.cut_value };
+        }
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Find a minimum edge cut between nodes `a` and `b`.
+///
+/// Each cut edge is written to `out_src` and `out_dst`, oriented from the side
+/// of `a`. Returns the number of cut edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_min_edge_cut(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    a: i64,
+    b: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_min_edge_cut(src, dst, a, b)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Find a minimum vertex cut between nodes `a` and `b`.
+///
+/// Returns the number of cut nodes written to `out_nodes`.
+#[no_mangle]
+pub extern "C" fn onager_compute_min_vertex_cut(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    a: i64,
+    b: i64,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_min_vertex_cut(src, dst, a, b)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Split the graph into two sides joined by the least total edge weight.
+///
+/// Empty weights mean every edge has weight 1.0. Each node is written to
+/// `out_nodes` with its side, 0 or 1, in `out_sides`. Writes the cut weight to
+/// `out_cut_weight` when it is not null. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_global_min_cut(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    out_nodes: *mut i64,
+    out_sides: *mut i64,
+    out_capacity: usize,
+    out_cut_weight: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_global_min_cut(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_sides => result.sides,
+        );
+        if !out_cut_weight.is_null() {
+            unsafe { *out_cut_weight = result.cut_weight };
+        }
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute edge connectivity, globally or between nodes `a` and `b`.
+///
+/// `a` and `b` are ignored unless `pairwise` is true. Returns -1 on error.
+#[no_mangle]
+pub extern "C" fn onager_compute_edge_connectivity(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    pairwise: bool,
+    a: i64,
+    b: i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_edge_connectivity(src, dst, pairwise.then_some((a, b)))
+    })
+}
+
+/// Compute node connectivity, globally or between nodes `a` and `b`.
+///
+/// `a` and `b` are ignored unless `pairwise` is true. Returns -1 on error.
+#[no_mangle]
+pub extern "C" fn onager_compute_node_connectivity(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    pairwise: bool,
+    a: i64,
+    b: i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_node_connectivity(src, dst, pairwise.then_some((a, b)))
+    })
+}
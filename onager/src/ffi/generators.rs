@@ -1,10 +1,11 @@
 //! Graph generators FFI exports.
 //!
-//! Erdős-Rényi, Barabási-Albert, Watts-Strogatz.
+//! Erdős-Rényi (G(n, p) and G(n, m)), Barabási-Albert, Watts-Strogatz, LFR,
+//! random geometric graphs, and k-nearest-neighbor and similarity graphs.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
-use crate::algorithms;
+use super::common::{edge_slices, input_slice};
+use crate::algorithms::{self, EdgeSpace, GeneratorResult};
 
 /// Generate Erdős-Rényi random graph.
 #[no_mangle]
@@ -15,24 +16,144 @@ pub extern "C" fn onager_generate_erdos_renyi(
     out_src: *mut i64,
     out_dst: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        match algorithms::generate_erdos_renyi(n, p, seed) {
-            Ok(result) => {
-                let edge_count = result.src.len();
-                if !out_src.is_null() && !out_dst.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, edge_count) }
-                        .copy_from_slice(&result.src);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, edge_count) }
-                        .copy_from_slice(&result.dst);
-                }
-                edge_count as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
+    onager_generate_erdos_renyi_v2(n, p, seed, out_src, out_dst, usize::MAX)
+}
+
+/// Capacity-checked variant of `onager_generate_erdos_renyi`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_generate_erdos_renyi_v2(
+    n: usize,
+    p: f64,
+    seed: u64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result = algorithms::generate_erdos_renyi(n, p, seed)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate an Erdős-Rényi random graph with node IDs starting at `start_id`.
+///
+/// Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+/// `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes,
+/// including those without edges, to `out_nodes`.
+#[no_mangle]
+pub extern "C" fn onager_generate_erdos_renyi_with_ids(
+    n: usize,
+    p: f64,
+    seed: u64,
+    start_id: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result = algorithms::generate_erdos_renyi(n, p, seed)?.with_start_id(start_id)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(out_nodes_capacity; out_nodes => result.node_ids);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate an Erdős-Rényi G(n, p) graph that may be directed or contain
+/// self-loops.
+///
+/// Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+/// `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes to
+/// `out_nodes`.
+#[no_mangle]
+pub extern "C" fn onager_generate_gnp(
+    n: usize,
+    p: f64,
+    directed: bool,
+    self_loops: bool,
+    seed: u64,
+    start_id: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let space = EdgeSpace {
+            directed,
+            self_loops,
+        };
+        let result = algorithms::generate_gnp(n, p, space, seed)?.with_start_id(start_id)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(out_nodes_capacity; out_nodes => result.node_ids);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate an Erdős-Rényi G(n, m) graph with exactly `m` edges.
+///
+/// Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+/// `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes to
+/// `out_nodes`.
+#[no_mangle]
+pub extern "C" fn onager_generate_gnm(
+    n: usize,
+    m: usize,
+    directed: bool,
+    self_loops: bool,
+    parallel_edges: bool,
+    seed: u64,
+    start_id: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let space = EdgeSpace {
+            directed,
+            self_loops,
+        };
+        let result =
+            algorithms::generate_gnm(n, m, space, parallel_edges, seed)?.with_start_id(start_id)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(out_nodes_capacity; out_nodes => result.node_ids);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Drop self-loops and repeated edges from a generated edge list.
+///
+/// Self-loops are kept when `self_loops` is set and repeated edges when
+/// `parallel_edges` is set. Returns the number of edges written.
+#[no_mangle]
+pub extern "C" fn onager_simplify_edges(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    directed: bool,
+    self_loops: bool,
+    parallel_edges: bool,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = GeneratorResult {
+            src: src.to_vec(),
+            dst: dst.to_vec(),
+            node_ids: Vec::new(),
         }
+        .simplify(directed, self_loops, parallel_edges);
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        Ok(result.src.len() as i64)
     })
 }
 
@@ -45,24 +166,50 @@ pub extern "C" fn onager_generate_barabasi_albert(
     out_src: *mut i64,
     out_dst: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        match algorithms::generate_barabasi_albert(n, m, seed) {
-            Ok(result) => {
-                let edge_count = result.src.len();
-                if !out_src.is_null() && !out_dst.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, edge_count) }
-                        .copy_from_slice(&result.src);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, edge_count) }
-                        .copy_from_slice(&result.dst);
-                }
-                edge_count as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_generate_barabasi_albert_v2(n, m, seed, out_src, out_dst, usize::MAX)
+}
+
+/// Capacity-checked variant of `onager_generate_barabasi_albert`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_generate_barabasi_albert_v2(
+    n: usize,
+    m: usize,
+    seed: u64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result = algorithms::generate_barabasi_albert(n, m, seed)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate a Barabási-Albert graph with node IDs starting at `start_id`.
+///
+/// Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+/// `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes,
+/// including those without edges, to `out_nodes`.
+#[no_mangle]
+pub extern "C" fn onager_generate_barabasi_albert_with_ids(
+    n: usize,
+    m: usize,
+    seed: u64,
+    start_id: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result = algorithms::generate_barabasi_albert(n, m, seed)?.with_start_id(start_id)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(out_nodes_capacity; out_nodes => result.node_ids);
+        Ok(result.src.len() as i64)
     })
 }
 
@@ -76,23 +223,245 @@ pub extern "C" fn onager_generate_watts_strogatz(
     out_src: *mut i64,
     out_dst: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        match algorithms::generate_watts_strogatz(n, k, beta, seed) {
-            Ok(result) => {
-                let edge_count = result.src.len();
-                if !out_src.is_null() && !out_dst.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, edge_count) }
-                        .copy_from_slice(&result.src);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, edge_count) }
-                        .copy_from_slice(&result.dst);
-                }
-                edge_count as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_generate_watts_strogatz_v2(n, k, beta, seed, out_src, out_dst, usize::MAX)
+}
+
+/// Capacity-checked variant of `onager_generate_watts_strogatz`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_generate_watts_strogatz_v2(
+    n: usize,
+    k: usize,
+    beta: f64,
+    seed: u64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result = algorithms::generate_watts_strogatz(n, k, beta, seed)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate a Watts-Strogatz graph with node IDs starting at `start_id`.
+///
+/// Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+/// `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes,
+/// including those without edges, to `out_nodes`.
+#[no_mangle]
+pub extern "C" fn onager_generate_watts_strogatz_with_ids(
+    n: usize,
+    k: usize,
+    beta: f64,
+    seed: u64,
+    start_id: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result =
+            algorithms::generate_watts_strogatz(n, k, beta, seed)?.with_start_id(start_id)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(out_nodes_capacity; out_nodes => result.node_ids);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate an LFR benchmark graph with ground-truth communities.
+///
+/// Writes the edges to `out_src` and `out_dst` and returns the edge count.
+/// Nodes `0..n` and their planted communities go to `out_nodes` and
+/// `out_communities`, which need room for `n` values.
+#[no_mangle]
+pub extern "C" fn onager_generate_lfr(
+    n: usize,
+    mu: f64,
+    avg_degree: f64,
+    seed: u64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result = algorithms::generate_lfr(n, mu, avg_degree, seed)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(
+            out_nodes_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate an LFR benchmark graph with node IDs starting at `start_id`.
+///
+/// Same as `onager_generate_lfr`, with nodes `start_id..start_id + n` written
+/// to `out_nodes`.
+#[no_mangle]
+pub extern "C" fn onager_generate_lfr_with_ids(
+    n: usize,
+    mu: f64,
+    avg_degree: f64,
+    seed: u64,
+    start_id: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result = algorithms::generate_lfr(n, mu, avg_degree, seed)?.with_start_id(start_id)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(
+            out_nodes_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Generate a random geometric graph in the unit square.
+///
+/// Node IDs run from `start_id` to `start_id + n - 1`. Writes the edges to
+/// `out_src` and `out_dst` and returns the edge count. Writes all `n` nodes
+/// with their positions to `out_nodes`, `out_x`, and `out_y`.
+#[no_mangle]
+pub extern "C" fn onager_generate_random_geometric(
+    n: usize,
+    radius: f64,
+    seed: u64,
+    start_id: i64,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+    out_nodes: *mut i64,
+    out_x: *mut f64,
+    out_y: *mut f64,
+    out_nodes_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let result =
+            algorithms::generate_random_geometric(n, radius, seed)?.with_start_id(start_id)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        crate::ffi_write_outputs!(
+            out_nodes_capacity;
+            out_nodes => result.node_ids,
+            out_x => result.x,
+            out_y => result.y,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Build a k-nearest-neighbor graph from 2-d points.
+///
+/// Writes an edge from each point to each of its `k` nearest points, with the
+/// distance, and returns the edge count.
+#[no_mangle]
+pub extern "C" fn onager_build_knn_graph(
+    node_ids_ptr: *const i64,
+    x_ptr: *const f64,
+    y_ptr: *const f64,
+    node_count: usize,
+    k: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let node_ids = unsafe { input_slice(node_ids_ptr, node_count, "node_ids")? };
+        let x = unsafe { input_slice(x_ptr, node_count, "x")? };
+        let y = unsafe { input_slice(y_ptr, node_count, "y")? };
+        let result = algorithms::build_knn_graph(node_ids, x, y, k)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_distances => result.distances,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Build a graph from a dense row-major similarity matrix over `node_ids`.
+///
+/// Writes an edge for each similarity at or above `threshold`, keeping the
+/// `top_k` most similar per node when `top_k` is positive, and returns the
+/// edge count.
+#[no_mangle]
+pub extern "C" fn onager_build_similarity_graph(
+    node_ids_ptr: *const i64,
+    node_count: usize,
+    matrix_ptr: *const f64,
+    matrix_len: usize,
+    threshold: f64,
+    top_k: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_similarities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let node_ids = unsafe { input_slice(node_ids_ptr, node_count, "node_ids")? };
+        let matrix = unsafe { input_slice(matrix_ptr, matrix_len, "similarity matrix")? };
+        let result = algorithms::build_graph_from_similarity(node_ids, matrix, threshold, top_k)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_similarities => result.similarities,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Build a graph from `(src, dst, similarity)` triples.
+///
+/// Same as `onager_build_similarity_graph` for a matrix given in coordinate
+/// form.
+#[no_mangle]
+pub extern "C" fn onager_build_similarity_graph_pairs(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    similarities_ptr: *const f64,
+    pair_count: usize,
+    threshold: f64,
+    top_k: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_similarities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, pair_count)? };
+        let similarities = unsafe { input_slice(similarities_ptr, pair_count, "similarities")? };
+        let result = algorithms::build_graph_from_similarity_pairs(
+            src,
+            dst,
+            similarities,
+            threshold,
+            top_k,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_similarities => result.similarities,
+        );
+        Ok(result.src.len() as i64)
     })
 }
@@ -0,0 +1,42 @@
+//! Hierarchical clustering FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, read_str};
+use crate::algorithms::{self, Linkage};
+
+/// Compute an agglomerative clustering dendrogram.
+///
+/// `linkage` is one of `single`, `complete`, or `average`. Each merge is
+/// written as a row of `out_child_a`, `out_child_b`, `out_merged`, and
+/// `out_heights`. Returns the number of merges.
+///
+/// # Safety
+/// `linkage` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_hierarchical_clustering(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    linkage: *const c_char,
+    out_child_a: *mut i64,
+    out_child_b: *mut i64,
+    out_merged: *mut i64,
+    out_heights: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let linkage: Linkage = unsafe { read_str(linkage, "linkage")? }.parse()?;
+        let result = algorithms::compute_hierarchical_clustering(src, dst, linkage)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_child_a => result.child_a,
+            out_child_b => result.child_b,
+            out_merged => result.merged_ids,
+            out_heights => result.heights,
+        );
+        Ok(result.merged_ids.len() as i64)
+    })
+}
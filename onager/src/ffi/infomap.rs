@@ -0,0 +1,52 @@
+//! Weighted and directed Infomap FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, optional_slice};
+use crate::algorithms;
+
+/// Compute two-level Infomap over weighted, directed or undirected edges.
+///
+/// Empty weights mean unit weights. `teleportation` is the PageRank
+/// teleportation probability used for directed flow. A negative `seed` means
+/// a random seed. Writes the codelength in bits to `out_codelength` when it is
+/// not null. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_weighted_infomap(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    directed: bool,
+    teleportation: f64,
+    max_iter: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+    out_codelength: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::compute_weighted_infomap(
+            src,
+            dst,
+            weights,
+            directed,
+            teleportation,
+            max_iter,
+            seed_opt,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        if !out_codelength.is_null() {
+            unsafe { *out_codelength = result.codelength };
+        }
+        Ok(result.node_ids.len() as i64)
+    })
+}
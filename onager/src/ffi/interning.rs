@@ -0,0 +1,149 @@
+//! String node ID FFI exports.
+//!
+//! Variants of common algorithms that take node IDs as C strings. The names
+//! are interned into integer IDs for the computation, and each result row
+//! points back at one of the caller's input strings, so the output stays valid
+//! as long as the input does and never needs to be freed.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::c_char;
+
+use super::common::{input_slice, optional_slice, read_str};
+use crate::algorithms::{self, NodeInterner};
+use crate::error::{OnagerError, Result};
+
+/// Integer edges built from string endpoints.
+struct InternedEdges {
+    src: Vec<i64>,
+    dst: Vec<i64>,
+    /// First input pointer carrying each interned ID
+    names: Vec<*const c_char>,
+}
+
+impl InternedEdges {
+    /// Maps result IDs back to the caller's strings.
+    fn node_names(&self, ids: &[i64]) -> Result<Vec<*const c_char>> {
+        ids.iter()
+            .map(|&id| {
+                usize::try_from(id)
+                    .ok()
+                    .and_then(|i| self.names.get(i))
+                    .copied()
+                    .ok_or(OnagerError::NodeNotFound(id))
+            })
+            .collect()
+    }
+}
+
+/// Reads and interns the string endpoints of every edge.
+///
+/// # Safety
+/// Non-null pointers must point to at least `edge_count` valid
+/// null-terminated C strings.
+unsafe fn intern_edges(
+    src_ptr: *const *const c_char,
+    dst_ptr: *const *const c_char,
+    edge_count: usize,
+) -> Result<InternedEdges> {
+    let src = unsafe { input_slice(src_ptr, edge_count, "src")? };
+    let dst = unsafe { input_slice(dst_ptr, edge_count, "dst")? };
+    let mut interner = NodeInterner::new();
+    let mut names = Vec::new();
+    let mut intern = |ptr: *const c_char| -> Result<i64> {
+        let id = interner.intern(unsafe { read_str(ptr, "node ID")? });
+        if id as usize == names.len() {
+            names.push(ptr);
+        }
+        Ok(id)
+    };
+    let src = src.iter().map(|&ptr| intern(ptr)).collect::<Result<_>>()?;
+    let dst = dst.iter().map(|&ptr| intern(ptr)).collect::<Result<_>>()?;
+    Ok(InternedEdges { src, dst, names })
+}
+
+/// Compute PageRank on edges with string node IDs.
+///
+/// Empty weights mean unit weights. Each node is written as a row of
+/// `out_nodes` and `out_ranks`, where `out_nodes` holds pointers into the
+/// input strings. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_pagerank_str(
+    src_ptr: *const *const c_char,
+    dst_ptr: *const *const c_char,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+    out_nodes: *mut *const c_char,
+    out_ranks: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let edges = unsafe { intern_edges(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_pagerank(
+            &edges.src, &edges.dst, weights, damping, iterations, directed,
+        )?;
+        let nodes = edges.node_names(&result.node_ids)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => nodes, out_ranks => result.ranks);
+        Ok(nodes.len() as i64)
+    })
+}
+
+/// Compute connected components on edges with string node IDs.
+///
+/// Each node is written as a row of `out_nodes` and `out_components`, where
+/// `out_nodes` holds pointers into the input strings. Returns the number of
+/// nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_connected_components_str(
+    src_ptr: *const *const c_char,
+    dst_ptr: *const *const c_char,
+    edge_count: usize,
+    out_nodes: *mut *const c_char,
+    out_components: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let edges = unsafe { intern_edges(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_connected_components(&edges.src, &edges.dst)?;
+        let nodes = edges.node_names(&result.node_ids)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => nodes,
+            out_components => result.component_ids,
+        );
+        Ok(nodes.len() as i64)
+    })
+}
+
+/// Compute Louvain communities on edges with string node IDs.
+///
+/// A negative `seed` means no fixed seed. Each node is written as a row of
+/// `out_nodes` and `out_communities`, where `out_nodes` holds pointers into
+/// the input strings. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_louvain_str(
+    src_ptr: *const *const c_char,
+    dst_ptr: *const *const c_char,
+    edge_count: usize,
+    seed: i64,
+    out_nodes: *mut *const c_char,
+    out_communities: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let edges = unsafe { intern_edges(src_ptr, dst_ptr, edge_count)? };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::compute_louvain(&edges.src, &edges.dst, seed_opt)?;
+        let nodes = edges.node_names(&result.node_ids)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => nodes,
+            out_communities => result.community_ids,
+        );
+        Ok(nodes.len() as i64)
+    })
+}
@@ -0,0 +1,60 @@
+//! Background job FFI exports.
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+
+use super::common::read_str;
+use crate::error::{OnagerError, Result};
+use crate::jobs;
+
+fn job_id(id: i64) -> Result<u64> {
+    u64::try_from(id).map_err(|_| OnagerError::InvalidArgument(format!("Unknown job {}", id)))
+}
+
+fn into_c_string(s: &str) -> *mut c_char {
+    CString::new(s)
+        .map(|s| s.into_raw())
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Submits an algorithm to run on a registry graph in the background.
+/// Returns the job handle, or -1 on error.
+/// # Safety
+/// `algorithm` and `params` must point to valid null-terminated C strings.
+#[no_mangle]
+pub unsafe extern "C" fn onager_submit_job(algorithm: *const c_char, params: *const c_char) -> i64 {
+    crate::ffi_call!(-1, {
+        let algorithm = unsafe { read_str(algorithm, "job algorithm")? };
+        let params = unsafe { read_str(params, "job parameters")? };
+        Ok(jobs::submit_job(algorithm, params)? as i64)
+    })
+}
+
+/// Returns the status of a job (`running`, `completed`, or `failed`), or null
+/// on error. The caller must free the string with onager_free.
+#[no_mangle]
+pub extern "C" fn onager_job_status(id: i64) -> *mut c_char {
+    crate::ffi_call!(std::ptr::null_mut(), {
+        let status = jobs::job_status(job_id(id)?)?;
+        Ok(into_c_string(status.as_str()))
+    })
+}
+
+/// Returns the JSON result of a completed job, or null if the job is unknown,
+/// running, or failed. The caller must free the string with onager_free.
+#[no_mangle]
+pub extern "C" fn onager_job_result(id: i64) -> *mut c_char {
+    crate::ffi_call!(std::ptr::null_mut(), {
+        let json = jobs::job_result(job_id(id)?)?;
+        Ok(into_c_string(&json))
+    })
+}
+
+/// Forgets a job and its result.
+#[no_mangle]
+pub extern "C" fn onager_drop_job(id: i64) -> i32 {
+    crate::ffi_call!(-1, {
+        jobs::drop_job(job_id(id)?)?;
+        Ok(0)
+    })
+}
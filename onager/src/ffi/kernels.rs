@@ -0,0 +1,74 @@
+//! Graph kernel FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, input_slice, read_str};
+use crate::algorithms::{self, GraphKernel};
+
+/// Compute Weisfeiler-Lehman subtree labels for `iterations` rounds.
+///
+/// Each node and iteration, from 0 to `iterations`, is written as a row of
+/// `out_nodes`, `out_iterations`, and `out_labels`. Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_wl_labels(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    iterations: usize,
+    out_nodes: *mut i64,
+    out_iterations: *mut i64,
+    out_labels: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_wl_labels(src, dst, iterations)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_iterations => result.iterations,
+            out_labels => result.labels,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute kernel similarities between every pair of graphs in a batch.
+///
+/// Edge `i` belongs to graph `graph_ids[i]`. `kernel` is `wl_subtree` or
+/// `shortest_path`, and `iterations` only applies to `wl_subtree`. Each pair
+/// is written as a row of `out_graph_a`, `out_graph_b`, and
+/// `out_similarities` with `graph_a < graph_b`. Returns the number of pairs.
+///
+/// # Safety
+/// `kernel` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_graph_kernel(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    graph_ids_ptr: *const i64,
+    edge_count: usize,
+    kernel: *const c_char,
+    iterations: usize,
+    normalize: bool,
+    out_graph_a: *mut i64,
+    out_graph_b: *mut i64,
+    out_similarities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let graph_ids = unsafe { input_slice(graph_ids_ptr, edge_count, "graph_ids")? };
+        let kernel: GraphKernel = unsafe { read_str(kernel, "graph kernel")? }.parse()?;
+        let result =
+            algorithms::compute_graph_kernel(src, dst, graph_ids, kernel, iterations, normalize)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_graph_a => result.graph_a,
+            out_graph_b => result.graph_b,
+            out_similarities => result.similarities,
+        );
+        Ok(result.graph_a.len() as i64)
+    })
+}
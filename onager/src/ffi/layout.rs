@@ -0,0 +1,104 @@
+//! Graph layout FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, optional_slice, read_str};
+use crate::algorithms::{self, LayoutMethod};
+
+/// Compute two-dimensional node coordinates.
+///
+/// `method` is `force`, `spectral`, `circular`, or `shell`. `iterations` and
+/// `seed` only affect the force layout, and a negative `seed` means a random
+/// seed. Empty weights mean unit weights. Each node is written to `out_nodes`
+/// with its coordinates in `out_x` and `out_y`. Returns the number of nodes.
+///
+/// # Safety
+/// `method` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_compute_layout(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    method: *const c_char,
+    iterations: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_x: *mut f64,
+    out_y: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let method: LayoutMethod = unsafe { read_str(method, "layout method")? }.parse()?;
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::compute_layout(src, dst, weights, method, iterations, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_x => result.x,
+            out_y => result.y,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute coordinates that keep every Louvain community together.
+///
+/// A negative `seed` means a random seed, and empty weights mean unit
+/// weights. Each node is written to `out_nodes` with its community in
+/// `out_communities` and its coordinates in `out_x` and `out_y`. Each
+/// community is written to `out_community_ids` with its number of members in
+/// `out_community_sizes` and its centroid in `out_community_x` and
+/// `out_community_y` when `out_community_capacity` is large enough, and the
+/// number of communities is written to `out_community_count` when non-null.
+/// Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_community_layout(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    iterations: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_x: *mut f64,
+    out_y: *mut f64,
+    out_capacity: usize,
+    out_community_ids: *mut i64,
+    out_community_sizes: *mut i64,
+    out_community_x: *mut f64,
+    out_community_y: *mut f64,
+    out_community_capacity: usize,
+    out_community_count: *mut i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::compute_community_layout(src, dst, weights, iterations, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.communities,
+            out_x => result.x,
+            out_y => result.y,
+        );
+        crate::ffi_write_outputs!(
+            out_community_capacity;
+            out_community_ids => result.community_ids,
+            out_community_sizes => result.community_sizes,
+            out_community_x => result.community_x,
+            out_community_y => result.community_y,
+        );
+        if !out_community_count.is_null() {
+            unsafe { *out_community_count = result.community_ids.len() as i64 };
+        }
+        Ok(result.node_ids.len() as i64)
+    })
+}
@@ -0,0 +1,67 @@
+//! Link analysis FFI exports.
+//!
+//! SALSA and co-HITS.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, optional_slice};
+use crate::algorithms;
+
+/// Compute SALSA hub and authority scores.
+#[no_mangle]
+pub extern "C" fn onager_compute_salsa(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_hubs: *mut f64,
+    out_authorities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_salsa(src, dst, max_iter, tolerance)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_hubs => result.hubs,
+            out_authorities => result.authorities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute co-HITS scores on a bipartite graph.
+///
+/// Empty weights mean unit weights.
+#[no_mangle]
+pub extern "C" fn onager_compute_cohits(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    lambda_u: f64,
+    lambda_v: f64,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_hubs: *mut f64,
+    out_authorities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result =
+            algorithms::compute_cohits(src, dst, weights, lambda_u, lambda_v, max_iter, tolerance)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_hubs => result.hubs,
+            out_authorities => result.authorities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
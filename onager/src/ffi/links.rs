@@ -1,10 +1,13 @@
 //! Link prediction FFI exports.
 //!
-//! Jaccard, Adamic-Adar, Preferential Attachment, Resource Allocation.
+//! Jaccard, Adamic-Adar, Preferential Attachment, Resource Allocation,
+//! and negative edge sampling.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
-use crate::algorithms;
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, read_str};
+use crate::algorithms::{self, NegativeSampling};
 
 /// Compute Jaccard coefficient.
 #[no_mangle]
@@ -16,32 +19,40 @@ pub extern "C" fn onager_compute_jaccard(
     out_node2: *mut i64,
     out_coefficients: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_jaccard(src, dst) {
-            Ok(result) => {
-                let n = result.node1.len();
-                if !out_node1.is_null() && !out_node2.is_null() && !out_coefficients.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_node1, n) }
-                        .copy_from_slice(&result.node1);
-                    unsafe { std::slice::from_raw_parts_mut(out_node2, n) }
-                        .copy_from_slice(&result.node2);
-                    unsafe { std::slice::from_raw_parts_mut(out_coefficients, n) }
-                        .copy_from_slice(&result.scores);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_jaccard_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_node1,
+        out_node2,
+        out_coefficients,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_jaccard`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_jaccard_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_node1: *mut i64,
+    out_node2: *mut i64,
+    out_coefficients: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_jaccard(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_node1 => result.node1,
+            out_node2 => result.node2,
+            out_coefficients => result.scores,
+        );
+        Ok(result.node1.len() as i64)
     })
 }
 
@@ -55,32 +66,40 @@ pub extern "C" fn onager_compute_adamic_adar(
     out_node2: *mut i64,
     out_scores: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_adamic_adar(src, dst) {
-            Ok(result) => {
-                let n = result.node1.len();
-                if !out_node1.is_null() && !out_node2.is_null() && !out_scores.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_node1, n) }
-                        .copy_from_slice(&result.node1);
-                    unsafe { std::slice::from_raw_parts_mut(out_node2, n) }
-                        .copy_from_slice(&result.node2);
-                    unsafe { std::slice::from_raw_parts_mut(out_scores, n) }
-                        .copy_from_slice(&result.scores);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_adamic_adar_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_node1,
+        out_node2,
+        out_scores,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_adamic_adar`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_adamic_adar_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_node1: *mut i64,
+    out_node2: *mut i64,
+    out_scores: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_adamic_adar(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_node1 => result.node1,
+            out_node2 => result.node2,
+            out_scores => result.scores,
+        );
+        Ok(result.node1.len() as i64)
     })
 }
 
@@ -94,32 +113,40 @@ pub extern "C" fn onager_compute_preferential_attachment(
     out_node2: *mut i64,
     out_scores: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_preferential_attachment(src, dst) {
-            Ok(result) => {
-                let n = result.node1.len();
-                if !out_node1.is_null() && !out_node2.is_null() && !out_scores.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_node1, n) }
-                        .copy_from_slice(&result.node1);
-                    unsafe { std::slice::from_raw_parts_mut(out_node2, n) }
-                        .copy_from_slice(&result.node2);
-                    unsafe { std::slice::from_raw_parts_mut(out_scores, n) }
-                        .copy_from_slice(&result.scores);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_preferential_attachment_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_node1,
+        out_node2,
+        out_scores,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_preferential_attachment`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_preferential_attachment_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_node1: *mut i64,
+    out_node2: *mut i64,
+    out_scores: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_preferential_attachment(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_node1 => result.node1,
+            out_node2 => result.node2,
+            out_scores => result.scores,
+        );
+        Ok(result.node1.len() as i64)
     })
 }
 
@@ -133,32 +160,40 @@ pub extern "C" fn onager_compute_resource_allocation(
     out_node2: *mut i64,
     out_scores: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_resource_allocation(src, dst) {
-            Ok(result) => {
-                let n = result.node1.len();
-                if !out_node1.is_null() && !out_node2.is_null() && !out_scores.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_node1, n) }
-                        .copy_from_slice(&result.node1);
-                    unsafe { std::slice::from_raw_parts_mut(out_node2, n) }
-                        .copy_from_slice(&result.node2);
-                    unsafe { std::slice::from_raw_parts_mut(out_scores, n) }
-                        .copy_from_slice(&result.scores);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_resource_allocation_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_node1,
+        out_node2,
+        out_scores,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_resource_allocation`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_resource_allocation_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_node1: *mut i64,
+    out_node2: *mut i64,
+    out_scores: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_resource_allocation(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_node1 => result.node1,
+            out_node2 => result.node2,
+            out_scores => result.scores,
+        );
+        Ok(result.node1.len() as i64)
     })
 }
 
@@ -172,31 +207,74 @@ pub extern "C" fn onager_compute_common_neighbors(
     out_node2: *mut i64,
     out_counts: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_common_neighbors(src, dst) {
-            Ok(result) => {
-                let n = result.node1.len();
-                if !out_node1.is_null() && !out_node2.is_null() && !out_counts.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_node1, n) }
-                        .copy_from_slice(&result.node1);
-                    unsafe { std::slice::from_raw_parts_mut(out_node2, n) }
-                        .copy_from_slice(&result.node2);
-                    unsafe { std::slice::from_raw_parts_mut(out_counts, n) }
-                        .copy_from_slice(&result.counts);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_common_neighbors_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_node1,
+        out_node2,
+        out_counts,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_common_neighbors`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_common_neighbors_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_node1: *mut i64,
+    out_node2: *mut i64,
+    out_counts: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_common_neighbors(src, dst)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_node1 => result.node1,
+            out_node2 => result.node2,
+            out_counts => result.counts,
+        );
+        Ok(result.node1.len() as i64)
+    })
+}
+
+/// Sample up to `n_samples` distinct node pairs that are not joined by an edge.
+///
+/// `strategy` is `uniform`, `degree`, or `hard`, and a negative `seed` means a
+/// random seed. Each pair is written as a row of `out_node1` and `out_node2`
+/// with `node1 < node2`. Returns the number of pairs.
+///
+/// # Safety
+/// `strategy` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_sample_negative_edges(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    n_samples: usize,
+    strategy: *const c_char,
+    seed: i64,
+    out_node1: *mut i64,
+    out_node2: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let strategy: NegativeSampling =
+            unsafe { read_str(strategy, "negative sampling strategy")? }.parse()?;
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::sample_negative_edges(src, dst, n_samples, strategy, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_node1 => result.node1,
+            out_node2 => result.node2,
+        );
+        Ok(result.node1.len() as i64)
     })
 }
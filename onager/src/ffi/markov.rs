@@ -0,0 +1,107 @@
+//! Markov chain FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, optional_slice};
+use crate::algorithms;
+
+/// Compute the stationary distribution of the random walk on weighted edges.
+///
+/// Empty weights mean unit weights. Each node is written as a row of
+/// `out_nodes` and `out_probabilities`. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_stationary_distribution(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    out_nodes: *mut i64,
+    out_probabilities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_stationary_distribution(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_probabilities => result.probabilities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute the expected number of random walk steps from each node to `target`.
+///
+/// Empty weights mean unit weights. Each node is written as a row of
+/// `out_nodes` and `out_hitting_times`, with infinity for nodes that may never
+/// reach the target. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_hitting_times(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    target: i64,
+    out_nodes: *mut i64,
+    out_hitting_times: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_hitting_times(src, dst, weights, target)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_hitting_times => result.hitting_times,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute removal-effect attribution of the channels in a journey graph.
+///
+/// Empty weights mean unit weights. `null_states` lists the non-converting
+/// absorbing states and may be empty. Each channel is written as a row of
+/// `out_nodes`, `out_removal_effects`, and `out_attributions`. Returns the
+/// number of channels.
+#[no_mangle]
+pub extern "C" fn onager_compute_markov_attribution(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    start: i64,
+    conversion: i64,
+    null_states_ptr: *const i64,
+    null_state_count: usize,
+    out_nodes: *mut i64,
+    out_removal_effects: *mut f64,
+    out_attributions: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let null_states = unsafe { optional_slice(null_states_ptr, null_state_count) };
+        let result = algorithms::compute_markov_attribution(
+            src,
+            dst,
+            weights,
+            start,
+            conversion,
+            null_states,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_removal_effects => result.removal_effects,
+            out_attributions => result.attributions,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
@@ -1,9 +1,11 @@
 //! Graph metrics FFI exports.
 //!
-//! Diameter, Radius, Average Clustering, Average Path Length, Transitivity, Triangle Count, Assortativity.
+//! Diameter, Radius, Average Clustering, Average Path Length, Transitivity, Triangle Count, Assortativity,
+//! Category Mixing Matrix, Directed Triangle Census, Weighted Triangle Intensity, Center and
+//! Periphery.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
+use super::common::{edge_slices, input_slice};
 use crate::algorithms;
 
 /// Compute graph diameter.
@@ -13,21 +15,9 @@ pub extern "C" fn onager_compute_diameter(
     dst_ptr: *const i64,
     edge_count: usize,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_diameter(src, dst) {
-            Ok(d) => d,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_diameter(src, dst)
     })
 }
 
@@ -38,21 +28,9 @@ pub extern "C" fn onager_compute_radius(
     dst_ptr: *const i64,
     edge_count: usize,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_radius(src, dst) {
-            Ok(r) => r,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_radius(src, dst)
     })
 }
 
@@ -63,21 +41,9 @@ pub extern "C" fn onager_compute_avg_clustering(
     dst_ptr: *const i64,
     edge_count: usize,
 ) -> f64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(f64::NAN, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return f64::NAN;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_avg_clustering(src, dst) {
-            Ok(v) => v,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                f64::NAN
-            }
-        }
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_avg_clustering(src, dst)
     })
 }
 
@@ -88,21 +54,9 @@ pub extern "C" fn onager_compute_avg_path_length(
     dst_ptr: *const i64,
     edge_count: usize,
 ) -> f64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(f64::NAN, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return f64::NAN;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_avg_path_length(src, dst) {
-            Ok(v) => v,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                f64::NAN
-            }
-        }
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_avg_path_length(src, dst)
     })
 }
 
@@ -113,21 +67,9 @@ pub extern "C" fn onager_compute_transitivity(
     dst_ptr: *const i64,
     edge_count: usize,
 ) -> f64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(f64::NAN, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return f64::NAN;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_transitivity(src, dst) {
-            Ok(v) => v,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                f64::NAN
-            }
-        }
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_transitivity(src, dst)
     })
 }
 
@@ -140,30 +82,33 @@ pub extern "C" fn onager_compute_triangle_count(
     out_nodes: *mut i64,
     out_counts: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_triangle_count(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_counts.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_counts, n) }
-                        .copy_from_slice(&result.triangle_counts);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_triangle_count_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_counts,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_triangle_count`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_triangle_count_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_counts: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_triangle_count(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_counts => result.triangle_counts);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -174,21 +119,9 @@ pub extern "C" fn onager_compute_assortativity(
     dst_ptr: *const i64,
     edge_count: usize,
 ) -> f64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(f64::NAN, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return f64::NAN;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_assortativity(src, dst) {
-            Ok(v) => v,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                f64::NAN
-            }
-        }
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_assortativity(src, dst)
     })
 }
 
@@ -202,20 +135,149 @@ pub extern "C" fn onager_compute_graph_density(
     edge_count: usize,
     directed: bool,
 ) -> f64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(f64::NAN, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return f64::NAN;
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_graph_density(src, dst, directed)
+    })
+}
+
+/// Compute the category mixing matrix and categorical assortativity.
+///
+/// `node_ids[i]` has category `node_categories[i]` for `i < node_count`. Each
+/// of the k x k matrix entries is written as a row of `out_row_categories`,
+/// `out_col_categories`, and `out_counts` in row-major order. Writes the
+/// assortativity, NaN when undefined, to `out_assortativity` when it is not
+/// null. Returns the number of entries.
+#[no_mangle]
+pub extern "C" fn onager_compute_mixing_matrix(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    node_ids_ptr: *const i64,
+    node_categories_ptr: *const i64,
+    node_count: usize,
+    directed: bool,
+    out_row_categories: *mut i64,
+    out_col_categories: *mut i64,
+    out_counts: *mut i64,
+    out_capacity: usize,
+    out_assortativity: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let node_ids = unsafe { input_slice(node_ids_ptr, node_count, "node_ids")? };
+        let node_categories =
+            unsafe { input_slice(node_categories_ptr, node_count, "node_categories")? };
+        let result =
+            algorithms::compute_mixing_matrix(src, dst, node_ids, node_categories, directed)?;
+        let k = result.categories.len();
+        let rows: Vec<i64> = (0..k * k).map(|e| result.categories[e / k]).collect();
+        let cols: Vec<i64> = (0..k * k).map(|e| result.categories[e % k]).collect();
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_row_categories => rows,
+            out_col_categories => cols,
+            out_counts => result.counts,
+        );
+        if !out_assortativity.is_null() {
+            unsafe { *out_assortativity = result.assortativity };
         }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_graph_density(src, dst, directed) {
-            Ok(v) => v,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                f64::NAN
-            }
+        Ok(result.counts.len() as i64)
+    })
+}
+
+/// Count directed triangles by type.
+///
+/// Writes one count per type to `out_counts`, ordered 030T, 030C, 120D, 120U,
+/// 120C, 210, and 300. Returns the number of types.
+#[no_mangle]
+pub extern "C" fn onager_compute_triangle_census(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_counts: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_triangle_census(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_counts => result.counts);
+        Ok(result.counts.len() as i64)
+    })
+}
+
+/// Count triangles and their weighted intensity for each node.
+///
+/// `weights_ptr` holds one weight per edge. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_weighted_triangles(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weights_ptr: *const f64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_counts: *mut i64,
+    out_intensities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weights_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_weighted_triangles(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_counts => result.triangle_counts,
+            out_intensities => result.intensities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute the center nodes, whose eccentricity equals the radius.
+///
+/// Writes the radius to `out_eccentricity` when it is not null. Returns the
+/// number of center nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_center_nodes(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+    out_eccentricity: *mut i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_center_nodes(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        if !out_eccentricity.is_null() {
+            unsafe { *out_eccentricity = result.eccentricity };
+        }
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute the periphery nodes, whose eccentricity equals the diameter.
+///
+/// Writes the diameter to `out_eccentricity` when it is not null. Returns the
+/// number of periphery nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_periphery_nodes(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+    out_eccentricity: *mut i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_periphery_nodes(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        if !out_eccentricity.is_null() {
+            unsafe { *out_eccentricity = result.eccentricity };
         }
+        Ok(result.node_ids.len() as i64)
     })
 }
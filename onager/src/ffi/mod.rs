@@ -4,28 +4,76 @@
 //! Functions are organized by category into submodules.
 
 mod approximation;
+mod bipartite;
 mod centrality;
+mod coarsening;
 mod common;
 mod community;
+mod correlation;
+mod drift;
+mod estimate;
+mod facility;
+mod flow;
 mod generators;
+mod hierarchical;
+mod infomap;
+mod interning;
+mod jobs;
+mod kernels;
+mod layout;
+mod link_analysis;
 mod links;
+mod markov;
 mod metrics;
 mod mst;
+mod multilayer;
 mod parallel;
+mod patterns;
 mod personalized;
+mod roles;
+mod sbm;
+mod spectral;
+mod spill;
 mod subgraphs;
+mod temporal;
 mod traversal;
+mod weighted_rank;
+mod weights;
 
 // Re-export all public FFI items
 pub use approximation::*;
+pub use bipartite::*;
 pub use centrality::*;
+pub use coarsening::*;
 pub use common::*;
 pub use community::*;
+pub use correlation::*;
+pub use drift::*;
+pub use estimate::*;
+pub use facility::*;
+pub use flow::*;
 pub use generators::*;
+pub use hierarchical::*;
+pub use infomap::*;
+pub use interning::*;
+pub use jobs::*;
+pub use kernels::*;
+pub use layout::*;
+pub use link_analysis::*;
 pub use links::*;
+pub use markov::*;
 pub use metrics::*;
 pub use mst::*;
+pub use multilayer::*;
 pub use parallel::*;
+pub use patterns::*;
 pub use personalized::*;
+pub use roles::*;
+pub use sbm::*;
+pub use spectral::*;
+pub use spill::*;
 pub use subgraphs::*;
+pub use temporal::*;
 pub use traversal::*;
+pub use weighted_rank::*;
+pub use weights::*;
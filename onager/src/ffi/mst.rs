@@ -1,9 +1,9 @@
 //! MST (Minimum Spanning Tree) FFI exports.
 //!
-//! Prim's and Kruskal's algorithms.
+//! Prim's and Kruskal's algorithms, degree-constrained spanning trees.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
+use super::common::{edge_slices, input_slice};
 use crate::algorithms;
 
 /// Compute Prim's MST on weighted edge arrays.
@@ -18,38 +18,48 @@ pub extern "C" fn onager_compute_prim_mst(
     out_weight: *mut f64,
     out_total: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() || weight_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        let weights = unsafe { std::slice::from_raw_parts(weight_ptr, edge_count) };
-        match algorithms::compute_prim_mst(src, dst, weights) {
-            Ok(result) => {
-                let n = result.src_nodes.len();
-                if !out_src.is_null() && !out_dst.is_null() && !out_weight.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, n) }
-                        .copy_from_slice(&result.src_nodes);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, n) }
-                        .copy_from_slice(&result.dst_nodes);
-                    unsafe { std::slice::from_raw_parts_mut(out_weight, n) }
-                        .copy_from_slice(&result.weights);
-                }
-                if !out_total.is_null() {
-                    unsafe {
-                        *out_total = result.total_weight;
-                    }
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
+    onager_compute_prim_mst_v2(
+        src_ptr,
+        dst_ptr,
+        weight_ptr,
+        edge_count,
+        out_src,
+        out_dst,
+        out_weight,
+        out_total,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_prim_mst`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_prim_mst_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weight: *mut f64,
+    out_total: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_prim_mst(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_weight => result.weights,
+        );
+        if !out_total.is_null() {
+            unsafe { *out_total = result.total_weight };
         }
+        Ok(result.src_nodes.len() as i64)
     })
 }
 
@@ -65,37 +75,151 @@ pub extern "C" fn onager_compute_kruskal_mst(
     out_weight: *mut f64,
     out_total: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() || weight_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        let weights = unsafe { std::slice::from_raw_parts(weight_ptr, edge_count) };
-        match algorithms::compute_kruskal_mst(src, dst, weights) {
-            Ok(result) => {
-                let n = result.src_nodes.len();
-                if !out_src.is_null() && !out_dst.is_null() && !out_weight.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, n) }
-                        .copy_from_slice(&result.src_nodes);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, n) }
-                        .copy_from_slice(&result.dst_nodes);
-                    unsafe { std::slice::from_raw_parts_mut(out_weight, n) }
-                        .copy_from_slice(&result.weights);
-                }
-                if !out_total.is_null() {
-                    unsafe {
-                        *out_total = result.total_weight;
-                    }
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
+    onager_compute_kruskal_mst_v2(
+        src_ptr,
+        dst_ptr,
+        weight_ptr,
+        edge_count,
+        out_src,
+        out_dst,
+        out_weight,
+        out_total,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_kruskal_mst`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_kruskal_mst_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weight: *mut f64,
+    out_total: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_kruskal_mst(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_weight => result.weights,
+        );
+        if !out_total.is_null() {
+            unsafe { *out_total = result.total_weight };
         }
+        Ok(result.src_nodes.len() as i64)
+    })
+}
+
+/// Compute Prim's MST and label every edge with its tree.
+///
+/// Each edge is written as a row of `out_src`, `out_dst`, `out_weight`,
+/// `out_component`, and `out_component_weight`, where the component is the
+/// smallest node ID of the tree and the component weight is its total weight.
+/// Returns the number of edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_prim_mst_forest(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weight: *mut f64,
+    out_component: *mut i64,
+    out_component_weight: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_prim_mst(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_weight => result.weights,
+            out_component => result.component_ids,
+            out_component_weight => result.component_weights,
+        );
+        Ok(result.src_nodes.len() as i64)
+    })
+}
+
+/// Compute Kruskal's MST and label every edge with its tree.
+///
+/// Each edge is written as a row of `out_src`, `out_dst`, `out_weight`,
+/// `out_component`, and `out_component_weight`, where the component is the
+/// smallest node ID of the tree and the component weight is its total weight.
+/// Returns the number of edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_kruskal_mst_forest(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weight: *mut f64,
+    out_component: *mut i64,
+    out_component_weight: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_kruskal_mst(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_weight => result.weights,
+            out_component => result.component_ids,
+            out_component_weight => result.component_weights,
+        );
+        Ok(result.src_nodes.len() as i64)
+    })
+}
+
+/// Compute a spanning tree where no node exceeds `max_degree` tree edges.
+///
+/// Writes the same rows as `onager_compute_kruskal_mst_forest`. Returns the
+/// number of edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_dcmst(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    max_degree: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weight: *mut f64,
+    out_component: *mut i64,
+    out_component_weight: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_dcmst(src, dst, weights, max_degree)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_weight => result.weights,
+            out_component => result.component_ids,
+            out_component_weight => result.component_weights,
+        );
+        Ok(result.src_nodes.len() as i64)
     })
 }
@@ -0,0 +1,157 @@
+//! Multilayer network FFI exports.
+//!
+//! Each edge carries a layer ID in `layers_ptr`, one per edge.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, input_slice};
+use crate::algorithms;
+
+/// Compute the degree of every node across all layers.
+///
+/// Writes the total degree, the number of layers the node appears in, and the
+/// participation coefficient. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_multiplex_degree(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    layers_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_degrees: *mut i64,
+    out_layer_counts: *mut i64,
+    out_participation: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let layers = unsafe { input_slice(layers_ptr, edge_count, "layers")? };
+        let result = algorithms::compute_multiplex_degree(src, dst, layers)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_degrees => result.degrees,
+            out_layer_counts => result.layer_counts,
+            out_participation => result.participation,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute the degree of every node in each layer.
+///
+/// Returns the number of `(node, layer)` rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_multiplex_layer_degree(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    layers_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_layers: *mut i64,
+    out_degrees: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let layers = unsafe { input_slice(layers_ptr, edge_count, "layers")? };
+        let result = algorithms::compute_multiplex_degree(src, dst, layers)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.layer_node_ids,
+            out_layers => result.layers,
+            out_degrees => result.layer_degrees,
+        );
+        Ok(result.layer_node_ids.len() as i64)
+    })
+}
+
+/// Compare the edge sets of every pair of layers.
+///
+/// Returns the number of layer pairs.
+#[no_mangle]
+pub extern "C" fn onager_compute_layer_overlap(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    layers_ptr: *const i64,
+    edge_count: usize,
+    directed: bool,
+    out_layer_a: *mut i64,
+    out_layer_b: *mut i64,
+    out_shared: *mut i64,
+    out_jaccard: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let layers = unsafe { input_slice(layers_ptr, edge_count, "layers")? };
+        let result = algorithms::compute_layer_overlap(src, dst, layers, directed)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_layer_a => result.layer_a,
+            out_layer_b => result.layer_b,
+            out_shared => result.shared_edges,
+            out_jaccard => result.jaccard,
+        );
+        Ok(result.layer_a.len() as i64)
+    })
+}
+
+/// Compute PageRank over the overlay of all layers.
+///
+/// Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_multiplex_pagerank(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    layers_ptr: *const i64,
+    edge_count: usize,
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+    out_nodes: *mut i64,
+    out_ranks: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let layers = unsafe { input_slice(layers_ptr, edge_count, "layers")? };
+        let result = algorithms::compute_multiplex_pagerank(
+            src, dst, layers, damping, iterations, directed,
+        )?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_ranks => result.ranks);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute PageRank within each layer.
+///
+/// Returns the number of `(node, layer)` rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_multiplex_layer_pagerank(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    layers_ptr: *const i64,
+    edge_count: usize,
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+    out_nodes: *mut i64,
+    out_layers: *mut i64,
+    out_ranks: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let layers = unsafe { input_slice(layers_ptr, edge_count, "layers")? };
+        let result = algorithms::compute_multiplex_pagerank(
+            src, dst, layers, damping, iterations, directed,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.layer_node_ids,
+            out_layers => result.layers,
+            out_ranks => result.layer_ranks,
+        );
+        Ok(result.layer_node_ids.len() as i64)
+    })
+}
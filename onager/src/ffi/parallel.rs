@@ -1,10 +1,10 @@
 //! Parallel algorithms FFI exports.
 //!
-//! Parallel BFS, shortest paths, components, clustering, triangles.
+//! Parallel BFS, shortest paths, components, clustering, triangles, Borůvka MST.
 //! Note: Parallel PageRank is in centrality.rs.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
+use super::common::{edge_slices, input_slice};
 use crate::algorithms;
 
 /// Compute parallel BFS from a single source.
@@ -16,28 +16,26 @@ pub extern "C" fn onager_compute_bfs_parallel(
     source: i64,
     out_order: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_bfs_parallel(src, dst, source) {
-            Ok(result) => {
-                let n = result.order.len();
-                if !out_order.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_order, n) }
-                        .copy_from_slice(&result.order);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_bfs_parallel_v2(src_ptr, dst_ptr, edge_count, source, out_order, usize::MAX)
+}
+
+/// Capacity-checked variant of `onager_compute_bfs_parallel`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_bfs_parallel_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    source: i64,
+    out_order: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_bfs_parallel(src, dst, source)?;
+        crate::ffi_write_outputs!(out_capacity; out_order => result.order);
+        Ok(result.order.len() as i64)
     })
 }
 
@@ -51,30 +49,35 @@ pub extern "C" fn onager_compute_shortest_paths_parallel(
     out_nodes: *mut i64,
     out_distances: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_shortest_paths_parallel(src, dst, source) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_distances.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_distances, n) }
-                        .copy_from_slice(&result.distances);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_shortest_paths_parallel_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        source,
+        out_nodes,
+        out_distances,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_shortest_paths_parallel`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_shortest_paths_parallel_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    source: i64,
+    out_nodes: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_shortest_paths_parallel(src, dst, source)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_distances => result.distances);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -87,30 +90,33 @@ pub extern "C" fn onager_compute_components_parallel(
     out_nodes: *mut i64,
     out_components: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_components_parallel(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_components.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_components, n) }
-                        .copy_from_slice(&result.component_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_components_parallel_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_components,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_components_parallel`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_components_parallel_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_components: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_components_parallel(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_components => result.component_ids);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -123,30 +129,33 @@ pub extern "C" fn onager_compute_clustering_parallel(
     out_nodes: *mut i64,
     out_coefficients: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_clustering_parallel(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_coefficients.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_coefficients, n) }
-                        .copy_from_slice(&result.coefficients);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_clustering_parallel_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_coefficients,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_clustering_parallel`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_clustering_parallel_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_coefficients: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_clustering_parallel(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_coefficients => result.coefficients);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -159,29 +168,66 @@ pub extern "C" fn onager_compute_triangles_parallel(
     out_nodes: *mut i64,
     out_counts: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_triangles_parallel(src, dst) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_counts.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_counts, n) }
-                        .copy_from_slice(&result.triangle_counts);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_triangles_parallel_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        out_nodes,
+        out_counts,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_triangles_parallel`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_triangles_parallel_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_counts: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_triangles_parallel(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_counts => result.triangle_counts);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute a minimum spanning tree with parallel Borůvka.
+///
+/// Each edge is written as a row of `out_src`, `out_dst`, `out_weight`,
+/// `out_component`, and `out_component_weight`, like
+/// `onager_compute_kruskal_mst_forest`. Returns the number of edges.
+#[no_mangle]
+pub extern "C" fn onager_compute_boruvka_mst(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weight: *mut f64,
+    out_component: *mut i64,
+    out_component_weight: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_boruvka_mst(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_weight => result.weights,
+            out_component => result.component_ids,
+            out_component_weight => result.component_weights,
+        );
+        Ok(result.src_nodes.len() as i64)
     })
 }
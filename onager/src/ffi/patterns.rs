@@ -0,0 +1,116 @@
+//! Transaction pattern FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, input_slice};
+use crate::algorithms;
+
+/// Find nodes with fan-in or fan-out bursts in a timestamped edge list.
+///
+/// Each reported node and direction is written as a row of `out_nodes`,
+/// `out_directions`, `out_fan_sizes`, `out_edge_counts`, `out_window_starts`,
+/// and `out_window_ends`. Directions are coded as 0 (fan-in) or 1 (fan-out).
+/// Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_fan_patterns(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    ts_ptr: *const i64,
+    edge_count: usize,
+    min_fan: usize,
+    window: i64,
+    out_nodes: *mut i64,
+    out_directions: *mut i64,
+    out_fan_sizes: *mut i64,
+    out_edge_counts: *mut i64,
+    out_window_starts: *mut i64,
+    out_window_ends: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let ts = unsafe { input_slice(ts_ptr, edge_count, "timestamps")? };
+        let result = algorithms::compute_fan_patterns(src, dst, ts, min_fan, window)?;
+        let directions: Vec<i64> = result.directions.iter().map(|&d| d as i64).collect();
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_directions => directions,
+            out_fan_sizes => result.fan_sizes,
+            out_edge_counts => result.edge_counts,
+            out_window_starts => result.window_starts,
+            out_window_ends => result.window_ends,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Find windows in which a node is unusually active.
+///
+/// Each flagged node and window is written as a row of `out_nodes`,
+/// `out_window_starts`, `out_activities`, and `out_zscores`.
+/// Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_detect_bursts(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    ts_ptr: *const i64,
+    edge_count: usize,
+    window: i64,
+    zscore_threshold: f64,
+    out_nodes: *mut i64,
+    out_window_starts: *mut i64,
+    out_activities: *mut i64,
+    out_zscores: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let ts = unsafe { input_slice(ts_ptr, edge_count, "timestamps")? };
+        let result = algorithms::detect_bursts(src, dst, ts, window, zscore_threshold)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_window_starts => result.window_starts,
+            out_activities => result.activities,
+            out_zscores => result.zscores,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Find directed cycles whose edge weights approximately conserve.
+///
+/// Each cycle node is written as a row of `out_cycle_ids`, `out_positions`,
+/// `out_nodes`, and `out_weights`, where the weight is that of the edge
+/// leaving the node. At most `max_cycles` cycles are searched for.
+/// Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_find_weighted_cycles(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weights_ptr: *const f64,
+    edge_count: usize,
+    max_len: usize,
+    tolerance: f64,
+    max_cycles: usize,
+    out_cycle_ids: *mut i64,
+    out_positions: *mut i64,
+    out_nodes: *mut i64,
+    out_weights: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weights_ptr, edge_count, "weights")? };
+        let result =
+            algorithms::find_weighted_cycles(src, dst, weights, max_len, tolerance, max_cycles)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_cycle_ids => result.cycle_ids,
+            out_positions => result.positions,
+            out_nodes => result.node_ids,
+            out_weights => result.weights,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
@@ -1,7 +1,7 @@
 //! Personalized PageRank FFI exports.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
+use super::common::{edge_slices, optional_slice};
 use crate::algorithms;
 
 /// Compute personalized PageRank.
@@ -19,51 +19,85 @@ pub extern "C" fn onager_compute_personalized_pagerank(
     out_nodes: *mut i64,
     out_scores: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
+    onager_compute_personalized_pagerank_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        pers_nodes_ptr,
+        pers_weights_ptr,
+        pers_count,
+        damping,
+        max_iter,
+        tolerance,
+        out_nodes,
+        out_scores,
+        usize::MAX,
+    )
+}
 
-        let personalization: Vec<(i64, f64)> =
-            if pers_count > 0 && !pers_nodes_ptr.is_null() && !pers_weights_ptr.is_null() {
-                let pers_nodes = unsafe { std::slice::from_raw_parts(pers_nodes_ptr, pers_count) };
-                let pers_weights =
-                    unsafe { std::slice::from_raw_parts(pers_weights_ptr, pers_count) };
-                pers_nodes
-                    .iter()
-                    .zip(pers_weights.iter())
-                    .map(|(&n, &w)| (n, w))
-                    .collect()
-            } else {
-                Vec::new()
-            };
+/// Capacity-checked variant of `onager_compute_personalized_pagerank`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_personalized_pagerank_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    pers_nodes_ptr: *const i64,
+    pers_weights_ptr: *const f64,
+    pers_count: usize,
+    damping: f64,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_scores: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let pers_nodes = unsafe { optional_slice(pers_nodes_ptr, pers_count) };
+        let pers_weights = unsafe { optional_slice(pers_weights_ptr, pers_count) };
+        let personalization: Vec<(i64, f64)> = pers_nodes
+            .iter()
+            .zip(pers_weights.iter())
+            .map(|(&n, &w)| (n, w))
+            .collect();
 
-        match algorithms::compute_personalized_pagerank(
+        let result = algorithms::compute_personalized_pagerank(
             src,
             dst,
             &personalization,
             damping,
             max_iter,
             tolerance,
-        ) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_scores.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_scores, n) }
-                        .copy_from_slice(&result.scores);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+        )?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_scores => result.scores);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute personalized PageRank with uniform teleportation over a node subset.
+#[no_mangle]
+pub extern "C" fn onager_compute_personalized_pagerank_uniform(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    subset_ptr: *const i64,
+    subset_count: usize,
+    damping: f64,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_scores: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let subset = unsafe { optional_slice(subset_ptr, subset_count) };
+        let result = algorithms::compute_personalized_pagerank_uniform(
+            src, dst, subset, damping, max_iter, tolerance,
+        )?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_scores => result.scores);
+        Ok(result.node_ids.len() as i64)
     })
 }
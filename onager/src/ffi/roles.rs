@@ -0,0 +1,42 @@
+//! Node role classification FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::edge_slices;
+use crate::algorithms;
+
+/// Classify nodes into hubs, connectors, peripheral nodes, and isolates.
+///
+/// Each node is written as a row of `out_nodes`, `out_roles`, `out_degrees`,
+/// `out_betweenness`, and `out_clustering`. Roles are coded as 0 (isolate),
+/// 1 (peripheral), 2 (connector), or 3 (hub). A negative `seed` means a
+/// random seed. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_classify_node_roles(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    samples: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_roles: *mut i64,
+    out_degrees: *mut i64,
+    out_betweenness: *mut f64,
+    out_clustering: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::classify_node_roles(src, dst, samples, seed_opt)?;
+        let roles: Vec<i64> = result.roles.iter().map(|&role| role as i64).collect();
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_roles => roles,
+            out_degrees => result.degrees,
+            out_betweenness => result.betweenness,
+            out_clustering => result.clustering,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
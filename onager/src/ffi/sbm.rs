@@ -0,0 +1,41 @@
+//! Stochastic block model FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::edge_slices;
+use crate::algorithms;
+
+/// Fit a degree-corrected stochastic block model.
+///
+/// Writes node block assignments to `out_nodes` and `out_blocks`, and the
+/// row-major `k x k` block probability matrix to `out_probabilities` when
+/// `out_probabilities_capacity` is at least `k * k`. A negative `seed` means
+/// a random seed. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_fit_sbm(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    k: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_blocks: *mut i64,
+    out_capacity: usize,
+    out_probabilities: *mut f64,
+    out_probabilities_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::fit_sbm(src, dst, k, seed_opt)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_blocks => result.block_ids,
+        );
+        crate::ffi_write_outputs!(
+            out_probabilities_capacity;
+            out_probabilities => result.block_probabilities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
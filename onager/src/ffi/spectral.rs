@@ -0,0 +1,45 @@
+//! Weighted spectral clustering FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, optional_slice};
+use crate::algorithms;
+
+/// Compute normalized spectral clustering over weighted edges.
+///
+/// Empty weights mean unit weights. A `k` of 0 picks the number of clusters
+/// in `1..=max_k` by the largest eigengap, and the number used is written to
+/// `out_k`. A negative `seed` means a random seed. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_weighted_spectral_clustering(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    k: usize,
+    max_k: usize,
+    seed: i64,
+    out_nodes: *mut i64,
+    out_communities: *mut i64,
+    out_capacity: usize,
+    out_k: *mut i64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let k_opt = if k == 0 { None } else { Some(k) };
+        let seed_opt = if seed < 0 { None } else { Some(seed as u64) };
+        let result = algorithms::compute_weighted_spectral_clustering(
+            src, dst, weights, k_opt, max_k, seed_opt,
+        )?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_communities => result.community_ids,
+        );
+        if !out_k.is_null() {
+            unsafe { *out_k = result.k as i64 };
+        }
+        Ok(result.node_ids.len() as i64)
+    })
+}
@@ -0,0 +1,102 @@
+//! Result spilling FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::ffi::c_void;
+use std::io::{self, BufWriter, Write};
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, optional_slice, read_str};
+use crate::algorithms::{self, LinkScore};
+use crate::error::{OnagerError, Result};
+
+/// Callback that appends `len` bytes at `data` to the output owned by `ctx`.
+///
+/// Returns 0 on success and any other value on failure, which aborts the
+/// spill. The caller keeps ownership of the output, so opening, permission
+/// checks, and cleanup of files stay on the caller's side.
+pub type OnagerWriteFn =
+    Option<unsafe extern "C" fn(ctx: *mut c_void, data: *const u8, len: usize) -> i32>;
+
+/// Adapts a write callback to `std::io::Write`.
+struct CallbackWriter {
+    write: unsafe extern "C" fn(*mut c_void, *const u8, usize) -> i32,
+    ctx: *mut c_void,
+}
+
+impl Write for CallbackWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if unsafe { (self.write)(self.ctx, buf.as_ptr(), buf.len()) } != 0 {
+            return Err(io::Error::other("spill output callback failed"));
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Wraps the callback in a buffered writer, failing on a null callback.
+fn spill_output(write: OnagerWriteFn, ctx: *mut c_void) -> Result<BufWriter<CallbackWriter>> {
+    let write = write.ok_or_else(|| {
+        OnagerError::InvalidArgument("Null pointer for spill output callback".to_string())
+    })?;
+    Ok(BufWriter::with_capacity(
+        1 << 20,
+        CallbackWriter { write, ctx },
+    ))
+}
+
+/// Write all-pairs shortest distances as a Parquet file through `write`.
+///
+/// The file has the columns `src`, `dst`, and `distance`. Returns the number
+/// of rows written, or -1 on error.
+///
+/// # Safety
+/// `write` must be safe to call with `ctx` until this function returns.
+#[no_mangle]
+pub unsafe extern "C" fn onager_spill_all_pairs_distances(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    write: OnagerWriteFn,
+    ctx: *mut c_void,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let out = spill_output(write, ctx)?;
+        let rows = algorithms::spill_all_pairs_distances(src, dst, weights, out)?;
+        Ok(rows as i64)
+    })
+}
+
+/// Write link prediction scores for all node pairs as a Parquet file through
+/// `write`.
+///
+/// `score` is one of `jaccard`, `adamic_adar`, `resource_alloc`,
+/// `common_neighbors`, or `pref_attach`. The file has the columns `node1`,
+/// `node2`, and `score`. Returns the number of rows written, or -1 on error.
+///
+/// # Safety
+/// `score` must point to a valid null-terminated C string, and `write` must be
+/// safe to call with `ctx` until this function returns.
+#[no_mangle]
+pub unsafe extern "C" fn onager_spill_link_scores(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    score: *const c_char,
+    write: OnagerWriteFn,
+    ctx: *mut c_void,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let score: LinkScore = unsafe { read_str(score, "link score")? }.parse()?;
+        let out = spill_output(write, ctx)?;
+        let rows = algorithms::spill_link_scores(src, dst, score, out)?;
+        Ok(rows as i64)
+    })
+}
@@ -1,9 +1,10 @@
 //! Subgraph operations FFI exports.
 //!
-//! Ego graph, k-hop neighbors, induced subgraph.
+//! Ego graph, ego network metrics, k-hop neighbors, induced subgraph, forbidden
+//! node and edge removal, and densest subgraph.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
+use super::common::{edge_slices, input_slice, optional_slice};
 use crate::algorithms;
 
 /// Compute ego graph.
@@ -17,30 +18,81 @@ pub extern "C" fn onager_compute_ego_graph(
     out_src: *mut i64,
     out_dst: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_ego_graph(src, dst, center, radius) {
-            Ok(result) => {
-                let n = result.src.len();
-                if !out_src.is_null() && !out_dst.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, n) }
-                        .copy_from_slice(&result.src);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, n) }
-                        .copy_from_slice(&result.dst);
-                }
-                n as i64
+    onager_compute_ego_graph_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        center,
+        radius,
+        out_src,
+        out_dst,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_ego_graph`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_ego_graph_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    center: i64,
+    radius: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_ego_graph(src, dst, center, radius)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Compute the ego network profile of a node.
+///
+/// Empty weights mean unit weights. Writes the number of alters and the
+/// number of the center's ties to `out_counts`, and the density, broker score,
+/// and mean, smallest, and largest tie strength to `out_metrics`, when
+/// non-null. `out_counts` takes two values and `out_metrics` takes five.
+/// Returns the number of alters.
+#[no_mangle]
+pub extern "C" fn onager_compute_ego_metrics(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    center: i64,
+    radius: usize,
+    directed: bool,
+    out_counts: *mut i64,
+    out_metrics: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_ego_metrics(src, dst, weights, center, radius, directed)?;
+        unsafe {
+            if !out_counts.is_null() {
+                *out_counts = result.size as i64;
+                *out_counts.add(1) = result.tie_count as i64;
             }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
+            if !out_metrics.is_null() {
+                let metrics = [
+                    result.density,
+                    result.broker_score,
+                    result.tie_strength_mean,
+                    result.tie_strength_min,
+                    result.tie_strength_max,
+                ];
+                std::ptr::copy_nonoverlapping(metrics.as_ptr(), out_metrics, metrics.len());
             }
         }
+        Ok(result.size as i64)
     })
 }
 
@@ -54,28 +106,35 @@ pub extern "C" fn onager_compute_k_hop_neighbors(
     k: usize,
     out_nodes: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_k_hop_neighbors(src, dst, start, k) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_k_hop_neighbors_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        start,
+        k,
+        out_nodes,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_k_hop_neighbors`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_k_hop_neighbors_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    start: i64,
+    k: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_k_hop_neighbors(src, dst, start, k)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -90,30 +149,104 @@ pub extern "C" fn onager_compute_induced_subgraph(
     out_src: *mut i64,
     out_dst: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() || node_ids_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
+    onager_compute_induced_subgraph_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        node_ids_ptr,
+        node_count,
+        out_src,
+        out_dst,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_induced_subgraph`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_induced_subgraph_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    node_ids_ptr: *const i64,
+    node_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let node_ids = unsafe { input_slice(node_ids_ptr, node_count, "node ids")? };
+        let result = algorithms::compute_induced_subgraph(src, dst, node_ids)?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Remove forbidden nodes and edges from an edge list.
+///
+/// Empty weights produce no output weights. Returns the number of kept edges.
+#[no_mangle]
+pub extern "C" fn onager_remove_forbidden(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    forbidden_nodes_ptr: *const i64,
+    forbidden_nodes_count: usize,
+    forbidden_src_ptr: *const i64,
+    forbidden_dst_ptr: *const i64,
+    forbidden_edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weights: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let forbidden_nodes = unsafe { optional_slice(forbidden_nodes_ptr, forbidden_nodes_count) };
+        let forbidden_src = unsafe { optional_slice(forbidden_src_ptr, forbidden_edge_count) };
+        let forbidden_dst = unsafe { optional_slice(forbidden_dst_ptr, forbidden_edge_count) };
+        let result = algorithms::remove_forbidden(
+            src,
+            dst,
+            weights,
+            forbidden_nodes,
+            forbidden_src,
+            forbidden_dst,
+        )?;
+        crate::ffi_write_outputs!(out_capacity; out_src => result.src, out_dst => result.dst);
+        if !result.weights.is_empty() {
+            crate::ffi_write_outputs!(out_capacity; out_weights => result.weights);
         }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        let node_ids = unsafe { std::slice::from_raw_parts(node_ids_ptr, node_count) };
-        match algorithms::compute_induced_subgraph(src, dst, node_ids) {
-            Ok(result) => {
-                let n = result.src.len();
-                if !out_src.is_null() && !out_dst.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, n) }
-                        .copy_from_slice(&result.src);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, n) }
-                        .copy_from_slice(&result.dst);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
+        Ok(result.src.len() as i64)
+    })
+}
+
+/// Find the densest subgraph by peeling, or exactly by maximum flow.
+///
+/// Writes the density of the returned node set to `out_density` when it is not
+/// null. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_densest_subgraph(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    exact: bool,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+    out_density: *mut f64,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_densest_subgraph(src, dst, exact)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        if !out_density.is_null() {
+            unsafe { *out_density = result.density };
         }
+        Ok(result.node_ids.len() as i64)
     })
 }
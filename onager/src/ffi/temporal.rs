@@ -0,0 +1,97 @@
+//! Temporal network FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, input_slice};
+use crate::algorithms;
+
+/// Compute betweenness centrality over time-respecting shortest paths.
+///
+/// Each node is written as a row of `out_nodes` and `out_centralities`.
+/// Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_temporal_betweenness(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    ts_ptr: *const i64,
+    edge_count: usize,
+    normalized: bool,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let ts = unsafe { input_slice(ts_ptr, edge_count, "timestamps")? };
+        let result = algorithms::compute_temporal_betweenness(src, dst, ts, normalized)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_centralities => result.centralities,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Find the nodes reachable from `source` by time-respecting paths within
+/// `[t_start, t_end]`.
+///
+/// Each reached node is written as a row of `out_nodes` and
+/// `out_arrival_times`. Returns the number of reached nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_temporal_reachability(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    ts_ptr: *const i64,
+    edge_count: usize,
+    source: i64,
+    t_start: i64,
+    t_end: i64,
+    out_nodes: *mut i64,
+    out_arrival_times: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let ts = unsafe { input_slice(ts_ptr, edge_count, "timestamps")? };
+        let result = algorithms::temporal_reachability(src, dst, ts, source, t_start, t_end)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_arrival_times => result.arrival_times,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Split each source entity's timestamped edges into sessions and return the
+/// nodes each session visits.
+///
+/// Each step is written as a row of `out_sessions`, `out_entities`,
+/// `out_steps`, and `out_nodes`. Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_extract_paths(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    ts_ptr: *const i64,
+    edge_count: usize,
+    session_gap: i64,
+    out_sessions: *mut i64,
+    out_entities: *mut i64,
+    out_steps: *mut i64,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let ts = unsafe { input_slice(ts_ptr, edge_count, "timestamps")? };
+        let result = algorithms::extract_paths(src, dst, ts, session_gap)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_sessions => result.session_ids,
+            out_entities => result.entity_ids,
+            out_steps => result.steps,
+            out_nodes => result.node_ids,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
@@ -1,9 +1,11 @@
 //! Traversal and path FFI exports.
 //!
-//! Dijkstra, BFS, DFS, Bellman-Ford, Floyd-Warshall, Shortest Distance.
+//! Dijkstra, BFS, DFS, Bellman-Ford, Shortest Path, Floyd-Warshall, Shortest Distance
+//! (unweighted and weighted), Shortest Path Tree, Nodes Within Cost, Topological Sort,
+//! DAG Check, Widest Path, Typed Paths, Pairwise Distances.
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use super::common::{clear_last_error, set_last_error};
+use super::common::{edge_slices, input_slice, optional_slice};
 use crate::algorithms;
 
 /// Compute Dijkstra shortest paths.
@@ -16,30 +18,35 @@ pub extern "C" fn onager_compute_dijkstra(
     out_nodes: *mut i64,
     out_distances: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_dijkstra(src, dst, source_node) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_distances.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_distances, n) }
-                        .copy_from_slice(&result.distances);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_dijkstra_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        source_node,
+        out_nodes,
+        out_distances,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_dijkstra`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_dijkstra_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    source_node: i64,
+    out_nodes: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_dijkstra(src, dst, source_node)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_distances => result.distances);
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -52,28 +59,33 @@ pub extern "C" fn onager_compute_bfs(
     source_node: i64,
     out_order: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_bfs(src, dst, source_node) {
-            Ok(result) => {
-                let n = result.order.len();
-                if !out_order.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_order, n) }
-                        .copy_from_slice(&result.order);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_bfs_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        source_node,
+        out_order,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_bfs`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_bfs_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    source_node: i64,
+    out_order: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_bfs(src, dst, source_node)?;
+        crate::ffi_write_outputs!(out_capacity; out_order => result.order);
+        Ok(result.order.len() as i64)
     })
 }
 
@@ -86,28 +98,33 @@ pub extern "C" fn onager_compute_dfs(
     source_node: i64,
     out_order: *mut i64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_dfs(src, dst, source_node) {
-            Ok(result) => {
-                let n = result.order.len();
-                if !out_order.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_order, n) }
-                        .copy_from_slice(&result.order);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_dfs_v2(
+        src_ptr,
+        dst_ptr,
+        edge_count,
+        source_node,
+        out_order,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_dfs`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_dfs_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    source_node: i64,
+    out_order: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_dfs(src, dst, source_node)?;
+        crate::ffi_write_outputs!(out_capacity; out_order => result.order);
+        Ok(result.order.len() as i64)
     })
 }
 
@@ -122,31 +139,72 @@ pub extern "C" fn onager_compute_bellman_ford(
     out_nodes: *mut i64,
     out_distances: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() || weight_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        let weights = unsafe { std::slice::from_raw_parts(weight_ptr, edge_count) };
-        match algorithms::compute_bellman_ford(src, dst, weights, source) {
-            Ok(result) => {
-                let n = result.node_ids.len();
-                if !out_nodes.is_null() && !out_distances.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_nodes, n) }
-                        .copy_from_slice(&result.node_ids);
-                    unsafe { std::slice::from_raw_parts_mut(out_distances, n) }
-                        .copy_from_slice(&result.distances);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_bellman_ford_v2(
+        src_ptr,
+        dst_ptr,
+        weight_ptr,
+        edge_count,
+        source,
+        out_nodes,
+        out_distances,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_bellman_ford`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_bellman_ford_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    source: i64,
+    out_nodes: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_bellman_ford(src, dst, weights, source)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_distances => result.distances);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute the shortest path between two nodes.
+///
+/// Writes the path nodes in order with the distance from the source to each
+/// one. Uses Bellman-Ford when a weight is negative and Dijkstra otherwise.
+/// Empty weights mean unit weights. Returns the number of path nodes, which
+/// is 0 when the target is unreachable.
+#[no_mangle]
+pub extern "C" fn onager_compute_shortest_path(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    source: i64,
+    target: i64,
+    directed: bool,
+    out_nodes: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result =
+            algorithms::compute_shortest_path(src, dst, weights, source, target, directed)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_distances => result.distances,
+        );
+        Ok(result.node_ids.len() as i64)
     })
 }
 
@@ -161,33 +219,43 @@ pub extern "C" fn onager_compute_floyd_warshall(
     out_dst: *mut i64,
     out_distances: *mut f64,
 ) -> i64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(-1, {
-        if src_ptr.is_null() || dst_ptr.is_null() || weight_ptr.is_null() {
-            set_last_error("Null pointer");
-            return -1;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        let weights = unsafe { std::slice::from_raw_parts(weight_ptr, edge_count) };
-        match algorithms::compute_floyd_warshall(src, dst, weights) {
-            Ok(result) => {
-                let n = result.src_nodes.len();
-                if !out_src.is_null() && !out_dst.is_null() && !out_distances.is_null() {
-                    unsafe { std::slice::from_raw_parts_mut(out_src, n) }
-                        .copy_from_slice(&result.src_nodes);
-                    unsafe { std::slice::from_raw_parts_mut(out_dst, n) }
-                        .copy_from_slice(&result.dst_nodes);
-                    unsafe { std::slice::from_raw_parts_mut(out_distances, n) }
-                        .copy_from_slice(&result.distances);
-                }
-                n as i64
-            }
-            Err(e) => {
-                set_last_error(&e.to_string());
-                -1
-            }
-        }
+    onager_compute_floyd_warshall_v2(
+        src_ptr,
+        dst_ptr,
+        weight_ptr,
+        edge_count,
+        out_src,
+        out_dst,
+        out_distances,
+        usize::MAX,
+    )
+}
+
+/// Capacity-checked variant of `onager_compute_floyd_warshall`.
+///
+/// Returns the required output size, or -1 without writing when it exceeds `out_capacity`.
+#[no_mangle]
+pub extern "C" fn onager_compute_floyd_warshall_v2(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    weight_ptr: *const f64,
+    edge_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { input_slice(weight_ptr, edge_count, "weights")? };
+        let result = algorithms::compute_floyd_warshall(src, dst, weights)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_distances => result.distances,
+        );
+        Ok(result.src_nodes.len() as i64)
     })
 }
 
@@ -200,20 +268,252 @@ pub extern "C" fn onager_compute_shortest_distance(
     source_node: i64,
     target_node: i64,
 ) -> f64 {
-    clear_last_error();
-    crate::ffi_catch_unwind!(f64::NAN, {
-        if src_ptr.is_null() || dst_ptr.is_null() {
-            set_last_error("Null pointer");
-            return f64::NAN;
-        }
-        let src = unsafe { std::slice::from_raw_parts(src_ptr, edge_count) };
-        let dst = unsafe { std::slice::from_raw_parts(dst_ptr, edge_count) };
-        match algorithms::compute_shortest_distance(src, dst, source_node, target_node) {
-            Ok(d) => d,
-            Err(e) => {
-                set_last_error(&e.to_string());
-                f64::NAN
-            }
-        }
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        algorithms::compute_shortest_distance(src, dst, source_node, target_node)
+    })
+}
+
+/// Compute the weighted shortest distance between two nodes.
+///
+/// Empty weights mean unit weights. Returns infinity when the target is
+/// unreachable and NaN on error.
+#[no_mangle]
+pub extern "C" fn onager_compute_shortest_distance_weighted(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    source_node: i64,
+    target_node: i64,
+) -> f64 {
+    crate::ffi_call!(f64::NAN, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        algorithms::compute_shortest_distance_weighted(src, dst, weights, source_node, target_node)
+    })
+}
+
+/// Compute the shortest path tree from a source node.
+///
+/// Writes one `(parent, child, distance)` row per node reachable from the
+/// source. Empty weights mean unit weights. Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_shortest_path_tree(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    source: i64,
+    out_parents: *mut i64,
+    out_children: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_shortest_path_tree(src, dst, weights, source)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_parents => result.parents,
+            out_children => result.children,
+            out_distances => result.distances,
+        );
+        Ok(result.children.len() as i64)
+    })
+}
+
+/// Compute the nodes within a path cost budget of a source node.
+///
+/// Writes one `(node, distance)` row per node whose shortest distance from
+/// the source is at most `max_cost`. Empty weights mean unit weights.
+/// Returns the number of rows.
+#[no_mangle]
+pub extern "C" fn onager_compute_nodes_within_cost(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    source: i64,
+    max_cost: f64,
+    out_nodes: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::nodes_within_cost(src, dst, weights, source, max_cost)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_distances => result.distances,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute a topological ordering of a directed graph.
+///
+/// Writes every node to `out_nodes` so that each edge points forward in
+/// the ordering. Returns the number of nodes, or -1 with an error naming a
+/// cycle when the graph is not acyclic.
+#[no_mangle]
+pub extern "C" fn onager_compute_topological_sort(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    out_nodes: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let result = algorithms::compute_topological_sort(src, dst)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Returns 1 if the directed graph has no cycles, 0 if it has one, or -1
+/// on error.
+#[no_mangle]
+pub extern "C" fn onager_is_dag(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+) -> i32 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        Ok(i32::from(algorithms::is_dag(src, dst)?))
+    })
+}
+
+/// Compute the widest path between two nodes.
+///
+/// Writes the path nodes in order with the width of each prefix. Empty
+/// weights mean unit weights. Returns the number of path nodes, which is 0
+/// when the target is unreachable.
+#[no_mangle]
+pub extern "C" fn onager_compute_widest_path(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    source: i64,
+    target: i64,
+    out_nodes: *mut i64,
+    out_widths: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_widest_path(src, dst, weights, source, target)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_widths => result.widths,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute widest path widths from a source to every reachable node.
+///
+/// Empty weights mean unit weights. Returns the number of nodes.
+#[no_mangle]
+pub extern "C" fn onager_compute_widest_paths(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    source: i64,
+    out_nodes: *mut i64,
+    out_widths: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let result = algorithms::compute_widest_paths(src, dst, weights, source)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_widths => result.widths,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute a path that only uses edges of the allowed types.
+///
+/// Writes the path nodes in order to `out_nodes` and the type of each of
+/// the edges between them to `out_types`, which gets one entry fewer.
+/// Returns the number of path nodes, which is 0 when the target is
+/// unreachable.
+#[no_mangle]
+pub extern "C" fn onager_compute_path_with_allowed_types(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    types_ptr: *const i64,
+    edge_count: usize,
+    allowed_ptr: *const i64,
+    allowed_count: usize,
+    source: i64,
+    target: i64,
+    out_nodes: *mut i64,
+    out_types: *mut i64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let types = unsafe { input_slice(types_ptr, edge_count, "types")? };
+        let allowed = unsafe { optional_slice(allowed_ptr, allowed_count) };
+        let result =
+            algorithms::compute_path_with_allowed_types(src, dst, types, allowed, source, target)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_nodes => result.node_ids,
+            out_types => result.edge_types,
+        );
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute shortest distances between every pair of nodes in a subset.
+///
+/// Empty weights mean unit weights. Returns the number of ordered pairs.
+#[no_mangle]
+pub extern "C" fn onager_compute_pairwise_distances(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    nodes_ptr: *const i64,
+    node_count: usize,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_distances: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let nodes = unsafe { optional_slice(nodes_ptr, node_count) };
+        let result = algorithms::compute_pairwise_distances(src, dst, weights, nodes)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src_nodes,
+            out_dst => result.dst_nodes,
+            out_distances => result.distances,
+        );
+        Ok(result.src_nodes.len() as i64)
     })
 }
@@ -0,0 +1,157 @@
+//! Edge-type weighted ranking FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use super::common::{edge_slices, input_slice, optional_slice};
+use crate::algorithms;
+use crate::error::Result;
+
+/// Reads the edge types and `(type, multiplier)` pairs and returns per-edge weights.
+///
+/// # Safety
+/// `types_ptr` must point to `edge_count` values, and non-null multiplier
+/// pointers to `type_count` values.
+unsafe fn typed_weights(
+    types_ptr: *const i64,
+    edge_count: usize,
+    type_ids_ptr: *const i64,
+    type_multipliers_ptr: *const f64,
+    type_count: usize,
+) -> Result<Vec<f64>> {
+    let types = unsafe { input_slice(types_ptr, edge_count, "types")? };
+    let type_ids = unsafe { optional_slice(type_ids_ptr, type_count) };
+    let type_multipliers = unsafe { optional_slice(type_multipliers_ptr, type_count) };
+    let multipliers: Vec<(i64, f64)> = type_ids
+        .iter()
+        .zip(type_multipliers.iter())
+        .map(|(&t, &m)| (t, m))
+        .collect();
+    algorithms::edge_type_weights(types, &multipliers)
+}
+
+/// Compute PageRank with per-edge-type weight multipliers.
+///
+/// Each edge is weighted by the multiplier of its type in `types`, taken from
+/// the `type_ids` and `type_multipliers` pairs. Types without a multiplier
+/// count as 1.
+#[no_mangle]
+pub extern "C" fn onager_compute_typed_pagerank(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    types_ptr: *const i64,
+    edge_count: usize,
+    type_ids_ptr: *const i64,
+    type_multipliers_ptr: *const f64,
+    type_count: usize,
+    damping: f64,
+    iterations: usize,
+    directed: bool,
+    out_nodes: *mut i64,
+    out_ranks: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe {
+            typed_weights(
+                types_ptr,
+                edge_count,
+                type_ids_ptr,
+                type_multipliers_ptr,
+                type_count,
+            )?
+        };
+        let result = algorithms::compute_weighted_pagerank(
+            src, dst, &weights, damping, iterations, directed,
+        )?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_ranks => result.ranks);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute personalized PageRank with per-edge-type weight multipliers.
+#[no_mangle]
+pub extern "C" fn onager_compute_typed_personalized_pagerank(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    types_ptr: *const i64,
+    edge_count: usize,
+    type_ids_ptr: *const i64,
+    type_multipliers_ptr: *const f64,
+    type_count: usize,
+    pers_nodes_ptr: *const i64,
+    pers_weights_ptr: *const f64,
+    pers_count: usize,
+    damping: f64,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_scores: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe {
+            typed_weights(
+                types_ptr,
+                edge_count,
+                type_ids_ptr,
+                type_multipliers_ptr,
+                type_count,
+            )?
+        };
+        let pers_nodes = unsafe { optional_slice(pers_nodes_ptr, pers_count) };
+        let pers_weights = unsafe { optional_slice(pers_weights_ptr, pers_count) };
+        let personalization: Vec<(i64, f64)> = pers_nodes
+            .iter()
+            .zip(pers_weights.iter())
+            .map(|(&n, &w)| (n, w))
+            .collect();
+
+        let result = algorithms::compute_weighted_personalized_pagerank(
+            src,
+            dst,
+            &weights,
+            &personalization,
+            damping,
+            max_iter,
+            tolerance,
+        )?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_scores => result.scores);
+        Ok(result.node_ids.len() as i64)
+    })
+}
+
+/// Compute Katz centrality with per-edge-type weight multipliers.
+#[no_mangle]
+pub extern "C" fn onager_compute_typed_katz(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    types_ptr: *const i64,
+    edge_count: usize,
+    type_ids_ptr: *const i64,
+    type_multipliers_ptr: *const f64,
+    type_count: usize,
+    alpha: f64,
+    max_iter: usize,
+    tolerance: f64,
+    out_nodes: *mut i64,
+    out_centralities: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe {
+            typed_weights(
+                types_ptr,
+                edge_count,
+                type_ids_ptr,
+                type_multipliers_ptr,
+                type_count,
+            )?
+        };
+        let result =
+            algorithms::compute_weighted_katz(src, dst, &weights, alpha, max_iter, tolerance)?;
+        crate::ffi_write_outputs!(out_capacity; out_nodes => result.node_ids, out_centralities => result.centralities);
+        Ok(result.node_ids.len() as i64)
+    })
+}
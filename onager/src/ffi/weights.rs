@@ -0,0 +1,94 @@
+//! Edge weight FFI exports.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::os::raw::c_char;
+
+use super::common::{edge_slices, input_slice, optional_slice, read_str};
+use crate::algorithms::{self, EdgeAggregation, WeightSemantics, WeightTransform};
+
+/// Transform edge weights.
+///
+/// `method` is one of `log`, `inverse`, `minmax`, or `zscore`. Writes the
+/// transformed weights to `out_weights` in input order. Returns the number of
+/// weights.
+///
+/// # Safety
+/// `method` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_transform_weights(
+    weights_ptr: *const f64,
+    weights_count: usize,
+    method: *const c_char,
+    out_weights: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let weights = unsafe { input_slice(weights_ptr, weights_count, "weights")? };
+        let method: WeightTransform = unsafe { read_str(method, "weight transform")? }.parse()?;
+        let result = algorithms::transform_weights(weights, method)?;
+        crate::ffi_write_outputs!(out_capacity; out_weights => result);
+        Ok(result.len() as i64)
+    })
+}
+
+/// Convert edge weights to distances.
+///
+/// `semantics` is `distance`, which copies the weights, or `similarity`, which
+/// inverts them. Writes the distances to `out_weights` in input order. Returns
+/// the number of weights.
+///
+/// # Safety
+/// `semantics` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_weights_to_distances(
+    weights_ptr: *const f64,
+    weights_count: usize,
+    semantics: *const c_char,
+    out_weights: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let weights = unsafe { input_slice(weights_ptr, weights_count, "weights")? };
+        let semantics: WeightSemantics =
+            unsafe { read_str(semantics, "weight semantics")? }.parse()?;
+        let result = semantics.to_distances(weights)?;
+        crate::ffi_write_outputs!(out_capacity; out_weights => result);
+        Ok(result.len() as i64)
+    })
+}
+
+/// Collapse repeated `(src, dst)` pairs into one weighted edge each.
+///
+/// `method` is one of `sum`, `count`, `mean`, or `max`. Empty weights mean
+/// every edge has weight 1. Writes one row per distinct pair in the order it
+/// first appears. Returns the number of rows.
+///
+/// # Safety
+/// `method` must point to a valid null-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn onager_aggregate_parallel_edges(
+    src_ptr: *const i64,
+    dst_ptr: *const i64,
+    edge_count: usize,
+    weights_ptr: *const f64,
+    weights_count: usize,
+    method: *const c_char,
+    out_src: *mut i64,
+    out_dst: *mut i64,
+    out_weights: *mut f64,
+    out_capacity: usize,
+) -> i64 {
+    crate::ffi_call!(-1, {
+        let (src, dst) = unsafe { edge_slices(src_ptr, dst_ptr, edge_count)? };
+        let weights = unsafe { optional_slice(weights_ptr, weights_count) };
+        let method: EdgeAggregation = unsafe { read_str(method, "edge aggregation")? }.parse()?;
+        let result = algorithms::aggregate_parallel_edges(src, dst, weights, method)?;
+        crate::ffi_write_outputs!(
+            out_capacity;
+            out_src => result.src,
+            out_dst => result.dst,
+            out_weights => result.weights,
+        );
+        Ok(result.src.len() as i64)
+    })
+}
@@ -3,20 +3,26 @@
 //! This module provides a thread-safe graph registry that stores named graphs
 //! and wraps the graphina library for graph operations.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use graphina::core::types::{Digraph, Graph, NodeId};
 use once_cell::sync::Lazy;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
 
+use crate::algorithms::{build_contraction_hierarchy, ContractionHierarchy};
 use crate::error::{OnagerError, Result};
+use crate::jobs::JobAlgorithm;
 
 /// Wrapper for an undirected graph with external ID mapping.
 pub struct UndirectedGraphWrapper {
     graph: Graph<i64, f64>,
     /// Maps external node IDs (provided by user) to internal graphina NodeIds
     node_mapping: HashMap<i64, NodeId>,
+    metrics: GraphMetrics,
+    cache: ResultCache,
 }
 
 /// Wrapper for a directed graph with external ID mapping.
@@ -24,6 +30,180 @@ pub struct DirectedGraphWrapper {
     graph: Digraph<i64, f64>,
     /// Maps external node IDs (provided by user) to internal graphina NodeIds
     node_mapping: HashMap<i64, NodeId>,
+    metrics: GraphMetrics,
+    cache: ResultCache,
+}
+
+/// Centrality scores of a registry graph, one per node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CentralityScores {
+    pub node_ids: Vec<i64>,
+    pub scores: Vec<f64>,
+}
+
+/// Expensive results computed on a graph, kept until the graph changes.
+///
+/// Entries are keyed by the algorithm with its parameters. Every mutation
+/// bumps the version and clears the entries, and a result computed from an
+/// earlier version is not stored, so a stale result is never returned.
+#[derive(Debug)]
+struct ResultCache {
+    version: u64,
+    centrality: Mutex<HashMap<JobAlgorithm, Arc<CentralityScores>>>,
+}
+
+/// Source of graph versions, shared by all graphs so that a graph dropped and
+/// created again under the same name never repeats an earlier version.
+static NEXT_VERSION: AtomicU64 = AtomicU64::new(1);
+
+impl ResultCache {
+    fn new() -> Self {
+        ResultCache {
+            version: NEXT_VERSION.fetch_add(1, Ordering::Relaxed),
+            centrality: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn invalidate(&mut self) {
+        self.version = NEXT_VERSION.fetch_add(1, Ordering::Relaxed);
+        self.centrality.get_mut().clear();
+    }
+}
+
+/// Structural metrics kept up to date as nodes and edges are added.
+///
+/// Triangles and clustering are defined on the simple undirected view of the
+/// graph, where edge direction, parallel edges, and self-loops are ignored.
+/// Each edge insertion costs O(min degree) and every query is O(1).
+#[derive(Debug, Clone, Default)]
+struct GraphMetrics {
+    /// Distinct neighbors of each node in the simple undirected view
+    neighbors: HashMap<i64, HashSet<i64>>,
+    /// Edges of the simple undirected view
+    edge_count: usize,
+    /// Triangles through each node
+    triangles: HashMap<i64, usize>,
+    triangle_count: usize,
+    /// Paths of length two, summed over centers as `d(d - 1) / 2`
+    triple_count: usize,
+    /// Sum of local clustering coefficients over all nodes
+    clustering_sum: f64,
+    /// Union-find parent of each node, ignoring edge direction
+    component_parent: HashMap<i64, i64>,
+    /// Number of nodes under each union-find root
+    component_size: HashMap<i64, usize>,
+    component_count: usize,
+}
+
+impl GraphMetrics {
+    fn add_node(&mut self, node: i64) {
+        self.neighbors.entry(node).or_default();
+        self.component_parent.insert(node, node);
+        self.component_size.insert(node, 1);
+        self.component_count += 1;
+    }
+
+    /// Returns the union-find root of `node` without compressing paths.
+    ///
+    /// Union by size keeps trees O(log n) deep, so read-only queries stay fast.
+    fn component_root(&self, mut node: i64) -> Option<i64> {
+        loop {
+            let parent = *self.component_parent.get(&node)?;
+            if parent == node {
+                return Some(node);
+            }
+            node = parent;
+        }
+    }
+
+    fn union_components(&mut self, src: i64, dst: i64) {
+        let (Some(a), Some(b)) = (self.component_root(src), self.component_root(dst)) else {
+            return;
+        };
+        if a == b {
+            return;
+        }
+        let size_a = self.component_size.get(&a).copied().unwrap_or(1);
+        let size_b = self.component_size.get(&b).copied().unwrap_or(1);
+        let (root, child) = if size_a >= size_b { (a, b) } else { (b, a) };
+        self.component_parent.insert(child, root);
+        self.component_size.insert(root, size_a + size_b);
+        self.component_size.remove(&child);
+        self.component_count -= 1;
+        // Point both endpoints straight at the root to keep later finds short
+        for node in [src, dst] {
+            self.component_parent.insert(node, root);
+        }
+    }
+
+    fn local_clustering(&self, node: i64) -> f64 {
+        let d = self.neighbors.get(&node).map_or(0, HashSet::len);
+        if d < 2 {
+            return 0.0;
+        }
+        let t = self.triangles.get(&node).copied().unwrap_or(0);
+        2.0 * t as f64 / (d * (d - 1)) as f64
+    }
+
+    fn add_edge(&mut self, src: i64, dst: i64) {
+        self.union_components(src, dst);
+        let already_linked = self.neighbors.get(&src).is_some_and(|n| n.contains(&dst));
+        if src == dst || already_linked {
+            return;
+        }
+        let (small, large) = match (self.neighbors.get(&src), self.neighbors.get(&dst)) {
+            (Some(a), Some(b)) if a.len() <= b.len() => (a, b),
+            (Some(a), Some(b)) => (b, a),
+            _ => return,
+        };
+        let common: Vec<i64> = small
+            .iter()
+            .filter(|n| large.contains(n))
+            .copied()
+            .collect();
+
+        // Only the endpoints and their common neighbors change their coefficient
+        let affected: Vec<i64> = [src, dst]
+            .into_iter()
+            .chain(common.iter().copied())
+            .collect();
+        for &node in &affected {
+            self.clustering_sum -= self.local_clustering(node);
+        }
+        for node in [src, dst] {
+            let d = self.neighbors.get(&node).map_or(0, HashSet::len);
+            // A new neighbor adds d new paths of length two centered here
+            self.triple_count += d;
+        }
+        self.neighbors.entry(src).or_default().insert(dst);
+        self.neighbors.entry(dst).or_default().insert(src);
+        self.edge_count += 1;
+        for &w in &common {
+            *self.triangles.entry(w).or_default() += 1;
+        }
+        *self.triangles.entry(src).or_default() += common.len();
+        *self.triangles.entry(dst).or_default() += common.len();
+        self.triangle_count += common.len();
+        for &node in &affected {
+            self.clustering_sum += self.local_clustering(node);
+        }
+    }
+}
+
+/// Summary metrics of a registry graph.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphInfo {
+    pub directed: bool,
+    pub node_count: usize,
+    pub edge_count: usize,
+    /// Distinct edges between distinct nodes, ignoring direction, divided by
+    /// the number of node pairs
+    pub density: f64,
+    pub triangle_count: usize,
+    /// Global clustering coefficient, three times triangles over connected triples
+    pub transitivity: f64,
+    /// Mean local clustering coefficient over all nodes
+    pub average_clustering: f64,
 }
 
 /// A graph that can be either directed or undirected.
@@ -39,11 +219,15 @@ impl GraphType {
             GraphType::Directed(DirectedGraphWrapper {
                 graph: Digraph::new(),
                 node_mapping: HashMap::new(),
+                metrics: GraphMetrics::default(),
+                cache: ResultCache::new(),
             })
         } else {
             GraphType::Undirected(UndirectedGraphWrapper {
                 graph: Graph::new(),
                 node_mapping: HashMap::new(),
+                metrics: GraphMetrics::default(),
+                cache: ResultCache::new(),
             })
         }
     }
@@ -82,6 +266,8 @@ impl GraphType {
                 }
                 let internal_id = w.graph.add_node(node_id);
                 w.node_mapping.insert(node_id, internal_id);
+                w.metrics.add_node(node_id);
+                w.cache.invalidate();
                 Ok(())
             }
             GraphType::Undirected(w) => {
@@ -93,6 +279,8 @@ impl GraphType {
                 }
                 let internal_id = w.graph.add_node(node_id);
                 w.node_mapping.insert(node_id, internal_id);
+                w.metrics.add_node(node_id);
+                w.cache.invalidate();
                 Ok(())
             }
         }
@@ -111,6 +299,8 @@ impl GraphType {
                     .get(&dst)
                     .ok_or(OnagerError::NodeNotFound(dst))?;
                 w.graph.add_edge(*src_id, *dst_id, weight);
+                w.metrics.add_edge(src, dst);
+                w.cache.invalidate();
                 Ok(())
             }
             GraphType::Undirected(w) => {
@@ -123,16 +313,153 @@ impl GraphType {
                     .get(&dst)
                     .ok_or(OnagerError::NodeNotFound(dst))?;
                 w.graph.add_edge(*src_id, *dst_id, weight);
+                w.metrics.add_edge(src, dst);
+                w.cache.invalidate();
                 Ok(())
             }
         }
     }
 }
 
+impl GraphType {
+    fn metrics(&self) -> &GraphMetrics {
+        match self {
+            GraphType::Directed(w) => &w.metrics,
+            GraphType::Undirected(w) => &w.metrics,
+        }
+    }
+
+    fn cache(&self) -> &ResultCache {
+        match self {
+            GraphType::Directed(w) => &w.cache,
+            GraphType::Undirected(w) => &w.cache,
+        }
+    }
+
+    /// Returns the version of the graph, which increases on every change.
+    pub fn version(&self) -> u64 {
+        self.cache().version
+    }
+
+    /// Returns the centrality scores of `job` cached for the current version.
+    pub(crate) fn cached_centrality(&self, job: &JobAlgorithm) -> Option<Arc<CentralityScores>> {
+        self.cache().centrality.lock().get(job).cloned()
+    }
+
+    /// Caches the centrality scores of `job` computed at graph version
+    /// `version`, unless the graph has changed since.
+    pub(crate) fn cache_centrality(
+        &self,
+        job: JobAlgorithm,
+        version: u64,
+        scores: Arc<CentralityScores>,
+    ) {
+        let cache = self.cache();
+        if cache.version == version {
+            cache.centrality.lock().insert(job, scores);
+        }
+    }
+
+    /// Returns summary metrics from the incrementally maintained counts.
+    pub fn info(&self) -> GraphInfo {
+        let metrics = self.metrics();
+        let n = self.node_count();
+        // Density uses the simple undirected view, like the snapshot stats
+        let possible = n * n.saturating_sub(1) / 2;
+        GraphInfo {
+            directed: self.is_directed(),
+            node_count: n,
+            edge_count: self.edge_count(),
+            density: if possible > 0 {
+                metrics.edge_count as f64 / possible as f64
+            } else {
+                0.0
+            },
+            triangle_count: metrics.triangle_count,
+            transitivity: if metrics.triple_count > 0 {
+                3.0 * metrics.triangle_count as f64 / metrics.triple_count as f64
+            } else {
+                0.0
+            },
+            average_clustering: if n > 0 {
+                (metrics.clustering_sum / n as f64).max(0.0)
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Returns true if the two nodes are joined by a path, ignoring edge direction.
+    pub fn same_component(&self, a: i64, b: i64) -> Result<bool> {
+        let metrics = self.metrics();
+        let root_a = metrics
+            .component_root(a)
+            .ok_or(OnagerError::NodeNotFound(a))?;
+        let root_b = metrics
+            .component_root(b)
+            .ok_or(OnagerError::NodeNotFound(b))?;
+        Ok(root_a == root_b)
+    }
+
+    /// Returns the number of connected components, ignoring edge direction.
+    pub fn component_count(&self) -> usize {
+        self.metrics().component_count
+    }
+
+    /// Returns all external node IDs in ascending order.
+    pub fn node_ids(&self) -> Vec<i64> {
+        let mut ids: Vec<i64> = match self {
+            GraphType::Directed(w) => w.node_mapping.keys().copied().collect(),
+            GraphType::Undirected(w) => w.node_mapping.keys().copied().collect(),
+        };
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Returns the edges as `(src, dst, weights)` arrays of external node IDs.
+    pub fn edge_list(&self) -> (Vec<i64>, Vec<i64>, Vec<f64>) {
+        let mut src = Vec::new();
+        let mut dst = Vec::new();
+        let mut weights = Vec::new();
+        match self {
+            GraphType::Directed(w) => {
+                let reverse: HashMap<NodeId, i64> =
+                    w.node_mapping.iter().map(|(&ext, &id)| (id, ext)).collect();
+                for (u, v, &weight) in w.graph.edges() {
+                    if let (Some(&u), Some(&v)) = (reverse.get(&u), reverse.get(&v)) {
+                        src.push(u);
+                        dst.push(v);
+                        weights.push(weight);
+                    }
+                }
+            }
+            GraphType::Undirected(w) => {
+                let reverse: HashMap<NodeId, i64> =
+                    w.node_mapping.iter().map(|(&ext, &id)| (id, ext)).collect();
+                for (u, v, &weight) in w.graph.edges() {
+                    if let (Some(&u), Some(&v)) = (reverse.get(&u), reverse.get(&v)) {
+                        src.push(u);
+                        dst.push(v);
+                        weights.push(weight);
+                    }
+                }
+            }
+        }
+        (src, dst, weights)
+    }
+}
+
 /// Global registry of named graphs.
 static GRAPH_REGISTRY: Lazy<Arc<RwLock<HashMap<String, GraphType>>>> =
     Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
 
+/// Contraction hierarchy indexes of registry graphs, keyed by graph name.
+///
+/// An index is dropped whenever its graph changes, so a stale index is never
+/// queried. Lock order is `GRAPH_REGISTRY` before `CH_INDEXES`.
+static CH_INDEXES: Lazy<RwLock<HashMap<String, ContractionHierarchy>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 /// Creates a new graph with the given name.
 pub fn create_graph(name: &str, directed: bool) -> Result<()> {
     let mut registry = GRAPH_REGISTRY.write();
@@ -149,6 +476,7 @@ pub fn drop_graph(name: &str) -> Result<()> {
     if registry.remove(name).is_none() {
         return Err(OnagerError::GraphNotFound(name.to_string()));
     }
+    CH_INDEXES.write().remove(name);
     Ok(())
 }
 
@@ -165,7 +493,9 @@ pub fn add_node(graph_name: &str, node_id: i64) -> Result<()> {
     let graph = registry
         .get_mut(graph_name)
         .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
-    graph.add_node(node_id)
+    graph.add_node(node_id)?;
+    CH_INDEXES.write().remove(graph_name);
+    Ok(())
 }
 
 /// Adds an edge to the specified graph.
@@ -174,7 +504,9 @@ pub fn add_edge(graph_name: &str, src: i64, dst: i64, weight: f64) -> Result<()>
     let graph = registry
         .get_mut(graph_name)
         .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
-    graph.add_edge(src, dst, weight)
+    graph.add_edge(src, dst, weight)?;
+    CH_INDEXES.write().remove(graph_name);
+    Ok(())
 }
 
 /// Returns the number of nodes in the graph.
@@ -253,6 +585,151 @@ pub fn get_node_out_degree(graph_name: &str, external_node_id: i64) -> Result<us
     }
 }
 
+/// Runs `f` on the named graph while holding the registry read lock.
+pub fn with_graph<T>(graph_name: &str, f: impl FnOnce(&GraphType) -> T) -> Result<T> {
+    let registry = GRAPH_REGISTRY.read();
+    let graph = registry
+        .get(graph_name)
+        .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
+    Ok(f(graph))
+}
+
+/// Returns summary metrics of the named graph in O(1).
+pub fn graph_info(graph_name: &str) -> Result<GraphInfo> {
+    let registry = GRAPH_REGISTRY.read();
+    let graph = registry
+        .get(graph_name)
+        .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
+    Ok(graph.info())
+}
+
+/// Returns true if two nodes of the named graph are in the same connected
+/// component, ignoring edge direction.
+///
+/// Components are kept up to date with a union-find structure as edges are
+/// added, so the query does not traverse the graph.
+pub fn same_component(graph_name: &str, a: i64, b: i64) -> Result<bool> {
+    let registry = GRAPH_REGISTRY.read();
+    let graph = registry
+        .get(graph_name)
+        .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
+    graph.same_component(a, b)
+}
+
+/// Returns the number of connected components of the named graph, ignoring
+/// edge direction.
+pub fn component_count(graph_name: &str) -> Result<usize> {
+    let registry = GRAPH_REGISTRY.read();
+    let graph = registry
+        .get(graph_name)
+        .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
+    Ok(graph.component_count())
+}
+
+/// Returns the version of the named graph.
+///
+/// The version increases whenever a node or edge is added, so a caller can
+/// tell whether the graph changed by comparing it with a version read
+/// earlier. Versions are never reused, even by a graph created again under
+/// the same name.
+pub fn graph_version(graph_name: &str) -> Result<u64> {
+    let registry = GRAPH_REGISTRY.read();
+    let graph = registry
+        .get(graph_name)
+        .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
+    Ok(graph.version())
+}
+
+/// Computes centrality scores of the named graph, reusing the cached result
+/// of an earlier call with the same algorithm and parameters.
+///
+/// `algorithm` and the JSON object `params` take the same values as a
+/// background job. Every node gets a score, and nodes without edges score 0.
+/// Cached scores are dropped when the graph is modified.
+pub fn centrality(
+    graph_name: &str,
+    algorithm: &str,
+    params: &str,
+) -> Result<Arc<CentralityScores>> {
+    let params: serde_json::Value = serde_json::from_str(params)?;
+    let params = params.as_object().ok_or_else(|| {
+        OnagerError::InvalidArgument("Centrality parameters must be a JSON object".to_string())
+    })?;
+    let job = JobAlgorithm::parse(algorithm, params)?;
+    // Snapshot the graph under the read lock and compute without it, so
+    // writers are not blocked for the length of the computation
+    let (version, directed, node_ids, (src, dst, weights)) = {
+        let registry = GRAPH_REGISTRY.read();
+        let graph = registry
+            .get(graph_name)
+            .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
+        if let Some(scores) = graph.cached_centrality(&job) {
+            return Ok(scores);
+        }
+        (
+            graph.version(),
+            graph.is_directed(),
+            graph.node_ids(),
+            graph.edge_list(),
+        )
+    };
+    let scores = Arc::new(job.centrality(&node_ids, &src, &dst, &weights, directed)?);
+    // The graph may have changed or been dropped in the meantime, in which
+    // case the scores still describe the snapshot but are not cached
+    if let Some(graph) = GRAPH_REGISTRY.read().get(graph_name) {
+        graph.cache_centrality(job, version, Arc::clone(&scores));
+    }
+    Ok(scores)
+}
+
+/// Builds a contraction hierarchy index for the named graph.
+///
+/// Returns the number of shortcut edges added. The index is kept until the
+/// graph is modified or dropped, and building again replaces it. Fails if the
+/// graph is modified while the index is being built.
+pub fn build_ch_index(graph_name: &str) -> Result<usize> {
+    // Snapshot the graph under the read lock and build without it, so
+    // writers are not blocked for the length of the preprocessing
+    let (version, directed, node_ids, (src, dst, weights)) = with_graph(graph_name, |g| {
+        (g.version(), g.is_directed(), g.node_ids(), g.edge_list())
+    })?;
+    let ch = build_contraction_hierarchy(&node_ids, &src, &dst, &weights, directed)?;
+    let shortcuts = ch.shortcut_count;
+
+    // Mutations drop the index under the registry write lock, so holding the
+    // read lock while inserting keeps a stale index out
+    let registry = GRAPH_REGISTRY.read();
+    let graph = registry
+        .get(graph_name)
+        .ok_or_else(|| OnagerError::GraphNotFound(graph_name.to_string()))?;
+    if graph.version() != version {
+        return Err(OnagerError::GraphError(format!(
+            "Graph '{}' was modified while its contraction hierarchy index was being built",
+            graph_name
+        )));
+    }
+    CH_INDEXES.write().insert(graph_name.to_string(), ch);
+    Ok(shortcuts)
+}
+
+/// Returns the shortest distance between two nodes using the graph's
+/// contraction hierarchy index.
+///
+/// Returns f64::INFINITY if the target is unreachable.
+pub fn ch_distance(graph_name: &str, source: i64, target: i64) -> Result<f64> {
+    if let Some(ch) = CH_INDEXES.read().get(graph_name) {
+        return ch.distance(source, target);
+    }
+    if GRAPH_REGISTRY.read().contains_key(graph_name) {
+        Err(OnagerError::InvalidArgument(format!(
+            "Graph '{}' has no contraction hierarchy index, call onager_build_ch_index first",
+            graph_name
+        )))
+    } else {
+        Err(OnagerError::GraphNotFound(graph_name.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -284,4 +761,188 @@ mod tests {
 
         drop_graph(name).unwrap();
     }
+
+    #[test]
+    fn test_graph_info_tracks_triangles() {
+        let name = "test_graph_info";
+        create_graph(name, false).unwrap();
+        for node in 1..=4 {
+            add_node(name, node).unwrap();
+        }
+        add_edge(name, 1, 2, 1.0).unwrap();
+        add_edge(name, 2, 3, 1.0).unwrap();
+        let info = graph_info(name).unwrap();
+        assert_eq!(info.triangle_count, 0);
+        assert_eq!(info.transitivity, 0.0);
+
+        // Closing the triangle, a parallel edge, and a self-loop
+        add_edge(name, 3, 1, 1.0).unwrap();
+        add_edge(name, 1, 3, 1.0).unwrap();
+        add_edge(name, 4, 4, 1.0).unwrap();
+        add_edge(name, 3, 4, 1.0).unwrap();
+        let info = graph_info(name).unwrap();
+        assert_eq!(info.edge_count, 6);
+        assert_eq!(info.triangle_count, 1);
+        // Four distinct non-loop edges out of six node pairs
+        assert!((info.density - 4.0 / 6.0).abs() < 1e-12);
+        // Triples: node 3 has degree 3, nodes 1 and 2 degree 2
+        assert!((info.transitivity - 3.0 / 5.0).abs() < 1e-12);
+        // Local clustering: 1, 1, 1/3, and 0
+        assert!((info.average_clustering - (7.0 / 3.0) / 4.0).abs() < 1e-12);
+
+        drop_graph(name).unwrap();
+        assert!(graph_info(name).is_err());
+    }
+
+    #[test]
+    fn test_incremental_components() {
+        let name = "test_graph_components";
+        create_graph(name, true).unwrap();
+        for node in 1..=5 {
+            add_node(name, node).unwrap();
+        }
+        assert_eq!(component_count(name).unwrap(), 5);
+
+        add_edge(name, 1, 2, 1.0).unwrap();
+        add_edge(name, 3, 2, 1.0).unwrap();
+        add_edge(name, 4, 4, 1.0).unwrap();
+        assert_eq!(component_count(name).unwrap(), 3);
+        // Direction is ignored, so 1 and 3 meet through 2
+        assert!(same_component(name, 1, 3).unwrap());
+        assert!(!same_component(name, 1, 4).unwrap());
+
+        add_edge(name, 5, 4, 1.0).unwrap();
+        add_edge(name, 4, 3, 1.0).unwrap();
+        assert_eq!(component_count(name).unwrap(), 1);
+        assert!(same_component(name, 5, 1).unwrap());
+        assert!(same_component(name, 1, 99).is_err());
+
+        drop_graph(name).unwrap();
+        assert!(component_count(name).is_err());
+    }
+
+    #[test]
+    fn test_ch_index_lifecycle() {
+        let name = "test_graph_ch";
+        create_graph(name, true).unwrap();
+        for node in 1..=4 {
+            add_node(name, node).unwrap();
+        }
+        add_edge(name, 1, 2, 1.0).unwrap();
+        add_edge(name, 2, 3, 2.0).unwrap();
+        add_edge(name, 1, 3, 5.0).unwrap();
+
+        assert!(ch_distance(name, 1, 3).is_err()); // No index yet
+        build_ch_index(name).unwrap();
+        assert_eq!(ch_distance(name, 1, 3).unwrap(), 3.0);
+        assert_eq!(ch_distance(name, 3, 1).unwrap(), f64::INFINITY);
+        assert_eq!(ch_distance(name, 1, 4).unwrap(), f64::INFINITY);
+
+        add_edge(name, 3, 4, 1.0).unwrap();
+        assert!(ch_distance(name, 1, 4).is_err()); // Index dropped on change
+        build_ch_index(name).unwrap();
+        assert_eq!(ch_distance(name, 1, 4).unwrap(), 4.0);
+
+        drop_graph(name).unwrap();
+        assert!(ch_distance(name, 1, 4).is_err());
+    }
+
+    #[test]
+    fn test_centrality_cache_invalidated_on_mutation() {
+        let name = "test_graph_centrality_cache";
+        create_graph(name, false).unwrap();
+        for node in 1..=3 {
+            add_node(name, node).unwrap();
+        }
+        add_edge(name, 1, 2, 1.0).unwrap();
+        add_edge(name, 2, 3, 1.0).unwrap();
+
+        let first = centrality(name, "betweenness", "{}").unwrap();
+        let again = centrality(name, "betweenness", "{\"normalized\": true}").unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+        let other = centrality(name, "betweenness", "{\"normalized\": false}").unwrap();
+        assert!(!Arc::ptr_eq(&first, &other));
+
+        add_node(name, 4).unwrap();
+        add_edge(name, 3, 4, 1.0).unwrap();
+        let updated = centrality(name, "betweenness", "{}").unwrap();
+        assert!(!Arc::ptr_eq(&first, &updated));
+        assert_eq!(updated.node_ids.len(), 4);
+
+        assert!(centrality(name, "louvain", "{}").is_err());
+        assert!(centrality(name, "betweenness", "[]").is_err());
+        drop_graph(name).unwrap();
+        assert!(centrality(name, "betweenness", "{}").is_err());
+    }
+
+    #[test]
+    fn test_centrality_scores_isolated_nodes() {
+        let name = "test_graph_centrality_isolated";
+        create_graph(name, true).unwrap();
+        add_node(name, 1).unwrap();
+        add_node(name, 2).unwrap();
+
+        // Without edges every node scores 0
+        let empty = centrality(name, "closeness", "{}").unwrap();
+        assert_eq!(empty.node_ids, vec![1, 2]);
+        assert_eq!(empty.scores, vec![0.0, 0.0]);
+
+        add_node(name, 3).unwrap();
+        add_edge(name, 1, 2, 1.0).unwrap();
+        let scores = centrality(name, "closeness", "{}").unwrap();
+        assert_eq!(scores.node_ids, vec![1, 2, 3]);
+        assert_eq!(scores.scores, vec![1.0, 0.0, 0.0]);
+        assert!(centrality(name, "louvain", "{}").is_err());
+        drop_graph(name).unwrap();
+    }
+
+    #[test]
+    fn test_centrality_from_stale_version_is_not_cached() {
+        let name = "test_graph_centrality_stale";
+        create_graph(name, false).unwrap();
+        add_node(name, 1).unwrap();
+        add_node(name, 2).unwrap();
+        add_edge(name, 1, 2, 1.0).unwrap();
+        let stale = graph_version(name).unwrap();
+        let scores = Arc::new(CentralityScores {
+            node_ids: vec![1, 2],
+            scores: vec![0.0, 0.0],
+        });
+
+        // The graph changes while the scores are being computed
+        add_edge(name, 2, 1, 1.0).unwrap();
+        let job = JobAlgorithm::parse("betweenness", &serde_json::Map::new()).unwrap();
+        with_graph(name, |g| {
+            g.cache_centrality(job.clone(), stale, Arc::clone(&scores));
+            assert!(g.cached_centrality(&job).is_none());
+        })
+        .unwrap();
+        let fresh = centrality(name, "betweenness", "{}").unwrap();
+        assert!(!Arc::ptr_eq(&fresh, &scores));
+        drop_graph(name).unwrap();
+    }
+
+    #[test]
+    fn test_graph_version_increases_on_mutation() {
+        let name = "test_graph_version";
+        create_graph(name, true).unwrap();
+        let created = graph_version(name).unwrap();
+        add_node(name, 1).unwrap();
+        add_node(name, 2).unwrap();
+        let nodes_added = graph_version(name).unwrap();
+        assert!(nodes_added > created);
+        assert_eq!(graph_version(name).unwrap(), nodes_added);
+        add_edge(name, 1, 2, 1.0).unwrap();
+        assert!(graph_version(name).unwrap() > nodes_added);
+        // Failed mutations leave the version alone
+        let before = graph_version(name).unwrap();
+        assert!(add_edge(name, 1, 3, 1.0).is_err());
+        assert_eq!(graph_version(name).unwrap(), before);
+
+        drop_graph(name).unwrap();
+        assert!(graph_version(name).is_err());
+        create_graph(name, true).unwrap();
+        assert!(graph_version(name).unwrap() > before);
+        drop_graph(name).unwrap();
+    }
 }
@@ -0,0 +1,435 @@
+//! Background job module.
+//!
+//! Runs long algorithms on registry graphs in a background thread, so a
+//! caller can submit work, return immediately, and poll for the result.
+
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use once_cell::sync::Lazy;
+use ordered_float::OrderedFloat;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::algorithms::{self, ParallelEdges, ToJson};
+use crate::error::{OnagerError, Result};
+use crate::graph::{self, CentralityScores};
+
+/// Lifecycle state of a background job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+impl JobStatus {
+    /// Returns the lowercase name of the status.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+struct Job {
+    status: JobStatus,
+    /// JSON result when completed, or the error message when failed
+    output: Option<String>,
+}
+
+/// Jobs keyed by handle. Finished jobs are kept until dropped.
+static JOBS: Lazy<Mutex<HashMap<u64, Job>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Algorithm and parameters of a job, validated before the job starts.
+///
+/// Jobs compare equal when the algorithm and every parameter after defaults
+/// are applied match, so a job also keys cached centrality results of
+/// registry graphs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum JobAlgorithm {
+    PageRank {
+        damping: OrderedFloat<f64>,
+        iterations: usize,
+    },
+    Betweenness {
+        normalized: bool,
+        parallel: ParallelEdges,
+        cutoff: Option<OrderedFloat<f64>>,
+    },
+    Closeness {
+        cutoff: Option<OrderedFloat<f64>>,
+    },
+    Harmonic {
+        cutoff: Option<OrderedFloat<f64>>,
+    },
+    Louvain {
+        seed: Option<u64>,
+    },
+    ConnectedComponents,
+}
+
+fn param_f64(params: &Map<String, Value>, key: &str) -> Result<Option<OrderedFloat<f64>>> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v.as_f64().map(|v| Some(OrderedFloat(v))).ok_or_else(|| {
+            OnagerError::InvalidArgument(format!("Job parameter '{}' must be a number", key))
+        }),
+    }
+}
+
+fn param_u64(params: &Map<String, Value>, key: &str) -> Result<Option<u64>> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v.as_u64().map(Some).ok_or_else(|| {
+            OnagerError::InvalidArgument(format!(
+                "Job parameter '{}' must be a non-negative integer",
+                key
+            ))
+        }),
+    }
+}
+
+fn param_bool(params: &Map<String, Value>, key: &str) -> Result<Option<bool>> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v.as_bool().map(Some).ok_or_else(|| {
+            OnagerError::InvalidArgument(format!("Job parameter '{}' must be a boolean", key))
+        }),
+    }
+}
+
+fn param_str<'a>(params: &'a Map<String, Value>, key: &str) -> Result<Option<&'a str>> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v.as_str().map(Some).ok_or_else(|| {
+            OnagerError::InvalidArgument(format!("Job parameter '{}' must be a string", key))
+        }),
+    }
+}
+
+impl JobAlgorithm {
+    pub(crate) fn parse(algorithm: &str, params: &Map<String, Value>) -> Result<Self> {
+        match algorithm.to_ascii_lowercase().as_str() {
+            "pagerank" => Ok(JobAlgorithm::PageRank {
+                damping: param_f64(params, "damping")?.unwrap_or(OrderedFloat(0.85)),
+                iterations: param_u64(params, "iterations")?.unwrap_or(100) as usize,
+            }),
+            "betweenness" => Ok(JobAlgorithm::Betweenness {
+                normalized: param_bool(params, "normalized")?.unwrap_or(true),
+                parallel: param_str(params, "parallel_edges")?
+                    .map(str::parse::<ParallelEdges>)
+                    .transpose()?
+                    .unwrap_or_default(),
+                cutoff: param_f64(params, "cutoff")?,
+            }),
+            "closeness" => Ok(JobAlgorithm::Closeness {
+                cutoff: param_f64(params, "cutoff")?,
+            }),
+            "harmonic" => Ok(JobAlgorithm::Harmonic {
+                cutoff: param_f64(params, "cutoff")?,
+            }),
+            "louvain" => Ok(JobAlgorithm::Louvain {
+                seed: param_u64(params, "seed")?,
+            }),
+            "connected_components" => Ok(JobAlgorithm::ConnectedComponents),
+            _ => Err(OnagerError::InvalidArgument(format!(
+                "Unknown job algorithm '{}', expected 'pagerank', 'betweenness', 'closeness', \
+                 'harmonic', 'louvain', or 'connected_components'",
+                algorithm
+            ))),
+        }
+    }
+
+    fn run(&self, src: &[i64], dst: &[i64], weights: &[f64], directed: bool) -> Result<String> {
+        match *self {
+            JobAlgorithm::PageRank {
+                damping,
+                iterations,
+            } => algorithms::compute_pagerank(src, dst, weights, damping.0, iterations, directed)?
+                .to_json(),
+            JobAlgorithm::Betweenness {
+                normalized,
+                parallel,
+                cutoff,
+            } => algorithms::compute_betweenness_general(
+                src,
+                dst,
+                weights,
+                directed,
+                normalized,
+                parallel,
+                cutoff.map(|c| c.0),
+            )?
+            .to_json(),
+            JobAlgorithm::Closeness { cutoff } => algorithms::compute_closeness_general(
+                src,
+                dst,
+                weights,
+                directed,
+                &[],
+                cutoff.map(|c| c.0),
+            )?
+            .to_json(),
+            JobAlgorithm::Harmonic { cutoff } => algorithms::compute_harmonic_general(
+                src,
+                dst,
+                weights,
+                directed,
+                &[],
+                cutoff.map(|c| c.0),
+            )?
+            .to_json(),
+            JobAlgorithm::Louvain { seed } => {
+                algorithms::compute_louvain(src, dst, seed)?.to_json()
+            }
+            JobAlgorithm::ConnectedComponents => {
+                algorithms::compute_connected_components(src, dst)?.to_json()
+            }
+        }
+    }
+
+    /// Runs a centrality algorithm and returns one score for each of
+    /// `node_ids`, the nodes of the graph in ascending order.
+    ///
+    /// Nodes without edges score 0, so a graph without edges scores 0
+    /// everywhere. Fails for algorithms that do not score nodes.
+    pub(crate) fn centrality(
+        &self,
+        node_ids: &[i64],
+        src: &[i64],
+        dst: &[i64],
+        weights: &[f64],
+        directed: bool,
+    ) -> Result<CentralityScores> {
+        if matches!(
+            self,
+            JobAlgorithm::Louvain { .. } | JobAlgorithm::ConnectedComponents
+        ) {
+            return Err(OnagerError::InvalidArgument(
+                "Centrality algorithm must be 'pagerank', 'betweenness', 'closeness', or \
+                 'harmonic'"
+                    .to_string(),
+            ));
+        }
+        let mut scores = vec![0.0; node_ids.len()];
+        if src.is_empty() {
+            return Ok(CentralityScores {
+                node_ids: node_ids.to_vec(),
+                scores,
+            });
+        }
+        let (scored_ids, scored) = match *self {
+            JobAlgorithm::PageRank {
+                damping,
+                iterations,
+            } => {
+                let result = algorithms::compute_pagerank(
+                    src, dst, weights, damping.0, iterations, directed,
+                )?;
+                (result.node_ids, result.ranks)
+            }
+            JobAlgorithm::Betweenness {
+                normalized,
+                parallel,
+                cutoff,
+            } => {
+                let result = algorithms::compute_betweenness_general(
+                    src,
+                    dst,
+                    weights,
+                    directed,
+                    normalized,
+                    parallel,
+                    cutoff.map(|c| c.0),
+                )?;
+                (result.node_ids, result.centralities)
+            }
+            JobAlgorithm::Closeness { cutoff } => {
+                let result = algorithms::compute_closeness_general(
+                    src,
+                    dst,
+                    weights,
+                    directed,
+                    &[],
+                    cutoff.map(|c| c.0),
+                )?;
+                (result.node_ids, result.centralities)
+            }
+            JobAlgorithm::Harmonic { cutoff } => {
+                let result = algorithms::compute_harmonic_general(
+                    src,
+                    dst,
+                    weights,
+                    directed,
+                    &[],
+                    cutoff.map(|c| c.0),
+                )?;
+                (result.node_ids, result.centralities)
+            }
+            JobAlgorithm::Louvain { .. } | JobAlgorithm::ConnectedComponents => {
+                unreachable!("rejected above")
+            }
+        };
+        for (id, score) in scored_ids.into_iter().zip(scored) {
+            if let Ok(i) = node_ids.binary_search(&id) {
+                scores[i] = score;
+            }
+        }
+        Ok(CentralityScores {
+            node_ids: node_ids.to_vec(),
+            scores,
+        })
+    }
+}
+
+/// Submits an algorithm to run on a registry graph in a background thread.
+///
+/// `params` is a JSON object with a required `graph` name and optional
+/// algorithm parameters. The graph is copied when the job is submitted, so
+/// later changes to it do not affect the job. Parameters are validated before
+/// the job starts. Returns the job handle.
+pub fn submit_job(algorithm: &str, params: &str) -> Result<u64> {
+    let params: Value = serde_json::from_str(params)?;
+    let params = params.as_object().ok_or_else(|| {
+        OnagerError::InvalidArgument("Job parameters must be a JSON object".to_string())
+    })?;
+    let graph_name = param_str(params, "graph")?.ok_or_else(|| {
+        OnagerError::InvalidArgument("Job parameters must include 'graph'".to_string())
+    })?;
+    let job = JobAlgorithm::parse(algorithm, params)?;
+    let (directed, (src, dst, weights)) =
+        graph::with_graph(graph_name, |g| (g.is_directed(), g.edge_list()))?;
+
+    let id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    JOBS.lock().insert(
+        id,
+        Job {
+            status: JobStatus::Running,
+            output: None,
+        },
+    );
+    let spawned = thread::Builder::new()
+        .name(format!("onager-job-{}", id))
+        .spawn(move || {
+            let outcome =
+                panic::catch_unwind(AssertUnwindSafe(|| job.run(&src, &dst, &weights, directed)));
+            let (status, output) = match outcome {
+                Ok(Ok(json)) => (JobStatus::Completed, json),
+                Ok(Err(e)) => (JobStatus::Failed, e.to_string()),
+                Err(_) => (JobStatus::Failed, "Internal panic in job".to_string()),
+            };
+            // The job may have been dropped while running
+            if let Some(entry) = JOBS.lock().get_mut(&id) {
+                entry.status = status;
+                entry.output = Some(output);
+            }
+        });
+    if let Err(e) = spawned {
+        JOBS.lock().remove(&id);
+        return Err(OnagerError::GraphError(format!(
+            "Failed to start job thread: {}",
+            e
+        )));
+    }
+    Ok(id)
+}
+
+/// Returns the status of a job.
+pub fn job_status(id: u64) -> Result<JobStatus> {
+    JOBS.lock()
+        .get(&id)
+        .map(|job| job.status)
+        .ok_or_else(|| OnagerError::InvalidArgument(format!("Unknown job {}", id)))
+}
+
+/// Returns the JSON result of a completed job.
+///
+/// Fails if the job is unknown, still running, or failed, in which case the
+/// error carries the job's error message.
+pub fn job_result(id: u64) -> Result<String> {
+    let jobs = JOBS.lock();
+    let job = jobs
+        .get(&id)
+        .ok_or_else(|| OnagerError::InvalidArgument(format!("Unknown job {}", id)))?;
+    match (job.status, &job.output) {
+        (JobStatus::Completed, Some(json)) => Ok(json.clone()),
+        (JobStatus::Failed, Some(msg)) => Err(OnagerError::GraphError(format!(
+            "Job {} failed: {}",
+            id, msg
+        ))),
+        _ => Err(OnagerError::InvalidArgument(format!(
+            "Job {} is still running",
+            id
+        ))),
+    }
+}
+
+/// Forgets a job and its result. A running job finishes in the background
+/// and its result is discarded.
+pub fn drop_job(id: u64) -> Result<()> {
+    JOBS.lock()
+        .remove(&id)
+        .map(|_| ())
+        .ok_or_else(|| OnagerError::InvalidArgument(format!("Unknown job {}", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn wait(id: u64) -> JobStatus {
+        for _ in 0..1000 {
+            let status = job_status(id).unwrap();
+            if status != JobStatus::Running {
+                return status;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        JobStatus::Running
+    }
+
+    #[test]
+    fn test_job_lifecycle() {
+        let name = "test_graph_jobs";
+        graph::create_graph(name, false).unwrap();
+        for node in 1..=3 {
+            graph::add_node(name, node).unwrap();
+        }
+        graph::add_edge(name, 1, 2, 1.0).unwrap();
+        graph::add_edge(name, 2, 3, 1.0).unwrap();
+
+        let id = submit_job("betweenness", r#"{"graph": "test_graph_jobs"}"#).unwrap();
+        assert_eq!(wait(id), JobStatus::Completed);
+        let json = job_result(id).unwrap();
+        assert!(json.contains("\"centralities\""));
+        drop_job(id).unwrap();
+        assert!(job_status(id).is_err());
+
+        // Negative weights make the job fail in the background
+        graph::add_edge(name, 3, 1, -1.0).unwrap();
+        let failed = submit_job("closeness", r#"{"graph": "test_graph_jobs"}"#).unwrap();
+        assert_eq!(wait(failed), JobStatus::Failed);
+        assert!(job_result(failed).is_err());
+
+        graph::drop_graph(name).unwrap();
+    }
+
+    #[test]
+    fn test_submit_job_validates_input() {
+        assert!(submit_job("pagerank", "[]").is_err());
+        assert!(submit_job("pagerank", "{}").is_err());
+        assert!(submit_job("pagerank", r#"{"graph": "no_such_graph_jobs"}"#).is_err());
+        assert!(submit_job("sort", r#"{"graph": "no_such_graph_jobs"}"#).is_err());
+        assert!(submit_job("louvain", r#"{"graph": "g", "seed": -1}"#).is_err());
+    }
+}
@@ -2,10 +2,33 @@
 //!
 //! This crate provides graph analytics capabilities within DuckDB,
 //! powered by the graphina library.
+//!
+//! Besides the C ABI used by the DuckDB extension, the crate can be used as a
+//! regular Rust library. Every algorithm takes edge arrays and returns a typed
+//! result struct that implements `Serialize` and `Deserialize`:
+//!
+//! ```
+//! use onager::algorithms::{compute_pagerank, ToJson};
+//!
+//! let src = [1, 2, 3];
+//! let dst = [2, 3, 1];
+//! let result = compute_pagerank(&src, &dst, &[], 0.85, 100, true).unwrap();
+//! assert_eq!(result.node_ids.len(), 3);
+//!
+//! let json = result.to_json().unwrap();
+//! assert!(json.contains("\"ranks\""));
+//! ```
+//!
+//! Node IDs are integers. For string IDs such as usernames, map them with
+//! [`algorithms::NodeInterner`] first and map the result IDs back with
+//! [`algorithms::NodeInterner::names`].
 
 pub mod algorithms;
+pub mod builder;
 pub mod error;
 pub mod ffi;
 pub mod graph;
+pub mod jobs;
 
-pub use error::OnagerError;
+pub use algorithms::ToJson;
+pub use error::{OnagerError, Result};